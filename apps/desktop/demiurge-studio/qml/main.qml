@@ -280,13 +280,13 @@ ApplicationWindow {
                                 ColumnLayout {
                                     spacing: 6
                                     Text {
-                                        text: "Address (32-byte hex)"
+                                        text: "Address"
                                         font.pixelSize: 11
                                         color: "#9ca3af"
                                     }
                                     TextField {
                                         id: addressField
-                                        placeholderText: "e.g. 0a1b... (64 hex characters)"
+                                        placeholderText: "dmrg1..."
                                         font.pixelSize: 12
                                         color: "#e5e7eb"
                                         selectByMouse: true
@@ -450,8 +450,8 @@ ApplicationWindow {
             chainErrorMessage = message
         }
 
-        function onBalanceUpdated(addressHex, balance) {
-            console.log("Balance updated for", addressHex, ":", balance)
+        function onBalanceUpdated(address, balance) {
+            console.log("Balance updated for", address, ":", balance)
             currentBalance = balance
             walletErrorMessage = ""
         }
@@ -461,8 +461,8 @@ ApplicationWindow {
             walletErrorMessage = message
         }
 
-        function onArchonStatusUpdated(addressHex, isArchon) {
-            console.log("Archon status updated for", addressHex, ":", isArchon)
+        function onArchonStatusUpdated(address, isArchon) {
+            console.log("Archon status updated for", address, ":", isArchon)
             archonKnown = true
             archonIsTrue = isArchon
         }
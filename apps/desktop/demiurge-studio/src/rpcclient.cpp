@@ -109,15 +109,15 @@ void RpcClient::fetchChainInfo()
     );
 }
 
-void RpcClient::fetchBalance(const QString& addressHex)
+void RpcClient::fetchBalance(const QString& address)
 {
     QJsonObject params;
-    params["address"] = addressHex;
+    params["address"] = address;
 
     sendJsonRpc(
         QStringLiteral("cgt_getBalance"),
         params,
-        [this, addressHex](const QJsonObject& result) {
+        [this, address](const QJsonObject& result) {
             const QJsonValue val = result.value("balance");
             quint64 balance = 0;
             if (val.isDouble()) {
@@ -126,7 +126,7 @@ void RpcClient::fetchBalance(const QString& addressHex)
                 bool ok = false;
                 balance = val.toString().toULongLong(&ok);
             }
-            emit balanceUpdated(addressHex, balance);
+            emit balanceUpdated(address, balance);
         },
         [this](const QString& msg) {
             emit balanceError(msg);
@@ -134,17 +134,17 @@ void RpcClient::fetchBalance(const QString& addressHex)
     );
 }
 
-void RpcClient::fetchIsArchon(const QString& addressHex)
+void RpcClient::fetchIsArchon(const QString& address)
 {
     QJsonObject params;
-    params["address"] = addressHex;
+    params["address"] = address;
 
     sendJsonRpc(
         QStringLiteral("cgt_isArchon"),
         params,
-        [this, addressHex](const QJsonObject& result) {
+        [this, address](const QJsonObject& result) {
             bool isArchon = result.value("is_archon").toBool(false);
-            emit archonStatusUpdated(addressHex, isArchon);
+            emit archonStatusUpdated(address, isArchon);
         },
         [this](const QString& msg) {
             emit archonStatusError(msg);
@@ -17,18 +17,18 @@ public:
     void setRpcUrl(const QString& url);
 
     Q_INVOKABLE void fetchChainInfo();
-    Q_INVOKABLE void fetchBalance(const QString& addressHex);
-    Q_INVOKABLE void fetchIsArchon(const QString& addressHex);
+    Q_INVOKABLE void fetchBalance(const QString& address);
+    Q_INVOKABLE void fetchIsArchon(const QString& address);
 
 signals:
     void rpcUrlChanged();
     void chainInfoUpdated(qint64 height);
     void chainInfoError(const QString& message);
 
-    void balanceUpdated(const QString& addressHex, quint64 balance);
+    void balanceUpdated(const QString& address, quint64 balance);
     void balanceError(const QString& message);
 
-    void archonStatusUpdated(const QString& addressHex, bool isArchon);
+    void archonStatusUpdated(const QString& address, bool isArchon);
     void archonStatusError(const QString& message);
 
 private:
@@ -6,12 +6,13 @@ import { useRouter } from "next/navigation";
 import { Wallet, Coins, Sparkles, Award, ArrowLeft } from "lucide-react";
 import QRCode from "react-qr-code";
 import { callRpc } from "@/lib/rpc";
-import { toAeonId } from "@/lib/aeonId";
+import { normalizeAddress, toDisplayAddress } from "@/lib/address";
 import { exportVault, importVault } from "@/lib/vault";
-import { DEMIURGE_RPC_URL } from "@/config/demiurge";
+import { DEMIURGE_RPC_URL, formatCgt } from "@/config/demiurge";
 
 type AeonProfile = {
   address: string;
+  address_hex: string;
   display_name: string;
   bio?: string;
   handle?: string | null;
@@ -59,10 +60,13 @@ export default function AeonPage() {
     const storedAddress = localStorage.getItem("demiurge_aeon_wallet_address");
     const storedKey = localStorage.getItem("demiurge_aeon_wallet_key");
     if (storedAddress && storedKey) {
-      setAddress(storedAddress);
+      // Wallets saved before display addresses still hold raw hex
+      const displayAddress = normalizeAddress(storedAddress);
+      localStorage.setItem("demiurge_aeon_wallet_address", displayAddress);
+      setAddress(displayAddress);
       setPrivateKey(storedKey);
       setStep("dashboard");
-      loadDashboard(storedAddress);
+      loadDashboard(displayAddress);
     }
   }, []);
 
@@ -77,7 +81,7 @@ export default function AeonPage() {
       .map((b) => b.toString(16).padStart(2, "0"))
       .join("");
 
-    setAddress(addrHex);
+    setAddress(toDisplayAddress(addrHex));
     setPrivateKey(keyHex);
   };
 
@@ -343,7 +347,7 @@ export default function AeonPage() {
             <div className="mt-2 space-y-2">
               <div>
                 <div className="text-[11px] text-slate-500 mb-1">
-                  Hex Address
+                  Address
                 </div>
                 <div className="flex items-center gap-2">
                   <code className="font-mono text-[11px] break-all text-slate-200">
@@ -361,17 +365,15 @@ export default function AeonPage() {
 
               <div>
                 <div className="text-[11px] text-slate-500 mb-1">
-                  Aeon ID (bech32)
+                  Hex Address
                 </div>
                 <div className="flex items-center gap-2">
                   <code className="font-mono text-[11px] break-all text-slate-200">
-                    {toAeonId(profile.address)}
+                    {profile.address_hex}
                   </code>
                   <button
                     type="button"
-                    onClick={() =>
-                      navigator.clipboard.writeText(toAeonId(profile.address))
-                    }
+                    onClick={() => navigator.clipboard.writeText(profile.address_hex)}
                     className="rounded-md border border-slate-600 px-2 py-[2px] text-[10px] text-slate-200 hover:bg-slate-800"
                   >
                     Copy
@@ -451,7 +453,7 @@ export default function AeonPage() {
               </div>
               <p className="text-2xl font-mono font-semibold text-emerald-400">
                 {balance !== null
-                  ? balance.toLocaleString()
+                  ? formatCgt(balance)
                   : "—"}{" "}
                 CGT
               </p>
@@ -592,6 +594,7 @@ export default function AeonPage() {
                       const text = await file.text();
                       const vault = JSON.parse(text);
                       const restored = await importVault(vault, password);
+                      restored.address = normalizeAddress(restored.address);
 
                       const newWallet = {
                         address: restored.address,
@@ -4,7 +4,7 @@ import { motion } from "framer-motion";
 import { Activity, CircuitBoard, Sparkles, Users, Wallet, Coins } from "lucide-react";
 import { useEffect, useState } from "react";
 import axios from "axios";
-import { DEMIURGE_RPC_URL, GENESIS_ARCHON_ADDRESS } from "@/config/demiurge";
+import { DEMIURGE_RPC_URL, GENESIS_ARCHON_ADDRESS, formatCgt } from "@/config/demiurge";
 
 type ChainInfo = {
   height: number;
@@ -332,7 +332,7 @@ export default function HomePage() {
             </div>
             <p className="text-lg font-mono font-semibold text-emerald-400">
               {genesisBalance !== null
-                ? genesisBalance.toLocaleString()
+                ? formatCgt(genesisBalance)
                 : "—"}{" "}
               CGT
             </p>
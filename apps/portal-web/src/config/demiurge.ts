@@ -4,5 +4,13 @@ export const DEMIURGE_RPC_URL =
 
 export const GENESIS_ARCHON_ADDRESS =
   process.env.NEXT_PUBLIC_GENESIS_ARCHON_ADDRESS ||
-  "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+  "dmrg1424242424242424242424242424242424242424242424242424q9f6mfr";
 
+// CGT amounts over RPC are base units; must match CGT_DECIMALS in chain/src/config.rs.
+export const CGT_DECIMALS = 8;
+
+export function formatCgt(baseUnits: number): string {
+  return (baseUnits / 10 ** CGT_DECIMALS).toLocaleString(undefined, {
+    maximumFractionDigits: CGT_DECIMALS,
+  });
+}
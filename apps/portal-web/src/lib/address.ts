@@ -0,0 +1,45 @@
+import { bech32m } from "bech32";
+
+// Must match ADDRESS_HRP in chain/src/core/transaction.rs.
+const ADDRESS_HRP = "dmrg";
+
+// Encode a 32-byte hex address as its checksummed display form ("dmrg1...").
+export function toDisplayAddress(addressHex: string): string {
+  return bech32m.encode(ADDRESS_HRP, bech32m.toWords(hexToBytes(addressHex)));
+}
+
+// Parse a display address or raw hex into 32-byte lowercase hex.
+// Throws if the checksum, prefix, or length is wrong.
+export function parseAddress(input: string): string {
+  const s = input.trim();
+  if (s.toLowerCase().startsWith(`${ADDRESS_HRP}1`)) {
+    const { prefix, words } = bech32m.decode(s);
+    if (prefix !== ADDRESS_HRP) {
+      throw new Error(`address prefix must be ${ADDRESS_HRP}`);
+    }
+    const bytes = bech32m.fromWords(words);
+    if (bytes.length !== 32) {
+      throw new Error("address must be 32 bytes");
+    }
+    return bytes.map((b) => b.toString(16).padStart(2, "0")).join("");
+  }
+  return Array.from(hexToBytes(s))
+    .map((b) => b.toString(16).padStart(2, "0"))
+    .join("");
+}
+
+// Normalize any accepted address form to its display form.
+export function normalizeAddress(input: string): string {
+  return toDisplayAddress(parseAddress(input));
+}
+
+function hexToBytes(hex: string): Uint8Array {
+  if (!/^[0-9a-fA-F]{64}$/.test(hex)) {
+    throw new Error("address must be dmrg1... or 64 hex characters");
+  }
+  const bytes = new Uint8Array(32);
+  for (let i = 0; i < 32; i++) {
+    bytes[i] = parseInt(hex.slice(i * 2, i * 2 + 2), 16);
+  }
+  return bytes;
+}
@@ -0,0 +1,45 @@
+//! Forge PoW throughput: `forge_hash` over a run of nonces at a few
+//! `ForgeConfig` settings, to inform `genesis.forge` and difficulty choices.
+//!
+//! Run with `cargo bench -p demiurge-chain --bench forge`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use demiurge_chain::forge::{forge_hash, ForgeConfig};
+
+/// Nonces hashed per iteration.
+const NONCES: u64 = 8;
+
+fn forge_hashes(c: &mut Criterion) {
+    let header_bytes = b"demiurge-forge-benchmark";
+    let configs = [
+        ForgeConfig {
+            memory_kib: 1024,
+            time_cost: 1,
+            lanes: 1,
+        },
+        ForgeConfig {
+            memory_kib: 4 * 1024,
+            time_cost: 2,
+            lanes: 1,
+        },
+        ForgeConfig::default(),
+    ];
+
+    let mut group = c.benchmark_group("forge_hash");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(NONCES));
+    for config in &configs {
+        group.bench_with_input(BenchmarkId::from_parameter(config), config, |b, config| {
+            b.iter(|| {
+                for nonce in 0..NONCES {
+                    black_box(forge_hash(header_bytes, black_box(nonce), config));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, forge_hashes);
+criterion_main!(benches);
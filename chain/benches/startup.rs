@@ -0,0 +1,95 @@
+//! Node startup against a large database: opening it, and the point lookups
+//! of missing keys that Bloom filters let RocksDB answer without reading data
+//! blocks. Each is measured with the default tuning and with filters off.
+//!
+//! The database holds `DEMIURGE_BENCH_KEYS` balances (1,000,000 by default)
+//! and is generated once per run, which takes a while.
+//!
+//! Run with `cargo bench -p demiurge-chain --bench startup`.
+
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use demiurge_chain::config::{ChainConfig, RocksConfig};
+use demiurge_chain::core::state::State;
+use demiurge_chain::node::Node;
+use demiurge_chain::runtime::storage_value::put_u64;
+use demiurge_chain::runtime::StorageKey;
+
+/// Keys written per commit while generating the database.
+const BATCH_KEYS: u64 = 10_000;
+
+fn balance_key(i: u64) -> Vec<u8> {
+    let mut addr = [0u8; 32];
+    addr[..8].copy_from_slice(&i.to_be_bytes());
+    StorageKey::new("bank_cgt", "balance")
+        .address(&addr)
+        .into_bytes()
+}
+
+/// Fill `path` with `keys` balances and initialize genesis on top.
+fn generate(path: &Path, keys: u64) {
+    let mut state = State::open_rocksdb(path).unwrap();
+    for start in (0..keys).step_by(BATCH_KEYS as usize) {
+        state.begin();
+        for i in start..keys.min(start + BATCH_KEYS) {
+            put_u64(&mut state, balance_key(i), i).unwrap();
+        }
+        state.commit().unwrap();
+    }
+    // Flush everything into table files, which carry the filters
+    state.compact();
+    drop(state);
+    Node::with_config(path.to_path_buf(), ChainConfig::default()).unwrap();
+}
+
+fn startup(c: &mut Criterion) {
+    let keys = std::env::var("DEMIURGE_BENCH_KEYS")
+        .ok()
+        .and_then(|keys| keys.parse().ok())
+        .unwrap_or(1_000_000);
+    let dir = tempfile::tempdir().unwrap();
+    generate(dir.path(), keys);
+
+    let tunings = [
+        ("default", RocksConfig::default()),
+        (
+            "no_bloom_filter",
+            RocksConfig {
+                bloom_filter_bits_per_key: 0,
+                ..RocksConfig::default()
+            },
+        ),
+    ];
+
+    let mut group = c.benchmark_group("node_open");
+    group.sample_size(10);
+    for (name, rocks) in &tunings {
+        let mut config = ChainConfig::default();
+        config.storage.rocksdb = rocks.clone();
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                let node = Node::with_config(dir.path().to_path_buf(), config.clone()).unwrap();
+                black_box(node.chain_tip());
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("missing_key_lookup");
+    for (name, rocks) in &tunings {
+        let state = State::open_rocksdb_cached(dir.path(), 0, rocks).unwrap();
+        let mut i = keys;
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                i += 1;
+                state.get_raw(black_box(&balance_key(i)))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, startup);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+//! Balance lookups: bincode-decoded reads against the fixed-width fast path.
+//!
+//! Run with `cargo bench -p demiurge-chain --bench storage`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use demiurge_chain::core::codec;
+use demiurge_chain::core::state::State;
+use demiurge_chain::runtime::bank_cgt::credit;
+use demiurge_chain::runtime::storage_value::get_u64;
+use demiurge_chain::runtime::StorageKey;
+
+fn balance_lookups(c: &mut Criterion) {
+    let mut state = State::in_memory();
+    let addr = [7u8; 32];
+    credit(&mut state, &addr, 1_000_000).unwrap();
+    let key = StorageKey::new("bank_cgt", "balance")
+        .address(&addr)
+        .into_bytes();
+
+    let mut group = c.benchmark_group("balance_lookup");
+    group.bench_function("bincode", |b| {
+        b.iter(|| {
+            state
+                .get_raw(black_box(&key))
+                .and_then(|bytes| codec::decode_state::<u64>(&bytes).ok())
+        })
+    });
+    group.bench_function("fixed_width", |b| {
+        b.iter(|| get_u64(&state, black_box(&key)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, balance_lookups);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+//! Embeds build metadata for `cgt_getNodeInfo`:
+//! - `DEMIURGE_GIT_COMMIT`: the checked-out commit, or "unknown" outside a
+//!   git checkout (set the variable to override)
+//! - `DEMIURGE_BUILD_PROFILE`: the cargo profile (`debug` or `release`)
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=DEMIURGE_GIT_COMMIT");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs/heads");
+
+    let commit = std::env::var("DEMIURGE_GIT_COMMIT")
+        .ok()
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "HEAD"])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .and_then(|output| String::from_utf8(output.stdout).ok())
+                .map(|commit| commit.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=DEMIURGE_GIT_COMMIT={}", commit);
+
+    let profile = std::env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=DEMIURGE_BUILD_PROFILE={}", profile);
+}
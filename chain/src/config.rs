@@ -1,11 +1,32 @@
 //! Configuration constants for the Demiurge chain.
 //!
 //! This module contains genesis addresses, dev-only settings, and other
-//! configuration constants used throughout the chain.
+//! configuration constants used throughout the chain, plus the `ChainConfig`
+//! a node is started with (loadable from a JSON file).
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::core::transaction::Address;
+use crate::forge::ForgeConfig;
+
+/// Chain identifier exchanged in the P2P handshake.
+///
+/// Nodes with a different chain id refuse to peer with each other.
+pub const CHAIN_ID: &str = "demiurge-devnet";
+
+/// Default Forge difficulty target (roughly 1 in 16 hashes succeeds).
+pub const DEFAULT_DIFFICULTY_TARGET: u128 = u128::MAX >> 4;
+
+/// Default target time between blocks, in seconds.
+pub const DEFAULT_TARGET_BLOCK_TIME_SECS: u64 = 10;
 
 /// Genesis Archon address (64 hex characters = 32 bytes).
 ///
-/// This address is pre-funded and marked as an Archon during genesis initialization.
+/// The default genesis archon set holds only this address, which is
+/// pre-funded and marked as an Archon during genesis initialization.
 pub const GENESIS_ARCHON_ADDRESS_HEX: &str =
     "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
 
@@ -15,10 +36,752 @@ pub const GENESIS_ARCHON_ADDRESS: [u8; 32] = [0xaa; 32];
 /// Genesis initialization key in state.
 const KEY_GENESIS_INITIALIZED: &[u8] = b"demiurge/genesis_initialized";
 
+/// CGT ticker symbol.
+pub const CGT_SYMBOL: &str = "CGT";
+
+/// CGT full token name.
+pub const CGT_NAME: &str = "Creator God Token";
+
+/// Number of decimal places in a CGT amount.
+///
+/// All on-chain amounts are `u64` base units; one CGT is `CGT_UNIT` base units.
+pub const CGT_DECIMALS: u32 = 8;
+
+/// Base units in one whole CGT.
+pub const CGT_UNIT: u64 = 10u64.pow(CGT_DECIMALS);
+
+/// Maximum total CGT supply in base units (1 billion CGT); 0 means uncapped.
+///
+/// Every mint (genesis, `mint_to`, the dev faucet, staking rewards) is
+/// checked against it.
+pub const CGT_MAX_SUPPLY: u64 = 1_000_000_000 * CGT_UNIT;
+
 /// Initial CGT balance for Genesis Archon (1 million CGT).
-pub const GENESIS_ARCHON_INITIAL_BALANCE: u64 = 1_000_000;
+pub const GENESIS_ARCHON_INITIAL_BALANCE: u64 = 1_000_000 * CGT_UNIT;
+
+/// Default dev faucet amount (10,000 CGT per request).
+pub const DEV_FAUCET_AMOUNT: u64 = 10_000 * CGT_UNIT;
+
+/// CGT minted to a new Aeon profile by `aeon_create` in debug builds
+/// (1,000 CGT; 0 disables the grant).
+pub const AEON_STARTER_GRANT: u64 = 1_000 * CGT_UNIT;
+
+/// Default number of blocks an address must wait between faucet claims.
+pub const DEFAULT_FAUCET_COOLDOWN_BLOCKS: u64 = 100;
+
+/// Default lifetime faucet cap per address (100,000 CGT).
+pub const DEFAULT_FAUCET_LIFETIME_CAP: u64 = 100_000 * CGT_UNIT;
+
+/// Default CGT weight (1,000 CGT) that must vote for a governance proposal to pass.
+pub const DEFAULT_GOVERNANCE_QUORUM: u64 = 1_000 * CGT_UNIT;
+
+/// Default share of the voting weight, in basis points, a proposal must exceed to pass.
+pub const DEFAULT_GOVERNANCE_APPROVAL_THRESHOLD_BPS: u64 = 5_000;
+
+/// Default Abyss marketplace fee, in basis points of the sale price.
+pub const DEFAULT_MARKETPLACE_FEE_BPS: u64 = 0;
+
+/// Default existential deposit, in CGT base units: the smallest balance an
+/// account other than a genesis Archon or module account may hold. 0 disables
+/// the rule.
+pub const DEFAULT_EXISTENTIAL_DEPOSIT: u64 = 0;
+
+/// Default longest memo a CGT transfer may carry, in bytes.
+pub const DEFAULT_MAX_MEMO_BYTES: u64 = 128;
+
+/// Default share of an Aeon's Syzygy Score lost each decay epoch, in basis
+/// points. 0 disables decay.
+pub const DEFAULT_SYZYGY_DECAY_BPS: u64 = 0;
+
+/// Default length of a Syzygy Score decay epoch, in blocks (about 30 days at
+/// the default 10-second block time).
+pub const DEFAULT_SYZYGY_DECAY_EPOCH_BLOCKS: u64 = 259_200;
+
+/// Default Syzygy Score at which an Aeon earns the Luminary badge.
+pub const DEFAULT_LUMINARY_SYZYGY_THRESHOLD: u64 = 10_000;
+
+/// Default CGT burned to register a name in the `names` module, in base
+/// units.
+pub const DEFAULT_NAME_REGISTRATION_FEE: u64 = CGT_UNIT;
+
+/// Default display name of the Genesis Archon's Aeon profile.
+pub const DEFAULT_GENESIS_ARCHON_NAME: &str = "Genesis Archon";
+
+/// Default number of later block heights a transaction that failed on its
+/// nonce is retried at before it is dead-lettered.
+pub const DEFAULT_NONCE_RETRIES: u64 = 5;
+
+/// Default number of dead-lettered transactions a node remembers.
+pub const DEFAULT_DEAD_LETTER_CAPACITY: usize = 1_024;
+
+/// Default cap on transactions one sender may have waiting in the mempool,
+/// queued future nonces included.
+pub const MAX_TXS_PER_SENDER: usize = 64;
+
+/// Default cap on transactions waiting in the mempool from all senders.
+pub const MAX_MEMPOOL_TXS: usize = 4_096;
+
+/// An Archon created at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GenesisArchon {
+    /// Checksummed or hex string in config files.
+    #[serde(with = "address_string")]
+    pub address: Address,
+    /// CGT base units minted to it.
+    pub balance: u64,
+    /// Display name of its Aeon profile; `GenesisConfig::archon_name` if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A D-GEN NFT minted at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GenesisNft {
+    /// Creator and first owner, which must be a genesis archon or mint
+    /// authority. Checksummed or hex string in config files.
+    #[serde(with = "address_string")]
+    pub owner: Address,
+    /// Hex string in config files.
+    #[serde(with = "hash_string")]
+    pub fabric_root_hash: [u8; 32],
+    /// Share of each sale paid as royalty, in basis points.
+    #[serde(default)]
+    pub royalty_bps: u16,
+    /// Address paid the royalty; the owner if unset.
+    #[serde(
+        default,
+        with = "optional_address_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub royalty_recipient: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri_hint: Option<String>,
+}
+
+/// Genesis parameters shared by every node on the chain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GenesisConfig {
+    /// Forge PoW difficulty target every block must meet (lower = harder).
+    pub difficulty_target: u128,
+    /// Display name of genesis Archons' Aeon profiles that do not set their own.
+    pub archon_name: String,
+    /// Archons funded and flagged at genesis. Defaults to the Genesis Archon
+    /// (`GENESIS_ARCHON_ADDRESS`) with `GENESIS_ARCHON_INITIAL_BALANCE`.
+    pub archons: Vec<GenesisArchon>,
+    /// Addresses allowed to call `bank_cgt.mint_to`, as checksummed or hex
+    /// strings. Defaults to no one, so a chain mints only what its config
+    /// says, whatever profile the node was built with.
+    #[serde(with = "address_list")]
+    pub mint_authorities: Vec<Address>,
+    /// Argon2id parameters of the Forge PoW hash. A data directory keeps the
+    /// parameters it was initialized with; starting it with others is refused.
+    pub forge: ForgeConfig,
+    /// Unix timestamp (seconds) of the genesis block. Every block must be
+    /// timestamped after its parent, so the first one after this.
+    pub genesis_timestamp: u64,
+    /// Height of the genesis block. A chain forked from another starts above
+    /// 0 so its heights carry on from the parent chain's.
+    pub start_height: u64,
+    /// NFTs minted when the chain is created, in order, so they take ids
+    /// from 0. Left out of the genesis fingerprint while empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nfts: Vec<GenesisNft>,
+}
+
+impl Default for GenesisConfig {
+    fn default() -> Self {
+        Self {
+            difficulty_target: DEFAULT_DIFFICULTY_TARGET,
+            archon_name: DEFAULT_GENESIS_ARCHON_NAME.to_string(),
+            archons: vec![GenesisArchon {
+                address: GENESIS_ARCHON_ADDRESS,
+                balance: GENESIS_ARCHON_INITIAL_BALANCE,
+                name: None,
+            }],
+            mint_authorities: Vec::new(),
+            forge: ForgeConfig::default(),
+            genesis_timestamp: 0,
+            start_height: 0,
+            nfts: Vec::new(),
+        }
+    }
+}
+
+impl GenesisConfig {
+    /// Check the genesis spec: no archon address may be listed twice, each
+    /// genesis NFT must belong to a genesis archon or mint authority with a
+    /// royalty of at most 10000 bps, and Argon2id must accept the Forge
+    /// parameters.
+    pub fn validate(&self) -> Result<()> {
+        self.forge.validate().map_err(anyhow::Error::msg)?;
+        let mut seen = std::collections::HashSet::new();
+        for archon in &self.archons {
+            if !seen.insert(archon.address) {
+                return Err(anyhow::anyhow!(
+                    "genesis archon {} is listed more than once",
+                    crate::core::transaction::AddressEncoding::to_display(&archon.address)
+                ));
+            }
+        }
+        for (index, nft) in self.nfts.iter().enumerate() {
+            if !seen.contains(&nft.owner) && !self.mint_authorities.contains(&nft.owner) {
+                return Err(anyhow::anyhow!(
+                    "genesis NFT {} is owned by {}, which is neither a genesis archon nor a mint authority",
+                    index,
+                    crate::core::transaction::AddressEncoding::to_display(&nft.owner)
+                ));
+            }
+            if nft.royalty_bps > 10_000 {
+                return Err(anyhow::anyhow!(
+                    "genesis NFT {} has a royalty of {} bps, more than 10000",
+                    index,
+                    nft.royalty_bps
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serde for a single address in config files: checksummed or raw hex
+/// string in, checksummed string out.
+mod address_string {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::core::transaction::{Address, AddressEncoding};
+
+    pub fn serialize<S: Serializer>(addr: &Address, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&addr.to_display())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Address, D::Error> {
+        Address::parse(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Serde for an optional address in config files, as `address_string`.
+mod optional_address_string {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::core::transaction::{Address, AddressEncoding};
+
+    pub fn serialize<S: Serializer>(
+        addr: &Option<Address>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match addr {
+            Some(addr) => serializer.serialize_some(&addr.to_display()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Address>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| Address::parse(&s).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Serde for 32-byte hashes in config files: hex strings.
+mod hash_string {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(hash: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(hash))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        hex::decode(String::deserialize(deserializer)?)
+            .map_err(D::Error::custom)?
+            .try_into()
+            .map_err(|_| D::Error::custom("expected 32 bytes of hex"))
+    }
+}
+
+/// Serde for address lists in config files: checksummed or raw hex strings
+/// in, checksummed strings out.
+mod address_list {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::core::transaction::{Address, AddressEncoding};
+
+    pub fn serialize<S: Serializer>(addrs: &[Address], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(addrs.iter().map(|addr| addr.to_display()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Address>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| Address::parse(s).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// Dev faucet settings.
+///
+/// Enabled by default only in debug builds; a public testnet can enable it
+/// in a release build deliberately through the config file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct FaucetConfig {
+    /// Whether `cgt_devFaucet` is served at all.
+    pub enabled: bool,
+    /// CGT base units minted per claim.
+    pub amount: u64,
+    /// Blocks an address must wait between claims.
+    pub cooldown_blocks: u64,
+    /// Maximum CGT base units a single address can ever receive.
+    pub lifetime_cap: u64,
+}
+
+impl Default for FaucetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            amount: DEV_FAUCET_AMOUNT,
+            cooldown_blocks: DEFAULT_FAUCET_COOLDOWN_BLOCKS,
+            lifetime_cap: DEFAULT_FAUCET_LIFETIME_CAP,
+        }
+    }
+}
+
+/// Governance voting rules.
+///
+/// A proposal passes when at least `quorum` CGT base units voted on it and
+/// its approving weight is more than `approval_threshold_bps` of that total.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GovernanceConfig {
+    /// Minimum total CGT base units (held, staked, or bonded) that must vote.
+    pub quorum: u64,
+    /// Share of the voting weight that must approve, in basis points.
+    pub approval_threshold_bps: u64,
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        Self {
+            quorum: DEFAULT_GOVERNANCE_QUORUM,
+            approval_threshold_bps: DEFAULT_GOVERNANCE_APPROVAL_THRESHOLD_BPS,
+        }
+    }
+}
+
+/// Entry in a CORS origin list that allows every origin.
+pub const CORS_ANY_ORIGIN: &str = "any";
+
+/// Default browser origins allowed to call the RPC: the portal's dev server.
+pub const DEFAULT_CORS_ORIGINS: &[&str] = &["http://localhost:3000", "http://127.0.0.1:3000"];
+
+/// Browser origin policy for the JSON-RPC endpoint.
+///
+/// Origins are matched exactly (`scheme://host[:port]`). Requests without an
+/// `Origin` header, such as those from curl or other servers, are not
+/// affected.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins allowed to call the RPC from a browser. `"any"` allows every
+    /// origin; an empty list allows none.
+    pub allowed_origins: Vec<String>,
+    /// Origins allowed to call privileged methods (see
+    /// `rpc::PRIVILEGED_METHODS`) from a browser, in the same form. Empty by
+    /// default, so browsers cannot invoke them cross-origin at all.
+    pub privileged_origins: Vec<String>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: DEFAULT_CORS_ORIGINS.iter().map(|o| o.to_string()).collect(),
+            privileged_origins: Vec::new(),
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Whether `origins` admits `origin`.
+    pub fn admits(origins: &[String], origin: &str) -> bool {
+        origins.iter().any(|o| o == CORS_ANY_ORIGIN || o == origin)
+    }
+}
+
+/// JSON-RPC settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// Omit the data directory from `cgt_getNodeInfo` (for public nodes).
+    pub redact_data_dir: bool,
+    /// Browser origin policy.
+    pub cors: CorsConfig,
+    /// PEM certificate chain to serve the RPC over HTTPS with; needs
+    /// `tls_key`. Plain HTTP when neither is set.
+    pub tls_cert: Option<PathBuf>,
+    /// PEM private key matching `tls_cert`.
+    pub tls_key: Option<PathBuf>,
+    /// Serve only the query methods in `rpc::READ_ONLY_METHODS`, and open
+    /// the database without any startup writes (for public query nodes).
+    pub read_only: bool,
+    /// Serve `dev_reset`, which wipes the chain and re-runs genesis (for
+    /// devnets and test harnesses only).
+    pub dev_reset: bool,
+}
+
+/// `keep_blocks` value that keeps every block.
+pub const KEEP_BLOCKS_ARCHIVE: &str = "archive";
+
+/// How much block history a node keeps.
+///
+/// Written in config files as `"archive"` or a number of blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeepBlocks {
+    /// Keep every block body and receipt.
+    #[default]
+    Archive,
+    /// Keep the bodies and receipts of only the most recent blocks, at least
+    /// one. Headers and the current state are always kept.
+    Recent(u64),
+}
+
+impl Serialize for KeepBlocks {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Archive => serializer.serialize_str(KEEP_BLOCKS_ARCHIVE),
+            Self::Recent(blocks) => serializer.serialize_u64(*blocks),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for KeepBlocks {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Blocks(u64),
+            Name(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Blocks(0) => Err(D::Error::custom("keep_blocks must be at least 1")),
+            Raw::Blocks(blocks) => Ok(Self::Recent(blocks)),
+            Raw::Name(name) if name == KEEP_BLOCKS_ARCHIVE => Ok(Self::Archive),
+            Raw::Name(name) => Err(D::Error::custom(format!(
+                "keep_blocks must be a number of blocks or \"{}\", not \"{}\"",
+                KEEP_BLOCKS_ARCHIVE, name
+            ))),
+        }
+    }
+}
+
+/// Block compression used for RocksDB's files.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RocksCompression {
+    None,
+    Snappy,
+    #[default]
+    Lz4,
+    Zstd,
+}
+
+/// RocksDB tuning, applied each time the database is opened.
+///
+/// None of these change what is stored, so they can be changed freely
+/// between restarts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RocksConfig {
+    /// Size of each memtable before it is flushed to disk, in MiB.
+    pub write_buffer_size_mib: usize,
+    /// Files RocksDB may keep open at once; -1 keeps every file open.
+    pub max_open_files: i32,
+    /// Size of the LRU cache of uncompressed data blocks, in MiB.
+    pub block_cache_size_mib: usize,
+    /// Compression for data blocks.
+    pub compression: RocksCompression,
+    /// Bits per key of the Bloom filter on each table, which lets point
+    /// lookups of missing keys skip reading data blocks; 0 disables it.
+    pub bloom_filter_bits_per_key: u32,
+    /// Write-ahead log size, in MiB, past which RocksDB flushes the memtables
+    /// it holds; bounds how much log replays when the database next opens.
+    /// 0 leaves RocksDB's default (four times the memtable budget).
+    pub max_total_wal_size_mib: u64,
+}
+
+impl Default for RocksConfig {
+    fn default() -> Self {
+        Self {
+            write_buffer_size_mib: 64,
+            max_open_files: 1024,
+            block_cache_size_mib: 128,
+            compression: RocksCompression::default(),
+            bloom_filter_bits_per_key: 10,
+            max_total_wal_size_mib: 0,
+        }
+    }
+}
+
+/// Storage settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Keys kept in the LRU read cache in front of RocksDB; 0 (the default)
+    /// disables the cache.
+    pub read_cache_entries: usize,
+    /// Block history to keep; older block bodies and receipts are pruned as
+    /// new blocks are finalized. Archive (keep everything) by default.
+    pub keep_blocks: KeepBlocks,
+    /// RocksDB tuning.
+    pub rocksdb: RocksConfig,
+    /// Seconds between full database compactions run in the background; 0
+    /// (the default) leaves compaction to RocksDB and `admin_compact`.
+    pub compact_interval_secs: u64,
+}
+
+/// Block execution settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ExecutionConfig {
+    /// Execute runs of transactions with non-overlapping declared writes in
+    /// parallel (see `core::parallel`). The resulting state is the same as
+    /// sequential execution, so nodes may differ in this setting. Off by
+    /// default.
+    pub parallel: bool,
+}
+
+/// Mempool settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MempoolConfig {
+    /// Later block heights a transaction that failed on its nonce, or waits
+    /// behind a nonce gap, is retried at before it is dead-lettered. Other
+    /// failures are dead-lettered at once.
+    pub nonce_retries: u64,
+    /// Dead-lettered transactions kept for `cgt_getFailedTransaction`; the
+    /// oldest is dropped beyond this.
+    pub dead_letter_capacity: usize,
+    /// Transactions one sender may have pending at once; more are refused at
+    /// submission, so no sender can fill the mempool with queued nonces.
+    pub max_txs_per_sender: usize,
+    /// Transactions the mempool holds from all senders. Once full, a
+    /// transaction paying a higher fee than the cheapest pending one evicts
+    /// it; one paying no more is refused.
+    pub max_txs: usize,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            nonce_retries: DEFAULT_NONCE_RETRIES,
+            dead_letter_capacity: DEFAULT_DEAD_LETTER_CAPACITY,
+            max_txs_per_sender: MAX_TXS_PER_SENDER,
+            max_txs: MAX_MEMPOOL_TXS,
+        }
+    }
+}
+
+/// Node configuration.
+///
+/// Missing fields in a config file fall back to their defaults, so an empty
+/// JSON object (`{}`) is a valid devnet configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ChainConfig {
+    /// Chain identifier (see `CHAIN_ID`).
+    pub chain_id: String,
+    /// Target time between blocks, in seconds.
+    pub target_block_time_secs: u64,
+    /// Genesis parameters.
+    pub genesis: GenesisConfig,
+    /// Dev faucet settings.
+    pub faucet: FaucetConfig,
+    /// Governance voting rules.
+    pub governance: GovernanceConfig,
+    /// JSON-RPC settings.
+    pub rpc: RpcConfig,
+    /// Storage settings.
+    pub storage: StorageConfig,
+    /// Block execution settings.
+    pub execution: ExecutionConfig,
+    /// Mempool settings.
+    pub mempool: MempoolConfig,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: CHAIN_ID.to_string(),
+            target_block_time_secs: DEFAULT_TARGET_BLOCK_TIME_SECS,
+            genesis: GenesisConfig::default(),
+            faucet: FaucetConfig::default(),
+            governance: GovernanceConfig::default(),
+            rpc: RpcConfig::default(),
+            storage: StorageConfig::default(),
+            execution: ExecutionConfig::default(),
+            mempool: MempoolConfig::default(),
+        }
+    }
+}
+
+impl ChainConfig {
+    /// Load a configuration from a JSON file.
+    ///
+    /// Fails if the genesis spec is invalid (see `GenesisConfig::validate`).
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let config: Self = serde_json::from_str(&contents)?;
+        config.genesis.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: ChainConfig =
+            serde_json::from_str(r#"{ "genesis": { "difficulty_target": 12345 } }"#).unwrap();
+        assert_eq!(config.genesis.difficulty_target, 12345);
+        assert_eq!(config.chain_id, CHAIN_ID);
+        assert_eq!(
+            config.target_block_time_secs,
+            DEFAULT_TARGET_BLOCK_TIME_SECS
+        );
+    }
+
+    #[test]
+    fn test_mint_authorities_parse_from_strings() {
+        use crate::core::transaction::AddressEncoding;
+
+        let checksummed = [7u8; 32].to_display();
+        let json = format!(
+            r#"{{ "genesis": {{ "mint_authorities": ["{}", "{}"] }} }}"#,
+            checksummed,
+            hex::encode([8u8; 32])
+        );
+        let config: ChainConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.genesis.mint_authorities, vec![[7u8; 32], [8u8; 32]]);
+
+        // Written back in checksummed form
+        let written = serde_json::to_string(&config).unwrap();
+        assert!(written.contains(&checksummed));
+        let reloaded: ChainConfig = serde_json::from_str(&written).unwrap();
+        assert_eq!(reloaded, config);
+
+        let bad = r#"{ "genesis": { "mint_authorities": ["not-an-address"] } }"#;
+        assert!(serde_json::from_str::<ChainConfig>(bad).is_err());
+    }
+
+    #[test]
+    fn test_rocksdb_tuning_parses_with_defaults() {
+        let config: ChainConfig = serde_json::from_str(
+            r#"{ "storage": { "rocksdb": { "max_open_files": -1, "compression": "zstd", "bloom_filter_bits_per_key": 0 } } }"#,
+        )
+        .unwrap();
+        let rocks = &config.storage.rocksdb;
+        assert_eq!(rocks.max_open_files, -1);
+        assert_eq!(rocks.compression, RocksCompression::Zstd);
+        assert_eq!(rocks.bloom_filter_bits_per_key, 0);
+        assert_eq!(rocks.max_total_wal_size_mib, 0);
+        assert_eq!(
+            rocks.write_buffer_size_mib,
+            RocksConfig::default().write_buffer_size_mib
+        );
+
+        let bad = r#"{ "storage": { "rocksdb": { "compression": "brotli" } } }"#;
+        assert!(serde_json::from_str::<ChainConfig>(bad).is_err());
+    }
+
+    #[test]
+    fn test_keep_blocks_parses_count_or_archive() {
+        let config: ChainConfig =
+            serde_json::from_str(r#"{ "storage": { "keep_blocks": 3 } }"#).unwrap();
+        assert_eq!(config.storage.keep_blocks, KeepBlocks::Recent(3));
+        let config: ChainConfig =
+            serde_json::from_str(r#"{ "storage": { "keep_blocks": "archive" } }"#).unwrap();
+        assert_eq!(config.storage.keep_blocks, KeepBlocks::Archive);
+        assert_eq!(StorageConfig::default().keep_blocks, KeepBlocks::Archive);
+
+        let written = serde_json::to_value(KeepBlocks::Recent(3)).unwrap();
+        assert_eq!(written, serde_json::json!(3));
+        for bad in [r#"0"#, r#""all""#, r#"-1"#] {
+            let json = format!(r#"{{ "storage": {{ "keep_blocks": {} }} }}"#, bad);
+            assert!(
+                serde_json::from_str::<ChainConfig>(&json).is_err(),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_genesis_archons_parse_and_reject_duplicates() {
+        use crate::core::transaction::AddressEncoding;
+
+        let json = format!(
+            r#"{{ "genesis": {{ "archons": [
+                {{ "address": "{}", "balance": 5, "name": "First" }},
+                {{ "address": "{}", "balance": 7 }}
+            ] }} }}"#,
+            [7u8; 32].to_display(),
+            hex::encode([8u8; 32])
+        );
+        let config: ChainConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.genesis.archons.len(), 2);
+        assert_eq!(config.genesis.archons[0].name.as_deref(), Some("First"));
+        assert_eq!(config.genesis.archons[1].address, [8u8; 32]);
+        assert_eq!(config.genesis.archons[1].name, None);
+        config.genesis.validate().unwrap();
+
+        // The same address in checksummed and hex form is still a duplicate
+        let json = format!(
+            r#"{{ "genesis": {{ "archons": [
+                {{ "address": "{}", "balance": 5 }},
+                {{ "address": "{}", "balance": 7 }}
+            ] }} }}"#,
+            [7u8; 32].to_display(),
+            hex::encode([7u8; 32])
+        );
+        let config: ChainConfig = serde_json::from_str(&json).unwrap();
+        let err = config.genesis.validate().unwrap_err();
+        assert!(err.to_string().contains("listed more than once"));
+
+        // Unset, the Genesis Archon alone
+        let default = GenesisConfig::default();
+        assert_eq!(default.archons.len(), 1);
+        assert_eq!(default.archons[0].address, GENESIS_ARCHON_ADDRESS);
+        assert_eq!(default.archons[0].balance, GENESIS_ARCHON_INITIAL_BALANCE);
+    }
 
-/// Dev faucet amount (10,000 CGT per request).
-#[cfg(debug_assertions)]
-pub const DEV_FAUCET_AMOUNT: u64 = 10_000;
+    #[test]
+    fn test_forge_params_load_and_validate() {
+        let config: ChainConfig =
+            serde_json::from_str(r#"{ "genesis": { "forge": { "memory_kib": 64 } } }"#).unwrap();
+        assert_eq!(
+            config.genesis.forge,
+            ForgeConfig {
+                memory_kib: 64,
+                ..ForgeConfig::default()
+            }
+        );
+        config.genesis.validate().unwrap();
 
+        let config: ChainConfig =
+            serde_json::from_str(r#"{ "genesis": { "forge": { "time_cost": 0 } } }"#).unwrap();
+        let err = config.genesis.validate().unwrap_err();
+        assert!(err.to_string().contains("invalid Forge parameters"));
+    }
+}
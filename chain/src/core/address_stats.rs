@@ -0,0 +1,186 @@
+//! Per-address activity counters for profile pages.
+//!
+//! Block execution updates a small `AddressStats` record for every address a
+//! transaction involves (see `tx_index::involved_addresses`), reading CGT
+//! amounts and NFT mints from the transaction's events. How many NFTs an
+//! address owns right now is not counted here: it is read from the
+//! `nft_dgen` owner index, which every mint and transfer already keeps exact.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::codec;
+use crate::core::receipt::Event;
+use crate::core::state::State;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+use crate::core::tx_index::involved_addresses;
+
+/// Storage prefix for activity counters, keyed by address.
+const STATS_KEY_PREFIX: &[u8] = b"stats:";
+
+/// Activity counters for one address, over the transactions included in
+/// blocks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddressStats {
+    /// Height of the first block with a transaction involving the address.
+    pub first_seen_height: u64,
+    /// Transactions the address sent, whether or not their calls succeeded.
+    pub txs_sent: u64,
+    /// CGT sent in `bank_cgt` transfers, in base units.
+    pub cgt_sent: u64,
+    /// CGT received in `bank_cgt` transfers and mints, in base units.
+    pub cgt_received: u64,
+    /// D-GEN NFTs minted by the address.
+    pub nfts_minted: u64,
+}
+
+fn stats_key(addr: &Address) -> Vec<u8> {
+    let mut key = STATS_KEY_PREFIX.to_vec();
+    key.extend_from_slice(addr);
+    key
+}
+
+/// Activity counters for an address, or `None` if no included transaction
+/// has involved it.
+pub fn address_stats(state: &State, addr: &Address) -> Option<AddressStats> {
+    state
+        .get_raw(&stats_key(addr))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+}
+
+/// Counters saturate rather than fail: they are bookkeeping, and must never
+/// fail the block that includes the transaction.
+fn add(counter: &mut u64, amount: u64) {
+    *counter = counter.saturating_add(amount);
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> Option<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// The event's amount, or 0 if it has none.
+fn amount(event: &Event) -> u64 {
+    attribute(event, "amount")
+        .and_then(|amount| amount.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Update the counters of every address involved in a transaction included
+/// at `height`. Failed calls emit no events, so they count only as sent.
+pub fn record_activity(
+    state: &mut State,
+    tx: &Transaction,
+    height: u64,
+    events: &[Event],
+) -> Result<(), String> {
+    for addr in involved_addresses(tx, events) {
+        let mut stats = address_stats(state, &addr).unwrap_or(AddressStats {
+            first_seen_height: height,
+            ..AddressStats::default()
+        });
+        if addr == tx.from {
+            add(&mut stats.txs_sent, 1);
+        }
+        for event in events {
+            match (event.module_id.as_str(), event.kind.as_str()) {
+                ("bank_cgt", "transfer") => {
+                    let amount = amount(event);
+                    if event.address == addr {
+                        add(&mut stats.cgt_sent, amount);
+                    }
+                    if attribute(event, "to").and_then(|to| Address::parse(to).ok()) == Some(addr) {
+                        add(&mut stats.cgt_received, amount);
+                    }
+                }
+                ("bank_cgt", "mint") if event.address == addr => {
+                    add(&mut stats.cgt_received, amount(event));
+                }
+                ("nft_dgen", "mint") if event.address == addr => {
+                    add(&mut stats.nfts_minted, 1);
+                }
+                _ => {}
+            }
+        }
+
+        let bytes = codec::encode(&stats).map_err(|e| e.to_string())?;
+        state
+            .put_raw(stats_key(&addr), bytes)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: Address = [1; 32];
+    const RECIPIENT: Address = [2; 32];
+
+    fn tx(from: Address) -> Transaction {
+        Transaction {
+            from,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    fn transfer(from: Address, to: Address, amount: u64) -> Event {
+        Event::new("bank_cgt", "transfer", from)
+            .with("to", to.to_display())
+            .with("amount", amount)
+    }
+
+    #[test]
+    fn test_failed_calls_count_only_as_sent() {
+        let mut state = State::in_memory();
+        record_activity(&mut state, &tx(SENDER), 4, &[]).unwrap();
+        record_activity(&mut state, &tx(SENDER), 5, &[]).unwrap();
+        assert_eq!(
+            address_stats(&state, &SENDER),
+            Some(AddressStats {
+                first_seen_height: 4,
+                txs_sent: 2,
+                ..AddressStats::default()
+            })
+        );
+        assert_eq!(address_stats(&state, &RECIPIENT), None);
+    }
+
+    #[test]
+    fn test_counters_saturate_instead_of_failing() {
+        let mut state = State::in_memory();
+        record_activity(
+            &mut state,
+            &tx(SENDER),
+            1,
+            &[transfer(SENDER, RECIPIENT, u64::MAX)],
+        )
+        .unwrap();
+        assert_eq!(
+            address_stats(&state, &RECIPIENT).unwrap().cgt_received,
+            u64::MAX
+        );
+
+        record_activity(
+            &mut state,
+            &tx(SENDER),
+            2,
+            &[transfer(SENDER, RECIPIENT, 1)],
+        )
+        .unwrap();
+        assert_eq!(
+            address_stats(&state, &RECIPIENT).unwrap().cgt_received,
+            u64::MAX
+        );
+    }
+}
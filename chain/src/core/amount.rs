@@ -0,0 +1,204 @@
+//! Conversion between CGT base units and human-readable decimal strings.
+//!
+//! On-chain amounts are always `u64` base units. These helpers format them
+//! with `CGT_DECIMALS` decimal places for display and parse decimal strings
+//! back without rounding.
+//!
+//! JSON-RPC keeps every amount field in base units and adds a
+//! `<field>_display` string formatted by `format_cgt`; JSON transaction
+//! params accept either form through `deserialize_cgt`.
+
+use serde::{de, Deserialize, Deserializer};
+use thiserror::Error;
+
+use crate::config::{CGT_DECIMALS, CGT_UNIT};
+
+/// Errors that can occur when parsing a decimal CGT amount.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("invalid amount: {0}")]
+    Invalid(String),
+    #[error("amount has more than {0} decimal places")]
+    TooPrecise(u32),
+    #[error("amount overflows u64 base units")]
+    Overflow,
+}
+
+/// Format a base-unit amount as a decimal CGT string.
+///
+/// Trailing fractional zeros are dropped: `150_000_000` formats as `"1.5"`
+/// and `100_000_000` as `"1"`.
+pub fn format_cgt(base_units: u64) -> String {
+    let whole = base_units / CGT_UNIT;
+    let frac = base_units % CGT_UNIT;
+    if frac == 0 {
+        return whole.to_string();
+    }
+    let frac = format!("{:0width$}", frac, width = CGT_DECIMALS as usize);
+    format!("{}.{}", whole, frac.trim_end_matches('0'))
+}
+
+/// Parse a decimal CGT string (e.g. `"1.5"`) into base units.
+///
+/// The conversion is exact: more than `CGT_DECIMALS` fractional digits is
+/// rejected rather than rounded, as is anything that overflows `u64`.
+pub fn parse_cgt(s: &str) -> Result<u64, AmountError> {
+    let (whole, frac) = match s.split_once('.') {
+        Some((whole, frac)) => (whole, frac),
+        None => (s, ""),
+    };
+
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty()
+        || !is_digits(whole)
+        || !is_digits(frac)
+        || (s.contains('.') && frac.is_empty())
+    {
+        return Err(AmountError::Invalid(s.to_string()));
+    }
+    if frac.len() > CGT_DECIMALS as usize {
+        return Err(AmountError::TooPrecise(CGT_DECIMALS));
+    }
+
+    let whole: u64 = whole.parse().map_err(|_| AmountError::Overflow)?;
+    let frac: u64 = if frac.is_empty() {
+        0
+    } else {
+        // At most CGT_DECIMALS digits, so this always fits
+        let digits: u64 = frac
+            .parse()
+            .map_err(|_| AmountError::Invalid(s.to_string()))?;
+        digits * 10u64.pow(CGT_DECIMALS - frac.len() as u32)
+    };
+
+    whole
+        .checked_mul(CGT_UNIT)
+        .and_then(|units| units.checked_add(frac))
+        .ok_or(AmountError::Overflow)
+}
+
+/// Deserialize a CGT amount given either as a JSON number of base units or
+/// as a decimal CGT string (`"1.5"`), converted exactly by `parse_cgt`.
+///
+/// The string must have a decimal point (`"2.0"`, not `"2"`): a bare integer
+/// string could be meant as either unit, and reading base units as CGT would
+/// move 10^8 times the intended amount.
+pub fn deserialize_cgt<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        BaseUnits(u64),
+        Decimal(String),
+    }
+
+    match Amount::deserialize(deserializer)? {
+        Amount::BaseUnits(base_units) => Ok(base_units),
+        Amount::Decimal(s) if !s.contains('.') => Err(de::Error::custom(format!(
+            "ambiguous amount \"{}\": write CGT with a decimal point (\"{}.0\") or base units as a JSON number",
+            s, s
+        ))),
+        Amount::Decimal(s) => parse_cgt(&s).map_err(de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_and_parse_round_trip() {
+        assert_eq!(format_cgt(0), "0");
+        assert_eq!(format_cgt(CGT_UNIT), "1");
+        assert_eq!(format_cgt(150_000_000), "1.5");
+        assert_eq!(format_cgt(1), "0.00000001");
+        assert_eq!(format_cgt(u64::MAX), "184467440737.09551615");
+
+        for amount in [0, 1, 150_000_000, CGT_UNIT * 42 + 7, u64::MAX] {
+            assert_eq!(parse_cgt(&format_cgt(amount)), Ok(amount));
+        }
+        assert_eq!(parse_cgt("1.50"), Ok(150_000_000));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert_eq!(
+            parse_cgt("0.000000001"),
+            Err(AmountError::TooPrecise(CGT_DECIMALS))
+        );
+        assert_eq!(
+            parse_cgt("184467440737.09551616"),
+            Err(AmountError::Overflow)
+        );
+        assert_eq!(
+            parse_cgt("99999999999999999999"),
+            Err(AmountError::Overflow)
+        );
+        for bad in ["", ".5", "1.", "-1", "1,5", "1.2.3", " 1"] {
+            assert!(
+                matches!(parse_cgt(bad), Err(AmountError::Invalid(_))),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_decimal_place() {
+        // A digit at each fractional place, alone, under a whole part, and
+        // padded with zeros out to CGT_DECIMALS places
+        for places in 1..=CGT_DECIMALS {
+            let base_units = 10u64.pow(CGT_DECIMALS - places);
+            let frac = format!("{}1", "0".repeat(places as usize - 1));
+            let padded = format!("{:0<width$}", frac, width = CGT_DECIMALS as usize);
+
+            assert_eq!(format_cgt(base_units), format!("0.{}", frac));
+            assert_eq!(format_cgt(7 * CGT_UNIT + base_units), format!("7.{}", frac));
+            assert_eq!(parse_cgt(&format!("0.{}", frac)), Ok(base_units));
+            assert_eq!(
+                parse_cgt(&format!("7.{}", padded)),
+                Ok(7 * CGT_UNIT + base_units)
+            );
+        }
+    }
+
+    #[test]
+    fn test_largest_amounts() {
+        let max = format_cgt(u64::MAX);
+        assert_eq!(parse_cgt(&max), Ok(u64::MAX));
+        assert_eq!(format_cgt(u64::MAX - 1), "184467440737.09551614");
+        assert_eq!(
+            parse_cgt("184467440737"),
+            Ok(u64::MAX / CGT_UNIT * CGT_UNIT)
+        );
+        assert_eq!(parse_cgt("184467440738"), Err(AmountError::Overflow));
+        assert_eq!(
+            parse_cgt("184467440737.095516150"),
+            Err(AmountError::TooPrecise(CGT_DECIMALS))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_base_units_or_decimal() {
+        #[derive(Deserialize)]
+        struct Params {
+            #[serde(deserialize_with = "deserialize_cgt")]
+            amount: u64,
+        }
+        let amount = |value: serde_json::Value| {
+            serde_json::from_value::<Params>(serde_json::json!({ "amount": value }))
+                .map(|params| params.amount)
+                .map_err(|e| e.to_string())
+        };
+
+        assert_eq!(amount(150.into()), Ok(150));
+        assert_eq!(amount("1.5".into()), Ok(150_000_000));
+        assert_eq!(amount("2.0".into()), Ok(2 * CGT_UNIT));
+        assert!(amount("2".into()).unwrap_err().contains("ambiguous amount"));
+        assert_eq!(amount(format_cgt(u64::MAX).into()), Ok(u64::MAX));
+        assert!(amount("0.000000001".into())
+            .unwrap_err()
+            .contains("more than 8 decimal places"));
+        assert!(amount((-1).into()).is_err());
+        assert!(amount(1.5.into()).is_err());
+    }
+}
@@ -7,6 +7,11 @@
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::config::GenesisConfig;
+use crate::core::codec;
+use crate::core::merkle;
+use crate::core::transaction::Transaction;
+
 /// Block header containing metadata about a block.
 ///
 /// The header includes chain position (height, prev_hash), state commitment
@@ -18,7 +23,8 @@ pub struct BlockHeader {
     pub height: u64,
     /// Hash of the previous block header.
     pub prev_hash: [u8; 32],
-    /// Merkle root of the state tree (placeholder for Phase 2).
+    /// Merkle root of module state after the block (`Runtime::state_root`),
+    /// or zeros on headers from before it was committed.
     pub state_root: [u8; 32],
     /// Unix timestamp in seconds.
     pub timestamp: u64,
@@ -26,6 +32,25 @@ pub struct BlockHeader {
     pub difficulty_target: u128,
     /// PoW solution nonce (placeholder for Phase 2 Forge).
     pub nonce: u64,
+    /// Merkle root of the body's transaction hashes (see `tx_root`).
+    ///
+    /// `None` for genesis and for headers from before the field existed; such
+    /// headers hash exactly as they always did, and their transactions have
+    /// no inclusion proofs.
+    #[serde(default)]
+    pub tx_root: Option<[u8; 32]>,
+}
+
+/// Header layout from before `tx_root`, kept for hashing and decoding old
+/// blocks.
+#[derive(Serialize, Deserialize)]
+struct BlockHeaderV1 {
+    height: u64,
+    prev_hash: [u8; 32],
+    state_root: [u8; 32],
+    timestamp: u64,
+    difficulty_target: u128,
+    nonce: u64,
 }
 
 /// A complete block containing a header and a list of transactions.
@@ -38,7 +63,48 @@ pub struct Block {
     /// Block header with metadata.
     pub header: BlockHeader,
     /// List of transactions to execute.
-    pub body: Vec<crate::core::transaction::Transaction>,
+    pub body: Vec<Transaction>,
+}
+
+/// Block layout from before `BlockHeader::tx_root`.
+#[derive(Deserialize)]
+struct BlockV1 {
+    header: BlockHeaderV1,
+    body: Vec<Transaction>,
+}
+
+impl From<BlockV1> for Block {
+    fn from(v1: BlockV1) -> Self {
+        let h = v1.header;
+        Self {
+            header: BlockHeader {
+                height: h.height,
+                prev_hash: h.prev_hash,
+                state_root: h.state_root,
+                timestamp: h.timestamp,
+                difficulty_target: h.difficulty_target,
+                nonce: h.nonce,
+                tx_root: None,
+            },
+            body: v1.body,
+        }
+    }
+}
+
+/// Blocks are stored and exported versioned: version 2 added
+/// `BlockHeader::tx_root`.
+impl codec::Versioned for Block {
+    const VERSION: u8 = 2;
+
+    fn upgrade(version: Option<u8>, body: &[u8]) -> bincode::Result<Self> {
+        match version {
+            None | Some(1) => codec::decode_state_exact::<BlockV1>(body).map(Into::into),
+            Some(other) => Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "no upgrade from block version {}",
+                other
+            )))),
+        }
+    }
 }
 
 impl BlockHeader {
@@ -47,8 +113,7 @@ impl BlockHeader {
     /// This function serializes the header using bincode and computes a SHA-256 hash.
     /// In Phase 2, this will be used as part of the Forge PoW verification process.
     pub fn hash(&self) -> [u8; 32] {
-        let serialized = bincode::serialize(self).expect("BlockHeader should always serialize");
-        let hash = Sha256::digest(&serialized);
+        let hash = Sha256::digest(self.canonical_bytes(self.nonce));
         hash.into()
     }
 
@@ -57,10 +122,108 @@ impl BlockHeader {
     /// This is used for Forge PoW computation, where the nonce is varied
     /// to find a valid proof-of-work solution.
     pub fn serialize_without_nonce(&self) -> Vec<u8> {
-        // Clone the header and zero out the nonce, then serialize
-        let mut clone = self.clone();
-        clone.nonce = 0;
-        bincode::serialize(&clone).expect("BlockHeader serialization failed")
+        self.canonical_bytes(0)
+    }
+
+    /// Encoding that is hashed: the pre-`tx_root` layout when there is no
+    /// root, so existing block hashes (genesis included) are unchanged.
+    fn canonical_bytes(&self, nonce: u64) -> Vec<u8> {
+        let encoded = match self.tx_root {
+            None => codec::encode(&BlockHeaderV1 {
+                height: self.height,
+                prev_hash: self.prev_hash,
+                state_root: self.state_root,
+                timestamp: self.timestamp,
+                difficulty_target: self.difficulty_target,
+                nonce,
+            }),
+            Some(_) => codec::encode(&BlockHeader {
+                nonce,
+                ..self.clone()
+            }),
+        };
+        encoded.expect("BlockHeader should always serialize")
+    }
+}
+
+/// Merkle root over the hashes of `body`, in order, committed to as
+/// `BlockHeader::tx_root`.
+pub fn tx_root(body: &[Transaction]) -> [u8; 32] {
+    let hashes: Vec<_> = body.iter().map(Transaction::hash).collect();
+    merkle::root_of_hashes(&hashes)
+}
+
+/// Proof that a transaction is in a block body, checked against the block
+/// header's `tx_root`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxInclusionProof {
+    pub tx_hash: [u8; 32],
+    /// Position of the transaction in the body.
+    pub index: u64,
+    /// Number of transactions in the body.
+    pub tx_count: u64,
+    /// Sibling hashes from the leaf up (see `core::merkle`).
+    pub branch: Vec<[u8; 32]>,
+}
+
+impl Block {
+    /// Inclusion proof for the body transaction with hash `tx_hash`, if any.
+    pub fn inclusion_proof(&self, tx_hash: &[u8; 32]) -> Option<TxInclusionProof> {
+        let hashes: Vec<_> = self.body.iter().map(Transaction::hash).collect();
+        let index = hashes.iter().position(|hash| hash == tx_hash)?;
+        Some(TxInclusionProof {
+            tx_hash: *tx_hash,
+            index: index as u64,
+            tx_count: hashes.len() as u64,
+            branch: merkle::branch_of_hashes(&hashes, index)?,
+        })
+    }
+}
+
+/// Whether `proof` shows its transaction is in a block whose header commits
+/// to `tx_root`.
+///
+/// Needs only the header, not the body, so light clients can check inclusion.
+pub fn verify_inclusion_proof(proof: &TxInclusionProof, tx_root: &[u8; 32]) -> bool {
+    merkle::verify_hash_branch(
+        tx_root,
+        &proof.tx_hash,
+        proof.index,
+        proof.tx_count,
+        &proof.branch,
+    )
+}
+
+/// Header of the genesis block, at `GenesisConfig::start_height` (0 unless
+/// the chain is a fork).
+///
+/// Genesis is fully deterministic so every node on the same chain derives the
+/// same genesis hash, which peers compare during the P2P handshake. The
+/// genesis block has no parent, so its `prev_hash` commits to the Forge
+/// parameters instead (`ForgeConfig::commitment`): nodes that would hash
+/// blocks differently disagree on the genesis hash. It stays zero for the
+/// default parameters, so chains from before they were configurable keep
+/// their genesis hash.
+pub fn genesis_header(genesis: &GenesisConfig) -> BlockHeader {
+    BlockHeader {
+        height: genesis.start_height,
+        prev_hash: genesis.forge.commitment(),
+        state_root: [0; 32],
+        timestamp: genesis.genesis_timestamp,
+        difficulty_target: genesis.difficulty_target,
+        nonce: 0,
+        tx_root: None,
+    }
+}
+
+/// The genesis block: the genesis header with an empty body.
+///
+/// Genesis state (the Archon's balance and profile) is written directly during
+/// initialization rather than by transactions, so the body stays empty.
+pub fn genesis_block(genesis: &GenesisConfig) -> Block {
+    Block {
+        header: genesis_header(genesis),
+        body: Vec::new(),
     }
 }
 
@@ -82,6 +245,7 @@ mod tests {
             timestamp: 1234567890,
             difficulty_target: 1000,
             nonce: 0,
+            tx_root: None,
         };
 
         let hash1 = header.hash();
@@ -96,4 +260,114 @@ mod tests {
         let hash3 = header2.hash();
         assert_ne!(hash1, hash3);
     }
+
+    #[test]
+    fn test_genesis_block_is_deterministic() {
+        let genesis = GenesisConfig::default();
+        let block = genesis_block(&genesis);
+        assert_eq!(block, genesis_block(&genesis));
+        assert_eq!(block.header.height, 0);
+        assert_eq!(block.header.prev_hash, [0; 32]);
+        assert!(block.body.is_empty());
+
+        // Pinned so an accidental change to genesis is caught
+        assert_eq!(
+            hex::encode(block.header.hash()),
+            "5f83d8bfbc825bd6cd46430eb73b715ba1a150b10a583b1f4a0e4500b53441df"
+        );
+
+        let mut other = genesis.clone();
+        other.difficulty_target -= 1;
+        assert_ne!(genesis_block(&other).header.hash(), block.header.hash());
+
+        // Forge parameters are committed to as well
+        let mut other = genesis.clone();
+        other.forge.lanes = 2;
+        assert_ne!(genesis_block(&other).header.hash(), block.header.hash());
+    }
+
+    fn block_with(tx_count: u64) -> Block {
+        let body: Vec<_> = (0..tx_count)
+            .map(|nonce| Transaction {
+                from: [1; 32],
+                nonce,
+                module_id: "bank_cgt".to_string(),
+                call_id: "transfer".to_string(),
+                payload: vec![],
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            })
+            .collect();
+        let mut header = genesis_header(&GenesisConfig::default());
+        header.height = 1;
+        header.tx_root = Some(tx_root(&body));
+        Block { header, body }
+    }
+
+    #[test]
+    fn test_inclusion_proof_for_every_position() {
+        for tx_count in [1, 2, 7] {
+            let block = block_with(tx_count);
+            let root = block.header.tx_root.unwrap();
+            for (index, tx) in block.body.iter().enumerate() {
+                let proof = block.inclusion_proof(&tx.hash()).unwrap();
+                assert_eq!(proof.index, index as u64);
+                assert_eq!(proof.tx_count, tx_count);
+                assert!(
+                    verify_inclusion_proof(&proof, &root),
+                    "tx_count={} index={}",
+                    tx_count,
+                    index
+                );
+            }
+        }
+        assert!(block_with(2).inclusion_proof(&[0; 32]).is_none());
+    }
+
+    #[test]
+    fn test_tampered_inclusion_proof_rejected() {
+        let block = block_with(7);
+        let root = block.header.tx_root.unwrap();
+        let proof = block.inclusion_proof(&block.body[3].hash()).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.branch[1][0] ^= 1;
+        assert!(!verify_inclusion_proof(&tampered, &root));
+
+        let mut moved = proof.clone();
+        moved.index = 2;
+        assert!(!verify_inclusion_proof(&moved, &root));
+
+        let mut other_tx = proof.clone();
+        other_tx.tx_hash = block.body[4].hash();
+        assert!(!verify_inclusion_proof(&other_tx, &root));
+
+        // Another block's root does not vouch for this transaction
+        assert!(!verify_inclusion_proof(
+            &proof,
+            &block_with(6).header.tx_root.unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_pre_tx_root_blocks_still_decode_and_hash() {
+        let mut block = block_with(2);
+        block.header.tx_root = None;
+        let legacy_header = BlockHeaderV1 {
+            height: block.header.height,
+            prev_hash: block.header.prev_hash,
+            state_root: block.header.state_root,
+            timestamp: block.header.timestamp,
+            difficulty_target: block.header.difficulty_target,
+            nonce: block.header.nonce,
+        };
+        let legacy_hash: [u8; 32] = Sha256::digest(codec::encode(&legacy_header).unwrap()).into();
+        assert_eq!(block.header.hash(), legacy_hash);
+
+        // Stored before versioning: the old layout with no prefix
+        let bytes = codec::encode(&(legacy_header, &block.body)).unwrap();
+        assert_eq!(codec::decode_versioned::<Block>(&bytes).unwrap(), block);
+    }
 }
@@ -0,0 +1,202 @@
+//! Bounded bincode encoding for payloads and stored state.
+//!
+//! All bincode in the chain goes through these helpers so that no decode can
+//! be talked into a huge allocation by a hostile length prefix, and so that
+//! every node encodes the same value to the same bytes. Block hashes, state
+//! roots, and transaction hashes are taken over these bytes, so the
+//! configuration is spelled out rather than left to bincode's defaults:
+//! fixed-width integers, little-endian, and an explicit byte limit. The
+//! layout is the same as `bincode::serialize`, so existing payloads and
+//! stored values still decode. Changing any option is a consensus change.
+//!
+//! The byte limit is also the collection cap: decoding reads through
+//! bincode's limited reader, which charges every string, byte array, and
+//! element against the limit before allocating, so a length prefix larger
+//! than the remaining budget fails fast. None of the chain's types are
+//! recursive, so there is no separate depth limit.
+//!
+//! Stored types whose layout evolves implement `Versioned`: their encoding
+//! starts with a one-byte version, and older versions are upgraded when read.
+//! Values written before a type was versioned carry no prefix and are handed
+//! to the upgrade hook as `None`.
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Largest value decoded from network input (transaction payloads, p2p
+/// messages). Raw transactions are bounded well below this.
+pub const NETWORK_LIMIT: u64 = 128 * 1024;
+
+/// Largest value read back from the state database or encoded for it.
+/// Stored values (blocks, NFT ownership lists) can outgrow a single payload.
+pub const STATE_LIMIT: u64 = 16 * 1024 * 1024;
+
+fn options(limit: u64) -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .with_little_endian()
+        .allow_trailing_bytes()
+        .with_limit(limit)
+}
+
+/// Encode a value for storage or the wire.
+pub fn encode<T: Serialize + ?Sized>(value: &T) -> bincode::Result<Vec<u8>> {
+    options(STATE_LIMIT).serialize(value)
+}
+
+/// Decode untrusted bytes, such as a peer's handshake, under
+/// `NETWORK_LIMIT`, ignoring any bytes left over.
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    options(NETWORK_LIMIT).deserialize_from(bytes)
+}
+
+/// Decode untrusted bytes under `NETWORK_LIMIT`, failing if any bytes are
+/// left over. Used for transaction payloads, and wherever an encoding must
+/// decode as exactly one layout.
+pub fn decode_exact<T: DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    options(NETWORK_LIMIT)
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+}
+
+/// Decode a value read back from the state database, under `STATE_LIMIT`.
+pub fn decode_state<T: DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    options(STATE_LIMIT).deserialize_from(bytes)
+}
+
+/// Decode a stored value under `STATE_LIMIT`, failing if any bytes are left
+/// over.
+pub fn decode_state_exact<T: DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    options(STATE_LIMIT)
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+}
+
+/// A stored type whose encoding carries a one-byte version prefix.
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Version written in front of new encodings.
+    const VERSION: u8;
+
+    /// Decode an older layout and upgrade it to the current one.
+    ///
+    /// `version` is `None` for a value stored before the type was versioned,
+    /// in which case `body` is the whole value.
+    fn upgrade(version: Option<u8>, body: &[u8]) -> bincode::Result<Self>;
+}
+
+/// Encode a value for storage behind its version byte.
+pub fn encode_versioned<T: Versioned>(value: &T) -> bincode::Result<Vec<u8>> {
+    let mut bytes = vec![T::VERSION];
+    options(STATE_LIMIT).serialize_into(&mut bytes, value)?;
+    Ok(bytes)
+}
+
+/// Decode a stored value of any version, upgrading older ones.
+///
+/// A prefix only counts if the rest decodes exactly as that version;
+/// otherwise the bytes are taken as an unprefixed value from before
+/// versioning, like legacy transaction encodings.
+pub fn decode_versioned<T: Versioned>(bytes: &[u8]) -> bincode::Result<T> {
+    let versioned = match bytes.split_first() {
+        Some((&version, body)) if version == T::VERSION => decode_state_exact(body),
+        Some((&version, body)) if version < T::VERSION => T::upgrade(Some(version), body),
+        _ => Err(Box::new(bincode::ErrorKind::Custom(format!(
+            "unknown storage version (latest is {})",
+            T::VERSION
+        )))),
+    };
+    versioned.or_else(|e| T::upgrade(None, bytes).map_err(|_| e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_matches_plain_bincode() {
+        let value = (7u64, "name".to_string(), vec![1u8, 2, 3], Some([9u8; 32]));
+        let bytes = encode(&value).unwrap();
+        assert_eq!(bytes, bincode::serialize(&value).unwrap());
+        assert_eq!(
+            decode::<(u64, String, Vec<u8>, Option<[u8; 32]>)>(&bytes).unwrap(),
+            value
+        );
+    }
+
+    #[derive(Serialize)]
+    enum Kind {
+        _Unit,
+        Pair(u8, u8),
+    }
+
+    #[derive(Serialize)]
+    struct Known {
+        small: u16,
+        id: u64,
+        amount: u128,
+        flag: bool,
+        name: String,
+        data: Vec<u8>,
+        expiry: Option<u64>,
+        none: Option<u32>,
+        kind: Kind,
+    }
+
+    #[test]
+    fn test_known_struct_encodes_to_stable_bytes() {
+        let known = Known {
+            small: 0x0102,
+            id: 0x0102_0304_0506_0708,
+            amount: 1,
+            flag: true,
+            name: "cgt".to_string(),
+            data: vec![0xaa, 0xbb],
+            expiry: Some(5),
+            none: None,
+            kind: Kind::Pair(7, 8),
+        };
+        let expected = concat!(
+            "0201",                             // small
+            "0807060504030201",                 // id
+            "01000000000000000000000000000000", // amount
+            "01",                               // flag
+            "0300000000000000",                 // name length
+            "636774",                           // name
+            "0200000000000000",                 // data length
+            "aabb",                             // data
+            "01",                               // expiry tag
+            "0500000000000000",                 // expiry
+            "00",                               // none tag
+            "01000000",                         // enum variant index
+            "0708",                             // variant fields
+        );
+        assert_eq!(hex::encode(encode(&known).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_decode_exact_rejects_trailing_bytes() {
+        let mut bytes = encode(&7u64).unwrap();
+        assert_eq!(decode_exact::<u64>(&bytes).unwrap(), 7);
+        bytes.push(0);
+        assert!(decode_exact::<u64>(&bytes).is_err());
+        assert_eq!(decode::<u64>(&bytes).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_length_prefix_bombs_rejected() {
+        let bomb = u64::MAX.to_le_bytes();
+        assert!(decode::<Vec<u8>>(&bomb).is_err());
+        assert!(decode::<String>(&bomb).is_err());
+        assert!(decode::<Vec<[u8; 32]>>(&bomb).is_err());
+        assert!(decode_state::<Vec<u64>>(&bomb).is_err());
+    }
+
+    #[test]
+    fn test_network_limit_below_state_limit() {
+        let big = vec![0u8; NETWORK_LIMIT as usize + 1];
+        let bytes = encode(&big).unwrap();
+        assert!(decode::<Vec<u8>>(&bytes).is_err());
+        assert_eq!(decode_state::<Vec<u8>>(&bytes).unwrap(), big);
+    }
+}
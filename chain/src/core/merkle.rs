@@ -0,0 +1,218 @@
+//! Binary Merkle tree over key-value entries, with inclusion proofs.
+//!
+//! Leaves are the entries in key order. A leaf hashes its length-prefixed
+//! key and value under a `0x00` tag; an inner node hashes its two children
+//! under a `0x01` tag, so a leaf can never pass for a node. A level with an
+//! odd number of nodes carries its last node up unchanged rather than
+//! duplicating it. The root of an empty tree is all zeros.
+//!
+//! The same tree also commits to an ordered list of hashes (a block's
+//! transactions), with each leaf hashing one item under the `0x00` tag.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+
+/// Root of a tree with no entries.
+pub const EMPTY_ROOT: [u8; 32] = [0; 32];
+
+fn leaf_hash(key: &[u8], value: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_TAG]);
+    hasher.update((key.len() as u64).to_be_bytes());
+    hasher.update(key);
+    hasher.update((value.len() as u64).to_be_bytes());
+    hasher.update(value);
+    hasher.finalize().into()
+}
+
+fn item_hash(item: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_TAG]);
+    hasher.update(item);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_TAG]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Hash one level into the next, carrying an odd last node up unchanged.
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [single] => *single,
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        })
+        .collect()
+}
+
+/// Root of the tree over already-hashed leaves.
+fn root_of_leaves(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    if level.is_empty() {
+        return EMPTY_ROOT;
+    }
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Sibling hashes on the path from leaf `index` to the root.
+fn branch_of_leaves(mut level: Vec<[u8; 32]>, mut index: usize) -> Vec<[u8; 32]> {
+    let mut siblings = Vec::new();
+    while level.len() > 1 {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            siblings.push(level[sibling]);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+    siblings
+}
+
+/// Hash `leaf` at `index` of `count` leaves up to the root along `siblings`.
+///
+/// Returns `None` if the branch has too few or too many siblings.
+fn climb(leaf: [u8; 32], index: u64, count: u64, siblings: &[[u8; 32]]) -> Option<[u8; 32]> {
+    if index >= count {
+        return None;
+    }
+    let mut hash = leaf;
+    let mut index = index;
+    let mut width = count;
+    let mut siblings = siblings.iter();
+    while width > 1 {
+        // The last node of an odd level has no sibling and moves up as is
+        if index != width - 1 || width.is_multiple_of(2) {
+            let sibling = siblings.next()?;
+            hash = if index.is_multiple_of(2) {
+                node_hash(&hash, sibling)
+            } else {
+                node_hash(sibling, &hash)
+            };
+        }
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none().then_some(hash)
+}
+
+/// Merkle root of `entries`, which must be sorted by key.
+pub fn root(entries: &[(Vec<u8>, Vec<u8>)]) -> [u8; 32] {
+    root_of_leaves(entries.iter().map(|(k, v)| leaf_hash(k, v)).collect())
+}
+
+/// Merkle root of an ordered list of hashes.
+pub fn root_of_hashes(items: &[[u8; 32]]) -> [u8; 32] {
+    root_of_leaves(items.iter().map(item_hash).collect())
+}
+
+/// Sibling hashes proving the item at `index` of `items`, if in range.
+pub fn branch_of_hashes(items: &[[u8; 32]], index: usize) -> Option<Vec<[u8; 32]>> {
+    (index < items.len()).then(|| branch_of_leaves(items.iter().map(item_hash).collect(), index))
+}
+
+/// Whether `branch` shows `item` at `index` of `count` hashes under `root`.
+pub fn verify_hash_branch(
+    root: &[u8; 32],
+    item: &[u8; 32],
+    index: u64,
+    count: u64,
+    branch: &[[u8; 32]],
+) -> bool {
+    climb(item_hash(item), index, count, branch).is_some_and(|hash| &hash == root)
+}
+
+/// Proof that a key held a value in the tree with a given root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proof {
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+    /// Position of the entry among the tree's leaves.
+    pub leaf_index: u64,
+    /// Number of leaves in the tree.
+    pub leaf_count: u64,
+    /// Sibling hashes from the leaf up, skipping levels where the node has
+    /// no sibling.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl Proof {
+    /// Whether this proof shows `key` = `value` under `root`.
+    pub fn verify(&self, root: &[u8; 32]) -> bool {
+        let leaf = leaf_hash(&self.key, &self.value);
+        climb(leaf, self.leaf_index, self.leaf_count, &self.siblings)
+            .is_some_and(|hash| &hash == root)
+    }
+}
+
+/// Inclusion proof for `key` in `entries` (sorted by key), if present.
+pub fn prove(entries: &[(Vec<u8>, Vec<u8>)], key: &[u8]) -> Option<Proof> {
+    let leaf_index = entries
+        .binary_search_by(|(k, _)| k.as_slice().cmp(key))
+        .ok()?;
+    let leaves = entries.iter().map(|(k, v)| leaf_hash(k, v)).collect();
+    let siblings = branch_of_leaves(leaves, leaf_index);
+    let (key, value) = entries[leaf_index].clone();
+    Some(Proof {
+        key,
+        value,
+        leaf_index: leaf_index as u64,
+        leaf_count: entries.len() as u64,
+        siblings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(n: u8) -> Vec<(Vec<u8>, Vec<u8>)> {
+        (0..n).map(|i| (vec![b'k', i], vec![i; 3])).collect()
+    }
+
+    #[test]
+    fn test_every_entry_proves_for_every_tree_size() {
+        for n in 1..=9 {
+            let entries = entries(n);
+            let root = root(&entries);
+            for (key, _) in &entries {
+                let proof = prove(&entries, key).unwrap();
+                assert!(proof.verify(&root), "n={} key={:?}", n, key);
+            }
+        }
+        assert_eq!(root(&[]), EMPTY_ROOT);
+        assert!(prove(&entries(3), b"missing").is_none());
+    }
+
+    #[test]
+    fn test_tampered_proofs_fail() {
+        let entries = entries(5);
+        let root = root(&entries);
+        let proof = prove(&entries, &entries[2].0).unwrap();
+
+        let mut wrong_value = proof.clone();
+        wrong_value.value = vec![9];
+        assert!(!wrong_value.verify(&root));
+
+        let mut wrong_index = proof.clone();
+        wrong_index.leaf_index = 3;
+        assert!(!wrong_index.verify(&root));
+
+        let mut extra_sibling = proof.clone();
+        extra_sibling.siblings.push([0; 32]);
+        assert!(!extra_sibling.verify(&root));
+
+        assert!(!proof.verify(&[1; 32]));
+    }
+}
@@ -2,9 +2,23 @@
 //!
 //! This module contains the fundamental building blocks of the Demiurge chain:
 //! - Blocks and block headers
+//! - Bounded bincode encoding
+//! - Merkle trees and inclusion proofs
 //! - Transactions
+//! - Transaction receipts and events
+//! - Transactions-by-address index
+//! - Per-address activity counters
 //! - State management
+//! - Parallel transaction execution
+//! - CGT amount formatting
 
+pub mod address_stats;
+pub mod amount;
 pub mod block;
+pub mod codec;
+pub mod merkle;
+pub mod parallel;
+pub mod receipt;
 pub mod state;
 pub mod transaction;
+pub mod tx_index;
@@ -0,0 +1,359 @@
+//! Parallel execution of a block's transactions.
+//!
+//! Modules may declare, per call, every storage key a transaction could
+//! write (`RuntimeModule::declared_writes`). Consecutive transactions whose
+//! declared keys don't overlap form a batch; each transaction of a batch is
+//! dispatched on the rayon pool by a worker `State` that reads through the
+//! block's state as it stood before the batch and buffers its own writes.
+//! The results are then merged in block order, with receipts, indexes, and
+//! address stats written exactly as sequential execution writes them.
+//!
+//! Workers record every key they touch. If one wrote a key it did not
+//! declare, or read a key that an earlier transaction of the batch wrote
+//! (including its receipt and index entries), its result could differ from
+//! sequential execution: the merge is dropped and the batch re-runs
+//! sequentially. The state after a block is therefore always the state
+//! sequential execution produces; parallelism only changes how fast it is
+//! reached. Transactions without declared writes run on their own.
+
+use std::collections::HashSet;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+
+use rayon::prelude::*;
+
+use crate::core::block::Block;
+use crate::core::receipt::Event;
+use crate::core::state::{check_not_expired, AccessLog, State, Writes};
+use crate::core::transaction::Transaction;
+use crate::runtime::Runtime;
+
+/// A run of consecutive transactions that may execute in parallel.
+#[derive(Debug, PartialEq, Eq)]
+struct Batch {
+    /// Position of the run in the block body.
+    range: Range<usize>,
+    /// Declared writes of each transaction in the run.
+    declared: Vec<HashSet<Vec<u8>>>,
+}
+
+/// What a worker left behind after dispatching one transaction.
+struct Outcome {
+    result: Result<(Option<String>, Vec<Event>), String>,
+    access: AccessLog,
+    writes: Writes,
+}
+
+/// Split a block body into batches: maximal runs of transactions whose
+/// declared writes are pairwise disjoint. A transaction that declares
+/// nothing gets a batch of its own.
+fn plan_batches(runtime: &Runtime, txs: &[Transaction]) -> Vec<Batch> {
+    let mut batches: Vec<Batch> = Vec::new();
+    // Keys declared by the open batch, the last one in `batches`
+    let mut open: Option<HashSet<Vec<u8>>> = None;
+    for (i, tx) in txs.iter().enumerate() {
+        let Some(keys) = runtime.declared_writes(tx) else {
+            open = None;
+            batches.push(Batch {
+                range: i..i + 1,
+                declared: vec![HashSet::new()],
+            });
+            continue;
+        };
+        let keys: HashSet<Vec<u8>> = keys.into_iter().collect();
+        match (&mut open, batches.last_mut()) {
+            (Some(union), Some(batch)) if union.is_disjoint(&keys) => {
+                union.extend(keys.iter().cloned());
+                batch.range.end = i + 1;
+                batch.declared.push(keys);
+            }
+            _ => {
+                open = Some(keys.clone());
+                batches.push(Batch {
+                    range: i..i + 1,
+                    declared: vec![keys],
+                });
+            }
+        }
+    }
+    batches
+}
+
+/// Apply a block's transactions, batching where declared writes allow.
+/// Leaves `state` exactly as `State`'s sequential loop would.
+pub(crate) fn apply_transactions(
+    runtime: &Runtime,
+    block: &Block,
+    state: &mut State,
+) -> Result<(), String> {
+    let height = block.header.height;
+    for batch in plan_batches(runtime, &block.body) {
+        let txs = &block.body[batch.range];
+        for tx in txs {
+            check_not_expired(tx, height)?;
+        }
+        if txs.len() > 1 && run_batch(runtime, txs, &batch.declared, height, state)? {
+            continue;
+        }
+        for tx in txs {
+            let (error, events) = state.dispatch_scoped(runtime, tx)?;
+            state.finish_tx(tx, height, error, events)?;
+        }
+    }
+    Ok(())
+}
+
+/// Dispatch a batch across the rayon pool and merge the results in order.
+///
+/// # Returns
+/// - `Ok(true)` once the batch is applied
+/// - `Ok(false)`, with nothing applied, if the parallel results can't be
+///   trusted to match sequential execution
+fn run_batch(
+    runtime: &Runtime,
+    txs: &[Transaction],
+    declared: &[HashSet<Vec<u8>>],
+    height: u64,
+    state: &mut State,
+) -> Result<bool, String> {
+    let frozen = state.freeze();
+    let outcomes = panic::catch_unwind(AssertUnwindSafe(|| {
+        txs.par_iter()
+            .map(|tx| {
+                let mut worker = frozen.worker(height);
+                let result = worker.dispatch_scoped(runtime, tx);
+                let access = worker.take_access();
+                Outcome {
+                    result,
+                    access,
+                    writes: worker.into_writes(),
+                }
+            })
+            .collect::<Vec<_>>()
+    }));
+    // Reattach the state before passing on a worker's panic
+    state.thaw(frozen);
+    let outcomes = outcomes.unwrap_or_else(|panic| panic::resume_unwind(panic));
+
+    state.begin();
+    match merge(txs, declared, outcomes, height, state) {
+        Ok(true) => {
+            state.commit().map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        other => {
+            state.rollback();
+            if other == Ok(false) {
+                tracing::debug!(
+                    height,
+                    txs = txs.len(),
+                    "parallel batch conflicted; executing sequentially"
+                );
+            }
+            other
+        }
+    }
+}
+
+fn merge(
+    txs: &[Transaction],
+    declared: &[HashSet<Vec<u8>>],
+    outcomes: Vec<Outcome>,
+    height: u64,
+    state: &mut State,
+) -> Result<bool, String> {
+    // Keys written by the transactions merged so far, receipts included
+    let mut written = HashSet::new();
+    for ((tx, declared), outcome) in txs.iter().zip(declared).zip(outcomes) {
+        if !outcome.access.writes.is_subset(declared) || outcome.access.read_any(&written) {
+            return Ok(false);
+        }
+        let (error, events) = outcome.result?;
+        state.track_access();
+        let applied = state
+            .apply_writes(outcome.writes)
+            .map_err(|e| e.to_string())
+            .and_then(|()| state.finish_tx(tx, height, error, events));
+        written.extend(state.take_access().writes);
+        applied?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ChainConfig;
+    use crate::core::block::BlockHeader;
+    use crate::core::codec;
+    use crate::core::receipt::get_receipt;
+    use crate::core::transaction::test_keys::{self, signed};
+    use crate::core::transaction::Address;
+    use crate::runtime::bank_cgt::{credit, get_nonce_cgt, TransferParams};
+    use proptest::prelude::*;
+
+    fn transfer(from: Address, nonce: u64, to: Address, amount: u64, fee: u64) -> Transaction {
+        signed(Transaction {
+            from,
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to,
+                amount,
+                memo: None,
+            })
+            .unwrap(),
+            fee,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        })
+    }
+
+    fn account(i: u8) -> Address {
+        test_keys::account(i + 1)
+    }
+
+    /// A chain with trivial PoW and `accounts` funded accounts.
+    fn funded(accounts: u8, parallel: bool) -> (ChainConfig, State) {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.execution.parallel = parallel;
+        let mut state = State::in_memory();
+        for i in 0..accounts {
+            credit(&mut state, &account(i), 1_000).unwrap();
+        }
+        (config, state)
+    }
+
+    fn block(body: Vec<Transaction>) -> Block {
+        Block {
+            header: BlockHeader {
+                height: 1,
+                prev_hash: [0; 32],
+                state_root: [0; 32],
+                tx_root: None,
+                timestamp: 0,
+                difficulty_target: u128::MAX,
+                nonce: 0,
+            },
+            body,
+        }
+    }
+
+    #[test]
+    fn test_plan_batches_splits_on_overlap_and_undeclared() {
+        let runtime = Runtime::with_default_modules();
+        let mut mint = transfer(account(0), 0, account(1), 1, 0);
+        mint.call_id = "mint_to".to_string();
+        let txs = vec![
+            transfer(account(0), 0, account(1), 1, 0),
+            transfer(account(2), 0, account(3), 1, 0),
+            // Shares account(1) with the first transfer
+            transfer(account(4), 0, account(1), 1, 0),
+            mint,
+            transfer(account(5), 0, account(6), 1, 0),
+        ];
+        let ranges: Vec<_> = plan_batches(&runtime, &txs)
+            .into_iter()
+            .map(|batch| batch.range)
+            .collect();
+        assert_eq!(ranges, vec![0..2, 2..3, 3..4, 4..5]);
+    }
+
+    #[test]
+    fn test_conflicting_batch_falls_back_to_sequential() {
+        // Both transfers burn a fee, so both write the total supply
+        let body = vec![
+            transfer(account(0), 0, account(1), 10, 1),
+            transfer(account(2), 0, account(3), 10, 1),
+        ];
+        let runtime = Runtime::with_default_modules();
+        assert_eq!(plan_batches(&runtime, &body).len(), 2);
+
+        // Declared writes that hide the shared key still execute correctly
+        let (config, mut state) = funded(4, true);
+        let mut declared = plan_batches(&runtime, &body)
+            .into_iter()
+            .flat_map(|batch| batch.declared)
+            .collect::<Vec<_>>();
+        let supply_key = b"bank_cgt/supply/".to_vec();
+        for keys in &mut declared {
+            keys.remove(&supply_key);
+        }
+        assert!(!run_batch(&runtime, &body, &declared, 1, &mut state).unwrap());
+        assert_eq!(get_nonce_cgt(&state, &account(0)), 0);
+
+        let (_, mut sequential) = funded(4, false);
+        let block = block(body);
+        sequential.execute_block(&block, &config).unwrap();
+        state.execute_block(&block, &config).unwrap();
+        assert_eq!(state.scan_prefix(b""), sequential.scan_prefix(b""));
+    }
+
+    /// Parallel and sequential execution of the same block leave identical
+    /// state and receipts.
+    fn assert_modes_agree(accounts: u8, body: Vec<Transaction>) {
+        let block = block(body);
+        let runtime = Runtime::with_default_modules();
+        let (config, mut sequential) = funded(accounts, false);
+        sequential.execute_block(&block, &config).unwrap();
+        let (config, mut parallel) = funded(accounts, true);
+        parallel.execute_block(&block, &config).unwrap();
+
+        assert_eq!(
+            runtime.state_root(&parallel),
+            runtime.state_root(&sequential)
+        );
+        assert_eq!(parallel.scan_prefix(b""), sequential.scan_prefix(b""));
+        for tx in &block.body {
+            assert_eq!(
+                get_receipt(&parallel, &tx.hash()),
+                get_receipt(&sequential, &tx.hash())
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        /// Random transfers among a few accounts: some share accounts, some
+        /// overspend, some reuse or skip nonces, some burn fees.
+        #[test]
+        fn prop_parallel_matches_sequential(
+            transfers in prop::collection::vec(
+                (0u8..8, 0u8..8, 0u64..400, 0u64..3, 0u64..2, any::<bool>()),
+                0..48,
+            )
+        ) {
+            let mut nonces = [0u64; 8];
+            let body = transfers
+                .into_iter()
+                .map(|(from, to, amount, fee, nonce_skew, bump)| {
+                    let nonce = nonces[from as usize] + nonce_skew;
+                    if bump {
+                        nonces[from as usize] += 1;
+                    }
+                    transfer(account(from), nonce, account(to), amount, fee)
+                })
+                .collect();
+            assert_modes_agree(8, body);
+        }
+    }
+
+    #[test]
+    fn test_disjoint_transfers_apply_in_one_batch() {
+        let body: Vec<_> = (0..4)
+            .map(|i| transfer(account(2 * i), 0, account(2 * i + 1), 100, 0))
+            .collect();
+        let runtime = Runtime::with_default_modules();
+        let batches = plan_batches(&runtime, &body);
+        assert_eq!(batches.len(), 1);
+
+        let (_, mut state) = funded(8, true);
+        assert!(run_batch(&runtime, &body, &batches[0].declared, 1, &mut state).unwrap());
+        assert_eq!(get_nonce_cgt(&state, &account(6)), 1);
+        assert_modes_agree(8, body);
+    }
+}
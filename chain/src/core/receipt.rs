@@ -0,0 +1,177 @@
+//! Transaction receipts and the events they carry.
+//!
+//! Block execution writes a `Receipt` for every transaction it applies, keyed
+//! by the transaction's canonical hash, so clients can submit a transaction
+//! and later poll for its outcome.
+//!
+//! Nodes that prune old blocks delete their receipts too, keeping only the
+//! height of the block each one belonged to, so a lookup can still tell a
+//! pruned transaction from one the chain never included. Those markers are
+//! dropped in turn, a block at a time, once they are old enough.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::Address;
+
+/// Storage prefix for receipts, keyed by transaction hash.
+const RECEIPT_KEY_PREFIX: &[u8] = b"chain:receipt:";
+
+/// Storage prefix for the block heights of pruned receipts, keyed by
+/// transaction hash.
+const PRUNED_RECEIPT_KEY_PREFIX: &[u8] = b"chain:receipt_pruned:";
+
+/// Storage prefix for the hashes of the receipts pruned from each block,
+/// keyed by big-endian height, so their markers can be dropped together.
+const PRUNED_AT_KEY_PREFIX: &[u8] = b"chain:receipt_pruned_at:";
+
+/// Something a module reports happened while executing a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Event {
+    /// Module that emitted the event (e.g. "bank_cgt").
+    pub module_id: String,
+    /// Kind of event within the module (e.g. "transfer").
+    pub kind: String,
+    /// Address the event is about, usually the transaction sender.
+    pub address: Address,
+    /// Extra details, in the order the module added them.
+    pub attributes: Vec<(String, String)>,
+}
+
+impl Event {
+    /// Create an event with no attributes.
+    pub fn new(module_id: &str, kind: &str, address: Address) -> Self {
+        Self {
+            module_id: module_id.to_string(),
+            kind: kind.to_string(),
+            address,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Add an attribute.
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.attributes.push((key.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Outcome of a transaction included in a block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Receipt {
+    /// Canonical hash of the transaction (see `Transaction::hash`).
+    pub tx_hash: [u8; 32],
+    /// Height of the block that included it.
+    pub height: u64,
+    /// Whether the transaction's call succeeded.
+    pub success: bool,
+    /// Why the call failed, if it did.
+    pub error: Option<String>,
+    /// Events emitted by a successful call; failed calls emit none.
+    pub events: Vec<Event>,
+}
+
+fn receipt_key(tx_hash: &[u8; 32]) -> Vec<u8> {
+    let mut key = RECEIPT_KEY_PREFIX.to_vec();
+    key.extend_from_slice(tx_hash);
+    key
+}
+
+fn pruned_receipt_key(tx_hash: &[u8; 32]) -> Vec<u8> {
+    let mut key = PRUNED_RECEIPT_KEY_PREFIX.to_vec();
+    key.extend_from_slice(tx_hash);
+    key
+}
+
+fn pruned_at_key(height: u64) -> Vec<u8> {
+    let mut key = PRUNED_AT_KEY_PREFIX.to_vec();
+    key.extend_from_slice(&height.to_be_bytes());
+    key
+}
+
+/// Store a receipt under its transaction hash.
+///
+/// A successful receipt from another block is kept: the transaction's first
+/// successful inclusion stays its record even if the same transaction is
+/// executed again in a later block. A failed receipt is replaced, since the
+/// transaction may be included again and succeed, and a receipt from the
+/// same block is replaced by its replay.
+pub fn put_receipt(state: &mut State, receipt: &Receipt) -> Result<(), String> {
+    if get_receipt(state, &receipt.tx_hash)
+        .is_some_and(|existing| existing.success && existing.height != receipt.height)
+    {
+        return Ok(());
+    }
+    let bytes = codec::encode(receipt).map_err(|e| e.to_string())?;
+    state
+        .put_raw(receipt_key(&receipt.tx_hash), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Receipt for a transaction hash, if a block has included the transaction.
+pub fn get_receipt(state: &State, tx_hash: &[u8; 32]) -> Option<Receipt> {
+    state
+        .get_raw(&receipt_key(tx_hash))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+}
+
+/// Delete the receipts the transactions `tx_hashes` got in the block at
+/// `height`, recording only that height for each.
+///
+/// A receipt from any other height is left alone: the transaction failed at
+/// `height` and was included again later.
+pub fn prune_receipts(
+    state: &mut State,
+    height: u64,
+    tx_hashes: &[[u8; 32]],
+) -> Result<(), String> {
+    let mut pruned = Vec::new();
+    for tx_hash in tx_hashes {
+        if get_receipt(state, tx_hash).is_none_or(|receipt| receipt.height != height) {
+            continue;
+        }
+        state
+            .delete_raw(&receipt_key(tx_hash))
+            .map_err(|e| e.to_string())?;
+        let bytes = codec::encode(&height).map_err(|e| e.to_string())?;
+        state
+            .put_raw(pruned_receipt_key(tx_hash), bytes)
+            .map_err(|e| e.to_string())?;
+        pruned.push(*tx_hash);
+    }
+    if pruned.is_empty() {
+        return Ok(());
+    }
+    let bytes = codec::encode(&pruned).map_err(|e| e.to_string())?;
+    state
+        .put_raw(pruned_at_key(height), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Drop the markers `prune_receipts` left for the block at `height`, after
+/// which its transactions read as unknown.
+pub fn forget_pruned_receipts(state: &mut State, height: u64) -> Result<(), String> {
+    let Some(bytes) = state.get_raw(&pruned_at_key(height)) else {
+        return Ok(());
+    };
+    let tx_hashes: Vec<[u8; 32]> = codec::decode_state(&bytes).map_err(|e| e.to_string())?;
+    for tx_hash in &tx_hashes {
+        if pruned_receipt_height(state, tx_hash) == Some(height) {
+            state
+                .delete_raw(&pruned_receipt_key(tx_hash))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    state
+        .delete_raw(&pruned_at_key(height))
+        .map_err(|e| e.to_string())
+}
+
+/// Height of the block a transaction was included in, if its receipt has
+/// since been pruned.
+pub fn pruned_receipt_height(state: &State, tx_hash: &[u8; 32]) -> Option<u64> {
+    state
+        .get_raw(&pruned_receipt_key(tx_hash))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+}
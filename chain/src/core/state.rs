@@ -3,17 +3,42 @@
 //! This module provides a pluggable key-value store abstraction that supports
 //! both in-memory (for tests) and RocksDB (for production) backends.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
+use lru::LruCache;
 use rocksdb::Options;
-use rocksdb::DB;
+use rocksdb::{
+    BlockBasedOptions, Cache, DBCompressionType, Direction, IteratorMode, Snapshot, WriteBatch, DB,
+};
+use yoke::{Yoke, Yokeable};
 
-use crate::core::block::Block;
-use crate::forge::{forge_hash, meets_difficulty, ForgeConfig};
+use crate::config::{ChainConfig, RocksCompression, RocksConfig};
+use crate::core::address_stats::record_activity;
+use crate::core::block::{tx_root, Block};
+use crate::core::codec;
+use crate::core::parallel;
+use crate::core::receipt::{put_receipt, Event, Receipt};
+use crate::core::transaction::Transaction;
+use crate::core::tx_index::index_transaction;
+use crate::forge::{forge_hash, meets_difficulty};
 use crate::runtime::Runtime;
 
+/// Key recording the timestamp of the last block applied, which the next
+/// block's must exceed. Written in the same commit as the block's state.
+const KEY_LAST_BLOCK_TIMESTAMP: &[u8] = b"chain:last_timestamp";
+
+/// Key recording the height of the first block applied with a `tx_root`;
+/// every block after it must carry one too.
+const KEY_TX_ROOT_FROM: &[u8] = b"chain:tx_root_from";
+
+/// Key recording the height of the first block applied with a `state_root`;
+/// every block after it must carry one too.
+const KEY_STATE_ROOT_FROM: &[u8] = b"chain:state_root_from";
+
 /// Trait for key-value storage backends.
 pub trait KvBackend: Send + Sync {
     /// Get a value by key.
@@ -21,11 +46,42 @@ pub trait KvBackend: Send + Sync {
 
     /// Set a key-value pair.
     fn put_raw(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<()>;
+
+    /// Remove a key. Removing a missing key is not an error.
+    fn delete_raw(&mut self, key: &[u8]) -> Result<()>;
+
+    /// All key-value pairs whose key starts with `prefix`.
+    fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)>;
+
+    /// Apply puts (`Some`) and deletes (`None`) atomically: after a crash or
+    /// an error either all of them are stored or none are.
+    fn write_batch(&mut self, writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()>;
+
+    /// Compact the whole store, dropping overwritten values and delete
+    /// tombstones. Backends with nothing to compact do nothing.
+    fn compact(&self) {}
+
+    /// A job that does what `compact` does without borrowing the backend, so
+    /// it can run after the lock guarding the backend is released; `None` if
+    /// there is nothing to compact.
+    fn compactor(&self) -> Option<Compactor> {
+        None
+    }
+
+    /// A view of the store as it is now, which later writes do not change,
+    /// or `None` if the backend cannot take one cheaply.
+    fn snapshot(&self) -> Option<Box<dyn KvBackend>> {
+        None
+    }
 }
 
+/// Compaction job detached from its backend (see `KvBackend::compactor`).
+pub type Compactor = Box<dyn FnOnce() + Send>;
+
 /// In-memory backend using HashMap.
 ///
 /// Used primarily for testing. All data is lost when the State is dropped.
+#[derive(Default)]
 pub struct InMemoryBackend {
     inner: HashMap<Vec<u8>, Vec<u8>>,
 }
@@ -48,6 +104,29 @@ impl KvBackend for InMemoryBackend {
         self.inner.insert(key, value);
         Ok(())
     }
+
+    fn delete_raw(&mut self, key: &[u8]) -> Result<()> {
+        self.inner.remove(key);
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.inner
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    fn write_batch(&mut self, writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()> {
+        for (key, value) in writes {
+            match value {
+                Some(value) => self.inner.insert(key, value),
+                None => self.inner.remove(&key),
+            };
+        }
+        Ok(())
+    }
 }
 
 /// RocksDB backend for persistent storage.
@@ -55,17 +134,56 @@ impl KvBackend for InMemoryBackend {
 /// This backend stores all data on disk using RocksDB, providing durability
 /// and efficient key-value operations.
 pub struct RocksDbBackend {
-    db: DB,
+    db: Arc<DB>,
 }
 
 impl RocksDbBackend {
-    /// Open or create a RocksDB database at the given path.
-    pub fn open(path: &Path) -> Result<Self> {
-        let mut opts = Options::default();
+    /// Open or create a RocksDB database at the given path, tuned by
+    /// `config`.
+    pub fn open(path: &Path, config: &RocksConfig) -> Result<Self> {
+        let mut opts = Self::options(config);
         opts.create_if_missing(true);
-        // Column families can be introduced later; for now use a single CF.
         let db = DB::open(&opts, path)?;
-        Ok(Self { db })
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Open an existing RocksDB database at the given path without write
+    /// access: every write fails, and no background flush or compaction
+    /// runs. Writes made by other processes after opening are not seen.
+    pub fn open_read_only(path: &Path, config: &RocksConfig) -> Result<Self> {
+        let db = DB::open_for_read_only(&Self::options(config), path, false)?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    fn options(config: &RocksConfig) -> Options {
+        let mut opts = Options::default();
+        opts.set_write_buffer_size(config.write_buffer_size_mib << 20);
+        opts.set_max_open_files(config.max_open_files);
+        opts.set_compression_type(match config.compression {
+            RocksCompression::None => DBCompressionType::None,
+            RocksCompression::Snappy => DBCompressionType::Snappy,
+            RocksCompression::Lz4 => DBCompressionType::Lz4,
+            RocksCompression::Zstd => DBCompressionType::Zstd,
+        });
+        if config.max_total_wal_size_mib > 0 {
+            opts.set_max_total_wal_size(config.max_total_wal_size_mib << 20);
+        }
+        // Opening reads every table file's statistics unless told not to,
+        // which dominates startup on a large database
+        opts.set_skip_stats_update_on_db_open(true);
+        opts.set_skip_checking_sst_file_sizes_on_db_open(true);
+        let mut table = BlockBasedOptions::default();
+        table.set_block_cache(&Cache::new_lru_cache(config.block_cache_size_mib << 20));
+        if config.bloom_filter_bits_per_key > 0 {
+            // Whole-key filters: reads are point lookups of full
+            // `module/prefix/...` keys, and prefix scans ignore the filter
+            table.set_bloom_filter(f64::from(config.bloom_filter_bits_per_key), false);
+            table.set_cache_index_and_filter_blocks(true);
+            table.set_pin_l0_filter_and_index_blocks_in_cache(true);
+        }
+        opts.set_block_based_table_factory(&table);
+        // Column families can be introduced later; for now use a single CF.
+        opts
     }
 }
 
@@ -78,6 +196,284 @@ impl KvBackend for RocksDbBackend {
         self.db.put(key, value)?;
         Ok(())
     }
+
+    fn delete_raw(&mut self, key: &[u8]) -> Result<()> {
+        self.db.delete(key)?;
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.db
+            .prefix_iterator(prefix)
+            .filter_map(|item| item.ok())
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect()
+    }
+
+    fn write_batch(&mut self, writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for (key, value) in writes {
+            match value {
+                Some(value) => batch.put(key, value),
+                None => batch.delete(key),
+            }
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    fn compact(&self) {
+        self.db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    }
+
+    /// Compacts through a handle on the database, which RocksDB lets run
+    /// alongside reads and writes.
+    fn compactor(&self) -> Option<Compactor> {
+        let db = Arc::clone(&self.db);
+        Some(Box::new(move || {
+            db.compact_range(None::<&[u8]>, None::<&[u8]>)
+        }))
+    }
+
+    /// A RocksDB snapshot, which keeps the database open while it lives.
+    fn snapshot(&self) -> Option<Box<dyn KvBackend>> {
+        let snapshot = Yoke::attach_to_cart(Arc::clone(&self.db), |db| DbSnapshot(db.snapshot()));
+        Some(Box::new(RocksDbSnapshot(snapshot)))
+    }
+}
+
+/// A RocksDB snapshot borrowing the database it was taken from.
+#[derive(Yokeable)]
+struct DbSnapshot<'a>(Snapshot<'a>);
+
+/// Backend reading a RocksDB snapshot (see `KvBackend::snapshot`). Writes
+/// are refused.
+struct RocksDbSnapshot(Yoke<DbSnapshot<'static>, Arc<DB>>);
+
+impl KvBackend for RocksDbSnapshot {
+    fn get_raw(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.0.get().0.get(key).ok().flatten()
+    }
+
+    fn put_raw(&mut self, _key: Vec<u8>, _value: Vec<u8>) -> Result<()> {
+        Err(anyhow::anyhow!("a database snapshot is read-only"))
+    }
+
+    fn delete_raw(&mut self, _key: &[u8]) -> Result<()> {
+        Err(anyhow::anyhow!("a database snapshot is read-only"))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.0
+            .get()
+            .0
+            .iterator(IteratorMode::From(prefix, Direction::Forward))
+            .filter_map(|item| item.ok())
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect()
+    }
+
+    fn write_batch(&mut self, _writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()> {
+        Err(anyhow::anyhow!("a database snapshot is read-only"))
+    }
+}
+
+/// LRU cache of recent reads in front of another backend.
+///
+/// `get_raw` results, including misses, are kept for the most recently read
+/// keys; `put_raw` and `delete_raw` drop the key's entry before writing
+/// through, so a cached value never outlives a write. Prefix scans always
+/// go to the inner backend.
+pub struct CachedBackend<B> {
+    inner: B,
+    cache: Mutex<LruCache<Vec<u8>, Option<Vec<u8>>>>,
+}
+
+impl<B: KvBackend> CachedBackend<B> {
+    /// Wrap `inner` with a cache of up to `capacity` keys.
+    pub fn new(inner: B, capacity: NonZeroUsize) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, LruCache<Vec<u8>, Option<Vec<u8>>>> {
+        // A panic mid-update may leave the cache inconsistent; start it over
+        self.cache.lock().unwrap_or_else(|poisoned| {
+            self.cache.clear_poison();
+            let mut cache = poisoned.into_inner();
+            cache.clear();
+            cache
+        })
+    }
+}
+
+impl<B: KvBackend> KvBackend for CachedBackend<B> {
+    fn get_raw(&self, key: &[u8]) -> Option<Vec<u8>> {
+        if let Some(value) = self.cache().get(key) {
+            return value.clone();
+        }
+        let value = self.inner.get_raw(key);
+        self.cache().put(key.to_vec(), value.clone());
+        value
+    }
+
+    fn put_raw(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+        self.cache().pop(&key);
+        self.inner.put_raw(key, value)
+    }
+
+    fn delete_raw(&mut self, key: &[u8]) -> Result<()> {
+        self.cache().pop(key);
+        self.inner.delete_raw(key)
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.inner.scan_prefix(prefix)
+    }
+
+    fn write_batch(&mut self, writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()> {
+        let mut cache = self.cache();
+        for (key, _) in &writes {
+            cache.pop(key);
+        }
+        drop(cache);
+        self.inner.write_batch(writes)
+    }
+
+    fn compact(&self) {
+        self.inner.compact()
+    }
+
+    fn compactor(&self) -> Option<Compactor> {
+        self.inner.compactor()
+    }
+
+    fn snapshot(&self) -> Option<Box<dyn KvBackend>> {
+        self.inner.snapshot()
+    }
+}
+
+/// Uncommitted writes of one scope: `None` marks a deleted key.
+pub(crate) type Writes = HashMap<Vec<u8>, Option<Vec<u8>>>;
+
+fn layered_get(backend: &dyn KvBackend, overlays: &[Writes], key: &[u8]) -> Option<Vec<u8>> {
+    for layer in overlays.iter().rev() {
+        if let Some(value) = layer.get(key) {
+            return value.clone();
+        }
+    }
+    backend.get_raw(key)
+}
+
+fn layered_scan(
+    backend: &dyn KvBackend,
+    overlays: &[Writes],
+    prefix: &[u8],
+) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut merged: BTreeMap<Vec<u8>, Option<Vec<u8>>> = backend
+        .scan_prefix(prefix)
+        .into_iter()
+        .map(|(key, value)| (key, Some(value)))
+        .collect();
+    for layer in overlays {
+        for (key, value) in layer {
+            if key.starts_with(prefix) {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    merged
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| (key, value)))
+        .collect()
+}
+
+/// Keys a `State` read, scanned, and wrote while access tracking was on
+/// (see `State::track_access`).
+#[derive(Debug, Default)]
+pub struct AccessLog {
+    /// Keys read with `get_raw`.
+    pub reads: HashSet<Vec<u8>>,
+    /// Prefixes read with `scan_prefix`.
+    pub scans: Vec<Vec<u8>>,
+    /// Keys written or deleted, including writes later rolled back.
+    pub writes: HashSet<Vec<u8>>,
+}
+
+impl AccessLog {
+    /// Whether a read or scan covered any of `keys`.
+    pub fn read_any(&self, keys: &HashSet<Vec<u8>>) -> bool {
+        keys.iter().any(|key| {
+            self.reads.contains(key) || self.scans.iter().any(|prefix| key.starts_with(prefix))
+        })
+    }
+}
+
+/// A State's backend and open scopes, detached by `State::freeze` so that
+/// parallel workers can read through them at once.
+struct FrozenView {
+    backend: Box<dyn KvBackend>,
+    overlays: Vec<Writes>,
+}
+
+/// Backend of a parallel worker or a snapshot: reads go to the shared frozen
+/// view. Every worker write lands in the worker's own scope, and snapshots
+/// are read-only, so writes here are refused.
+struct SharedView(Arc<FrozenView>);
+
+impl KvBackend for SharedView {
+    fn get_raw(&self, key: &[u8]) -> Option<Vec<u8>> {
+        layered_get(self.0.backend.as_ref(), &self.0.overlays, key)
+    }
+
+    fn put_raw(&mut self, _key: Vec<u8>, _value: Vec<u8>) -> Result<()> {
+        Err(anyhow::anyhow!("frozen state is read-only"))
+    }
+
+    fn delete_raw(&mut self, _key: &[u8]) -> Result<()> {
+        Err(anyhow::anyhow!("frozen state is read-only"))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        layered_scan(self.0.backend.as_ref(), &self.0.overlays, prefix)
+    }
+
+    fn write_batch(&mut self, _writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()> {
+        Err(anyhow::anyhow!("frozen state is read-only"))
+    }
+}
+
+/// Handle to a frozen State, from which parallel workers are made. Hand it
+/// back with `State::thaw` once every worker is dropped.
+pub(crate) struct Frozen(Arc<FrozenView>);
+
+impl Frozen {
+    /// A State that reads through the frozen one and buffers its own writes
+    /// in a single open scope (see `State::into_writes`), with access
+    /// tracking on.
+    pub(crate) fn worker(&self, block_height: u64) -> State {
+        let mut state = State::with_backend(Box::new(SharedView(self.0.clone())));
+        state.block_height = block_height;
+        state.begin();
+        state.track_access();
+        state
+    }
+}
+
+/// Reject a transaction that has expired by `height`.
+pub(crate) fn check_not_expired(tx: &Transaction, height: u64) -> Result<(), String> {
+    if tx.is_expired_at(height) {
+        return Err(format!(
+            "transaction expired at height {} (block height {})",
+            tx.valid_until_height.unwrap_or_default(),
+            height
+        ));
+    }
+    Ok(())
 }
 
 /// State wrapper that abstracts over different storage backends.
@@ -87,68 +483,613 @@ impl KvBackend for RocksDbBackend {
 /// execution via runtime modules.
 pub struct State {
     backend: Box<dyn KvBackend>,
+    /// Stack of uncommitted write layers opened with `begin` (innermost last).
+    overlays: Vec<Writes>,
+    /// Height of the block whose transactions are being applied. Not persisted.
+    block_height: u64,
+    /// `module_id/` prefix writes are confined to while a module dispatches.
+    write_namespace: Option<Vec<u8>>,
+    /// Events emitted by the transaction being executed; `None` when no
+    /// receipt is being built.
+    events: Option<Vec<Event>>,
+    /// Canonical hash of the transaction being dispatched by
+    /// `dispatch_scoped`.
+    tx_hash: Option<[u8; 32]>,
+    /// Events from the current block's successful transactions.
+    block_events: Vec<Event>,
+    /// Keys accessed since `track_access`, if tracking is on.
+    access: Option<Mutex<AccessLog>>,
 }
 
 impl State {
-    /// Create a new in-memory state (for testing).
-    pub fn in_memory() -> Self {
+    fn with_backend(backend: Box<dyn KvBackend>) -> Self {
         State {
-            backend: Box::new(InMemoryBackend::new()),
+            backend,
+            overlays: Vec::new(),
+            block_height: 0,
+            write_namespace: None,
+            events: None,
+            tx_hash: None,
+            block_events: Vec::new(),
+            access: None,
         }
     }
 
-    /// Open a RocksDB-backed state at the given path.
+    /// Create a new in-memory state (for testing).
+    pub fn in_memory() -> Self {
+        Self::with_backend(Box::new(InMemoryBackend::new()))
+    }
+
+    /// Open a RocksDB-backed state at the given path with default tuning.
     pub fn open_rocksdb(path: &Path) -> Result<Self> {
-        let backend = RocksDbBackend::open(path)?;
-        Ok(State {
-            backend: Box::new(backend),
-        })
+        Self::open_rocksdb_cached(path, 0, &RocksConfig::default())
+    }
+
+    /// Open a RocksDB-backed state tuned by `rocks`, with an LRU read cache
+    /// of `cache_entries` keys in front of it (see `CachedBackend`); 0
+    /// disables the cache.
+    pub fn open_rocksdb_cached(
+        path: &Path,
+        cache_entries: usize,
+        rocks: &RocksConfig,
+    ) -> Result<Self> {
+        let backend = RocksDbBackend::open(path, rocks)?;
+        Ok(Self::with_rocksdb(backend, cache_entries))
+    }
+
+    /// Like `open_rocksdb_cached`, but opens an existing database read-only
+    /// (see `RocksDbBackend::open_read_only`).
+    pub fn open_rocksdb_read_only(
+        path: &Path,
+        cache_entries: usize,
+        rocks: &RocksConfig,
+    ) -> Result<Self> {
+        let backend = RocksDbBackend::open_read_only(path, rocks)?;
+        Ok(Self::with_rocksdb(backend, cache_entries))
+    }
+
+    fn with_rocksdb(backend: RocksDbBackend, cache_entries: usize) -> Self {
+        let backend: Box<dyn KvBackend> = match NonZeroUsize::new(cache_entries) {
+            Some(capacity) => Box::new(CachedBackend::new(backend, capacity)),
+            None => Box::new(backend),
+        };
+        Self::with_backend(backend)
+    }
+
+    /// Start recording the keys this State reads, scans, and writes,
+    /// discarding any earlier record.
+    pub fn track_access(&mut self) {
+        self.access = Some(Mutex::new(AccessLog::default()));
+    }
+
+    /// Stop recording accesses and return what was recorded.
+    pub fn take_access(&mut self) -> AccessLog {
+        self.access
+            .take()
+            .map(|log| {
+                log.into_inner()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+            })
+            .unwrap_or_default()
+    }
+
+    fn record(&self, f: impl FnOnce(&mut AccessLog)) {
+        if let Some(log) = &self.access {
+            f(&mut log.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+        }
     }
 
     /// Get a value by key.
     ///
     /// Returns `None` if the key does not exist.
     pub fn get_raw(&self, key: &[u8]) -> Option<Vec<u8>> {
-        self.backend.get_raw(key)
+        self.record(|log| {
+            log.reads.insert(key.to_vec());
+        });
+        layered_get(self.backend.as_ref(), &self.overlays, key)
+    }
+
+    /// All key-value pairs whose key starts with `prefix`, including writes
+    /// buffered in open scopes, sorted by key.
+    pub fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.record(|log| log.scans.push(prefix.to_vec()));
+        layered_scan(self.backend.as_ref(), &self.overlays, prefix)
+    }
+
+    /// Compact the backing store (see `KvBackend::compact`). Writes still
+    /// buffered in open scopes are not affected.
+    pub fn compact(&self) {
+        self.backend.compact()
+    }
+
+    /// A job compacting the backing store that holds no borrow of this
+    /// State (see `KvBackend::compactor`); `None` if there is nothing to
+    /// compact.
+    pub fn compactor(&self) -> Option<Compactor> {
+        self.backend.compactor()
+    }
+
+    /// A read-only State holding this one's contents as they are now,
+    /// including writes buffered in open scopes, which later writes here do
+    /// not change; `None` if the backend cannot take a snapshot cheaply, as
+    /// the in-memory one cannot.
+    ///
+    /// Lets a long scan run without holding the lock this State is kept
+    /// under.
+    pub fn snapshot(&self) -> Option<State> {
+        let view = FrozenView {
+            backend: self.backend.snapshot()?,
+            overlays: self.overlays.clone(),
+        };
+        let mut state = State::with_backend(Box::new(SharedView(Arc::new(view))));
+        state.block_height = self.block_height;
+        Some(state)
+    }
+
+    /// Run `f` with writes confined to `module_id`'s storage namespace.
+    ///
+    /// The runtime wraps every module dispatch in this. Cross-module API
+    /// functions (`runtime::module_api`) call it with their own module id as
+    /// an explicit escape hatch when invoked from another module's dispatch.
+    pub fn with_namespace<R>(&mut self, module_id: &str, f: impl FnOnce(&mut State) -> R) -> R {
+        let mut namespace = module_id.as_bytes().to_vec();
+        namespace.push(b'/');
+        let previous = self.write_namespace.replace(namespace);
+        let result = f(self);
+        self.write_namespace = previous;
+        result
+    }
+
+    /// Module whose namespace writes are currently confined to, if any.
+    ///
+    /// During a dispatch this is the dispatching module until a cross-module
+    /// API function switches to its own.
+    pub fn namespace_module(&self) -> Option<&[u8]> {
+        self.write_namespace
+            .as_deref()
+            .map(|namespace| &namespace[..namespace.len() - 1])
+    }
+
+    fn check_namespace(&self, key: &[u8]) -> Result<()> {
+        match &self.write_namespace {
+            Some(namespace) if !key.starts_with(namespace) => Err(anyhow::anyhow!(
+                "write outside storage namespace {}",
+                String::from_utf8_lossy(namespace)
+            )),
+            _ => Ok(()),
+        }
     }
 
     /// Set a key-value pair.
     ///
-    /// If the key already exists, the value will be overwritten.
+    /// If the key already exists, the value will be overwritten. Inside a
+    /// `begin` scope the write is buffered until the scope is committed.
+    /// Fails if the key is outside the active module namespace.
     pub fn put_raw(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
-        self.backend.put_raw(key, value)
+        self.check_namespace(&key)?;
+        self.record(|log| {
+            log.writes.insert(key.clone());
+        });
+        match self.overlays.last_mut() {
+            Some(layer) => {
+                layer.insert(key, Some(value));
+                Ok(())
+            }
+            None => self.backend.put_raw(key, value),
+        }
+    }
+
+    /// Remove a key.
+    ///
+    /// Removing a missing key is not an error. Inside a `begin` scope the
+    /// removal is buffered like any other write. Fails if the key is outside
+    /// the active module namespace.
+    pub fn delete_raw(&mut self, key: &[u8]) -> Result<()> {
+        self.check_namespace(key)?;
+        self.record(|log| {
+            log.writes.insert(key.to_vec());
+        });
+        match self.overlays.last_mut() {
+            Some(layer) => {
+                layer.insert(key.to_vec(), None);
+                Ok(())
+            }
+            None => self.backend.delete_raw(key),
+        }
+    }
+
+    /// Height of the block currently being executed.
+    ///
+    /// Runtime modules use this for deadlines and other height-based rules.
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    /// Set the height reported to runtime modules by `block_height`.
+    pub fn set_block_height(&mut self, height: u64) {
+        self.block_height = height;
+    }
+
+    /// Record an event for the receipt of the transaction being executed.
+    ///
+    /// Outside block execution (e.g. block template dry-runs) events are dropped.
+    pub fn emit_event(&mut self, event: Event) {
+        if let Some(events) = self.events.as_mut() {
+            events.push(event);
+        }
+    }
+
+    /// Canonical hash of the transaction being executed, for modules that
+    /// index their own records of it; `None` outside `dispatch_scoped`.
+    pub fn tx_hash(&self) -> Option<[u8; 32]> {
+        self.tx_hash
+    }
+
+    /// Timestamp of the last block applied, or `None` if no block has
+    /// recorded one (see `set_last_block_timestamp`).
+    pub fn last_block_timestamp(&self) -> Option<u64> {
+        self.get_raw(KEY_LAST_BLOCK_TIMESTAMP)
+            .and_then(|bytes| codec::decode_state(&bytes).ok())
+    }
+
+    /// Record the timestamp the next block must exceed; genesis init records
+    /// the tip's, and block execution each block's.
+    pub(crate) fn set_last_block_timestamp(&mut self, timestamp: u64) -> Result<()> {
+        self.put_raw(
+            KEY_LAST_BLOCK_TIMESTAMP.to_vec(),
+            codec::encode(&timestamp)?,
+        )
+    }
+
+    /// Events emitted by the successful transactions of the block being
+    /// executed, in order, for end-of-block hooks to react to.
+    pub fn block_events(&self) -> &[Event] {
+        &self.block_events
+    }
+
+    /// Open a write scope.
+    ///
+    /// Writes made until the matching `commit` or `rollback` are buffered and
+    /// visible to reads through this State only. Scopes nest.
+    pub fn begin(&mut self) {
+        self.overlays.push(HashMap::new());
+    }
+
+    /// Close the innermost write scope, keeping its writes.
+    ///
+    /// Writes are folded into the enclosing scope, or written to the backend
+    /// in one atomic batch when this was the outermost scope, so a crash
+    /// never leaves part of a scope stored.
+    pub fn commit(&mut self) -> Result<()> {
+        let layer = match self.overlays.pop() {
+            Some(layer) => layer,
+            None => return Ok(()),
+        };
+        match self.overlays.last_mut() {
+            Some(parent) => parent.extend(layer),
+            None => self.backend.write_batch(layer.into_iter().collect())?,
+        }
+        Ok(())
+    }
+
+    /// Close the innermost write scope, discarding its writes.
+    pub fn rollback(&mut self) {
+        self.overlays.pop();
+    }
+
+    /// Run `f` in its own write scope, keeping its writes and events only if
+    /// it succeeds.
+    pub fn atomically<R>(
+        &mut self,
+        f: impl FnOnce(&mut State) -> Result<R, String>,
+    ) -> Result<R, String> {
+        let events = self.events.as_ref().map(Vec::len);
+        self.begin();
+        match f(self) {
+            Ok(value) => {
+                self.commit().map_err(|e| e.to_string())?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.rollback();
+                if let (Some(events), Some(len)) = (self.events.as_mut(), events) {
+                    events.truncate(len);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Detach the backend and open scopes so parallel workers can read
+    /// through them (see `Frozen::worker`). Until `thaw` reattaches them this
+    /// State reads as empty.
+    pub(crate) fn freeze(&mut self) -> Frozen {
+        let backend = std::mem::replace(&mut self.backend, Box::new(InMemoryBackend::new()));
+        let overlays = std::mem::take(&mut self.overlays);
+        Frozen(Arc::new(FrozenView { backend, overlays }))
+    }
+
+    /// Reattach what `freeze` detached.
+    ///
+    /// # Panics
+    /// If a worker made from `frozen` is still alive.
+    pub(crate) fn thaw(&mut self, frozen: Frozen) {
+        let view = Arc::try_unwrap(frozen.0)
+            .ok()
+            .expect("parallel workers outlived their batch");
+        self.backend = view.backend;
+        self.overlays = view.overlays;
+    }
+
+    /// The writes buffered in a worker's scope (see `Frozen::worker`).
+    pub(crate) fn into_writes(mut self) -> Writes {
+        self.overlays.pop().unwrap_or_default()
+    }
+
+    /// Apply writes taken from another State, as if made here.
+    pub(crate) fn apply_writes(&mut self, writes: Writes) -> Result<()> {
+        for (key, value) in writes {
+            match value {
+                Some(value) => self.put_raw(key, value)?,
+                None => self.delete_raw(&key)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Close every open write scope, discarding their writes.
+    ///
+    /// Used to recover after a panic interrupted execution mid-scope.
+    pub fn rollback_all(&mut self) {
+        self.overlays.clear();
+        self.write_namespace = None;
+        self.events = None;
     }
 
     /// Execute a block, applying all transactions.
     ///
     /// This function:
-    /// 1. Verifies Forge PoW
-    /// 2. Dispatches each transaction to the appropriate runtime module
+    /// 1. Checks the header's difficulty target against the chain's configured
+    ///    target, its `tx_root` (when set) against the body, and its timestamp
+    ///    against the last block's, which it must exceed
+    /// 2. Verifies Forge PoW
+    /// 3. Rejects transactions whose `valid_until_height` is below the block height
+    /// 4. Dispatches each transaction to the appropriate runtime module and
+    ///    stores its receipt
+    /// 5. Runs each module's end-of-block hook, which can read the block's events
+    /// 6. Checks the header's `state_root` against the module state the block
+    ///    leaves (`Runtime::state_root`)
     ///
-    /// For now, parent hash and state_root consistency are not enforced;
-    /// they will be introduced once block storage and chain selection are added.
-    pub fn execute_block(&mut self, block: &Block) -> Result<(), String> {
+    /// A transaction whose call fails has its writes discarded and gets a
+    /// failed receipt; the rest of the block still applies. The block as a
+    /// whole is atomic: if it is invalid (e.g. includes an expired
+    /// transaction) or a hook fails, none of its writes are applied.
+    ///
+    /// Headers from before state roots were committed carry a zero
+    /// `state_root`, which is not checked until a block has carried one.
+    /// Parent hashes are checked by the node, which knows the chain tip.
+    pub fn execute_block(&mut self, block: &Block, chain: &ChainConfig) -> Result<(), String> {
+        self.execute_block_with(block, chain, &Runtime::for_chain(chain))
+    }
+
+    /// Execute a block against the given runtime's modules.
+    ///
+    /// Same as `execute_block`, for nodes that register additional modules.
+    pub fn execute_block_with(
+        &mut self,
+        block: &Block,
+        chain: &ChainConfig,
+        runtime: &Runtime,
+    ) -> Result<(), String> {
+        let _span = tracing::info_span!(
+            "execute_block",
+            height = block.header.height,
+            txs = block.body.len()
+        )
+        .entered();
+        self.verify_and_apply(block, chain, runtime)
+            .inspect_err(|e| tracing::warn!(error = %e, "block execution failed"))
+    }
+
+    fn verify_and_apply(
+        &mut self,
+        block: &Block,
+        chain: &ChainConfig,
+        runtime: &Runtime,
+    ) -> Result<(), String> {
+        // A block may not claim an easier target than the chain requires
+        if block.header.difficulty_target > chain.genesis.difficulty_target {
+            return Err("block difficulty target is easier than the chain target".into());
+        }
+
+        // The header must commit to exactly this body. Headers from before
+        // the transaction root have none, but once a block has carried one,
+        // a later block without one is refused rather than left unchecked
+        let tx_root_from: Option<u64> = self
+            .get_raw(KEY_TX_ROOT_FROM)
+            .and_then(|bytes| codec::decode_state(&bytes).ok());
+        match (block.header.tx_root, tx_root_from) {
+            (Some(root), _) if root != tx_root(&block.body) => {
+                return Err("block tx_root does not match its transactions".into());
+            }
+            (None, Some(from)) => {
+                return Err(format!(
+                    "block {} has no tx_root, which every block since {} must carry",
+                    block.header.height, from
+                ));
+            }
+            _ => {}
+        }
+
+        // Each block must be timestamped after its parent
+        if let Some(parent) = self.last_block_timestamp() {
+            if block.header.timestamp <= parent {
+                return Err(format!(
+                    "block {} timestamp {} is not after its parent's ({})",
+                    block.header.height, block.header.timestamp, parent
+                ));
+            }
+        }
+
         // Verify Forge PoW
-        let config = ForgeConfig::default();
         let header_bytes = block.header.serialize_without_nonce();
-        let hash = forge_hash(&header_bytes, block.header.nonce, &config);
+        let hash = forge_hash(&header_bytes, block.header.nonce, &chain.genesis.forge);
 
         if !meets_difficulty(&hash, block.header.difficulty_target) {
             return Err("Forge PoW verification failed".into());
         }
 
-        // Create runtime with all default modules
-        let mut runtime = Runtime::with_default_modules();
-
         // Dispatch each transaction to the appropriate module
-        for tx in &block.body {
-            runtime.dispatch_tx(tx, self)?;
+        self.set_block_height(block.header.height);
+        self.begin();
+        if let Err(e) = Self::apply_transactions(runtime, block, self, chain.execution.parallel)
+            .and_then(|()| runtime.end_block(self))
+            .and_then(|()| {
+                self.set_last_block_timestamp(block.header.timestamp)
+                    .map_err(|e| e.to_string())
+            })
+            .and_then(|()| match (tx_root_from, block.header.tx_root) {
+                (None, Some(_)) => self
+                    .put_raw(
+                        KEY_TX_ROOT_FROM.to_vec(),
+                        codec::encode(&block.header.height).map_err(|e| e.to_string())?,
+                    )
+                    .map_err(|e| e.to_string()),
+                _ => Ok(()),
+            })
+            .and_then(|()| self.check_state_root(block, runtime))
+        {
+            self.rollback();
+            return Err(e);
         }
+        self.commit().map_err(|e| e.to_string())?;
+        Ok(())
+    }
 
-        // TODO: calculate and persist new state_root in header.
+    /// Check the header's state root against module state after the block.
+    ///
+    /// Like `tx_root`, headers from before the state root carry none (zero),
+    /// but once a block has carried one, a later block without one is
+    /// refused rather than left unchecked.
+    fn check_state_root(&mut self, block: &Block, runtime: &Runtime) -> Result<(), String> {
+        let state_root_from: Option<u64> = self
+            .get_raw(KEY_STATE_ROOT_FROM)
+            .and_then(|bytes| codec::decode_state(&bytes).ok());
+        if block.header.state_root == [0; 32] {
+            return match state_root_from {
+                Some(from) => Err(format!(
+                    "block {} has no state_root, which every block since {} must carry",
+                    block.header.height, from
+                )),
+                None => Ok(()),
+            };
+        }
+        if runtime.state_root(self) != block.header.state_root {
+            return Err("block state_root does not match the state it leaves".into());
+        }
+        if state_root_from.is_none() {
+            self.put_raw(
+                KEY_STATE_ROOT_FROM.to_vec(),
+                codec::encode(&block.header.height).map_err(|e| e.to_string())?,
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Module state root `block` would leave, for its header's `state_root`.
+    ///
+    /// Applies the block's transactions and end-of-block hooks in a scope
+    /// that is rolled back, so nothing is written; the header's own roots
+    /// and PoW are not checked.
+    pub fn state_root_after(
+        &mut self,
+        block: &Block,
+        chain: &ChainConfig,
+        runtime: &Runtime,
+    ) -> Result<[u8; 32], String> {
+        self.set_block_height(block.header.height);
+        self.begin();
+        let root = Self::apply_transactions(runtime, block, self, chain.execution.parallel)
+            .and_then(|()| runtime.end_block(self))
+            .map(|()| runtime.state_root(self));
+        self.rollback();
+        root
+    }
 
+    fn apply_transactions(
+        runtime: &Runtime,
+        block: &Block,
+        state: &mut State,
+        parallel: bool,
+    ) -> Result<(), String> {
+        state.block_events.clear();
+        if parallel {
+            return parallel::apply_transactions(runtime, block, state);
+        }
+        for tx in &block.body {
+            check_not_expired(tx, block.header.height)?;
+            let (error, events) = state.dispatch_scoped(runtime, tx)?;
+            state.finish_tx(tx, block.header.height, error, events)?;
+        }
         Ok(())
     }
+
+    /// Dispatch a transaction in its own scope, so a failure discards only
+    /// its writes.
+    ///
+    /// # Returns
+    /// The call's error, if it failed, and the events it emitted (none when
+    /// it failed).
+    pub(crate) fn dispatch_scoped(
+        &mut self,
+        runtime: &Runtime,
+        tx: &Transaction,
+    ) -> Result<(Option<String>, Vec<Event>), String> {
+        self.begin();
+        self.events = Some(Vec::new());
+        self.tx_hash = Some(tx.hash());
+        let result = runtime.dispatch_tx(tx, self);
+        self.tx_hash = None;
+        let events = self.events.take().unwrap_or_default();
+        match result {
+            Ok(()) => {
+                self.commit().map_err(|e| e.to_string())?;
+                Ok((None, events))
+            }
+            Err(e) => {
+                self.rollback();
+                Ok((Some(e), Vec::new()))
+            }
+        }
+    }
+
+    /// Record a dispatched transaction: pass its events to end-of-block
+    /// hooks, index it, count it in address stats, and store its receipt.
+    pub(crate) fn finish_tx(
+        &mut self,
+        tx: &Transaction,
+        height: u64,
+        error: Option<String>,
+        events: Vec<Event>,
+    ) -> Result<(), String> {
+        if error.is_none() {
+            self.block_events.extend(events.iter().cloned());
+        }
+        let tx_hash = tx.hash();
+        index_transaction(self, tx, &tx_hash, &events)?;
+        record_activity(self, tx, height, &events)?;
+        put_receipt(
+            self,
+            &Receipt {
+                tx_hash,
+                height,
+                success: error.is_none(),
+                error,
+                events,
+            },
+        )
+    }
 }
 
 impl Default for State {
@@ -160,6 +1101,76 @@ impl Default for State {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::receipt::get_receipt;
+    use crate::core::transaction::test_keys::{account, signed};
+
+    #[test]
+    fn test_cached_backend_serves_reads_and_drops_stale_entries() {
+        let mut backend = InMemoryBackend::new();
+        backend.put_raw(b"k".to_vec(), vec![1]).unwrap();
+        let mut cached = CachedBackend::new(backend, NonZeroUsize::new(2).unwrap());
+
+        assert_eq!(cached.get_raw(b"k"), cached.inner.get_raw(b"k"));
+        assert_eq!(cached.cache().peek(b"k".as_slice()), Some(&Some(vec![1])));
+        // Misses are cached too
+        assert_eq!(cached.get_raw(b"missing"), None);
+        assert_eq!(cached.cache().peek(b"missing".as_slice()), Some(&None));
+
+        cached.put_raw(b"k".to_vec(), vec![2]).unwrap();
+        assert_eq!(cached.cache().peek(b"k".as_slice()), None);
+        assert_eq!(cached.get_raw(b"k"), Some(vec![2]));
+        cached.put_raw(b"missing".to_vec(), vec![3]).unwrap();
+        assert_eq!(cached.get_raw(b"missing"), Some(vec![3]));
+        cached.delete_raw(b"k").unwrap();
+        assert_eq!(cached.get_raw(b"k"), None);
+
+        // Least recently read keys are evicted
+        cached.get_raw(b"a");
+        assert_eq!(cached.cache().len(), 2);
+        assert!(!cached.cache().contains(b"missing".as_slice()));
+    }
+
+    #[test]
+    fn test_scan_prefix_sees_scoped_writes() {
+        let mut state = State::in_memory();
+        state.put_raw(b"a/1".to_vec(), vec![1]).unwrap();
+        state.put_raw(b"a/2".to_vec(), vec![2]).unwrap();
+        state.put_raw(b"b/1".to_vec(), vec![3]).unwrap();
+
+        state.begin();
+        state.delete_raw(b"a/1").unwrap();
+        state.put_raw(b"a/3".to_vec(), vec![4]).unwrap();
+        assert_eq!(
+            state.scan_prefix(b"a/"),
+            vec![(b"a/2".to_vec(), vec![2]), (b"a/3".to_vec(), vec![4])]
+        );
+        state.rollback();
+
+        assert_eq!(state.scan_prefix(b"a/").len(), 2);
+    }
+
+    #[test]
+    fn test_namespace_confines_writes() {
+        let mut state = State::in_memory();
+        state.with_namespace("bank_cgt", |state| {
+            assert!(state
+                .put_raw(b"bank_cgt/balance/x".to_vec(), vec![1])
+                .is_ok());
+            assert!(state
+                .put_raw(b"nft_dgen/token/x".to_vec(), vec![1])
+                .is_err());
+            assert!(state.delete_raw(b"nft_dgen/token/x").is_err());
+
+            // Nested scopes restore the outer namespace on exit
+            state.with_namespace("nft_dgen", |state| {
+                assert!(state.put_raw(b"nft_dgen/token/x".to_vec(), vec![1]).is_ok());
+            });
+            assert!(state
+                .put_raw(b"nft_dgen/token/y".to_vec(), vec![1])
+                .is_err());
+        });
+        assert!(state.put_raw(b"chain:block:x".to_vec(), vec![1]).is_ok());
+    }
 
     #[test]
     fn test_in_memory_state_put_get() {
@@ -194,6 +1205,158 @@ mod tests {
         assert_eq!(state.get_raw(b"key"), Some(b"value2".to_vec()));
     }
 
+    #[test]
+    fn test_nested_write_scopes() {
+        let mut state = State::in_memory();
+        state.put_raw(b"a".to_vec(), b"1".to_vec()).unwrap();
+
+        state.begin();
+        state.put_raw(b"a".to_vec(), b"2".to_vec()).unwrap();
+        state.begin();
+        state.put_raw(b"b".to_vec(), b"3".to_vec()).unwrap();
+        assert_eq!(state.get_raw(b"b"), Some(b"3".to_vec()));
+
+        // Inner scope discarded, outer scope kept
+        state.rollback();
+        assert_eq!(state.get_raw(b"b"), None);
+        assert_eq!(state.get_raw(b"a"), Some(b"2".to_vec()));
+        state.commit().unwrap();
+
+        assert_eq!(state.get_raw(b"a"), Some(b"2".to_vec()));
+        assert_eq!(state.get_raw(b"b"), None);
+    }
+
+    #[test]
+    fn test_atomically_keeps_writes_and_events_only_on_success() {
+        let mut state = State::in_memory();
+        state.events = Some(Vec::new());
+
+        state
+            .atomically(|state| {
+                state.put_raw(b"a".to_vec(), b"1".to_vec()).unwrap();
+                state.emit_event(Event::new("test", "kept", [1; 32]));
+                Ok(())
+            })
+            .unwrap();
+        let err = state
+            .atomically::<()>(|state| {
+                state.put_raw(b"b".to_vec(), b"2".to_vec()).unwrap();
+                state.emit_event(Event::new("test", "dropped", [1; 32]));
+                Err("failed".into())
+            })
+            .unwrap_err();
+        assert_eq!(err, "failed");
+
+        assert_eq!(state.get_raw(b"a"), Some(b"1".to_vec()));
+        assert_eq!(state.get_raw(b"b"), None);
+        let events = state.events.take().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, "kept");
+    }
+
+    #[test]
+    fn test_delete_raw_direct_and_scoped() {
+        let mut state = State::in_memory();
+        state.put_raw(b"a".to_vec(), b"1".to_vec()).unwrap();
+        state.put_raw(b"b".to_vec(), b"2".to_vec()).unwrap();
+
+        state.delete_raw(b"a").unwrap();
+        assert_eq!(state.get_raw(b"a"), None);
+        // Deleting a missing key is fine
+        state.delete_raw(b"missing").unwrap();
+
+        // A scoped delete hides the key until rolled back...
+        state.begin();
+        state.delete_raw(b"b").unwrap();
+        assert_eq!(state.get_raw(b"b"), None);
+        state.rollback();
+        assert_eq!(state.get_raw(b"b"), Some(b"2".to_vec()));
+
+        // ...and reaches the backend when committed
+        state.begin();
+        state.delete_raw(b"b").unwrap();
+        state.commit().unwrap();
+        assert_eq!(state.get_raw(b"b"), None);
+    }
+
+    /// Backend that logs how writes reach it.
+    #[derive(Default)]
+    struct RecordingBackend {
+        inner: InMemoryBackend,
+        log: std::sync::Arc<Mutex<Vec<String>>>,
+    }
+
+    impl KvBackend for RecordingBackend {
+        fn get_raw(&self, key: &[u8]) -> Option<Vec<u8>> {
+            self.inner.get_raw(key)
+        }
+
+        fn put_raw(&mut self, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+            self.log.lock().unwrap().push("put".to_string());
+            self.inner.put_raw(key, value)
+        }
+
+        fn delete_raw(&mut self, key: &[u8]) -> Result<()> {
+            self.log.lock().unwrap().push("delete".to_string());
+            self.inner.delete_raw(key)
+        }
+
+        fn scan_prefix(&self, prefix: &[u8]) -> Vec<(Vec<u8>, Vec<u8>)> {
+            self.inner.scan_prefix(prefix)
+        }
+
+        fn write_batch(&mut self, writes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<()> {
+            let entry = format!("batch of {}", writes.len());
+            self.log.lock().unwrap().push(entry);
+            self.inner.write_batch(writes)
+        }
+    }
+
+    #[test]
+    fn test_rocksdb_opens_with_custom_tuning() {
+        let dir = tempfile::tempdir().unwrap();
+        let rocks = RocksConfig {
+            write_buffer_size_mib: 4,
+            max_open_files: 64,
+            block_cache_size_mib: 8,
+            compression: RocksCompression::Zstd,
+            bloom_filter_bits_per_key: 16,
+            max_total_wal_size_mib: 32,
+        };
+        let mut state = State::open_rocksdb_cached(dir.path(), 0, &rocks).unwrap();
+        state.put_raw(b"k".to_vec(), vec![1, 2, 3]).unwrap();
+        assert_eq!(state.get_raw(b"k"), Some(vec![1, 2, 3]));
+        drop(state);
+
+        // Tuning only affects performance, so a reopen with other settings
+        // reads the same data
+        let state = State::open_rocksdb(dir.path()).unwrap();
+        assert_eq!(state.get_raw(b"k"), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_outermost_commit_is_one_batch() {
+        let mut backend = RecordingBackend::default();
+        backend.inner.put_raw(b"stale".to_vec(), vec![0]).unwrap();
+        let log = backend.log.clone();
+        let mut state = State::in_memory();
+        state.backend = Box::new(backend);
+
+        state.begin();
+        state.put_raw(b"block".to_vec(), vec![1]).unwrap();
+        state.begin();
+        state.put_raw(b"tip".to_vec(), vec![2]).unwrap();
+        state.delete_raw(b"stale").unwrap();
+        state.commit().unwrap();
+        state.commit().unwrap();
+
+        assert_eq!(state.get_raw(b"block"), Some(vec![1]));
+        assert_eq!(state.get_raw(b"tip"), Some(vec![2]));
+        assert_eq!(state.get_raw(b"stale"), None);
+        // Nested scopes fold into one write of all three keys
+        assert_eq!(*log.lock().unwrap(), vec!["batch of 3"]);
+    }
+
     #[test]
     fn test_execute_block_with_easy_difficulty() {
         let mut state = State::in_memory();
@@ -205,11 +1368,391 @@ mod tests {
                 timestamp: 0,
                 difficulty_target: u128::MAX, // Easy difficulty - always passes
                 nonce: 0,
+                tx_root: None,
             },
             body: vec![],
         };
 
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+
         // Should succeed with easy difficulty
-        assert!(state.execute_block(&block).is_ok());
+        assert!(state.execute_block(&block, &chain).is_ok());
+    }
+
+    fn expiring_block(height: u64, valid_until_height: u64) -> Block {
+        // Unknown module: only reached if the expiry check lets the tx through
+        let tx = crate::core::transaction::Transaction {
+            from: [1; 32],
+            nonce: 0,
+            module_id: "noop".to_string(),
+            call_id: "noop".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: Some(valid_until_height),
+            sig_scheme: 0,
+        };
+        Block {
+            header: crate::core::block::BlockHeader {
+                height,
+                prev_hash: [0; 32],
+                state_root: [0; 32],
+                timestamp: height,
+                difficulty_target: u128::MAX,
+                nonce: 0,
+                tx_root: None,
+            },
+            body: vec![tx],
+        }
+    }
+
+    #[test]
+    fn test_execute_block_transaction_expiry() {
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+
+        // Included at its last valid height: passes the expiry check and
+        // reaches dispatch (which fails on the unknown module)
+        let block = expiring_block(5, 5);
+        state.execute_block(&block, &chain).unwrap();
+        let receipt = get_receipt(&state, &block.body[0].hash()).unwrap();
+        assert!(receipt.error.unwrap().contains("Unknown module"));
+
+        // One block later the transaction has expired
+        let err = state
+            .execute_block(&expiring_block(6, 5), &chain)
+            .unwrap_err();
+        assert!(err.contains("transaction expired"));
+    }
+
+    #[test]
+    fn test_execute_block_requires_later_timestamp() {
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+        state.set_last_block_timestamp(5).unwrap();
+
+        let block = expiring_block(5, 5);
+        let err = state.execute_block(&block, &chain).unwrap_err();
+        assert_eq!(err, "block 5 timestamp 5 is not after its parent's (5)");
+        assert!(get_receipt(&state, &block.body[0].hash()).is_none());
+
+        state.execute_block(&expiring_block(6, 6), &chain).unwrap();
+        assert_eq!(state.last_block_timestamp(), Some(6));
+    }
+
+    #[test]
+    fn test_execute_block_checks_tx_root() {
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+
+        let mut block = expiring_block(5, 5);
+        block.header.tx_root = Some(tx_root(&[]));
+        let err = state.execute_block(&block, &chain).unwrap_err();
+        assert!(err.contains("tx_root does not match"));
+        assert!(get_receipt(&state, &block.body[0].hash()).is_none());
+
+        block.header.tx_root = Some(tx_root(&block.body));
+        state.execute_block(&block, &chain).unwrap();
+
+        // Once a block has carried a root, later ones must too
+        let mut unrooted = expiring_block(6, 6);
+        let err = state.execute_block(&unrooted, &chain).unwrap_err();
+        assert_eq!(
+            err,
+            "block 6 has no tx_root, which every block since 5 must carry"
+        );
+        unrooted.header.tx_root = Some(tx_root(&unrooted.body));
+        state.execute_block(&unrooted, &chain).unwrap();
+    }
+
+    #[test]
+    fn test_execute_block_checks_state_root() {
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+        let runtime = Runtime::for_chain(&chain);
+        // An empty state's root is zero, the root of no commitment
+        crate::runtime::bank_cgt::mint(&mut state, &[1; 32], 100).unwrap();
+
+        let mut block = expiring_block(5, 5);
+        block.header.state_root = [1; 32];
+        let err = state.execute_block(&block, &chain).unwrap_err();
+        assert_eq!(err, "block state_root does not match the state it leaves");
+        assert!(get_receipt(&state, &block.body[0].hash()).is_none());
+
+        block.header.state_root = state.state_root_after(&block, &chain, &runtime).unwrap();
+        assert!(get_receipt(&state, &block.body[0].hash()).is_none());
+        state.execute_block(&block, &chain).unwrap();
+        assert_eq!(runtime.state_root(&state), block.header.state_root);
+
+        // Once a block has carried a root, later ones must too
+        let mut unrooted = expiring_block(6, 6);
+        let err = state.execute_block(&unrooted, &chain).unwrap_err();
+        assert_eq!(
+            err,
+            "block 6 has no state_root, which every block since 5 must carry"
+        );
+        unrooted.header.state_root = state.state_root_after(&unrooted, &chain, &runtime).unwrap();
+        state.execute_block(&unrooted, &chain).unwrap();
+    }
+
+    #[test]
+    fn test_failed_block_leaves_no_writes() {
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+
+        // A successful mint followed by an expired transaction
+        let mint = crate::core::transaction::Transaction {
+            from: [0; 32],
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "mint_to".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::MintToParams {
+                to: [9; 32],
+                amount: 100,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let mut block = expiring_block(2, 1);
+        block.body.insert(0, mint.clone());
+
+        assert!(state.execute_block(&block, &chain).is_err());
+        assert_eq!(crate::runtime::get_balance_cgt(&state, &[9; 32]), 0);
+        assert_eq!(get_receipt(&state, &mint.hash()), None);
+    }
+
+    #[test]
+    fn test_receipts_record_success_and_failure() {
+        use crate::runtime::bank_cgt::{MintToParams, TransferParams};
+
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+        chain.genesis.mint_authorities = vec![account(0)];
+
+        let call = |from, call_id: &str, payload| {
+            signed(crate::core::transaction::Transaction {
+                from,
+                nonce: 0,
+                module_id: "bank_cgt".to_string(),
+                call_id: call_id.to_string(),
+                payload,
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            })
+        };
+        let mint = call(
+            account(0),
+            "mint_to",
+            codec::encode(&MintToParams {
+                to: account(9),
+                amount: 100,
+            })
+            .unwrap(),
+        );
+        // Overdraws, so it fails after the mint
+        let transfer = call(
+            account(9),
+            "transfer",
+            codec::encode(&TransferParams {
+                to: [8; 32],
+                amount: 500,
+                memo: None,
+            })
+            .unwrap(),
+        );
+        let mut block = expiring_block(3, 3);
+        block.body = vec![mint.clone(), transfer.clone()];
+
+        // The failed transfer does not fail the block
+        state.execute_block(&block, &chain).unwrap();
+        assert_eq!(crate::runtime::get_balance_cgt(&state, &account(9)), 100);
+
+        let receipt = get_receipt(&state, &mint.hash()).unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.height, 3);
+        assert_eq!(receipt.error, None);
+        assert_eq!(receipt.events[0].kind, "mint");
+        assert_eq!(receipt.events[0].address, account(9));
+
+        let receipt = get_receipt(&state, &transfer.hash()).unwrap();
+        assert!(!receipt.success);
+        assert!(receipt.error.unwrap().contains("insufficient balance"));
+        assert!(receipt.events.is_empty());
+
+        // Included again later, the mint keeps its first successful receipt,
+        // while the failed transfer's receipt moves to the later block
+        let mut again = expiring_block(4, 4);
+        again.body = vec![mint.clone(), transfer.clone()];
+        state.execute_block(&again, &chain).unwrap();
+        assert_eq!(get_receipt(&state, &mint.hash()).unwrap().height, 3);
+        assert_eq!(get_receipt(&state, &transfer.hash()).unwrap().height, 4);
+    }
+
+    #[test]
+    fn test_execute_block_rejects_easier_target() {
+        let mut state = State::in_memory();
+        let block = Block {
+            header: crate::core::block::BlockHeader {
+                height: 0,
+                prev_hash: [0; 32],
+                state_root: [0; 32],
+                timestamp: 0,
+                difficulty_target: u128::MAX,
+                nonce: 0,
+                tx_root: None,
+            },
+            body: vec![],
+        };
+
+        // Chain requires a harder target than the block claims
+        let chain = ChainConfig::default();
+        let result = state.execute_block(&block, &chain);
+        assert!(result.unwrap_err().contains("easier than the chain target"));
+    }
+
+    /// Records every span and event as its `/`-joined span path plus fields.
+    #[derive(Clone, Default)]
+    struct SpanRecorder(std::sync::Arc<std::sync::Mutex<Vec<(String, String)>>>);
+
+    struct FieldRecorder(String);
+
+    impl tracing::field::Visit for FieldRecorder {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0 += &format!("{}={:?} ", field.name(), value);
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanRecorder
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            id: &tracing::span::Id,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let path: Vec<_> = ctx
+                .span_scope(id)
+                .unwrap()
+                .from_root()
+                .map(|span| span.name())
+                .collect();
+            let mut fields = FieldRecorder(String::new());
+            attrs.record(&mut fields);
+            self.0.lock().unwrap().push((path.join("/"), fields.0));
+        }
+
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            let mut path: Vec<_> = ctx
+                .event_scope(event)
+                .map(|scope| scope.from_root().map(|span| span.name()).collect())
+                .unwrap_or_default();
+            path.push("event");
+            let mut fields = FieldRecorder(String::new());
+            event.record(&mut fields);
+            self.0.lock().unwrap().push((path.join("/"), fields.0));
+        }
+    }
+
+    #[test]
+    fn test_dispatch_span_hierarchy() {
+        use crate::core::transaction::AddressEncoding;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let mut state = State::in_memory();
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+        // Overdraws an empty account, so the dispatch fails
+        let tx = signed(crate::core::transaction::Transaction {
+            from: account(9),
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                to: [8; 32],
+                amount: 500,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+        let mut block = expiring_block(4, 4);
+        block.body = vec![tx.clone()];
+
+        let recorder = SpanRecorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            state.execute_block(&block, &chain).unwrap();
+        });
+
+        let records = recorder.0.lock().unwrap().clone();
+        let paths: Vec<_> = records.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "execute_block",
+                "execute_block/dispatch_tx",
+                "execute_block/dispatch_tx/module_dispatch",
+                "execute_block/dispatch_tx/event",
+            ]
+        );
+        assert_eq!(records[0].1, "height=4 txs=1 ");
+        assert_eq!(
+            records[1].1,
+            format!(
+                "tx_hash={} module_id=bank_cgt call_id=transfer from={} ",
+                hex::encode(tx.hash()),
+                account(9).to_display()
+            )
+        );
+        assert_eq!(records[2].1, "module_id=bank_cgt call_id=transfer ");
+        assert!(records[3].1.contains("error=insufficient balance"));
+    }
+
+    #[test]
+    fn test_snapshot_is_unchanged_by_later_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = State::open_rocksdb(dir.path()).unwrap();
+        state.put_raw(b"a".to_vec(), b"1".to_vec()).unwrap();
+        state.begin();
+        state.put_raw(b"b".to_vec(), b"2".to_vec()).unwrap();
+        let mut snapshot = state.snapshot().unwrap();
+        state.put_raw(b"a".to_vec(), b"3".to_vec()).unwrap();
+        state.commit().unwrap();
+        state.delete_raw(b"b").unwrap();
+
+        assert_eq!(snapshot.get_raw(b"a"), Some(b"1".to_vec()));
+        assert_eq!(
+            snapshot.scan_prefix(b""),
+            vec![
+                (b"a".to_vec(), b"1".to_vec()),
+                (b"b".to_vec(), b"2".to_vec())
+            ]
+        );
+        assert!(snapshot.put_raw(b"c".to_vec(), Vec::new()).is_err());
+        assert_eq!(state.get_raw(b"a"), Some(b"3".to_vec()));
+
+        // Copying a whole in-memory map is not cheap
+        assert!(State::in_memory().snapshot().is_none());
     }
 }
@@ -4,18 +4,82 @@
 //! on the chain. Transactions are routed to runtime modules via `module_id` and
 //! `call_id`, with parameters encoded in the `payload` field.
 
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, Hrp};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
+use crate::core::codec;
+
 /// Address type: a 32-byte public key identifier.
 pub type Address = [u8; 32];
 
+/// Human-readable prefix of display-encoded addresses.
+pub const ADDRESS_HRP: &str = "dmrg";
+
+/// Canonical display encoding for addresses.
+///
+/// Addresses are displayed as bech32m with the `dmrg` prefix
+/// (`dmrg1...`), whose checksum catches mistyped characters that raw hex
+/// would silently accept. Parsing also accepts raw 64-character hex during
+/// the deprecation window.
+pub trait AddressEncoding: Sized {
+    /// Encode as a checksummed `dmrg1...` string.
+    fn to_display(&self) -> String;
+
+    /// Parse a checksummed `dmrg1...` string or raw hex.
+    fn parse(s: &str) -> Result<Self, String>;
+}
+
+impl AddressEncoding for Address {
+    fn to_display(&self) -> String {
+        bech32::encode::<Bech32m>(Hrp::parse_unchecked(ADDRESS_HRP), self)
+            .expect("32-byte address always fits in bech32m")
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        let bytes = if s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            hex::decode(s).map_err(|e| format!("invalid address hex: {}", e))?
+        } else {
+            let checked = CheckedHrpstring::new::<Bech32m>(s)
+                .map_err(|e| format!("invalid address: {}", e))?;
+            if checked.hrp().to_lowercase() != ADDRESS_HRP {
+                return Err(format!(
+                    "invalid address prefix '{}', expected '{}'",
+                    checked.hrp(),
+                    ADDRESS_HRP
+                ));
+            }
+            checked.byte_iter().collect()
+        };
+        bytes
+            .try_into()
+            .map_err(|_| "address must be 32 bytes".to_string())
+    }
+}
+
 /// Signature type: a 64-byte Ed25519-style signature.
 ///
 /// Using Vec<u8> instead of [u8; 64] for serde compatibility.
 /// In production, consider using serde_bytes for fixed-size arrays.
 pub type Signature = Vec<u8>;
 
+/// `Transaction::sig_scheme` for Ed25519 signatures by `from`.
+pub const SIG_SCHEME_ED25519: u8 = 0;
+
+/// `Transaction::sig_scheme` for a multisig account's signers: `signature`
+/// is a bincode-encoded `Vec<MultisigSignature>`.
+pub const SIG_SCHEME_MULTISIG: u8 = 1;
+
+/// One signer's Ed25519 signature on a multisig transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MultisigSignature {
+    /// Position of the signer's key in the account's registered keys.
+    pub signer_index: u8,
+    pub signature: Signature,
+}
+
 /// A transaction that can be executed on the chain.
 ///
 /// Transactions are routed to runtime modules based on `module_id` (e.g., "bank_cgt")
@@ -39,8 +103,102 @@ pub struct Transaction {
     pub fee: u64,
     /// Transaction signature (placeholder for Phase 3 signature verification).
     pub signature: Signature,
+    /// Last block height this transaction may be included at (`None` = never expires).
+    pub valid_until_height: Option<u64>,
+    /// Scheme `signature` is made with (`SIG_SCHEME_ED25519` is the only one
+    /// so far). Signed, so a signature cannot be reinterpreted under another
+    /// scheme.
+    pub sig_scheme: u8,
+}
+
+/// Wire version of the original transaction layout (no expiry).
+pub const TX_VERSION_V1: u8 = 1;
+
+/// Wire version that added `valid_until_height`.
+pub const TX_VERSION_V2: u8 = 2;
+
+/// Wire version that added `sig_scheme`.
+pub const TX_VERSION_V3: u8 = 3;
+
+/// Version emitted by `Transaction::to_bytes`.
+pub const TX_VERSION_LATEST: u8 = TX_VERSION_V3;
+
+/// V1 wire layout: the original transaction fields.
+///
+/// Decodes upgrade to a `Transaction` that never expires.
+#[derive(Serialize, Deserialize)]
+struct TransactionV1 {
+    from: Address,
+    nonce: u64,
+    module_id: String,
+    call_id: String,
+    payload: Vec<u8>,
+    fee: u64,
+    signature: Signature,
+}
+
+impl From<TransactionV1> for Transaction {
+    fn from(v1: TransactionV1) -> Self {
+        Transaction {
+            from: v1.from,
+            nonce: v1.nonce,
+            module_id: v1.module_id,
+            call_id: v1.call_id,
+            payload: v1.payload,
+            fee: v1.fee,
+            signature: v1.signature,
+            valid_until_height: None,
+            sig_scheme: SIG_SCHEME_ED25519,
+        }
+    }
+}
+
+/// V2 wire layout: V1 plus `valid_until_height`.
+///
+/// Decodes upgrade to an Ed25519-signed `Transaction`.
+#[derive(Serialize, Deserialize)]
+struct TransactionV2 {
+    from: Address,
+    nonce: u64,
+    module_id: String,
+    call_id: String,
+    payload: Vec<u8>,
+    fee: u64,
+    signature: Signature,
+    valid_until_height: Option<u64>,
 }
 
+impl From<TransactionV2> for Transaction {
+    fn from(v2: TransactionV2) -> Self {
+        Transaction {
+            from: v2.from,
+            nonce: v2.nonce,
+            module_id: v2.module_id,
+            call_id: v2.call_id,
+            payload: v2.payload,
+            fee: v2.fee,
+            signature: v2.signature,
+            valid_until_height: v2.valid_until_height,
+            sig_scheme: SIG_SCHEME_ED25519,
+        }
+    }
+}
+
+/// V3 wire layout: V2 plus `sig_scheme`.
+///
+/// Matches the canonical struct field-for-field, so it is encoded directly
+/// from `Transaction`.
+type TransactionV3 = Transaction;
+
+/// Version of the `Transaction::canonical_bytes` layout for Ed25519
+/// transactions: the layout from before `sig_scheme`, so their hashes and
+/// signatures stay what they were.
+pub const CANONICAL_TX_VERSION_V1: u8 = 1;
+
+/// Version of the `Transaction::canonical_bytes` layout for every other
+/// signature scheme, which ends with `sig_scheme`.
+pub const CANONICAL_TX_VERSION: u8 = 2;
+
 /// Errors that can occur during transaction serialization/deserialization.
 #[derive(Debug, Error)]
 pub enum TransactionError {
@@ -48,29 +206,324 @@ pub enum TransactionError {
     SerializationError(#[from] bincode::Error),
     #[error("Deserialization error: {0}")]
     DeserializationError(String),
+    #[error("Transaction cannot be encoded as version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("unsupported signature scheme {0}")]
+    UnsupportedSignatureScheme(u8),
 }
 
 impl Transaction {
     /// Serialize this transaction to bytes.
     ///
-    /// Returns the bincode-serialized representation of the transaction.
+    /// Always emits the latest wire version: a version byte followed by the
+    /// bincode-serialized fields.
     pub fn to_bytes(&self) -> Result<Vec<u8>, TransactionError> {
-        bincode::serialize(self).map_err(TransactionError::SerializationError)
+        self.to_bytes_versioned(TX_VERSION_LATEST)
+    }
+
+    /// Serialize this transaction in a specific wire version.
+    ///
+    /// Fails if the transaction uses fields the version cannot represent
+    /// (e.g. an expiry in V1, or a non-Ed25519 scheme before V3).
+    pub fn to_bytes_versioned(&self, version: u8) -> Result<Vec<u8>, TransactionError> {
+        let ed25519 = self.sig_scheme == SIG_SCHEME_ED25519;
+        let body = match version {
+            TX_VERSION_V1 if ed25519 && self.valid_until_height.is_none() => {
+                codec::encode(&TransactionV1 {
+                    from: self.from,
+                    nonce: self.nonce,
+                    module_id: self.module_id.clone(),
+                    call_id: self.call_id.clone(),
+                    payload: self.payload.clone(),
+                    fee: self.fee,
+                    signature: self.signature.clone(),
+                })?
+            }
+            TX_VERSION_V2 if ed25519 => codec::encode(&TransactionV2 {
+                from: self.from,
+                nonce: self.nonce,
+                module_id: self.module_id.clone(),
+                call_id: self.call_id.clone(),
+                payload: self.payload.clone(),
+                fee: self.fee,
+                signature: self.signature.clone(),
+                valid_until_height: self.valid_until_height,
+            })?,
+            TX_VERSION_V3 => codec::encode::<TransactionV3>(self)?,
+            _ => return Err(TransactionError::UnsupportedVersion(version)),
+        };
+        let mut bytes = Vec::with_capacity(body.len() + 1);
+        bytes.push(version);
+        bytes.extend_from_slice(&body);
+        Ok(bytes)
     }
 
     /// Deserialize a transaction from bytes.
     ///
-    /// Attempts to deserialize a `Transaction` from the provided byte slice.
+    /// See `decode`; the wire version is discarded.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, TransactionError> {
-        bincode::deserialize(bytes)
+        Self::decode(bytes).map(|(tx, _)| tx)
+    }
+
+    /// Deserialize a transaction, returning it with the wire version it used.
+    ///
+    /// The leading version byte selects the layout; older versions are
+    /// upgraded into the canonical struct with defaulted fields. Encodings
+    /// from before versioning (bare bincode of the V2 or V1 layout) are still
+    /// accepted and reported as those versions. Encodings over
+    /// `codec::NETWORK_LIMIT` bytes are refused before decoding.
+    pub fn decode(bytes: &[u8]) -> Result<(Self, u8), TransactionError> {
+        if bytes.len() as u64 > codec::NETWORK_LIMIT {
+            return Err(TransactionError::DeserializationError(format!(
+                "transaction is {} bytes, over the {} byte limit",
+                bytes.len(),
+                codec::NETWORK_LIMIT
+            )));
+        }
+        let versioned = match bytes.split_first() {
+            Some((&TX_VERSION_V1, body)) => {
+                codec::decode_exact::<TransactionV1>(body).map(|v1| (v1.into(), TX_VERSION_V1))
+            }
+            Some((&TX_VERSION_V2, body)) => {
+                codec::decode_exact::<TransactionV2>(body).map(|v2| (v2.into(), TX_VERSION_V2))
+            }
+            Some((&TX_VERSION_V3, body)) => {
+                codec::decode_exact::<TransactionV3>(body).map(|tx| (tx, TX_VERSION_V3))
+            }
+            _ => Err(Box::new(bincode::ErrorKind::Custom(
+                "unknown transaction version".to_string(),
+            ))),
+        };
+
+        versioned
+            .or_else(|e| {
+                codec::decode_exact::<TransactionV2>(bytes)
+                    .map(|v2| (v2.into(), TX_VERSION_V2))
+                    .or_else(|_| {
+                        codec::decode_exact::<TransactionV1>(bytes)
+                            .map(|v1| (v1.into(), TX_VERSION_V1))
+                    })
+                    .map_err(|_| e)
+            })
             .map_err(|e| TransactionError::DeserializationError(e.to_string()))
     }
+
+    /// Canonical byte form of the transaction, covered by `hash` and the signature.
+    ///
+    /// The layout is written out by hand rather than derived from bincode, so
+    /// it cannot drift with serializer defaults or the wire version a
+    /// transaction arrived in. Fields are positional; integers are big-endian
+    /// and variable-length fields carry a `u64` length prefix:
+    ///
+    /// | Field                | Encoding                                  |
+    /// |----------------------|-------------------------------------------|
+    /// | layout version       | `u8` (see below)                          |
+    /// | `from`               | 32 bytes                                  |
+    /// | `nonce`              | `u64`                                     |
+    /// | `module_id`          | `u64` length + UTF-8 bytes                |
+    /// | `call_id`            | `u64` length + UTF-8 bytes                |
+    /// | `payload`            | `u64` length + bytes                      |
+    /// | `fee`                | `u64`                                     |
+    /// | `valid_until_height` | `0u8` for `None`, or `1u8` + `u64`        |
+    /// | `sig_scheme`         | `u8`, version 2 only                      |
+    ///
+    /// Ed25519 transactions use version 1 (`CANONICAL_TX_VERSION_V1`), which
+    /// leaves `sig_scheme` out, so transactions from before the field keep
+    /// their hashes and signatures. Every other scheme uses version 2
+    /// (`CANONICAL_TX_VERSION`); the differing version byte still binds each
+    /// signature to its scheme.
+    ///
+    /// The signature is excluded, so every other field is signed and the
+    /// hash does not change when a transaction is signed.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        fn put_len_prefixed(out: &mut Vec<u8>, bytes: &[u8]) {
+            out.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+            out.extend_from_slice(bytes);
+        }
+
+        let mut out = Vec::with_capacity(
+            1 + 32 + 8 * 6 + self.module_id.len() + self.call_id.len() + self.payload.len() + 2,
+        );
+        let ed25519 = self.sig_scheme == SIG_SCHEME_ED25519;
+        out.push(if ed25519 {
+            CANONICAL_TX_VERSION_V1
+        } else {
+            CANONICAL_TX_VERSION
+        });
+        out.extend_from_slice(&self.from);
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        put_len_prefixed(&mut out, self.module_id.as_bytes());
+        put_len_prefixed(&mut out, self.call_id.as_bytes());
+        put_len_prefixed(&mut out, &self.payload);
+        out.extend_from_slice(&self.fee.to_be_bytes());
+        match self.valid_until_height {
+            None => out.push(0),
+            Some(height) => {
+                out.push(1);
+                out.extend_from_slice(&height.to_be_bytes());
+            }
+        }
+        if !ed25519 {
+            out.push(self.sig_scheme);
+        }
+        out
+    }
+
+    /// SHA-256 of the canonical bytes.
+    ///
+    /// Identical for every wire version a transaction can be encoded in.
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.canonical_bytes()).into()
+    }
+
+    /// Check the signature by `from` over the canonical bytes, under the
+    /// transaction's `sig_scheme`.
+    ///
+    /// Multisig signatures can only be checked against the account's
+    /// registered keys, with `verify_multisig`.
+    pub fn verify_signature(&self) -> Result<(), TransactionError> {
+        match self.sig_scheme {
+            SIG_SCHEME_ED25519 => {
+                verify_ed25519(&self.from, &self.signature, &self.canonical_bytes())
+            }
+            SIG_SCHEME_MULTISIG => Err(TransactionError::InvalidSignature(
+                "multisig signatures are checked against the account's keys".to_string(),
+            )),
+            other => Err(TransactionError::UnsupportedSignatureScheme(other)),
+        }
+    }
+
+    /// Check a `SIG_SCHEME_MULTISIG` signature: at least `threshold` distinct
+    /// signers from `keys`, each signing the same canonical bytes.
+    pub fn verify_multisig(&self, keys: &[Address], threshold: u8) -> Result<(), TransactionError> {
+        let invalid = TransactionError::InvalidSignature;
+        if self.sig_scheme != SIG_SCHEME_MULTISIG {
+            return Err(invalid(format!(
+                "multisig account needs signature scheme {}, got {}",
+                SIG_SCHEME_MULTISIG, self.sig_scheme
+            )));
+        }
+        let signatures: Vec<MultisigSignature> = codec::decode_exact(&self.signature)
+            .map_err(|e| invalid(format!("malformed multisig signatures: {}", e)))?;
+        let message = self.canonical_bytes();
+        let mut signed = vec![false; keys.len()];
+        for entry in &signatures {
+            let index = usize::from(entry.signer_index);
+            match signed.get_mut(index) {
+                None => return Err(invalid(format!("no signer at index {}", index))),
+                Some(true) => return Err(invalid(format!("signer {} signed twice", index))),
+                Some(seen) => *seen = true,
+            }
+            verify_ed25519(&keys[index], &entry.signature, &message)
+                .map_err(|e| invalid(format!("signer {}: {}", index, e)))?;
+        }
+        if signatures.len() < usize::from(threshold) {
+            return Err(invalid(format!(
+                "{} of {} required signatures",
+                signatures.len(),
+                threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check whether this transaction has expired for a block at `height`.
+    pub fn is_expired_at(&self, height: u64) -> bool {
+        self.valid_until_height
+            .map(|until| height > until)
+            .unwrap_or(false)
+    }
+}
+
+fn verify_ed25519(key: &Address, signature: &[u8], message: &[u8]) -> Result<(), TransactionError> {
+    let key = ed25519_zebra::VerificationKey::try_from(*key)
+        .map_err(|e| TransactionError::InvalidSignature(e.to_string()))?;
+    let signature = ed25519_zebra::Signature::try_from(signature)
+        .map_err(|e| TransactionError::InvalidSignature(e.to_string()))?;
+    key.verify(&signature, message)
+        .map_err(|e| TransactionError::InvalidSignature(e.to_string()))
+}
+
+/// Deterministic Ed25519 keys for tests that send transactions through
+/// dispatch or admission, where signatures are checked. Also built with the
+/// `test-support` feature, for the end-to-end harness.
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_keys {
+    use std::sync::OnceLock;
+
+    use super::{Address, Transaction};
+
+    /// Signing key seeded with `seed`.
+    pub fn signer(seed: u8) -> ed25519_zebra::SigningKey {
+        ed25519_zebra::SigningKey::from([seed; 32])
+    }
+
+    /// Address of `signer(seed)`.
+    pub fn account(seed: u8) -> Address {
+        ed25519_zebra::VerificationKey::from(&signer(seed)).into()
+    }
+
+    /// `tx` signed by its sender, which must be one of the `account`s.
+    pub fn signed(mut tx: Transaction) -> Transaction {
+        static ACCOUNTS: OnceLock<Vec<Address>> = OnceLock::new();
+        let accounts = ACCOUNTS.get_or_init(|| (0..=u8::MAX).map(account).collect());
+        let seed = accounts
+            .iter()
+            .position(|address| *address == tx.from)
+            .expect("sender is not a test account") as u8;
+        let signature: [u8; 64] = signer(seed).sign(&tx.canonical_bytes()).into();
+        tx.signature = signature.to_vec();
+        tx
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_address_display_known_vectors() {
+        let zero: Address = [0; 32];
+        let display = zero.to_display();
+        assert_eq!(
+            display,
+            "dmrg1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq6j930g"
+        );
+        assert_eq!(Address::parse(&display), Ok(zero));
+
+        let genesis: Address = [0xaa; 32];
+        assert_eq!(
+            genesis.to_display(),
+            "dmrg1424242424242424242424242424242424242424242424242424q9f6mfr"
+        );
+        assert_eq!(Address::parse(&genesis.to_display()), Ok(genesis));
+        assert_eq!(Address::parse(&hex::encode(genesis)), Ok(genesis));
+        assert_eq!(
+            Address::parse(&genesis.to_display().to_uppercase()),
+            Ok(genesis)
+        );
+    }
+
+    #[test]
+    fn test_address_parse_rejects_corruption() {
+        let display = [7u8; 32].to_display();
+
+        // Flip each character in turn: the checksum must catch every one
+        for i in 5..display.len() {
+            let mut chars: Vec<char> = display.chars().collect();
+            chars[i] = if chars[i] == 'q' { 'p' } else { 'q' };
+            let corrupted: String = chars.into_iter().collect();
+            assert!(Address::parse(&corrupted).is_err(), "{}", corrupted);
+        }
+
+        // Wrong prefix and wrong length
+        let other = bech32::encode::<Bech32m>(Hrp::parse_unchecked("other"), &[7u8; 32]).unwrap();
+        assert!(Address::parse(&other).unwrap_err().contains("prefix"));
+        assert!(Address::parse(&hex::encode([7u8; 31])).is_err());
+    }
+
     #[test]
     fn test_transaction_serialization_round_trip() {
         let tx = Transaction {
@@ -81,6 +534,8 @@ mod tests {
             payload: vec![1, 2, 3, 4],
             fee: 100,
             signature: vec![0; 64],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         // Serialize
@@ -110,6 +565,8 @@ mod tests {
             payload: vec![],
             fee: 0,
             signature: vec![0; 64],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         let bytes = tx.to_bytes().expect("Serialization should succeed");
@@ -118,4 +575,316 @@ mod tests {
         assert_eq!(tx.module_id, tx2.module_id);
         assert_eq!(tx.call_id, tx2.call_id);
     }
+
+    #[derive(Serialize)]
+    struct LegacyEncoding {
+        from: Address,
+        nonce: u64,
+        module_id: String,
+        call_id: String,
+        payload: Vec<u8>,
+        fee: u64,
+        signature: Signature,
+    }
+
+    #[test]
+    fn test_legacy_encoding_decodes_without_expiry() {
+        let legacy = LegacyEncoding {
+            from: [3; 32],
+            nonce: 7,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![9, 9],
+            fee: 1,
+            signature: vec![0; 64],
+        };
+        let bytes = codec::encode(&legacy).unwrap();
+
+        let tx = Transaction::from_bytes(&bytes).expect("legacy bytes should decode");
+        assert_eq!(tx.nonce, 7);
+        assert_eq!(tx.payload, vec![9, 9]);
+        assert_eq!(tx.valid_until_height, None);
+    }
+
+    /// V1 fixture: version 0x01, from = [0x11; 32], nonce 5, "bank_cgt" /
+    /// "transfer", payload [0xaa, 0xbb], fee 3, signature de ad be ef.
+    /// Must keep decoding forever; never regenerate it.
+    const V1_FIXTURE_HEX: &str = concat!(
+        "01",
+        "1111111111111111111111111111111111111111111111111111111111111111",
+        "0500000000000000",
+        "0800000000000000",
+        "62616e6b5f636774",
+        "0800000000000000",
+        "7472616e73666572",
+        "0200000000000000",
+        "aabb",
+        "0300000000000000",
+        "0400000000000000",
+        "deadbeef",
+    );
+
+    /// V2 fixture: the V1 fixture's fields plus valid_until_height = Some(100).
+    const V2_FIXTURE_HEX: &str = concat!(
+        "02",
+        "1111111111111111111111111111111111111111111111111111111111111111",
+        "0500000000000000",
+        "0800000000000000",
+        "62616e6b5f636774",
+        "0800000000000000",
+        "7472616e73666572",
+        "0200000000000000",
+        "aabb",
+        "0300000000000000",
+        "0400000000000000",
+        "deadbeef",
+        "01",
+        "6400000000000000",
+    );
+
+    /// V3 fixture: the V2 fixture's fields plus sig_scheme = 0.
+    const V3_FIXTURE_HEX: &str = concat!(
+        "03",
+        "1111111111111111111111111111111111111111111111111111111111111111",
+        "0500000000000000",
+        "0800000000000000",
+        "62616e6b5f636774",
+        "0800000000000000",
+        "7472616e73666572",
+        "0200000000000000",
+        "aabb",
+        "0300000000000000",
+        "0400000000000000",
+        "deadbeef",
+        "01",
+        "6400000000000000",
+        "00",
+    );
+
+    fn fixture_tx(valid_until_height: Option<u64>) -> Transaction {
+        Transaction {
+            from: [0x11; 32],
+            nonce: 5,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![0xaa, 0xbb],
+            fee: 3,
+            signature: vec![0xde, 0xad, 0xbe, 0xef],
+            valid_until_height,
+            sig_scheme: 0,
+        }
+    }
+
+    #[test]
+    fn test_v1_fixture_decodes() {
+        let bytes = hex::decode(V1_FIXTURE_HEX).unwrap();
+        let (tx, version) = Transaction::decode(&bytes).unwrap();
+        assert_eq!(version, TX_VERSION_V1);
+        assert_eq!(tx, fixture_tx(None));
+
+        // Re-encoding in V1 reproduces the submitted bytes, and the hash
+        // matches the same transaction submitted as V2
+        assert_eq!(tx.to_bytes_versioned(TX_VERSION_V1).unwrap(), bytes);
+        assert_eq!(
+            tx.hash(),
+            Transaction::from_bytes(&tx.to_bytes().unwrap())
+                .unwrap()
+                .hash()
+        );
+    }
+
+    #[test]
+    fn test_v2_fixture_decodes() {
+        let bytes = hex::decode(V2_FIXTURE_HEX).unwrap();
+        let (tx, version) = Transaction::decode(&bytes).unwrap();
+        assert_eq!(version, TX_VERSION_V2);
+        assert_eq!(tx, fixture_tx(Some(100)));
+        assert_eq!(tx.to_bytes_versioned(TX_VERSION_V2).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_v3_fixture_decodes() {
+        let bytes = hex::decode(V3_FIXTURE_HEX).unwrap();
+        let (tx, version) = Transaction::decode(&bytes).unwrap();
+        assert_eq!(version, TX_VERSION_V3);
+        assert_eq!(tx, fixture_tx(Some(100)));
+        assert_eq!(tx.to_bytes().unwrap(), bytes);
+
+        // Only V3 can carry a scheme other than Ed25519
+        let mut other = tx.clone();
+        other.sig_scheme = 7;
+        assert_eq!(
+            Transaction::from_bytes(&other.to_bytes().unwrap()).unwrap(),
+            other
+        );
+        assert!(matches!(
+            other.to_bytes_versioned(TX_VERSION_V2),
+            Err(TransactionError::UnsupportedVersion(TX_VERSION_V2))
+        ));
+    }
+
+    #[test]
+    fn test_to_bytes_emits_latest_version() {
+        let bytes = fixture_tx(None).to_bytes().unwrap();
+        assert_eq!(bytes[0], TX_VERSION_LATEST);
+
+        // An upgraded V1 transaction re-encodes as the latest version
+        let v1 = Transaction::from_bytes(&hex::decode(V1_FIXTURE_HEX).unwrap()).unwrap();
+        assert_eq!(v1.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_versioned_decode_rejects_bad_input() {
+        let mut bytes = hex::decode(V1_FIXTURE_HEX).unwrap();
+        bytes.push(0);
+        assert!(Transaction::from_bytes(&bytes).is_err());
+
+        bytes = hex::decode(V2_FIXTURE_HEX).unwrap();
+        bytes[0] = 0xff;
+        assert!(Transaction::from_bytes(&bytes).is_err());
+
+        // V1 cannot carry an expiry
+        assert!(matches!(
+            fixture_tx(Some(1)).to_bytes_versioned(TX_VERSION_V1),
+            Err(TransactionError::UnsupportedVersion(TX_VERSION_V1))
+        ));
+
+        // Oversized encodings are refused outright
+        let mut tx = fixture_tx(None);
+        tx.payload = vec![0; codec::NETWORK_LIMIT as usize];
+        assert!(Transaction::from_bytes(&tx.to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_valid_until_height_round_trip_and_expiry() {
+        let tx = Transaction {
+            from: [1; 32],
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![0; 64],
+            valid_until_height: Some(10),
+            sig_scheme: 0,
+        };
+
+        let tx2 = Transaction::from_bytes(&tx.to_bytes().unwrap()).unwrap();
+        assert_eq!(tx2.valid_until_height, Some(10));
+
+        assert!(!tx.is_expired_at(10));
+        assert!(tx.is_expired_at(11));
+
+        // Expiry is covered by the signature
+        let mut extended = tx.clone();
+        extended.valid_until_height = Some(20);
+        assert_ne!(tx.canonical_bytes(), extended.canonical_bytes());
+    }
+
+    #[test]
+    fn test_canonical_bytes_layout() {
+        // Ed25519 keeps the layout from before sig_scheme, and its hashes
+        let fields = concat!(
+            "1111111111111111111111111111111111111111111111111111111111111111",
+            "0000000000000005",
+            "0000000000000008",
+            "62616e6b5f636774",
+            "0000000000000008",
+            "7472616e73666572",
+            "0000000000000002",
+            "aabb",
+            "0000000000000003",
+            "01",
+            "0000000000000064",
+        );
+        let mut tx = fixture_tx(Some(100));
+        assert_eq!(tx.sig_scheme, SIG_SCHEME_ED25519);
+        assert_eq!(
+            tx.canonical_bytes(),
+            hex::decode(format!("01{}", fields)).unwrap()
+        );
+
+        // Other schemes add it, under a version of their own
+        tx.sig_scheme = SIG_SCHEME_MULTISIG;
+        assert_eq!(
+            tx.canonical_bytes(),
+            hex::decode(format!("02{}01", fields)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_canonical_bytes_identical_for_identical_transactions() {
+        // Built separately, signed differently, and decoded from another wire version
+        let a = fixture_tx(Some(100));
+        let mut b = fixture_tx(Some(100));
+        b.signature = vec![];
+        assert_eq!(a.canonical_bytes(), b.canonical_bytes());
+        assert_eq!(a.hash(), b.hash());
+
+        let unexpiring = fixture_tx(None);
+        let v1 = unexpiring.to_bytes_versioned(TX_VERSION_V1).unwrap();
+        let decoded = Transaction::from_bytes(&v1).unwrap();
+        assert_eq!(decoded.canonical_bytes(), unexpiring.canonical_bytes());
+    }
+
+    #[test]
+    fn test_canonical_bytes_are_positional() {
+        // Swapping values between fields changes the bytes even though the
+        // multiset of values is the same
+        let a = fixture_tx(Some(100));
+        let mut swapped = fixture_tx(Some(100));
+        swapped.module_id = a.call_id.clone();
+        swapped.call_id = a.module_id.clone();
+        assert_ne!(a.canonical_bytes(), swapped.canonical_bytes());
+
+        let mut swapped = fixture_tx(Some(100));
+        swapped.nonce = a.fee;
+        swapped.fee = a.nonce;
+        assert_ne!(a.canonical_bytes(), swapped.canonical_bytes());
+
+        // Length prefixes keep string boundaries unambiguous
+        let mut shifted = fixture_tx(Some(100));
+        shifted.module_id = "bank_cgttrans".to_string();
+        shifted.call_id = "fer".to_string();
+        assert_ne!(a.canonical_bytes(), shifted.canonical_bytes());
+    }
+
+    #[test]
+    fn test_verify_signature_over_canonical_bytes() {
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let mut tx = fixture_tx(Some(100));
+        tx.from = ed25519_zebra::VerificationKey::from(&signing_key).into();
+        let signature: [u8; 64] = signing_key.sign(&tx.canonical_bytes()).into();
+        tx.signature = signature.to_vec();
+        assert!(tx.verify_signature().is_ok());
+
+        let mut tampered = tx.clone();
+        tampered.fee += 1;
+        assert!(tampered.verify_signature().is_err());
+
+        tx.signature.truncate(10);
+        assert!(tx.verify_signature().is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_dispatches_on_scheme() {
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let mut tx = fixture_tx(None);
+        tx.from = ed25519_zebra::VerificationKey::from(&signing_key).into();
+        tx.sig_scheme = SIG_SCHEME_ED25519;
+        let signature: [u8; 64] = signing_key.sign(&tx.canonical_bytes()).into();
+        tx.signature = signature.to_vec();
+        assert!(tx.verify_signature().is_ok());
+
+        // The scheme is signed, and an unknown one is refused outright
+        let mut unknown = tx.clone();
+        unknown.sig_scheme = 0xff;
+        assert_ne!(unknown.canonical_bytes(), tx.canonical_bytes());
+        let err = unknown.verify_signature().unwrap_err();
+        assert!(matches!(
+            err,
+            TransactionError::UnsupportedSignatureScheme(0xff)
+        ));
+        assert_eq!(err.to_string(), "unsupported signature scheme 255");
+    }
 }
@@ -0,0 +1,299 @@
+//! Transactions-by-address index for explorers.
+//!
+//! Block execution records every transaction's hash under its sender and
+//! under the counterparties named in its events (transfer and NFT
+//! recipients, the buyer and seller of a listing). Each address's list is
+//! append-only and stored in fixed-size pages, so appending touches one small
+//! value and a query reads only the pages it returns.
+//!
+//! Pruning drops transactions from the front of each list, oldest first. A
+//! page is deleted once every entry in it is pruned; positions and the total
+//! stay as they were, so later pages never move.
+
+use std::collections::BTreeSet;
+
+use crate::core::codec;
+use crate::core::receipt::Event;
+use crate::core::state::State;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+
+/// Storage prefix for index pages, keyed by address and big-endian page number.
+const PAGE_KEY_PREFIX: &[u8] = b"addr:txs:";
+
+/// Storage prefix for the number of transactions indexed per address.
+const COUNT_KEY_PREFIX: &[u8] = b"addr:txcount:";
+
+/// Storage prefix for the number of an address's oldest indexed
+/// transactions that pruning has dropped.
+const PRUNED_KEY_PREFIX: &[u8] = b"addr:txpruned:";
+
+/// Transaction hashes per index page.
+pub const TX_INDEX_PAGE_SIZE: u64 = 128;
+
+/// Event attributes whose value is a counterparty address.
+const COUNTERPARTY_ATTRIBUTES: &[&str] = &["to", "seller"];
+
+fn page_key(addr: &Address, page: u64) -> Vec<u8> {
+    let mut key = PAGE_KEY_PREFIX.to_vec();
+    key.extend_from_slice(addr);
+    key.extend_from_slice(&page.to_be_bytes());
+    key
+}
+
+fn count_key(addr: &Address) -> Vec<u8> {
+    let mut key = COUNT_KEY_PREFIX.to_vec();
+    key.extend_from_slice(addr);
+    key
+}
+
+fn pruned_key(addr: &Address) -> Vec<u8> {
+    let mut key = PRUNED_KEY_PREFIX.to_vec();
+    key.extend_from_slice(addr);
+    key
+}
+
+fn load_page(state: &State, addr: &Address, page: u64) -> Vec<[u8; 32]> {
+    state
+        .get_raw(&page_key(addr, page))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// Number of transactions indexed under an address.
+pub fn indexed_count(state: &State, addr: &Address) -> u64 {
+    state
+        .get_raw(&count_key(addr))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+        .unwrap_or(0)
+}
+
+/// Addresses a transaction is indexed under: its sender, plus every address
+/// its events are about or name as a counterparty.
+pub fn involved_addresses(tx: &Transaction, events: &[Event]) -> BTreeSet<Address> {
+    let mut addrs = BTreeSet::from([tx.from]);
+    for event in events {
+        addrs.insert(event.address);
+        addrs.extend(
+            event
+                .attributes
+                .iter()
+                .filter(|(key, _)| COUNTERPARTY_ATTRIBUTES.contains(&key.as_str()))
+                .filter_map(|(_, value)| Address::parse(value).ok()),
+        );
+    }
+    addrs
+}
+
+/// Append a transaction to the index of every address involved in it.
+pub fn index_transaction(
+    state: &mut State,
+    tx: &Transaction,
+    tx_hash: &[u8; 32],
+    events: &[Event],
+) -> Result<(), String> {
+    for addr in involved_addresses(tx, events) {
+        let count = indexed_count(state, &addr);
+        let page = count / TX_INDEX_PAGE_SIZE;
+        let mut hashes = load_page(state, &addr, page);
+        hashes.push(*tx_hash);
+
+        let bytes = codec::encode(&hashes).map_err(|e| e.to_string())?;
+        state
+            .put_raw(page_key(&addr, page), bytes)
+            .map_err(|e| e.to_string())?;
+        let bytes = codec::encode(&(count + 1)).map_err(|e| e.to_string())?;
+        state
+            .put_raw(count_key(&addr), bytes)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Number of an address's oldest indexed transactions dropped by
+/// `prune_transaction`.
+pub fn pruned_count(state: &State, addr: &Address) -> u64 {
+    state
+        .get_raw(&pruned_key(addr))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+        .unwrap_or(0)
+}
+
+/// Drop a transaction whose block is pruned from the front of the index of
+/// every address involved in it, deleting a page once all of its entries
+/// are dropped.
+///
+/// `events` must be the ones it was indexed with, and transactions must be
+/// dropped in the order they were indexed.
+pub fn prune_transaction(
+    state: &mut State,
+    tx: &Transaction,
+    events: &[Event],
+) -> Result<(), String> {
+    for addr in involved_addresses(tx, events) {
+        let pruned = pruned_count(state, &addr) + 1;
+        if pruned > indexed_count(state, &addr) {
+            continue;
+        }
+        if pruned.is_multiple_of(TX_INDEX_PAGE_SIZE) {
+            state
+                .delete_raw(&page_key(&addr, pruned / TX_INDEX_PAGE_SIZE - 1))
+                .map_err(|e| e.to_string())?;
+        }
+        let bytes = codec::encode(&pruned).map_err(|e| e.to_string())?;
+        state
+            .put_raw(pruned_key(&addr), bytes)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// A window of an address's indexed transactions.
+///
+/// `offset` and `limit` count from the oldest transaction, or from the newest
+/// when `newest_first` is set; hashes come back in that same order.
+///
+/// # Returns
+/// The total number of indexed transactions and the hashes in the window.
+pub fn transactions_by_address(
+    state: &State,
+    addr: &Address,
+    offset: u64,
+    limit: u64,
+    newest_first: bool,
+) -> (u64, Vec<[u8; 32]>) {
+    let total = indexed_count(state, addr);
+    let len = limit.min(total.saturating_sub(offset));
+    if len == 0 {
+        return (total, Vec::new());
+    }
+
+    // Positions [start, end) in oldest-first order
+    let (start, end) = if newest_first {
+        (total - offset - len, total - offset)
+    } else {
+        (offset, offset + len)
+    };
+    let mut hashes = Vec::with_capacity(len as usize);
+    for page in start / TX_INDEX_PAGE_SIZE..=(end - 1) / TX_INDEX_PAGE_SIZE {
+        let first = page * TX_INDEX_PAGE_SIZE;
+        let from = start.max(first) - first;
+        let to = end.min(first + TX_INDEX_PAGE_SIZE) - first;
+        let entries = load_page(state, addr, page);
+        hashes.extend(
+            entries
+                .get(from as usize..(to as usize).min(entries.len()))
+                .unwrap_or_default(),
+        );
+    }
+    if newest_first {
+        hashes.reverse();
+    }
+    (total, hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: Address = [1; 32];
+    const RECIPIENT: Address = [2; 32];
+
+    fn tx(nonce: u64) -> Transaction {
+        Transaction {
+            from: SENDER,
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    fn hash(i: u64) -> [u8; 32] {
+        let mut hash = [0; 32];
+        hash[..8].copy_from_slice(&i.to_be_bytes());
+        hash
+    }
+
+    #[test]
+    fn test_counterparties_from_events() {
+        let event = Event::new("bank_cgt", "transfer", SENDER)
+            .with("to", RECIPIENT.to_display())
+            .with("amount", 5);
+        assert_eq!(
+            involved_addresses(&tx(0), &[event]),
+            BTreeSet::from([SENDER, RECIPIENT])
+        );
+        // Failed calls emit no events and are indexed under the sender only
+        assert_eq!(involved_addresses(&tx(0), &[]), BTreeSet::from([SENDER]));
+    }
+
+    #[test]
+    fn test_pagination_across_page_boundary() {
+        let mut state = State::in_memory();
+        let n = TX_INDEX_PAGE_SIZE + 5;
+        for i in 0..n {
+            index_transaction(&mut state, &tx(i), &hash(i), &[]).unwrap();
+        }
+        assert_eq!(indexed_count(&state, &SENDER), n);
+        assert_eq!(
+            load_page(&state, &SENDER, 0).len() as u64,
+            TX_INDEX_PAGE_SIZE
+        );
+        assert_eq!(load_page(&state, &SENDER, 1).len(), 5);
+
+        // Oldest first, straddling the boundary
+        let (total, hashes) =
+            transactions_by_address(&state, &SENDER, TX_INDEX_PAGE_SIZE - 2, 4, false);
+        assert_eq!(total, n);
+        let expected: Vec<_> = (TX_INDEX_PAGE_SIZE - 2..TX_INDEX_PAGE_SIZE + 2)
+            .map(hash)
+            .collect();
+        assert_eq!(hashes, expected);
+
+        // Newest first
+        let (_, hashes) = transactions_by_address(&state, &SENDER, 0, 7, true);
+        let expected: Vec<_> = (n - 7..n).rev().map(hash).collect();
+        assert_eq!(hashes, expected);
+
+        // Windows past the end are clipped
+        let (_, hashes) = transactions_by_address(&state, &SENDER, n - 1, 10, false);
+        assert_eq!(hashes, vec![hash(n - 1)]);
+        assert!(transactions_by_address(&state, &SENDER, n, 10, true)
+            .1
+            .is_empty());
+        assert_eq!(
+            transactions_by_address(&state, &RECIPIENT, 0, 10, false),
+            (0, vec![])
+        );
+    }
+
+    #[test]
+    fn test_pruning_deletes_whole_pages_only() {
+        let mut state = State::in_memory();
+        let n = TX_INDEX_PAGE_SIZE + 5;
+        for i in 0..n {
+            index_transaction(&mut state, &tx(i), &hash(i), &[]).unwrap();
+        }
+
+        for i in 0..TX_INDEX_PAGE_SIZE - 1 {
+            prune_transaction(&mut state, &tx(i), &[]).unwrap();
+        }
+        assert_eq!(
+            load_page(&state, &SENDER, 0).len() as u64,
+            TX_INDEX_PAGE_SIZE
+        );
+        prune_transaction(&mut state, &tx(TX_INDEX_PAGE_SIZE - 1), &[]).unwrap();
+        assert!(state.get_raw(&page_key(&SENDER, 0)).is_none());
+        assert_eq!(pruned_count(&state, &SENDER), TX_INDEX_PAGE_SIZE);
+
+        // Later positions don't move, and the pruned ones read as empty
+        let (total, hashes) = transactions_by_address(&state, &SENDER, 0, n, false);
+        assert_eq!(total, n);
+        let expected: Vec<_> = (TX_INDEX_PAGE_SIZE..n).map(hash).collect();
+        assert_eq!(hashes, expected);
+    }
+}
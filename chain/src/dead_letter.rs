@@ -0,0 +1,213 @@
+//! Dead-letter store for transactions that block building keeps failing.
+//!
+//! Each block template dry-runs the mempool, and a transaction whose dry run
+//! fails is recorded here instead of being left behind without a word. A
+//! failure on the nonce, including a transfer still queued behind a nonce gap,
+//! may clear once the missing earlier transaction arrives, so it is retried at
+//! up to `MempoolConfig::nonce_retries` later heights; any other failure, or a
+//! nonce failure with no retries left, moves the transaction out of the
+//! mempool and into the store with its reason. Templates rebuilt at the same
+//! height count as one attempt.
+//!
+//! The store lives in memory, like the mempool, and keeps at most
+//! `MempoolConfig::dead_letter_capacity` transactions, dropping the oldest.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+
+use crate::config::MempoolConfig;
+use crate::core::transaction::Transaction;
+
+/// A transaction whose dry run failed while building a block template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedTransaction {
+    pub tx: Transaction,
+    /// Error from the most recent attempt.
+    pub reason: String,
+    /// Block heights the transaction failed at.
+    pub attempts: u64,
+    /// Height of the most recent failed attempt.
+    pub last_attempted_height: u64,
+}
+
+/// Why a block template dry run left a transaction out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The transaction consumes a nonce and did not carry its sender's next
+    /// one, which may clear once an earlier transaction from the same sender
+    /// is included.
+    Nonce(String),
+    /// Anything else.
+    Other(String),
+}
+
+/// Failed transactions still being retried, and those given up on.
+pub struct DeadLetters {
+    /// Failures of transactions still in the mempool, by hash.
+    retrying: HashMap<[u8; 32], FailedTransaction>,
+    /// Dead-lettered transactions by hash; the least recently added goes first.
+    dead: LruCache<[u8; 32], FailedTransaction>,
+    nonce_retries: u64,
+}
+
+impl DeadLetters {
+    pub fn new(config: &MempoolConfig) -> Self {
+        Self {
+            retrying: HashMap::new(),
+            dead: LruCache::new(
+                NonZeroUsize::new(config.dead_letter_capacity).unwrap_or(NonZeroUsize::MIN),
+            ),
+            nonce_retries: config.nonce_retries,
+        }
+    }
+
+    /// Record the failures of one block template built at `height`.
+    ///
+    /// A transaction that failed before but not in this template starts over
+    /// if it fails again later.
+    ///
+    /// # Returns
+    /// The transactions dead-lettered by this template, which must leave the
+    /// mempool
+    pub fn record_template(
+        &mut self,
+        height: u64,
+        failed: Vec<(Transaction, Failure)>,
+    ) -> Vec<FailedTransaction> {
+        let mut previous = std::mem::take(&mut self.retrying);
+        let mut dead = Vec::new();
+        for (tx, cause) in failed {
+            let hash = tx.hash();
+            let mut failure = match previous.remove(&hash) {
+                Some(failure) if failure.last_attempted_height == height => failure,
+                Some(failure) => FailedTransaction {
+                    attempts: failure.attempts + 1,
+                    last_attempted_height: height,
+                    ..failure
+                },
+                None => FailedTransaction {
+                    tx,
+                    reason: String::new(),
+                    attempts: 1,
+                    last_attempted_height: height,
+                },
+            };
+            let (reason, on_nonce) = match cause {
+                Failure::Nonce(reason) => (reason, true),
+                Failure::Other(reason) => (reason, false),
+            };
+            failure.reason = reason;
+            if on_nonce && failure.attempts <= self.nonce_retries {
+                self.retrying.insert(hash, failure);
+            } else {
+                self.dead.put(hash, failure.clone());
+                dead.push(failure);
+            }
+        }
+        dead
+    }
+
+    /// A dead-lettered transaction by hash.
+    pub fn get(&self, tx_hash: &[u8; 32]) -> Option<&FailedTransaction> {
+        self.dead.peek(tx_hash)
+    }
+
+    /// Drop one dead-lettered transaction, or all of them.
+    ///
+    /// # Returns
+    /// How many were dropped
+    pub fn purge(&mut self, tx_hash: Option<&[u8; 32]>) -> usize {
+        match tx_hash {
+            Some(tx_hash) => usize::from(self.dead.pop(tx_hash).is_some()),
+            None => {
+                let purged = self.dead.len();
+                self.dead.clear();
+                purged
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(nonce: u64) -> Transaction {
+        Transaction {
+            from: [1; 32],
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    fn store(nonce_retries: u64, dead_letter_capacity: usize) -> DeadLetters {
+        DeadLetters::new(&MempoolConfig {
+            nonce_retries,
+            dead_letter_capacity,
+            ..MempoolConfig::default()
+        })
+    }
+
+    fn nonce_failure(nonce: u64) -> (Transaction, Failure) {
+        (
+            tx(nonce),
+            Failure::Nonce(format!("invalid nonce: expected 0, got {}", nonce)),
+        )
+    }
+
+    #[test]
+    fn test_nonce_failures_retry_once_per_height() {
+        let mut letters = store(1, 8);
+        assert!(letters
+            .record_template(1, vec![nonce_failure(3)])
+            .is_empty());
+        // Rebuilding the template at the same height is not another attempt
+        assert!(letters
+            .record_template(1, vec![nonce_failure(3)])
+            .is_empty());
+
+        let dead = letters.record_template(2, vec![nonce_failure(3)]);
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(dead[0].last_attempted_height, 2);
+        assert_eq!(letters.get(&tx(3).hash()), Some(&dead[0]));
+    }
+
+    #[test]
+    fn test_other_failures_and_passing_attempts() {
+        let mut letters = store(1, 8);
+        let dead = letters.record_template(
+            1,
+            vec![(tx(0), Failure::Other("insufficient balance".to_string()))],
+        );
+        assert_eq!(dead[0].attempts, 1);
+
+        // Passing a template in between resets the count
+        letters.record_template(1, vec![nonce_failure(3)]);
+        letters.record_template(2, vec![]);
+        assert!(letters
+            .record_template(3, vec![nonce_failure(3)])
+            .is_empty());
+        assert_eq!(letters.get(&tx(3).hash()), None);
+    }
+
+    #[test]
+    fn test_capacity_and_purge() {
+        let mut letters = store(0, 2);
+        letters.record_template(1, (0..3).map(nonce_failure).collect());
+        assert_eq!(letters.get(&tx(0).hash()), None);
+
+        assert_eq!(letters.purge(Some(&tx(1).hash())), 1);
+        assert_eq!(letters.purge(Some(&tx(1).hash())), 0);
+        assert_eq!(letters.purge(None), 1);
+        assert_eq!(letters.get(&tx(2).hash()), None);
+    }
+}
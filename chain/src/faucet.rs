@@ -0,0 +1,205 @@
+//! Dev faucet with per-address cooldown and lifetime cap.
+//!
+//! Each claim records the chain height it happened at under
+//! `faucet:last:<addr>` and the running total under `faucet:total:<addr>`,
+//! so limits survive restarts. Amount, cooldown, cap, and availability all
+//! come from `FaucetConfig`, though governance may override the cooldown; the
+//! faucet is off by default outside debug builds.
+
+use thiserror::Error;
+
+use crate::config::ChainConfig;
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::Address;
+use crate::runtime::module_api::{bank, governance};
+
+const PREFIX_FAUCET_LAST: &[u8] = b"faucet:last:";
+const PREFIX_FAUCET_TOTAL: &[u8] = b"faucet:total:";
+
+/// Result of a successful faucet claim.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaucetReceipt {
+    /// The address's balance after the drip.
+    pub new_balance: u64,
+    /// Whether an Aeon profile was created alongside the drip.
+    pub profile_created: bool,
+}
+
+/// Reasons a faucet claim is refused.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FaucetError {
+    #[error("faucet is disabled on this node")]
+    Disabled,
+    #[error("faucet cooldown active: {remaining_blocks} blocks (~{remaining_secs}s) remaining")]
+    Cooldown {
+        remaining_blocks: u64,
+        /// Estimate from the target block time.
+        remaining_secs: u64,
+    },
+    #[error("faucet lifetime cap of {cap} reached for this address")]
+    CapReached { cap: u64 },
+    #[error("faucet error: {0}")]
+    State(String),
+}
+
+fn faucet_key(prefix: &[u8], addr: &Address) -> Vec<u8> {
+    let mut key = Vec::with_capacity(prefix.len() + addr.len());
+    key.extend_from_slice(prefix);
+    key.extend_from_slice(addr);
+    key
+}
+
+fn get_u64(state: &State, key: &[u8]) -> Option<u64> {
+    state
+        .get_raw(key)
+        .and_then(|bytes| codec::decode_state::<u64>(&bytes).ok())
+}
+
+fn put_u64(state: &mut State, key: Vec<u8>, value: u64) -> Result<(), FaucetError> {
+    let bytes = codec::encode(&value).map_err(|e| FaucetError::State(e.to_string()))?;
+    state
+        .put_raw(key, bytes)
+        .map_err(|e| FaucetError::State(e.to_string()))
+}
+
+/// Chain height of an address's last faucet claim, if any.
+pub fn last_claim_height(state: &State, addr: &Address) -> Option<u64> {
+    get_u64(state, &faucet_key(PREFIX_FAUCET_LAST, addr))
+}
+
+/// Total CGT base units an address has received from the faucet.
+pub fn total_claimed(state: &State, addr: &Address) -> u64 {
+    get_u64(state, &faucet_key(PREFIX_FAUCET_TOTAL, addr)).unwrap_or(0)
+}
+
+/// Every stored lifetime total, as raw key/value pairs, so a chain reset can
+/// carry them over (`node::reset_chain`). Last-claim heights belong to the
+/// chain they were claimed on and are not included.
+pub fn claimed_totals(state: &State) -> Vec<(Vec<u8>, Vec<u8>)> {
+    state.scan_prefix(PREFIX_FAUCET_TOTAL)
+}
+
+/// Mint a faucet drip to `addr` at chain `height`.
+///
+/// The final claim is trimmed so the lifetime cap is never exceeded. The
+/// remaining cooldown is reported in blocks and, estimated from the target
+/// block time, in seconds. The caller is responsible for making this atomic
+/// with any related writes.
+///
+/// # Returns
+/// - `Ok(u64)` with the address's new balance
+/// - `Err(FaucetError)` if the faucet is disabled or a limit applies
+pub fn claim(
+    state: &mut State,
+    chain: &ChainConfig,
+    addr: &Address,
+    height: u64,
+) -> Result<u64, FaucetError> {
+    let config = &chain.faucet;
+    if !config.enabled {
+        return Err(FaucetError::Disabled);
+    }
+
+    if let Some(last) = last_claim_height(state, addr) {
+        let cooldown_blocks = governance::param(state, governance::PARAM_FAUCET_COOLDOWN_BLOCKS)
+            .unwrap_or(config.cooldown_blocks);
+        let ready_at = last.saturating_add(cooldown_blocks);
+        if height < ready_at {
+            let remaining_blocks = ready_at - height;
+            return Err(FaucetError::Cooldown {
+                remaining_blocks,
+                remaining_secs: remaining_blocks.saturating_mul(chain.target_block_time_secs),
+            });
+        }
+    }
+
+    let claimed = total_claimed(state, addr);
+    let amount = config
+        .amount
+        .min(config.lifetime_cap.saturating_sub(claimed));
+    if amount == 0 {
+        return Err(FaucetError::CapReached {
+            cap: config.lifetime_cap,
+        });
+    }
+
+    let balance = bank::mint(state, addr, amount).map_err(FaucetError::State)?;
+    put_u64(state, faucet_key(PREFIX_FAUCET_LAST, addr), height)?;
+    put_u64(
+        state,
+        faucet_key(PREFIX_FAUCET_TOTAL, addr),
+        claimed + amount,
+    )?;
+
+    Ok(balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FaucetConfig;
+
+    fn config() -> ChainConfig {
+        ChainConfig {
+            target_block_time_secs: 30,
+            faucet: FaucetConfig {
+                enabled: true,
+                amount: 1_000,
+                cooldown_blocks: 10,
+                lifetime_cap: 2_500,
+            },
+            ..ChainConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_claim_cooldown_and_cap() {
+        let mut state = State::in_memory();
+        let addr = [5u8; 32];
+        let config = config();
+
+        // First claim succeeds
+        assert_eq!(claim(&mut state, &config, &addr, 3), Ok(1_000));
+        assert_eq!(last_claim_height(&state, &addr), Some(3));
+
+        // Immediate second claim is refused with the remaining blocks
+        assert_eq!(
+            claim(&mut state, &config, &addr, 3),
+            Err(FaucetError::Cooldown {
+                remaining_blocks: 10,
+                remaining_secs: 300,
+            })
+        );
+        assert_eq!(
+            claim(&mut state, &config, &addr, 9),
+            Err(FaucetError::Cooldown {
+                remaining_blocks: 4,
+                remaining_secs: 120,
+            })
+        );
+
+        // Claim after the cooldown succeeds
+        assert_eq!(claim(&mut state, &config, &addr, 13), Ok(2_000));
+
+        // The third claim is trimmed to the cap, then the cap is final
+        assert_eq!(claim(&mut state, &config, &addr, 23), Ok(2_500));
+        assert_eq!(
+            claim(&mut state, &config, &addr, 33),
+            Err(FaucetError::CapReached { cap: 2_500 })
+        );
+        assert_eq!(total_claimed(&state, &addr), 2_500);
+    }
+
+    #[test]
+    fn test_disabled_faucet_refuses() {
+        let mut state = State::in_memory();
+        let mut config = config();
+        config.faucet.enabled = false;
+        assert_eq!(
+            claim(&mut state, &config, &[5u8; 32], 0),
+            Err(FaucetError::Disabled)
+        );
+        assert_eq!(bank::balance(&state, &[5u8; 32]), 0);
+    }
+}
@@ -2,14 +2,28 @@
 //!
 //! Forge uses Argon2id (memory-hard) followed by SHA-256 to create a
 //! computationally expensive hash that serves as the Proof-of-Work.
+//!
+//! The Argon2id parameters are part of the genesis spec (`genesis.forge`), so
+//! every node on a chain hashes identically. `bench_forge` measures how fast
+//! this machine hashes with them.
+
+/// Human-readable name of the Forge hashing pipeline (reported over RPC).
+pub const FORGE_ALGORITHM: &str = "argon2id+sha256";
+
+use std::fmt;
+use std::time::{Duration, Instant};
 
 use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 /// Configuration for Forge PoW.
 ///
 /// These parameters control the memory and time cost of the Argon2id hashing.
 /// Higher values increase security but also increase computation time.
+/// Missing fields in a config file fall back to their defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ForgeConfig {
     /// Memory cost in KiB (kilobytes).
     pub memory_kib: u32,
@@ -29,6 +43,45 @@ impl Default for ForgeConfig {
     }
 }
 
+impl ForgeConfig {
+    /// Check that Argon2id accepts these parameters.
+    ///
+    /// Argon2id needs at least one iteration and one lane, and at least
+    /// 8 KiB of memory per lane.
+    pub fn validate(&self) -> Result<(), String> {
+        self.params().map(|_| ())
+    }
+
+    /// Commitment to these parameters that the genesis header carries (see
+    /// `core::block::genesis_header`); zero for the defaults.
+    pub fn commitment(&self) -> [u8; 32] {
+        if *self == Self::default() {
+            return [0; 32];
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(b"demiurge-forge-params");
+        for value in [self.memory_kib, self.time_cost, self.lanes] {
+            hasher.update(value.to_le_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    fn params(&self) -> Result<Params, String> {
+        Params::new(self.memory_kib, self.time_cost, self.lanes, None)
+            .map_err(|e| format!("invalid Forge parameters ({}): {}", self, e))
+    }
+}
+
+impl fmt::Display for ForgeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "memory_kib={}, time_cost={}, lanes={}",
+            self.memory_kib, self.time_cost, self.lanes
+        )
+    }
+}
+
 /// Computes the Forge hash for a block header.
 ///
 /// This function:
@@ -52,8 +105,7 @@ pub fn forge_hash(header_bytes: &[u8], nonce: u64, config: &ForgeConfig) -> [u8;
 
     let salt = b"demiurge-forge"; // Fixed salt; this is acceptable for PoW
 
-    let params = Params::new(config.memory_kib, config.time_cost, config.lanes, None)
-        .expect("invalid Forge PoW params");
+    let params = config.params().expect("invalid Forge PoW params");
 
     let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
 
@@ -90,6 +142,66 @@ pub fn meets_difficulty(hash: &[u8; 32], difficulty_target: u128) -> bool {
     value <= difficulty_target
 }
 
+/// Expected number of Forge hashes to find one meeting `difficulty_target`.
+///
+/// A hash meets the target with probability `(target + 1) / 2^128`.
+pub fn expected_hashes(difficulty_target: u128) -> f64 {
+    2f64.powi(128) / (difficulty_target as f64 + 1.0)
+}
+
+/// Result of one `bench_forge` run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgeBenchmark {
+    /// Worker threads hashing in parallel.
+    pub threads: usize,
+    /// Hashes computed across all threads.
+    pub hashes: u64,
+    /// Wall-clock time the run took.
+    pub elapsed: Duration,
+}
+
+impl ForgeBenchmark {
+    /// Hashes per second across all threads.
+    pub fn hashes_per_sec(&self) -> f64 {
+        self.hashes as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Expected time to find a block at `difficulty_target` at this rate, or
+    /// `Duration::MAX` if that is too long to represent (e.g. nothing was
+    /// hashed).
+    pub fn estimated_block_time(&self, difficulty_target: u128) -> Duration {
+        Duration::try_from_secs_f64(expected_hashes(difficulty_target) / self.hashes_per_sec())
+            .unwrap_or(Duration::MAX)
+    }
+}
+
+/// Time `hashes_per_thread` Forge hashes on each of `threads` threads.
+///
+/// Every thread hashes its own run of nonces over a fixed header, as
+/// independent miners would.
+///
+/// # Panics
+/// If `config` is invalid (see `ForgeConfig::validate`).
+pub fn bench_forge(config: &ForgeConfig, threads: usize, hashes_per_thread: u64) -> ForgeBenchmark {
+    let header_bytes = b"demiurge-forge-benchmark";
+    let started = Instant::now();
+    std::thread::scope(|scope| {
+        for thread in 0..threads as u64 {
+            scope.spawn(move || {
+                let first = thread * hashes_per_thread;
+                for nonce in first..first + hashes_per_thread {
+                    forge_hash(header_bytes, nonce, config);
+                }
+            });
+        }
+    });
+    ForgeBenchmark {
+        threads,
+        hashes: threads as u64 * hashes_per_thread,
+        elapsed: started.elapsed(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +231,37 @@ mod tests {
         assert_ne!(hash1, hash2);
     }
 
+    #[test]
+    fn test_forge_hash_depends_on_every_parameter() {
+        let tiny = ForgeConfig {
+            memory_kib: 8,
+            time_cost: 1,
+            lanes: 1,
+        };
+        let base = forge_hash(b"header", 7, &tiny);
+        assert_eq!(forge_hash(b"header", 7, &tiny.clone()), base);
+
+        // Retuning the config changes every hash, so all nodes must agree on it
+        for tuned in [
+            ForgeConfig {
+                memory_kib: 16,
+                ..tiny.clone()
+            },
+            ForgeConfig {
+                time_cost: 2,
+                ..tiny.clone()
+            },
+            ForgeConfig {
+                lanes: 2,
+                memory_kib: 16,
+                ..tiny.clone()
+            },
+        ] {
+            assert_ne!(forge_hash(b"header", 7, &tuned), base, "{}", tuned);
+        }
+        assert_ne!(forge_hash(b"other", 7, &tiny), base);
+    }
+
     #[test]
     fn test_meets_difficulty() {
         // Easy difficulty (max u128) - should always pass
@@ -134,4 +277,41 @@ mod tests {
         assert!(meets_difficulty(&zero_hash, u128::MAX));
         assert!(meets_difficulty(&zero_hash, 0));
     }
+
+    #[test]
+    fn test_validate_rejects_params_argon2_refuses() {
+        assert!(ForgeConfig::default().validate().is_ok());
+        for (memory_kib, time_cost, lanes) in [(8, 0, 1), (8, 1, 0), (4, 1, 1), (15, 1, 2)] {
+            let config = ForgeConfig {
+                memory_kib,
+                time_cost,
+                lanes,
+            };
+            let err = config.validate().unwrap_err();
+            assert!(err.contains("invalid Forge parameters"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_bench_forge_counts_every_thread() {
+        let tiny = ForgeConfig {
+            memory_kib: 8,
+            time_cost: 1,
+            lanes: 1,
+        };
+        let bench = bench_forge(&tiny, 3, 2);
+        assert_eq!(bench.threads, 3);
+        assert_eq!(bench.hashes, 6);
+        assert!(bench.hashes_per_sec() > 0.0);
+
+        // Every hash meets the easiest target; half of them meet a target
+        // of 2^127 - 1
+        assert_eq!(expected_hashes(u128::MAX), 1.0);
+        assert_eq!(expected_hashes(u128::MAX >> 1), 2.0);
+        assert!(bench.estimated_block_time(u128::MAX >> 1) > bench.estimated_block_time(u128::MAX));
+
+        // A run that hashed nothing has no finite estimate
+        let idle = bench_forge(&tiny, 0, 2);
+        assert_eq!(idle.estimated_block_time(u128::MAX), Duration::MAX);
+    }
 }
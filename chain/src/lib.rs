@@ -0,0 +1,22 @@
+//! Demiurge chain library.
+//!
+//! Exposes the node's modules so the `demiurge-chain` binary, integration
+//! tests, and tools can share them. With the `test-support` feature it also
+//! provides [`test_support`], an in-process node harness for end-to-end tests.
+
+pub mod config;
+pub mod core;
+pub mod dead_letter;
+pub mod faucet;
+pub mod forge;
+pub mod mempool;
+pub mod migrations;
+pub mod module_state;
+pub mod node;
+pub mod p2p;
+pub mod replay;
+pub mod rpc;
+pub mod runtime;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+pub mod ws;
@@ -10,49 +10,515 @@
 //! - P2P networking
 //! - Block production (mining)
 //! - Runtime module execution
+//!
+//! Node flags:
+//! - `--tls-cert PATH --tls-key PATH`: serve JSON-RPC over HTTPS with this
+//!   PEM certificate chain and key (overriding `rpc.tls_cert` and
+//!   `rpc.tls_key` in the config); plain HTTP when neither is set
+//! - `--read-only`: serve only query methods from an existing database and
+//!   write nothing to it (sets `rpc.read_only`)
+//!
+//! Subcommands (the node itself runs when none is given):
+//! - `replay [--db PATH] [--blocks FILE] [--from-height N]`: re-execute
+//!   stored blocks and compare the result with the database
+//! - `export-blocks --out FILE [--db PATH]`: write stored blocks to a file
+//! - `module export --module ID --out FILE [--db PATH]`: write one runtime
+//!   module's storage, with a manifest, to a file (see `module_state`)
+//! - `module import --in FILE [--db PATH] [--on-collision fail|overwrite]`:
+//!   load a module export into a stopped node's database; keys that already
+//!   hold other values fail the import unless `--on-collision overwrite`
+//! - `migrate [--db PATH]`: rewrite a stopped node's versioned records at
+//!   their current layout version (see `migrations`); this changes the state
+//!   root, so every node of the chain must migrate at the same height
+//! - `reset [--db PATH] [--keep-faucet-history]`: wipe a stopped node's
+//!   chain state, blocks, and receipts and re-run genesis from the config;
+//!   faucet lifetime totals survive with `--keep-faucet-history`. Only the
+//!   database is touched, not the files next to it
+//! - `bench-forge [--threads N] [--hashes N]`: measure Forge hashes/second
+//!   with the configured parameters on 1..N threads (default: every core),
+//!   `--hashes` per thread (default 16), and estimate the block time at the
+//!   configured difficulty
+//!
+//! Logging flags, accepted anywhere on the command line:
+//! - `--log-format text|json`: human-readable (default) or JSON lines
+//! - `--log-level FILTER`: tracing filter such as `debug` or
+//!   `demiurge_chain=debug`; overrides `RUST_LOG`, which defaults to `info`
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use anyhow::Result;
-use tokio::net::TcpListener;
+use anyhow::{bail, Result};
+use tracing_subscriber::EnvFilter;
+
+use demiurge_chain::config::ChainConfig;
+use demiurge_chain::core::state::State;
+use demiurge_chain::forge::bench_forge;
+use demiurge_chain::migrations::migrate;
+use demiurge_chain::module_state::{
+    export_module, import_module, read_module_export, write_module_export, OnCollision,
+};
+use demiurge_chain::node::{pruned_below, reset_chain, Node, StoredBlocks};
+use demiurge_chain::replay::{read_export, replay, write_export};
+use demiurge_chain::rpc::{load_tls, rpc_router, serve};
+use demiurge_chain::runtime::Runtime;
 
-mod config;
-mod core;
-mod forge;
-mod node;
-mod rpc;
-mod runtime;
+/// Database directory used when `--db` is not given.
+const DEFAULT_DB_PATH: &str = ".demiurge/data";
 
-use crate::node::Node;
-use crate::rpc::rpc_router;
+/// Hashes each `bench-forge` thread computes when `--hashes` is not given.
+const DEFAULT_BENCH_HASHES: u64 = 16;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
     // Initialize tracing for structured logging
-    tracing_subscriber::fmt().with_env_filter("info").init();
+    init_tracing(take_log_flags(&mut args)?)?;
+
+    match args.first().map(String::as_str) {
+        Some("replay") => return run_replay(&args[1..]),
+        Some("export-blocks") => return run_export_blocks(&args[1..]),
+        Some("module") => return run_module(&args[1..]),
+        Some("migrate") => return run_migrate(&args[1..]),
+        Some("reset") => return run_reset(&args[1..]),
+        Some("bench-forge") => return run_bench_forge(&args[1..]),
+        Some(other) if !other.starts_with("--") => bail!(
+            "unknown subcommand '{}'; expected replay, export-blocks, module, migrate, reset, or bench-forge",
+            other
+        ),
+        _ => {}
+    }
+    let read_only = take_switch(&mut args, "--read-only");
+    let flags = parse_flags(&args, &["--tls-cert", "--tls-key"])?;
+
+    let mut config = load_config()?;
+    config.rpc.read_only |= read_only;
+
+    // Determine DB path (create if needed; a read-only node needs one)
+    let db_path = PathBuf::from(DEFAULT_DB_PATH);
+    if config.rpc.read_only {
+        if !db_path.exists() {
+            bail!(
+                "a read-only node needs an existing database at {}",
+                db_path.display()
+            );
+        }
+    } else {
+        std::fs::create_dir_all(&db_path)?;
+    }
 
-    // Determine DB path (create if needed)
-    let db_path = PathBuf::from(".demiurge/data");
-    std::fs::create_dir_all(&db_path)?;
+    if let Some(path) = flags.get("--tls-cert") {
+        config.rpc.tls_cert = Some(PathBuf::from(path));
+    }
+    if let Some(path) = flags.get("--tls-key") {
+        config.rpc.tls_key = Some(PathBuf::from(path));
+    }
+    // Load TLS up front so a bad certificate stops startup before anything else
+    let tls = match (&config.rpc.tls_cert, &config.rpc.tls_key) {
+        (Some(cert), Some(key)) => Some(load_tls(cert, key)?),
+        (None, None) => None,
+        _ => bail!("TLS needs both a certificate (--tls-cert) and a key (--tls-key)"),
+    };
+
+    #[cfg(not(debug_assertions))]
+    if config.faucet.enabled {
+        tracing::warn!("Dev faucet is enabled in a release build; disable it outside devnets");
+    }
 
     // Create node with RocksDB-backed state
-    let node = Arc::new(Node::new(db_path)?);
+    let node = Arc::new(Node::with_config(db_path, config)?);
 
     tracing::info!("Demiurge chain node starting (Phase 2: persistence + RPC)");
+    if node.config.rpc.read_only {
+        tracing::info!("Read-only mode: serving query methods only");
+    }
+
+    // A read-only node admits no transactions and cannot write to its
+    // database, so it runs neither background task
+    if !node.config.rpc.read_only {
+        spawn_background_tasks(&node);
+    }
 
     // Start JSON-RPC server
     let addr: std::net::SocketAddr = "127.0.0.1:8545".parse().unwrap();
-    let listener = TcpListener::bind(addr).await?;
+    let listener = std::net::TcpListener::bind(addr)?;
     let app = rpc_router(node);
 
-    tracing::info!("JSON-RPC server listening on http://{}", addr);
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    tracing::info!("JSON-RPC server listening on {}://{}", scheme, addr);
     tracing::info!(
         "Available methods: cgt_getChainInfo, cgt_getBlockByHeight, cgt_sendRawTransaction"
     );
 
     // Serve requests
-    axum::serve(listener, app).await?;
+    serve(listener, app, tls).await?;
+
+    Ok(())
+}
+
+/// Evict expired mempool transactions every block time, and compact the
+/// database every `storage.compact_interval_secs` if set.
+fn spawn_background_tasks(node: &Arc<Node>) {
+    // Periodically evict mempool transactions past their valid_until_height
+    let sweep_node = node.clone();
+    let sweep_interval = std::time::Duration::from_secs(node.config.target_block_time_secs.max(1));
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(sweep_interval);
+        loop {
+            interval.tick().await;
+            let evicted = sweep_node.sweep_expired_mempool();
+            if evicted > 0 {
+                tracing::info!(
+                    "Evicted {} expired transaction(s) from the mempool",
+                    evicted
+                );
+            }
+        }
+    });
+
+    // Optionally compact the database on a timer
+    if node.config.storage.compact_interval_secs > 0 {
+        let compact_node = node.clone();
+        let compact_interval =
+            std::time::Duration::from_secs(node.config.storage.compact_interval_secs);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + compact_interval,
+                compact_interval,
+            );
+            loop {
+                interval.tick().await;
+                let node = compact_node.clone();
+                if let Err(e) = tokio::task::spawn_blocking(move || node.compact()).await {
+                    tracing::warn!("Background compaction failed: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Remove the value-less flag `name` from `args`, returning whether it was
+/// there.
+fn take_switch(args: &mut Vec<String>, name: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != before
+}
+
+/// Logging options from `--log-format` and `--log-level`.
+#[derive(Default)]
+struct LogOptions {
+    json: bool,
+    level: Option<String>,
+}
+
+/// Remove the logging flags from `args`, wherever they appear.
+fn take_log_flags(args: &mut Vec<String>) -> Result<LogOptions> {
+    let mut options = LogOptions::default();
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag != "--log-format" && flag != "--log-level" {
+            i += 1;
+            continue;
+        }
+        if i + 1 == args.len() {
+            bail!("{} needs a value", flag);
+        }
+        let value = args.remove(i + 1);
+        match (args.remove(i).as_str(), value.as_str()) {
+            ("--log-format", "json") => options.json = true,
+            ("--log-format", "text") => options.json = false,
+            ("--log-format", other) => {
+                bail!("unknown log format '{}'; expected text or json", other)
+            }
+            (_, level) => options.level = Some(level.to_string()),
+        }
+    }
+    Ok(options)
+}
+
+/// Install the global tracing subscriber.
+///
+/// The filter comes from `--log-level`, then `RUST_LOG`, then `info`.
+fn init_tracing(options: LogOptions) -> Result<()> {
+    let filter = match options.level {
+        Some(level) => EnvFilter::try_new(&level)
+            .map_err(|e| anyhow::anyhow!("invalid --log-level '{}': {}", level, e))?,
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    };
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+    if options.json {
+        subscriber.json().init();
+    } else {
+        subscriber.init();
+    }
+    Ok(())
+}
+
+/// Load node configuration (defaults if DEMIURGE_CONFIG is unset).
+fn load_config() -> Result<ChainConfig> {
+    match std::env::var("DEMIURGE_CONFIG") {
+        Ok(path) => {
+            tracing::info!("Loading chain config from {}", path);
+            ChainConfig::load(&PathBuf::from(path))
+        }
+        Err(_) => Ok(ChainConfig::default()),
+    }
+}
+
+/// Parse `--flag value` pairs, rejecting flags not in `allowed`.
+fn parse_flags<'a>(args: &'a [String], allowed: &[&str]) -> Result<HashMap<&'a str, &'a str>> {
+    let mut flags = HashMap::new();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if !allowed.contains(&flag.as_str()) {
+            bail!(
+                "unknown flag '{}'; expected one of {}",
+                flag,
+                allowed.join(", ")
+            );
+        }
+        let Some(value) = args.next() else {
+            bail!("{} needs a value", flag);
+        };
+        flags.insert(flag.as_str(), value.as_str());
+    }
+    Ok(flags)
+}
+
+/// Open an existing database without running genesis or migrations.
+fn open_existing(db_path: &Path) -> Result<State> {
+    if !db_path.exists() {
+        bail!("no database at {}", db_path.display());
+    }
+    State::open_rocksdb(db_path)
+}
+
+fn run_replay(args: &[String]) -> Result<()> {
+    let flags = parse_flags(args, &["--db", "--blocks", "--from-height"])?;
+    let config = load_config()?;
+    let live = open_existing(Path::new(
+        flags.get("--db").copied().unwrap_or(DEFAULT_DB_PATH),
+    ))?;
+    let from_height = match flags.get("--from-height") {
+        Some(height) => height.parse()?,
+        None => config.genesis.start_height,
+    };
+
+    let report = match flags.get("--blocks") {
+        Some(path) => replay(&config, &live, read_export(File::open(path)?)?, from_height)?,
+        None if pruned_below(&live) > 0 => bail!(
+            "blocks below height {} are pruned in this database; replay them from an export with --blocks",
+            pruned_below(&live)
+        ),
+        None => replay(
+            &config,
+            &live,
+            StoredBlocks::new(&live, from_height),
+            from_height,
+        )?,
+    };
+
+    println!(
+        "Replayed {} block(s) to height {}",
+        report.blocks_replayed, report.tip_height
+    );
+    println!("Live state root:     {}", hex::encode(report.expected_root));
+    println!("Replayed state root: {}", hex::encode(report.actual_root));
+    match report.divergence {
+        Some(divergence) => bail!("state diverged at {}", divergence),
+        None => {
+            println!("State matches");
+            Ok(())
+        }
+    }
+}
+
+fn run_export_blocks(args: &[String]) -> Result<()> {
+    let flags = parse_flags(args, &["--db", "--out"])?;
+    let Some(out) = flags.get("--out") else {
+        bail!("export-blocks needs --out FILE");
+    };
+    let config = load_config()?;
+    let state = open_existing(Path::new(
+        flags.get("--db").copied().unwrap_or(DEFAULT_DB_PATH),
+    ))?;
+    if pruned_below(&state) > 0 {
+        bail!(
+            "blocks below height {} are pruned in this database and cannot be exported",
+            pruned_below(&state)
+        );
+    }
+    let count = write_export(
+        StoredBlocks::new(&state, config.genesis.start_height),
+        BufWriter::new(File::create(out)?),
+    )?;
+    println!("Exported {} block(s) to {}", count, out);
+    Ok(())
+}
+
+fn run_module(args: &[String]) -> Result<()> {
+    match args.first().map(String::as_str) {
+        Some("export") => run_module_export(&args[1..]),
+        Some("import") => run_module_import(&args[1..]),
+        _ => bail!("module needs a subcommand: export or import"),
+    }
+}
+
+fn run_module_export(args: &[String]) -> Result<()> {
+    let flags = parse_flags(args, &["--db", "--module", "--out"])?;
+    let (Some(module_id), Some(out)) = (flags.get("--module"), flags.get("--out")) else {
+        bail!("module export needs --module ID and --out FILE");
+    };
+    let config = load_config()?;
+    let state = open_existing(Path::new(
+        flags.get("--db").copied().unwrap_or(DEFAULT_DB_PATH),
+    ))?;
+    let export = export_module(&Runtime::for_chain(&config), &state, module_id)?;
+    write_module_export(&export, BufWriter::new(File::create(out)?))?;
+    println!(
+        "Exported {} key(s) of {} at height {} to {}",
+        export.entries.len(),
+        module_id,
+        export.manifest.source_height,
+        out
+    );
+    Ok(())
+}
+
+fn run_module_import(args: &[String]) -> Result<()> {
+    let flags = parse_flags(args, &["--db", "--in", "--on-collision"])?;
+    let Some(input) = flags.get("--in") else {
+        bail!("module import needs --in FILE");
+    };
+    let on_collision = match flags.get("--on-collision").copied() {
+        None | Some("fail") => OnCollision::Fail,
+        Some("overwrite") => OnCollision::Overwrite,
+        Some(other) => bail!(
+            "unknown --on-collision '{}'; expected fail or overwrite",
+            other
+        ),
+    };
+    let config = load_config()?;
+    let export = read_module_export(BufReader::new(File::open(input)?))?;
+    let mut state = open_existing(Path::new(
+        flags.get("--db").copied().unwrap_or(DEFAULT_DB_PATH),
+    ))?;
+    let report = import_module(
+        &Runtime::for_chain(&config),
+        &mut state,
+        &export,
+        on_collision,
+    )?;
+    println!(
+        "Imported {} key(s) of {} from height {} ({} overwritten)",
+        report.written,
+        export.manifest.module_id,
+        export.manifest.source_height,
+        report.overwritten
+    );
+    Ok(())
+}
+
+fn run_migrate(args: &[String]) -> Result<()> {
+    let flags = parse_flags(args, &["--db"])?;
+    let config = load_config()?;
+    let runtime = Runtime::for_chain(&config);
+    let mut state = open_existing(Path::new(
+        flags.get("--db").copied().unwrap_or(DEFAULT_DB_PATH),
+    ))?;
+    let root_before = runtime.state_root(&state);
+    for report in migrate(&mut state)? {
+        let record = report.record;
+        if report.up_to_date {
+            println!(
+                "{} ({}/{}): already at version {}",
+                record.type_name, record.module_id, record.prefix, record.version
+            );
+        } else {
+            println!(
+                "{} ({}/{}): rewrote {} of {} record(s) at version {}",
+                record.type_name,
+                record.module_id,
+                record.prefix,
+                report.rewritten,
+                report.scanned,
+                record.version
+            );
+        }
+    }
+    println!("State root before: {}", hex::encode(root_before));
+    println!(
+        "State root after:  {}",
+        hex::encode(runtime.state_root(&state))
+    );
+    Ok(())
+}
+
+fn run_reset(args: &[String]) -> Result<()> {
+    let mut args = args.to_vec();
+    let keep_faucet_history = take_switch(&mut args, "--keep-faucet-history");
+    let flags = parse_flags(&args, &["--db"])?;
+    let config = load_config()?;
+    let runtime = Runtime::for_chain(&config);
+    let db_path = Path::new(flags.get("--db").copied().unwrap_or(DEFAULT_DB_PATH));
+    // RocksDB locks the database, so this fails while a node has it open
+    let mut state = open_existing(db_path).map_err(|e| {
+        anyhow::anyhow!(
+            "cannot open {}; stop any node using it first ({})",
+            db_path.display(),
+            e
+        )
+    })?;
+    let report = reset_chain(&mut state, &config, &runtime, keep_faucet_history)?;
+    state.compact();
+    println!("Deleted {} key(s)", report.deleted_keys);
+    if keep_faucet_history {
+        println!(
+            "Kept {} faucet lifetime total(s)",
+            report.kept_faucet_totals
+        );
+    }
+    println!(
+        "Chain reset to genesis {} at height {}",
+        hex::encode(report.tip.hash),
+        report.tip.height
+    );
+    Ok(())
+}
+
+fn run_bench_forge(args: &[String]) -> Result<()> {
+    let flags = parse_flags(args, &["--threads", "--hashes"])?;
+    let config = load_config()?;
+    config.genesis.validate()?;
+    let max_threads = match flags.get("--threads") {
+        Some(threads) => threads.parse()?,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let hashes = match flags.get("--hashes") {
+        Some(hashes) => hashes.parse()?,
+        None => DEFAULT_BENCH_HASHES,
+    };
+    if max_threads == 0 || hashes == 0 {
+        bail!("--threads and --hashes must be at least 1");
+    }
 
+    let forge = &config.genesis.forge;
+    let target = config.genesis.difficulty_target;
+    println!("Forge parameters: {}", forge);
+    println!("Difficulty target: {}", target);
+    for threads in 1..=max_threads {
+        let bench = bench_forge(forge, threads, hashes);
+        println!(
+            "{:>3} thread(s): {:>10.2} hashes/s, estimated block time {:.1}s",
+            threads,
+            bench.hashes_per_sec(),
+            bench.estimated_block_time(target).as_secs_f64()
+        );
+    }
     Ok(())
 }
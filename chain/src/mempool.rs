@@ -0,0 +1,275 @@
+//! Pool of transactions waiting to be included in a block.
+//!
+//! Transactions are kept in arrival order, which block templates try them
+//! in. The pool holds at most `MempoolConfig::max_txs` transactions, and one
+//! sender at most `MempoolConfig::max_txs_per_sender`. Once the pool is full,
+//! a transaction paying a higher fee than the cheapest one pending evicts it;
+//! one paying no more is refused. Per-sender counts are kept in a map, so
+//! admission does not scan the pool for them. Each transaction's hash is
+//! computed once on admission and kept beside it, so duplicate checks and
+//! removals by hash do not rehash the pool.
+//!
+//! Clearing the pool starts a new generation. Admission checks run against
+//! the state before the pool is locked, so callers compare the generation
+//! from before their checks to tell whether the pool was cleared, as a chain
+//! reset does, while they ran.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config::MempoolConfig;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+
+/// Pending transactions and how many each sender has.
+pub struct Mempool {
+    txs: Vec<Transaction>,
+    /// `txs[i].hash()` at `hashes[i]`.
+    hashes: Vec<[u8; 32]>,
+    pending: HashSet<[u8; 32]>,
+    per_sender: HashMap<Address, usize>,
+    max_txs: usize,
+    max_txs_per_sender: usize,
+    /// Times the pool has been cleared.
+    generation: u64,
+}
+
+impl Mempool {
+    pub fn new(config: &MempoolConfig) -> Self {
+        Self {
+            txs: Vec::new(),
+            hashes: Vec::new(),
+            pending: HashSet::new(),
+            per_sender: HashMap::new(),
+            max_txs: config.max_txs,
+            max_txs_per_sender: config.max_txs_per_sender,
+            generation: 0,
+        }
+    }
+
+    /// Admit a transaction.
+    ///
+    /// # Returns
+    /// - `Ok(Some(tx))` with the transaction it evicted from a full pool
+    /// - `Ok(None)` if there was room
+    /// - `Err(String)` if the same transaction is already pending, its sender
+    ///   already has `max_txs_per_sender` pending, or the pool is full and it
+    ///   pays no more than the cheapest pending transaction
+    pub fn insert(&mut self, tx: Transaction) -> Result<Option<Transaction>, String> {
+        let hash = tx.hash();
+        if self.pending.contains(&hash) {
+            return Err("transaction already in mempool".into());
+        }
+        if self.pending_from(&tx.from) >= self.max_txs_per_sender {
+            return Err(format!(
+                "{} already has {} transactions pending, the most one sender may have",
+                tx.from.to_display(),
+                self.max_txs_per_sender
+            ));
+        }
+        let evicted = if self.txs.len() >= self.max_txs {
+            // The cheapest transaction goes, the latest of them on a tie
+            let cheapest = self
+                .txs
+                .iter()
+                .enumerate()
+                .min_by_key(|(index, pending)| (pending.fee, std::cmp::Reverse(*index)))
+                .map(|(index, pending)| (index, pending.fee));
+            match cheapest {
+                Some((index, fee)) if fee < tx.fee => Some(self.remove(index)),
+                Some((_, fee)) => {
+                    return Err(format!(
+                        "mempool is full: a transaction must pay a fee above {} to replace a pending one",
+                        fee
+                    ))
+                }
+                None => return Err("mempool is full".into()),
+            }
+        } else {
+            None
+        };
+        *self.per_sender.entry(tx.from).or_default() += 1;
+        self.pending.insert(hash);
+        self.hashes.push(hash);
+        self.txs.push(tx);
+        Ok(evicted)
+    }
+
+    /// Keep only the transactions `keep` accepts.
+    ///
+    /// # Returns
+    /// The removed transactions, in arrival order
+    pub fn retain(&mut self, mut keep: impl FnMut(&Transaction) -> bool) -> Vec<Transaction> {
+        self.retain_with_hash(|_, tx| keep(tx))
+    }
+
+    /// Remove the transactions whose hashes are in `hashes`.
+    ///
+    /// # Returns
+    /// The removed transactions, in arrival order
+    pub fn remove_hashes(&mut self, hashes: &HashSet<[u8; 32]>) -> Vec<Transaction> {
+        self.retain_with_hash(|hash, _| !hashes.contains(hash))
+    }
+
+    /// Remove every transaction and start a new generation.
+    ///
+    /// # Returns
+    /// The removed transactions, in arrival order
+    pub fn clear(&mut self) -> Vec<Transaction> {
+        self.generation += 1;
+        self.per_sender.clear();
+        self.pending.clear();
+        self.hashes.clear();
+        std::mem::take(&mut self.txs)
+    }
+
+    /// Changes each time the pool is cleared.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Pending transactions in arrival order.
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.txs
+    }
+
+    /// Whether a transaction with this hash is pending.
+    pub fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.pending.contains(hash)
+    }
+
+    /// How many transactions `sender` has pending.
+    pub fn pending_from(&self, sender: &Address) -> usize {
+        self.per_sender.get(sender).copied().unwrap_or(0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    fn remove(&mut self, index: usize) -> Transaction {
+        let tx = self.txs.remove(index);
+        let hash = self.hashes.remove(index);
+        self.pending.remove(&hash);
+        self.uncount(&tx.from);
+        tx
+    }
+
+    fn retain_with_hash(
+        &mut self,
+        mut keep: impl FnMut(&[u8; 32], &Transaction) -> bool,
+    ) -> Vec<Transaction> {
+        let mut removed = Vec::new();
+        let mut kept = Vec::with_capacity(self.txs.len());
+        let mut kept_hashes = Vec::with_capacity(self.hashes.len());
+        for (tx, hash) in self.txs.drain(..).zip(self.hashes.drain(..)) {
+            if keep(&hash, &tx) {
+                kept.push(tx);
+                kept_hashes.push(hash);
+            } else {
+                self.pending.remove(&hash);
+                removed.push(tx);
+            }
+        }
+        self.txs = kept;
+        self.hashes = kept_hashes;
+        for tx in &removed {
+            self.uncount(&tx.from);
+        }
+        removed
+    }
+
+    fn uncount(&mut self, sender: &Address) {
+        if let Some(count) = self.per_sender.get_mut(sender) {
+            *count -= 1;
+            if *count == 0 {
+                self.per_sender.remove(sender);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(from: u8, nonce: u64, fee: u64) -> Transaction {
+        Transaction {
+            from: [from; 32],
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![],
+            fee,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    fn pool(max_txs: usize, max_txs_per_sender: usize) -> Mempool {
+        Mempool::new(&MempoolConfig {
+            max_txs,
+            max_txs_per_sender,
+            ..MempoolConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_full_pool_evicts_the_cheapest_for_a_higher_fee() {
+        let mut mempool = pool(3, 3);
+        assert_eq!(mempool.insert(tx(1, 0, 5)), Ok(None));
+        assert_eq!(mempool.insert(tx(2, 0, 2)), Ok(None));
+        assert_eq!(mempool.insert(tx(3, 0, 2)), Ok(None));
+
+        // Equal to the cheapest is not enough
+        let err = mempool.insert(tx(4, 0, 2)).unwrap_err();
+        assert!(err.contains("fee above 2"), "{}", err);
+
+        // The later of the two cheapest goes
+        assert_eq!(mempool.insert(tx(4, 0, 3)), Ok(Some(tx(3, 0, 2))));
+        assert_eq!(
+            mempool.transactions(),
+            &[tx(1, 0, 5), tx(2, 0, 2), tx(4, 0, 3)]
+        );
+        assert_eq!(mempool.pending_from(&[3; 32]), 0);
+        assert_eq!(mempool.pending_from(&[4; 32]), 1);
+    }
+
+    #[test]
+    fn test_sender_counts_follow_removals() {
+        let mut mempool = pool(10, 2);
+        mempool.insert(tx(1, 0, 0)).unwrap();
+        mempool.insert(tx(1, 1, 0)).unwrap();
+        let err = mempool.insert(tx(1, 2, 0)).unwrap_err();
+        assert!(
+            err.contains("already has 2 transactions pending"),
+            "{}",
+            err
+        );
+        assert_eq!(
+            mempool.insert(tx(1, 0, 0)),
+            Err("transaction already in mempool".to_string())
+        );
+
+        assert_eq!(mempool.retain(|tx| tx.nonce != 0), vec![tx(1, 0, 0)]);
+        assert_eq!(mempool.pending_from(&[1; 32]), 1);
+        assert!(!mempool.contains(&tx(1, 0, 0).hash()));
+        mempool.insert(tx(1, 2, 0)).unwrap();
+        assert_eq!(
+            mempool.remove_hashes(&HashSet::from([tx(1, 2, 0).hash()])),
+            vec![tx(1, 2, 0)]
+        );
+        assert!(mempool.contains(&tx(1, 1, 0).hash()));
+        assert!(!mempool.contains(&tx(1, 2, 0).hash()));
+        mempool.insert(tx(1, 2, 0)).unwrap();
+
+        let generation = mempool.generation();
+        assert_eq!(mempool.clear().len(), 2);
+        assert_eq!(mempool.pending_from(&[1; 32]), 0);
+        assert!(mempool.is_empty());
+        assert_ne!(mempool.generation(), generation);
+    }
+}
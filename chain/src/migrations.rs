@@ -0,0 +1,274 @@
+//! Layout migrations of stored records.
+//!
+//! bincode carries no schema, so a field added to a stored struct cannot be
+//! filled in by `#[serde(default)]`: the old bytes simply end early, or are
+//! misread. Records whose layout evolves are stored behind a version byte
+//! instead (`codec::Versioned`), and each type keeps its older layouts with
+//! an upgrade step from every version to the next. Adding a field means
+//! adding a version and one step; reads chain the steps, so any older record
+//! loads as the current layout. That is the lazy path, and records stay in
+//! their old encoding until they are next written.
+//!
+//! `migrate` is the eager path behind `demiurge-chain migrate`: it rewrites
+//! every record of the types in `RECORD_TYPES` at their current version and
+//! records that version under `chain:schema_version:{module_id}/{prefix}`,
+//! outside module state. Rewritten records encode differently, so the state
+//! root changes even though no value does. Every node of a chain must
+//! therefore migrate at the same height, with the node stopped, or none.
+
+use anyhow::{bail, Result};
+
+use crate::core::codec::{self, Versioned};
+use crate::core::state::State;
+use crate::runtime::abyss_registry::Listing;
+use crate::runtime::fabric_manager::FabricAsset;
+use crate::runtime::nft_dgen::DGenMetadata;
+use crate::runtime::{AeonProfile, StorageKey};
+
+/// Prefix of the node meta keys holding each record type's migrated version.
+const SCHEMA_VERSION_PREFIX: &[u8] = b"chain:schema_version:";
+
+/// A versioned record type and the storage prefix its records live under.
+#[derive(Debug)]
+pub struct RecordType {
+    pub module_id: &'static str,
+    pub prefix: &'static str,
+    pub type_name: &'static str,
+    /// Current layout version.
+    pub version: u8,
+    /// Re-encodes a record of any version at the current one.
+    rewrite: fn(&[u8]) -> bincode::Result<Vec<u8>>,
+}
+
+impl RecordType {
+    const fn of<T: Versioned>(
+        module_id: &'static str,
+        prefix: &'static str,
+        type_name: &'static str,
+    ) -> Self {
+        Self {
+            module_id,
+            prefix,
+            type_name,
+            version: T::VERSION,
+            rewrite: rewrite::<T>,
+        }
+    }
+
+    fn schema_version_key(&self) -> Vec<u8> {
+        let mut key = SCHEMA_VERSION_PREFIX.to_vec();
+        key.extend_from_slice(format!("{}/{}", self.module_id, self.prefix).as_bytes());
+        key
+    }
+}
+
+fn rewrite<T: Versioned>(bytes: &[u8]) -> bincode::Result<Vec<u8>> {
+    codec::encode_versioned(&codec::decode_versioned::<T>(bytes)?)
+}
+
+/// Every versioned record type in module storage.
+pub const RECORD_TYPES: &[RecordType] = &[
+    RecordType::of::<AeonProfile>("avatars_profiles", "profile", "AeonProfile"),
+    RecordType::of::<DGenMetadata>("nft_dgen", "token", "DGenMetadata"),
+    RecordType::of::<Listing>("abyss_registry", "listing", "Listing"),
+    RecordType::of::<FabricAsset>("fabric_manager", "asset", "FabricAsset"),
+];
+
+/// What `migrate` did for one record type.
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationReport {
+    pub record: &'static RecordType,
+    /// Records found under the type's prefix; 0 if it was already migrated.
+    pub scanned: u64,
+    /// Records whose stored bytes changed.
+    pub rewritten: u64,
+    /// Whether the type was already migrated to its current version.
+    pub up_to_date: bool,
+}
+
+/// The version `record`'s records were last migrated to, if ever.
+pub fn schema_version(state: &State, record: &RecordType) -> Option<u8> {
+    match state.get_raw(&record.schema_version_key())?.as_slice() {
+        &[version] => Some(version),
+        _ => None,
+    }
+}
+
+/// Rewrite every record of `RECORD_TYPES` at its current version, atomically.
+///
+/// Types already migrated to their current version are skipped.
+///
+/// # Returns
+/// - `Ok` with one report per record type
+/// - `Err` with nothing written if any record fails to decode
+pub fn migrate(state: &mut State) -> Result<Vec<MigrationReport>> {
+    let mut writes = Vec::new();
+    let mut reports = Vec::new();
+    for record in RECORD_TYPES {
+        let mut report = MigrationReport {
+            record,
+            scanned: 0,
+            rewritten: 0,
+            up_to_date: schema_version(state, record) == Some(record.version),
+        };
+        if !report.up_to_date {
+            let key_prefix = StorageKey::new(record.module_id, record.prefix).into_bytes();
+            for (key, value) in state.scan_prefix(&key_prefix) {
+                report.scanned += 1;
+                let rewritten = match (record.rewrite)(&value) {
+                    Ok(rewritten) => rewritten,
+                    Err(e) => bail!(
+                        "{} at {} does not decode: {}",
+                        record.type_name,
+                        String::from_utf8_lossy(&key),
+                        e
+                    ),
+                };
+                if rewritten != value {
+                    report.rewritten += 1;
+                    writes.push((key, rewritten));
+                }
+            }
+            writes.push((record.schema_version_key(), vec![record.version]));
+        }
+        reports.push(report);
+    }
+
+    state.begin();
+    for (key, value) in writes {
+        if let Err(e) = state.put_raw(key, value) {
+            state.rollback();
+            return Err(e);
+        }
+    }
+    state.commit()?;
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{get_aeon_profile, get_listing, Runtime};
+
+    /// An Aeon profile as stored before versioning (layout version 1, no
+    /// prefix): address 0x11.., "nyx", no bio, handle "nyx", 120 XP, Syzygy
+    /// Score 40, level 1, the badge "genesis", created at height 5.
+    const PROFILE_V1: &str = "1111111111111111111111111111111111111111111111111111111111111111\
+        03000000000000006e7978\
+        00\
+        0103000000000000006e7978\
+        7800000000000000\
+        2800000000000000\
+        01000000\
+        0100000000000000070000000000000067656e65736973\
+        0500000000000000";
+
+    /// A listing as stored before versioning: id 7, NFT 3, seller 0xaa..,
+    /// 1500 CGT, active.
+    const LISTING_V1: &str = "0700000000000000\
+        0300000000000000\
+        aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+        dc05000000000000\
+        01";
+
+    fn key(module_id: &str, prefix: &str) -> StorageKey {
+        StorageKey::new(module_id, prefix)
+    }
+
+    fn state_with_fixtures() -> State {
+        let mut state = State::in_memory();
+        state
+            .put_raw(
+                key("avatars_profiles", "profile")
+                    .address(&[0x11; 32])
+                    .into_bytes(),
+                hex::decode(PROFILE_V1).unwrap(),
+            )
+            .unwrap();
+        state
+            .put_raw(
+                key("abyss_registry", "listing").u64(7).into_bytes(),
+                hex::decode(LISTING_V1).unwrap(),
+            )
+            .unwrap();
+        state
+    }
+
+    fn assert_fixtures_load(state: &State) {
+        let profile = get_aeon_profile(state, &[0x11; 32]).unwrap();
+        assert_eq!(profile.display_name, "nyx");
+        assert_eq!(profile.handle.as_deref(), Some("nyx"));
+        assert_eq!(profile.gnosis_xp, 120);
+        assert_eq!(profile.syzygy_score, 40);
+        assert_eq!(profile.badges, vec!["genesis".to_string()]);
+        // Filled in by the 1 -> 2 and 2 -> 3 steps
+        assert_eq!(profile.avatar_uri, None);
+        assert_eq!(profile.syzygy_decayed_at, 5);
+
+        let listing = get_listing(state, 7).unwrap();
+        assert_eq!(listing.token_id, 3);
+        assert_eq!(listing.seller, [0xaa; 32]);
+        assert_eq!(listing.price_cgt, 1500);
+        assert!(listing.active);
+        assert_eq!(listing.allowed_buyer, None);
+        assert!(!listing.escrowed);
+    }
+
+    #[test]
+    fn test_record_types_are_module_prefixes() {
+        let runtime = Runtime::with_default_modules();
+        for record in RECORD_TYPES {
+            let prefixes = runtime.storage_prefixes(record.module_id).unwrap();
+            assert!(prefixes.contains(&record.prefix), "{}", record.type_name);
+        }
+    }
+
+    #[test]
+    fn test_v1_records_load_through_chained_upgrades() {
+        let state = state_with_fixtures();
+        assert_fixtures_load(&state);
+    }
+
+    #[test]
+    fn test_migrate_rewrites_once_and_records_versions() {
+        let mut state = state_with_fixtures();
+        let reports = migrate(&mut state).unwrap();
+        for report in &reports {
+            let expected = u64::from(matches!(report.record.type_name, "AeonProfile" | "Listing"));
+            assert_eq!((report.scanned, report.rewritten), (expected, expected));
+            assert_eq!(
+                schema_version(&state, report.record),
+                Some(report.record.version)
+            );
+        }
+        let listing_key = key("abyss_registry", "listing").u64(7).into_bytes();
+        assert_eq!(
+            state.get_raw(&listing_key).unwrap()[0],
+            <Listing as Versioned>::VERSION
+        );
+        assert_fixtures_load(&state);
+
+        // A second run finds every type up to date
+        let before = state.scan_prefix(b"");
+        assert!(migrate(&mut state)
+            .unwrap()
+            .iter()
+            .all(|report| report.up_to_date && report.scanned == 0));
+        assert_eq!(state.scan_prefix(b""), before);
+    }
+
+    #[test]
+    fn test_migrate_is_atomic() {
+        let mut state = state_with_fixtures();
+        state
+            .put_raw(
+                key("fabric_manager", "asset").bytes(&[1; 32]).into_bytes(),
+                vec![0xff],
+            )
+            .unwrap();
+        let before = state.scan_prefix(b"");
+        let err = migrate(&mut state).unwrap_err();
+        assert!(err.to_string().contains("FabricAsset"), "{}", err);
+        assert_eq!(state.scan_prefix(b""), before);
+    }
+}
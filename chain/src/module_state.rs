@@ -0,0 +1,373 @@
+//! Export and import of a single runtime module's storage.
+//!
+//! Seeds a devnet with one module's state from another chain, such as the
+//! NFTs of `nft_dgen` or the listings of `abyss_registry`, without copying
+//! balances or anything else. An export holds every key under the prefixes
+//! the module declares (`RuntimeModule::storage_prefixes`) and a manifest with
+//! the source chain height and the number of keys per prefix.
+//!
+//! Modules keep their next-id counter under `COUNTER_PREFIX`, and declare
+//! any other counter-like keys, such as nonces or the stats that number
+//! royalty payouts, through `RuntimeModule::reconcile_import`. Importing
+//! never moves those back, so ids minted afterwards don't collide with
+//! imported ones; other keys that already hold a different value are
+//! collisions, handled as `OnCollision` says. Overwriting can leave a
+//! module's indexes disagreeing with its records (e.g. an NFT listed under a
+//! local owner it no longer has); `admin_verifyState` reports those.
+//!
+//! Imported state was not produced by blocks, so replaying the chain will
+//! not reproduce it (see `replay`).
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::codec;
+use crate::core::state::State;
+use crate::node::chain_tip;
+use crate::runtime::{Runtime, StorageKey};
+
+/// First bytes of a module export file.
+const EXPORT_MAGIC: &[u8; 8] = b"DMGMOD01";
+
+/// Storage prefix of the `u64` next-id counter of the modules that mint ids
+/// (`nft_dgen`, `abyss_registry`, `escrow`, `governance`, `quests`).
+pub const COUNTER_PREFIX: &str = "counter";
+
+/// What an export holds and where it came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleManifest {
+    pub module_id: String,
+    /// Chain tip height of the exported state.
+    pub source_height: u64,
+    /// Keys exported under each storage prefix.
+    pub key_counts: BTreeMap<String, u64>,
+}
+
+/// A module's keys with their manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleExport {
+    pub manifest: ModuleManifest,
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+/// How an import treats a key that already holds a different value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnCollision {
+    /// Refuse the whole import.
+    Fail,
+    /// Replace the existing value.
+    Overwrite,
+}
+
+/// What an import changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Keys written, including overwritten ones and raised counters.
+    pub written: u64,
+    /// Keys whose different existing value was replaced.
+    pub overwritten: u64,
+}
+
+/// Storage prefixes of `module_id` in `runtime`, as key prefixes.
+fn module_prefixes(runtime: &Runtime, module_id: &str) -> Result<Vec<(&'static str, Vec<u8>)>> {
+    let Some(prefixes) = runtime.storage_prefixes(module_id) else {
+        bail!(
+            "unknown module '{}'; expected one of {}",
+            module_id,
+            runtime.module_ids().join(", ")
+        );
+    };
+    Ok(prefixes
+        .iter()
+        .map(|prefix| (*prefix, StorageKey::new(module_id, prefix).into_bytes()))
+        .collect())
+}
+
+/// Collect every key of `module_id` from `state`.
+pub fn export_module(runtime: &Runtime, state: &State, module_id: &str) -> Result<ModuleExport> {
+    let mut key_counts = BTreeMap::new();
+    let mut entries = Vec::new();
+    for (prefix, key_prefix) in module_prefixes(runtime, module_id)? {
+        let found = state.scan_prefix(&key_prefix);
+        key_counts.insert(prefix.to_string(), found.len() as u64);
+        entries.extend(found);
+    }
+    Ok(ModuleExport {
+        manifest: ModuleManifest {
+            module_id: module_id.to_string(),
+            source_height: chain_tip(state).map_or(0, |tip| tip.height),
+            key_counts,
+        },
+        entries,
+    })
+}
+
+/// Write `bytes` with a little-endian `u64` length before it.
+fn write_entry(out: &mut impl Write, bytes: &[u8]) -> Result<()> {
+    out.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    out.write_all(bytes)?;
+    Ok(())
+}
+
+/// Read one entry written by `write_entry`, or `None` at the end of input.
+fn read_entry(input: &mut impl Read) -> Result<Option<Vec<u8>>> {
+    let mut len = [0u8; 8];
+    match input.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u64::from_le_bytes(len);
+    if len > codec::STATE_LIMIT {
+        bail!("module export entry of {} bytes is too large", len);
+    }
+    let mut bytes = vec![0u8; len as usize];
+    input.read_exact(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+/// Write a module export: `EXPORT_MAGIC`, then the manifest and each
+/// key-value pair, bincode-encoded behind a little-endian `u64` length.
+pub fn write_module_export(export: &ModuleExport, mut out: impl Write) -> Result<()> {
+    out.write_all(EXPORT_MAGIC)?;
+    write_entry(&mut out, &codec::encode(&export.manifest)?)?;
+    for entry in &export.entries {
+        write_entry(&mut out, &codec::encode(entry)?)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Read a module export written by `write_module_export`, checking its
+/// entries against the manifest.
+pub fn read_module_export(mut input: impl Read) -> Result<ModuleExport> {
+    let mut magic = [0u8; 8];
+    input.read_exact(&mut magic)?;
+    if &magic != EXPORT_MAGIC {
+        bail!("not a module export");
+    }
+    let Some(manifest) = read_entry(&mut input)? else {
+        bail!("module export has no manifest");
+    };
+    let manifest: ModuleManifest = codec::decode_state_exact(&manifest)?;
+    let mut entries = Vec::new();
+    while let Some(entry) = read_entry(&mut input)? {
+        entries.push(codec::decode_state_exact(&entry)?);
+    }
+    let expected: u64 = manifest.key_counts.values().sum();
+    if entries.len() as u64 != expected {
+        bail!(
+            "module export holds {} keys, but its manifest lists {}",
+            entries.len(),
+            expected
+        );
+    }
+    Ok(ModuleExport { manifest, entries })
+}
+
+/// Write an export's keys into `state`, atomically.
+///
+/// Every key must belong to one of the module's prefixes in `runtime`.
+/// Counter-like keys are reconciled by the module
+/// (`RuntimeModule::reconcile_import`) and never collide.
+///
+/// # Returns
+/// - `Ok(ImportReport)` once every key is written
+/// - `Err` with nothing written if a key falls outside the module, a
+///   counter-like value does not decode, or, with `OnCollision::Fail`, any
+///   other key already holds a different value
+pub fn import_module(
+    runtime: &Runtime,
+    state: &mut State,
+    export: &ModuleExport,
+    on_collision: OnCollision,
+) -> Result<ImportReport> {
+    let module_id = &export.manifest.module_id;
+    let prefixes = module_prefixes(runtime, module_id)?;
+
+    let mut writes = Vec::new();
+    let mut report = ImportReport::default();
+    for (key, value) in &export.entries {
+        if !prefixes
+            .iter()
+            .any(|(_, key_prefix)| key.starts_with(key_prefix))
+        {
+            bail!(
+                "key {} is outside module {}",
+                String::from_utf8_lossy(key),
+                module_id
+            );
+        }
+        let local = state.get_raw(key);
+        match runtime.reconcile_import(module_id, key, local.as_deref(), value) {
+            Ok(Some(reconciled)) => {
+                if local.as_ref() != Some(&reconciled) {
+                    writes.push((key.clone(), reconciled));
+                }
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => bail!(
+                "key {} of module {} does not decode: {}",
+                String::from_utf8_lossy(key),
+                module_id,
+                e
+            ),
+        }
+        match local {
+            Some(local) if local == *value => continue,
+            Some(_) if on_collision == OnCollision::Fail => bail!(
+                "key {} already holds a different value",
+                String::from_utf8_lossy(key)
+            ),
+            Some(_) => report.overwritten += 1,
+            None => {}
+        }
+        writes.push((key.clone(), value.clone()));
+    }
+
+    state.begin();
+    for (key, value) in writes {
+        if let Err(e) = state.put_raw(key, value) {
+            state.rollback();
+            return Err(e);
+        }
+        report.written += 1;
+    }
+    state.commit()?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::abyss_registry::{get_royalty_stats, RoyaltyStats};
+    use crate::runtime::bank_cgt::{
+        consume_nonce, get_balance_cgt, get_nonce_cgt, mint as mint_cgt,
+    };
+    use crate::runtime::nft_dgen::{get_nft, mint, MintDgenParams};
+
+    fn mint_nft(state: &mut State, owner: u8, fabric: u8) -> u64 {
+        let params = MintDgenParams {
+            fabric_root_hash: [fabric; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
+        };
+        mint(state, &[owner; 32], &params).unwrap()
+    }
+
+    /// A state with three NFTs and some CGT, exported through a file.
+    fn nft_export(runtime: &Runtime) -> ModuleExport {
+        let mut source = State::in_memory();
+        mint_cgt(&mut source, &[1; 32], 500).unwrap();
+        for fabric in 0..3 {
+            mint_nft(&mut source, 1, fabric);
+        }
+        let export = export_module(runtime, &source, "nft_dgen").unwrap();
+        assert_eq!(export.manifest.key_counts["token"], 3);
+        assert_eq!(export.manifest.key_counts["counter"], 1);
+
+        let mut file = Vec::new();
+        write_module_export(&export, &mut file).unwrap();
+        let read = read_module_export(file.as_slice()).unwrap();
+        assert_eq!(read, export);
+        read
+    }
+
+    #[test]
+    fn test_import_reconciles_counter() {
+        let runtime = Runtime::with_default_modules();
+        let export = nft_export(&runtime);
+
+        let mut target = State::in_memory();
+        let report = import_module(&runtime, &mut target, &export, OnCollision::Fail).unwrap();
+        assert_eq!(report.written, export.entries.len() as u64);
+        assert_eq!(get_nft(&target, 2).unwrap().owner, [1; 32]);
+        // Balances stay behind
+        assert_eq!(get_balance_cgt(&target, &[1; 32]), 0);
+
+        // The next mint follows the imported ids
+        assert_eq!(mint_nft(&mut target, 2, 9), 3);
+        // and a second import leaves the raised counter alone
+        let report = import_module(&runtime, &mut target, &export, OnCollision::Fail).unwrap();
+        assert_eq!(report, ImportReport::default());
+        assert_eq!(mint_nft(&mut target, 2, 10), 4);
+    }
+
+    #[test]
+    fn test_import_keeps_declared_counters_moving_forward() {
+        let runtime = Runtime::with_default_modules();
+        let stats_key = |recipient: u8| {
+            StorageKey::new("abyss_registry", "royalties")
+                .address(&[recipient; 32])
+                .into_bytes()
+        };
+        let stats = |sale_count: u64| RoyaltyStats {
+            total_amount: sale_count * 10,
+            sale_count,
+            last_height: sale_count,
+        };
+        let put_stats = |state: &mut State, recipient: u8, sale_count: u64| {
+            state
+                .put_raw(
+                    stats_key(recipient),
+                    codec::encode(&stats(sale_count)).unwrap(),
+                )
+                .unwrap();
+        };
+
+        let mut source = State::in_memory();
+        put_stats(&mut source, 1, 3);
+        put_stats(&mut source, 2, 3);
+        consume_nonce(&mut source, &[1; 32], 3).unwrap();
+        let mut target = State::in_memory();
+        put_stats(&mut target, 1, 5);
+        put_stats(&mut target, 2, 1);
+        consume_nonce(&mut target, &[1; 32], 6).unwrap();
+
+        for module_id in ["abyss_registry", "bank_cgt"] {
+            let export = export_module(&runtime, &source, module_id).unwrap();
+            import_module(&runtime, &mut target, &export, OnCollision::Fail).unwrap();
+        }
+        // The stats that counted more sales win, so payouts stay numbered
+        assert_eq!(get_royalty_stats(&target, &[1; 32]), stats(5));
+        assert_eq!(get_royalty_stats(&target, &[2; 32]), stats(3));
+        // and the nonce is not lowered
+        assert_eq!(get_nonce_cgt(&target, &[1; 32]), 7);
+    }
+
+    #[test]
+    fn test_import_collisions() {
+        let runtime = Runtime::with_default_modules();
+        let export = nft_export(&runtime);
+
+        // A local NFT 0 owned by someone else collides
+        let mut target = State::in_memory();
+        mint_nft(&mut target, 2, 7);
+        let before = target.scan_prefix(b"");
+        let err = import_module(&runtime, &mut target, &export, OnCollision::Fail).unwrap_err();
+        assert!(err.to_string().contains("already holds a different value"));
+        assert_eq!(target.scan_prefix(b""), before);
+
+        let report = import_module(&runtime, &mut target, &export, OnCollision::Overwrite).unwrap();
+        assert_eq!(report.overwritten, 1);
+        assert_eq!(get_nft(&target, 0).unwrap().owner, [1; 32]);
+        assert_eq!(mint_nft(&mut target, 2, 8), 3);
+
+        // Keys outside the module are refused
+        let mut foreign = export.clone();
+        foreign
+            .entries
+            .push((b"bank_cgt/balance/x".to_vec(), vec![1]));
+        let err =
+            import_module(&runtime, &mut target, &foreign, OnCollision::Overwrite).unwrap_err();
+        assert!(err.to_string().contains("outside module nft_dgen"));
+    }
+}
@@ -3,22 +3,320 @@
 //! The Node struct owns the persistent state, maintains a mempool for pending
 //! transactions, and tracks chain height. In Phase 5, State is wrapped in
 //! Arc<Mutex<...>> for thread-safe concurrent reads from JSON-RPC handlers.
+//! A panic while holding one of the node's locks does not poison it for good:
+//! the next caller recovers the lock and the node keeps serving.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
-use bincode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::broadcast;
 
-use crate::config::{GENESIS_ARCHON_ADDRESS, GENESIS_ARCHON_INITIAL_BALANCE};
-use crate::core::block::Block;
+use crate::config::{ChainConfig, GenesisConfig, KeepBlocks};
+use crate::core::address_stats::{address_stats, AddressStats};
+use crate::core::block::{
+    genesis_block, genesis_header, tx_root, Block, BlockHeader, TxInclusionProof,
+};
+use crate::core::codec;
+use crate::core::merkle;
+use crate::core::receipt::{
+    forget_pruned_receipts, get_receipt, prune_receipts, pruned_receipt_height, Event, Receipt,
+};
 use crate::core::state::State;
-use crate::core::transaction::{Address, Transaction};
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+use crate::core::tx_index::{prune_transaction, transactions_by_address};
+use crate::dead_letter::{DeadLetters, FailedTransaction, Failure};
+use crate::faucet::{self, FaucetError, FaucetReceipt};
+use crate::forge::ForgeConfig;
+use crate::mempool::Mempool;
+use crate::p2p::{Handshake, PeerInfo, PeerManager, PROTOCOL_VERSION};
+use crate::runtime::bank_cgt::{check_memo, decode_transfer, TransferToHandleParams};
+use crate::runtime::batch;
+use crate::runtime::module_api::{avatars, bank, nft};
+use crate::runtime::nft_dgen::{nft_storage_key, MintDgenParams};
+use crate::runtime::storage_key::migrate_legacy_keys;
 use crate::runtime::{
-    get_balance_cgt, get_fabric_asset, get_listing, get_nft, get_nfts_by_owner, is_archon,
-    AvatarsProfilesModule, BankCgtModule, FabricRootHash, ListingId, NftId, RuntimeModule,
+    account_exists, backfill_archon_index, backfill_escrow_account, balance_of_asset,
+    check_signature, get_aeon_profile, get_asset, get_balance_cgt, get_bond_unbondings, get_bonded,
+    get_escrow, get_fabric_asset, get_listing, get_multisig, get_nft, get_nfts_by_owner,
+    get_nonce_cgt, get_progress, get_proposal, get_royalty_payouts, get_royalty_stats, get_stake,
+    get_unbondings, is_archon, list_archons, list_proposals, list_quests, module_account_owner,
+    module_address, CallSchema, EscrowId, FabricRootHash, ListingId, ModuleInfo, MultisigAccount,
+    NftId, ProposalId, QuestId, RoyaltyStats, Runtime, RuntimeFactory,
 };
 
+/// Storage prefix for finalized blocks, keyed by big-endian height.
+const BLOCK_KEY_PREFIX: &[u8] = b"chain:block:";
+
+/// Key recording the hash of the genesis block this data directory was
+/// initialized with.
+const KEY_GENESIS_HASH: &[u8] = b"chain:genesis_hash";
+
+/// Key recording the Forge parameters this data directory was initialized
+/// with.
+const KEY_FORGE_CONFIG: &[u8] = b"chain:forge_config";
+
+/// Key recording `genesis_fingerprint` of the genesis spec every step of
+/// `apply_genesis` last completed against.
+const KEY_GENESIS_APPLIED: &[u8] = b"chain:genesis_applied";
+
+/// Version of the steps in `apply_genesis`; bump it when adding a step so
+/// existing data directories run the steps once more.
+const GENESIS_STEPS_VERSION: u8 = 1;
+
+/// Key recording the chain tip (`ChainTip`). It is written in the same
+/// atomic commit as the block it points to.
+const KEY_CHAIN_TIP: &[u8] = b"chain:tip";
+
+/// Key recording the height below which block bodies and receipts have been
+/// pruned (the genesis block has none to prune).
+const KEY_PRUNED_BELOW: &[u8] = b"chain:pruned_below";
+
+/// Key recording the height below which the markers of pruned receipts have
+/// been dropped as well.
+const KEY_PRUNED_MARKERS_BELOW: &[u8] = b"chain:pruned_markers_below";
+
+/// Most blocks pruned after a single finalized block, so catching up on a
+/// long history (e.g. after switching from archive) never stalls block
+/// production for long.
+pub const PRUNE_BATCH_BLOCKS: u64 = 32;
+
+fn block_key(height: u64) -> Vec<u8> {
+    let mut key = BLOCK_KEY_PREFIX.to_vec();
+    key.extend_from_slice(&height.to_be_bytes());
+    key
+}
+
+fn load_block(state: &State, height: u64) -> Option<Block> {
+    state
+        .get_raw(&block_key(height))
+        .and_then(|bytes| codec::decode_versioned(&bytes).ok())
+}
+
+/// Lowest height whose block body and receipts are still stored; 0 while
+/// nothing has been pruned.
+pub fn pruned_below(state: &State) -> u64 {
+    state
+        .get_raw(KEY_PRUNED_BELOW)
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+        .unwrap_or(0)
+}
+
+/// Whether the body of the block at `height`, above the genesis block at
+/// `genesis_height`, has been pruned.
+fn is_pruned(state: &State, genesis_height: u64, height: u64) -> bool {
+    height > genesis_height && height < pruned_below(state)
+}
+
+/// Prune the next batch of blocks outside the `keep` most recent up to `tip`.
+///
+/// Each pruned block is stored again with its header only, its transactions
+/// are dropped from the transactions-by-address index, and their receipts
+/// (with their events) are deleted. The markers left for those receipts are
+/// kept for `keep` blocks more, then dropped too. At most
+/// `PRUNE_BATCH_BLOCKS` blocks are pruned per call, and never the genesis
+/// block at `genesis_height`.
+///
+/// # Returns
+/// The number of blocks pruned
+fn prune_blocks(
+    state: &mut State,
+    genesis_height: u64,
+    keep: u64,
+    tip: u64,
+) -> Result<u64, String> {
+    let start = pruned_below(state).max(genesis_height + 1);
+    let first_kept = (tip.saturating_sub(keep) + 1).min(start + PRUNE_BATCH_BLOCKS);
+    if first_kept <= start {
+        forget_pruned_markers(state, genesis_height, keep, tip)?;
+        return Ok(0);
+    }
+    for height in start..first_kept {
+        let Some(block) = load_block(state, height) else {
+            continue;
+        };
+        let mut tx_hashes = Vec::with_capacity(block.body.len());
+        for tx in &block.body {
+            let tx_hash = tx.hash();
+            // A transaction included again later failed here, and failed
+            // calls are indexed with no events
+            let events = get_receipt(state, &tx_hash)
+                .filter(|receipt| receipt.height == height)
+                .map_or_else(Vec::new, |receipt| receipt.events);
+            prune_transaction(state, tx, &events)?;
+            tx_hashes.push(tx_hash);
+        }
+        prune_receipts(state, height, &tx_hashes)?;
+        let header_only = Block {
+            header: block.header,
+            body: Vec::new(),
+        };
+        let bytes = codec::encode_versioned(&header_only).map_err(|e| e.to_string())?;
+        state
+            .put_raw(block_key(height), bytes)
+            .map_err(|e| e.to_string())?;
+    }
+    let bytes = codec::encode(&first_kept).map_err(|e| e.to_string())?;
+    state
+        .put_raw(KEY_PRUNED_BELOW.to_vec(), bytes)
+        .map_err(|e| e.to_string())?;
+    forget_pruned_markers(state, genesis_height, keep, tip)?;
+    Ok(first_kept - start)
+}
+
+/// Drop the receipt markers of the next batch of pruned blocks outside the
+/// `2 * keep` most recent up to `tip`.
+fn forget_pruned_markers(
+    state: &mut State,
+    genesis_height: u64,
+    keep: u64,
+    tip: u64,
+) -> Result<(), String> {
+    let start = state
+        .get_raw(KEY_PRUNED_MARKERS_BELOW)
+        .and_then(|bytes| codec::decode_state::<u64>(&bytes).ok())
+        .unwrap_or(0)
+        .max(genesis_height + 1);
+    let first_kept = (tip.saturating_sub(keep.saturating_mul(2)) + 1)
+        .min(pruned_below(state))
+        .min(start + PRUNE_BATCH_BLOCKS);
+    if first_kept <= start {
+        return Ok(());
+    }
+    for height in start..first_kept {
+        forget_pruned_receipts(state, height)?;
+    }
+    let bytes = codec::encode(&first_kept).map_err(|e| e.to_string())?;
+    state
+        .put_raw(KEY_PRUNED_MARKERS_BELOW.to_vec(), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Iterator over the finalized blocks stored in a node's state, in height
+/// order.
+///
+/// Stops at the first height with no stored block, so on a node's own data
+/// it yields the genesis block through the tip. Pruned blocks (see
+/// `pruned_below`) come back with empty bodies.
+pub struct StoredBlocks<'a> {
+    state: &'a State,
+    next_height: u64,
+}
+
+impl<'a> StoredBlocks<'a> {
+    /// Iterate stored blocks starting at `from_height`.
+    pub fn new(state: &'a State, from_height: u64) -> Self {
+        Self {
+            state,
+            next_height: from_height,
+        }
+    }
+}
+
+impl Iterator for StoredBlocks<'_> {
+    type Item = Block;
+
+    fn next(&mut self) -> Option<Block> {
+        let block = load_block(self.state, self.next_height)?;
+        self.next_height += 1;
+        Some(block)
+    }
+}
+
+/// The last finalized block: its height and header hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainTip {
+    pub height: u64,
+    pub hash: [u8; 32],
+}
+
+/// The chain tip recorded in `state`, if it has been initialized.
+pub fn chain_tip(state: &State) -> Option<ChainTip> {
+    state
+        .get_raw(KEY_CHAIN_TIP)
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+}
+
+/// What `reset_chain` wiped and kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetReport {
+    /// Keys deleted: all of chain state, blocks, receipts, and indexes.
+    pub deleted_keys: u64,
+    /// Faucet lifetime totals carried over.
+    pub kept_faucet_totals: u64,
+    /// The new chain tip, the genesis block.
+    pub tip: ChainTip,
+}
+
+/// Wipe every key in `state` and re-run genesis from `config`, atomically.
+///
+/// With `keep_faucet_history`, each address's faucet lifetime total
+/// survives, so the cap still applies; cooldowns start over. Nothing outside
+/// the database, such as the config file, is touched.
+///
+/// # Returns
+/// - `Ok(ResetReport)` once the new genesis is committed
+/// - `Err` with nothing written if genesis or module account registration
+///   fails, or the node is read-only
+pub fn reset_chain(
+    state: &mut State,
+    config: &ChainConfig,
+    runtime: &Runtime,
+    keep_faucet_history: bool,
+) -> Result<ResetReport> {
+    if config.rpc.read_only {
+        anyhow::bail!("a read-only node cannot reset its chain");
+    }
+    let kept = if keep_faucet_history {
+        faucet::claimed_totals(state)
+    } else {
+        Vec::new()
+    };
+    let keys: Vec<Vec<u8>> = state
+        .scan_prefix(b"")
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+
+    state.begin();
+    let reset = keys
+        .iter()
+        .try_for_each(|key| state.delete_raw(key))
+        .and_then(|()| {
+            kept.iter()
+                .try_for_each(|(key, value)| state.put_raw(key.clone(), value.clone()))
+        })
+        .and_then(|()| init_genesis_state(state, &config.genesis))
+        .and_then(|()| {
+            runtime
+                .register_module_accounts(state)
+                .map_err(|e| anyhow::anyhow!("Failed to register module accounts: {}", e))
+        });
+    if let Err(e) = reset {
+        state.rollback();
+        return Err(e);
+    }
+    state.commit()?;
+
+    let tip = chain_tip(state).ok_or_else(|| anyhow::anyhow!("genesis recorded no chain tip"))?;
+    Ok(ResetReport {
+        deleted_keys: keys.len() as u64,
+        kept_faucet_totals: kept.len() as u64,
+        tip,
+    })
+}
+
+/// Record `tip` as the chain tip; callers write it in the same scope as the
+/// block it points to.
+pub(crate) fn put_chain_tip(state: &mut State, tip: &ChainTip) -> Result<()> {
+    state.put_raw(KEY_CHAIN_TIP.to_vec(), codec::encode(tip)?)
+}
+
 /// Chain information returned by JSON-RPC queries.
 #[derive(Clone)]
 pub struct ChainInfo {
@@ -26,27 +324,402 @@ pub struct ChainInfo {
     pub height: u64,
 }
 
+/// A consistent read-only view of the committed state (see `Node::snapshot`).
+///
+/// On RocksDB it reads a database snapshot, which later commits do not
+/// change. A backend without snapshots is instead read under the state's
+/// read lock, held until the view is dropped; blocks commit under the write
+/// lock, so either way every read sees the same block's state.
+pub struct Snapshot<'a> {
+    state: SnapshotState<'a>,
+}
+
+enum SnapshotState<'a> {
+    Backend(Box<State>),
+    Locked(RwLockReadGuard<'a, State>),
+}
+
+impl std::ops::Deref for Snapshot<'_> {
+    type Target = State;
+
+    fn deref(&self) -> &State {
+        match &self.state {
+            SnapshotState::Backend(state) => state,
+            SnapshotState::Locked(state) => state,
+        }
+    }
+}
+
+/// An NFT's owner with a Merkle proof of its metadata entry.
+#[derive(Debug, Clone)]
+pub struct NftOwnerProof {
+    pub owner: Address,
+    /// Chain height the state was read at.
+    pub height: u64,
+    /// Hash of the block at `height`.
+    pub block_hash: [u8; 32],
+    /// `Runtime::state_root` the proof verifies against.
+    pub state_root: [u8; 32],
+    /// Whether the header of the block at `height` commits to `state_root`.
+    /// Not when state was written outside blocks since (dev RPCs), or the
+    /// block is from before headers carried state roots.
+    pub committed: bool,
+    /// Proof of the encoded `DGenMetadata` under the NFT's storage key.
+    pub proof: merkle::Proof,
+}
+
+/// Build and runtime details of a node, assembled once at startup.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeInfo {
+    /// Package version of the node binary.
+    pub version: &'static str,
+    /// Git commit the node was built from, or "unknown".
+    pub git_commit: &'static str,
+    /// Cargo profile the node was built with (`debug` or `release`).
+    pub build_profile: &'static str,
+    /// Optional features and whether this node runs them.
+    pub features: NodeFeatures,
+    /// Unix time, in seconds, the node started at.
+    pub started_at: u64,
+    /// Database directory; `None` for in-memory nodes or when
+    /// `rpc.redact_data_dir` is set.
+    pub data_dir: Option<String>,
+    /// How long each stage of startup took.
+    pub startup: StartupTimings,
+}
+
+/// Time spent in each stage of opening a node, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct StartupTimings {
+    /// Opening the database; 0 for in-memory nodes.
+    pub db_open_ms: f64,
+    /// Storage key migrations and backfills.
+    pub migrations_ms: f64,
+    /// Initializing genesis state, or confirming it matches the config.
+    pub genesis_check_ms: f64,
+    /// Checking the recorded chain tip against its stored block.
+    pub tip_recovery_ms: f64,
+}
+
+impl StartupTimings {
+    /// Total time to open the node.
+    pub fn total_ms(&self) -> f64 {
+        self.db_open_ms + self.migrations_ms + self.genesis_check_ms + self.tip_recovery_ms
+    }
+}
+
+fn millis(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1000.0
+}
+
+/// Optional node features, as reported by `cgt_getNodeInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeFeatures {
+    /// Whether `cgt_devFaucet` is served.
+    pub dev_faucet: bool,
+    /// Whether the node mines its own blocks. Blocks currently come only
+    /// from external miners through `cgt_getWork`/`cgt_submitWork`.
+    pub block_production: bool,
+    /// Whether the node accepts P2P connections. Peers are tracked, but no
+    /// listener runs yet.
+    pub p2p: bool,
+    /// Whether only query methods are served (`rpc.read_only`).
+    pub read_only: bool,
+    /// Whether `dev_reset` is served (`rpc.dev_reset`).
+    pub dev_reset: bool,
+}
+
+impl NodeInfo {
+    fn new(db_path: &std::path::Path, config: &ChainConfig, startup: StartupTimings) -> Self {
+        let in_memory = db_path.as_os_str().is_empty();
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_commit: env!("DEMIURGE_GIT_COMMIT"),
+            build_profile: env!("DEMIURGE_BUILD_PROFILE"),
+            features: NodeFeatures {
+                dev_faucet: config.faucet.enabled && !config.rpc.read_only,
+                block_production: false,
+                p2p: false,
+                read_only: config.rpc.read_only,
+                dev_reset: config.rpc.dev_reset && !config.rpc.read_only,
+            },
+            started_at: unix_now(),
+            data_dir: (!in_memory && !config.rpc.redact_data_dir)
+                .then(|| db_path.display().to_string()),
+            startup,
+        }
+    }
+
+    /// Seconds since the node started.
+    pub fn uptime_secs(&self) -> u64 {
+        unix_now().saturating_sub(self.started_at)
+    }
+}
+
+/// Current Unix time in seconds (0 if the clock is before the epoch).
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Transactions a block template dry run included and left out.
+#[derive(Default)]
+struct DryRun {
+    body: Vec<Transaction>,
+    /// Transactions left out, with why.
+    failed: Vec<(Transaction, Failure)>,
+}
+
+impl DryRun {
+    /// Dry-run `tx` inside the template scope, including it if it executes.
+    fn include(&mut self, runtime: &Runtime, state: &mut State, tx: Transaction) -> bool {
+        state.begin();
+        match runtime.dispatch_tx(&tx, state) {
+            Ok(()) => {
+                // Folding into the dry-run scope cannot touch the backend
+                let _ = state.commit();
+                self.body.push(tx);
+                true
+            }
+            Err(e) => {
+                state.rollback();
+                tracing::debug!("Leaving transaction out of block template: {}", e);
+                // Told apart by the state it ran against, not the message
+                let failure = if consumes_nonce(&tx) && tx.nonce != get_nonce_cgt(state, &tx.from) {
+                    Failure::Nonce(e)
+                } else {
+                    Failure::Other(e)
+                };
+                self.failed.push((tx, failure));
+                false
+            }
+        }
+    }
+}
+
+/// Whether `tx` is a CGT transfer, to an address or to a handle: calls
+/// that check and bump the sender's nonce.
+fn is_transfer(tx: &Transaction) -> bool {
+    tx.module_id == "bank_cgt" && matches!(tx.call_id.as_str(), "transfer" | "transfer_to_handle")
+}
+
+/// Whether `tx` checks and consumes its sender's nonce: a CGT transfer, a
+/// Syzygy record, or a batch, which consumes its own nonce whatever calls it
+/// holds.
+fn consumes_nonce(tx: &Transaction) -> bool {
+    is_transfer(tx)
+        || tx.module_id == batch::MODULE_ID
+        || (tx.module_id == "avatars_profiles" && tx.call_id == "record_syzygy")
+}
+
+/// Where a submitted transaction is in its lifecycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Waiting in the mempool.
+    Pending,
+    /// Included in a block and executed successfully.
+    Confirmed { height: u64 },
+    /// Included in a block, but its call failed.
+    Failed { height: u64, error: String },
+    /// Included in a block whose receipts this node has pruned.
+    Pruned { height: u64 },
+    /// Evicted from the mempool because block templates kept leaving it out.
+    DeadLettered { reason: String },
+    /// Neither pending nor executed on this node.
+    Unknown,
+}
+
+/// Where an executed transaction sits in the chain, with its inclusion proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionProof {
+    pub height: u64,
+    pub block_hash: [u8; 32],
+    /// The header's transaction root; `None` for blocks produced before
+    /// headers committed to their transactions, which cannot be proven.
+    pub tx_root: Option<[u8; 32]>,
+    pub proof: TxInclusionProof,
+}
+
+/// An event emitted by a successful transaction, with where it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainEvent {
+    pub height: u64,
+    pub tx_hash: [u8; 32],
+    pub event: Event,
+}
+
+/// A query for block data this node has pruned (see
+/// `StorageConfig::keep_blocks`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pruned {
+    /// Height of the pruned block.
+    pub height: u64,
+}
+
+impl fmt::Display for Pruned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pruned: block {} is no longer stored on this node",
+            self.height
+        )
+    }
+}
+
+impl std::error::Error for Pruned {}
+
+/// Mempool notifications buffered per subscriber before it starts lagging.
+const MEMPOOL_EVENT_CAPACITY: usize = 1024;
+
+/// Compact summary of a transaction for mempool notifications.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTx {
+    pub tx_hash: [u8; 32],
+    pub from: Address,
+    pub module_id: String,
+    pub call_id: String,
+    pub fee: u64,
+}
+
+impl From<&Transaction> for PendingTx {
+    fn from(tx: &Transaction) -> Self {
+        Self {
+            tx_hash: tx.hash(),
+            from: tx.from,
+            module_id: tx.module_id.clone(),
+            call_id: tx.call_id.clone(),
+            fee: tx.fee,
+        }
+    }
+}
+
+/// A change to the mempool, broadcast to `Node::subscribe_mempool` receivers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MempoolEvent {
+    /// Admitted by `submit_transaction`.
+    Admitted(PendingTx),
+    /// Left the mempool in the block at `height`.
+    Included { tx: PendingTx, height: u64 },
+    /// Dropped without being included, for `reason`.
+    Evicted { tx: PendingTx, reason: String },
+}
+
+impl MempoolEvent {
+    /// The transaction the event is about.
+    pub fn tx(&self) -> &PendingTx {
+        match self {
+            MempoolEvent::Admitted(tx)
+            | MempoolEvent::Included { tx, .. }
+            | MempoolEvent::Evicted { tx, .. } => tx,
+        }
+    }
+}
+
+/// A range of nonces, inclusive at both ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct NonceRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Nonce and mempool picture of one account, for diagnosing stuck wallets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountStatus {
+    /// Nonce the account's next transaction must carry.
+    pub committed_nonce: u64,
+    /// Highest nonce such that every nonce from `committed_nonce` up to it
+    /// is pending, or `None` if `committed_nonce` itself is not pending.
+    pub highest_contiguous_pending_nonce: Option<u64>,
+    /// Nonces missing between `committed_nonce` and the highest pending
+    /// nonce; transactions above a gap cannot be included until it is filled.
+    pub nonce_gaps: Vec<NonceRange>,
+    /// Pending transactions from the account as `(nonce, hash)`, in nonce
+    /// order. Transfers and batches with nonces below `committed_nonce` can
+    /// no longer be included; other calls do not consume a nonce, so theirs
+    /// neither fill nor open gaps.
+    pub pending: Vec<(u64, [u8; 32])>,
+    /// Spendable CGT balance.
+    pub balance: u64,
+}
+
+impl AccountStatus {
+    /// Derive the contiguous run and gaps from the nonces of the pending
+    /// transactions that consume one (`consumes_nonce`); `others` are only
+    /// listed.
+    pub fn new(
+        committed_nonce: u64,
+        mut sequenced: Vec<(u64, [u8; 32])>,
+        others: Vec<(u64, [u8; 32])>,
+        balance: u64,
+    ) -> Self {
+        sequenced.sort();
+        let mut highest_contiguous_pending_nonce = None;
+        let mut nonce_gaps = Vec::new();
+        // Next nonce that would extend the run from committed_nonce
+        let mut expected = committed_nonce;
+        for &(nonce, _) in &sequenced {
+            if nonce < expected {
+                continue;
+            }
+            if nonce > expected {
+                nonce_gaps.push(NonceRange {
+                    start: expected,
+                    end: nonce - 1,
+                });
+            } else if nonce_gaps.is_empty() {
+                highest_contiguous_pending_nonce = Some(nonce);
+            }
+            expected = nonce + 1;
+        }
+        let mut pending = sequenced;
+        pending.extend(others);
+        pending.sort();
+        Self {
+            committed_nonce,
+            highest_contiguous_pending_nonce,
+            nonce_gaps,
+            pending,
+            balance,
+        }
+    }
+}
+
 /// Node structure managing chain state and operations.
 ///
 /// The Node owns:
-/// - A thread-safe State (RocksDB-backed, wrapped in Arc<Mutex<...>>)
+/// - A thread-safe State (RocksDB-backed, wrapped in Arc<RwLock<...>>)
 /// - A mempool for pending transactions
 /// - Chain height tracking
 ///
-/// In Phase 5, State is wrapped for concurrent read access from JSON-RPC handlers.
+/// State sits behind a read-write lock, so JSON-RPC reads run concurrently and
+/// only writers (block execution, dev writes) take it exclusively.
 pub struct Node {
     /// Thread-safe persistent state storage.
-    state: Arc<Mutex<State>>,
+    state: Arc<RwLock<State>>,
     /// Path to the RocksDB database.
     pub db_path: PathBuf,
     /// Mempool of pending transactions (not yet included in blocks).
-    pub mempool: Arc<Mutex<Vec<Transaction>>>,
-    /// Current chain height.
-    pub height: Arc<Mutex<u64>>,
+    pub mempool: Arc<Mutex<Mempool>>,
+    /// Connected P2P peers and temporary bans.
+    pub peers: Arc<Mutex<PeerManager>>,
+    /// Configuration this node was started with.
+    pub config: ChainConfig,
+    /// Block template most recently handed out to external miners.
+    work: Arc<Mutex<Option<Block>>>,
+    /// Runtime used to execute blocks, built once since modules are stateless.
+    runtime: Arc<Runtime>,
+    /// Build and runtime details reported by `cgt_getNodeInfo`.
+    info: NodeInfo,
+    /// Publishes mempool admissions, inclusions, and evictions.
+    mempool_events: broadcast::Sender<MempoolEvent>,
+    /// Transactions that failed block template dry runs.
+    dead_letters: Arc<Mutex<DeadLetters>>,
 }
 
 impl Node {
-    /// Create a new node with RocksDB-backed state.
+    /// Create a new node with RocksDB-backed state and the default configuration.
     ///
     /// # Arguments
     /// - `db_path`: Path to the RocksDB database directory
@@ -57,23 +730,209 @@ impl Node {
     /// # Note
     /// This function automatically initializes genesis state if not already done.
     pub fn new(db_path: PathBuf) -> Result<Self> {
-        let mut state = State::open_rocksdb(&db_path)?;
-        
+        Self::with_config(db_path, ChainConfig::default())
+    }
+
+    /// Create a new node with RocksDB-backed state and the given configuration.
+    ///
+    /// With `rpc.read_only` set, the database is opened read-only and
+    /// opening writes nothing: there are no key migrations or genesis init,
+    /// and the database must already hold a chain.
+    pub fn with_config(db_path: PathBuf, config: ChainConfig) -> Result<Self> {
+        let started = Instant::now();
+        let open = if config.rpc.read_only {
+            State::open_rocksdb_read_only
+        } else {
+            State::open_rocksdb_cached
+        };
+        let state = open(
+            &db_path,
+            config.storage.read_cache_entries,
+            &config.storage.rocksdb,
+        )?;
+        Self::from_state(state, db_path, config, started.elapsed())
+    }
+
+    /// Create a node backed by in-memory state (for tests).
+    pub fn in_memory(config: ChainConfig) -> Result<Self> {
+        Self::from_state(State::in_memory(), PathBuf::new(), config, Duration::ZERO)
+    }
+
+    fn from_state(
+        mut state: State,
+        db_path: PathBuf,
+        config: ChainConfig,
+        db_open: Duration,
+    ) -> Result<Self> {
+        let started = Instant::now();
+        // A read-only node makes none of the startup writes below, genesis
+        // init included, so it needs a database a writable node has opened
+        let read_only = config.rpc.read_only;
+        if !read_only {
+            // Move data written before module namespacing, index Archons granted
+            // before the Archon index, and fund the escrow account with escrows
+            // opened before it held them; must precede genesis init so existing
+            // genesis data is recognized
+            state.begin();
+            let migrated = migrate_legacy_keys(&mut state).and_then(|moved| {
+                Ok((
+                    moved,
+                    backfill_archon_index(&mut state)?,
+                    backfill_escrow_account(&mut state)?,
+                ))
+            });
+            match migrated {
+                Ok((moved, archons, escrowed)) => {
+                    state.commit()?;
+                    if moved > 0 {
+                        tracing::info!("Migrated {} storage key(s) to module namespaces", moved);
+                    }
+                    if archons > 0 {
+                        tracing::info!("Indexed {} existing Archon(s)", archons);
+                    }
+                    if escrowed > 0 {
+                        tracing::info!("Moved {} escrowed CGT into the escrow account", escrowed);
+                    }
+                }
+                Err(e) => {
+                    state.rollback();
+                    return Err(anyhow::anyhow!("Failed to migrate storage keys: {}", e));
+                }
+            }
+        }
+
+        let migrated = started.elapsed();
+
         // Initialize genesis state if needed
-        init_genesis_state(&mut state)?;
-        
+        let started = Instant::now();
+        if read_only {
+            if chain_tip(&state).is_none() {
+                anyhow::bail!("a read-only node needs a database with a chain; open it with a writable node first");
+            }
+        } else {
+            init_genesis_state(&mut state, &config.genesis)?;
+        }
+        let genesis_checked = started.elapsed();
+        let started = Instant::now();
+        check_chain_tip(&state)?;
+
+        let startup = StartupTimings {
+            db_open_ms: millis(db_open),
+            migrations_ms: millis(migrated),
+            genesis_check_ms: millis(genesis_checked),
+            tip_recovery_ms: millis(started.elapsed()),
+        };
+        tracing::info!(
+            "Opened the node in {:.1} ms (database {:.1} ms, migrations {:.1} ms, genesis check {:.1} ms, tip recovery {:.1} ms)",
+            startup.total_ms(),
+            startup.db_open_ms,
+            startup.migrations_ms,
+            startup.genesis_check_ms,
+            startup.tip_recovery_ms
+        );
+        let info = NodeInfo::new(&db_path, &config, startup);
+        let dead_letters = DeadLetters::new(&config.mempool);
+        let runtime = Arc::new(Runtime::for_chain(&config));
+        if !read_only {
+            register_module_accounts(&mut state, &runtime)?;
+        }
         Ok(Self {
-            state: Arc::new(Mutex::new(state)),
+            state: Arc::new(RwLock::new(state)),
             db_path,
-            mempool: Arc::new(Mutex::new(Vec::new())),
-            height: Arc::new(Mutex::new(0)),
+            mempool: Arc::new(Mutex::new(Mempool::new(&config.mempool))),
+            peers: Arc::new(Mutex::new(PeerManager::new())),
+            config,
+            work: Arc::new(Mutex::new(None)),
+            runtime,
+            info,
+            mempool_events: broadcast::channel(MEMPOOL_EVENT_CAPACITY).0,
+            dead_letters: Arc::new(Mutex::new(dead_letters)),
         })
     }
 
+    /// Execute blocks with the runtime built by `factory` instead of the defaults.
+    ///
+    /// Lets an embedding binary register its own modules, typically with
+    /// `Runtime::for_chain(chain).with_module(...)`. Fails if the new modules'
+    /// accounts cannot be registered.
+    ///
+    /// # Panics
+    /// If the factory registers the same module id twice. The factory is
+    /// invoked once here, so conflicts surface at startup, not at the first block.
+    pub fn with_runtime_factory(mut self, factory: RuntimeFactory) -> Result<Self> {
+        let runtime = factory(&self.config);
+        self.with_state_mut(|state| register_module_accounts(state, &runtime))?;
+        self.runtime = Arc::new(runtime);
+        Ok(self)
+    }
+
+    /// The runtime with this node's modules.
+    pub fn runtime(&self) -> Arc<Runtime> {
+        Arc::clone(&self.runtime)
+    }
+
+    /// Registered runtime modules and the calls they accept.
+    pub fn list_modules(&self) -> Vec<ModuleInfo> {
+        self.runtime.modules()
+    }
+
+    /// Payload layout of a module call, or `None` if the module or call is
+    /// unknown or undescribed.
+    pub fn call_schema(&self, module_id: &str, call_id: &str) -> Option<CallSchema> {
+        self.runtime.call_schema(module_id, call_id)
+    }
+
+    /// Account address of a registered module, or `None` for an unknown id.
+    pub fn module_address(&self, module_id: &str) -> Option<Address> {
+        self.runtime
+            .module_ids()
+            .contains(&module_id)
+            .then(|| module_address(module_id))
+    }
+
     /// Get current chain information.
     pub fn chain_info(&self) -> ChainInfo {
-        let height = *self.height.lock().expect("height mutex poisoned");
-        ChainInfo { height }
+        ChainInfo {
+            height: self.chain_tip().height,
+        }
+    }
+
+    /// The last finalized block, as committed to state.
+    pub fn chain_tip(&self) -> ChainTip {
+        self.with_state(chain_tip).unwrap_or(ChainTip {
+            height: self.config.genesis.start_height,
+            hash: self.genesis_hash(),
+        })
+    }
+
+    /// Build and runtime details of this node.
+    pub fn node_info(&self) -> &NodeInfo {
+        &self.info
+    }
+
+    /// Hash of this chain's genesis block header.
+    pub fn genesis_hash(&self) -> [u8; 32] {
+        genesis_header(&self.config.genesis).hash()
+    }
+
+    /// Forge PoW parameters blocks on this chain are hashed with.
+    pub fn forge_config(&self) -> ForgeConfig {
+        self.config.genesis.forge.clone()
+    }
+
+    /// Build the handshake this node sends on new P2P connections.
+    pub fn local_handshake(&self) -> Handshake {
+        Handshake {
+            protocol_version: PROTOCOL_VERSION,
+            chain_id: self.config.chain_id.clone(),
+            genesis_hash: self.genesis_hash(),
+            height: self.chain_info().height,
+        }
+    }
+
+    /// Get metadata for all connected peers.
+    pub fn peers(&self) -> Vec<PeerInfo> {
+        lock(&self.peers, "peers").peers()
     }
 
     /// Get a block by height.
@@ -82,15 +941,322 @@ impl Node {
     /// - `height`: The block height to query
     ///
     /// # Returns
-    /// `Some(Block)` if the block exists, `None` otherwise
+    /// - `Ok(Some(Block))` if the block exists, `Ok(None)` otherwise
+    /// - `Err(Pruned)` if its body has been pruned; `get_header_by_height`
+    ///   still has its header
     ///
     /// # Note
-    /// This is a stub implementation. In Phase 2.5+, block persistence will be
-    /// implemented and blocks will be read from RocksDB.
-    pub fn get_block_by_height(&self, _height: u64) -> Option<Block> {
-        // TODO: Phase 2.5+ - implement block persistence and retrieval
-        // For now, return None and document this limitation
-        None
+    /// The genesis block, at `GenesisConfig::start_height` (0 by default), is
+    /// stored during initialization; later heights are the blocks finalized
+    /// through `submit_work`.
+    pub fn get_block_by_height(&self, height: u64) -> Result<Option<Block>, Pruned> {
+        let genesis_height = self.config.genesis.start_height;
+        self.with_state(|state| {
+            if is_pruned(state, genesis_height, height) {
+                return Err(Pruned { height });
+            }
+            Ok(load_block(state, height))
+        })
+    }
+
+    /// Up to `count` blocks from `from_height` (the chain tip if `None` or
+    /// above it) down toward genesis, newest first.
+    ///
+    /// Read under one state lock, so the blocks all come from the same tip.
+    /// Stops early at the first pruned block.
+    pub fn latest_blocks(&self, from_height: Option<u64>, count: u64) -> Vec<Block> {
+        let genesis_height = self.config.genesis.start_height;
+        self.with_state(|state| {
+            let tip = chain_tip(state).map_or(genesis_height, |tip| tip.height);
+            let newest = from_height.map_or(tip, |height| height.min(tip));
+            (genesis_height..=newest)
+                .rev()
+                .take(count as usize)
+                .take_while(|height| !is_pruned(state, genesis_height, *height))
+                .map_while(|height| load_block(state, height))
+                .collect()
+        })
+    }
+
+    /// Events emitted in blocks `from_height..=to_height`, in chain order,
+    /// optionally only those of one module and/or one kind.
+    ///
+    /// Events are read from the receipts of each block's transactions, under
+    /// one state lock. Heights above the chain tip are skipped; a pruned block
+    /// in the range fails the whole query.
+    pub fn events(
+        &self,
+        from_height: u64,
+        to_height: u64,
+        module_id: Option<&str>,
+        kind: Option<&str>,
+    ) -> Result<Vec<ChainEvent>, Pruned> {
+        let genesis_height = self.config.genesis.start_height;
+        self.with_state(|state| {
+            let mut matched = Vec::new();
+            for height in from_height..=to_height {
+                if is_pruned(state, genesis_height, height) {
+                    return Err(Pruned { height });
+                }
+                let Some(block) = load_block(state, height) else {
+                    break;
+                };
+                for tx in &block.body {
+                    let tx_hash = tx.hash();
+                    // A receipt from another block is that block's to report
+                    let Some(receipt) =
+                        get_receipt(state, &tx_hash).filter(|receipt| receipt.height == height)
+                    else {
+                        continue;
+                    };
+                    matched.extend(
+                        receipt
+                            .events
+                            .into_iter()
+                            .filter(|event| {
+                                module_id.is_none_or(|id| event.module_id == id)
+                                    && kind.is_none_or(|kind| event.kind == kind)
+                            })
+                            .map(|event| ChainEvent {
+                                height,
+                                tx_hash,
+                                event,
+                            }),
+                    );
+                }
+            }
+            Ok(matched)
+        })
+    }
+
+    /// Get a block header by height; headers are kept when blocks are pruned.
+    pub fn get_header_by_height(&self, height: u64) -> Option<BlockHeader> {
+        self.with_state(|state| load_block(state, height))
+            .map(|block| block.header)
+    }
+
+    /// Build a block template for external miners on top of the current tip.
+    ///
+    /// Mempool transactions are dry-run in order against current state and
+    /// only those that would execute are included. A CGT transfer or batch
+    /// whose nonce is ahead of its sender's is held back until the
+    /// transactions filling the gap have been included, so each sender's
+    /// nonce-consuming transactions go in nonce order whatever order they
+    /// arrived in; one still gapped stays in the mempool for a later block.
+    /// Transactions left out are recorded in the dead-letter store, which
+    /// evicts them from the mempool once they are not worth retrying (see
+    /// `dead_letter`). The header commits to the template's transactions
+    /// (`tx_root`) and to the module state they leave (`state_root`), which
+    /// takes a pass over all module state. The template is remembered so a
+    /// later `submit_work` can finalize it.
+    pub fn block_template(&self) -> Block {
+        let tip = self.chain_tip();
+        let parent_timestamp = self
+            .get_header_by_height(tip.height)
+            .map_or(0, |h| h.timestamp);
+        let mut header = BlockHeader {
+            height: tip.height + 1,
+            prev_hash: tip.hash,
+            state_root: [0; 32],
+            // Blocks mined within a second, or after a genesis timestamped
+            // ahead of the clock, still go after their parent
+            timestamp: unix_now().max(parent_timestamp.saturating_add(1)),
+            difficulty_target: self.config.genesis.difficulty_target,
+            nonce: 0,
+            tx_root: None,
+        };
+
+        let candidates = self.mempool_transactions();
+        let DryRun { body, failed } = self.with_state_mut(|state| {
+            let mut run = DryRun::default();
+            // Transfers waiting for an earlier nonce, by sender and nonce
+            let mut queued: HashMap<Address, BTreeMap<u64, Transaction>> = HashMap::new();
+            state.set_block_height(header.height);
+            state.begin();
+            for tx in candidates {
+                if tx.is_expired_at(header.height) {
+                    continue;
+                }
+                if !consumes_nonce(&tx) {
+                    run.include(&self.runtime, state, tx);
+                    continue;
+                }
+                let from = tx.from;
+                if tx.nonce > get_nonce_cgt(state, &from) {
+                    queued.entry(from).or_default().insert(tx.nonce, tx);
+                    continue;
+                }
+                if !run.include(&self.runtime, state, tx) {
+                    continue;
+                }
+                // Promote queued successors for as long as they execute
+                while let Some(next) = queued
+                    .get_mut(&from)
+                    .and_then(|txs| txs.remove(&get_nonce_cgt(state, &from)))
+                {
+                    if !run.include(&self.runtime, state, next) {
+                        break;
+                    }
+                }
+            }
+            for (from, txs) in queued {
+                let nonce = get_nonce_cgt(state, &from);
+                for tx in txs.into_values() {
+                    let reason = format!("invalid nonce: expected {}, got {}", nonce, tx.nonce);
+                    run.failed.push((tx, Failure::Nonce(reason)));
+                }
+            }
+            state.rollback();
+            run
+        });
+        self.dead_letter(header.height, failed);
+
+        header.tx_root = Some(tx_root(&body));
+        let mut block = Block { header, body };
+        match self
+            .with_state_mut(|state| state.state_root_after(&block, &self.config, &self.runtime))
+        {
+            Ok(root) => block.header.state_root = root,
+            Err(e) => tracing::warn!(error = %e, "could not compute the template's state root"),
+        }
+        *lock(&self.work, "work") = Some(block.clone());
+        block
+    }
+
+    /// Record a template's failed transactions and evict the ones the
+    /// dead-letter store gives up on.
+    fn dead_letter(&self, height: u64, failed: Vec<(Transaction, Failure)>) {
+        let dead = lock(&self.dead_letters, "dead letters").record_template(height, failed);
+        if dead.is_empty() {
+            return;
+        }
+        let hashes: HashSet<_> = dead.iter().map(|failure| failure.tx.hash()).collect();
+        lock(&self.mempool, "mempool").remove_hashes(&hashes);
+        for failure in &dead {
+            tracing::info!(
+                tx_hash = %hex::encode(failure.tx.hash()),
+                attempts = failure.attempts,
+                "Dead-lettered transaction: {}",
+                failure.reason
+            );
+            self.publish(MempoolEvent::Evicted {
+                tx: (&failure.tx).into(),
+                reason: format!("dead-lettered: {}", failure.reason),
+            });
+        }
+    }
+
+    /// A transaction dead-lettered while building block templates, with why
+    /// it failed.
+    pub fn failed_transaction(&self, tx_hash: &[u8; 32]) -> Option<FailedTransaction> {
+        lock(&self.dead_letters, "dead letters")
+            .get(tx_hash)
+            .cloned()
+    }
+
+    /// Forget one dead-lettered transaction, or all of them.
+    ///
+    /// # Returns
+    /// How many were forgotten
+    pub fn purge_failed(&self, tx_hash: Option<&[u8; 32]>) -> usize {
+        lock(&self.dead_letters, "dead letters").purge(tx_hash)
+    }
+
+    /// Finalize the current block template with a miner-supplied nonce.
+    ///
+    /// The nonce is verified with Forge PoW during block execution. On success
+    /// the block's state changes, the block itself, and the new chain tip are
+    /// committed in one atomic write, and included transactions leave the
+    /// mempool.
+    ///
+    /// # Returns
+    /// - `Ok(Block)` with the finalized block
+    /// - `Err(String)` if there is no current template, it is stale, the nonce
+    ///   does not meet the difficulty target, or execution fails
+    pub fn submit_work(&self, nonce: u64) -> Result<Block, String> {
+        let mut work = lock(&self.work, "work");
+        let mut block = work
+            .clone()
+            .ok_or_else(|| "no block template; call cgt_getWork first".to_string())?;
+
+        // Only this function moves the tip, and the work lock serializes it
+        let tip = self.chain_tip();
+        if block.header.height != tip.height + 1 {
+            *work = None;
+            return Err(format!(
+                "stale block template for height {} (chain height is {})",
+                block.header.height, tip.height
+            ));
+        }
+        if block.header.prev_hash != tip.hash {
+            *work = None;
+            return Err(format!(
+                "block template for height {} does not extend the chain tip",
+                block.header.height
+            ));
+        }
+        block.header.nonce = nonce;
+        self.with_state_mut(|state| {
+            state.begin();
+            let stored = state
+                .execute_block_with(&block, &self.config, &self.runtime)
+                .and_then(|()| {
+                    let bytes = codec::encode_versioned(&block).map_err(|e| e.to_string())?;
+                    let tip = ChainTip {
+                        height: block.header.height,
+                        hash: block.header.hash(),
+                    };
+                    state
+                        .put_raw(block_key(block.header.height), bytes)
+                        .and_then(|()| put_chain_tip(state, &tip))
+                        .map_err(|e| e.to_string())
+                });
+            match stored {
+                Ok(()) => state.commit().map_err(|e| e.to_string()),
+                Err(e) => {
+                    state.rollback();
+                    Err(e)
+                }
+            }
+        })?;
+
+        *work = None;
+        self.prune_history(block.header.height);
+        let hashes: HashSet<_> = block.body.iter().map(Transaction::hash).collect();
+        let included = lock(&self.mempool, "mempool").remove_hashes(&hashes);
+        for tx in &included {
+            self.publish(MempoolEvent::Included {
+                tx: tx.into(),
+                height: block.header.height,
+            });
+        }
+
+        Ok(block)
+    }
+
+    /// Prune block bodies and receipts that have left the retention window
+    /// (`StorageConfig::keep_blocks`) now that `tip` is finalized.
+    ///
+    /// Runs in its own write scope and at most `PRUNE_BATCH_BLOCKS` at a time;
+    /// a failure is logged and retried after the next block.
+    fn prune_history(&self, tip: u64) {
+        let KeepBlocks::Recent(keep) = self.config.storage.keep_blocks else {
+            return;
+        };
+        self.with_state_mut(|state| {
+            state.begin();
+            match prune_blocks(state, self.config.genesis.start_height, keep, tip) {
+                Ok(pruned) => match state.commit() {
+                    Ok(()) if pruned > 0 => tracing::debug!(pruned, tip, "pruned old blocks"),
+                    Ok(()) => {}
+                    Err(e) => tracing::warn!("failed to prune old blocks: {}", e),
+                },
+                Err(e) => {
+                    state.rollback();
+                    tracing::warn!("failed to prune old blocks: {}", e);
+                }
+            }
+        });
     }
 
     /// Submit a transaction to the mempool.
@@ -98,34 +1264,353 @@ impl Node {
     /// # Arguments
     /// - `tx`: The transaction to add to the mempool
     ///
+    /// # Returns
+    /// - `Ok(())` if the transaction was admitted
+    /// - `Err(String)` if it has already expired for the next block, is sent
+    ///   from a module account, is not signed the way its sender requires
+    ///   (see `check_signature`), is certain to fail (see `check_admissible`), the same
+    ///   transaction is already pending, its sender already has
+    ///   `mempool.max_txs_per_sender` transactions pending, or the mempool
+    ///   is full and it pays no more than the cheapest pending transaction
+    ///
     /// # Note
     /// This adds the transaction to the mempool but does not immediately
     /// include it in a block. Block production and transaction inclusion
     /// will be implemented in later phases.
-    pub fn submit_transaction(&self, tx: Transaction) {
-        let mut mempool = self.mempool.lock().expect("mempool mutex poisoned");
-        mempool.push(tx);
-        // Later phases will include block production and actual inclusion.
+    pub fn submit_transaction(&self, tx: Transaction) -> Result<(), String> {
+        // Taken before any check, so a reset while they run is noticed
+        let generation = lock(&self.mempool, "mempool").generation();
+        let next_height = self.chain_info().height + 1;
+        if tx.is_expired_at(next_height) {
+            return Err(format!(
+                "transaction expired: valid until height {}, next block is {}",
+                tx.valid_until_height.unwrap_or_default(),
+                next_height
+            ));
+        }
+        if self.with_state(|state| module_account_owner(state, &tx.from).is_some()) {
+            return Err(format!(
+                "{} is a module account and cannot send transactions",
+                tx.from.to_display()
+            ));
+        }
+        self.with_state(|state| check_signature(state, &tx))?;
+        self.check_admissible(&tx)?;
+        self.admit(tx, generation)
+    }
+
+    /// Add a transaction that passed `submit_transaction`'s checks, unless
+    /// the mempool was cleared since `generation`: the chain was reset, so
+    /// the checks ran against a chain that is gone.
+    fn admit(&self, tx: Transaction, generation: u64) -> Result<(), String> {
+        let mut mempool = lock(&self.mempool, "mempool");
+        if mempool.generation() != generation {
+            return Err(
+                "the chain was reset while the transaction was being checked; submit it again"
+                    .into(),
+            );
+        }
+        let hash = tx.hash();
+        let admitted = (&tx).into();
+        let evicted = mempool.insert(tx)?;
+        // A resubmitted dead letter is pending again
+        lock(&self.dead_letters, "dead letters").purge(Some(&hash));
+        self.publish(MempoolEvent::Admitted(admitted));
+        if let Some(evicted) = evicted {
+            self.publish(MempoolEvent::Evicted {
+                tx: (&evicted).into(),
+                reason: "replaced by a higher-fee transaction in a full mempool".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Dry-run checks for a transaction that could never execute: its module
+    /// or call is not registered, it consumes a nonce (see `consumes_nonce`)
+    /// that is already used, or it is a transfer whose sender cannot
+    /// cover amount + fee.
+    ///
+    /// Checks run against committed state. A nonce ahead of the account's is
+    /// allowed, so transactions can queue behind ones still pending.
+    fn check_admissible(&self, tx: &Transaction) -> Result<(), String> {
+        let known_call = self
+            .runtime
+            .modules()
+            .iter()
+            .find(|module| module.module_id == tx.module_id)
+            .map(|module| module.call_ids.contains(&tx.call_id.as_str()));
+        match known_call {
+            None => return Err(format!("unknown module '{}'", tx.module_id)),
+            Some(false) => return Err(format!("unknown call {}.{}", tx.module_id, tx.call_id)),
+            Some(true) => {}
+        }
+        if !consumes_nonce(tx) {
+            return Ok(());
+        }
+        if !is_transfer(tx) {
+            // A batch's costs are only known once its calls run, and a Syzygy
+            // record has none
+            let nonce = self.with_state(|state| get_nonce_cgt(state, &tx.from));
+            if tx.nonce < nonce {
+                return Err(format!(
+                    "nonce too low: account nonce is {}, got {}",
+                    nonce, tx.nonce
+                ));
+            }
+            return Ok(());
+        }
+
+        // A handle is resolved only when the transfer executes, since it may
+        // be registered or released before then
+        let (amount, memo) = if tx.call_id == "transfer_to_handle" {
+            let params: TransferToHandleParams = codec::decode_exact(&tx.payload)
+                .map_err(|e| format!("invalid transfer payload: {}", e))?;
+            (params.amount, params.memo)
+        } else {
+            let params = decode_transfer(&tx.payload)
+                .map_err(|e| format!("invalid transfer payload: {}", e))?;
+            (params.amount, params.memo)
+        };
+        self.with_state(|state| check_memo(state, memo.as_deref()))?;
+        let needed = amount
+            .checked_add(tx.fee)
+            .ok_or("transfer amount + fee overflows")?;
+        let (nonce, balance) = self.with_state(|state| {
+            (
+                get_nonce_cgt(state, &tx.from),
+                get_balance_cgt(state, &tx.from),
+            )
+        });
+        if tx.nonce < nonce {
+            return Err(format!(
+                "nonce too low: account nonce is {}, got {}",
+                nonce, tx.nonce
+            ));
+        }
+        if balance < needed {
+            return Err(format!(
+                "insufficient balance: {} needed for amount + fee, {} available",
+                needed, balance
+            ));
+        }
+        Ok(())
+    }
+
+    /// Receive every later mempool admission, inclusion, and eviction.
+    ///
+    /// A receiver that falls more than `MEMPOOL_EVENT_CAPACITY` events
+    /// behind skips the oldest ones.
+    pub fn subscribe_mempool(&self) -> broadcast::Receiver<MempoolEvent> {
+        self.mempool_events.subscribe()
+    }
+
+    fn publish(&self, event: MempoolEvent) {
+        // Sending fails only when nobody is subscribed
+        let _ = self.mempool_events.send(event);
+    }
+
+    /// Get a copy of all pending mempool transactions.
+    pub fn mempool_transactions(&self) -> Vec<Transaction> {
+        lock(&self.mempool, "mempool").transactions().to_vec()
+    }
+
+    /// Whether a transaction with this hash is waiting in the mempool.
+    pub fn is_pending(&self, tx_hash: &[u8; 32]) -> bool {
+        lock(&self.mempool, "mempool").contains(tx_hash)
+    }
+
+    /// Committed nonce, pending transactions, nonce gaps, and balance of an
+    /// account.
+    pub fn account_status(&self, addr: &Address) -> AccountStatus {
+        let (sequenced, others) = self
+            .mempool_transactions()
+            .into_iter()
+            .filter(|tx| &tx.from == addr)
+            .partition::<Vec<_>, _>(consumes_nonce);
+        let entries = |txs: Vec<Transaction>| {
+            txs.iter()
+                .map(|tx| (tx.nonce, tx.hash()))
+                .collect::<Vec<_>>()
+        };
+        let (committed_nonce, balance) =
+            self.with_state(|state| (get_nonce_cgt(state, addr), get_balance_cgt(state, addr)));
+        AccountStatus::new(
+            committed_nonce,
+            entries(sequenced),
+            entries(others),
+            balance,
+        )
+    }
+
+    /// Lifecycle status of a transaction, from its receipt or the mempool.
+    ///
+    /// The receipt is checked first, so a transaction reads as executed even
+    /// in the moment before a finalized block's transactions leave the mempool.
+    pub fn transaction_status(&self, tx_hash: &[u8; 32]) -> TxStatus {
+        match self.get_receipt(tx_hash) {
+            Ok(Some(receipt)) => match receipt.error {
+                None => TxStatus::Confirmed {
+                    height: receipt.height,
+                },
+                Some(error) => TxStatus::Failed {
+                    height: receipt.height,
+                    error,
+                },
+            },
+            Err(Pruned { height }) => TxStatus::Pruned { height },
+            Ok(None) if self.is_pending(tx_hash) => TxStatus::Pending,
+            Ok(None) => match self.failed_transaction(tx_hash) {
+                Some(failure) => TxStatus::DeadLettered {
+                    reason: failure.reason,
+                },
+                None => TxStatus::Unknown,
+            },
+        }
+    }
+
+    /// Evict mempool transactions that can no longer be included in the next block.
+    ///
+    /// # Returns
+    /// The number of evicted transactions
+    pub fn sweep_expired_mempool(&self) -> usize {
+        let next_height = self.chain_info().height + 1;
+        let expired = lock(&self.mempool, "mempool").retain(|tx| !tx.is_expired_at(next_height));
+        for tx in &expired {
+            self.publish(MempoolEvent::Evicted {
+                tx: tx.into(),
+                reason: format!("expired before block {}", next_height),
+            });
+        }
+        expired.len()
+    }
+
+    /// Mint a dev faucet drip to an address, subject to the configured cooldown
+    /// and lifetime cap.
+    ///
+    /// If `profile_name` is given and the address has no Aeon profile, one is
+    /// created with that display name. The mint, the claim record, and the
+    /// profile are written atomically: a failure leaves none of them applied.
+    ///
+    /// # Returns
+    /// - `Ok(FaucetReceipt)` with the new balance and whether a profile was created
+    /// - `Err(FaucetError)` if the faucet is disabled, a limit applies, or
+    ///   profile creation fails
+    pub fn dev_faucet(
+        &self,
+        addr: &Address,
+        profile_name: Option<String>,
+    ) -> Result<FaucetReceipt, FaucetError> {
+        let mut state = self.write_state();
+        let height = chain_tip(&state).map_or(0, |tip| tip.height);
+        state.begin();
+        let result =
+            faucet::claim(&mut state, &self.config, addr, height).and_then(|new_balance| {
+                let profile_created = match profile_name {
+                    Some(name) if get_aeon_profile(&state, addr).is_none() => {
+                        avatars::create_aeon_profile(&mut state, *addr, name, None, height)
+                            .map_err(FaucetError::State)?;
+                        true
+                    }
+                    _ => false,
+                };
+                Ok(FaucetReceipt {
+                    new_balance,
+                    profile_created,
+                })
+            });
+        match result {
+            Ok(receipt) => {
+                state
+                    .commit()
+                    .map_err(|e| FaucetError::State(e.to_string()))?;
+                Ok(receipt)
+            }
+            Err(e) => {
+                state.rollback();
+                Err(e)
+            }
+        }
     }
 
     /// Execute a function with read-only access to state.
     ///
     /// This helper provides thread-safe read access to the state for RPC handlers.
+    /// It takes a shared lock, so readers proceed in parallel and wait only
+    /// while `with_state_mut` holds the lock.
     pub fn with_state<R>(&self, f: impl FnOnce(&State) -> R) -> R {
-        let state = self.state.lock().expect("state mutex poisoned");
+        let state = self.read_state();
         f(&state)
     }
 
+    /// Open a read transaction: a view of the state that no block commit can
+    /// change while it is held.
+    ///
+    /// Responses built from several reads (an index, then the entries it
+    /// lists) should take them all from one snapshot rather than through
+    /// separate getters, each of which locks on its own and may see a
+    /// different block. A RocksDB snapshot leaves block finalization free
+    /// to go on, but pins the versions it reads until dropped; an in-memory
+    /// node's snapshot holds the state lock, so finalization waits for it.
+    /// Either way, drop it as soon as the reads are done and never hold it
+    /// across an `.await`.
+    pub fn snapshot(&self) -> Snapshot<'_> {
+        let guard = self.read_state();
+        let state = match guard.snapshot() {
+            Some(state) => SnapshotState::Backend(Box::new(state)),
+            None => SnapshotState::Locked(guard),
+        };
+        Snapshot { state }
+    }
+
+    /// Get the multisig account registered at an address.
+    pub fn get_multisig(&self, addr: &Address) -> Option<MultisigAccount> {
+        self.with_state(|state| get_multisig(state, addr))
+    }
+
     /// Get CGT balance for an address.
     pub fn get_balance_cgt(&self, addr: &Address) -> u64 {
         self.with_state(|state| get_balance_cgt(state, addr))
     }
 
+    /// Get CGT balances for several addresses, read under one state lock.
+    pub fn get_balances_cgt(&self, addrs: &[Address]) -> Vec<u64> {
+        self.with_state(|state| {
+            addrs
+                .iter()
+                .map(|addr| get_balance_cgt(state, addr))
+                .collect()
+        })
+    }
+
+    /// Check whether an address has ever been seen on chain.
+    pub fn account_exists(&self, addr: &Address) -> bool {
+        self.with_state(|state| account_exists(state, addr))
+    }
+
     /// Check if an address has Archon status.
     pub fn is_archon(&self, addr: &Address) -> bool {
         self.with_state(|state| is_archon(state, addr))
     }
 
+    /// A window of the Archon list, in address order, with each Archon's
+    /// Aeon display name if it has a profile.
+    ///
+    /// # Returns
+    /// The total number of Archons and the entries in the window.
+    pub fn list_archons(&self, offset: u64, limit: u64) -> (u64, Vec<(Address, Option<String>)>) {
+        self.with_state(|state| {
+            let (total, archons) = list_archons(state, offset, limit);
+            let entries = archons
+                .into_iter()
+                .map(|addr| {
+                    let name = get_aeon_profile(state, &addr).map(|p| p.display_name);
+                    (addr, name)
+                })
+                .collect();
+            (total, entries)
+        })
+    }
+
     /// Get NFT IDs owned by an address.
     pub fn get_nfts_by_owner(&self, owner: &Address) -> Vec<NftId> {
         self.with_state(|state| get_nfts_by_owner(state, owner))
@@ -136,11 +1621,198 @@ impl Node {
         self.with_state(|state| get_nft(state, id))
     }
 
-    /// Get marketplace listing by ID.
-    pub fn get_listing(&self, id: ListingId) -> Option<crate::runtime::abyss_registry::Listing> {
+    /// Get the current owner of an NFT.
+    pub fn get_nft_owner(&self, id: NftId) -> Option<Address> {
+        self.get_nft(id).map(|nft| nft.owner)
+    }
+
+    /// Get the current owner of an NFT with a proof against the state root,
+    /// read under one state lock.
+    ///
+    /// Proving builds the Merkle tree over all module state, so this costs
+    /// time linear in the state size.
+    pub fn get_nft_owner_with_proof(&self, id: NftId) -> Option<NftOwnerProof> {
+        let state = self.read_state();
+        let owner = get_nft(&state, id)?.owner;
+        let proof = self.runtime.prove(&state, &nft_storage_key(id))?;
+        let tip = chain_tip(&state).unwrap_or(ChainTip {
+            height: self.config.genesis.start_height,
+            hash: self.genesis_hash(),
+        });
+        let state_root = self.runtime.state_root(&state);
+        let committed = load_block(&state, tip.height)
+            .is_some_and(|block| block.header.state_root == state_root);
+        Some(NftOwnerProof {
+            owner,
+            height: tip.height,
+            block_hash: tip.hash,
+            state_root,
+            committed,
+            proof,
+        })
+    }
+
+    /// Get marketplace listing by ID.
+    pub fn get_listing(&self, id: ListingId) -> Option<crate::runtime::abyss_registry::Listing> {
         self.with_state(|state| get_listing(state, id))
     }
 
+    /// Get escrow by ID.
+    pub fn get_escrow(&self, id: EscrowId) -> Option<crate::runtime::escrow::Escrow> {
+        self.with_state(|state| get_escrow(state, id))
+    }
+
+    /// Get an asset and an address's balance of it, or `None` if the asset
+    /// is not registered.
+    pub fn get_asset_balance(
+        &self,
+        asset_id: &str,
+        addr: &Address,
+    ) -> Option<(crate::runtime::assets::Asset, u64)> {
+        self.with_state(|state| {
+            get_asset(state, asset_id).map(|asset| (asset, balance_of_asset(state, asset_id, addr)))
+        })
+    }
+
+    /// Get governance proposal by ID.
+    pub fn get_proposal(&self, id: ProposalId) -> Option<crate::runtime::governance::Proposal> {
+        self.with_state(|state| get_proposal(state, id))
+    }
+
+    /// Get the receipt of an executed transaction by its hash.
+    ///
+    /// Fails with `Pruned` if the transaction's block has been pruned.
+    pub fn get_receipt(&self, tx_hash: &[u8; 32]) -> Result<Option<Receipt>, Pruned> {
+        self.with_state(|state| match get_receipt(state, tx_hash) {
+            Some(receipt) => Ok(Some(receipt)),
+            None => match pruned_receipt_height(state, tx_hash) {
+                Some(height) => Err(Pruned { height }),
+                None => Ok(None),
+            },
+        })
+    }
+
+    /// Locate an executed transaction in its block and build its inclusion
+    /// proof against the header's `tx_root`.
+    ///
+    /// Returns `Ok(None)` if the transaction has no receipt or its block is
+    /// missing from storage, and `Err(Pruned)` if its block has been pruned.
+    pub fn transaction_proof(
+        &self,
+        tx_hash: &[u8; 32],
+    ) -> Result<Option<TransactionProof>, Pruned> {
+        let Some(receipt) = self.get_receipt(tx_hash)? else {
+            return Ok(None);
+        };
+        let height = receipt.height;
+        let Some(block) = self.get_block_by_height(height)? else {
+            return Ok(None);
+        };
+        Ok(block
+            .inclusion_proof(tx_hash)
+            .map(|proof| TransactionProof {
+                height,
+                block_hash: block.header.hash(),
+                tx_root: block.header.tx_root,
+                proof,
+            }))
+    }
+
+    /// Receipts of the transactions involving an address, as a window of its
+    /// index (see `tx_index::transactions_by_address`), with the total count.
+    ///
+    /// Transactions whose receipts have been pruned are left out of the
+    /// window but still count toward the total.
+    pub fn get_transactions_by_address(
+        &self,
+        addr: &Address,
+        offset: u64,
+        limit: u64,
+        newest_first: bool,
+    ) -> (u64, Vec<Receipt>) {
+        self.with_state(|state| {
+            let (total, hashes) = transactions_by_address(state, addr, offset, limit, newest_first);
+            let receipts = hashes
+                .iter()
+                .filter_map(|hash| get_receipt(state, hash))
+                .collect();
+            (total, receipts)
+        })
+    }
+
+    /// An address's lifetime marketplace royalties and a window of its royalty
+    /// payouts, newest first, read under one state lock.
+    ///
+    /// Payouts come from `abyss_registry`'s per-recipient payout index (see
+    /// `get_royalty_payouts`), so a page costs what it returns however many
+    /// transactions the address has; `offset` and `limit` count payouts.
+    pub fn royalty_stats(
+        &self,
+        addr: &Address,
+        offset: u64,
+        limit: u64,
+    ) -> (RoyaltyStats, Vec<ChainEvent>) {
+        self.with_state(|state| {
+            let payouts = get_royalty_payouts(state, addr, offset, limit)
+                .into_iter()
+                .map(|payout| ChainEvent {
+                    height: payout.height,
+                    tx_hash: payout.tx_hash,
+                    event: payout.event,
+                })
+                .collect();
+            (get_royalty_stats(state, addr), payouts)
+        })
+    }
+
+    /// Get an address's activity counters (`None` if no transaction has
+    /// involved it) and the number of NFTs it owns now, read under one state
+    /// lock.
+    pub fn address_stats(&self, addr: &Address) -> (Option<AddressStats>, u64) {
+        self.with_state(|state| {
+            let owned = get_nfts_by_owner(state, addr).len() as u64;
+            (address_stats(state, addr), owned)
+        })
+    }
+
+    /// List all quests, in id order.
+    pub fn list_quests(&self) -> Vec<crate::runtime::quests::Quest> {
+        self.with_state(list_quests)
+    }
+
+    /// Get an address's progress on each quest it has worked toward.
+    pub fn get_quest_progress(
+        &self,
+        addr: &Address,
+    ) -> Vec<(QuestId, crate::runtime::quests::QuestProgress)> {
+        self.with_state(|state| get_progress(state, addr))
+    }
+
+    /// List all governance proposals, in id order.
+    pub fn list_proposals(&self) -> Vec<crate::runtime::governance::Proposal> {
+        self.with_state(list_proposals)
+    }
+
+    /// Get an address's stake, with rewards accrued to the chain tip, and its
+    /// pending unbondings.
+    pub fn get_stake(
+        &self,
+        addr: &Address,
+    ) -> (
+        Option<crate::runtime::staking::Stake>,
+        Vec<crate::runtime::staking::Unbonding>,
+    ) {
+        self.with_state(|state| {
+            let height = chain_tip(state).map_or(0, |tip| tip.height);
+            (get_stake(state, addr, height), get_unbondings(state, addr))
+        })
+    }
+
+    /// Get an address's CGT bonded for Aeon progression and its pending unbonds.
+    pub fn get_bond(&self, addr: &Address) -> (u64, Vec<crate::runtime::staking::Unbonding>) {
+        self.with_state(|state| (get_bonded(state, addr), get_bond_unbondings(state, addr)))
+    }
+
     /// Get Fabric asset by root hash.
     pub fn get_fabric_asset(
         &self,
@@ -149,71 +1821,1606 @@ impl Node {
         self.with_state(|state| get_fabric_asset(state, root))
     }
 
+    /// Get an NFT together with the Fabric asset its content lives in, read
+    /// under one state lock. The asset is `None` when the NFT's root hash was
+    /// never registered.
+    pub fn resolve_nft_content(
+        &self,
+        id: NftId,
+    ) -> Option<(
+        crate::runtime::nft_dgen::DGenMetadata,
+        Option<crate::runtime::fabric_manager::FabricAsset>,
+    )> {
+        self.with_state(|state| {
+            let nft = get_nft(state, id)?;
+            let asset = get_fabric_asset(state, &nft.fabric_root_hash);
+            Some((nft, asset))
+        })
+    }
+
+    /// Compact the database, reclaiming space held by overwritten values and
+    /// deleted keys (such as pruned blocks and receipts).
+    ///
+    /// The state lock is only held to reach the database, not while it
+    /// compacts, so blocks keep being finalized. Takes seconds or more on a
+    /// large database, so async callers should run it on a blocking thread.
+    pub fn compact(&self) {
+        let Some(compact) = self.with_state(State::compactor) else {
+            return;
+        };
+        let started = std::time::Instant::now();
+        compact();
+        tracing::info!("Compacted the database in {:?}", started.elapsed());
+    }
+
+    /// Check the state's invariants (see `Runtime::verify_invariants`).
+    ///
+    /// Scans a `State::snapshot` on RocksDB, so blocks keep being finalized
+    /// while it runs; an in-memory state is scanned under the state lock.
+    pub fn verify_invariants(&self) -> Result<(), Vec<String>> {
+        match self.with_state(State::snapshot) {
+            Some(snapshot) => self.runtime.verify_invariants(&snapshot),
+            None => self.with_state(|state| self.runtime.verify_invariants(state)),
+        }
+    }
+
+    /// Wipe the chain and start over from the configured genesis (see
+    /// `reset_chain`), for devnets and test harnesses.
+    ///
+    /// Holds the work template, so no block is finalized mid-reset, and the
+    /// state lock for writing, so in-flight reads finish first and later ones
+    /// see only the new chain. The template, mempool, and dead letters are
+    /// cleared with it; dropped transactions are published as evictions.
+    /// Clearing starts a new mempool generation, so a submission whose checks
+    /// ran against the old chain is refused rather than admitted after it.
+    pub fn reset(&self, keep_faucet_history: bool) -> Result<ResetReport> {
+        let mut work = lock(&self.work, "work");
+        let mut state = self.write_state();
+        let report = reset_chain(&mut state, &self.config, &self.runtime, keep_faucet_history)?;
+        *work = None;
+        let dropped = lock(&self.mempool, "mempool").clear();
+        drop(state);
+        drop(work);
+
+        lock(&self.dead_letters, "dead letters").purge(None);
+        for tx in &dropped {
+            self.publish(MempoolEvent::Evicted {
+                tx: tx.into(),
+                reason: "chain reset".to_string(),
+            });
+        }
+        tracing::info!(
+            "Reset the chain to genesis {} ({} key(s) deleted, {} pending transaction(s) dropped)",
+            hex::encode(report.tip.hash),
+            report.deleted_keys,
+            dropped.len()
+        );
+        Ok(report)
+    }
+
     /// Execute a function with mutable access to state.
     ///
     /// This helper provides thread-safe mutable access to the state for operations
     /// like genesis initialization, dev faucet, and direct minting.
     pub fn with_state_mut<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
-        let mut state = self.state.lock().expect("state mutex poisoned");
+        let mut state = self.write_state();
         f(&mut state)
     }
+
+    /// Take the state lock for writing.
+    ///
+    /// If a writer panicked while holding it, the write scopes it left open
+    /// are rolled back, so only committed state survives, and the node keeps
+    /// serving.
+    fn write_state(&self) -> RwLockWriteGuard<'_, State> {
+        self.state.write().unwrap_or_else(|poisoned| {
+            tracing::warn!("state lock poisoned by a panicked thread; discarding its open writes");
+            self.state.clear_poison();
+            let mut state = poisoned.into_inner();
+            state.rollback_all();
+            state
+        })
+    }
+
+    /// Take the state lock for reading, recovering it first if poisoned.
+    fn read_state(&self) -> RwLockReadGuard<'_, State> {
+        if self.state.is_poisoned() {
+            drop(self.write_state());
+        }
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Lock one of the node's mutexes, recovering it if a thread panicked while
+/// holding it.
+///
+/// Each guarded value (height, mempool, work template, peers) is updated in
+/// single steps, so it is still usable after a panic; failing every later
+/// call would take the whole node down instead.
+fn lock<'a, T>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        tracing::warn!("{} mutex poisoned by a panicked thread; recovering", name);
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// Add `runtime`'s module accounts to the registry, atomically.
+fn register_module_accounts(state: &mut State, runtime: &Runtime) -> Result<()> {
+    state.begin();
+    match runtime.register_module_accounts(state) {
+        Ok(registered) => {
+            state.commit()?;
+            if !registered.is_empty() {
+                tracing::info!("Registered module accounts for {}", registered.join(", "));
+            }
+            Ok(())
+        }
+        Err(e) => {
+            state.rollback();
+            Err(anyhow::anyhow!("Failed to register module accounts: {}", e))
+        }
+    }
 }
 
 /// Initialize genesis state if not already initialized.
 ///
 /// This function:
 /// 1. Checks if genesis has already been initialized
-/// 2. If not, mints CGT to the Genesis Archon address
-/// 3. Marks the Genesis Archon address as an Archon
+/// 2. If not, mints each genesis archon's configured balance to it
+/// 3. Marks each genesis archon as an Archon
 /// 4. Sets the genesis initialization flag
-fn init_genesis_state(state: &mut State) -> Result<()> {
+/// 5. Creates an Aeon profile for each genesis archon that has none and
+///    exempts it from the existential deposit (this also backfills chains
+///    initialized before either existed)
+/// 6. Stores the genesis block at height 0 and records its hash, or checks
+///    the recorded hash against the configured genesis on later starts
+/// 7. Records the Forge parameters, or checks the recorded ones against the
+///    configured parameters on later starts
+/// 8. Records the genesis block as the chain tip if no tip is recorded
+/// 9. Records the spec's fingerprint (`genesis_fingerprint`)
+///
+/// All writes are applied atomically: on failure, none of them are kept. An
+/// invalid genesis spec is refused up front.
+///
+/// Later starts whose spec has the recorded fingerprint stop at reading it
+/// back, since each step would find nothing to write and nothing to refuse.
+fn init_genesis_state(state: &mut State, genesis: &GenesisConfig) -> Result<()> {
+    genesis.validate()?;
+    let fingerprint = genesis_fingerprint(genesis)?;
+    if state.get_raw(KEY_GENESIS_APPLIED).as_deref() == Some(fingerprint.as_slice()) {
+        return Ok(());
+    }
+    state.begin();
+    let applied = apply_genesis(state, genesis)
+        .and_then(|()| state.put_raw(KEY_GENESIS_APPLIED.to_vec(), fingerprint));
+    match applied {
+        Ok(()) => state.commit(),
+        Err(e) => {
+            state.rollback();
+            Err(e)
+        }
+    }
+}
+
+/// SHA-256 over `GENESIS_STEPS_VERSION` and the encoded genesis spec.
+fn genesis_fingerprint(genesis: &GenesisConfig) -> Result<Vec<u8>> {
+    let mut hasher = Sha256::new();
+    hasher.update([GENESIS_STEPS_VERSION]);
+    hasher.update(codec::encode(genesis)?);
+    Ok(hasher.finalize().to_vec())
+}
+
+fn apply_genesis(state: &mut State, genesis: &GenesisConfig) -> Result<()> {
     const KEY_GENESIS_INITIALIZED: &[u8] = b"demiurge/genesis_initialized";
 
-    // Check if already initialized
-    if state.get_raw(KEY_GENESIS_INITIALIZED).is_some() {
-        return Ok(());
+    // Initialize the genesis archons: mint CGT and mark as Archon
+    if state.get_raw(KEY_GENESIS_INITIALIZED).is_none() {
+        for archon in &genesis.archons {
+            bank::mint(state, &archon.address, archon.balance)
+                .map_err(|e| anyhow::anyhow!("Failed to mint genesis CGT: {}", e))?;
+            avatars::grant_archon(state, &archon.address)
+                .map_err(|e| anyhow::anyhow!("Failed to claim genesis Archon: {}", e))?;
+        }
+
+        // Mint the genesis NFTs, which take the first ids in order
+        for genesis_nft in &genesis.nfts {
+            let params = MintDgenParams {
+                fabric_root_hash: genesis_nft.fabric_root_hash,
+                forge_model_id: None,
+                forge_prompt_hash: None,
+                royalty_recipient: (genesis_nft.royalty_bps > 0)
+                    .then(|| genesis_nft.royalty_recipient.unwrap_or(genesis_nft.owner)),
+                royalty_bps: genesis_nft.royalty_bps,
+                uri_hint: genesis_nft.uri_hint.clone(),
+                royalty_splits: Vec::new(),
+            };
+            nft::mint(state, &genesis_nft.owner, &params)
+                .map_err(|e| anyhow::anyhow!("Failed to mint genesis NFT: {}", e))?;
+        }
+
+        // Mark genesis as initialized
+        state.put_raw(KEY_GENESIS_INITIALIZED.to_vec(), vec![1u8])?;
     }
 
-    // Initialize Genesis Archon: mint CGT and mark as Archon
-    let genesis_addr = GENESIS_ARCHON_ADDRESS;
+    // Give each genesis archon an Aeon profile and exempt it from the
+    // existential deposit
+    for archon in &genesis.archons {
+        bank::exempt_from_existential_deposit(state, &archon.address)
+            .map_err(|e| anyhow::anyhow!("Failed to exempt genesis Archon: {}", e))?;
+        if get_aeon_profile(state, &archon.address).is_none() {
+            let name = archon.name.as_ref().unwrap_or(&genesis.archon_name).clone();
+            avatars::create_aeon_profile(state, archon.address, name, None, 0)
+                .map_err(|e| anyhow::anyhow!("Failed to create genesis Aeon profile: {}", e))?;
+        }
+    }
 
-    // Mint CGT to Genesis Archon
-    let bank_module = BankCgtModule::new();
-    let mint_params = crate::runtime::bank_cgt::MintToParams {
-        to: genesis_addr,
-        amount: GENESIS_ARCHON_INITIAL_BALANCE,
+    // Data directories from before the Forge parameters were configurable
+    // were all hashed with the defaults
+    let forge = match state.get_raw(KEY_FORGE_CONFIG) {
+        Some(bytes) => Some(codec::decode_state::<ForgeConfig>(&bytes)?),
+        None if state.get_raw(KEY_GENESIS_HASH).is_some() => Some(ForgeConfig::default()),
+        None => None,
     };
-    let mint_tx = Transaction {
-        from: [0u8; 32], // Genesis authority (all zeros)
-        nonce: 0,
-        module_id: "bank_cgt".to_string(),
-        call_id: "mint_to".to_string(),
-        payload: bincode::serialize(&mint_params)?,
-        fee: 0,
-        signature: vec![],
-    };
-    bank_module
-        .dispatch("mint_to", &mint_tx, state)
-        .map_err(|e| anyhow::anyhow!("Failed to mint genesis CGT: {}", e))?;
-
-    // Mark Genesis Archon as Archon
-    let avatars_module = AvatarsProfilesModule::new();
-    let claim_tx = Transaction {
-        from: genesis_addr,
-        nonce: 0,
-        module_id: "avatars_profiles".to_string(),
-        call_id: "claim_archon".to_string(),
-        payload: vec![],
-        fee: 0,
-        signature: vec![],
-    };
-    avatars_module
-        .dispatch("claim_archon", &claim_tx, state)
-        .map_err(|e| anyhow::anyhow!("Failed to claim genesis Archon: {}", e))?;
+    match forge {
+        Some(recorded) if recorded != genesis.forge => {
+            return Err(anyhow::anyhow!(
+                "data directory was initialized with Forge parameters {}, but the config gives {}",
+                recorded,
+                genesis.forge
+            ));
+        }
+        _ => state.put_raw(KEY_FORGE_CONFIG.to_vec(), codec::encode(&genesis.forge)?)?,
+    }
 
-    // Mark genesis as initialized
-    state
-        .put_raw(KEY_GENESIS_INITIALIZED.to_vec(), vec![1u8])?;
+    // Store the genesis block; like the profile, this backfills older chains
+    let block = genesis_block(genesis);
+    let hash = block.header.hash();
+    match state.get_raw(KEY_GENESIS_HASH) {
+        Some(recorded) if recorded != hash => {
+            return Err(anyhow::anyhow!(
+                "data directory was initialized with genesis {}, but the config gives {}",
+                hex::encode(recorded),
+                hex::encode(hash)
+            ));
+        }
+        Some(_) => {}
+        None => {
+            state.put_raw(
+                block_key(genesis.start_height),
+                codec::encode_versioned(&block)?,
+            )?;
+            state.put_raw(KEY_GENESIS_HASH.to_vec(), hash.to_vec())?;
+        }
+    }
+
+    // Data directories from before the tip was recorded take the highest
+    // stored block that links back to genesis
+    if chain_tip(state).is_none() {
+        let mut tip = ChainTip {
+            height: genesis.start_height,
+            hash,
+        };
+        while let Some(next) = load_block(state, tip.height + 1) {
+            if next.header.prev_hash != tip.hash {
+                break;
+            }
+            tip = ChainTip {
+                height: next.header.height,
+                hash: next.header.hash(),
+            };
+        }
+        put_chain_tip(state, &tip)?;
+    }
+
+    // Data directories from before block execution recorded timestamps
+    // start checking them against the tip's
+    if state.last_block_timestamp().is_none() {
+        let tip =
+            chain_tip(state).ok_or_else(|| anyhow::anyhow!("genesis recorded no chain tip"))?;
+        let timestamp = load_block(state, tip.height).map_or(0, |block| block.header.timestamp);
+        state.set_last_block_timestamp(timestamp)?;
+    }
 
     Ok(())
 }
+
+/// Check that the recorded chain tip names a stored block with that hash.
+///
+/// The tip and its block are committed together, so a mismatch means the
+/// data directory was changed outside the node.
+fn check_chain_tip(state: &State) -> Result<()> {
+    let tip = chain_tip(state).ok_or_else(|| anyhow::anyhow!("no chain tip recorded"))?;
+    match load_block(state, tip.height) {
+        Some(block) if block.header.hash() == tip.hash => Ok(()),
+        _ => Err(anyhow::anyhow!(
+            "chain tip at height {} ({}) does not match the stored block",
+            tip.height,
+            hex::encode(tip.hash)
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        GenesisArchon, DEFAULT_GENESIS_ARCHON_NAME, GENESIS_ARCHON_ADDRESS,
+        GENESIS_ARCHON_INITIAL_BALANCE,
+    };
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::core::tx_index::pruned_count;
+    use crate::runtime::bank_cgt::TransferParams;
+
+    /// A zero-CGT transfer, which an unfunded sender can afford.
+    fn expiring_tx(nonce: u64, valid_until_height: Option<u64>) -> Transaction {
+        signed(Transaction {
+            from: account(1),
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to: [2; 32],
+                amount: 0,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height,
+            sig_scheme: 0,
+        })
+    }
+
+    #[test]
+    fn test_concurrent_readers_share_state() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let (a_tx, a_rx) = mpsc::channel();
+        let (b_tx, b_rx) = mpsc::channel();
+        // Each reader waits, while holding the lock, for the other to get in
+        let reader = |signal: mpsc::Sender<()>, wait: mpsc::Receiver<()>| {
+            node.with_state(|_| {
+                signal.send(()).unwrap();
+                wait.recv_timeout(Duration::from_secs(5)).is_ok()
+            })
+        };
+        let (a, b) = std::thread::scope(|s| {
+            let a = s.spawn(|| reader(a_tx, b_rx));
+            let b = s.spawn(|| reader(b_tx, a_rx));
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert!(a && b, "readers were serialized");
+    }
+
+    #[test]
+    fn test_node_serves_after_panic_while_locked() {
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let balance = node.get_balance_cgt(&GENESIS_ARCHON_ADDRESS);
+        std::thread::scope(|s| {
+            // A writer panics with an uncommitted write in an open scope
+            let writer = s.spawn(|| {
+                node.with_state_mut(|state| {
+                    state.begin();
+                    bank::credit(state, &GENESIS_ARCHON_ADDRESS, 5).unwrap();
+                    panic!("handler bug");
+                })
+            });
+            assert!(writer.join().is_err());
+            let holder = s.spawn(|| {
+                let _mempool = lock(&node.mempool, "mempool");
+                panic!("handler bug");
+            });
+            assert!(holder.join().is_err());
+        });
+        assert!(node.state.is_poisoned());
+        assert!(node.mempool.is_poisoned());
+
+        // Reads recover the lock and see only committed state
+        assert_eq!(node.get_balance_cgt(&GENESIS_ARCHON_ADDRESS), balance);
+        assert!(!node.state.is_poisoned());
+        assert!(node.mempool_transactions().is_empty());
+        assert!(!node.mempool.is_poisoned());
+
+        // Writes work again too
+        node.with_state_mut(|state| bank::credit(state, &[1; 32], 7))
+            .unwrap();
+        assert_eq!(node.get_balance_cgt(&[1; 32]), 7);
+    }
+
+    #[test]
+    fn test_account_status_reports_nonce_gap() {
+        use crate::runtime::storage_key::StorageKey;
+
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let nonce_key = StorageKey::new("bank_cgt", "nonce")
+            .address(&account(1))
+            .into_bytes();
+        node.with_state_mut(|state| state.put_raw(nonce_key, codec::encode(&5u64).unwrap()))
+            .unwrap();
+        for nonce in [7, 5] {
+            node.submit_transaction(expiring_tx(nonce, None)).unwrap();
+        }
+        let mut other = expiring_tx(6, None);
+        other.from = account(2);
+        node.submit_transaction(signed(other)).unwrap();
+        // A call that does not consume a nonce neither fills nor opens a gap
+        let mut mint = expiring_tx(6, None);
+        mint.module_id = "nft_dgen".to_string();
+        mint.call_id = "mint_dgen".to_string();
+        let mint = signed(mint);
+        node.submit_transaction(mint.clone()).unwrap();
+
+        let status = node.account_status(&account(1));
+        assert_eq!(status.committed_nonce, 5);
+        assert_eq!(status.highest_contiguous_pending_nonce, Some(5));
+        assert_eq!(status.nonce_gaps, vec![NonceRange { start: 6, end: 6 }]);
+        assert_eq!(
+            status.pending,
+            vec![
+                (5, expiring_tx(5, None).hash()),
+                (6, mint.hash()),
+                (7, expiring_tx(7, None).hash())
+            ]
+        );
+        assert_eq!(status.balance, 0);
+
+        // Without the committed nonce pending, nothing can be included
+        let status =
+            AccountStatus::new(5, vec![(3, [0; 32]), (8, [0; 32])], vec![(12, [0; 32])], 0);
+        assert_eq!(status.highest_contiguous_pending_nonce, None);
+        assert_eq!(status.nonce_gaps, vec![NonceRange { start: 5, end: 7 }]);
+    }
+
+    #[test]
+    fn test_submit_rejects_expired_transaction() {
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        node.with_state_mut(|state| {
+            put_chain_tip(
+                state,
+                &ChainTip {
+                    height: 5,
+                    hash: [0; 32],
+                },
+            )
+        })
+        .unwrap();
+
+        // Next block is 6: valid until 6 is still admissible, 5 is not
+        assert!(node.submit_transaction(expiring_tx(0, Some(6))).is_ok());
+        let err = node
+            .submit_transaction(expiring_tx(1, Some(5)))
+            .unwrap_err();
+        assert!(err.contains("expired"));
+        assert_eq!(node.mempool_transactions().len(), 1);
+    }
+
+    #[test]
+    fn test_submit_rejects_transactions_certain_to_fail() {
+        use crate::runtime::storage_key::StorageKey;
+        use crate::runtime::storage_value::put_u64;
+
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        node.with_state_mut(|state| bank::credit(state, &account(1), 1_000))
+            .unwrap();
+        let transfer = |nonce: u64, amount: u64, fee: u64| {
+            signed(Transaction {
+                fee,
+                payload: codec::encode(&TransferParams {
+                    to: [2; 32],
+                    amount,
+                    memo: None,
+                })
+                .unwrap(),
+                ..expiring_tx(nonce, None)
+            })
+        };
+        let reject = |tx: Transaction| node.submit_transaction(signed(tx)).unwrap_err();
+
+        let mut unknown_module = expiring_tx(0, None);
+        unknown_module.module_id = "vault".to_string();
+        assert!(reject(unknown_module).contains("unknown module 'vault'"));
+        let mut unknown_call = expiring_tx(0, None);
+        unknown_call.call_id = "burn".to_string();
+        assert!(reject(unknown_call).contains("unknown call bank_cgt.burn"));
+        let mut garbled = expiring_tx(0, None);
+        garbled.payload = vec![1, 2, 3];
+        assert!(reject(garbled).contains("invalid transfer payload"));
+        // Bytes past the params are refused rather than read as an older layout
+        let mut padded = transfer(0, 1, 0);
+        padded.payload.push(0);
+        assert!(reject(padded).contains("invalid transfer payload"));
+
+        // Only the sender's own signature over the transaction is accepted
+        let mut forged = transfer(0, 1, 0);
+        forged.fee = 1;
+        let err = node.submit_transaction(forged).unwrap_err();
+        assert!(err.contains("Invalid signature"), "{}", err);
+        let mut unsigned = transfer(0, 1, 0);
+        unsigned.signature.clear();
+        let err = node.submit_transaction(unsigned).unwrap_err();
+        assert!(err.contains("Invalid signature"), "{}", err);
+
+        // Amount + fee is checked against the committed balance
+        let balance = node.get_balance_cgt(&account(1));
+        assert!(reject(transfer(0, balance, 1)).contains("insufficient balance"));
+        assert!(reject(transfer(0, u64::MAX, 1)).contains("overflows"));
+        assert!(node.mempool_transactions().is_empty());
+
+        node.submit_transaction(transfer(0, balance - 1, 1))
+            .unwrap();
+
+        // A used nonce is rejected; a future one queues
+        let nonce_key = StorageKey::new("bank_cgt", "nonce")
+            .address(&account(1))
+            .into_bytes();
+        node.with_state_mut(|state| put_u64(state, nonce_key, 2))
+            .unwrap();
+        assert!(reject(transfer(1, 10, 0)).contains("nonce too low: account nonce is 2"));
+        node.submit_transaction(transfer(4, 10, 0)).unwrap();
+        assert_eq!(node.mempool_transactions().len(), 2);
+    }
+
+    /// Mints from `account(0)`, which `minting_config` authorizes.
+    fn mint_tx(to: Address, amount: u64) -> Transaction {
+        signed(Transaction {
+            from: account(0),
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "mint_to".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::MintToParams { to, amount }).unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        })
+    }
+
+    #[test]
+    fn test_genesis_block_stored_at_height_zero() {
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let genesis = node.get_block_by_height(0).unwrap().unwrap();
+        assert_eq!(genesis, genesis_block(&node.config.genesis));
+        assert_eq!(genesis.header.hash(), node.genesis_hash());
+        assert_eq!(node.block_template().header.prev_hash, node.genesis_hash());
+
+        // Same genesis on every node of the chain
+        let other = Node::in_memory(ChainConfig::default()).unwrap();
+        assert_eq!(other.get_block_by_height(0), Ok(Some(genesis)));
+    }
+
+    #[test]
+    fn test_configured_genesis_timestamp_and_start_height() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.genesis.genesis_timestamp = unix_now() + 3_600;
+        config.genesis.start_height = 500;
+        let genesis_timestamp = config.genesis.genesis_timestamp;
+        let node = Node::in_memory(config).unwrap();
+
+        let genesis = node.get_block_by_height(500).unwrap().unwrap();
+        assert_eq!(genesis.header.timestamp, genesis_timestamp);
+        assert_eq!(genesis.header.hash(), node.genesis_hash());
+        assert_eq!(node.get_block_by_height(0), Ok(None));
+        assert_eq!(node.chain_info().height, 500);
+
+        // The first block must be timestamped after genesis
+        let mut template = node.block_template();
+        assert_eq!(template.header.height, 501);
+        assert_eq!(template.header.timestamp, genesis_timestamp + 1);
+        template.header.timestamp = genesis_timestamp;
+        *lock(&node.work, "work") = Some(template);
+        let err = node.submit_work(0).unwrap_err();
+        assert!(err.contains("not after its parent's"), "{}", err);
+        assert_eq!(node.chain_info().height, 500);
+
+        let block = mine_block(&node);
+        assert_eq!(block.header.height, 501);
+        assert_eq!(node.latest_blocks(None, 10), vec![block, genesis]);
+    }
+
+    #[test]
+    fn test_genesis_mismatch_refused() {
+        let mut state = State::in_memory();
+        let genesis = GenesisConfig::default();
+        init_genesis_state(&mut state, &genesis).unwrap();
+        // Re-running with the same genesis is a no-op
+        init_genesis_state(&mut state, &genesis).unwrap();
+
+        let mut changed = genesis.clone();
+        changed.difficulty_target -= 1;
+        let err = init_genesis_state(&mut state, &changed).unwrap_err();
+        assert!(err.to_string().contains("initialized with genesis"));
+        assert_eq!(
+            state.get_raw(KEY_GENESIS_HASH),
+            Some(genesis_header(&genesis).hash().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_forge_params_fixed_at_initialization() {
+        let mut state = State::in_memory();
+        let genesis = GenesisConfig::default();
+        init_genesis_state(&mut state, &genesis).unwrap();
+
+        let mut changed = genesis.clone();
+        changed.forge.memory_kib /= 2;
+        let err = init_genesis_state(&mut state, &changed).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("initialized with Forge parameters"));
+
+        // A directory from before the parameters were recorded was hashed
+        // with the defaults
+        state.delete_raw(KEY_FORGE_CONFIG).unwrap();
+        state.delete_raw(KEY_GENESIS_APPLIED).unwrap();
+        assert!(init_genesis_state(&mut state, &changed).is_err());
+        state.delete_raw(KEY_FORGE_CONFIG).unwrap();
+        init_genesis_state(&mut state, &genesis).unwrap();
+        assert_eq!(
+            state.get_raw(KEY_FORGE_CONFIG),
+            Some(codec::encode(&genesis.forge).unwrap())
+        );
+
+        // A new directory takes whatever the config gives
+        let mut fresh = State::in_memory();
+        init_genesis_state(&mut fresh, &changed).unwrap();
+        changed.forge.lanes = 0;
+        let err = init_genesis_state(&mut fresh, &changed).unwrap_err();
+        assert!(err.to_string().contains("invalid Forge parameters"));
+    }
+
+    fn minting_config() -> ChainConfig {
+        let mut config = ChainConfig::default();
+        config.genesis.mint_authorities = vec![account(0)];
+        config
+    }
+
+    #[test]
+    fn test_block_template_skips_failing_transactions() {
+        let node = Node::in_memory(minting_config()).unwrap();
+        node.submit_transaction(mint_tx([9; 32], 50)).unwrap();
+        node.submit_transaction(expiring_tx(0, None)).unwrap();
+        // Admitted, but nonce 0 is taken by the transfer before it
+        node.submit_transaction(expiring_tx(0, Some(10))).unwrap();
+
+        let template = node.block_template();
+        assert_eq!(template.header.height, 1);
+        assert_eq!(template.header.prev_hash, node.genesis_hash());
+        assert_eq!(
+            template.body,
+            vec![mint_tx([9; 32], 50), expiring_tx(0, None)]
+        );
+
+        // Building a template does not touch state
+        assert_eq!(node.get_balance_cgt(&[9; 32]), 0);
+    }
+
+    #[test]
+    fn test_submit_work_accepts_and_rejects_nonce() {
+        use crate::forge::{forge_hash, meets_difficulty};
+
+        // Roughly half of all nonces meet this target
+        let mut config = minting_config();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        let node = Node::in_memory(config).unwrap();
+        node.submit_transaction(mint_tx([9; 32], 50)).unwrap();
+
+        assert!(node.submit_work(0).unwrap_err().contains("cgt_getWork"));
+
+        let template = node.block_template();
+        let header_bytes = template.header.serialize_without_nonce();
+        let target = template.header.difficulty_target;
+        let meets = |nonce: u64| {
+            meets_difficulty(
+                &forge_hash(&header_bytes, nonce, &node.forge_config()),
+                target,
+            )
+        };
+        let bad = (0..).find(|n| !meets(*n)).unwrap();
+        let good = (0..).find(|n| meets(*n)).unwrap();
+
+        assert!(node.submit_work(bad).is_err());
+        assert_eq!(node.chain_info().height, 0);
+        assert_eq!(node.get_block_by_height(1), Ok(None));
+
+        let block = node.submit_work(good).unwrap();
+        assert_eq!(block.header.nonce, good);
+        assert_eq!(node.chain_info().height, 1);
+        assert_eq!(node.get_block_by_height(1), Ok(Some(block.clone())));
+        assert_eq!(node.get_balance_cgt(&[9; 32]), 50);
+        assert!(node.mempool_transactions().is_empty());
+
+        // The template was consumed; the next one builds on the new tip
+        assert!(node.submit_work(good).is_err());
+        assert_eq!(node.block_template().header.prev_hash, block.header.hash());
+    }
+
+    #[test]
+    fn test_future_nonces_wait_for_their_predecessors() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Node::in_memory(config).unwrap();
+        node.submit_transaction(expiring_tx(0, None)).unwrap();
+        mine_block(&node);
+
+        node.submit_transaction(expiring_tx(2, None)).unwrap();
+        // Nonce 1 is missing, so nonce 2 waits in the mempool
+        assert!(mine_block(&node).body.is_empty());
+        assert_eq!(node.mempool_transactions(), vec![expiring_tx(2, None)]);
+
+        node.submit_transaction(expiring_tx(1, None)).unwrap();
+        let block = mine_block(&node);
+        assert_eq!(block.body, vec![expiring_tx(1, None), expiring_tx(2, None)]);
+        for tx in &block.body {
+            assert!(node.get_receipt(&tx.hash()).unwrap().unwrap().success);
+        }
+        assert_eq!(node.account_status(&account(1)).committed_nonce, 3);
+        assert!(node.mempool_transactions().is_empty());
+    }
+
+    #[test]
+    fn test_batches_go_in_nonce_order_with_transfers() {
+        use crate::runtime::batch::{BatchCall, ExecuteBatchParams};
+
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Node::in_memory(config).unwrap();
+        let batch = |nonce, calls: Vec<BatchCall>| {
+            signed(Transaction {
+                module_id: "batch".to_string(),
+                call_id: "execute_batch".to_string(),
+                payload: codec::encode(&ExecuteBatchParams { calls }).unwrap(),
+                ..expiring_tx(nonce, None)
+            })
+        };
+        let transfer = BatchCall {
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: expiring_tx(0, None).payload,
+        };
+        let not_a_transfer = BatchCall {
+            module_id: "avatars_profiles".to_string(),
+            call_id: "claim_archon".to_string(),
+            payload: Vec::new(),
+        };
+
+        // Arriving out of order, a batch holding two transfers and one
+        // holding none each take one nonce between the plain transfers
+        let txs = [
+            expiring_tx(3, None),
+            batch(2, vec![not_a_transfer.clone()]),
+            batch(1, vec![transfer.clone(), transfer]),
+            expiring_tx(0, None),
+        ];
+        for tx in &txs {
+            node.submit_transaction(tx.clone()).unwrap();
+        }
+        let block = mine_block(&node);
+        let nonces: Vec<_> = block.body.iter().map(|tx| tx.nonce).collect();
+        assert_eq!(nonces, vec![0, 1, 2, 3]);
+        for tx in &block.body {
+            let receipt = node.get_receipt(&tx.hash()).unwrap().unwrap();
+            assert!(receipt.success, "{:?}", receipt.error);
+        }
+        assert_eq!(node.account_status(&account(1)).committed_nonce, 4);
+
+        // A stale batch is refused at admission
+        let err = node
+            .submit_transaction(batch(1, vec![not_a_transfer]))
+            .unwrap_err();
+        assert!(err.contains("nonce too low"), "{}", err);
+    }
+
+    #[test]
+    fn test_pending_transactions_capped_per_sender() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.mempool.max_txs_per_sender = 2;
+        let node = Node::in_memory(config).unwrap();
+
+        node.submit_transaction(expiring_tx(0, None)).unwrap();
+        node.submit_transaction(expiring_tx(5, None)).unwrap();
+        let err = node.submit_transaction(expiring_tx(1, None)).unwrap_err();
+        assert!(
+            err.contains("already has 2 transactions pending"),
+            "{}",
+            err
+        );
+
+        // Other senders keep their own allowance
+        let mut other = expiring_tx(0, None);
+        other.from = account(3);
+        node.submit_transaction(signed(other.clone())).unwrap();
+        other.nonce = 1;
+        node.submit_transaction(signed(other)).unwrap();
+        assert_eq!(node.mempool_transactions().len(), 4);
+
+        // Inclusion frees the sender's slot
+        mine_block(&node);
+        node.submit_transaction(expiring_tx(1, None)).unwrap();
+    }
+
+    #[test]
+    fn test_full_mempool_evicts_for_a_higher_fee() {
+        let mut config = ChainConfig::default();
+        config.mempool.max_txs = 1;
+        let node = Node::in_memory(config).unwrap();
+        let mut events = node.subscribe_mempool();
+
+        let cheap = expiring_tx(0, None);
+        node.submit_transaction(cheap.clone()).unwrap();
+        let mut same_fee = expiring_tx(0, None);
+        same_fee.from = account(3);
+        let err = node.submit_transaction(signed(same_fee)).unwrap_err();
+        assert!(err.contains("mempool is full"), "{}", err);
+
+        node.with_state_mut(|state| bank::credit(state, &account(4), 1))
+            .unwrap();
+        let paying = signed(Transaction {
+            from: account(4),
+            fee: 1,
+            ..expiring_tx(0, None)
+        });
+        node.submit_transaction(paying.clone()).unwrap();
+        assert_eq!(node.mempool_transactions(), vec![paying]);
+        let evicted = std::iter::from_fn(|| events.try_recv().ok()).find_map(|event| match event {
+            MempoolEvent::Evicted { tx, .. } => Some(tx.tx_hash),
+            _ => None,
+        });
+        assert_eq!(evicted, Some(cheap.hash()));
+    }
+
+    #[test]
+    fn test_nonce_failures_retry_then_dead_letter() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.mempool.nonce_retries = 2;
+        let node = Node::in_memory(config).unwrap();
+        let mut events = node.subscribe_mempool();
+
+        // Nonce 1 shows up while nonce 2 still has retries left
+        node.submit_transaction(expiring_tx(2, None)).unwrap();
+        mine_block(&node);
+        node.submit_transaction(expiring_tx(0, None)).unwrap();
+        node.submit_transaction(expiring_tx(1, None)).unwrap();
+        assert_eq!(mine_block(&node).body.len(), 3);
+        assert_eq!(node.failed_transaction(&expiring_tx(2, None).hash()), None);
+
+        // Nonce 4 never gets its predecessor
+        let gapped = expiring_tx(4, None);
+        node.submit_transaction(gapped.clone()).unwrap();
+        for _ in 0..2 {
+            node.block_template();
+            mine_block(&node);
+        }
+        assert_eq!(node.mempool_transactions(), vec![gapped.clone()]);
+        assert_eq!(node.failed_transaction(&gapped.hash()), None);
+
+        mine_block(&node);
+        assert!(node.mempool_transactions().is_empty());
+        let failure = node.failed_transaction(&gapped.hash()).unwrap();
+        assert_eq!(failure.tx, gapped);
+        assert_eq!(failure.reason, "invalid nonce: expected 3, got 4");
+        assert_eq!(failure.attempts, 3);
+        assert_eq!(failure.last_attempted_height, 5);
+
+        let evicted = std::iter::from_fn(|| events.try_recv().ok())
+            .find_map(|event| match event {
+                MempoolEvent::Evicted { tx, reason } => Some((tx, reason)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(evicted.0.tx_hash, gapped.hash());
+        assert_eq!(evicted.1, "dead-lettered: invalid nonce: expected 3, got 4");
+
+        assert_eq!(node.purge_failed(None), 1);
+        assert_eq!(node.failed_transaction(&gapped.hash()), None);
+    }
+
+    /// Mine the current template with the first nonce that meets the target.
+    fn mine_block(node: &Node) -> Block {
+        use crate::forge::{forge_hash, meets_difficulty};
+
+        let template = node.block_template();
+        let header_bytes = template.header.serialize_without_nonce();
+        let nonce = (0..)
+            .find(|n| {
+                meets_difficulty(
+                    &forge_hash(&header_bytes, *n, &node.forge_config()),
+                    template.header.difficulty_target,
+                )
+            })
+            .unwrap();
+        node.submit_work(nonce).unwrap()
+    }
+
+    /// Start a new node on `node`'s state, as if the process had restarted.
+    fn restart(node: Node) -> Result<Node> {
+        let config = node.config.clone();
+        let state = Arc::try_unwrap(node.state)
+            .ok()
+            .expect("no other handles to the state")
+            .into_inner()
+            .unwrap();
+        Node::from_state(state, PathBuf::new(), config, Duration::ZERO)
+    }
+
+    #[test]
+    fn test_read_only_start_writes_nothing() {
+        let mut config = ChainConfig::default();
+        config.rpc.read_only = true;
+        let err = Node::from_state(
+            State::in_memory(),
+            PathBuf::new(),
+            config.clone(),
+            Duration::ZERO,
+        )
+        .err()
+        .unwrap();
+        assert!(
+            err.to_string().contains("needs a database with a chain"),
+            "{}",
+            err
+        );
+
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let before = node.with_state(|state| state.scan_prefix(b""));
+        let state = Arc::try_unwrap(node.state)
+            .ok()
+            .expect("no other handles to the state")
+            .into_inner()
+            .unwrap();
+        let node = Node::from_state(state, PathBuf::new(), config.clone(), Duration::ZERO).unwrap();
+        assert_eq!(node.with_state(|state| state.scan_prefix(b"")), before);
+        assert!(node.node_info().features.read_only);
+        assert!(!node.node_info().features.dev_faucet);
+
+        // On disk, the database is opened read-only once a chain is in it
+        let dir = tempfile::tempdir().unwrap();
+        let genesis = Node::with_config(dir.path().to_path_buf(), ChainConfig::default())
+            .unwrap()
+            .genesis_hash();
+        let node = Node::with_config(dir.path().to_path_buf(), config).unwrap();
+        assert_eq!(node.genesis_hash(), genesis);
+    }
+
+    #[test]
+    fn test_compact_keeps_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Node::with_config(dir.path().to_path_buf(), config).unwrap();
+        let block = mine_block(&node);
+        node.with_state_mut(|state| -> Result<()> {
+            for i in 0u8..10 {
+                state.put_raw(vec![b'k', i], vec![i])?;
+            }
+            for i in 0u8..5 {
+                state.delete_raw(&[b'k', i])?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        node.compact();
+        node.with_state(|state| {
+            for i in 0u8..5 {
+                assert_eq!(state.get_raw(&[b'k', i]), None);
+            }
+            for i in 5u8..10 {
+                assert_eq!(state.get_raw(&[b'k', i]), Some(vec![i]));
+            }
+        });
+        assert_eq!(node.chain_tip().hash, block.header.hash());
+        assert_eq!(
+            node.get_block_by_height(1).unwrap().unwrap().header,
+            block.header
+        );
+
+        // Compaction runs without the state lock, so a writer holding it
+        // does not stall it
+        let compact = node.with_state(State::compactor).unwrap();
+        node.with_state_mut(|_| compact());
+
+        // The in-memory backend has nothing to compact
+        Node::in_memory(ChainConfig::default()).unwrap().compact();
+    }
+
+    #[test]
+    fn test_snapshot_reads_are_not_split_by_a_transfer() {
+        use crate::runtime::module_api::nft;
+        use crate::runtime::nft_dgen::MintDgenParams;
+        use std::sync::mpsc;
+
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let owner = GENESIS_ARCHON_ADDRESS;
+        let buyer = [9; 32];
+        let id = node
+            .with_state_mut(|state| {
+                nft::mint(
+                    state,
+                    &owner,
+                    &MintDgenParams {
+                        fabric_root_hash: [7; 32],
+                        forge_model_id: None,
+                        forge_prompt_hash: None,
+                        royalty_recipient: None,
+                        royalty_bps: 0,
+                        uri_hint: None,
+                        royalty_splits: Vec::new(),
+                    },
+                )
+            })
+            .unwrap();
+
+        let node = &node;
+        std::thread::scope(|scope| {
+            let snapshot = node.snapshot();
+            let ids = get_nfts_by_owner(&snapshot, &owner);
+            assert_eq!(ids, vec![id]);
+
+            // Between the index read and the metadata reads, another thread
+            // transfers the NFT away
+            let (started, wait) = mpsc::channel();
+            let writer = scope.spawn(move || {
+                started.send(()).unwrap();
+                node.with_state_mut(|state| nft::force_transfer(state, id, &buyer))
+            });
+            wait.recv().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(50));
+
+            // The snapshot still lists only NFTs the address owns
+            assert!(!writer.is_finished());
+            for id in &ids {
+                assert_eq!(get_nft(&snapshot, *id).unwrap().owner, owner);
+            }
+            drop(snapshot);
+            writer.join().unwrap().unwrap();
+        });
+
+        // The transfer applies once the snapshot is released
+        let snapshot = node.snapshot();
+        assert!(get_nfts_by_owner(&snapshot, &owner).is_empty());
+        assert_eq!(get_nft(&snapshot, id).unwrap().owner, buyer);
+    }
+
+    #[test]
+    fn test_rocksdb_snapshot_does_not_block_writers() {
+        use crate::runtime::module_api::nft;
+
+        let dir = tempfile::tempdir().unwrap();
+        let node = Node::with_config(dir.path().to_path_buf(), ChainConfig::default()).unwrap();
+        let (owner, buyer) = (GENESIS_ARCHON_ADDRESS, [9; 32]);
+        let params = MintDgenParams {
+            fabric_root_hash: [7; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
+        };
+        let id = node
+            .with_state_mut(|state| nft::mint(state, &owner, &params))
+            .unwrap();
+
+        // The transfer commits while the snapshot is open, which still sees
+        // the state from before it
+        let snapshot = node.snapshot();
+        node.with_state_mut(|state| nft::force_transfer(state, id, &buyer))
+            .unwrap();
+        assert_eq!(get_nfts_by_owner(&snapshot, &owner), vec![id]);
+        assert_eq!(get_nft(&snapshot, id).unwrap().owner, owner);
+        drop(snapshot);
+
+        assert_eq!(get_nft(&node.snapshot(), id).unwrap().owner, buyer);
+    }
+
+    #[test]
+    fn test_chain_tip_survives_restart() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Node::in_memory(config).unwrap();
+        assert_eq!(node.chain_tip().hash, node.genesis_hash());
+        let first = mine_block(&node);
+
+        let node = restart(node).unwrap();
+        let first_tip = ChainTip {
+            height: 1,
+            hash: first.header.hash(),
+        };
+        assert_eq!(node.chain_tip(), first_tip);
+        assert_eq!(node.block_template().header.prev_hash, first_tip.hash);
+
+        // A block stored without its tip (as a commit split between the two
+        // could once leave) is ignored, and the next block replaces it
+        let orphan = node.block_template();
+        node.with_state_mut(|state| {
+            state.put_raw(block_key(2), codec::encode_versioned(&orphan).unwrap())
+        })
+        .unwrap();
+        let node = restart(node).unwrap();
+        assert_eq!(node.chain_tip(), first_tip);
+        let second = mine_block(&node);
+        assert_eq!(second.header.prev_hash, first_tip.hash);
+        assert_eq!(node.chain_info().height, 2);
+
+        // Data directories from before the tip was recorded find it by
+        // following the stored blocks
+        node.with_state_mut(|state| {
+            state.delete_raw(KEY_CHAIN_TIP)?;
+            state.delete_raw(KEY_GENESIS_APPLIED)
+        })
+        .unwrap();
+        let node = restart(node).unwrap();
+        assert_eq!(node.chain_tip().hash, second.header.hash());
+
+        // A tip without its block is refused rather than built on
+        node.with_state_mut(|state| {
+            put_chain_tip(
+                state,
+                &ChainTip {
+                    height: 3,
+                    hash: [7; 32],
+                },
+            )
+        })
+        .unwrap();
+        let err = restart(node).err().unwrap();
+        assert!(err.to_string().contains("does not match the stored block"));
+    }
+
+    #[test]
+    fn test_stake_rewards_accrue_to_the_chain_tip() {
+        use crate::runtime::staking::StakeParams;
+
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Node::in_memory(config).unwrap();
+        let staker = account(1);
+        node.with_state_mut(|state| bank::credit(state, &staker, 1_000_000_000))
+            .unwrap();
+        node.submit_transaction(signed(Transaction {
+            module_id: "staking".to_string(),
+            call_id: "stake".to_string(),
+            payload: codec::encode(&StakeParams {
+                amount: 1_000_000_000,
+            })
+            .unwrap(),
+            ..expiring_tx(0, None)
+        }))
+        .unwrap();
+        for _ in 0..4 {
+            mine_block(&node);
+        }
+        let pending = |node: &Node| node.get_stake(&staker).0.unwrap().pending_rewards;
+        // 10 base units per block from the stake's block to the tip
+        assert_eq!(pending(&node), 30);
+
+        // Neither a template's dry run nor a restart moves the height it
+        // settles at
+        node.block_template();
+        assert_eq!(pending(&node), 30);
+        let node = restart(node).unwrap();
+        assert_eq!(pending(&node), 30);
+    }
+
+    #[test]
+    fn test_transaction_proof_against_mined_block() {
+        use crate::core::block::verify_inclusion_proof;
+
+        let mut config = minting_config();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        let node = Node::in_memory(config).unwrap();
+        node.submit_transaction(mint_tx([9; 32], 50)).unwrap();
+        node.submit_transaction(mint_tx([8; 32], 20)).unwrap();
+        let block = mine_block(&node);
+        assert_eq!(block.header.tx_root, Some(tx_root(&block.body)));
+
+        let tx_hash = block.body[1].hash();
+        let found = node.transaction_proof(&tx_hash).unwrap().unwrap();
+        assert_eq!(found.height, 1);
+        assert_eq!(found.block_hash, block.header.hash());
+        assert_eq!((found.proof.index, found.proof.tx_count), (1, 2));
+        assert!(verify_inclusion_proof(
+            &found.proof,
+            &found.tx_root.unwrap()
+        ));
+        assert_eq!(node.transaction_proof(&[7; 32]), Ok(None));
+
+        // A block stored before headers carried a root has nothing to prove against
+        node.with_state_mut(|state| {
+            let mut legacy = block.clone();
+            legacy.header.tx_root = None;
+            state
+                .put_raw(block_key(1), codec::encode_versioned(&legacy).unwrap())
+                .unwrap();
+        });
+        let found = node.transaction_proof(&tx_hash).unwrap().unwrap();
+        assert_eq!(found.tx_root, None);
+        assert_eq!(found.proof.index, 1);
+    }
+
+    #[test]
+    fn test_pruning_runs_in_batches() {
+        let mut state = State::in_memory();
+        let header = genesis_header(&GenesisConfig::default());
+        for height in 1..=40 {
+            let block = Block {
+                header: BlockHeader {
+                    height,
+                    ..header.clone()
+                },
+                body: vec![mint_tx([9; 32], height)],
+            };
+            state
+                .put_raw(block_key(height), codec::encode_versioned(&block).unwrap())
+                .unwrap();
+            let event = Event::new("bank_cgt", "mint", [9; 32]);
+            state
+                .finish_tx(&block.body[0], height, None, vec![event])
+                .unwrap();
+        }
+
+        // A long backlog is worked off a batch at a time
+        assert_eq!(
+            prune_blocks(&mut state, 0, 3, 40).unwrap(),
+            PRUNE_BATCH_BLOCKS
+        );
+        assert_eq!(pruned_below(&state), PRUNE_BATCH_BLOCKS + 1);
+        assert_eq!(prune_blocks(&mut state, 0, 3, 40).unwrap(), 5);
+        assert_eq!(pruned_below(&state), 38);
+        assert_eq!(prune_blocks(&mut state, 0, 3, 40).unwrap(), 0);
+
+        assert!(load_block(&state, 37).unwrap().body.is_empty());
+        assert_eq!(load_block(&state, 37).unwrap().header.height, 37);
+        assert_eq!(load_block(&state, 38).unwrap().body.len(), 1);
+        assert!(!is_pruned(&state, 0, 0));
+
+        // Pruned transactions leave the index of everyone involved
+        let tx_hash = |height| mint_tx([9; 32], height).hash();
+        assert_eq!(pruned_count(&state, &[9; 32]), 37);
+        assert_eq!(pruned_count(&state, &account(0)), 37);
+        assert!(get_receipt(&state, &tx_hash(37)).is_none());
+        assert!(get_receipt(&state, &tx_hash(38)).is_some());
+
+        // Receipt markers last another 3 blocks, and go a batch at a time too
+        assert_eq!(pruned_receipt_height(&state, &tx_hash(34)), None);
+        assert_eq!(pruned_receipt_height(&state, &tx_hash(35)), Some(35));
+        prune_blocks(&mut state, 0, 3, 41).unwrap();
+        assert_eq!(pruned_receipt_height(&state, &tx_hash(35)), None);
+        assert_eq!(pruned_receipt_height(&state, &tx_hash(36)), Some(36));
+    }
+
+    #[test]
+    fn test_bonded_aeon_accrues_xp_over_mined_blocks() {
+        use crate::config::CGT_UNIT;
+        use crate::runtime::staking_cgt::{BondParams, BONDED_BADGE};
+
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        let node = Node::in_memory(config).unwrap();
+        let aeon = account(6);
+        node.with_state_mut(|state| {
+            bank::credit(state, &aeon, 5 * CGT_UNIT).unwrap();
+            avatars::create_aeon_profile(state, aeon, "Bonder".to_string(), None, 0).unwrap();
+        });
+
+        node.submit_transaction(signed(Transaction {
+            from: aeon,
+            nonce: 0,
+            module_id: "staking_cgt".to_string(),
+            call_id: "bond".to_string(),
+            payload: codec::encode(&BondParams {
+                amount: 5 * CGT_UNIT,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }))
+        .unwrap();
+
+        // The bonding block itself accrues, then two empty blocks follow
+        for _ in 0..3 {
+            mine_block(&node);
+        }
+
+        let profile = node
+            .with_state(|state| get_aeon_profile(state, &aeon))
+            .unwrap();
+        assert_eq!(profile.gnosis_xp, 15);
+        assert!(profile.badges.contains(&BONDED_BADGE.to_string()));
+        assert_eq!(node.get_bond(&aeon).0, 5 * CGT_UNIT);
+    }
+
+    #[test]
+    fn test_sweep_evicts_expired_transactions() {
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        node.submit_transaction(expiring_tx(0, Some(1))).unwrap();
+        node.submit_transaction(expiring_tx(1, Some(3))).unwrap();
+        node.submit_transaction(expiring_tx(2, None)).unwrap();
+
+        // Chain advances past the first transaction's expiry
+        node.with_state_mut(|state| {
+            put_chain_tip(
+                state,
+                &ChainTip {
+                    height: 1,
+                    hash: [0; 32],
+                },
+            )
+        })
+        .unwrap();
+        assert_eq!(node.sweep_expired_mempool(), 1);
+
+        let remaining: Vec<u64> = node
+            .mempool_transactions()
+            .iter()
+            .map(|tx| tx.nonce)
+            .collect();
+        assert_eq!(remaining, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_genesis_creates_archon_profile() {
+        let mut config = ChainConfig::default();
+        config.genesis.archon_name = "Demiurge".to_string();
+        let node = Node::in_memory(config).unwrap();
+
+        let profile = node
+            .with_state(|state| get_aeon_profile(state, &GENESIS_ARCHON_ADDRESS))
+            .expect("genesis profile");
+        assert_eq!(profile.display_name, "Demiurge");
+        assert_eq!(profile.created_at_height, 0);
+        assert!(node.is_archon(&GENESIS_ARCHON_ADDRESS));
+        assert_eq!(
+            node.get_balance_cgt(&GENESIS_ARCHON_ADDRESS),
+            GENESIS_ARCHON_INITIAL_BALANCE
+        );
+    }
+
+    #[test]
+    fn test_genesis_is_idempotent() {
+        let genesis = GenesisConfig::default();
+        let mut state = State::in_memory();
+        init_genesis_state(&mut state, &genesis).unwrap();
+        init_genesis_state(&mut state, &genesis).unwrap();
+
+        assert_eq!(
+            get_balance_cgt(&state, &GENESIS_ARCHON_ADDRESS),
+            GENESIS_ARCHON_INITIAL_BALANCE
+        );
+        assert!(get_aeon_profile(&state, &GENESIS_ARCHON_ADDRESS).is_some());
+    }
+
+    #[test]
+    fn test_applied_genesis_is_checked_by_fingerprint() {
+        let genesis = GenesisConfig::default();
+        let mut fresh = State::in_memory();
+        init_genesis_state(&mut fresh, &genesis).unwrap();
+        assert_eq!(
+            fresh.get_raw(KEY_GENESIS_APPLIED),
+            Some(genesis_fingerprint(&genesis).unwrap())
+        );
+
+        // A directory from before the fingerprint runs every step once and
+        // ends up as a fresh one
+        let mut legacy = State::in_memory();
+        init_genesis_state(&mut legacy, &genesis).unwrap();
+        legacy.delete_raw(KEY_GENESIS_APPLIED).unwrap();
+        init_genesis_state(&mut legacy, &genesis).unwrap();
+        assert_eq!(legacy.scan_prefix(b""), fresh.scan_prefix(b""));
+
+        // With the fingerprint recorded, the steps are skipped
+        let profile_key = crate::runtime::StorageKey::new("avatars_profiles", "profile")
+            .address(&GENESIS_ARCHON_ADDRESS)
+            .into_bytes();
+        fresh.delete_raw(&profile_key).unwrap();
+        init_genesis_state(&mut fresh, &genesis).unwrap();
+        assert!(get_aeon_profile(&fresh, &GENESIS_ARCHON_ADDRESS).is_none());
+        // but a changed spec runs them again
+        let mut renamed = genesis.clone();
+        renamed.archon_name = "Demiurge".to_string();
+        init_genesis_state(&mut fresh, &renamed).unwrap();
+        assert_eq!(
+            get_aeon_profile(&fresh, &GENESIS_ARCHON_ADDRESS)
+                .unwrap()
+                .display_name,
+            "Demiurge"
+        );
+    }
+
+    #[test]
+    fn test_multiple_genesis_archons() {
+        let mut config = ChainConfig::default();
+        config.genesis.archons = vec![
+            GenesisArchon {
+                address: [1; 32],
+                balance: 500,
+                name: Some("First Founder".to_string()),
+            },
+            GenesisArchon {
+                address: [2; 32],
+                balance: 700,
+                name: None,
+            },
+        ];
+        let node = Node::in_memory(config).unwrap();
+
+        assert_eq!(node.get_balance_cgt(&[1; 32]), 500);
+        assert_eq!(node.get_balance_cgt(&[2; 32]), 700);
+        assert!(node.is_archon(&[1; 32]));
+        assert!(node.is_archon(&[2; 32]));
+        assert!(!node.is_archon(&GENESIS_ARCHON_ADDRESS));
+        assert_eq!(node.with_state(bank::total_supply), 1_200);
+        let name =
+            |addr| node.with_state(|state| get_aeon_profile(state, addr).unwrap().display_name);
+        assert_eq!(name(&[1; 32]), "First Founder");
+        assert_eq!(name(&[2; 32]), DEFAULT_GENESIS_ARCHON_NAME);
+
+        // Duplicates are refused before anything is written
+        let mut genesis = GenesisConfig::default();
+        genesis.archons.push(genesis.archons[0].clone());
+        let mut state = State::in_memory();
+        let err = init_genesis_state(&mut state, &genesis).unwrap_err();
+        assert!(err.to_string().contains("listed more than once"));
+        assert_eq!(get_balance_cgt(&state, &GENESIS_ARCHON_ADDRESS), 0);
+    }
+
+    #[test]
+    fn test_genesis_nfts() {
+        let mut config = ChainConfig::default();
+        config.genesis.mint_authorities = vec![[7; 32]];
+        config.genesis.nfts = serde_json::from_value(serde_json::json!([
+            {
+                "owner": GENESIS_ARCHON_ADDRESS.to_display(),
+                "fabric_root_hash": hex::encode([1u8; 32]),
+                "royalty_bps": 500,
+                "uri_hint": "ipfs://first",
+            },
+            {
+                "owner": hex::encode([7u8; 32]),
+                "fabric_root_hash": hex::encode([2u8; 32]),
+                "royalty_bps": 250,
+                "royalty_recipient": hex::encode([8u8; 32]),
+            },
+            {
+                "owner": GENESIS_ARCHON_ADDRESS.to_display(),
+                "fabric_root_hash": hex::encode([3u8; 32]),
+            },
+        ]))
+        .unwrap();
+        let node = Node::in_memory(config).unwrap();
+
+        let first = node.with_state(|state| get_nft(state, 0)).unwrap();
+        assert_eq!(first.owner, GENESIS_ARCHON_ADDRESS);
+        assert_eq!(first.creator, GENESIS_ARCHON_ADDRESS);
+        assert_eq!(first.fabric_root_hash, [1; 32]);
+        assert_eq!(first.royalty_recipient, Some(GENESIS_ARCHON_ADDRESS));
+        assert_eq!(first.uri_hint.as_deref(), Some("ipfs://first"));
+        let second = node.with_state(|state| get_nft(state, 1)).unwrap();
+        assert_eq!(second.owner, [7; 32]);
+        assert_eq!(second.royalty_recipient, Some([8; 32]));
+        let third = node.with_state(|state| get_nft(state, 2)).unwrap();
+        assert_eq!(third.royalty_recipient, None);
+        assert_eq!(
+            node.with_state(|state| get_nfts_by_owner(state, &GENESIS_ARCHON_ADDRESS)),
+            vec![0, 2]
+        );
+        assert_eq!(
+            node.with_state(|state| get_nfts_by_owner(state, &[7; 32])),
+            vec![1]
+        );
+
+        // The counter moved past them, so the next mint takes the next id
+        let params = MintDgenParams {
+            fabric_root_hash: [4; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
+        };
+        let next = node
+            .with_state_mut(|state| nft::mint(state, &GENESIS_ARCHON_ADDRESS, &params))
+            .unwrap();
+        assert_eq!(next, 3);
+
+        // An owner that is neither an archon nor a mint authority is refused
+        let mut genesis = GenesisConfig::default();
+        genesis.nfts = node.config.genesis.nfts.clone();
+        genesis.nfts[1].owner = [9; 32];
+        let mut state = State::in_memory();
+        let err = init_genesis_state(&mut state, &genesis).unwrap_err();
+        assert!(
+            err.to_string().contains("neither a genesis archon"),
+            "{}",
+            err
+        );
+        assert!(get_nft(&state, 0).is_none());
+
+        genesis.nfts[1].owner = GENESIS_ARCHON_ADDRESS;
+        genesis.nfts[1].royalty_bps = 10_001;
+        let err = init_genesis_state(&mut state, &genesis).unwrap_err();
+        assert!(err.to_string().contains("more than 10000"), "{}", err);
+    }
+
+    #[test]
+    fn test_dev_faucet_creates_profile_with_mint() {
+        let mut config = ChainConfig::default();
+        config.faucet.enabled = true;
+        let node = Node::in_memory(config).unwrap();
+        let addr = [4u8; 32];
+
+        let receipt = node.dev_faucet(&addr, Some("Nomad".to_string())).unwrap();
+        assert!(receipt.profile_created);
+        assert_eq!(receipt.new_balance, node.config.faucet.amount);
+        let profile = node.with_state(|state| get_aeon_profile(state, &addr));
+        assert_eq!(profile.unwrap().display_name, "Nomad");
+
+        // A refused claim leaves no profile behind for a new address
+        let other = [5u8; 32];
+        node.dev_faucet(&other, None).unwrap();
+        assert!(node.dev_faucet(&other, Some("Late".to_string())).is_err());
+        assert!(node
+            .with_state(|state| get_aeon_profile(state, &other))
+            .is_none());
+    }
+
+    #[test]
+    fn test_reset_keeps_only_faucet_totals() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.faucet.enabled = true;
+        let node = Node::in_memory(config).unwrap();
+        let genesis = node.with_state(|state| state.scan_prefix(b""));
+        let addr = [4u8; 32];
+        node.dev_faucet(&addr, None).unwrap();
+        mine_block(&node);
+
+        let report = node.reset(true).unwrap();
+        assert_eq!(report.tip.height, 0);
+        assert_eq!(report.kept_faucet_totals, 1);
+        assert_eq!(node.chain_tip(), report.tip);
+        assert_eq!(node.get_balance_cgt(&addr), 0);
+        node.with_state(|state| {
+            assert_eq!(
+                faucet::total_claimed(state, &addr),
+                node.config.faucet.amount
+            );
+            assert_eq!(faucet::last_claim_height(state, &addr), None);
+        });
+        // The cooldown starts over, but the claim counts toward the cap
+        node.dev_faucet(&addr, None).unwrap();
+        node.with_state(|state| {
+            assert_eq!(
+                faucet::total_claimed(state, &addr),
+                2 * node.config.faucet.amount
+            )
+        });
+
+        // Without history the state is exactly a fresh genesis again
+        node.reset(false).unwrap();
+        assert_eq!(node.with_state(|state| state.scan_prefix(b"")), genesis);
+    }
+
+    #[test]
+    fn test_reset_refuses_transactions_checked_before_it() {
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        // A submission passed its checks, then the chain was reset before
+        // it reached the mempool
+        let generation = lock(&node.mempool, "mempool").generation();
+        node.reset(false).unwrap();
+        let err = node.admit(expiring_tx(0, None), generation).unwrap_err();
+        assert!(err.contains("chain was reset"), "{}", err);
+        assert!(node.mempool_transactions().is_empty());
+
+        node.submit_transaction(expiring_tx(0, None)).unwrap();
+    }
+}
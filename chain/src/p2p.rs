@@ -0,0 +1,337 @@
+//! P2P handshake and peer bookkeeping.
+//!
+//! Every new P2P connection starts with a `Handshake` exchange carrying the
+//! protocol version, chain id, genesis hash, and current height. Peers on a
+//! different chain (chain id or genesis mismatch) are disconnected and
+//! temporarily banned; peers speaking an older but still supported protocol
+//! version are negotiated down to the common version.
+//!
+//! The transport itself lands with P2P networking; this module holds the
+//! protocol messages, the validator, and the peer table the transport reports into.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::core::codec;
+
+/// Current P2P protocol version spoken by this node.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest protocol version this node can still negotiate down to.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// How long (in seconds) a peer stays banned after a failed handshake.
+pub const PEER_BAN_SECS: u64 = 10 * 60;
+
+/// Handshake message exchanged when a P2P connection is opened.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Handshake {
+    /// Highest protocol version the sender speaks.
+    pub protocol_version: u32,
+    /// Chain identifier (e.g., "demiurge-devnet").
+    pub chain_id: String,
+    /// Hash of the sender's genesis block header.
+    pub genesis_hash: [u8; 32],
+    /// Sender's current chain height.
+    pub height: u64,
+}
+
+impl Handshake {
+    /// Serialize this handshake for the wire.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(self).expect("Handshake should always serialize")
+    }
+
+    /// Deserialize a handshake received from a peer.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, HandshakeError> {
+        codec::decode(bytes).map_err(|e| HandshakeError::Malformed(e.to_string()))
+    }
+}
+
+/// Reasons a handshake can be refused.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum HandshakeError {
+    #[error("malformed handshake: {0}")]
+    Malformed(String),
+    #[error("chain id mismatch: expected {expected}, got {got}")]
+    ChainIdMismatch { expected: String, got: String },
+    #[error("genesis hash mismatch: expected {expected}, got {got}")]
+    GenesisMismatch { expected: String, got: String },
+    #[error("incompatible protocol version {got} (minimum supported {min})")]
+    IncompatibleVersion { got: u32, min: u32 },
+    #[error("peer is temporarily banned")]
+    Banned,
+}
+
+impl HandshakeError {
+    /// Whether this failure means the peer is on a different chain and should be banned.
+    pub fn is_bannable(&self) -> bool {
+        matches!(
+            self,
+            HandshakeError::ChainIdMismatch { .. } | HandshakeError::GenesisMismatch { .. }
+        )
+    }
+}
+
+/// Validate a peer's raw handshake against our own.
+///
+/// # Returns
+/// - `Ok((handshake, version))` with the decoded handshake and the negotiated
+///   protocol version (the lower of the two sides)
+/// - `Err(HandshakeError)` if the peer must be disconnected
+pub fn validate_handshake(
+    local: &Handshake,
+    remote_bytes: &[u8],
+) -> Result<(Handshake, u32), HandshakeError> {
+    let remote = Handshake::from_bytes(remote_bytes)?;
+
+    if remote.chain_id != local.chain_id {
+        return Err(HandshakeError::ChainIdMismatch {
+            expected: local.chain_id.clone(),
+            got: remote.chain_id,
+        });
+    }
+
+    if remote.genesis_hash != local.genesis_hash {
+        return Err(HandshakeError::GenesisMismatch {
+            expected: hex::encode(local.genesis_hash),
+            got: hex::encode(remote.genesis_hash),
+        });
+    }
+
+    if remote.protocol_version < MIN_PROTOCOL_VERSION {
+        return Err(HandshakeError::IncompatibleVersion {
+            got: remote.protocol_version,
+            min: MIN_PROTOCOL_VERSION,
+        });
+    }
+
+    let version = remote.protocol_version.min(local.protocol_version);
+    Ok((remote, version))
+}
+
+/// Metadata tracked for each connected peer.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerInfo {
+    /// Remote socket address.
+    pub addr: SocketAddr,
+    /// Negotiated protocol version.
+    pub protocol_version: u32,
+    /// Height the peer last reported.
+    pub height: u64,
+    /// Unix timestamp (seconds) the peer was last heard from.
+    pub last_seen: u64,
+}
+
+/// Peer table and temporary ban list.
+#[derive(Default)]
+pub struct PeerManager {
+    peers: HashMap<SocketAddr, PeerInfo>,
+    /// Banned IPs mapped to the unix timestamp the ban expires at.
+    bans: HashMap<IpAddr, u64>,
+}
+
+impl PeerManager {
+    /// Create an empty peer manager.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Process a handshake received on a new connection.
+    ///
+    /// On success the peer is recorded and the negotiated version returned.
+    /// On failure the reason is logged, and peers on a different chain are
+    /// banned for `PEER_BAN_SECS`. The caller must disconnect on `Err`.
+    pub fn on_handshake(
+        &mut self,
+        addr: SocketAddr,
+        local: &Handshake,
+        remote_bytes: &[u8],
+        now: u64,
+    ) -> Result<u32, HandshakeError> {
+        if self.is_banned(&addr.ip(), now) {
+            tracing::debug!("Rejecting handshake from banned peer {}", addr);
+            return Err(HandshakeError::Banned);
+        }
+
+        match validate_handshake(local, remote_bytes) {
+            Ok((remote, version)) => {
+                if version < local.protocol_version {
+                    tracing::info!(
+                        "Negotiated protocol version {} with peer {} (local {})",
+                        version,
+                        addr,
+                        local.protocol_version
+                    );
+                }
+                self.peers.insert(
+                    addr,
+                    PeerInfo {
+                        addr,
+                        protocol_version: version,
+                        height: remote.height,
+                        last_seen: now,
+                    },
+                );
+                Ok(version)
+            }
+            Err(e) => {
+                tracing::warn!("Disconnecting peer {}: {}", addr, e);
+                self.peers.remove(&addr);
+                if e.is_bannable() {
+                    self.bans.insert(addr.ip(), now + PEER_BAN_SECS);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Record a height update from an already-connected peer.
+    pub fn touch(&mut self, addr: &SocketAddr, height: u64, now: u64) {
+        if let Some(peer) = self.peers.get_mut(addr) {
+            peer.height = height;
+            peer.last_seen = now;
+        }
+    }
+
+    /// Remove a peer when its connection closes.
+    pub fn remove(&mut self, addr: &SocketAddr) {
+        self.peers.remove(addr);
+    }
+
+    /// Check whether an IP is currently banned.
+    pub fn is_banned(&self, ip: &IpAddr, now: u64) -> bool {
+        self.bans.get(ip).map(|until| now < *until).unwrap_or(false)
+    }
+
+    /// Snapshot of all connected peers, ordered by address.
+    pub fn peers(&self) -> Vec<PeerInfo> {
+        let mut peers: Vec<PeerInfo> = self.peers.values().cloned().collect();
+        peers.sort_by_key(|p| p.addr);
+        peers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local() -> Handshake {
+        Handshake {
+            protocol_version: PROTOCOL_VERSION,
+            chain_id: "demiurge-devnet".to_string(),
+            genesis_hash: [7u8; 32],
+            height: 10,
+        }
+    }
+
+    fn peer_addr() -> SocketAddr {
+        "10.0.0.2:30333".parse().unwrap()
+    }
+
+    #[test]
+    fn test_matching_handshake_accepted() {
+        let local = local();
+        let mut remote = local.clone();
+        remote.height = 42;
+
+        let (decoded, version) = validate_handshake(&local, &remote.to_bytes()).unwrap();
+        assert_eq!(decoded.height, 42);
+        assert_eq!(version, PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_chain_id_mismatch_rejected() {
+        let local = local();
+        let mut remote = local.clone();
+        remote.chain_id = "other-chain".to_string();
+
+        let err = validate_handshake(&local, &remote.to_bytes()).unwrap_err();
+        assert!(matches!(err, HandshakeError::ChainIdMismatch { .. }));
+        assert!(err.is_bannable());
+    }
+
+    #[test]
+    fn test_genesis_mismatch_rejected() {
+        let local = local();
+        let mut remote = local.clone();
+        remote.genesis_hash = [8u8; 32];
+
+        let err = validate_handshake(&local, &remote.to_bytes()).unwrap_err();
+        assert!(matches!(err, HandshakeError::GenesisMismatch { .. }));
+        assert!(err.is_bannable());
+    }
+
+    #[test]
+    fn test_malformed_handshake_rejected() {
+        let local = local();
+        let err = validate_handshake(&local, &[1, 2, 3]).unwrap_err();
+        assert!(matches!(err, HandshakeError::Malformed(_)));
+        assert!(!err.is_bannable());
+    }
+
+    #[test]
+    fn test_version_negotiation() {
+        let mut local = local();
+        local.protocol_version = MIN_PROTOCOL_VERSION + 1;
+
+        // Older but supported peer: negotiate down
+        let mut remote = local.clone();
+        remote.protocol_version = MIN_PROTOCOL_VERSION;
+        let (_, version) = validate_handshake(&local, &remote.to_bytes()).unwrap();
+        assert_eq!(version, MIN_PROTOCOL_VERSION);
+
+        // Newer peer: we speak our own version
+        remote.protocol_version = local.protocol_version + 5;
+        let (_, version) = validate_handshake(&local, &remote.to_bytes()).unwrap();
+        assert_eq!(version, local.protocol_version);
+
+        // Too old: disconnect without ban
+        remote.protocol_version = MIN_PROTOCOL_VERSION - 1;
+        let err = validate_handshake(&local, &remote.to_bytes()).unwrap_err();
+        assert!(matches!(err, HandshakeError::IncompatibleVersion { .. }));
+        assert!(!err.is_bannable());
+    }
+
+    #[test]
+    fn test_peer_manager_tracks_and_bans() {
+        let local = local();
+        let mut manager = PeerManager::new();
+        let addr = peer_addr();
+
+        manager
+            .on_handshake(addr, &local, &local.to_bytes(), 100)
+            .unwrap();
+        let peers = manager.peers();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].height, 10);
+        assert_eq!(peers[0].last_seen, 100);
+
+        manager.touch(&addr, 11, 105);
+        assert_eq!(manager.peers()[0].height, 11);
+
+        // Same peer reconnects claiming a different chain: dropped and banned
+        let mut bad = local.clone();
+        bad.chain_id = "other-chain".to_string();
+        assert!(manager
+            .on_handshake(addr, &local, &bad.to_bytes(), 110)
+            .is_err());
+        assert!(manager.peers().is_empty());
+        assert!(manager.is_banned(&addr.ip(), 110));
+
+        // Even a valid handshake is refused while banned
+        assert_eq!(
+            manager.on_handshake(addr, &local, &local.to_bytes(), 111),
+            Err(HandshakeError::Banned)
+        );
+
+        // Ban expires
+        assert!(!manager.is_banned(&addr.ip(), 110 + PEER_BAN_SECS));
+        assert!(manager
+            .on_handshake(addr, &local, &local.to_bytes(), 110 + PEER_BAN_SECS)
+            .is_ok());
+    }
+}
@@ -0,0 +1,391 @@
+//! Deterministic replay audit.
+//!
+//! Re-executes a chain's finalized blocks into a fresh in-memory state and
+//! compares the result against a live database, so nondeterministic
+//! execution shows up as a concrete diverging key. Blocks come from the
+//! database itself (`node::StoredBlocks`) or from a block export file
+//! written by `write_export`.
+//!
+//! Only module state is compared (see `Runtime::module_state`). Writes made
+//! outside blocks, such as dev faucet claims or `cgt_mintDgenNft`, cannot be
+//! replayed and are reported as divergences.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{Read, Write};
+
+use anyhow::{bail, Result};
+
+use crate::config::ChainConfig;
+use crate::core::block::Block;
+use crate::core::codec;
+use crate::core::state::State;
+use crate::node::Node;
+use crate::runtime::Runtime;
+
+/// First key whose value differs between the live and replayed state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub key: Vec<u8>,
+    /// Value in the live database, if the key exists there.
+    pub expected: Option<Vec<u8>>,
+    /// Value after replay, if the key exists there.
+    pub actual: Option<Vec<u8>>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Vec<u8>>| match value {
+            Some(bytes) => hex::encode(bytes),
+            None => "<missing>".to_string(),
+        };
+        // Keys start with a readable `module/prefix/` path
+        let path_len = self
+            .key
+            .iter()
+            .rposition(|b| *b == b'/')
+            .map_or(0, |i| i + 1);
+        write!(
+            f,
+            "key {}{} expected {} actual {}",
+            String::from_utf8_lossy(&self.key[..path_len]),
+            hex::encode(&self.key[path_len..]),
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+/// Outcome of a replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Blocks executed, not counting genesis.
+    pub blocks_replayed: u64,
+    /// Height of the last block executed.
+    pub tip_height: u64,
+    /// State root of the live database.
+    pub expected_root: [u8; 32],
+    /// State root after replay.
+    pub actual_root: [u8; 32],
+    /// First differing key, when the roots differ.
+    pub divergence: Option<Divergence>,
+}
+
+impl ReplayReport {
+    /// Whether replay reproduced the live state exactly.
+    pub fn is_consistent(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+/// Replay `blocks` from genesis and compare the result with `live`.
+///
+/// `blocks` must run in height order; the genesis block, at
+/// `GenesisConfig::start_height`, is skipped, since genesis state comes from
+/// `config`. Each block must extend the previous one and pass full
+/// validation, Forge PoW included.
+///
+/// `from_height` starts the replay at a later block, which needs a state
+/// snapshot at that height to start from; until snapshots exist only the
+/// genesis height is accepted.
+pub fn replay(
+    config: &ChainConfig,
+    live: &State,
+    blocks: impl IntoIterator<Item = Block>,
+    from_height: u64,
+) -> Result<ReplayReport> {
+    let genesis_height = config.genesis.start_height;
+    if from_height > genesis_height {
+        bail!(
+            "no state snapshot at height {}; replays start from genesis",
+            from_height
+        );
+    }
+
+    let node = Node::in_memory(config.clone())?;
+    let runtime = Runtime::for_chain(config);
+    let mut prev_hash = node.genesis_hash();
+    let mut tip_height = genesis_height;
+    let mut blocks_replayed = 0;
+    for block in blocks {
+        if block.header.height == genesis_height {
+            if block.header.hash() != prev_hash {
+                bail!("genesis block does not match the chain config");
+            }
+            continue;
+        }
+        if block.header.height != tip_height + 1 {
+            bail!(
+                "expected block {} but found block {}",
+                tip_height + 1,
+                block.header.height
+            );
+        }
+        if block.header.prev_hash != prev_hash {
+            bail!(
+                "block {} does not extend block {}",
+                block.header.height,
+                tip_height
+            );
+        }
+        node.with_state_mut(|state| state.execute_block_with(&block, config, &runtime))
+            .map_err(|e| anyhow::anyhow!("block {} failed: {}", block.header.height, e))?;
+        prev_hash = block.header.hash();
+        tip_height = block.header.height;
+        blocks_replayed += 1;
+    }
+
+    let expected_root = runtime.state_root(live);
+    let (actual_root, divergence) = node.with_state(|replayed| {
+        let actual_root = runtime.state_root(replayed);
+        let divergence = (actual_root != expected_root)
+            .then(|| first_divergence(&runtime, live, replayed))
+            .flatten();
+        (actual_root, divergence)
+    });
+
+    Ok(ReplayReport {
+        blocks_replayed,
+        tip_height,
+        expected_root,
+        actual_root,
+        divergence,
+    })
+}
+
+/// Walk both states' module entries in order and return the first mismatch.
+fn first_divergence(runtime: &Runtime, live: &State, replayed: &State) -> Option<Divergence> {
+    let mut expected = runtime.module_state(live).into_iter().peekable();
+    let mut actual = runtime.module_state(replayed).into_iter().peekable();
+    loop {
+        // Entries are ordered, so the side with the smaller key lacks it on the other
+        let step = match (expected.peek(), actual.peek()) {
+            (None, None) => return None,
+            (Some((ek, _)), Some((ak, _))) if ek == ak => Ordering::Equal,
+            (Some((ek, _)), Some((ak, _))) if module_order_before(runtime, ek, ak) => {
+                Ordering::Less
+            }
+            (Some(_), None) => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        match step {
+            Ordering::Equal => {
+                let (key, ev) = expected.next()?;
+                let (_, av) = actual.next()?;
+                if ev != av {
+                    return Some(Divergence {
+                        key,
+                        expected: Some(ev),
+                        actual: Some(av),
+                    });
+                }
+            }
+            Ordering::Less => {
+                let (key, ev) = expected.next()?;
+                return Some(Divergence {
+                    key,
+                    expected: Some(ev),
+                    actual: None,
+                });
+            }
+            Ordering::Greater => {
+                let (key, av) = actual.next()?;
+                return Some(Divergence {
+                    key,
+                    expected: None,
+                    actual: Some(av),
+                });
+            }
+        }
+    }
+}
+
+/// Whether `a` comes before `b` in `Runtime::module_state` order.
+fn module_order_before(runtime: &Runtime, a: &[u8], b: &[u8]) -> bool {
+    let module_index = |key: &[u8]| {
+        runtime
+            .module_ids()
+            .iter()
+            .position(|id| key.starts_with(format!("{}/", id).as_bytes()))
+    };
+    (module_index(a), a) < (module_index(b), b)
+}
+
+/// Write blocks to a block export: each block is a little-endian `u64`
+/// byte length followed by its versioned encoding (see `codec::Versioned`).
+///
+/// # Returns
+/// The number of blocks written.
+pub fn write_export(blocks: impl IntoIterator<Item = Block>, mut out: impl Write) -> Result<u64> {
+    let mut count = 0;
+    for block in blocks {
+        let bytes = codec::encode_versioned(&block)?;
+        out.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        out.write_all(&bytes)?;
+        count += 1;
+    }
+    out.flush()?;
+    Ok(count)
+}
+
+/// Read every block from a block export written by `write_export`.
+pub fn read_export(mut input: impl Read) -> Result<Vec<Block>> {
+    let mut blocks = Vec::new();
+    let mut len = [0u8; 8];
+    loop {
+        match input.read_exact(&mut len) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(blocks),
+            Err(e) => return Err(e.into()),
+        }
+        let len = u64::from_le_bytes(len);
+        if len > codec::STATE_LIMIT {
+            bail!("block export entry of {} bytes is too large", len);
+        }
+        let mut bytes = vec![0u8; len as usize];
+        input.read_exact(&mut bytes)?;
+        blocks.push(codec::decode_versioned(&bytes)?);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::core::transaction::{Address, Transaction};
+    use crate::node::StoredBlocks;
+    use crate::runtime::bank_cgt::TransferParams;
+
+    const RECIPIENT: Address = [0x33; 32];
+
+    fn easy_config() -> ChainConfig {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.genesis.archons[0].address = account(1);
+        config
+    }
+
+    /// A node with two blocks, each transferring CGT to `RECIPIENT` from
+    /// the genesis Archon.
+    fn node_with_blocks(config: &ChainConfig) -> Node {
+        let node = Node::in_memory(config.clone()).unwrap();
+        for nonce in 0..2 {
+            node.submit_transaction(signed(Transaction {
+                from: account(1),
+                nonce,
+                module_id: "bank_cgt".to_string(),
+                call_id: "transfer".to_string(),
+                payload: codec::encode(&TransferParams {
+                    to: RECIPIENT,
+                    amount: 100,
+                    memo: None,
+                })
+                .unwrap(),
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            }))
+            .unwrap();
+            node.block_template();
+            node.submit_work(0).unwrap();
+        }
+        node
+    }
+
+    /// A separate state holding every key of `state`.
+    fn copy_state(state: &State) -> State {
+        let mut copy = State::in_memory();
+        for (key, value) in state.scan_prefix(b"") {
+            copy.put_raw(key, value).unwrap();
+        }
+        copy
+    }
+
+    #[test]
+    fn test_replay_matches_live_state() {
+        let config = easy_config();
+        let node = node_with_blocks(&config);
+        let report = node
+            .with_state(|live| replay(&config, live, StoredBlocks::new(live, 0), 0))
+            .unwrap();
+        assert!(report.is_consistent());
+        assert_eq!(report.blocks_replayed, 2);
+        assert_eq!(report.tip_height, 2);
+        assert_eq!(report.expected_root, report.actual_root);
+
+        // A forked chain's blocks carry on from its genesis height
+        let mut forked = easy_config();
+        forked.genesis.start_height = 100;
+        let node = node_with_blocks(&forked);
+        let report = node
+            .with_state(|live| replay(&forked, live, StoredBlocks::new(live, 100), 100))
+            .unwrap();
+        assert!(report.is_consistent());
+        assert_eq!(report.blocks_replayed, 2);
+        assert_eq!(report.tip_height, 102);
+    }
+
+    #[test]
+    fn test_replay_pinpoints_corrupted_value() {
+        let config = easy_config();
+        let node = node_with_blocks(&config);
+        let mut corrupted = node.with_state(copy_state);
+        let (key, value) = Runtime::for_chain(&config)
+            .module_state(&corrupted)
+            .into_iter()
+            .find(|(key, _)| key.starts_with(b"bank_cgt/balance/") && key.ends_with(&RECIPIENT))
+            .unwrap();
+        corrupted
+            .put_raw(key.clone(), codec::encode(&7u64).unwrap())
+            .unwrap();
+
+        let report = replay(&config, &corrupted, StoredBlocks::new(&corrupted, 0), 0).unwrap();
+        assert_ne!(report.expected_root, report.actual_root);
+        let divergence = report.divergence.unwrap();
+        assert_eq!(divergence.key, key);
+        assert_eq!(divergence.expected, Some(codec::encode(&7u64).unwrap()));
+        assert_eq!(divergence.actual, Some(value));
+        assert!(divergence
+            .to_string()
+            .starts_with("key bank_cgt/balance/3333"));
+
+        // A key only the live side has is reported as missing after replay
+        let mut extra = node.with_state(copy_state);
+        extra.put_raw(b"quests/quest/x".to_vec(), vec![1]).unwrap();
+        let report = replay(&config, &extra, StoredBlocks::new(&extra, 0), 0).unwrap();
+        let divergence = report.divergence.unwrap();
+        assert_eq!(divergence.key, b"quests/quest/x".to_vec());
+        assert_eq!(divergence.actual, None);
+    }
+
+    #[test]
+    fn test_replay_from_block_export() {
+        let config = easy_config();
+        let node = node_with_blocks(&config);
+        let mut export = Vec::new();
+        let written = node
+            .with_state(|live| write_export(StoredBlocks::new(live, 0), &mut export))
+            .unwrap();
+        assert_eq!(written, 3);
+
+        let blocks = read_export(export.as_slice()).unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(Ok(Some(blocks[2].clone())), node.get_block_by_height(2));
+        let report = node
+            .with_state(|live| replay(&config, live, blocks.clone(), 0))
+            .unwrap();
+        assert!(report.is_consistent());
+
+        // Out-of-order blocks and unsupported partial replays are refused
+        let skipped = vec![blocks[0].clone(), blocks[2].clone()];
+        assert!(node
+            .with_state(|live| replay(&config, live, skipped, 0))
+            .is_err());
+        assert!(node
+            .with_state(|live| replay(&config, live, blocks, 1))
+            .unwrap_err()
+            .to_string()
+            .contains("no state snapshot"));
+    }
+}
@@ -3,29 +3,98 @@
 //! This module provides HTTP/JSON-RPC endpoints for interacting with the
 //! Demiurge chain node. Supported methods:
 //! - cgt_getChainInfo: Get current chain status
-//! - cgt_getBlockByHeight: Get a block by height (stubbed for now)
+//! - cgt_getNodeInfo: Get the node's version, build, features, uptime, and startup timings
+//! - cgt_getForgeConfig: Get the active Forge PoW parameters
+//! - cgt_getTokenInfo: Get CGT symbol, name, decimals, and supply
+//! - cgt_getBlockByHeight: Get a finalized block by height
+//! - cgt_getBlockSummary: Get a finalized block's overview without its transactions
+//! - cgt_getLatestBlocks: Page through block summaries from the chain tip down
+//! - cgt_getWork: Get a block template for external miners
+//! - cgt_submitWork: Submit a nonce that finalizes the current template
 //! - cgt_sendRawTransaction: Submit a transaction to the mempool
+//! - cgt_sendTransaction: Submit a transaction given as structured JSON
+//! - cgt_getMempool: List pending mempool transactions
+//! - cgt_getTransactionReceipt: Get an executed transaction's receipt by hash
+//! - cgt_getTransactionStatus: Get whether a transaction is pending, confirmed, or failed
+//! - cgt_getTransactionProof: Get a Merkle proof that a transaction is in its block
+//! - cgt_getEvents: Get the events of a block range, filtered by module and name
+//! - cgt_getFailedTransaction: Get why a dead-lettered transaction left the mempool
+//! - cgt_purgeFailed: Forget one or all dead-lettered transactions (admin)
+//! - cgt_getTransactionsByAddress: Page through the transactions involving an address
 //! - cgt_getBalance: Get CGT balance by address
+//! - cgt_getBalances: Get CGT balances for up to `MAX_BALANCES_PER_REQUEST` addresses
 //! - cgt_isArchon: Check Archon status by address
-//! - cgt_getNftsByOwner: Get NFTs owned by an address
+//! - cgt_getArchons: Page through all Archons with their Aeon display names
+//! - cgt_resolveHandle: Resolve an Aeon handle to the address holding it
+//! - cgt_getHandle: Get the Aeon handle an address holds
+//! - cgt_resolveName: Resolve a registered name to its address
+//! - cgt_getName: Get the name an address registered
+//! - cgt_accountExists: Check whether an address has ever been seen on chain
+//! - cgt_getMultisig: Get the keys and threshold of a multisig account
+//! - cgt_getAccountStatus: Get an address's nonce, pending transactions, nonce gaps, and balance
+//! - cgt_getAddressStats: Get an address's activity counters for profile pages
+//! - cgt_getNftsByOwner: Get NFTs owned by an address, and those it has listed
+//! - cgt_getNftOwner: Get the current owner of an NFT
+//! - cgt_getNftOwnerWithProof: Get an NFT's owner with a Merkle proof against the state root
 //! - cgt_getListing: Get marketplace listing by ID
+//! - cgt_previewPurchase: Get how buying a listing would split its price
+//! - cgt_getBundleListing: Get a bundle listing with how its price splits per NFT
+//! - cgt_getRoyaltyStats: Get an address's lifetime royalties and recent royalty payouts
 //! - cgt_getFabricAsset: Get Fabric asset by root hash
+//! - cgt_resolveNftContent: Join an NFT to its Fabric asset and where to fetch its bytes
+//! - cgt_getEscrow: Get escrow by ID
+//! - cgt_getStake: Get an address's stake, pending rewards, and unbondings
+//! - cgt_getAssetBalance: Get an address's balance of a non-CGT asset
+//! - gov_getProposal: Get governance proposal and tally by ID
+//! - gov_listProposals: List all governance proposals
+//! - quests_list: List all Gnosis quests
+//! - quests_getProgress: Get an address's progress on each quest
+//! - cgt_devFaucet: Mint dev CGT to an address (rate-limited, config-gated)
+//! - net_getPeers: List connected peers (admin)
+//! - admin_compact: Compact the node's database (admin)
+//! - admin_verifyState: Check balance and NFT ownership invariants (admin)
+//! - dev_reset: Wipe the chain and re-run genesis (admin, config-gated)
+//! - runtime_listModules: List registered runtime modules and their call ids
+//! - cgt_describeCall: Get the payload fields and types a module call expects
+//! - cgt_getModuleAddress: Get the account address a runtime module holds funds at
+//!
+//! A node with `rpc.read_only` set (`--read-only`) serves only the query
+//! methods in `READ_ONLY_METHODS` and refuses the rest with -32605.
 
+use std::path::Path;
 use std::sync::Arc;
 
-use axum::{extract::Extension, routing::post, Json, Router};
+use axum::http::{header, HeaderMap, HeaderValue, Method};
+use axum::routing::{get, post};
+use axum::{extract::Extension, Json, Router};
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::Instrument;
 
-use crate::config::DEV_FAUCET_AMOUNT;
-use crate::core::transaction::{Address, Transaction};
-use crate::node::Node;
+use crate::config::{
+    CorsConfig, RpcConfig, AEON_STARTER_GRANT, CGT_DECIMALS, CGT_MAX_SUPPLY, CGT_NAME, CGT_SYMBOL,
+    CORS_ANY_ORIGIN,
+};
+use crate::core::amount::{deserialize_cgt, format_cgt};
+use crate::core::block::{Block, BlockHeader};
+use crate::core::receipt::{Event, Receipt};
+use crate::core::state::State;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+use crate::forge::FORGE_ALGORITHM;
+use crate::node::{chain_tip, ChainEvent, Node, Pruned, TxStatus};
+use crate::runtime::module_api::{avatars, bank, names, nft};
 use crate::runtime::{
-    add_gnosis_xp, add_syzygy_score, create_aeon_profile, get_aeon_profile,
-    get_address_by_handle, recompute_ascension, set_handle, update_badges, BankCgtModule,
-    FabricRootHash, ListingId, NftDgenModule, NftId, RuntimeModule,
+    bundle_breakdown, create_aeon_profile, get_active_bundles, get_address_by_handle,
+    get_aeon_profile_at, get_bond_unbondings, get_bonded, get_bundle_listing,
+    get_escrowed_listings, get_listing, get_nft, get_nfts_by_owner, get_stake, get_unbondings,
+    purchase_breakdown, set_avatar_uri, set_handle, AeonProfile, CodecRegistry, EscrowId,
+    FabricRootHash, ListingId, NftId, ProposalId, PurchaseBreakdown,
 };
+use crate::ws;
 
 /// JSON-RPC request envelope.
 #[derive(Debug, Deserialize)]
@@ -62,6 +131,101 @@ pub struct JsonRpcError {
     pub message: String,
 }
 
+/// Error codes this node returns: the standard JSON-RPC ones, its own in the
+/// server-defined range (-32000 to -32099), and -32605 from read-only nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcErrorCode {
+    /// The request is not valid JSON (-32700).
+    ParseError,
+    /// The method does not exist (-32601).
+    MethodNotFound,
+    /// Parameters are missing, malformed, or out of range (-32602).
+    InvalidParams,
+    /// The request was valid but the node failed to serve it (-32603).
+    Internal,
+    /// The mempool refused a transaction (-32001).
+    TransactionRejected,
+    /// Submitted work does not seal a valid block (-32002).
+    WorkRejected,
+    /// The dev faucet refused a claim, e.g. during its cooldown (-32003).
+    FaucetRefused,
+    /// A privileged method was called from an origin not allowed to call it
+    /// (-32004; see `PRIVILEGED_METHODS`).
+    OriginNotAllowed,
+    /// The block bodies, receipts, or proofs asked for were pruned (-32005;
+    /// see `StorageConfig::keep_blocks`).
+    Pruned,
+    /// A read-only node was asked for a method outside `READ_ONLY_METHODS`
+    /// (-32605).
+    ReadOnly,
+}
+
+impl RpcErrorCode {
+    /// The code as sent on the wire.
+    pub const fn as_i32(self) -> i32 {
+        match self {
+            RpcErrorCode::ParseError => -32700,
+            RpcErrorCode::MethodNotFound => -32601,
+            RpcErrorCode::InvalidParams => -32602,
+            RpcErrorCode::Internal => -32603,
+            RpcErrorCode::TransactionRejected => -32001,
+            RpcErrorCode::WorkRejected => -32002,
+            RpcErrorCode::FaucetRefused => -32003,
+            RpcErrorCode::OriginNotAllowed => -32004,
+            RpcErrorCode::Pruned => -32005,
+            RpcErrorCode::ReadOnly => -32605,
+        }
+    }
+
+    /// An error with this code.
+    pub fn error(self, message: impl Into<String>) -> JsonRpcError {
+        JsonRpcError {
+            code: self.as_i32(),
+            message: message.into(),
+        }
+    }
+
+    pub fn parse_error(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::ParseError.error(message)
+    }
+
+    pub fn method_not_found(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::MethodNotFound.error(message)
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::InvalidParams.error(message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::Internal.error(message)
+    }
+
+    pub fn transaction_rejected(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::TransactionRejected.error(message)
+    }
+
+    pub fn work_rejected(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::WorkRejected.error(message)
+    }
+
+    pub fn faucet_refused(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::FaucetRefused.error(message)
+    }
+
+    pub fn origin_not_allowed(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::OriginNotAllowed.error(message)
+    }
+
+    pub fn pruned(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::Pruned.error(message)
+    }
+
+    pub fn read_only(message: impl Into<String>) -> JsonRpcError {
+        RpcErrorCode::ReadOnly.error(message)
+    }
+}
+
 /// Request parameter structs for new methods
 
 #[derive(Debug, Deserialize)]
@@ -69,21 +233,222 @@ pub struct GetBalanceParams {
     pub address: String, // hex string
 }
 
+/// Most addresses a single `cgt_getBalances` request may ask for.
+pub const MAX_BALANCES_PER_REQUEST: usize = 256;
+
+#[derive(Debug, Deserialize)]
+pub struct GetBalancesParams {
+    pub addresses: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct IsArchonParams {
     pub address: String,
 }
 
+/// Default page size for `cgt_getArchons`.
+pub const DEFAULT_ARCHONS_LIMIT: u64 = 20;
+
+/// Largest page `cgt_getArchons` returns.
+pub const MAX_ARCHONS_LIMIT: u64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct GetArchonsParams {
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountExistsParams {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetMultisigParams {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetAccountStatusParams {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetAddressStatsParams {
+    pub address: String,
+}
+
+/// Human-readable transaction for `cgt_sendTransaction`.
+///
+/// `params` is the call's parameters as JSON (addresses and hashes as hex);
+/// it is encoded to the module's bincode payload server-side.
+#[derive(Debug, Deserialize)]
+pub struct SendTransactionParams {
+    pub from: String,
+    pub nonce: u64,
+    pub module_id: String,
+    pub call_id: String,
+    #[serde(default)]
+    pub params: Value,
+    /// Base units, or a decimal CGT string such as "0.001".
+    #[serde(default, deserialize_with = "deserialize_cgt")]
+    pub fee: u64,
+    /// Hex-encoded signature by `from` over the canonical bytes.
+    #[serde(default)]
+    pub signature: String,
+    #[serde(default)]
+    pub valid_until_height: Option<u64>,
+    /// Signature scheme (0 = Ed25519, the default).
+    #[serde(default)]
+    pub sig_scheme: u8,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GetNftsByOwnerParams {
     pub address: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GetNftOwnerParams {
+    pub token_id: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GetListingParams {
     pub listing_id: u64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct GetBundleListingParams {
+    pub bundle_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetModuleAddressParams {
+    pub module_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DescribeCallParams {
+    pub module_id: String,
+    pub call_id: String,
+}
+
+/// Default number of summaries `cgt_getLatestBlocks` returns.
+pub const DEFAULT_LATEST_BLOCKS_COUNT: u64 = 10;
+
+/// Most summaries `cgt_getLatestBlocks` returns.
+pub const MAX_LATEST_BLOCKS_COUNT: u64 = 100;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct GetLatestBlocksParams {
+    #[serde(default)]
+    pub count: Option<u64>,
+    /// Height of the newest block to return; the chain tip if unset. Pass
+    /// the lowest height of one page minus one to fetch the next page.
+    #[serde(default)]
+    pub from_height: Option<u64>,
+}
+
+/// Most blocks one `cgt_getEvents` call may span.
+pub const MAX_EVENTS_BLOCK_SPAN: u64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct GetEventsParams {
+    pub from_height: u64,
+    /// Inclusive; at most `MAX_EVENTS_BLOCK_SPAN - 1` above `from_height`.
+    pub to_height: u64,
+    #[serde(default)]
+    pub module_id: Option<String>,
+    /// Event kind, e.g. "transfer".
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetEscrowParams {
+    pub escrow_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetRoyaltyStatsParams {
+    pub address: String,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub limit: Option<u64>,
+}
+
+/// Default number of payouts `cgt_getRoyaltyStats` lists.
+pub const DEFAULT_ROYALTY_PAYOUTS_LIMIT: u64 = 20;
+
+/// Most payouts `cgt_getRoyaltyStats` lists.
+pub const MAX_ROYALTY_PAYOUTS_LIMIT: u64 = 100;
+
+/// Default page size for `cgt_getTransactionsByAddress`.
+pub const DEFAULT_TXS_BY_ADDRESS_LIMIT: u64 = 20;
+
+/// Largest page `cgt_getTransactionsByAddress` returns.
+pub const MAX_TXS_BY_ADDRESS_LIMIT: u64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct GetTransactionsByAddressParams {
+    pub address: String,
+    #[serde(default)]
+    pub offset: u64,
+    #[serde(default)]
+    pub limit: Option<u64>,
+    /// "desc" (newest first, the default) or "asc".
+    #[serde(default)]
+    pub direction: Option<String>,
+}
+
+/// Params for `cgt_getTransactionReceipt`, `cgt_getTransactionStatus`,
+/// `cgt_getTransactionProof`, and `cgt_getFailedTransaction`.
+#[derive(Debug, Deserialize)]
+pub struct GetTransactionReceiptParams {
+    pub tx_hash: String,
+}
+
+/// Params for `cgt_purgeFailed`; without a `tx_hash`, every dead-lettered
+/// transaction is forgotten.
+#[derive(Debug, Default, Deserialize)]
+pub struct PurgeFailedParams {
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+}
+
+/// Params for `dev_reset`; faucet lifetime totals are dropped unless
+/// `keep_faucet_history` is set.
+#[derive(Debug, Default, Deserialize)]
+pub struct DevResetParams {
+    #[serde(default)]
+    pub keep_faucet_history: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetProposalParams {
+    pub proposal_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetQuestProgressParams {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetStakeParams {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetAssetBalanceParams {
+    pub asset_id: String,
+    pub address: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct GetFabricAssetParams {
     pub fabric_root_hash: String, // hex string
@@ -92,6 +457,12 @@ pub struct GetFabricAssetParams {
 #[derive(Debug, Deserialize)]
 pub struct DevFaucetParams {
     pub address: String, // hex string
+    /// Create an Aeon profile for the address if it has none.
+    #[serde(default)]
+    pub create_profile: bool,
+    /// Display name for the created profile (required with `create_profile`).
+    #[serde(default)]
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -102,6 +473,9 @@ pub struct MintDgenNftParams {
     pub fabric_root_hash: String, // hex string
     pub name: String,
     pub description: Option<String>,
+    /// Where to fetch the content's bytes (`ipfs://`, `https://`, or `fabric://`)
+    #[serde(default)]
+    pub uri_hint: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -109,6 +483,7 @@ pub struct AeonCreateParams {
     pub address: String, // hex string
     pub display_name: String,
     pub bio: Option<String>,
+    pub avatar_uri: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -121,6 +496,19 @@ pub struct AeonRecordSyzygyParams {
     pub from: String, // hex string - seeding Aeon
     pub to: String,   // hex string - original content Aeon
     pub weight: u64,  // volume/importance
+    /// Sender when a recorder records on the seeding Aeon's behalf.
+    #[serde(default)]
+    pub recorder: Option<String>,
+    /// Next nonce of the sender (`recorder`, or else `from`).
+    pub nonce: u64,
+    #[serde(default)]
+    pub fee: u64,
+    #[serde(default)]
+    pub signature: String,
+    #[serde(default)]
+    pub valid_until_height: Option<u64>,
+    #[serde(default)]
+    pub sig_scheme: u8,
 }
 
 #[derive(Debug, Deserialize)]
@@ -139,16 +527,66 @@ pub struct AeonGetByHandleParams {
     pub handle: String, // handle without @
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ResolveHandleParams {
+    pub handle: String, // "@orpheus", "Orpheus", etc.
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetHandleParams {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveNameParams {
+    pub name: String, // "orpheus", "@Orpheus", etc.
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetNameParams {
+    pub address: String,
+}
+
 /// Helper functions for parsing hex addresses and hashes
 
+/// Parse an address in checksummed (`dmrg1...`) or raw hex form.
 fn parse_address_hex(s: &str) -> Result<Address, String> {
-    let bytes = hex::decode(s).map_err(|e| format!("invalid address hex: {}", e))?;
-    if bytes.len() != 32 {
-        return Err("address must be 32 bytes".into());
+    Address::parse(s)
+}
+
+/// Render a response struct as JSON, replacing the named address field (a raw
+/// byte array under serde) with its checksummed form plus a `<field>_hex` copy.
+fn with_display_address<T: Serialize>(value: &T, field: &str, addr: &Address) -> Value {
+    let mut json = serde_json::to_value(value).unwrap_or(Value::Null);
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert(field.to_string(), json!(addr.to_display()));
+        obj.insert(format!("{}_hex", field), json!(hex::encode(addr)));
     }
-    let mut addr = [0u8; 32];
-    addr.copy_from_slice(&bytes);
-    Ok(addr)
+    json
+}
+
+/// Render how a purchase splits its price, with display amounts.
+fn purchase_breakdown_json(split: &PurchaseBreakdown) -> Value {
+    json!({
+        "price": split.price,
+        "price_display": format_cgt(split.price),
+        "royalty_amount": split.royalty_amount,
+        "royalty_amount_display": format_cgt(split.royalty_amount),
+        "royalties": split
+            .royalties
+            .iter()
+            .map(|(recipient, amount)| json!({
+                "recipient": recipient.to_display(),
+                "recipient_hex": hex::encode(recipient),
+                "amount": amount,
+                "amount_display": format_cgt(*amount),
+            }))
+            .collect::<Vec<_>>(),
+        "seller_amount": split.seller_amount,
+        "seller_amount_display": format_cgt(split.seller_amount),
+        "protocol_fee": split.protocol_fee,
+        "protocol_fee_display": format_cgt(split.protocol_fee),
+    })
 }
 
 fn parse_root_hash_hex(s: &str) -> Result<FabricRootHash, String> {
@@ -161,33 +599,421 @@ fn parse_root_hash_hex(s: &str) -> Result<FabricRootHash, String> {
     Ok(root)
 }
 
+fn parse_tx_hash_hex(s: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(s).map_err(|e| format!("invalid tx_hash hex: {}", e))?;
+    bytes
+        .try_into()
+        .map_err(|_| "tx_hash must be 32 bytes".to_string())
+}
+
+/// Event attributes that hold CGT amounts, by module.
+const CGT_EVENT_ATTRIBUTES: &[(&str, &str)] = &[
+    ("bank_cgt", "amount"),
+    ("abyss_registry", "price"),
+    ("abyss_registry", "amount"),
+];
+
+/// An Aeon's profile with the Syzygy Score decay due at the chain tip applied.
+fn decayed_profile(state: &State, address: &Address) -> Option<AeonProfile> {
+    let height = chain_tip(state).map_or(0, |tip| tip.height);
+    get_aeon_profile_at(state, address, height)
+}
+
+/// Render an event as JSON, with its address checksummed and in hex, and a
+/// `<key>_display` attribute beside each CGT amount.
+fn event_json(event: &Event) -> Value {
+    let mut attributes = serde_json::Map::new();
+    for (key, value) in &event.attributes {
+        attributes.insert(key.clone(), json!(value));
+        let is_cgt = CGT_EVENT_ATTRIBUTES.contains(&(event.module_id.as_str(), key.as_str()));
+        if let Some(base_units) = value.parse().ok().filter(|_| is_cgt) {
+            attributes.insert(format!("{}_display", key), json!(format_cgt(base_units)));
+        }
+    }
+    json!({
+        "module_id": event.module_id,
+        "kind": event.kind,
+        "address": event.address.to_display(),
+        "address_hex": hex::encode(event.address),
+        "attributes": attributes,
+    })
+}
+
+/// Render a chain event as its event JSON plus where it was emitted.
+fn chain_event_json(chain_event: &ChainEvent) -> Value {
+    let mut value = event_json(&chain_event.event);
+    value["height"] = json!(chain_event.height);
+    value["tx_hash"] = json!(hex::encode(chain_event.tx_hash));
+    value
+}
+
+/// Render a receipt as JSON, with hex hashes and checksummed event addresses.
+fn receipt_json(receipt: &Receipt) -> Value {
+    let events: Vec<Value> = receipt.events.iter().map(event_json).collect();
+    json!({
+        "tx_hash": hex::encode(receipt.tx_hash),
+        "height": receipt.height,
+        "success": receipt.success,
+        "error": receipt.error,
+        "events": events,
+    })
+}
+
+/// Summarize a transaction as JSON (as listed by `cgt_getMempool`).
+fn transaction_json(tx: &Transaction) -> Value {
+    json!({
+        "from": tx.from.to_display(),
+        "from_hex": hex::encode(tx.from),
+        "nonce": tx.nonce,
+        "module_id": tx.module_id,
+        "call_id": tx.call_id,
+        "fee": tx.fee,
+        "fee_display": format_cgt(tx.fee),
+        "valid_until_height": tx.valid_until_height,
+        "sig_scheme": tx.sig_scheme,
+    })
+}
+
+/// Render a block header as JSON with its hash, without the transactions.
+///
+/// The u128 difficulty target does not fit in a JSON number, so it is sent as
+/// a decimal string as in `cgt_getWork`.
+fn header_json(header: &BlockHeader) -> Value {
+    json!({
+        "hash": hex::encode(header.hash()),
+        "header": {
+            "height": header.height,
+            "prev_hash": hex::encode(header.prev_hash),
+            "state_root": hex::encode(header.state_root),
+            "timestamp": header.timestamp,
+            "difficulty_target": header.difficulty_target.to_string(),
+            "nonce": header.nonce,
+            "tx_root": header.tx_root.map(hex::encode),
+        },
+    })
+}
+
+/// Render a block as JSON, with hex hashes and transaction summaries.
+fn block_json(block: &Block) -> Value {
+    let mut result = header_json(&block.header);
+    result["transactions"] = block
+        .body
+        .iter()
+        .map(|tx| {
+            let mut summary = transaction_json(tx);
+            summary["hash"] = json!(hex::encode(tx.hash()));
+            summary
+        })
+        .collect();
+    result
+}
+
+/// Overview of a block for explorers: its header fields, transaction count,
+/// and the fees its transactions paid, without the transactions themselves.
+fn block_summary_json(block: &Block) -> Value {
+    let header = &block.header;
+    json!({
+        "height": header.height,
+        "hash": hex::encode(header.hash()),
+        "prev_hash": hex::encode(header.prev_hash),
+        "timestamp": header.timestamp,
+        "tx_count": block.body.len(),
+        "total_fees": block.body.iter().fold(0u64, |sum, tx| sum.saturating_add(tx.fee)),
+    })
+}
+
+/// Build a transaction from its JSON form, encoding `params` via the codec registry.
+pub(crate) fn json_transaction(params: SendTransactionParams) -> Result<Transaction, String> {
+    let payload = CodecRegistry::with_default_codecs().encode(
+        &params.module_id,
+        &params.call_id,
+        &params.params,
+    )?;
+    let signature =
+        hex::decode(&params.signature).map_err(|e| format!("invalid signature hex: {}", e))?;
+
+    Ok(Transaction {
+        from: parse_address_hex(&params.from)?,
+        nonce: params.nonce,
+        module_id: params.module_id,
+        call_id: params.call_id,
+        payload,
+        fee: params.fee,
+        signature,
+        valid_until_height: params.valid_until_height,
+        sig_scheme: params.sig_scheme,
+    })
+}
+
+/// Admit a decoded transaction to the mempool and build the RPC response.
+///
+/// Shared by the raw and JSON submission paths so both validate identically.
+fn submit_transaction_response(
+    node: &Node,
+    tx: Transaction,
+    hash: [u8; 32],
+    id: Option<Value>,
+) -> Json<JsonRpcResponse<Value>> {
+    match node.submit_transaction(tx) {
+        Ok(()) => Json(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(json!({ "accepted": true, "hash": hex::encode(hash) })),
+            error: None,
+            id,
+        }),
+        Err(msg) => Json(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcErrorCode::transaction_rejected(msg)),
+            id,
+        }),
+    }
+}
+
+fn pruned_response(pruned: Pruned, id: Option<Value>) -> Json<JsonRpcResponse<Value>> {
+    Json(JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: None,
+        error: Some(RpcErrorCode::pruned(pruned.to_string())),
+        id,
+    })
+}
+
+/// Methods that browsers may only call from `rpc.cors.privileged_origins`.
+pub const PRIVILEGED_METHODS: &[&str] = &[
+    "net_getPeers",
+    "admin_compact",
+    "admin_verifyState",
+    "cgt_purgeFailed",
+    "dev_reset",
+];
+
+/// The only methods a read-only node (`rpc.read_only`) serves: queries of
+/// chain, account, NFT, marketplace, and transaction state. Everything that
+/// submits transactions or work, mints, or administers the node is refused.
+pub const READ_ONLY_METHODS: &[&str] = &[
+    "cgt_getChainInfo",
+    "cgt_getNodeInfo",
+    "cgt_getForgeConfig",
+    "cgt_getTokenInfo",
+    "runtime_listModules",
+    "cgt_describeCall",
+    "cgt_getModuleAddress",
+    "cgt_getBalance",
+    "cgt_getBalances",
+    "cgt_isArchon",
+    "cgt_getArchons",
+    "cgt_accountExists",
+    "cgt_getMultisig",
+    "cgt_getAccountStatus",
+    "cgt_getAddressStats",
+    "cgt_getNftsByOwner",
+    "cgt_getNftOwner",
+    "cgt_getNftOwnerWithProof",
+    "cgt_getListing",
+    "cgt_previewPurchase",
+    "cgt_getBundleListing",
+    "cgt_getRoyaltyStats",
+    "cgt_getEscrow",
+    "gov_getProposal",
+    "gov_listProposals",
+    "quests_list",
+    "quests_getProgress",
+    "cgt_getStake",
+    "cgt_getAssetBalance",
+    "cgt_getFabricAsset",
+    "cgt_resolveNftContent",
+    "cgt_getBlockByHeight",
+    "cgt_getBlockSummary",
+    "cgt_getLatestBlocks",
+    "aeon_get",
+    "aeon_getAscension",
+    "aeon_getByHandle",
+    "cgt_resolveHandle",
+    "cgt_getHandle",
+    "cgt_resolveName",
+    "cgt_getName",
+    "cgt_getTransactionReceipt",
+    "cgt_getTransactionsByAddress",
+    "cgt_getTransactionStatus",
+    "cgt_getTransactionProof",
+    "cgt_getEvents",
+    "cgt_getFailedTransaction",
+    "cgt_getMempool",
+];
+
+/// Refuse a method outside `READ_ONLY_METHODS` on a read-only node.
+fn check_read_only(config: &RpcConfig, method: &str) -> Result<(), JsonRpcError> {
+    if config.read_only && !READ_ONLY_METHODS.contains(&method) {
+        return Err(RpcErrorCode::read_only(format!(
+            "read-only node: {} is not served",
+            method
+        )));
+    }
+    Ok(())
+}
+
 /// Create the JSON-RPC router.
 ///
 /// # Arguments
 /// - `node`: Shared reference to the Node instance
 ///
 /// # Returns
-/// An Axum Router configured with the RPC endpoint and CORS support
+/// An Axum Router configured with the RPC endpoint and the CORS policy from
+/// `node.config.rpc.cors`
 pub fn rpc_router(node: Arc<Node>) -> Router {
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+    let cors = cors_layer(&node.config.rpc.cors);
 
     Router::new()
         .route("/rpc", post(handle_rpc))
+        .route("/ws", get(ws::handle_ws))
         .layer(cors)
         .layer(Extension(node))
 }
 
+/// Load a PEM certificate chain and private key for serving over TLS.
+///
+/// # Returns
+/// - `Ok(RustlsConfig)` ready for `serve`
+/// - `Err` naming the file if either cannot be read or parsed, or if the key
+///   does not belong to the certificate
+pub fn load_tls(cert_path: &Path, key_path: &Path) -> anyhow::Result<RustlsConfig> {
+    let cert_pem = std::fs::read(cert_path).map_err(|e| {
+        anyhow::anyhow!("cannot read TLS certificate {}: {}", cert_path.display(), e)
+    })?;
+    let key_pem = std::fs::read(key_path)
+        .map_err(|e| anyhow::anyhow!("cannot read TLS key {}: {}", key_path.display(), e))?;
+
+    let certs = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("invalid TLS certificate {}: {}", cert_path.display(), e))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificates in {}", cert_path.display());
+    }
+    let key = PrivateKeyDer::from_pem_slice(&key_pem)
+        .map_err(|e| anyhow::anyhow!("invalid TLS key {}: {}", key_path.display(), e))?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "TLS key {} does not fit certificate {}: {}",
+                key_path.display(),
+                cert_path.display(),
+                e
+            )
+        })?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+/// Serve `app` on `listener`, over TLS when `tls` is set and plain HTTP
+/// otherwise.
+pub async fn serve(
+    listener: std::net::TcpListener,
+    app: Router,
+    tls: Option<RustlsConfig>,
+) -> std::io::Result<()> {
+    match tls {
+        Some(tls) => {
+            axum_server::from_tcp_rustls(listener, tls)
+                .serve(app.into_make_service())
+                .await
+        }
+        None => {
+            listener.set_nonblocking(true)?;
+            axum::serve(tokio::net::TcpListener::from_std(listener)?, app).await
+        }
+    }
+}
+
+/// Build the CORS layer for the allowed origins.
+///
+/// Only `POST` with a `Content-Type` header is allowed, which is what JSON
+/// requests need to pass preflight. Origins that are not valid header values
+/// are skipped with a warning.
+fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let origins = &config.allowed_origins;
+    let allow_origin = if origins.iter().any(|o| o == CORS_ANY_ORIGIN) {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(origins.iter().filter_map(|origin| {
+            match HeaderValue::from_str(origin) {
+                // A literal `*` would make the list a wildcard; "any" is the opt-in
+                Ok(value) if origin != "*" => Some(value),
+                _ => {
+                    tracing::warn!(%origin, "ignoring invalid CORS origin");
+                    None
+                }
+            }
+        }))
+    };
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::POST])
+        .allow_headers([header::CONTENT_TYPE])
+}
+
+/// Refuse a privileged method called from a browser origin outside
+/// `privileged_origins`. Requests without an `Origin` header pass.
+fn check_origin(
+    config: &CorsConfig,
+    headers: &HeaderMap,
+    method: &str,
+) -> Result<(), JsonRpcError> {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return Ok(());
+    };
+    let origin = origin.to_str().unwrap_or_default();
+    if PRIVILEGED_METHODS.contains(&method)
+        && !CorsConfig::admits(&config.privileged_origins, origin)
+    {
+        return Err(RpcErrorCode::origin_not_allowed(format!(
+            "{} may not be called from origin {}",
+            method, origin
+        )));
+    }
+    Ok(())
+}
+
 /// Handle JSON-RPC requests.
 ///
-/// This function dispatches requests to the appropriate handler based on
-/// the method name. Unsupported methods return a "Method not found" error.
+/// Each request runs in an `rpc` span carrying its method; error responses
+/// are logged with their code and message.
 async fn handle_rpc(
     Extension(node): Extension<Arc<Node>>,
+    headers: HeaderMap,
     Json(req): Json<JsonRpcRequest<Value>>,
 ) -> Json<JsonRpcResponse<Value>> {
+    let span = tracing::info_span!("rpc", method = %req.method);
+    async move {
+        let checked = check_read_only(&node.config.rpc, &req.method)
+            .and_then(|()| check_origin(&node.config.rpc.cors, &headers, &req.method));
+        let resp = match checked {
+            Ok(()) => dispatch_rpc(node, req).await,
+            Err(error) => Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(error),
+                id: req.id,
+            }),
+        };
+        if let Some(err) = &resp.error {
+            tracing::info!(code = err.code, error = %err.message, "rpc request failed");
+        }
+        resp
+    }
+    .instrument(span)
+    .await
+}
+
+/// Dispatch a JSON-RPC request to the handler for its method.
+///
+/// Unsupported methods return a "Method not found" error.
+async fn dispatch_rpc(node: Arc<Node>, req: JsonRpcRequest<Value>) -> Json<JsonRpcResponse<Value>> {
     let id = req.id.clone();
 
     match req.method.as_str() {
@@ -200,57 +1026,266 @@ async fn handle_rpc(
                 id,
             })
         }
-        "cgt_getBalance" => {
-            let params: GetBalanceParams = match req.params.as_ref() {
+        "cgt_getNodeInfo" => {
+            let info = node.node_info();
+            let mut result = serde_json::to_value(info).unwrap_or(Value::Null);
+            result["uptime_secs"] = json!(info.uptime_secs());
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getForgeConfig" => {
+            let forge = node.forge_config();
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({
+                    // u128 doesn't fit in a JSON number for JS clients; send it as a string
+                    "difficulty_target": node.config.genesis.difficulty_target.to_string(),
+                    "algorithm": FORGE_ALGORITHM,
+                    "memory_kib": forge.memory_kib,
+                    "time_cost": forge.time_cost,
+                    "lanes": forge.lanes,
+                    "target_block_time_secs": node.config.target_block_time_secs,
+                })),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getTokenInfo" => {
+            let total_supply = node.with_state(bank::total_supply);
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({
+                    "symbol": CGT_SYMBOL,
+                    "name": CGT_NAME,
+                    "decimals": CGT_DECIMALS,
+                    "total_supply": total_supply,
+                    "total_supply_display": format_cgt(total_supply),
+                    "max_supply": CGT_MAX_SUPPLY,
+                    "max_supply_display": format_cgt(CGT_MAX_SUPPLY),
+                })),
+                error: None,
+                id,
+            })
+        }
+        "runtime_listModules" => Json(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(json!({ "modules": node.list_modules() })),
+            error: None,
+            id,
+        }),
+        "cgt_describeCall" => {
+            let params: DescribeCallParams = match req
+                .params
+                .as_ref()
+                .map(|raw| serde_json::from_value(raw.clone()))
+            {
+                Some(Ok(params)) => params,
+                _ => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(
+                            "Missing or invalid module_id or call_id",
+                        )),
+                        id,
+                    })
+                }
+            };
+
+            let result = match node.call_schema(&params.module_id, &params.call_id) {
+                Some(schema) => json!({
+                    "module_id": params.module_id,
+                    "call_id": params.call_id,
+                    "fields": schema.fields,
+                }),
+                None => Value::Null,
+            };
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getModuleAddress" => {
+            let params: GetModuleAddressParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(GetBalanceParams {
-                        address: String::new(),
+                    .unwrap_or(GetModuleAddressParams {
+                        module_id: String::new(),
                     }),
-                None => GetBalanceParams {
-                    address: String::new(),
+                None => GetModuleAddressParams {
+                    module_id: String::new(),
                 },
             };
 
-            match parse_address_hex(&params.address) {
-                Ok(addr) => {
-                    let balance = node.get_balance_cgt(&addr);
+            match node.module_address(&params.module_id) {
+                Some(address) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "module_id": params.module_id,
+                        "address": address.to_display(),
+                        "address_hex": hex::encode(address),
+                    })),
+                    error: None,
+                    id,
+                }),
+                None => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(format!(
+                        "unknown module '{}'",
+                        params.module_id
+                    ))),
+                    id,
+                }),
+            }
+        }
+        "net_getPeers" => {
+            let peers: Vec<Value> = node
+                .peers()
+                .into_iter()
+                .map(|peer| {
+                    json!({
+                        "addr": peer.addr.to_string(),
+                        "protocol_version": peer.protocol_version,
+                        "height": peer.height,
+                        "last_seen": peer.last_seen,
+                    })
+                })
+                .collect();
+
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({ "peers": peers })),
+                error: None,
+                id,
+            })
+        }
+        "admin_compact" => {
+            let started = std::time::Instant::now();
+            let compacting = node.clone();
+            match tokio::task::spawn_blocking(move || compacting.compact()).await {
+                Ok(()) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({ "elapsed_ms": started.elapsed().as_millis() as u64 })),
+                    error: None,
+                    id,
+                }),
+                Err(e) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::internal(format!("compaction failed: {}", e))),
+                    id,
+                }),
+            }
+        }
+        "admin_verifyState" => {
+            let checking = node.clone();
+            match tokio::task::spawn_blocking(move || checking.verify_invariants()).await {
+                Ok(checked) => {
+                    let violations = checked.err().unwrap_or_default();
                     Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: Some(json!({ "balance": balance })),
+                        result: Some(json!({
+                            "consistent": violations.is_empty(),
+                            "violations": violations,
+                        })),
                         error: None,
                         id,
                     })
                 }
+                Err(e) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::internal(format!(
+                        "state verification failed: {}",
+                        e
+                    ))),
+                    id,
+                }),
+            }
+        }
+        "dev_reset" => {
+            if !node.config.rpc.dev_reset {
+                return Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::method_not_found(
+                        "dev_reset is disabled on this node",
+                    )),
+                    id,
+                });
+            }
+            let params = match req.params.as_ref().filter(|raw| !raw.is_null()) {
+                Some(raw) => {
+                    serde_json::from_value::<DevResetParams>(raw.clone()).map_err(|e| e.to_string())
+                }
+                None => Ok(DevResetParams::default()),
+            };
+            let params = match params {
+                Ok(params) => params,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(msg)),
+                        id,
+                    })
+                }
+            };
+
+            let resetting = node.clone();
+            let reset =
+                tokio::task::spawn_blocking(move || resetting.reset(params.keep_faucet_history))
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|reset| reset.map_err(|e| e.to_string()));
+            match reset {
+                Ok(report) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "height": report.tip.height,
+                        "genesis_hash": hex::encode(report.tip.hash),
+                        "deleted_keys": report.deleted_keys,
+                        "kept_faucet_totals": report.kept_faucet_totals,
+                    })),
+                    error: None,
+                    id,
+                }),
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32602,
-                        message: msg,
-                    }),
+                    error: Some(RpcErrorCode::internal(format!("reset failed: {}", msg))),
                     id,
                 }),
             }
         }
-        "cgt_isArchon" => {
-            let params: IsArchonParams = match req.params.as_ref() {
+        "cgt_getBalance" => {
+            let params: GetBalanceParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(IsArchonParams {
+                    .unwrap_or(GetBalanceParams {
                         address: String::new(),
                     }),
-                None => IsArchonParams {
+                None => GetBalanceParams {
                     address: String::new(),
                 },
             };
 
             match parse_address_hex(&params.address) {
                 Ok(addr) => {
-                    let flag = node.is_archon(&addr);
+                    let balance = node.get_balance_cgt(&addr);
                     Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: Some(json!({ "is_archon": flag })),
+                        result: Some(json!({
+                            "balance": balance,
+                            "balance_display": format_cgt(balance),
+                        })),
                         error: None,
                         id,
                     })
@@ -258,50 +1293,77 @@ async fn handle_rpc(
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32602,
-                        message: msg,
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "cgt_getNftsByOwner" => {
-            let params: GetNftsByOwnerParams = match req.params.as_ref() {
+        "cgt_getBalances" => {
+            let params: GetBalancesParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(GetNftsByOwnerParams {
+                    .unwrap_or(GetBalancesParams { addresses: vec![] }),
+                None => GetBalancesParams { addresses: vec![] },
+            };
+
+            let addrs = if params.addresses.len() > MAX_BALANCES_PER_REQUEST {
+                Err(format!(
+                    "too many addresses: {} (max {})",
+                    params.addresses.len(),
+                    MAX_BALANCES_PER_REQUEST
+                ))
+            } else {
+                params
+                    .addresses
+                    .iter()
+                    .map(|a| parse_address_hex(a))
+                    .collect::<Result<Vec<Address>, String>>()
+            };
+
+            match addrs {
+                Ok(addrs) => {
+                    let mut balances = serde_json::Map::new();
+                    let mut balances_display = serde_json::Map::new();
+                    for (addr, balance) in addrs.iter().zip(node.get_balances_cgt(&addrs)) {
+                        balances.insert(hex::encode(addr), json!(balance));
+                        balances_display.insert(hex::encode(addr), json!(format_cgt(balance)));
+                    }
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(json!({
+                            "balances": balances,
+                            "balances_display": balances_display,
+                        })),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_isArchon" => {
+            let params: IsArchonParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(IsArchonParams {
                         address: String::new(),
                     }),
-                None => GetNftsByOwnerParams {
+                None => IsArchonParams {
                     address: String::new(),
                 },
             };
 
             match parse_address_hex(&params.address) {
-                Ok(owner) => {
-                    let ids: Vec<NftId> = node.get_nfts_by_owner(&owner);
-                    // Fetch metadata for each
-                    let nfts: Vec<Value> = ids
-                        .into_iter()
-                        .map(|id| {
-                            if let Some(meta) = node.get_nft(id) {
-                                json!({
-                                    "id": id,
-                                    "owner": hex::encode(meta.owner),
-                                    "creator": hex::encode(meta.creator),
-                                    "fabric_root_hash": hex::encode(meta.fabric_root_hash),
-                                    "royalty_bps": meta.royalty_bps,
-                                })
-                            } else {
-                                json!({ "id": id, "missing": true })
-                            }
-                        })
-                        .collect();
-
+                Ok(addr) => {
+                    let flag = node.is_archon(&addr);
                     Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: Some(json!({ "nfts": nfts })),
+                        result: Some(json!({ "is_archon": flag })),
                         error: None,
                         id,
                     })
@@ -309,48 +1371,103 @@ async fn handle_rpc(
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32602,
-                        message: msg,
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "cgt_getListing" => {
-            let params: GetListingParams = match req.params.as_ref() {
+        "cgt_getArchons" => {
+            let params: GetArchonsParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(GetListingParams { listing_id: 0 }),
-                None => GetListingParams { listing_id: 0 },
+                    .unwrap_or(GetArchonsParams {
+                        offset: 0,
+                        limit: None,
+                    }),
+                None => GetArchonsParams {
+                    offset: 0,
+                    limit: None,
+                },
             };
 
-            let listing_opt = node.get_listing(params.listing_id as ListingId);
+            let limit = params
+                .limit
+                .unwrap_or(DEFAULT_ARCHONS_LIMIT)
+                .min(MAX_ARCHONS_LIMIT);
+            let (total, entries) = node.list_archons(params.offset, limit);
+            let archons: Vec<Value> = entries
+                .iter()
+                .map(|(addr, display_name)| {
+                    json!({
+                        "address": addr.to_display(),
+                        "address_hex": hex::encode(addr),
+                        "display_name": display_name,
+                    })
+                })
+                .collect();
             Json(JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
-                result: Some(json!(listing_opt)),
+                result: Some(json!({ "total": total, "archons": archons })),
                 error: None,
                 id,
             })
         }
-        "cgt_getFabricAsset" => {
-            let params: GetFabricAssetParams = match req.params.as_ref() {
+        "cgt_accountExists" => {
+            let params: AccountExistsParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(GetFabricAssetParams {
-                        fabric_root_hash: String::new(),
+                    .unwrap_or(AccountExistsParams {
+                        address: String::new(),
                     }),
-                None => GetFabricAssetParams {
-                    fabric_root_hash: String::new(),
+                None => AccountExistsParams {
+                    address: String::new(),
                 },
             };
 
-            match parse_root_hash_hex(&params.fabric_root_hash) {
-                Ok(root) => {
-                    let asset_opt = node.get_fabric_asset(&root);
+            match parse_address_hex(&params.address) {
+                Ok(addr) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({ "exists": node.account_exists(&addr) })),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getMultisig" => {
+            let params: GetMultisigParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetMultisigParams {
+                        address: String::new(),
+                    }),
+                None => GetMultisigParams {
+                    address: String::new(),
+                },
+            };
+
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let result = match node.get_multisig(&addr) {
+                        Some(account) => json!({
+                            "address": addr.to_display(),
+                            "keys": account
+                                .keys
+                                .iter()
+                                .map(|key| key.to_display())
+                                .collect::<Vec<_>>(),
+                            "threshold": account.threshold,
+                        }),
+                        None => Value::Null,
+                    };
                     Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: Some(json!(asset_opt)),
+                        result: Some(result),
                         error: None,
                         id,
                     })
@@ -358,351 +1475,400 @@ async fn handle_rpc(
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32602,
-                        message: msg,
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "cgt_getBlockByHeight" => {
-            let height = req
-                .params
-                .as_ref()
-                .and_then(|p| p.get("height"))
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0);
-
-            let block = node.get_block_by_height(height);
+        "cgt_getAccountStatus" => {
+            let params: GetAccountStatusParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetAccountStatusParams {
+                        address: String::new(),
+                    }),
+                None => GetAccountStatusParams {
+                    address: String::new(),
+                },
+            };
 
-            Json(JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                result: Some(serde_json::to_value(block).unwrap_or(Value::Null)),
-                error: None,
-                id,
-            })
-        }
-        "cgt_devFaucet" => {
-            #[cfg(not(debug_assertions))]
-            {
-                return Json(JsonRpcResponse {
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let status = node.account_status(&addr);
+                    let pending: Vec<Value> = status
+                        .pending
+                        .iter()
+                        .map(
+                            |(nonce, hash)| json!({ "nonce": nonce, "tx_hash": hex::encode(hash) }),
+                        )
+                        .collect();
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(json!({
+                            "address": addr.to_display(),
+                            "address_hex": hex::encode(addr),
+                            "committed_nonce": status.committed_nonce,
+                            "highest_contiguous_pending_nonce": status.highest_contiguous_pending_nonce,
+                            "nonce_gaps": status.nonce_gaps,
+                            "pending": pending,
+                            "balance": status.balance,
+                            "balance_display": format_cgt(status.balance),
+                        })),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32601,
-                        message: "Dev faucet not available in release mode".to_string(),
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
-                });
+                }),
             }
-
-            #[cfg(debug_assertions)]
-            {
-                let params: DevFaucetParams = match req.params.as_ref() {
-                    Some(raw) => serde_json::from_value(raw.clone())
-                        .map_err(|e| e.to_string())
-                        .unwrap_or(DevFaucetParams {
-                            address: String::new(),
-                        }),
-                    None => DevFaucetParams {
+        }
+        "cgt_getAddressStats" => {
+            let params: GetAddressStatsParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetAddressStatsParams {
                         address: String::new(),
-                    },
-                };
+                    }),
+                None => GetAddressStatsParams {
+                    address: String::new(),
+                },
+            };
 
-                match parse_address_hex(&params.address) {
-                    Ok(addr) => {
-                        // Mint CGT directly via state mutation
-                        let result: Result<u64, String> = node.with_state_mut(|state| {
-                            let bank_module = BankCgtModule::new();
-                            let mint_params = crate::runtime::bank_cgt::MintToParams {
-                                to: addr,
-                                amount: DEV_FAUCET_AMOUNT,
-                            };
-                            let mint_tx = Transaction {
-                                from: [0u8; 32], // Genesis authority
-                                nonce: 0,
-                                module_id: "bank_cgt".to_string(),
-                                call_id: "mint_to".to_string(),
-                                payload: bincode::serialize(&mint_params)
-                                    .map_err(|e| format!("serialization error: {}", e))?,
-                                fee: 0,
-                                signature: vec![],
-                            };
-                            bank_module
-                                .dispatch("mint_to", &mint_tx, state)
-                                .map_err(|e| format!("mint failed: {}", e))?;
-
-                            // Get new balance
-                            let balance = crate::runtime::bank_cgt::get_balance_cgt(state, &addr);
-                            Ok(balance)
-                        });
-
-                        match result {
-                            Ok(new_balance) => Json(JsonRpcResponse {
-                                jsonrpc: "2.0".to_string(),
-                                result: Some(json!({
-                                    "ok": true,
-                                    "new_balance": new_balance
-                                })),
-                                error: None,
-                                id,
-                            }),
-                            Err(msg) => Json(JsonRpcResponse {
-                                jsonrpc: "2.0".to_string(),
-                                result: None,
-                                error: Some(JsonRpcError {
-                                    code: -32603,
-                                    message: format!("Faucet error: {}", msg),
-                                }),
-                                id,
-                            }),
-                        }
-                    }
-                    Err(msg) => Json(JsonRpcResponse {
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let (stats, nfts_owned) = node.address_stats(&addr);
+                    let first_seen_height = stats.as_ref().map(|s| s.first_seen_height);
+                    let stats = stats.unwrap_or_default();
+                    Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: msg,
-                        }),
+                        result: Some(json!({
+                            "address": addr.to_display(),
+                            "address_hex": hex::encode(addr),
+                            "first_seen_height": first_seen_height,
+                            "txs_sent": stats.txs_sent,
+                            "cgt_sent": stats.cgt_sent,
+                            "cgt_received": stats.cgt_received,
+                            "nfts_minted": stats.nfts_minted,
+                            "nfts_owned": nfts_owned,
+                        })),
+                        error: None,
                         id,
-                    }),
+                    })
                 }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
             }
         }
-        "cgt_mintDgenNft" => {
-            let params: MintDgenNftParams = match req.params.as_ref() {
+        "cgt_getNftsByOwner" => {
+            let params: GetNftsByOwnerParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(MintDgenNftParams {
-                        owner: String::new(),
-                        forge_model_id: None,
-                        forge_prompt_hash: None,
-                        fabric_root_hash: String::new(),
-                        name: String::new(),
-                        description: None,
+                    .unwrap_or(GetNftsByOwnerParams {
+                        address: String::new(),
                     }),
-                None => MintDgenNftParams {
-                    owner: String::new(),
-                    forge_model_id: None,
-                    forge_prompt_hash: None,
-                    fabric_root_hash: String::new(),
-                    name: String::new(),
-                    description: None,
+                None => GetNftsByOwnerParams {
+                    address: String::new(),
                 },
             };
 
-            let owner_addr = match parse_address_hex(&params.owner) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
+            match parse_address_hex(&params.address) {
+                Ok(owner) => {
+                    // The index and the metadata it lists come from one
+                    // snapshot, so a transfer cannot land between them.
+                    // Listed NFTs are held by the marketplace, so they are
+                    // joined in from the seller's listings and bundles
+                    let snapshot = node.snapshot();
+                    let owned = get_nfts_by_owner(&snapshot, &owner)
+                        .into_iter()
+                        .map(|id| (id, None, None));
+                    let listed = get_escrowed_listings(&snapshot, &owner)
+                        .into_iter()
+                        .map(|listing| (listing.token_id, Some(listing.id), None));
+                    let bundled =
+                        get_active_bundles(&snapshot, &owner)
+                            .into_iter()
+                            .flat_map(|bundle| {
+                                let bundle_id = bundle.id;
+                                bundle
+                                    .token_ids
+                                    .into_iter()
+                                    .map(move |id| (id, None, Some(bundle_id)))
+                            });
+                    let nfts: Vec<Value> = owned
+                        .chain(listed)
+                        .chain(bundled)
+                        .map(
+                            |(id, listing_id, bundle_id): (
+                                NftId,
+                                Option<ListingId>,
+                                Option<ListingId>,
+                            )| {
+                                if let Some(meta) = get_nft(&snapshot, id) {
+                                    json!({
+                                        "id": id,
+                                        "owner": meta.owner.to_display(),
+                                        "owner_hex": hex::encode(meta.owner),
+                                        "creator": meta.creator.to_display(),
+                                        "creator_hex": hex::encode(meta.creator),
+                                        "fabric_root_hash": hex::encode(meta.fabric_root_hash),
+                                        "royalty_bps": meta.royalty_bps,
+                                        "royalty_splits": meta
+                                            .royalty_splits
+                                            .iter()
+                                            .map(|(recipient, bps)| json!({
+                                                "recipient": recipient.to_display(),
+                                                "recipient_hex": hex::encode(recipient),
+                                                "bps": bps,
+                                            }))
+                                            .collect::<Vec<_>>(),
+                                        "uri_hint": meta.uri_hint,
+                                        "listed": listing_id.is_some() || bundle_id.is_some(),
+                                        "listing_id": listing_id,
+                                        "bundle_id": bundle_id,
+                                    })
+                                } else {
+                                    json!({ "id": id, "missing": true })
+                                }
+                            },
+                        )
+                        .collect();
+
+                    Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid owner address: {}", msg),
-                        }),
+                        result: Some(json!({ "nfts": nfts })),
+                        error: None,
                         id,
-                    });
+                    })
                 }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getNftOwner" => {
+            let params: GetNftOwnerParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetNftOwnerParams { token_id: 0 }),
+                None => GetNftOwnerParams { token_id: 0 },
             };
 
-            let fabric_hash = match parse_address_hex(&params.fabric_root_hash) {
-                Ok(hash) => hash,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid fabric_root_hash: {}", msg),
-                        }),
-                        id,
-                    });
-                }
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(match node.get_nft_owner(params.token_id as NftId) {
+                    Some(owner) => json!({
+                        "token_id": params.token_id,
+                        "owner": owner.to_display(),
+                        "owner_hex": hex::encode(owner),
+                    }),
+                    None => Value::Null,
+                }),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getNftOwnerWithProof" => {
+            let params: GetNftOwnerParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetNftOwnerParams { token_id: 0 }),
+                None => GetNftOwnerParams { token_id: 0 },
             };
 
-            let forge_model_id = match params
-                .forge_model_id
-                .as_ref()
-                .map(|s| parse_address_hex(s))
-                .transpose()
-            {
-                Ok(Some(hash)) => Some(hash),
-                Ok(None) => None,
-                Err(e) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid forge_model_id: {}", e),
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(
+                    match node.get_nft_owner_with_proof(params.token_id as NftId) {
+                        Some(owned) => json!({
+                            "token_id": params.token_id,
+                            "owner": owned.owner.to_display(),
+                            "owner_hex": hex::encode(owned.owner),
+                            "height": owned.height,
+                            "block_hash": hex::encode(owned.block_hash),
+                            "state_root": hex::encode(owned.state_root),
+                            "committed": owned.committed,
+                            "proof": {
+                                "key": hex::encode(&owned.proof.key),
+                                "value": hex::encode(&owned.proof.value),
+                                "leaf_index": owned.proof.leaf_index,
+                                "leaf_count": owned.proof.leaf_count,
+                                "siblings": owned
+                                    .proof
+                                    .siblings
+                                    .iter()
+                                    .map(hex::encode)
+                                    .collect::<Vec<_>>(),
+                            },
                         }),
-                        id,
-                    });
-                }
+                        None => Value::Null,
+                    },
+                ),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getListing" => {
+            let params: GetListingParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetListingParams { listing_id: 0 }),
+                None => GetListingParams { listing_id: 0 },
             };
 
-            let forge_prompt_hash = match params
-                .forge_prompt_hash
-                .as_ref()
-                .map(|s| parse_address_hex(s))
-                .transpose()
-            {
-                Ok(Some(hash)) => Some(hash),
-                Ok(None) => None,
-                Err(e) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid forge_prompt_hash: {}", e),
-                        }),
-                        id,
-                    });
-                }
+            let listing_opt = node.get_listing(params.listing_id as ListingId);
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(match listing_opt {
+                    Some(listing) => {
+                        let mut json = with_display_address(&listing, "seller", &listing.seller);
+                        json["allowed_buyer"] =
+                            json!(listing.allowed_buyer.map(|a| a.to_display()));
+                        json["allowed_buyer_hex"] = json!(listing.allowed_buyer.map(hex::encode));
+                        json["price_cgt_display"] = json!(format_cgt(listing.price_cgt));
+                        json
+                    }
+                    None => Value::Null,
+                }),
+                error: None,
+                id,
+            })
+        }
+        "cgt_previewPurchase" => {
+            let params: GetListingParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetListingParams { listing_id: 0 }),
+                None => GetListingParams { listing_id: 0 },
             };
 
-            // For dev mode, we'll use Genesis Archon as the signer if owner is Genesis Archon
-            // Otherwise, we'll bypass signature checks and mint directly
-            let result = node.with_state_mut(|state| {
-                // Check if owner is Archon (required for minting)
-                if !crate::runtime::avatars_profiles::is_archon(state, &owner_addr) {
-                    return Err("only Archons may mint D-GEN NFTs".to_string());
+            // The listing and the royalty and fee it is split by come from
+            // one snapshot
+            let snapshot = node.snapshot();
+            let preview = get_listing(&snapshot, params.listing_id).map(|listing| {
+                if !listing.active {
+                    return Err(format!("listing {} is not active", listing.id));
                 }
-
-                let nft_module = NftDgenModule::new();
-                let mint_params = crate::runtime::nft_dgen::MintDgenParams {
-                    fabric_root_hash: fabric_hash,
-                    forge_model_id,
-                    forge_prompt_hash,
-                    royalty_recipient: None,
-                    royalty_bps: 0,
-                };
-
-                let mint_tx = Transaction {
-                    from: owner_addr,
-                    nonce: 0, // For dev, we skip nonce checks
-                    module_id: "nft_dgen".to_string(),
-                    call_id: "mint_dgen".to_string(),
-                    payload: bincode::serialize(&mint_params)
-                        .map_err(|e| format!("serialization error: {}", e))?,
-                    fee: 0,
-                    signature: vec![],
-                };
-
-                nft_module
-                    .dispatch("mint_dgen", &mint_tx, state)
-                    .map_err(|e| format!("mint failed: {}", e))?;
-
-                // Get the newly minted NFT ID (it will be the current counter - 1)
-                let nft_ids = crate::runtime::nft_dgen::get_nfts_by_owner(state, &owner_addr);
-                let nft_id = nft_ids.last().copied().ok_or("NFT not found after minting")?;
-                let nft_meta = crate::runtime::nft_dgen::get_nft(state, nft_id)
-                    .ok_or("NFT metadata not found")?;
-
-                Ok((nft_id, nft_meta))
+                purchase_breakdown(&snapshot, &listing)
             });
+            drop(snapshot);
 
-            match result {
-                Ok((nft_id, nft_meta)) => Json(JsonRpcResponse {
+            match preview.transpose() {
+                Ok(split) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
-                    result: Some(json!({
-                        "nft_id": nft_id,
-                        "owner": hex::encode(nft_meta.owner),
-                        "fabric_root_hash": hex::encode(nft_meta.fabric_root_hash),
-                        "forge_model_id": nft_meta.forge_model_id.map(hex::encode),
-                        "forge_prompt_hash": nft_meta.forge_prompt_hash.map(hex::encode),
-                    })),
+                    result: Some(match split {
+                        Some(split) => {
+                            let mut json = purchase_breakdown_json(&split);
+                            json["listing_id"] = json!(params.listing_id);
+                            json
+                        }
+                        None => Value::Null,
+                    }),
                     error: None,
                     id,
                 }),
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32603,
-                        message: format!("Mint error: {}", msg),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getBundleListing" => {
+            let params: GetBundleListingParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetBundleListingParams { bundle_id: 0 }),
+                None => GetBundleListingParams { bundle_id: 0 },
+            };
+
+            // The bundle and the royalties and fee its price splits by come
+            // from one snapshot
+            let snapshot = node.snapshot();
+            let found = get_bundle_listing(&snapshot, params.bundle_id)
+                .map(|bundle| bundle_breakdown(&snapshot, &bundle).map(|splits| (bundle, splits)));
+            drop(snapshot);
+
+            match found.transpose() {
+                Ok(found) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(match found {
+                        Some((bundle, splits)) => {
+                            let mut json = with_display_address(&bundle, "seller", &bundle.seller);
+                            json["allowed_buyer"] =
+                                json!(bundle.allowed_buyer.map(|a| a.to_display()));
+                            json["allowed_buyer_hex"] =
+                                json!(bundle.allowed_buyer.map(hex::encode));
+                            json["price_cgt_display"] = json!(format_cgt(bundle.price_cgt));
+                            json["breakdown"] = bundle
+                                .token_ids
+                                .iter()
+                                .zip(&splits)
+                                .map(|(token_id, split)| {
+                                    let mut json = purchase_breakdown_json(split);
+                                    json["token_id"] = json!(token_id);
+                                    json
+                                })
+                                .collect();
+                            json
+                        }
+                        None => Value::Null,
                     }),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "aeon_create" => {
-            let params: AeonCreateParams = match req.params.as_ref() {
+        "cgt_getRoyaltyStats" => {
+            let params: GetRoyaltyStatsParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(AeonCreateParams {
+                    .unwrap_or(GetRoyaltyStatsParams {
                         address: String::new(),
-                        display_name: String::new(),
-                        bio: None,
+                        offset: 0,
+                        limit: None,
                     }),
-                None => AeonCreateParams {
+                None => GetRoyaltyStatsParams {
                     address: String::new(),
-                    display_name: String::new(),
-                    bio: None,
+                    offset: 0,
+                    limit: None,
                 },
             };
 
-            let address = match parse_address_hex(&params.address) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid address: {}", msg),
-                        }),
-                        id,
-                    });
-                }
-            };
-
-            let current_height = node.chain_info().height;
-
-            let result = node.with_state_mut(|state| {
-                create_aeon_profile(state, address, params.display_name, params.bio, current_height)
-            });
-
-            match result {
-                Ok(profile) => {
-                    // In dev mode, optionally mint starter CGT
-                    #[cfg(debug_assertions)]
-                    {
-                        let _ = node.with_state_mut(|state| {
-                            let bank_module = BankCgtModule::new();
-                            let mint_params = crate::runtime::bank_cgt::MintToParams {
-                                to: address,
-                                amount: 1_000, // Small starter allowance
-                            };
-                            let mint_tx = Transaction {
-                                from: [0u8; 32],
-                                nonce: 0,
-                                module_id: "bank_cgt".to_string(),
-                                call_id: "mint_to".to_string(),
-                                payload: bincode::serialize(&mint_params)
-                                    .map_err(|e| format!("serialization error: {}", e))?,
-                                fee: 0,
-                                signature: vec![],
-                            };
-                            bank_module.dispatch("mint_to", &mint_tx, state)
-                        });
-                    }
-
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let limit = params
+                        .limit
+                        .unwrap_or(DEFAULT_ROYALTY_PAYOUTS_LIMIT)
+                        .min(MAX_ROYALTY_PAYOUTS_LIMIT);
+                    let (stats, payouts) = node.royalty_stats(&addr, params.offset, limit);
                     Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
                         result: Some(json!({
-                            "address": hex::encode(profile.address),
-                            "display_name": profile.display_name,
-                            "bio": profile.bio,
-                            "gnosis_xp": profile.gnosis_xp,
-                            "syzygy_score": profile.syzygy_score,
-                            "ascension_level": profile.ascension_level,
-                            "badges": profile.badges,
-                            "created_at_height": profile.created_at_height,
+                            "address": addr.to_display(),
+                            "address_hex": hex::encode(addr),
+                            "total_amount": stats.total_amount,
+                            "total_amount_display": format_cgt(stats.total_amount),
+                            "sale_count": stats.sale_count,
+                            "last_height": stats.last_height,
+                            "payouts": payouts.iter().map(chain_event_json).collect::<Vec<_>>(),
                         })),
                         error: None,
                         id,
@@ -711,373 +1877,3748 @@ async fn handle_rpc(
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32603,
-                        message: format!("Failed to create Aeon profile: {}", msg),
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "aeon_get" => {
-            let params: AeonGetParams = match req.params.as_ref() {
+        "cgt_getEscrow" => {
+            let params: GetEscrowParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(AeonGetParams {
-                        address: String::new(),
-                    }),
-                None => AeonGetParams {
-                    address: String::new(),
-                },
+                    .unwrap_or(GetEscrowParams { escrow_id: 0 }),
+                None => GetEscrowParams { escrow_id: 0 },
             };
 
-            let address = match parse_address_hex(&params.address) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid address: {}", msg),
-                        }),
-                        id,
-                    });
-                }
+            let escrow_opt = node.get_escrow(params.escrow_id as EscrowId);
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(match escrow_opt {
+                    Some(escrow) => {
+                        let json = with_display_address(&escrow, "payer", &escrow.payer);
+                        let mut json =
+                            with_display_address(&json, "beneficiary", &escrow.beneficiary);
+                        json["amount_display"] = json!(format_cgt(escrow.amount));
+                        json
+                    }
+                    None => Value::Null,
+                }),
+                error: None,
+                id,
+            })
+        }
+        "gov_getProposal" => {
+            let params: GetProposalParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetProposalParams { proposal_id: 0 }),
+                None => GetProposalParams { proposal_id: 0 },
             };
 
-            let profile_opt = node.with_state(|state| get_aeon_profile(state, &address));
-
+            let proposal_opt = node.get_proposal(params.proposal_id as ProposalId);
             Json(JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
-                result: Some(match profile_opt {
-                    Some(profile) => json!({
-                        "address": hex::encode(profile.address),
-                        "display_name": profile.display_name,
-                        "bio": profile.bio,
-                        "gnosis_xp": profile.gnosis_xp,
-                        "syzygy_score": profile.syzygy_score,
-                        "ascension_level": profile.ascension_level,
-                        "badges": profile.badges,
-                        "created_at_height": profile.created_at_height,
-                    }),
-                    None => serde_json::Value::Null,
+                result: Some(match proposal_opt {
+                    Some(proposal) => {
+                        with_display_address(&proposal, "proposer", &proposal.proposer)
+                    }
+                    None => Value::Null,
                 }),
                 error: None,
                 id,
             })
         }
-        "aeon_recordSyzygy" => {
-            let params: AeonRecordSyzygyParams = match req.params.as_ref() {
+        "gov_listProposals" => {
+            let proposals: Vec<Value> = node
+                .list_proposals()
+                .iter()
+                .map(|proposal| with_display_address(proposal, "proposer", &proposal.proposer))
+                .collect();
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({ "proposals": proposals })),
+                error: None,
+                id,
+            })
+        }
+        "quests_list" => {
+            let quests: Vec<Value> = node
+                .list_quests()
+                .iter()
+                .map(|quest| with_display_address(quest, "creator", &quest.creator))
+                .collect();
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({ "quests": quests })),
+                error: None,
+                id,
+            })
+        }
+        "quests_getProgress" => {
+            let params: GetQuestProgressParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(AeonRecordSyzygyParams {
-                        from: String::new(),
-                        to: String::new(),
-                        weight: 0,
+                    .unwrap_or(GetQuestProgressParams {
+                        address: String::new(),
                     }),
-                None => AeonRecordSyzygyParams {
-                    from: String::new(),
-                    to: String::new(),
-                    weight: 0,
+                None => GetQuestProgressParams {
+                    address: String::new(),
                 },
             };
 
-            let from_addr = match parse_address_hex(&params.from) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let progress: Vec<Value> = node
+                        .get_quest_progress(&addr)
+                        .into_iter()
+                        .map(|(quest_id, progress)| {
+                            json!({
+                                "quest_id": quest_id,
+                                "window_start": progress.window_start,
+                                "count": progress.count,
+                                "completions": progress.completions,
+                            })
+                        })
+                        .collect();
+                    Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid 'from' address: {}", msg),
-                        }),
+                        result: Some(json!({ "progress": progress })),
+                        error: None,
                         id,
-                    });
+                    })
                 }
-            };
-
-            let _to_addr = match parse_address_hex(&params.to) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid 'to' address: {}", msg),
-                        }),
-                        id,
-                    });
-                }
-            };
-
-            let result: Result<serde_json::Value, String> = node.with_state_mut(|state| {
-                // Add Syzygy Score to seeding Aeon
-                add_syzygy_score(state, &from_addr, params.weight)?;
-
-                // Add Gnosis XP (weight / 2) to seeding Aeon
-                let xp_gain = params.weight / 2;
-                if xp_gain > 0 {
-                    add_gnosis_xp(state, &from_addr, xp_gain)?;
-                }
-
-                // Recompute ascension and update badges
-                recompute_ascension(state, &from_addr)?;
-                update_badges(state, &from_addr)?;
-
-                // Get updated profile
-                let profile = get_aeon_profile(state, &from_addr)
-                    .ok_or("Profile not found after update")?;
-
-                Ok(json!({
-                    "address": hex::encode(profile.address),
-                    "gnosis_xp": profile.gnosis_xp,
-                    "syzygy_score": profile.syzygy_score,
-                    "ascension_level": profile.ascension_level,
-                    "badges": profile.badges,
-                }))
-            });
-
-            match result {
-                Ok(stats) => Json(JsonRpcResponse {
-                    jsonrpc: "2.0".to_string(),
-                    result: Some(stats),
-                    error: None,
-                    id,
-                }),
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32603,
-                        message: format!("Failed to record Syzygy: {}", msg),
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "aeon_getAscension" => {
-            let params: AeonGetAscensionParams = match req.params.as_ref() {
+        "cgt_getStake" => {
+            let params: GetStakeParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(AeonGetAscensionParams {
+                    .unwrap_or(GetStakeParams {
                         address: String::new(),
                     }),
-                None => AeonGetAscensionParams {
+                None => GetStakeParams {
                     address: String::new(),
                 },
             };
 
-            let address = match parse_address_hex(&params.address) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let snapshot = node.snapshot();
+                    let height = chain_tip(&snapshot).map_or(0, |tip| tip.height);
+                    let stake = get_stake(&snapshot, &addr, height).unwrap_or_default();
+                    let unbonding = get_unbondings(&snapshot, &addr);
+                    let bonded = get_bonded(&snapshot, &addr);
+                    let bond_unbonding = get_bond_unbondings(&snapshot, &addr);
+                    drop(snapshot);
+                    Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid address: {}", msg),
-                        }),
+                        result: Some(json!({
+                            "amount": stake.amount,
+                            "amount_display": format_cgt(stake.amount),
+                            "pending_rewards": stake.pending_rewards,
+                            "pending_rewards_display": format_cgt(stake.pending_rewards),
+                            "unbonding": unbonding,
+                            "bonded": {
+                                "amount": bonded,
+                                "amount_display": format_cgt(bonded),
+                                "unbonding": bond_unbonding,
+                            },
+                        })),
+                        error: None,
                         id,
-                    });
+                    })
                 }
-            };
-
-            let profile_opt = node.with_state(|state| get_aeon_profile(state, &address));
-
-            match profile_opt {
-                Some(profile) => Json(JsonRpcResponse {
-                    jsonrpc: "2.0".to_string(),
-                    result: Some(json!({
-                        "gnosis_xp": profile.gnosis_xp,
-                        "syzygy_score": profile.syzygy_score,
-                        "ascension_level": profile.ascension_level,
-                        "badges": profile.badges,
-                    })),
-                    error: None,
-                    id,
-                }),
-                None => Json(JsonRpcResponse {
+                Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
-                    result: Some(serde_json::Value::Null),
-                    error: None,
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "aeon_setHandle" => {
-            let params: AeonSetHandleParams = match req.params.as_ref() {
+        "cgt_getAssetBalance" => {
+            let params: GetAssetBalanceParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(AeonSetHandleParams {
+                    .unwrap_or(GetAssetBalanceParams {
+                        asset_id: String::new(),
                         address: String::new(),
-                        handle: String::new(),
                     }),
-                None => AeonSetHandleParams {
+                None => GetAssetBalanceParams {
+                    asset_id: String::new(),
                     address: String::new(),
-                    handle: String::new(),
                 },
             };
 
-            let address = match parse_address_hex(&params.address) {
-                Ok(addr) => addr,
-                Err(msg) => {
-                    return Json(JsonRpcResponse {
-                        jsonrpc: "2.0".to_string(),
-                        result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602,
-                            message: format!("invalid address: {}", msg),
-                        }),
-                        id,
-                    });
-                }
-            };
-
-            let result = node.with_state_mut(|state| {
-                set_handle(state, address, params.handle)
-            });
-
-            match result {
-                Ok(profile) => Json(JsonRpcResponse {
+            match parse_address_hex(&params.address) {
+                Ok(addr) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
-                    result: Some(json!({
-                        "address": hex::encode(profile.address),
-                        "display_name": profile.display_name,
-                        "bio": profile.bio,
-                        "handle": profile.handle,
-                        "gnosis_xp": profile.gnosis_xp,
-                        "syzygy_score": profile.syzygy_score,
-                        "ascension_level": profile.ascension_level,
-                        "badges": profile.badges,
-                        "created_at_height": profile.created_at_height,
-                    })),
+                    result: Some(match node.get_asset_balance(&params.asset_id, &addr) {
+                        Some((asset, balance)) => json!({
+                            "asset_id": asset.asset_id,
+                            "balance": balance,
+                            "decimals": asset.decimals,
+                        }),
+                        None => Value::Null,
+                    }),
                     error: None,
                     id,
                 }),
                 Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     result: None,
-                    error: Some(JsonRpcError {
-                        code: -32603,
-                        message: format!("Failed to set handle: {}", msg),
-                    }),
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "aeon_getByHandle" => {
-            let params: AeonGetByHandleParams = match req.params.as_ref() {
+        "cgt_getFabricAsset" => {
+            let params: GetFabricAssetParams = match req.params.as_ref() {
                 Some(raw) => serde_json::from_value(raw.clone())
                     .map_err(|e| e.to_string())
-                    .unwrap_or(AeonGetByHandleParams {
-                        handle: String::new(),
+                    .unwrap_or(GetFabricAssetParams {
+                        fabric_root_hash: String::new(),
                     }),
-                None => AeonGetByHandleParams {
-                    handle: String::new(),
+                None => GetFabricAssetParams {
+                    fabric_root_hash: String::new(),
                 },
             };
 
-            // Normalize handle (lowercase, trim)
-            let normalized = params.handle.trim().to_lowercase();
-
-            let address_opt = node.with_state(|state| {
-                get_address_by_handle(state, &normalized)
-            });
-
-            match address_opt {
-                Some(addr) => {
-                    let profile_opt = node.with_state(|state| get_aeon_profile(state, &addr));
-                    match profile_opt {
-                        Some(profile) => Json(JsonRpcResponse {
-                            jsonrpc: "2.0".to_string(),
-                            result: Some(json!({
-                                "address": hex::encode(profile.address),
-                                "display_name": profile.display_name,
-                                "bio": profile.bio,
-                                "handle": profile.handle,
-                                "gnosis_xp": profile.gnosis_xp,
-                                "syzygy_score": profile.syzygy_score,
-                                "ascension_level": profile.ascension_level,
-                                "badges": profile.badges,
-                                "created_at_height": profile.created_at_height,
-                            })),
-                            error: None,
-                            id,
-                        }),
-                        None => Json(JsonRpcResponse {
-                            jsonrpc: "2.0".to_string(),
-                            result: Some(serde_json::Value::Null),
-                            error: None,
-                            id,
+            match parse_root_hash_hex(&params.fabric_root_hash) {
+                Ok(root) => {
+                    let asset_opt = node.get_fabric_asset(&root);
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(match asset_opt {
+                            Some(asset) => with_display_address(&asset, "owner", &asset.owner),
+                            None => Value::Null,
                         }),
-                    }
+                        error: None,
+                        id,
+                    })
                 }
-                None => Json(JsonRpcResponse {
+                Err(msg) => Json(JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
-                    result: Some(serde_json::Value::Null),
-                    error: None,
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
                     id,
                 }),
             }
         }
-        "cgt_sendRawTransaction" => {
-            let tx_hex = req
+        "cgt_resolveNftContent" => {
+            let Some(nft_id) = req
                 .params
                 .as_ref()
-                .and_then(|p| p.get("tx"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
+                .and_then(|p| p.get("nft_id"))
+                .and_then(|v| v.as_u64())
+            else {
+                return Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params("Missing or invalid nft_id")),
+                    id,
+                });
+            };
 
-            let bytes = match hex::decode(tx_hex) {
-                Ok(b) => b,
-                Err(e) => {
+            let result = match node.resolve_nft_content(nft_id as NftId) {
+                Some((nft, asset)) => json!({
+                    "nft_id": nft_id,
+                    "fabric_root_hash": hex::encode(nft.fabric_root_hash),
+                    "asset_registered": asset.is_some(),
+                    "uri_hint": nft
+                        .uri_hint
+                        .or_else(|| asset.and_then(|asset| asset.uri_hint)),
+                }),
+                None => Value::Null,
+            };
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getBlockByHeight" => {
+            let height = req
+                .params
+                .as_ref()
+                .and_then(|p| p.get("height"))
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let include_transactions = req
+                .params
+                .as_ref()
+                .and_then(|p| p.get("include_transactions"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+
+            // Headers outlive pruning; bodies do not
+            let result = if include_transactions {
+                match node.get_block_by_height(height) {
+                    Ok(block) => block.as_ref().map(block_json),
+                    Err(pruned) => return pruned_response(pruned, id),
+                }
+            } else {
+                node.get_header_by_height(height).as_ref().map(header_json)
+            };
+
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result.unwrap_or(Value::Null)),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getBlockSummary" => {
+            let Some(height) = req
+                .params
+                .as_ref()
+                .and_then(|p| p.get("height"))
+                .and_then(|v| v.as_u64())
+            else {
+                return Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params("Missing or invalid height")),
+                    id,
+                });
+            };
+
+            // The fee total needs the body, so pruned blocks have no summary
+            let result = match node.get_block_by_height(height) {
+                Ok(block) => block.as_ref().map(block_summary_json),
+                Err(pruned) => return pruned_response(pruned, id),
+            };
+
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result.unwrap_or(Value::Null)),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getLatestBlocks" => {
+            let params: GetLatestBlocksParams = req
+                .params
+                .as_ref()
+                .and_then(|raw| serde_json::from_value(raw.clone()).ok())
+                .unwrap_or_default();
+
+            let count = params
+                .count
+                .unwrap_or(DEFAULT_LATEST_BLOCKS_COUNT)
+                .min(MAX_LATEST_BLOCKS_COUNT);
+            let summaries: Vec<Value> = node
+                .latest_blocks(params.from_height, count)
+                .iter()
+                .map(block_summary_json)
+                .collect();
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!(summaries)),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getWork" => {
+            let block = node.block_template();
+            let header = &block.header;
+
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({
+                    "height": header.height,
+                    "prev_hash": hex::encode(header.prev_hash),
+                    "header": hex::encode(header.serialize_without_nonce()),
+                    // u128 does not fit in a JSON number; send it as a decimal string
+                    "difficulty_target": header.difficulty_target.to_string(),
+                    "tx_count": block.body.len(),
+                })),
+                error: None,
+                id,
+            })
+        }
+        "cgt_submitWork" => {
+            let nonce = match req
+                .params
+                .as_ref()
+                .and_then(|p| p.get("nonce"))
+                .and_then(|v| v.as_u64())
+            {
+                Some(n) => n,
+                None => {
                     return Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
                         result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602, // Invalid params
-                            message: format!("invalid tx hex: {}", e),
-                        }),
+                        error: Some(RpcErrorCode::invalid_params("Missing or invalid nonce")),
                         id,
                     })
                 }
             };
 
-            let tx = match crate::core::transaction::Transaction::from_bytes(&bytes) {
-                Ok(tx) => tx,
-                Err(e) => {
+            match node.submit_work(nonce) {
+                Ok(block) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "accepted": true,
+                        "height": block.header.height,
+                        "hash": hex::encode(block.header.hash()),
+                    })),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::work_rejected(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_devFaucet" => {
+            if !node.config.faucet.enabled {
+                return Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::method_not_found(
+                        "Dev faucet is disabled on this node",
+                    )),
+                    id,
+                });
+            }
+
+            let params: DevFaucetParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(DevFaucetParams {
+                        address: String::new(),
+                        create_profile: false,
+                        display_name: None,
+                    }),
+                None => DevFaucetParams {
+                    address: String::new(),
+                    create_profile: false,
+                    display_name: None,
+                },
+            };
+
+            let profile_name = match (params.create_profile, params.display_name) {
+                (false, _) => None,
+                (true, Some(name)) if !name.trim().is_empty() => Some(name),
+                (true, _) => {
                     return Json(JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
                         result: None,
-                        error: Some(JsonRpcError {
-                            code: -32602, // Invalid params
-                            message: format!("invalid tx encoding: {}", e),
-                        }),
+                        error: Some(RpcErrorCode::invalid_params(
+                            "display_name is required when create_profile is set".to_string(),
+                        )),
                         id,
-                    })
+                    });
                 }
             };
 
-            node.submit_transaction(tx);
-
-            Json(JsonRpcResponse {
-                jsonrpc: "2.0".to_string(),
-                result: Some(json!({ "accepted": true })),
-                error: None,
-                id,
-            })
-        }
-        _ => Json(JsonRpcResponse {
-            jsonrpc: "2.0".to_string(),
-            result: None,
-            error: Some(JsonRpcError {
-                code: -32601, // Method not found
-                message: "Method not found".to_string(),
-            }),
-            id,
-        }),
+            match parse_address_hex(&params.address) {
+                Ok(addr) => match node.dev_faucet(&addr, profile_name) {
+                    Ok(receipt) => Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(json!({
+                            "ok": true,
+                            "new_balance": receipt.new_balance,
+                            "profile_created": receipt.profile_created
+                        })),
+                        error: None,
+                        id,
+                    }),
+                    // Refusals (cooldown with remaining blocks, cap) are reported in the message
+                    Err(e) => Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::faucet_refused(e.to_string())),
+                        id,
+                    }),
+                },
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_mintDgenNft" => {
+            let params: MintDgenNftParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(MintDgenNftParams {
+                        owner: String::new(),
+                        forge_model_id: None,
+                        forge_prompt_hash: None,
+                        fabric_root_hash: String::new(),
+                        name: String::new(),
+                        description: None,
+                        uri_hint: None,
+                    }),
+                None => MintDgenNftParams {
+                    owner: String::new(),
+                    forge_model_id: None,
+                    forge_prompt_hash: None,
+                    fabric_root_hash: String::new(),
+                    name: String::new(),
+                    description: None,
+                    uri_hint: None,
+                },
+            };
+
+            let owner_addr = match parse_address_hex(&params.owner) {
+                Ok(addr) => addr,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid owner address: {}",
+                            msg
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let fabric_hash = match parse_address_hex(&params.fabric_root_hash) {
+                Ok(hash) => hash,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid fabric_root_hash: {}",
+                            msg
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let forge_model_id = match params
+                .forge_model_id
+                .as_ref()
+                .map(|s| parse_address_hex(s))
+                .transpose()
+            {
+                Ok(Some(hash)) => Some(hash),
+                Ok(None) => None,
+                Err(e) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid forge_model_id: {}",
+                            e
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let forge_prompt_hash = match params
+                .forge_prompt_hash
+                .as_ref()
+                .map(|s| parse_address_hex(s))
+                .transpose()
+            {
+                Ok(Some(hash)) => Some(hash),
+                Ok(None) => None,
+                Err(e) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid forge_prompt_hash: {}",
+                            e
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            // Dev mode: signature checks are bypassed and the NFT is minted directly,
+            // so the Archon check the mint_dgen call would make is done here
+            let result = node.with_state_mut(|state| {
+                if !avatars::is_archon(state, &owner_addr) {
+                    return Err("only Archons may mint D-GEN NFTs".to_string());
+                }
+
+                let mint_params = crate::runtime::nft_dgen::MintDgenParams {
+                    fabric_root_hash: fabric_hash,
+                    forge_model_id,
+                    forge_prompt_hash,
+                    royalty_recipient: None,
+                    royalty_bps: 0,
+                    uri_hint: params.uri_hint.clone(),
+                    royalty_splits: Vec::new(),
+                };
+                let nft_id = nft::mint(state, &owner_addr, &mint_params)
+                    .map_err(|e| format!("mint failed: {}", e))?;
+                let nft_meta = nft::metadata(state, nft_id).ok_or("NFT metadata not found")?;
+
+                Ok((nft_id, nft_meta))
+            });
+
+            match result {
+                Ok((nft_id, nft_meta)) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "nft_id": nft_id,
+                        "owner": nft_meta.owner.to_display(),
+                        "owner_hex": hex::encode(nft_meta.owner),
+                        "fabric_root_hash": hex::encode(nft_meta.fabric_root_hash),
+                        "forge_model_id": nft_meta.forge_model_id.map(hex::encode),
+                        "forge_prompt_hash": nft_meta.forge_prompt_hash.map(hex::encode),
+                        "uri_hint": nft_meta.uri_hint,
+                    })),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::internal(format!("Mint error: {}", msg))),
+                    id,
+                }),
+            }
+        }
+        "aeon_create" => {
+            let params: AeonCreateParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(AeonCreateParams {
+                        address: String::new(),
+                        display_name: String::new(),
+                        bio: None,
+                        avatar_uri: None,
+                    }),
+                None => AeonCreateParams {
+                    address: String::new(),
+                    display_name: String::new(),
+                    bio: None,
+                    avatar_uri: None,
+                },
+            };
+
+            let address = match parse_address_hex(&params.address) {
+                Ok(addr) => addr,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid address: {}",
+                            msg
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let current_height = node.chain_info().height;
+
+            // Debug builds mint a starter grant with the profile; both apply or neither
+            let starter_grant = if cfg!(debug_assertions) {
+                AEON_STARTER_GRANT
+            } else {
+                0
+            };
+            let result = node.with_state_mut(|state| {
+                state.begin();
+                let result = create_aeon_profile(
+                    state,
+                    address,
+                    params.display_name,
+                    params.bio,
+                    current_height,
+                )
+                .and_then(|profile| match params.avatar_uri {
+                    Some(uri) => set_avatar_uri(state, &address, Some(uri)),
+                    None => Ok(profile),
+                })
+                .and_then(|profile| {
+                    if starter_grant > 0 {
+                        bank::mint(state, &address, starter_grant)?;
+                    }
+                    Ok(profile)
+                });
+                match result {
+                    Ok(profile) => state.commit().map(|_| profile).map_err(|e| e.to_string()),
+                    Err(e) => {
+                        state.rollback();
+                        Err(e)
+                    }
+                }
+            });
+
+            match result {
+                Ok(profile) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "address": profile.address.to_display(),
+                        "address_hex": hex::encode(profile.address),
+                        "display_name": profile.display_name,
+                        "bio": profile.bio,
+                        "avatar_uri": profile.avatar_uri,
+                        "gnosis_xp": profile.gnosis_xp,
+                        "syzygy_score": profile.syzygy_score,
+                        "ascension_level": profile.ascension_level,
+                        "badges": profile.badges,
+                        "created_at_height": profile.created_at_height,
+                        "starter_grant": starter_grant,
+                    })),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::internal(format!(
+                        "Failed to create Aeon profile: {}",
+                        msg
+                    ))),
+                    id,
+                }),
+            }
+        }
+        "aeon_get" => {
+            let params: AeonGetParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(AeonGetParams {
+                        address: String::new(),
+                    }),
+                None => AeonGetParams {
+                    address: String::new(),
+                },
+            };
+
+            let address = match parse_address_hex(&params.address) {
+                Ok(addr) => addr,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid address: {}",
+                            msg
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let profile_opt = node.with_state(|state| decayed_profile(state, &address));
+
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(match profile_opt {
+                    Some(profile) => json!({
+                        "address": profile.address.to_display(),
+                        "address_hex": hex::encode(profile.address),
+                        "display_name": profile.display_name,
+                        "bio": profile.bio,
+                        "avatar_uri": profile.avatar_uri,
+                        "gnosis_xp": profile.gnosis_xp,
+                        "syzygy_score": profile.syzygy_score,
+                        "ascension_level": profile.ascension_level,
+                        "badges": profile.badges,
+                        "created_at_height": profile.created_at_height,
+                    }),
+                    None => serde_json::Value::Null,
+                }),
+                error: None,
+                id,
+            })
+        }
+        "aeon_recordSyzygy" => {
+            let params: AeonRecordSyzygyParams = match req
+                .params
+                .as_ref()
+                .map(|raw| serde_json::from_value(raw.clone()))
+            {
+                Some(Ok(p)) => p,
+                Some(Err(e)) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid params: {}",
+                            e
+                        ))),
+                        id,
+                    })
+                }
+                None => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params("Missing params")),
+                        id,
+                    })
+                }
+            };
+
+            // The seeding Aeon sends the transaction itself unless a recorder
+            // it delegated to does
+            let tx = parse_address_hex(&params.from)
+                .map_err(|msg| format!("invalid 'from' address: {}", msg))
+                .and_then(|from| {
+                    let to = parse_address_hex(&params.to)
+                        .map_err(|msg| format!("invalid 'to' address: {}", msg))?;
+                    json_transaction(SendTransactionParams {
+                        from: params.recorder.unwrap_or(params.from),
+                        nonce: params.nonce,
+                        module_id: "avatars_profiles".to_string(),
+                        call_id: "record_syzygy".to_string(),
+                        params: json!({
+                            "from": hex::encode(from),
+                            "to": hex::encode(to),
+                            "weight": params.weight,
+                        }),
+                        fee: params.fee,
+                        signature: params.signature,
+                        valid_until_height: params.valid_until_height,
+                        sig_scheme: params.sig_scheme,
+                    })
+                });
+            let tx = match tx {
+                Ok(tx) => tx,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(msg)),
+                        id,
+                    })
+                }
+            };
+
+            let hash = tx.hash();
+            submit_transaction_response(&node, tx, hash, id)
+        }
+        "aeon_getAscension" => {
+            let params: AeonGetAscensionParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(AeonGetAscensionParams {
+                        address: String::new(),
+                    }),
+                None => AeonGetAscensionParams {
+                    address: String::new(),
+                },
+            };
+
+            let address = match parse_address_hex(&params.address) {
+                Ok(addr) => addr,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid address: {}",
+                            msg
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let profile_opt = node.with_state(|state| decayed_profile(state, &address));
+
+            match profile_opt {
+                Some(profile) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "gnosis_xp": profile.gnosis_xp,
+                        "syzygy_score": profile.syzygy_score,
+                        "ascension_level": profile.ascension_level,
+                        "badges": profile.badges,
+                    })),
+                    error: None,
+                    id,
+                }),
+                None => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(serde_json::Value::Null),
+                    error: None,
+                    id,
+                }),
+            }
+        }
+        "aeon_setHandle" => {
+            let params: AeonSetHandleParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(AeonSetHandleParams {
+                        address: String::new(),
+                        handle: String::new(),
+                    }),
+                None => AeonSetHandleParams {
+                    address: String::new(),
+                    handle: String::new(),
+                },
+            };
+
+            let address = match parse_address_hex(&params.address) {
+                Ok(addr) => addr,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid address: {}",
+                            msg
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let result = node.with_state_mut(|state| {
+                set_handle(state, address, params.handle)
+            });
+
+            match result {
+                Ok(profile) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "address": profile.address.to_display(),
+                        "address_hex": hex::encode(profile.address),
+                        "display_name": profile.display_name,
+                        "bio": profile.bio,
+                        "avatar_uri": profile.avatar_uri,
+                        "handle": profile.handle,
+                        "gnosis_xp": profile.gnosis_xp,
+                        "syzygy_score": profile.syzygy_score,
+                        "ascension_level": profile.ascension_level,
+                        "badges": profile.badges,
+                        "created_at_height": profile.created_at_height,
+                    })),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::internal(format!(
+                        "Failed to set handle: {}",
+                        msg
+                    ))),
+                    id,
+                }),
+            }
+        }
+        "aeon_getByHandle" => {
+            let params: AeonGetByHandleParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(AeonGetByHandleParams {
+                        handle: String::new(),
+                    }),
+                None => AeonGetByHandleParams {
+                    handle: String::new(),
+                },
+            };
+
+            let normalized = avatars::normalize_handle(&params.handle);
+
+            // Resolve the handle and read the profile from one snapshot, so a
+            // handle change in between cannot return another Aeon's profile
+            let snapshot = node.snapshot();
+            let profile_opt = get_address_by_handle(&snapshot, &normalized)
+                .and_then(|addr| decayed_profile(&snapshot, &addr));
+            drop(snapshot);
+
+            match profile_opt {
+                Some(profile) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({
+                        "address": profile.address.to_display(),
+                        "address_hex": hex::encode(profile.address),
+                        "display_name": profile.display_name,
+                        "bio": profile.bio,
+                        "avatar_uri": profile.avatar_uri,
+                        "handle": profile.handle,
+                        "gnosis_xp": profile.gnosis_xp,
+                        "syzygy_score": profile.syzygy_score,
+                        "ascension_level": profile.ascension_level,
+                        "badges": profile.badges,
+                        "created_at_height": profile.created_at_height,
+                    })),
+                    error: None,
+                    id,
+                }),
+                None => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(serde_json::Value::Null),
+                    error: None,
+                    id,
+                }),
+            }
+        }
+        "cgt_resolveHandle" => {
+            let params: ResolveHandleParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(ResolveHandleParams {
+                        handle: String::new(),
+                    }),
+                None => ResolveHandleParams {
+                    handle: String::new(),
+                },
+            };
+
+            let handle = avatars::normalize_handle(&params.handle);
+            let result = match node.with_state(|state| avatars::resolve_handle(state, &handle)) {
+                Some(addr) => json!({
+                    "handle": handle,
+                    "address": addr.to_display(),
+                    "address_hex": hex::encode(addr),
+                }),
+                None => Value::Null,
+            };
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getHandle" => {
+            let params: GetHandleParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetHandleParams {
+                        address: String::new(),
+                    }),
+                None => GetHandleParams {
+                    address: String::new(),
+                },
+            };
+
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let handle = node.with_state(|state| {
+                        avatars::profile(state, &addr).and_then(|profile| profile.handle)
+                    });
+                    let result = match handle {
+                        Some(handle) => json!({
+                            "address": addr.to_display(),
+                            "handle": handle,
+                        }),
+                        None => Value::Null,
+                    };
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(result),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_resolveName" => {
+            let params: ResolveNameParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(ResolveNameParams {
+                        name: String::new(),
+                    }),
+                None => ResolveNameParams {
+                    name: String::new(),
+                },
+            };
+
+            let result = match node.with_state(|state| {
+                let addr = names::resolve_name(state, &params.name)?;
+                Some((addr, names::name_of(state, &addr)?))
+            }) {
+                Some((addr, name)) => json!({
+                    "name": name,
+                    "address": addr.to_display(),
+                    "address_hex": hex::encode(addr),
+                }),
+                None => Value::Null,
+            };
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result),
+                error: None,
+                id,
+            })
+        }
+        "cgt_getName" => {
+            let params: GetNameParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetNameParams {
+                        address: String::new(),
+                    }),
+                None => GetNameParams {
+                    address: String::new(),
+                },
+            };
+
+            match parse_address_hex(&params.address) {
+                Ok(addr) => {
+                    let result = match node.with_state(|state| names::name_of(state, &addr)) {
+                        Some(name) => json!({
+                            "address": addr.to_display(),
+                            "name": name,
+                        }),
+                        None => Value::Null,
+                    };
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(result),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_sendRawTransaction" => {
+            let tx_hex = req
+                .params
+                .as_ref()
+                .and_then(|p| p.get("tx"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+
+            let bytes = match hex::decode(tx_hex) {
+                Ok(b) => b,
+                Err(e) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid tx hex: {}",
+                            e
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            let tx = match crate::core::transaction::Transaction::from_bytes(&bytes) {
+                Ok(tx) => tx,
+                Err(e) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid tx encoding: {}",
+                            e
+                        ))),
+                        id,
+                    });
+                }
+            };
+
+            // The canonical hash is the same whichever wire version was submitted
+            let hash = tx.hash();
+            submit_transaction_response(&node, tx, hash, id)
+        }
+        "cgt_sendTransaction" => {
+            let params: SendTransactionParams = match req
+                .params
+                .as_ref()
+                .map(|raw| serde_json::from_value(raw.clone()))
+            {
+                Some(Ok(p)) => p,
+                Some(Err(e)) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid transaction: {}",
+                            e
+                        ))),
+                        id,
+                    })
+                }
+                None => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params("Missing params")),
+                        id,
+                    })
+                }
+            };
+
+            let tx = match json_transaction(params) {
+                Ok(tx) => tx,
+                Err(msg) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(msg)),
+                        id,
+                    })
+                }
+            };
+
+            let hash = tx.hash();
+            submit_transaction_response(&node, tx, hash, id)
+        }
+        "cgt_getTransactionReceipt" => {
+            let params: GetTransactionReceiptParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetTransactionReceiptParams {
+                        tx_hash: String::new(),
+                    }),
+                None => GetTransactionReceiptParams {
+                    tx_hash: String::new(),
+                },
+            };
+
+            match parse_tx_hash_hex(&params.tx_hash) {
+                Ok(tx_hash) => match node.get_receipt(&tx_hash) {
+                    Ok(receipt) => Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(receipt.as_ref().map(receipt_json).unwrap_or(Value::Null)),
+                        error: None,
+                        id,
+                    }),
+                    Err(pruned) => pruned_response(pruned, id),
+                },
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getTransactionsByAddress" => {
+            let params: GetTransactionsByAddressParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetTransactionsByAddressParams {
+                        address: String::new(),
+                        offset: 0,
+                        limit: None,
+                        direction: None,
+                    }),
+                None => GetTransactionsByAddressParams {
+                    address: String::new(),
+                    offset: 0,
+                    limit: None,
+                    direction: None,
+                },
+            };
+
+            let newest_first = match params.direction.as_deref() {
+                None | Some("desc") => Ok(true),
+                Some("asc") => Ok(false),
+                Some(other) => Err(format!(
+                    "invalid direction '{}'; expected asc or desc",
+                    other
+                )),
+            };
+            match parse_address_hex(&params.address).and_then(|addr| Ok((addr, newest_first?))) {
+                Ok((addr, newest_first)) => {
+                    let limit = params
+                        .limit
+                        .unwrap_or(DEFAULT_TXS_BY_ADDRESS_LIMIT)
+                        .min(MAX_TXS_BY_ADDRESS_LIMIT);
+                    let (total, receipts) =
+                        node.get_transactions_by_address(&addr, params.offset, limit, newest_first);
+                    let transactions: Vec<Value> = receipts
+                        .iter()
+                        .map(|receipt| {
+                            json!({
+                                "tx_hash": hex::encode(receipt.tx_hash),
+                                "height": receipt.height,
+                                "success": receipt.success,
+                                "error": receipt.error,
+                            })
+                        })
+                        .collect();
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(json!({ "total": total, "transactions": transactions })),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getTransactionStatus" => {
+            let params: GetTransactionReceiptParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetTransactionReceiptParams {
+                        tx_hash: String::new(),
+                    }),
+                None => GetTransactionReceiptParams {
+                    tx_hash: String::new(),
+                },
+            };
+
+            match parse_tx_hash_hex(&params.tx_hash) {
+                Ok(tx_hash) => {
+                    let result = match node.transaction_status(&tx_hash) {
+                        TxStatus::Pending => json!({ "status": "pending" }),
+                        TxStatus::Confirmed { height } => {
+                            json!({ "status": "confirmed", "height": height })
+                        }
+                        TxStatus::Failed { height, error } => {
+                            json!({ "status": "failed", "height": height, "error": error })
+                        }
+                        TxStatus::Pruned { height } => {
+                            json!({ "status": "pruned", "height": height })
+                        }
+                        TxStatus::DeadLettered { reason } => {
+                            json!({ "status": "dead_lettered", "reason": reason })
+                        }
+                        TxStatus::Unknown => json!({ "status": "unknown" }),
+                    };
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(result),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getTransactionProof" => {
+            let params: GetTransactionReceiptParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetTransactionReceiptParams {
+                        tx_hash: String::new(),
+                    }),
+                None => GetTransactionReceiptParams {
+                    tx_hash: String::new(),
+                },
+            };
+
+            match parse_tx_hash_hex(&params.tx_hash) {
+                Ok(tx_hash) => {
+                    // Blocks from before headers carried a tx_root report the
+                    // transaction's position but no proof
+                    let result = match node.transaction_proof(&tx_hash) {
+                        Ok(Some(found)) => json!({
+                            "height": found.height,
+                            "block_hash": hex::encode(found.block_hash),
+                            "index": found.proof.index,
+                            "tx_count": found.proof.tx_count,
+                            "available": found.tx_root.is_some(),
+                            "tx_root": found.tx_root.map(hex::encode),
+                            "branch": found.tx_root.map(|_| {
+                                found.proof.branch.iter().map(hex::encode).collect::<Vec<_>>()
+                            }),
+                        }),
+                        Ok(None) => Value::Null,
+                        Err(pruned) => return pruned_response(pruned, id),
+                    };
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(result),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getEvents" => {
+            let params: GetEventsParams = match req
+                .params
+                .as_ref()
+                .map(|raw| serde_json::from_value(raw.clone()))
+            {
+                Some(Ok(p)) => p,
+                Some(Err(e)) => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params(format!(
+                            "invalid params: {}",
+                            e
+                        ))),
+                        id,
+                    })
+                }
+                None => {
+                    return Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: None,
+                        error: Some(RpcErrorCode::invalid_params("Missing params")),
+                        id,
+                    })
+                }
+            };
+
+            let in_span = params
+                .to_height
+                .checked_sub(params.from_height)
+                .is_some_and(|diff| diff < MAX_EVENTS_BLOCK_SPAN);
+            if !in_span {
+                return Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(format!(
+                        "to_height must be at least from_height and span at most {} blocks",
+                        MAX_EVENTS_BLOCK_SPAN
+                    ))),
+                    id,
+                });
+            }
+
+            match node.events(
+                params.from_height,
+                params.to_height,
+                params.module_id.as_deref(),
+                params.name.as_deref(),
+            ) {
+                Ok(events) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!(events
+                        .iter()
+                        .map(chain_event_json)
+                        .collect::<Vec<_>>())),
+                    error: None,
+                    id,
+                }),
+                Err(pruned) => pruned_response(pruned, id),
+            }
+        }
+        "cgt_getFailedTransaction" => {
+            let params: GetTransactionReceiptParams = match req.params.as_ref() {
+                Some(raw) => serde_json::from_value(raw.clone())
+                    .map_err(|e| e.to_string())
+                    .unwrap_or(GetTransactionReceiptParams {
+                        tx_hash: String::new(),
+                    }),
+                None => GetTransactionReceiptParams {
+                    tx_hash: String::new(),
+                },
+            };
+
+            match parse_tx_hash_hex(&params.tx_hash) {
+                Ok(tx_hash) => {
+                    let result = match node.failed_transaction(&tx_hash) {
+                        Some(failure) => json!({
+                            "tx_hash": hex::encode(tx_hash),
+                            "transaction": transaction_json(&failure.tx),
+                            "reason": failure.reason,
+                            "attempts": failure.attempts,
+                            "last_attempted_height": failure.last_attempted_height,
+                        }),
+                        None => Value::Null,
+                    };
+                    Json(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        result: Some(result),
+                        error: None,
+                        id,
+                    })
+                }
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_purgeFailed" => {
+            let params = match req.params.as_ref().filter(|raw| !raw.is_null()) {
+                Some(raw) => serde_json::from_value::<PurgeFailedParams>(raw.clone())
+                    .map_err(|e| e.to_string()),
+                None => Ok(PurgeFailedParams::default()),
+            };
+            let tx_hash = params
+                .and_then(|params| params.tx_hash.as_deref().map(parse_tx_hash_hex).transpose());
+
+            match tx_hash {
+                Ok(tx_hash) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({ "purged": node.purge_failed(tx_hash.as_ref()) })),
+                    error: None,
+                    id,
+                }),
+                Err(msg) => Json(JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcErrorCode::invalid_params(msg)),
+                    id,
+                }),
+            }
+        }
+        "cgt_getMempool" => {
+            let txs: Vec<Value> = node
+                .mempool_transactions()
+                .iter()
+                .map(transaction_json)
+                .collect();
+
+            Json(JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({ "transactions": txs })),
+                error: None,
+                id,
+            })
+        }
+        _ => Json(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcErrorCode::method_not_found("Method not found")),
+            id,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ChainConfig, GENESIS_ARCHON_INITIAL_BALANCE};
+    use crate::core::codec;
+    use crate::core::transaction::test_keys::{account, signed};
+
+    async fn call(node: &Arc<Node>, method: &str, params: Value) -> JsonRpcResponse<Value> {
+        let req = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: Some(params),
+            id: Some(json!(1)),
+        };
+        let Json(resp) = handle_rpc(Extension(node.clone()), HeaderMap::new(), Json(req)).await;
+        resp
+    }
+
+    /// `cgt_sendTransaction` params with the signature of the transaction
+    /// they build filled in; its sender must be a test account. Params that
+    /// build no transaction are left as they are.
+    fn sign_json(mut params: Value) -> Value {
+        let tx = serde_json::from_value(params.clone())
+            .map_err(|e| e.to_string())
+            .and_then(json_transaction);
+        if let Ok(tx) = tx {
+            params["signature"] = json!(hex::encode(signed(tx).signature));
+        }
+        params
+    }
+
+    /// A chain with trivial PoW whose genesis Archon is `account(1)`, so
+    /// tests can sign for its funds.
+    fn signing_config() -> ChainConfig {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.genesis.archons[0].address = account(1);
+        config
+    }
+
+    /// `Access-Control-Allow-Origin` of a preflight from `origin`.
+    async fn preflight(cors: CorsConfig, origin: &str) -> Option<HeaderValue> {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let mut config = ChainConfig::default();
+        config.rpc.cors = cors;
+        let app = rpc_router(Arc::new(Node::in_memory(config).unwrap()));
+        let req = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/rpc")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "content-type")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        resp.headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowlist_any_and_empty() {
+        let cors = |origins: &[&str]| CorsConfig {
+            allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+            privileged_origins: Vec::new(),
+        };
+        let portal = "http://localhost:3000";
+        let other = "https://evil.example";
+
+        // The default allowlist admits the portal and echoes its origin
+        assert_eq!(
+            preflight(CorsConfig::default(), portal).await.unwrap(),
+            portal
+        );
+        assert!(preflight(CorsConfig::default(), other).await.is_none());
+
+        assert_eq!(preflight(cors(&["any"]), other).await.unwrap(), "*");
+        assert!(preflight(cors(&[]), portal).await.is_none());
+        // A bare wildcard is not an opt-in and is skipped
+        assert!(preflight(cors(&["*", portal]), other).await.is_none());
+    }
+
+    /// Error code the router at `app` answers `method` with, if any.
+    async fn error_code(app: &Router, method: &str) -> Option<i64> {
+        use axum::body::{to_bytes, Body};
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let body = json!({ "jsonrpc": "2.0", "method": method, "params": null, "id": 1 });
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/rpc")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice::<Value>(&bytes).unwrap()["error"]["code"].as_i64()
+    }
+
+    #[tokio::test]
+    async fn test_read_only_router_serves_only_queries() {
+        // Everything served that is not a query
+        const REFUSED: &[&str] = &[
+            "cgt_sendRawTransaction",
+            "cgt_sendTransaction",
+            "cgt_getWork",
+            "cgt_submitWork",
+            "cgt_devFaucet",
+            "cgt_mintDgenNft",
+            "aeon_create",
+            "aeon_recordSyzygy",
+            "aeon_setHandle",
+            "cgt_purgeFailed",
+            "net_getPeers",
+            "admin_compact",
+            "admin_verifyState",
+            "dev_reset",
+        ];
+        let read_only = RpcErrorCode::ReadOnly.as_i32() as i64;
+        let not_found = RpcErrorCode::MethodNotFound.as_i32() as i64;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = ChainConfig::default();
+        config.faucet.enabled = true;
+        config.rpc.dev_reset = true;
+        let full = Arc::new(Node::with_config(dir.path().to_path_buf(), config.clone()).unwrap());
+        let app = rpc_router(full.clone());
+        for method in READ_ONLY_METHODS.iter().chain(REFUSED) {
+            let code = error_code(&app, method).await;
+            assert!(
+                code != Some(read_only) && code != Some(not_found),
+                "{}",
+                method
+            );
+        }
+        drop(app);
+        drop(full);
+
+        config.rpc.read_only = true;
+        let node = Arc::new(Node::with_config(dir.path().to_path_buf(), config).unwrap());
+        let app = rpc_router(node);
+        for method in READ_ONLY_METHODS {
+            let code = error_code(&app, method).await;
+            assert!(
+                code != Some(read_only) && code != Some(not_found),
+                "{}",
+                method
+            );
+        }
+        for method in REFUSED.iter().chain(&["cgt_noSuchMethod"]) {
+            assert_eq!(
+                error_code(&app, method).await,
+                Some(read_only),
+                "{}",
+                method
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_codes_on_the_wire() {
+        // Clients match on these numbers, so they must not drift
+        for (error, code) in [
+            (RpcErrorCode::parse_error("oops"), -32700),
+            (RpcErrorCode::method_not_found("oops"), -32601),
+            (RpcErrorCode::invalid_params("oops"), -32602),
+            (RpcErrorCode::internal("oops"), -32603),
+            (RpcErrorCode::transaction_rejected("oops"), -32001),
+            (RpcErrorCode::work_rejected("oops"), -32002),
+            (RpcErrorCode::faucet_refused("oops"), -32003),
+            (RpcErrorCode::origin_not_allowed("oops"), -32004),
+            (RpcErrorCode::pruned("oops"), -32005),
+            (RpcErrorCode::read_only("oops"), -32605),
+        ] {
+            assert_eq!(
+                serde_json::to_value(&error).unwrap(),
+                json!({ "code": code, "message": "oops" })
+            );
+        }
+    }
+
+    #[test]
+    fn test_privileged_methods_need_privileged_origin() {
+        let mut config = CorsConfig::default();
+        let from = |origin: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ORIGIN, HeaderValue::from_str(origin).unwrap());
+            headers
+        };
+        let portal = from("http://localhost:3000");
+
+        assert_eq!(
+            check_origin(&config, &portal, "net_getPeers")
+                .unwrap_err()
+                .code,
+            -32004
+        );
+        assert!(check_origin(&config, &portal, "cgt_getBalance").is_ok());
+        // Non-browser clients send no Origin
+        assert!(check_origin(&config, &HeaderMap::new(), "net_getPeers").is_ok());
+
+        assert!(check_origin(&config, &portal, "admin_compact").is_err());
+
+        config.privileged_origins = vec!["http://localhost:3000".to_string()];
+        assert!(check_origin(&config, &portal, "net_getPeers").is_ok());
+        assert!(check_origin(&config, &from("http://127.0.0.1:3000"), "net_getPeers").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_forge_config_reflects_config() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = 987_654_321;
+        config.target_block_time_secs = 42;
+        config.genesis.forge.memory_kib = 64;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let resp = call(&node, "cgt_getForgeConfig", Value::Null).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["difficulty_target"], "987654321");
+        assert_eq!(result["target_block_time_secs"], 42);
+        assert_eq!(result["algorithm"], FORGE_ALGORITHM);
+        assert_eq!(result["memory_kib"], 64);
+        assert_eq!(result["time_cost"], node.forge_config().time_cost);
+    }
+
+    #[tokio::test]
+    async fn test_get_node_info() {
+        let mut config = ChainConfig::default();
+        config.faucet.enabled = false;
+        config.rpc.redact_data_dir = true;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let result = call(&node, "cgt_getNodeInfo", Value::Null)
+            .await
+            .result
+            .unwrap();
+        assert_eq!(result["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(result["git_commit"], env!("DEMIURGE_GIT_COMMIT"));
+        let profile = if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        };
+        assert_eq!(result["build_profile"], profile);
+        assert_eq!(
+            result["features"],
+            json!({
+                "dev_faucet": false,
+                "block_production": false,
+                "p2p": false,
+                "read_only": false,
+                "dev_reset": false,
+            })
+        );
+        assert_eq!(result["data_dir"], Value::Null);
+        assert!(result["started_at"].as_u64().unwrap() > 0);
+        assert!(result["uptime_secs"].is_u64());
+        // In-memory nodes open no database
+        assert_eq!(result["startup"]["db_open_ms"], 0.0);
+        for stage in ["migrations_ms", "genesis_check_ms", "tip_recovery_ms"] {
+            assert!(result["startup"][stage].as_f64().unwrap() >= 0.0);
+        }
+
+        let mut config = ChainConfig::default();
+        config.faucet.enabled = true;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let result = call(&node, "cgt_getNodeInfo", Value::Null)
+            .await
+            .result
+            .unwrap();
+        assert_eq!(result["features"]["dev_faucet"], true);
+    }
+
+    #[tokio::test]
+    async fn test_get_token_info() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let result = call(&node, "cgt_getTokenInfo", Value::Null)
+            .await
+            .result
+            .unwrap();
+        assert_eq!(result["symbol"], "CGT");
+        assert_eq!(result["name"], "Creator God Token");
+        assert_eq!(result["decimals"], CGT_DECIMALS);
+        assert_eq!(result["total_supply"], GENESIS_ARCHON_INITIAL_BALANCE);
+        assert_eq!(result["max_supply"], CGT_MAX_SUPPLY);
+        assert_eq!(result["total_supply_display"], "1000000");
+        assert_eq!(result["max_supply_display"], "1000000000");
+    }
+
+    #[tokio::test]
+    async fn test_decimal_cgt_amounts_in_and_out() {
+        let node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        let send = |amount: Value, fee: Value| {
+            call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": 0,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": { "to": hex::encode([2u8; 32]), "amount": amount },
+                    "fee": fee,
+                })),
+            )
+        };
+
+        // Excess precision is rejected, never rounded
+        for (amount, fee) in [
+            (json!("1.000000001"), json!(0)),
+            (json!("1"), json!("0.000000005")),
+        ] {
+            let resp = send(amount, fee).await;
+            assert_eq!(resp.error.unwrap().code, -32602);
+        }
+        let resp = send(json!("1.5"), json!("0.001")).await;
+        assert!(resp.error.is_none(), "{:?}", resp.error);
+        let mempool = call(&node, "cgt_getMempool", Value::Null)
+            .await
+            .result
+            .unwrap();
+        let pending = &mempool["transactions"][0];
+        assert_eq!(pending["fee"], 100_000);
+        assert_eq!(pending["fee_display"], "0.001");
+        mine_block(&node).await;
+
+        let resp = call(
+            &node,
+            "cgt_getBalance",
+            json!({ "address": hex::encode([2u8; 32]) }),
+        )
+        .await;
+        assert_eq!(
+            resp.result.unwrap(),
+            json!({ "balance": 150_000_000, "balance_display": "1.5" })
+        );
+        let resp = call(
+            &node,
+            "cgt_getBalances",
+            json!({ "addresses": [hex::encode(account(1))] }),
+        )
+        .await;
+        assert_eq!(
+            resp.result.unwrap()["balances_display"][hex::encode(account(1))],
+            "999998.499"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transfer_memo_in_receipt() {
+        let node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        let send = |memo: Vec<u8>| {
+            call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": 0,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": {
+                        "to": hex::encode([2u8; 32]),
+                        "amount": 5,
+                        "memo": hex::encode(memo),
+                    },
+                    "fee": 0,
+                })),
+            )
+        };
+
+        let resp = send(vec![0; 129]).await;
+        let error = resp.error.unwrap();
+        assert_eq!(error.code, -32001);
+        assert!(error.message.contains("more than the limit of 128"));
+
+        let tx_hash = send(b"INV-42".to_vec()).await.result.unwrap()["hash"].clone();
+        mine_block(&node).await;
+        let receipt = call(
+            &node,
+            "cgt_getTransactionReceipt",
+            json!({ "tx_hash": tx_hash }),
+        )
+        .await
+        .result
+        .unwrap();
+        assert_eq!(receipt["success"], true);
+        assert_eq!(
+            receipt["events"][0]["attributes"]["memo"],
+            hex::encode("INV-42")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dev_faucet_cooldown_and_disable() {
+        let mut config = ChainConfig::default();
+        config.faucet.enabled = true;
+        config.faucet.cooldown_blocks = 5;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let params = json!({ "address": hex::encode([9u8; 32]) });
+
+        let first = call(&node, "cgt_devFaucet", params.clone()).await;
+        assert_eq!(
+            first.result.unwrap()["new_balance"],
+            node.config.faucet.amount
+        );
+
+        let second = call(&node, "cgt_devFaucet", params.clone()).await;
+        let error = second.error.unwrap();
+        assert_eq!(error.code, -32003);
+        assert!(error
+            .message
+            .starts_with("faucet cooldown active: 5 blocks"));
+
+        let mut config = ChainConfig::default();
+        config.faucet.enabled = false;
+        let disabled = Arc::new(Node::in_memory(config).unwrap());
+        let resp = call(&disabled, "cgt_devFaucet", params).await;
+        assert_eq!(resp.error.unwrap().code, -32601);
+    }
+
+    /// Module registered from outside the default set.
+    struct ArcadeModule;
+
+    impl crate::runtime::RuntimeModule for ArcadeModule {
+        fn module_id(&self) -> &'static str {
+            "arcade"
+        }
+
+        fn storage_prefixes(&self) -> &'static [&'static str] {
+            &["scores"]
+        }
+
+        fn call_ids(&self) -> &'static [&'static str] {
+            &["record"]
+        }
+
+        fn dispatch(
+            &self,
+            call_id: &str,
+            tx: &Transaction,
+            state: &mut crate::core::state::State,
+        ) -> Result<(), String> {
+            match call_id {
+                "record" => state
+                    .put_raw(score_key(&tx.from), tx.payload.clone())
+                    .map_err(|e| e.to_string()),
+                other => Err(format!("arcade: unknown call_id '{}'", other)),
+            }
+        }
+    }
+
+    fn score_key(addr: &Address) -> Vec<u8> {
+        crate::runtime::StorageKey::new("arcade", "scores")
+            .address(addr)
+            .into_bytes()
+    }
+
+    /// Mine the node's current template through `cgt_submitWork`.
+    async fn mine_block(node: &Arc<Node>) {
+        use crate::forge::{forge_hash, meets_difficulty};
+
+        let template = node.block_template();
+        let header_bytes = template.header.serialize_without_nonce();
+        let nonce = (0..)
+            .find(|n| {
+                meets_difficulty(
+                    &forge_hash(&header_bytes, *n, &node.forge_config()),
+                    template.header.difficulty_target,
+                )
+            })
+            .unwrap();
+
+        let resp = call(node, "cgt_submitWork", json!({ "nonce": nonce })).await;
+        assert!(resp.error.is_none(), "{:?}", resp.error);
+    }
+
+    #[tokio::test]
+    async fn test_failed_transaction_and_purge() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.mempool.nonce_retries = 0;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let tx = signed(Transaction {
+            from: account(5),
+            nonce: 1,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                to: [6; 32],
+                amount: 0,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+        let tx_hash = json!({ "tx_hash": hex::encode(tx.hash()) });
+        node.submit_transaction(tx.clone()).unwrap();
+
+        let resp = call(&node, "cgt_getFailedTransaction", tx_hash.clone()).await;
+        assert_eq!(resp.result, Some(Value::Null));
+
+        mine_block(&node).await;
+        let resp = call(&node, "cgt_getFailedTransaction", tx_hash.clone()).await;
+        let failure = resp.result.unwrap();
+        assert_eq!(failure["tx_hash"], hex::encode(tx.hash()));
+        assert_eq!(failure["transaction"], transaction_json(&tx));
+        assert_eq!(failure["reason"], "invalid nonce: expected 0, got 1");
+        assert_eq!(failure["attempts"], 1);
+        assert_eq!(failure["last_attempted_height"], 1);
+        let resp = call(&node, "cgt_getTransactionStatus", tx_hash.clone()).await;
+        assert_eq!(
+            resp.result.unwrap(),
+            json!({ "status": "dead_lettered", "reason": "invalid nonce: expected 0, got 1" })
+        );
+
+        let resp = call(&node, "cgt_purgeFailed", json!({ "tx_hash": "zz" })).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+        let resp = call(&node, "cgt_purgeFailed", tx_hash.clone()).await;
+        assert_eq!(resp.result.unwrap()["purged"], 1);
+        let resp = call(&node, "cgt_purgeFailed", Value::Null).await;
+        assert_eq!(resp.result.unwrap()["purged"], 0);
+        let resp = call(&node, "cgt_getFailedTransaction", tx_hash).await;
+        assert_eq!(resp.result, Some(Value::Null));
+    }
+
+    #[tokio::test]
+    async fn test_verify_state_reports_violations() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        mine_block(&node).await;
+        let resp = call(&node, "admin_verifyState", Value::Null).await;
+        assert_eq!(
+            resp.result.unwrap(),
+            json!({ "consistent": true, "violations": [] })
+        );
+
+        node.with_state_mut(|state| crate::runtime::bank_cgt::credit(state, &[5; 32], 1))
+            .unwrap();
+        let resp = call(&node, "admin_verifyState", Value::Null).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["consistent"], false);
+        let violations = result["violations"].as_array().unwrap();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0]
+            .as_str()
+            .unwrap()
+            .starts_with("CGT balances sum to"));
+    }
+
+    fn node_with_arcade(config: ChainConfig) -> Arc<Node> {
+        let factory: crate::runtime::RuntimeFactory = Arc::new(|chain| {
+            crate::runtime::Runtime::for_chain(chain).with_module(Box::new(ArcadeModule))
+        });
+        Arc::new(
+            Node::in_memory(config)
+                .unwrap()
+                .with_runtime_factory(factory)
+                .unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_external_module_registered_and_dispatched() {
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        let node = node_with_arcade(config);
+
+        let resp = call(&node, "runtime_listModules", Value::Null).await;
+        let modules = resp.result.unwrap()["modules"].clone();
+        assert_eq!(modules[0]["module_id"], "bank_cgt");
+        assert!(modules
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "module_id": "arcade", "call_ids": ["record"] })));
+
+        let player = account(4);
+        node.submit_transaction(signed(Transaction {
+            from: player,
+            nonce: 0,
+            module_id: "arcade".to_string(),
+            call_id: "record".to_string(),
+            payload: vec![7],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }))
+        .unwrap();
+
+        assert_eq!(node.block_template().body.len(), 1);
+        mine_block(&node).await;
+        assert_eq!(
+            node.with_state(|state| state.get_raw(&score_key(&player))),
+            Some(vec![7])
+        );
+        // Its account is registered along with the runtime
+        assert_eq!(
+            node.with_state(|state| crate::runtime::module_account_owner(
+                state,
+                &crate::runtime::module_address("arcade")
+            )),
+            Some("arcade".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "module arcade is already registered")]
+    fn test_runtime_factory_with_duplicate_module_panics() {
+        let factory: crate::runtime::RuntimeFactory = Arc::new(|chain| {
+            crate::runtime::Runtime::for_chain(chain)
+                .with_module(Box::new(ArcadeModule))
+                .with_module(Box::new(ArcadeModule))
+        });
+        let _ = Node::in_memory(ChainConfig::default())
+            .unwrap()
+            .with_runtime_factory(factory);
+    }
+
+    #[tokio::test]
+    async fn test_get_work_template_round_trip() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+
+        let resp = call(&node, "cgt_getWork", Value::Null).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["height"], 1);
+        assert_eq!(
+            result["difficulty_target"],
+            node.config.genesis.difficulty_target.to_string()
+        );
+
+        let bytes = hex::decode(result["header"].as_str().unwrap()).unwrap();
+        let header: crate::core::block::BlockHeader = codec::decode(&bytes).unwrap();
+        assert_eq!(header.height, 1);
+        assert_eq!(header.prev_hash, node.genesis_hash());
+        assert_eq!(header.nonce, 0);
+        assert_eq!(header.serialize_without_nonce(), bytes);
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_returns_canonical_hash() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let tx = signed(Transaction {
+            from: account(1),
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                to: [2; 32],
+                amount: 0,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+
+        // An older client submitting V1 bytes gets the same canonical hash
+        let v1 = tx
+            .to_bytes_versioned(crate::core::transaction::TX_VERSION_V1)
+            .unwrap();
+        let resp = call(
+            &node,
+            "cgt_sendRawTransaction",
+            json!({ "tx": hex::encode(&v1) }),
+        )
+        .await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["hash"], hex::encode(tx.hash()));
+        assert_eq!(node.mempool_transactions(), vec![tx]);
+    }
+
+    #[tokio::test]
+    async fn test_send_raw_transaction_rejects_unaffordable_transfer() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let tx = signed(Transaction {
+            from: account(1),
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                to: [2; 32],
+                amount: 5,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+        let resp = call(
+            &node,
+            "cgt_sendRawTransaction",
+            json!({ "tx": hex::encode(tx.to_bytes().unwrap()) }),
+        )
+        .await;
+        let err = resp.error.unwrap();
+        assert_eq!(err.code, -32001);
+        assert!(err.message.contains("insufficient balance"));
+        assert!(node.mempool_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_addresses_accepted_and_emitted_checksummed() {
+        let node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        let genesis = account(1);
+
+        // Raw hex and checksummed input resolve to the same account
+        for address in [hex::encode(genesis), genesis.to_display()] {
+            let resp = call(&node, "cgt_isArchon", json!({ "address": address })).await;
+            assert_eq!(resp.result.unwrap()["is_archon"], true);
+        }
+
+        // A single corrupted character is rejected rather than hitting another account
+        let mut corrupted = genesis.to_display().into_bytes();
+        let last = corrupted.len() - 1;
+        corrupted[last] = if corrupted[last] == b'q' { b'p' } else { b'q' };
+        let resp = call(
+            &node,
+            "cgt_isArchon",
+            json!({ "address": String::from_utf8(corrupted).unwrap() }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+
+        // Responses carry the checksummed form plus a hex field
+        let mempool_tx = signed(Transaction {
+            from: genesis,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                to: [2; 32],
+                amount: 0,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+        node.submit_transaction(mempool_tx).unwrap();
+        let resp = call(&node, "cgt_getMempool", Value::Null).await;
+        let entry = &resp.result.unwrap()["transactions"][0];
+        assert_eq!(entry["from"], genesis.to_display());
+        assert_eq!(entry["from_hex"], hex::encode(genesis));
+    }
+
+    #[tokio::test]
+    async fn test_account_exists() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+
+        let unseen = call(
+            &node,
+            "cgt_accountExists",
+            json!({ "address": hex::encode([7u8; 32]) }),
+        )
+        .await;
+        assert_eq!(unseen.result.unwrap()["exists"], false);
+
+        // The Genesis Archon is minted to at startup
+        let genesis = call(
+            &node,
+            "cgt_accountExists",
+            json!({ "address": crate::config::GENESIS_ARCHON_ADDRESS_HEX }),
+        )
+        .await;
+        assert_eq!(genesis.result.unwrap()["exists"], true);
+    }
+
+    #[tokio::test]
+    async fn test_get_multisig() {
+        use crate::runtime::multisig_address;
+
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let keys: Vec<Address> = (1..=3u8).map(account).collect();
+        let multisig = multisig_address(&keys, 2);
+        let get = |address: String| call(&node, "cgt_getMultisig", json!({ "address": address }));
+        assert_eq!(get(multisig.to_display()).await.result, Some(Value::Null));
+
+        let resp = call(
+            &node,
+            "cgt_sendTransaction",
+            sign_json(json!({
+                "from": hex::encode(account(9)),
+                "nonce": 0,
+                "module_id": "multisig",
+                "call_id": "multisig_create",
+                "params": {
+                    "keys": keys.iter().map(hex::encode).collect::<Vec<_>>(),
+                    "threshold": 2,
+                },
+            })),
+        )
+        .await;
+        assert!(resp.error.is_none(), "{:?}", resp.error);
+        mine_block(&node).await;
+
+        assert_eq!(
+            get(hex::encode(multisig)).await.result.unwrap(),
+            json!({
+                "address": multisig.to_display(),
+                "keys": keys.iter().map(|key| key.to_display()).collect::<Vec<_>>(),
+                "threshold": 2,
+            })
+        );
+        assert_eq!(get("nope".to_string()).await.error.unwrap().code, -32602);
+
+        // Unsigned transactions from the account are refused at admission
+        let unsigned = Transaction {
+            from: multisig,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let resp = call(
+            &node,
+            "cgt_sendRawTransaction",
+            json!({ "tx": hex::encode(unsigned.to_bytes().unwrap()) }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32001);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_name_both_ways() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let owner = account(9);
+        node.with_state_mut(|state| bank::mint(state, &owner, 5 * crate::config::CGT_UNIT))
+            .unwrap();
+        let resolve = |name: &str| call(&node, "cgt_resolveName", json!({ "name": name }));
+        assert_eq!(resolve("orpheus").await.result, Some(Value::Null));
+
+        let resp = call(
+            &node,
+            "cgt_sendTransaction",
+            sign_json(json!({
+                "from": hex::encode(owner),
+                "nonce": 0,
+                "module_id": "names",
+                "call_id": "register_name",
+                "params": { "name": "orpheus" },
+            })),
+        )
+        .await;
+        assert!(resp.error.is_none(), "{:?}", resp.error);
+        mine_block(&node).await;
+
+        assert_eq!(
+            resolve("@Orpheus").await.result.unwrap(),
+            json!({
+                "name": "orpheus",
+                "address": owner.to_display(),
+                "address_hex": hex::encode(owner),
+            })
+        );
+        let get = |address: String| call(&node, "cgt_getName", json!({ "address": address }));
+        assert_eq!(
+            get(owner.to_display()).await.result.unwrap(),
+            json!({ "address": owner.to_display(), "name": "orpheus" })
+        );
+        assert_eq!(get(hex::encode([8u8; 32])).await.result, Some(Value::Null));
+        assert_eq!(get("nope".to_string()).await.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_get_events_filters_by_module_and_name() {
+        let node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        let send = |nonce: u64, module_id: &str, call_id: &str, params: Value| {
+            call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": module_id,
+                    "call_id": call_id,
+                    "params": params,
+                })),
+            )
+        };
+        let transfer = |amount: u64| json!({ "to": hex::encode([2u8; 32]), "amount": amount });
+
+        let resp = send(0, "bank_cgt", "transfer", transfer(100)).await;
+        let first = resp.result.unwrap()["hash"].clone();
+        mine_block(&node).await;
+        let multisig = json!({ "keys": [hex::encode(account(2))], "threshold": 1 });
+        assert!(send(0, "multisig", "multisig_create", multisig)
+            .await
+            .error
+            .is_none());
+        let resp = send(1, "bank_cgt", "transfer", transfer(200)).await;
+        let second = resp.result.unwrap()["hash"].clone();
+        mine_block(&node).await;
+
+        let events = |params: Value| call(&node, "cgt_getEvents", params);
+        let transfers = events(json!({
+            "from_height": 1,
+            "to_height": 2,
+            "module_id": "bank_cgt",
+        }))
+        .await
+        .result
+        .unwrap();
+        let transfers = transfers.as_array().unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0]["height"], 1);
+        assert_eq!(transfers[0]["tx_hash"], first);
+        assert_eq!(transfers[0]["attributes"]["amount"], "100");
+        assert_eq!(transfers[1]["height"], 2);
+        assert_eq!(transfers[1]["tx_hash"], second);
+
+        let created = events(json!({ "from_height": 0, "to_height": 5, "name": "create" }))
+            .await
+            .result
+            .unwrap();
+        assert_eq!(created.as_array().unwrap().len(), 1);
+        assert_eq!(created[0]["module_id"], "multisig");
+        assert_eq!(created[0]["height"], 2);
+        let none = events(json!({
+            "from_height": 1,
+            "to_height": 1,
+            "module_id": "multisig",
+            "name": "create",
+        }))
+        .await
+        .result
+        .unwrap();
+        assert_eq!(none, json!([]));
+
+        for bad in [
+            json!({ "from_height": 2, "to_height": 1 }),
+            json!({ "from_height": 0, "to_height": MAX_EVENTS_BLOCK_SPAN }),
+            json!({ "from_height": 0 }),
+        ] {
+            let resp = call(&node, "cgt_getEvents", bad).await;
+            assert_eq!(resp.error.unwrap().code, -32602);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_balances_mixed_addresses() {
+        use crate::config::GENESIS_ARCHON_ADDRESS;
+
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let unfunded = hex::encode([7u8; 32]);
+        // Checksummed and hex forms are both accepted; keys are hex
+        let resp = call(
+            &node,
+            "cgt_getBalances",
+            json!({ "addresses": [GENESIS_ARCHON_ADDRESS.to_display(), unfunded] }),
+        )
+        .await;
+        let balances = resp.result.unwrap()["balances"].clone();
+        assert_eq!(
+            balances,
+            json!({
+                hex::encode(GENESIS_ARCHON_ADDRESS): GENESIS_ARCHON_INITIAL_BALANCE,
+                unfunded.clone(): 0,
+            })
+        );
+
+        let resp = call(
+            &node,
+            "cgt_getBalances",
+            json!({ "addresses": [unfunded, "not-an-address"] }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_get_balances_cap() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let at_cap = vec![hex::encode([7u8; 32]); MAX_BALANCES_PER_REQUEST];
+        let resp = call(&node, "cgt_getBalances", json!({ "addresses": at_cap })).await;
+        assert!(resp.result.is_some());
+
+        let over_cap = vec![hex::encode([7u8; 32]); MAX_BALANCES_PER_REQUEST + 1];
+        let resp = call(&node, "cgt_getBalances", json!({ "addresses": over_cap })).await;
+        let err = resp.error.unwrap();
+        assert_eq!(err.code, -32602);
+        assert!(err.message.contains("too many addresses"));
+    }
+
+    /// Sign `raw` and submit it as JSON and as raw bytes to fresh nodes; both
+    /// paths must admit it with the same hash.
+    async fn assert_json_matches_raw(mut json_tx: Value, raw: Transaction) {
+        let raw = signed(raw);
+        json_tx["signature"] = json!(hex::encode(&raw.signature));
+        let json_node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        let json_resp = call(&json_node, "cgt_sendTransaction", json_tx).await;
+        let json_result = json_resp.result.expect("JSON submission accepted");
+
+        let raw_node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        let raw_hex = hex::encode(raw.to_bytes().unwrap());
+        let raw_resp = call(
+            &raw_node,
+            "cgt_sendRawTransaction",
+            json!({ "tx": raw_hex }),
+        )
+        .await;
+        let raw_result = raw_resp.result.expect("raw submission accepted");
+
+        assert_eq!(json_result["hash"], raw_result["hash"]);
+        assert_eq!(json_node.mempool_transactions(), vec![raw]);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_json_transfer() {
+        let json_tx = json!({
+            "from": hex::encode(account(1)),
+            "nonce": 3,
+            "module_id": "bank_cgt",
+            "call_id": "transfer",
+            "params": { "to": hex::encode([2u8; 32]), "amount": 300 },
+            "fee": 10,
+        });
+        let raw = Transaction {
+            from: account(1),
+            nonce: 3,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                to: [2; 32],
+                amount: 300,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 10,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        assert_json_matches_raw(json_tx, raw).await;
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_json_nft_transfer() {
+        let json_tx = json!({
+            "from": hex::encode(account(1)),
+            "nonce": 0,
+            "module_id": "nft_dgen",
+            "call_id": "transfer_nft",
+            "params": { "token_id": 7, "to": hex::encode([3u8; 32]) },
+            "valid_until_height": 50,
+        });
+        let raw = Transaction {
+            from: account(1),
+            nonce: 0,
+            module_id: "nft_dgen".to_string(),
+            call_id: "transfer_nft".to_string(),
+            payload: codec::encode(&crate::runtime::nft_dgen::TransferNftParams {
+                token_id: 7,
+                to: [3; 32],
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: Some(50),
+            sig_scheme: 0,
+        };
+        assert_json_matches_raw(json_tx, raw).await;
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_unsupported_call() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let resp = call(
+            &node,
+            "cgt_sendTransaction",
+            json!({
+                "from": hex::encode([1u8; 32]),
+                "nonce": 0,
+                "module_id": "bank_cgt",
+                "call_id": "burn",
+                "params": {},
+            }),
+        )
+        .await;
+        let err = resp.error.unwrap();
+        assert_eq!(err.code, -32602);
+        assert!(err.message.contains("supported: "));
+        assert!(node.mempool_transactions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_work_requires_nonce() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let resp = call(&node, "cgt_submitWork", json!({})).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_governance_proposal_lifecycle() {
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let archon = hex::encode(account(1));
+        for (nonce, call_id, params) in [
+            (
+                0,
+                "create_proposal",
+                json!({
+                    "description": "1% marketplace fee",
+                    "param_key": "abyss.marketplace_fee_bps",
+                    "new_value": 100,
+                    "voting_deadline": 1,
+                }),
+            ),
+            (1, "vote", json!({ "proposal_id": 0, "approve": true })),
+        ] {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": archon,
+                    "nonce": nonce,
+                    "module_id": "governance",
+                    "call_id": call_id,
+                    "params": params,
+                })),
+            )
+            .await;
+            assert!(resp.error.is_none(), "{:?}", resp.error);
+        }
+
+        // Voting stays open through the deadline block
+        mine_block(&node).await;
+        let resp = call(&node, "gov_listProposals", Value::Null).await;
+        let proposals = resp.result.unwrap()["proposals"].clone();
+        assert_eq!(proposals.as_array().unwrap().len(), 1);
+        assert_eq!(proposals[0]["status"], "Open");
+        assert_eq!(proposals[0]["proposer"], account(1).to_display());
+
+        // The next block tallies and applies it
+        mine_block(&node).await;
+        let resp = call(&node, "gov_getProposal", json!({ "proposal_id": 0 })).await;
+        assert_eq!(resp.result.unwrap()["status"], "Passed");
+        assert_eq!(
+            node.with_state(|state| crate::runtime::governance::get_param(
+                state,
+                crate::runtime::governance::PARAM_MARKETPLACE_FEE_BPS
+            )),
+            Some(100)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transactions_by_address_indexes_both_parties() {
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let recipient = hex::encode([2u8; 32]);
+
+        let mut hashes = Vec::new();
+        for nonce in 0..2 {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": { "to": recipient, "amount": 300 },
+                })),
+            )
+            .await;
+            hashes.push(resp.result.unwrap()["hash"].clone());
+            mine_block(&node).await;
+        }
+
+        // Both parties see both transfers, newest first by default
+        for address in [hex::encode(account(1)), recipient.clone()] {
+            let resp = call(
+                &node,
+                "cgt_getTransactionsByAddress",
+                json!({ "address": address }),
+            )
+            .await;
+            let result = resp.result.unwrap();
+            assert_eq!(result["total"], 2);
+            let txs = result["transactions"].as_array().unwrap();
+            assert_eq!(txs[0]["tx_hash"], hashes[1]);
+            assert_eq!(txs[0]["height"], 2);
+            assert_eq!(txs[0]["success"], true);
+            assert_eq!(txs[1]["tx_hash"], hashes[0]);
+        }
+
+        let resp = call(
+            &node,
+            "cgt_getTransactionsByAddress",
+            json!({ "address": recipient, "offset": 1, "limit": 5, "direction": "asc" }),
+        )
+        .await;
+        let txs = resp.result.unwrap()["transactions"].clone();
+        assert_eq!(txs.as_array().unwrap().len(), 1);
+        assert_eq!(txs[0]["tx_hash"], hashes[1]);
+
+        let resp = call(
+            &node,
+            "cgt_getTransactionsByAddress",
+            json!({ "address": recipient, "direction": "sideways" }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_proof_verifies_against_block_header() {
+        use crate::core::block::{verify_inclusion_proof, TxInclusionProof};
+
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let mut hashes = Vec::new();
+        for nonce in 0..3 {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": { "to": hex::encode([2u8; 32]), "amount": 5 },
+                })),
+            )
+            .await;
+            hashes.push(resp.result.unwrap()["hash"].as_str().unwrap().to_string());
+        }
+        mine_block(&node).await;
+
+        let block = call(&node, "cgt_getBlockByHeight", json!({ "height": 1 }))
+            .await
+            .result
+            .unwrap();
+        let tx_root = block["header"]["tx_root"].as_str().unwrap().to_string();
+        for (index, hash) in hashes.iter().enumerate() {
+            let resp = call(&node, "cgt_getTransactionProof", json!({ "tx_hash": hash })).await;
+            let result = resp.result.unwrap();
+            assert_eq!(result["available"], true);
+            assert_eq!(result["height"], 1);
+            assert_eq!(result["index"], index);
+            assert_eq!(result["tx_count"], 3);
+            assert_eq!(result["tx_root"], tx_root);
+            assert_eq!(result["block_hash"], block["hash"]);
+
+            let proof = TxInclusionProof {
+                tx_hash: parse_tx_hash_hex(hash).unwrap(),
+                index: index as u64,
+                tx_count: 3,
+                branch: result["branch"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|h| parse_tx_hash_hex(h.as_str().unwrap()).unwrap())
+                    .collect(),
+            };
+            assert!(verify_inclusion_proof(
+                &proof,
+                &parse_tx_hash_hex(&tx_root).unwrap()
+            ));
+        }
+
+        let resp = call(
+            &node,
+            "cgt_getTransactionProof",
+            json!({ "tx_hash": hex::encode([7u8; 32]) }),
+        )
+        .await;
+        assert_eq!(resp.result, Some(Value::Null));
+        let resp = call(
+            &node,
+            "cgt_getTransactionProof",
+            json!({ "tx_hash": "xyz" }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_block_summary_counts_transactions_and_fees() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        for (nonce, fee) in [(0, 7), (1, 35)] {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": { "to": hex::encode([2u8; 32]), "amount": 5 },
+                    "fee": fee,
+                })),
+            )
+            .await;
+            assert!(resp.error.is_none(), "{:?}", resp.error);
+        }
+        mine_block(&node).await;
+
+        let block = call(&node, "cgt_getBlockByHeight", json!({ "height": 1 }))
+            .await
+            .result
+            .unwrap();
+        let summary = call(&node, "cgt_getBlockSummary", json!({ "height": 1 }))
+            .await
+            .result
+            .unwrap();
+        assert_eq!(summary["height"], 1);
+        assert_eq!(summary["hash"], block["hash"]);
+        assert_eq!(summary["prev_hash"], hex::encode(node.genesis_hash()));
+        assert_eq!(summary["timestamp"], block["header"]["timestamp"]);
+        assert_eq!(summary["tx_count"], 2);
+        assert_eq!(summary["total_fees"], 42);
+        assert!(summary.get("transactions").is_none());
+
+        // Genesis has no transactions
+        let genesis = call(&node, "cgt_getBlockSummary", json!({ "height": 0 }))
+            .await
+            .result
+            .unwrap();
+        assert_eq!(genesis["tx_count"], 0);
+        assert_eq!(genesis["total_fees"], 0);
+
+        let resp = call(&node, "cgt_getBlockSummary", json!({ "height": 2 })).await;
+        assert_eq!(resp.result, Some(Value::Null));
+        let resp = call(&node, "cgt_getBlockSummary", json!({})).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_latest_blocks_newest_first() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        mine_block(&node).await;
+        mine_block(&node).await;
+
+        let heights = |result: Value| -> Vec<u64> {
+            result
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|summary| summary["height"].as_u64().unwrap())
+                .collect()
+        };
+
+        // Fewer blocks than asked for: all of them, genesis included
+        let resp = call(&node, "cgt_getLatestBlocks", json!({ "count": 10 })).await;
+        let result = resp.result.unwrap();
+        assert_eq!(heights(result.clone()), vec![2, 1, 0]);
+        let summary = call(&node, "cgt_getBlockSummary", json!({ "height": 2 }))
+            .await
+            .result
+            .unwrap();
+        assert_eq!(result[0], summary);
+        assert_eq!(result[0]["prev_hash"], result[1]["hash"]);
+
+        let resp = call(&node, "cgt_getLatestBlocks", json!({ "count": 2 })).await;
+        assert_eq!(heights(resp.result.unwrap()), vec![2, 1]);
+        // The next page starts below the last one
+        let resp = call(
+            &node,
+            "cgt_getLatestBlocks",
+            json!({ "count": 2, "from_height": 0 }),
+        )
+        .await;
+        assert_eq!(heights(resp.result.unwrap()), vec![0]);
+        let resp = call(&node, "cgt_getLatestBlocks", json!({ "count": 0 })).await;
+        assert_eq!(heights(resp.result.unwrap()), Vec::<u64>::new());
+
+        // Without params, the default count
+        let resp = call(&node, "cgt_getLatestBlocks", Value::Null).await;
+        assert_eq!(heights(resp.result.unwrap()).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pruned_blocks_keep_headers_and_report_pruned() {
+        use crate::config::KeepBlocks;
+
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        config.storage.keep_blocks = KeepBlocks::Recent(3);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let mut hashes = Vec::new();
+        for nonce in 0..10 {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": { "to": hex::encode([2u8; 32]), "amount": 5 },
+                })),
+            )
+            .await;
+            hashes.push(resp.result.unwrap()["hash"].clone());
+            mine_block(&node).await;
+        }
+
+        // Blocks 8 through 10 survive whole, with their receipts and events
+        for height in 8..=10u64 {
+            let resp = call(&node, "cgt_getBlockByHeight", json!({ "height": height })).await;
+            let block = resp.result.unwrap();
+            assert_eq!(
+                block["transactions"][0]["hash"],
+                hashes[height as usize - 1]
+            );
+            let resp = call(
+                &node,
+                "cgt_getTransactionReceipt",
+                json!({ "tx_hash": hashes[height as usize - 1] }),
+            )
+            .await;
+            let receipt = resp.result.unwrap();
+            assert_eq!(receipt["height"], height);
+            assert_eq!(receipt["events"].as_array().unwrap().len(), 1);
+        }
+
+        // Older bodies and receipts are gone, and say so
+        for height in 1..=7u64 {
+            let tx_hash = &hashes[height as usize - 1];
+            let resp = call(&node, "cgt_getBlockByHeight", json!({ "height": height })).await;
+            let error = resp.error.unwrap();
+            assert_eq!(error.code, RpcErrorCode::Pruned.as_i32());
+            assert!(error.message.starts_with("pruned"));
+            let status = call(
+                &node,
+                "cgt_getTransactionStatus",
+                json!({ "tx_hash": tx_hash }),
+            )
+            .await
+            .result
+            .unwrap();
+            // Receipt markers last another 3 blocks, after which the
+            // transactions read as unknown
+            if height <= 4 {
+                assert_eq!(status, json!({ "status": "unknown" }));
+                let resp = call(
+                    &node,
+                    "cgt_getTransactionReceipt",
+                    json!({ "tx_hash": tx_hash }),
+                )
+                .await;
+                assert_eq!(resp.result, Some(Value::Null));
+            } else {
+                assert_eq!(status, json!({ "status": "pruned", "height": height }));
+                for method in ["cgt_getTransactionReceipt", "cgt_getTransactionProof"] {
+                    let resp = call(&node, method, json!({ "tx_hash": tx_hash })).await;
+                    assert_eq!(
+                        resp.error.unwrap().code,
+                        RpcErrorCode::Pruned.as_i32(),
+                        "{}",
+                        method
+                    );
+                }
+            }
+
+            // Headers stay queryable and still chain together
+            let resp = call(
+                &node,
+                "cgt_getBlockByHeight",
+                json!({ "height": height, "include_transactions": false }),
+            )
+            .await;
+            let header = resp.result.unwrap();
+            assert_eq!(header["header"]["height"], height);
+            assert!(header.get("transactions").is_none());
+            let next = node.get_header_by_height(height + 1).unwrap();
+            assert_eq!(header["hash"], hex::encode(next.prev_hash));
+        }
+
+        // Genesis, current state, and unknown hashes are unaffected
+        let resp = call(&node, "cgt_getBlockByHeight", json!({ "height": 0 })).await;
+        assert!(resp.result.unwrap()["transactions"]
+            .as_array()
+            .unwrap()
+            .is_empty());
+        assert_eq!(node.get_balance_cgt(&[2u8; 32]), 50);
+        let resp = call(
+            &node,
+            "cgt_getTransactionReceipt",
+            json!({ "tx_hash": hex::encode([7u8; 32]) }),
+        )
+        .await;
+        assert_eq!(resp.result, Some(Value::Null));
+    }
+
+    #[tokio::test]
+    async fn test_describe_call() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+
+        let resp = call(
+            &node,
+            "cgt_describeCall",
+            json!({ "module_id": "bank_cgt", "call_id": "transfer" }),
+        )
+        .await;
+        assert_eq!(
+            resp.result.unwrap(),
+            json!({
+                "module_id": "bank_cgt",
+                "call_id": "transfer",
+                "fields": [
+                    { "name": "to", "type": "address" },
+                    { "name": "amount", "type": "u64" },
+                    { "name": "memo", "type": "option<bytes>" },
+                ],
+            })
+        );
+
+        let resp = call(
+            &node,
+            "cgt_describeCall",
+            json!({ "module_id": "bank_cgt", "call_id": "burn" }),
+        )
+        .await;
+        assert_eq!(resp.result, Some(Value::Null));
+        let resp = call(
+            &node,
+            "cgt_describeCall",
+            json!({ "module_id": "bank_cgt" }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_escrowed_funds_held_at_module_address() {
+        use crate::runtime::bank_cgt::TransferParams;
+        use crate::runtime::escrow::{CreateEscrowParams, ReleaseEscrowParams};
+
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let beneficiary = [5u8; 32];
+
+        let resp = call(
+            &node,
+            "cgt_getModuleAddress",
+            json!({ "module_id": "escrow" }),
+        )
+        .await;
+        let result = resp.result.unwrap();
+        let escrow = crate::runtime::escrow_account();
+        assert_eq!(result["address_hex"], hex::encode(escrow));
+        assert_eq!(result["address"], escrow.to_display());
+        let resp = call(
+            &node,
+            "cgt_getModuleAddress",
+            json!({ "module_id": "vault" }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+
+        let tx = |from: Address, nonce: u64, module_id: &str, call_id: &str, payload| Transaction {
+            from,
+            nonce,
+            module_id: module_id.to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let create = CreateEscrowParams {
+            beneficiary,
+            amount: 400,
+            refund_after_height: 100,
+        };
+        node.submit_transaction(signed(tx(
+            account(1),
+            0,
+            "escrow",
+            "create_escrow",
+            codec::encode(&create).unwrap(),
+        )))
+        .unwrap();
+        mine_block(&node).await;
+        assert_eq!(node.get_balance_cgt(&escrow), 400);
+
+        // Nobody can move the escrowed funds with a plain transfer
+        let theft = tx(
+            escrow,
+            0,
+            "bank_cgt",
+            "transfer",
+            codec::encode(&TransferParams {
+                to: [6; 32],
+                amount: 400,
+                memo: None,
+            })
+            .unwrap(),
+        );
+        assert!(node
+            .submit_transaction(theft.clone())
+            .unwrap_err()
+            .contains("is a module account and cannot send transactions"));
+        // Nor execute in a block that carries one anyway
+        let runtime = crate::runtime::Runtime::for_chain(&node.config);
+        let err = node.with_state_mut(|state| runtime.dispatch_tx(&theft, state));
+        assert!(err.unwrap_err().contains("is a module account"));
+        assert_eq!(node.get_balance_cgt(&escrow), 400);
+
+        // The escrow module pays out of its own account
+        node.submit_transaction(signed(tx(
+            account(1),
+            1,
+            "escrow",
+            "release_escrow",
+            codec::encode(&ReleaseEscrowParams { id: 0 }).unwrap(),
+        )))
+        .unwrap();
+        mine_block(&node).await;
+        assert_eq!(node.get_balance_cgt(&escrow), 0);
+        assert_eq!(node.get_balance_cgt(&beneficiary), 400);
+    }
+
+    #[tokio::test]
+    async fn test_aeon_create_mints_starter_grant() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let address = [4u8; 32];
+        let supply_before = node.with_state(bank::total_supply);
+        let expected = if cfg!(debug_assertions) {
+            AEON_STARTER_GRANT
+        } else {
+            0
+        };
+
+        let resp = call(
+            &node,
+            "aeon_create",
+            json!({
+                "address": hex::encode(address),
+                "display_name": "Nomad",
+                "avatar_uri": "ipfs://nomad",
+            }),
+        )
+        .await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["starter_grant"], expected);
+        assert_eq!(result["avatar_uri"], "ipfs://nomad");
+        assert_eq!(node.get_balance_cgt(&address), expected);
+        assert_eq!(
+            node.with_state(bank::total_supply),
+            supply_before + expected
+        );
+
+        // A refused profile mints nothing
+        let resp = call(
+            &node,
+            "aeon_create",
+            json!({ "address": hex::encode(address), "display_name": "Again" }),
+        )
+        .await;
+        assert!(resp.error.is_some());
+        assert_eq!(node.get_balance_cgt(&address), expected);
+        assert_eq!(
+            node.with_state(bank::total_supply),
+            supply_before + expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_archons_lists_names() {
+        let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+        let aeon = [7u8; 32];
+        node.with_state_mut(|state| {
+            avatars::grant_archon(state, &aeon).unwrap();
+            avatars::create_aeon_profile(state, aeon, "Sophia".to_string(), None, 0).unwrap();
+        });
+
+        let resp = call(&node, "cgt_getArchons", json!({})).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["total"], 2);
+        let archons = result["archons"].as_array().unwrap();
+        let sophia = archons
+            .iter()
+            .find(|a| a["address_hex"] == hex::encode(aeon))
+            .unwrap();
+        assert_eq!(sophia["display_name"], "Sophia");
+        assert_eq!(sophia["address"], aeon.to_display());
+
+        let resp = call(&node, "cgt_getArchons", json!({ "offset": 1, "limit": 5 })).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["total"], 2);
+        assert_eq!(result["archons"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_account_status_shape() {
+        let node = Arc::new(Node::in_memory(signing_config()).unwrap());
+        for nonce in [0, 2] {
+            call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": "bank_cgt",
+                    "call_id": "transfer",
+                    "params": { "to": hex::encode([2u8; 32]), "amount": 1 },
+                })),
+            )
+            .await;
+        }
+
+        let resp = call(
+            &node,
+            "cgt_getAccountStatus",
+            json!({ "address": hex::encode(account(1)) }),
+        )
+        .await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["committed_nonce"], 0);
+        assert_eq!(result["highest_contiguous_pending_nonce"], 0);
+        assert_eq!(result["nonce_gaps"], json!([{ "start": 1, "end": 1 }]));
+        assert_eq!(result["pending"][1]["nonce"], 2);
+        assert_eq!(result["balance"], GENESIS_ARCHON_INITIAL_BALANCE);
+        assert_eq!(result["balance_display"], "1000000");
+
+        let resp = call(&node, "cgt_getAccountStatus", json!({ "address": "nope" })).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_nft_content_joins_fabric_asset() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let root = hex::encode([7u8; 32]);
+
+        let resp = call(
+            &node,
+            "cgt_mintDgenNft",
+            json!({
+                "owner": hex::encode(account(1)),
+                "fabric_root_hash": root,
+                "name": "Relic",
+            }),
+        )
+        .await;
+        let nft_id = resp.result.unwrap()["nft_id"].as_u64().unwrap();
+
+        // No asset registered for the root yet: the join comes back empty
+        let resolved = call(&node, "cgt_resolveNftContent", json!({ "nft_id": nft_id }))
+            .await
+            .result
+            .unwrap();
+        assert_eq!(resolved["fabric_root_hash"], root);
+        assert_eq!(resolved["asset_registered"], false);
+        assert!(resolved["uri_hint"].is_null());
+
+        call(
+            &node,
+            "cgt_sendTransaction",
+            sign_json(json!({
+                "from": hex::encode(account(1)),
+                "nonce": 0,
+                "module_id": "fabric_manager",
+                "call_id": "register_asset",
+                "params": {
+                    "fabric_root_hash": root,
+                    "initial_pool_cgt": 0,
+                    "uri_hint": "ipfs://bafyrelic",
+                },
+            })),
+        )
+        .await;
+        mine_block(&node).await;
+
+        // The NFT has no hint of its own, so the asset's is used
+        let resolved = call(&node, "cgt_resolveNftContent", json!({ "nft_id": nft_id }))
+            .await
+            .result
+            .unwrap();
+        assert_eq!(resolved["asset_registered"], true);
+        assert_eq!(resolved["uri_hint"], "ipfs://bafyrelic");
+
+        let resp = call(&node, "cgt_resolveNftContent", json!({ "nft_id": 999 })).await;
+        assert!(resp.result.unwrap().is_null());
+        let resp = call(&node, "cgt_resolveNftContent", json!({})).await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_nft_owner_follows_transfer_with_proof() {
+        use crate::core::merkle;
+        use crate::runtime::nft_dgen::{nft_storage_key, DGenMetadata};
+
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let recipient = [2u8; 32];
+
+        let resp = call(
+            &node,
+            "cgt_mintDgenNft",
+            json!({
+                "owner": hex::encode(account(1)),
+                "fabric_root_hash": hex::encode([0u8; 32]),
+                "name": "Relic",
+            }),
+        )
+        .await;
+        let token_id = resp.result.unwrap()["nft_id"].as_u64().unwrap();
+        let resp = call(&node, "cgt_getNftOwner", json!({ "token_id": token_id })).await;
+        assert_eq!(resp.result.unwrap()["owner_hex"], hex::encode(account(1)));
+
+        call(
+            &node,
+            "cgt_sendTransaction",
+            sign_json(json!({
+                "from": hex::encode(account(1)),
+                "nonce": 0,
+                "module_id": "nft_dgen",
+                "call_id": "transfer_nft",
+                "params": { "token_id": token_id, "to": hex::encode(recipient) },
+            })),
+        )
+        .await;
+        mine_block(&node).await;
+
+        let resp = call(&node, "cgt_getNftOwner", json!({ "token_id": token_id })).await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["owner"], recipient.to_display());
+        assert_eq!(result["owner_hex"], hex::encode(recipient));
+
+        let resp = call(
+            &node,
+            "cgt_getNftOwnerWithProof",
+            json!({ "token_id": token_id }),
+        )
+        .await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["owner_hex"], hex::encode(recipient));
+        assert_eq!(result["height"], 1);
+        let decode32 = |v: &Value| -> [u8; 32] {
+            hex::decode(v.as_str().unwrap())
+                .unwrap()
+                .try_into()
+                .unwrap()
+        };
+        let proof = &result["proof"];
+        let proof = merkle::Proof {
+            key: hex::decode(proof["key"].as_str().unwrap()).unwrap(),
+            value: hex::decode(proof["value"].as_str().unwrap()).unwrap(),
+            leaf_index: proof["leaf_index"].as_u64().unwrap(),
+            leaf_count: proof["leaf_count"].as_u64().unwrap(),
+            siblings: proof["siblings"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(decode32)
+                .collect(),
+        };
+        let state_root = decode32(&result["state_root"]);
+        assert!(proof.verify(&state_root));
+        // The mined block's header commits to the root
+        let header = node.get_header_by_height(1).unwrap();
+        assert_eq!(state_root, header.state_root);
+        assert_eq!(decode32(&result["block_hash"]), header.hash());
+        assert_eq!(result["committed"], true);
+        assert_eq!(proof.key, nft_storage_key(token_id));
+        let metadata: DGenMetadata = codec::decode_versioned(&proof.value).unwrap();
+        assert_eq!(metadata.owner, recipient);
+
+        // A dev write after the block leaves the proof uncommitted
+        call(
+            &node,
+            "cgt_mintDgenNft",
+            json!({
+                "owner": hex::encode(account(1)),
+                "fabric_root_hash": hex::encode([1u8; 32]),
+                "name": "Echo",
+            }),
+        )
+        .await;
+        let resp = call(
+            &node,
+            "cgt_getNftOwnerWithProof",
+            json!({ "token_id": token_id }),
+        )
+        .await;
+        assert_eq!(resp.result.unwrap()["committed"], false);
+
+        let resp = call(
+            &node,
+            "cgt_getNftOwnerWithProof",
+            json!({ "token_id": 999 }),
+        )
+        .await;
+        assert!(resp.result.unwrap().is_null());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_receipt_after_mining() {
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let resp = call(
+            &node,
+            "cgt_sendTransaction",
+            sign_json(json!({
+                "from": hex::encode(account(1)),
+                "nonce": 0,
+                "module_id": "bank_cgt",
+                "call_id": "transfer",
+                "params": { "to": hex::encode([2u8; 32]), "amount": 300 },
+            })),
+        )
+        .await;
+        let tx_hash = resp.result.unwrap()["hash"].clone();
+
+        // No receipt while the transaction is pending
+        let resp = call(
+            &node,
+            "cgt_getTransactionReceipt",
+            json!({ "tx_hash": tx_hash }),
+        )
+        .await;
+        assert_eq!(resp.result, Some(Value::Null));
+
+        mine_block(&node).await;
+        let resp = call(
+            &node,
+            "cgt_getTransactionReceipt",
+            json!({ "tx_hash": tx_hash }),
+        )
+        .await;
+        let receipt = resp.result.unwrap();
+        assert_eq!(receipt["tx_hash"], tx_hash);
+        assert_eq!(receipt["height"], 1);
+        assert_eq!(receipt["success"], true);
+        assert_eq!(receipt["error"], Value::Null);
+        assert_eq!(receipt["events"][0]["kind"], "transfer");
+        assert_eq!(receipt["events"][0]["attributes"]["amount"], "300");
+        assert_eq!(
+            receipt["events"][0]["attributes"]["amount_display"],
+            "0.000003"
+        );
+
+        let resp = call(
+            &node,
+            "cgt_getTransactionReceipt",
+            json!({ "tx_hash": "abcd" }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
+    }
+
+    #[tokio::test]
+    async fn test_quests_list_and_progress() {
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+
+        let archon = hex::encode(account(1));
+        for (nonce, module_id, call_id, params) in [
+            (
+                0,
+                "quests",
+                "create_quest",
+                json!({
+                    "name": "Generous",
+                    "criteria": {
+                        "module_id": "bank_cgt",
+                        "event_kind": "transfer",
+                        "count": 2,
+                        "window_blocks": 10,
+                    },
+                    "xp_reward": 5,
+                    "badge": null,
+                }),
+            ),
+            (
+                0,
+                "bank_cgt",
+                "transfer",
+                json!({ "to": hex::encode([2u8; 32]), "amount": 1 }),
+            ),
+        ] {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": archon,
+                    "nonce": nonce,
+                    "module_id": module_id,
+                    "call_id": call_id,
+                    "params": params,
+                })),
+            )
+            .await;
+            assert!(resp.error.is_none(), "{:?}", resp.error);
+        }
+        mine_block(&node).await;
+
+        let resp = call(&node, "quests_list", Value::Null).await;
+        let quests = resp.result.unwrap()["quests"].clone();
+        assert_eq!(quests[0]["name"], "Generous");
+        assert_eq!(quests[0]["creator"], account(1).to_display());
+
+        // The transfer in the quest's creation block already counts
+        let resp = call(&node, "quests_getProgress", json!({ "address": archon })).await;
+        let progress = resp.result.unwrap()["progress"].clone();
+        assert_eq!(
+            progress,
+            json!([{ "quest_id": 0, "window_start": 1, "count": 1, "completions": 0 }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_status_lifecycle() {
+        use crate::core::block::{tx_root, Block, BlockHeader};
+
+        // Every nonce meets this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        async fn status(node: &Arc<Node>, hash: [u8; 32]) -> Value {
+            let params = json!({ "tx_hash": hex::encode(hash) });
+            call(node, "cgt_getTransactionStatus", params)
+                .await
+                .result
+                .unwrap()
+        }
+
+        let transfer = |from, amount| {
+            signed(Transaction {
+                from,
+                nonce: 0,
+                module_id: "bank_cgt".to_string(),
+                call_id: "transfer".to_string(),
+                payload: codec::encode(&crate::runtime::bank_cgt::TransferParams {
+                    to: [2; 32],
+                    amount,
+                    memo: None,
+                })
+                .unwrap(),
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            })
+        };
+        let funded = transfer(account(1), 300);
+        assert_eq!(status(&node, funded.hash()).await["status"], "unknown");
+
+        node.submit_transaction(funded.clone()).unwrap();
+        assert_eq!(status(&node, funded.hash()).await["status"], "pending");
+        let err = node.submit_transaction(funded.clone()).unwrap_err();
+        assert!(err.contains("already in mempool"));
+
+        mine_block(&node).await;
+        assert_eq!(
+            status(&node, funded.hash()).await,
+            json!({ "status": "confirmed", "height": 1 })
+        );
+
+        // Templates leave failing transactions out, so execute one directly
+        let unfunded = transfer(account(7), 300);
+        let mut block = Block {
+            header: BlockHeader {
+                height: 2,
+                prev_hash: [0; 32],
+                state_root: [0; 32],
+                timestamp: node.get_header_by_height(1).unwrap().timestamp + 1,
+                difficulty_target: u128::MAX,
+                nonce: 0,
+                tx_root: Some(tx_root(std::slice::from_ref(&unfunded))),
+            },
+            body: vec![unfunded.clone()],
+        };
+        node.with_state_mut(|state| {
+            block.header.state_root =
+                state.state_root_after(&block, &node.config, &node.runtime())?;
+            state.execute_block(&block, &node.config)
+        })
+        .unwrap();
+        let result = status(&node, unfunded.hash()).await;
+        assert_eq!(result["status"], "failed");
+        assert_eq!(result["height"], 2);
+        assert!(result["error"]
+            .as_str()
+            .unwrap()
+            .contains("insufficient balance"));
+    }
+
+    #[tokio::test]
+    async fn test_get_asset_balance() {
+        // Roughly half of all nonces meet this target
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX >> 1;
+        config.genesis.archons[0].address = account(1);
+        let node = Arc::new(Node::in_memory(config).unwrap());
+        let holder = hex::encode([2u8; 32]);
+
+        let calls = [
+            (
+                "register_asset",
+                json!({ "asset_id": "wETH", "name": "Wrapped Ether", "decimals": 18 }),
+            ),
+            (
+                "mint_asset",
+                json!({ "asset_id": "wETH", "to": holder, "amount": 750 }),
+            ),
+        ];
+        for (nonce, (call_id, params)) in calls.into_iter().enumerate() {
+            let resp = call(
+                &node,
+                "cgt_sendTransaction",
+                sign_json(json!({
+                    "from": hex::encode(account(1)),
+                    "nonce": nonce,
+                    "module_id": "assets",
+                    "call_id": call_id,
+                    "params": params,
+                })),
+            )
+            .await;
+            assert!(resp.error.is_none(), "{:?}", resp.error);
+        }
+        mine_block(&node).await;
+
+        let resp = call(
+            &node,
+            "cgt_getAssetBalance",
+            json!({ "asset_id": "wETH", "address": holder }),
+        )
+        .await;
+        let result = resp.result.unwrap();
+        assert_eq!(result["balance"], 750);
+        assert_eq!(result["decimals"], 18);
+        // The CGT balance is unaffected
+        let resp = call(&node, "cgt_getBalance", json!({ "address": holder })).await;
+        assert_eq!(resp.result.unwrap()["balance"], 0);
+
+        let resp = call(
+            &node,
+            "cgt_getAssetBalance",
+            json!({ "asset_id": "wBTC", "address": holder }),
+        )
+        .await;
+        assert_eq!(resp.result, Some(Value::Null));
+        let resp = call(
+            &node,
+            "cgt_getAssetBalance",
+            json!({ "asset_id": "wETH", "address": "nope" }),
+        )
+        .await;
+        assert_eq!(resp.error.unwrap().code, -32602);
     }
 }
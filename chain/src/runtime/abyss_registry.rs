@@ -1,25 +1,64 @@
 //! Abyss registry module for NFT marketplace listings and CGT-based sales with royalties.
 //!
 //! This module handles:
-//! - Creating and canceling NFT listings
+//! - Creating, repricing, and canceling NFT listings
+//! - Private listings that only one allowlisted buyer may buy
+//! - Bundle listings selling several NFTs as one lot for a single price
 //! - Purchasing NFTs with CGT
-//! - Royalty distribution to creators
+//! - Royalty distribution to creators, with each recipient's lifetime
+//!   royalties accumulated (`get_royalty_stats`), a `royalty_paid` event per
+//!   payout, and each payout indexed under its recipient
+//!   (`get_royalty_payouts`)
+//! - A governed marketplace fee, burned from each sale
+//!
+//! A listed NFT is held by the module's account (`listing_escrow_account`)
+//! until the listing closes: canceling returns it to the seller and buying
+//! hands it to the buyer. Since the seller no longer owns it, ordinary
+//! ownership checks keep them from transferring it away after a buyer pays.
+//! Listings created before listed NFTs were escrowed move their NFT into the
+//! account the first time they are repriced or bought. A bundle escrows all
+//! of its NFTs when it is created and hands them all over when it closes.
 
 use serde::{Deserialize, Serialize};
 
-use super::bank_cgt::{get_balance_cgt, set_balance_for_module};
-use super::nft_dgen::{get_nft, NftDgenModule, NftId, TransferNftParams};
-use super::RuntimeModule;
+use super::id_index;
+use super::module_api::{bank, governance, nft};
+use super::nft_dgen::NftId;
+use super::storage_value::raise_u64;
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::DEFAULT_MARKETPLACE_FEE_BPS;
+use crate::core::codec;
+use crate::core::receipt::Event;
 use crate::core::state::State;
-use crate::core::transaction::{Address, Transaction};
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
 
-const PREFIX_LISTING: &[u8] = b"abyss:listing:";
-const KEY_LISTING_COUNTER: &[u8] = b"abyss:listing:counter";
+const MODULE_ID: &str = "abyss_registry";
+const PREFIX_LISTING: &str = "listing";
+const PREFIX_LISTING_COUNTER: &str = "counter";
+const PREFIX_SELLER_LISTINGS: &str = "seller";
+const PREFIX_BUNDLE: &str = "bundle";
+const PREFIX_SELLER_BUNDLES: &str = "seller_bundle";
+const PREFIX_ROYALTIES: &str = "royalties";
+const PREFIX_ROYALTY_PAYOUTS: &str = "royalty_payout";
+const STORAGE_PREFIXES: &[&str] = &[
+    PREFIX_LISTING,
+    PREFIX_LISTING_COUNTER,
+    PREFIX_SELLER_LISTINGS,
+    PREFIX_BUNDLE,
+    PREFIX_SELLER_BUNDLES,
+    PREFIX_ROYALTIES,
+    PREFIX_ROYALTY_PAYOUTS,
+];
 
-/// Listing ID type
+/// Listing ID type; bundle listings take their ids from the same sequence.
 pub type ListingId = u64;
 
+/// Most NFTs one bundle listing may hold.
+pub const MAX_BUNDLE_TOKENS: usize = 32;
+
 /// Marketplace listing
+///
+/// Stored versioned (see `codec::Versioned`); this is layout version 3.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Listing {
     pub id: ListingId,
@@ -27,6 +66,114 @@ pub struct Listing {
     pub seller: Address,
     pub price_cgt: u64,
     pub active: bool,
+    /// The only address that may buy a private listing (added in version 2)
+    pub allowed_buyer: Option<Address>,
+    /// Whether `listing_escrow_account` holds the NFT for this listing
+    /// (added in version 3)
+    pub escrowed: bool,
+}
+
+/// Version 2 listing layout.
+#[derive(Deserialize)]
+struct ListingV2 {
+    id: ListingId,
+    token_id: NftId,
+    seller: Address,
+    price_cgt: u64,
+    active: bool,
+    allowed_buyer: Option<Address>,
+}
+
+impl From<ListingV2> for Listing {
+    fn from(v2: ListingV2) -> Self {
+        Self {
+            id: v2.id,
+            token_id: v2.token_id,
+            seller: v2.seller,
+            price_cgt: v2.price_cgt,
+            active: v2.active,
+            allowed_buyer: v2.allowed_buyer,
+            escrowed: false,
+        }
+    }
+}
+
+/// Version 1 listing layout, stored without a version prefix.
+#[derive(Deserialize)]
+struct ListingV1 {
+    id: ListingId,
+    token_id: NftId,
+    seller: Address,
+    price_cgt: u64,
+    active: bool,
+}
+
+impl From<ListingV1> for ListingV2 {
+    fn from(v1: ListingV1) -> Self {
+        Self {
+            id: v1.id,
+            token_id: v1.token_id,
+            seller: v1.seller,
+            price_cgt: v1.price_cgt,
+            active: v1.active,
+            allowed_buyer: None,
+        }
+    }
+}
+
+impl codec::Versioned for Listing {
+    const VERSION: u8 = 3;
+
+    fn upgrade(version: Option<u8>, body: &[u8]) -> bincode::Result<Self> {
+        match version {
+            None | Some(1) => codec::decode_state_exact::<ListingV1>(body)
+                .map(ListingV2::from)
+                .map(Into::into),
+            Some(2) => codec::decode_state_exact::<ListingV2>(body).map(Into::into),
+            Some(other) => Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "no upgrade from listing version {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Bundle listing: several NFTs sold together for one price
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BundleListing {
+    pub id: ListingId,
+    pub token_ids: Vec<NftId>,
+    /// Each NFT's part of the price, in `token_ids` order; sums to
+    /// `price_cgt`. The royalty of each NFT is paid out of its part.
+    pub allocation: Vec<u64>,
+    pub seller: Address,
+    pub price_cgt: u64,
+    pub active: bool,
+    /// The only address that may buy a private bundle
+    pub allowed_buyer: Option<Address>,
+}
+
+/// Royalties one address has received from marketplace sales
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct RoyaltyStats {
+    /// CGT base units received, in total
+    pub total_amount: u64,
+    /// NFT sales that paid it a royalty; each NFT of a bundle is one sale
+    pub sale_count: u64,
+    /// Height of the block with the latest of those sales
+    pub last_height: u64,
+}
+
+/// One royalty payout, indexed under its recipient by its place in their
+/// `RoyaltyStats::sale_count`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RoyaltyPayout {
+    /// Height of the block with the sale
+    pub height: u64,
+    /// Canonical hash of the buying transaction
+    pub tx_hash: [u8; 32],
+    /// The `royalty_paid` event emitted for the payout
+    pub event: Event,
 }
 
 /// Create listing parameters
@@ -34,6 +181,22 @@ pub struct Listing {
 pub struct CreateListingParams {
     pub token_id: NftId,
     pub price_cgt: u64,
+    /// Make the listing private to this buyer; `None` lists publicly.
+    pub allowed_buyer: Option<Address>,
+}
+
+/// Create listing parameters as encoded before private listings.
+#[derive(Deserialize)]
+struct CreateListingParamsV1 {
+    token_id: NftId,
+    price_cgt: u64,
+}
+
+/// Update listing price parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateListingPriceParams {
+    pub listing_id: ListingId,
+    pub price_cgt: u64,
 }
 
 /// Cancel listing parameters
@@ -48,22 +211,188 @@ pub struct BuyListingParams {
     pub listing_id: ListingId,
 }
 
+/// Create bundle listing parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateBundleListingParams {
+    pub token_ids: Vec<NftId>,
+    pub price_cgt: u64,
+    /// Each NFT's part of the price; empty splits it evenly.
+    pub allocation: Vec<u64>,
+    /// Make the bundle private to this buyer; `None` lists publicly.
+    pub allowed_buyer: Option<Address>,
+}
+
+/// Cancel bundle listing parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelBundleListingParams {
+    pub bundle_id: ListingId,
+}
+
+/// Buy bundle listing parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuyBundleListingParams {
+    pub bundle_id: ListingId,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "create_listing",
+        CallSchema::new(&[
+            FieldSchema::new("token_id", FieldType::U64),
+            FieldSchema::new("price_cgt", FieldType::U64),
+            FieldSchema::new("allowed_buyer", FieldType::Option(&FieldType::Address)),
+        ]),
+    ),
+    (
+        "update_listing_price",
+        CallSchema::new(&[
+            FieldSchema::new("listing_id", FieldType::U64),
+            FieldSchema::new("price_cgt", FieldType::U64),
+        ]),
+    ),
+    (
+        "cancel_listing",
+        CallSchema::new(&[FieldSchema::new("listing_id", FieldType::U64)]),
+    ),
+    (
+        "buy_listing",
+        CallSchema::new(&[FieldSchema::new("listing_id", FieldType::U64)]),
+    ),
+    (
+        "create_bundle_listing",
+        CallSchema::new(&[
+            FieldSchema::new("token_ids", FieldType::List(&FieldType::U64)),
+            FieldSchema::new("price_cgt", FieldType::U64),
+            FieldSchema::new("allocation", FieldType::List(&FieldType::U64)),
+            FieldSchema::new("allowed_buyer", FieldType::Option(&FieldType::Address)),
+        ]),
+    ),
+    (
+        "cancel_bundle_listing",
+        CallSchema::new(&[FieldSchema::new("bundle_id", FieldType::U64)]),
+    ),
+    (
+        "buy_bundle_listing",
+        CallSchema::new(&[FieldSchema::new("bundle_id", FieldType::U64)]),
+    ),
+];
+
+/// How a purchase's price is split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurchaseBreakdown {
+    pub price: u64,
+    /// Royalty paid in total; 0 without a recipient.
+    pub royalty_amount: u64,
+    /// Each royalty recipient's part of `royalty_amount`, in the NFT's order.
+    pub royalties: Vec<(Address, u64)>,
+    pub seller_amount: u64,
+    /// Marketplace fee, burned.
+    pub protocol_fee: u64,
+}
+
+/// Split a listing's price as buying it would now: the marketplace fee
+/// (`abyss.marketplace_fee_bps`) is taken first, then the NFT's royalty out
+/// of what is left, and the seller gets the rest.
+///
+/// A royalty split between several recipients (`nft::royalty_shares`) is
+/// shared out in proportion to their basis points, rounding down; what the
+/// rounding leaves goes to the first recipient.
+///
+/// # Returns
+/// - `Ok(PurchaseBreakdown)` whose parts sum to the price
+/// - `Err` if the listed NFT no longer exists
+pub fn purchase_breakdown(state: &State, listing: &Listing) -> Result<PurchaseBreakdown, String> {
+    token_breakdown(state, listing.token_id, listing.price_cgt)
+}
+
+/// Split a bundle's price as buying it would now: each NFT's part of the
+/// price (`BundleListing::allocation`) is split as `purchase_breakdown`
+/// splits a listing of that NFT alone.
+///
+/// # Returns
+/// - `Ok` with one breakdown per NFT, in bundle order; together they sum to
+///   the price
+/// - `Err` if a bundled NFT no longer exists
+pub fn bundle_breakdown(
+    state: &State,
+    bundle: &BundleListing,
+) -> Result<Vec<PurchaseBreakdown>, String> {
+    bundle
+        .token_ids
+        .iter()
+        .zip(&bundle.allocation)
+        .map(|(token_id, price)| token_breakdown(state, *token_id, *price))
+        .collect()
+}
+
+/// Split `price` paid for `token_id` into fee, royalties, and seller amount.
+fn token_breakdown(
+    state: &State,
+    token_id: NftId,
+    price: u64,
+) -> Result<PurchaseBreakdown, String> {
+    let nft = nft::metadata(state, token_id).ok_or_else(|| "NFT not found".to_string())?;
+
+    let shares = nft::royalty_shares(&nft);
+    let royalty_bps: u64 = shares.iter().map(|(_, bps)| u64::from(*bps)).sum();
+    let royalty_amount = ((price as u128).saturating_mul(royalty_bps as u128) / 10_000) as u64;
+
+    let fee_bps = governance::param(state, governance::PARAM_MARKETPLACE_FEE_BPS)
+        .unwrap_or(DEFAULT_MARKETPLACE_FEE_BPS);
+    let protocol_fee = ((price as u128).saturating_mul(fee_bps as u128) / 10_000) as u64;
+    let protocol_fee = protocol_fee.min(price);
+    let royalty_amount = royalty_amount.min(price - protocol_fee);
+
+    let mut royalties: Vec<(Address, u64)> = shares
+        .iter()
+        .map(|(recipient, bps)| {
+            let part = (royalty_amount as u128) * u128::from(*bps) / u128::from(royalty_bps);
+            (*recipient, part as u64)
+        })
+        .collect();
+    let shared: u64 = royalties.iter().map(|(_, part)| part).sum();
+    if let Some((_, first)) = royalties.first_mut() {
+        *first += royalty_amount - shared;
+    }
+
+    Ok(PurchaseBreakdown {
+        price,
+        royalty_amount,
+        royalties,
+        seller_amount: price - protocol_fee - royalty_amount,
+        protocol_fee,
+    })
+}
+
 /// Helper functions for listing management
 
 fn listing_key(id: ListingId) -> Vec<u8> {
-    let mut key = Vec::from(PREFIX_LISTING);
-    key.extend_from_slice(&id.to_be_bytes());
-    key
+    StorageKey::new(MODULE_ID, PREFIX_LISTING)
+        .u64(id)
+        .into_bytes()
+}
+
+fn listing_counter_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_LISTING_COUNTER).into_bytes()
+}
+
+/// Prefix of a seller's escrowed listings, one key per listing (see
+/// `id_index`).
+fn seller_listings_key(seller: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_SELLER_LISTINGS)
+        .address(seller)
+        .into_bytes()
 }
 
 fn load_listing(state: &State, id: ListingId) -> Option<Listing> {
     state
         .get_raw(&listing_key(id))
-        .and_then(|bytes| bincode::deserialize::<Listing>(&bytes).ok())
+        .and_then(|bytes| codec::decode_versioned::<Listing>(&bytes).ok())
 }
 
 fn store_listing(state: &mut State, listing: &Listing) -> Result<(), String> {
-    let bytes = bincode::serialize(listing).map_err(|e| e.to_string())?;
+    let bytes = codec::encode_versioned(listing).map_err(|e| e.to_string())?;
     state
         .put_raw(listing_key(listing.id), bytes)
         .map_err(|e| e.to_string())
@@ -71,24 +400,246 @@ fn store_listing(state: &mut State, listing: &Listing) -> Result<(), String> {
 
 fn get_next_listing_id(state: &State) -> ListingId {
     state
-        .get_raw(KEY_LISTING_COUNTER)
-        .and_then(|bytes| bincode::deserialize::<ListingId>(&bytes).ok())
+        .get_raw(&listing_counter_key())
+        .and_then(|bytes| codec::decode_state::<ListingId>(&bytes).ok())
         .unwrap_or(0)
 }
 
 fn set_next_listing_id(state: &mut State, next: ListingId) -> Result<(), String> {
-    let bytes = bincode::serialize(&next).map_err(|e| e.to_string())?;
+    let bytes = codec::encode(&next).map_err(|e| e.to_string())?;
     state
-        .put_raw(KEY_LISTING_COUNTER.to_vec(), bytes)
+        .put_raw(listing_counter_key(), bytes)
         .map_err(|e| e.to_string())
 }
 
+fn load_seller_listings(state: &State, seller: &Address) -> Vec<ListingId> {
+    id_index::ids(state, &seller_listings_key(seller))
+}
+
+fn bundle_key(id: ListingId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_BUNDLE)
+        .u64(id)
+        .into_bytes()
+}
+
+fn seller_bundles_key(seller: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_SELLER_BUNDLES)
+        .address(seller)
+        .into_bytes()
+}
+
+fn royalties_key(recipient: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ROYALTIES)
+        .address(recipient)
+        .into_bytes()
+}
+
+fn royalty_payout_key(recipient: &Address, seq: u64) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ROYALTY_PAYOUTS)
+        .address(recipient)
+        .u64(seq)
+        .into_bytes()
+}
+
+fn load_bundle(state: &State, id: ListingId) -> Option<BundleListing> {
+    state
+        .get_raw(&bundle_key(id))
+        .and_then(|bytes| codec::decode_state::<BundleListing>(&bytes).ok())
+}
+
+fn store_bundle(state: &mut State, bundle: &BundleListing) -> Result<(), String> {
+    let bytes = codec::encode(bundle).map_err(|e| e.to_string())?;
+    state
+        .put_raw(bundle_key(bundle.id), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn load_seller_bundles(state: &State, seller: &Address) -> Vec<ListingId> {
+    state
+        .get_raw(&seller_bundles_key(seller))
+        .and_then(|bytes| codec::decode_state::<Vec<ListingId>>(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn store_seller_bundles(
+    state: &mut State,
+    seller: &Address,
+    ids: &[ListingId],
+) -> Result<(), String> {
+    if ids.is_empty() {
+        return state
+            .delete_raw(&seller_bundles_key(seller))
+            .map_err(|e| e.to_string());
+    }
+    let bytes = codec::encode(ids).map_err(|e| e.to_string())?;
+    state
+        .put_raw(seller_bundles_key(seller), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Take the next id of the listing sequence.
+fn allocate_listing_id(state: &mut State) -> Result<ListingId, String> {
+    let id = get_next_listing_id(state);
+    let next = id.checked_add(1).ok_or("listing id overflow")?;
+    set_next_listing_id(state, next)?;
+    Ok(id)
+}
+
+/// Account holding the NFTs of active listings (see `bank::module_address`).
+pub fn listing_escrow_account() -> Address {
+    bank::module_address(MODULE_ID)
+}
+
+/// Move a listing's NFT from its seller into `listing_escrow_account`.
+fn escrow_nft(state: &mut State, listing: &mut Listing) -> Result<(), String> {
+    nft::force_transfer(state, listing.token_id, &listing_escrow_account())?;
+    id_index::insert(state, &seller_listings_key(&listing.seller), listing.id)?;
+    listing.escrowed = true;
+    Ok(())
+}
+
+/// Escrow the NFT of a listing created before listed NFTs were escrowed.
+///
+/// # Returns
+/// - `Ok(())` once the NFT is escrowed, including if it already was
+/// - `Err` if the seller no longer owns the NFT, so the listing is stale
+fn ensure_escrowed(state: &mut State, listing: &mut Listing) -> Result<(), String> {
+    if listing.escrowed {
+        return Ok(());
+    }
+    let nft = nft::metadata(state, listing.token_id).ok_or_else(|| "NFT not found".to_string())?;
+    if nft.owner != listing.seller {
+        return Err("listing seller is no longer NFT owner".into());
+    }
+    escrow_nft(state, listing)
+}
+
+/// Deactivate a listing, handing its escrowed NFT to `to`.
+fn close_listing(state: &mut State, listing: &mut Listing, to: &Address) -> Result<(), String> {
+    if listing.escrowed {
+        nft::force_transfer(state, listing.token_id, to)?;
+        id_index::remove(state, &seller_listings_key(&listing.seller), listing.id)?;
+        listing.escrowed = false;
+    }
+    listing.active = false;
+    store_listing(state, listing)
+}
+
 /// Public helper for querying listing (for RPC/SDK use).
 pub fn get_listing(state: &State, id: ListingId) -> Option<Listing> {
     load_listing(state, id)
 }
 
+/// Active listings of `seller` whose NFTs are escrowed, in listing order
+/// (for RPC/SDK use).
+pub fn get_escrowed_listings(state: &State, seller: &Address) -> Vec<Listing> {
+    load_seller_listings(state, seller)
+        .into_iter()
+        .filter_map(|id| load_listing(state, id))
+        .collect()
+}
+
+/// Public helper for querying a bundle listing (for RPC/SDK use).
+pub fn get_bundle_listing(state: &State, id: ListingId) -> Option<BundleListing> {
+    load_bundle(state, id)
+}
+
+/// Active bundle listings of `seller`, in listing order (for RPC/SDK use).
+pub fn get_active_bundles(state: &State, seller: &Address) -> Vec<BundleListing> {
+    load_seller_bundles(state, seller)
+        .into_iter()
+        .filter_map(|id| load_bundle(state, id))
+        .collect()
+}
+
+/// Lifetime royalties `recipient` has received from marketplace sales; all
+/// zero if it never received one (for RPC/SDK use).
+pub fn get_royalty_stats(state: &State, recipient: &Address) -> RoyaltyStats {
+    state
+        .get_raw(&royalties_key(recipient))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// A window of `recipient`'s royalty payouts, newest first, skipping the
+/// `offset` newest (for RPC/SDK use). Payouts made before the index existed
+/// are not in it, so a window reaching back to them comes up short.
+pub fn get_royalty_payouts(
+    state: &State,
+    recipient: &Address,
+    offset: u64,
+    limit: u64,
+) -> Vec<RoyaltyPayout> {
+    let newest = get_royalty_stats(state, recipient)
+        .sale_count
+        .saturating_sub(offset);
+    (newest.saturating_sub(limit)..newest)
+        .rev()
+        .filter_map(|seq| state.get_raw(&royalty_payout_key(recipient, seq)))
+        .filter_map(|bytes| codec::decode_state(&bytes).ok())
+        .collect()
+}
+
+/// Burn a sale's marketplace fee out of the price already debited from the
+/// buyer, taking it out of the total supply. It passes through the module's
+/// account, so the buyer is debited once, for the whole price.
+fn burn_fee(state: &mut State, fee: u64) -> Result<(), String> {
+    if fee == 0 {
+        return Ok(());
+    }
+    let account = listing_escrow_account();
+    bank::credit(state, &account, fee)?;
+    bank::burn(state, &account, fee)?;
+    Ok(())
+}
+
+/// Pay one royalty of the sale of `token_id`, adding it to the recipient's
+/// `RoyaltyStats`, emitting `royalty_paid`, and indexing the payout. `sale` names the listing or
+/// bundle sold, e.g. `("listing_id", 4)`.
+fn pay_royalty(
+    state: &mut State,
+    recipient: &Address,
+    amount: u64,
+    token_id: NftId,
+    sale: (&str, ListingId),
+) -> Result<(), String> {
+    if amount == 0 {
+        return Ok(());
+    }
+    bank::credit(state, recipient, amount)?;
+
+    let mut stats = get_royalty_stats(state, recipient);
+    stats.total_amount = stats
+        .total_amount
+        .checked_add(amount)
+        .ok_or("royalty total overflow")?;
+    let seq = stats.sale_count;
+    stats.sale_count += 1;
+    stats.last_height = state.block_height();
+    let bytes = codec::encode(&stats).map_err(|e| e.to_string())?;
+    state
+        .put_raw(royalties_key(recipient), bytes)
+        .map_err(|e| e.to_string())?;
+
+    let event = Event::new(MODULE_ID, "royalty_paid", *recipient)
+        .with("token_id", token_id)
+        .with(sale.0, sale.1)
+        .with("amount", amount);
+    let payout = RoyaltyPayout {
+        height: stats.last_height,
+        tx_hash: state.tx_hash().unwrap_or_default(),
+        event: event.clone(),
+    };
+    let bytes = codec::encode(&payout).map_err(|e| e.to_string())?;
+    state
+        .put_raw(royalty_payout_key(recipient, seq), bytes)
+        .map_err(|e| e.to_string())?;
+    state.emit_event(event);
+    Ok(())
+}
+
 /// AbyssRegistryModule handles marketplace operations
+#[derive(Default)]
 pub struct AbyssRegistryModule;
 
 impl AbyssRegistryModule {
@@ -99,25 +650,88 @@ impl AbyssRegistryModule {
 
 impl RuntimeModule for AbyssRegistryModule {
     fn module_id(&self) -> &'static str {
-        "abyss_registry"
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &[
+            "create_listing",
+            "update_listing_price",
+            "cancel_listing",
+            "buy_listing",
+            "create_bundle_listing",
+            "cancel_bundle_listing",
+            "buy_bundle_listing",
+        ]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
     }
 
     fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
         match call_id {
             "create_listing" => handle_create_listing(tx, state),
+            "update_listing_price" => handle_update_listing_price(tx, state),
             "cancel_listing" => handle_cancel_listing(tx, state),
             "buy_listing" => handle_buy_listing(tx, state),
+            "create_bundle_listing" => handle_create_bundle_listing(tx, state),
+            "cancel_bundle_listing" => handle_cancel_bundle_listing(tx, state),
+            "buy_bundle_listing" => handle_buy_bundle_listing(tx, state),
             other => Err(format!("abyss_registry: unknown call_id '{}'", other)),
         }
     }
+
+    /// Besides the listing counter, a recipient's royalty stats number their
+    /// payouts (`royalty_payout_key`), so the stats that counted more sales
+    /// are kept and the local ones never go back.
+    fn reconcile_import(
+        &self,
+        key: &[u8],
+        local: Option<&[u8]>,
+        imported: &[u8],
+    ) -> Result<Option<Vec<u8>>, String> {
+        if *key == listing_counter_key() {
+            return raise_u64(local, imported).map(Some);
+        }
+        if !key.starts_with(&StorageKey::new(MODULE_ID, PREFIX_ROYALTIES).into_bytes()) {
+            return Ok(None);
+        }
+        let imported_stats: RoyaltyStats =
+            codec::decode_state(imported).map_err(|e| e.to_string())?;
+        let local_stats = local.and_then(|bytes| codec::decode_state::<RoyaltyStats>(bytes).ok());
+        Ok(Some(match (local, local_stats) {
+            (Some(local), Some(stats)) if stats.sale_count >= imported_stats.sale_count => {
+                local.to_vec()
+            }
+            _ => imported.to_vec(),
+        }))
+    }
+}
+
+/// Decode `create_listing` params, including payloads from before private
+/// listings, which end after the price.
+fn decode_create_listing(payload: &[u8]) -> Result<CreateListingParams, String> {
+    codec::decode_exact(payload).or_else(|e| {
+        codec::decode_exact::<CreateListingParamsV1>(payload)
+            .map(|v1| CreateListingParams {
+                token_id: v1.token_id,
+                price_cgt: v1.price_cgt,
+                allowed_buyer: None,
+            })
+            .map_err(|_| e.to_string())
+    })
 }
 
 fn handle_create_listing(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    let params: CreateListingParams =
-        bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params = decode_create_listing(&tx.payload)?;
 
     // Ensure NFT exists and tx.from is current owner
-    let nft = get_nft(state, params.token_id).ok_or_else(|| "NFT not found".to_string())?;
+    let nft = nft::metadata(state, params.token_id).ok_or_else(|| "NFT not found".to_string())?;
 
     if nft.owner != tx.from {
         return Err("only the NFT owner may create a listing".into());
@@ -127,19 +741,45 @@ fn handle_create_listing(tx: &Transaction, state: &mut State) -> Result<(), Stri
         return Err("price must be > 0".into());
     }
 
-    let mut next_id = get_next_listing_id(state);
-    let id = next_id;
-    next_id = next_id.checked_add(1).ok_or("listing id overflow")?;
-    set_next_listing_id(state, next_id)?;
+    let id = allocate_listing_id(state)?;
 
-    let listing = Listing {
+    let mut listing = Listing {
         id,
         token_id: params.token_id,
         seller: tx.from,
         price_cgt: params.price_cgt,
         active: true,
+        allowed_buyer: params.allowed_buyer,
+        escrowed: false,
     };
 
+    escrow_nft(state, &mut listing)?;
+    store_listing(state, &listing)?;
+
+    Ok(())
+}
+
+fn handle_update_listing_price(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: UpdateListingPriceParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let mut listing =
+        load_listing(state, params.listing_id).ok_or_else(|| "Listing not found".to_string())?;
+
+    if listing.seller != tx.from {
+        return Err("only the listing seller may change its price".into());
+    }
+
+    if !listing.active {
+        return Err("listing is not active".into());
+    }
+
+    if params.price_cgt == 0 {
+        return Err("price must be > 0".into());
+    }
+
+    ensure_escrowed(state, &mut listing)?;
+    listing.price_cgt = params.price_cgt;
     store_listing(state, &listing)?;
 
     Ok(())
@@ -147,7 +787,7 @@ fn handle_create_listing(tx: &Transaction, state: &mut State) -> Result<(), Stri
 
 fn handle_cancel_listing(tx: &Transaction, state: &mut State) -> Result<(), String> {
     let params: CancelListingParams =
-        bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
     let mut listing =
         load_listing(state, params.listing_id).ok_or_else(|| "Listing not found".to_string())?;
@@ -160,14 +800,15 @@ fn handle_cancel_listing(tx: &Transaction, state: &mut State) -> Result<(), Stri
         return Err("listing is already inactive".into());
     }
 
-    listing.active = false;
-    store_listing(state, &listing)?;
+    // A listing from before escrow never took the NFT, so there is nothing to return
+    let seller = listing.seller;
+    close_listing(state, &mut listing, &seller)?;
 
     Ok(())
 }
 
 fn handle_buy_listing(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    let params: BuyListingParams = bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params: BuyListingParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
     let mut listing =
         load_listing(state, params.listing_id).ok_or_else(|| "Listing not found".to_string())?;
@@ -176,92 +817,957 @@ fn handle_buy_listing(tx: &Transaction, state: &mut State) -> Result<(), String>
         return Err("listing is not active".into());
     }
 
+    if listing
+        .allowed_buyer
+        .is_some_and(|allowed| allowed != tx.from)
+    {
+        return Err("listing is reserved for another buyer".into());
+    }
+
     let buyer = tx.from;
     let seller = listing.seller;
     let price = listing.price_cgt;
 
-    // Load NFT & metadata for royalties
-    let nft = get_nft(state, listing.token_id).ok_or_else(|| "NFT not found".to_string())?;
+    ensure_escrowed(state, &mut listing)?;
 
-    if nft.owner != seller {
-        // listing is stale; can't sell it
-        listing.active = false;
-        store_listing(state, &listing)?;
-        return Err("listing seller is no longer NFT owner".into());
-    }
+    let split = purchase_breakdown(state, &listing)?;
 
-    let buyer_balance = get_balance_cgt(state, &buyer);
-    if buyer_balance < price {
+    if bank::balance(state, &buyer) < price {
         return Err("buyer has insufficient CGT".into());
     }
 
-    // Very simple royalty logic:
-    // - if royalty_recipient and royalty_bps are set:
-    //     royalty_amount = price * royalty_bps / 10_000
-    // - seller gets the rest.
+    // Debit buyer, credit seller and royalty recipients, and burn the fee
+    bank::debit(state, &buyer, price)?;
+    bank::credit(state, &seller, split.seller_amount)?;
+    burn_fee(state, split.protocol_fee)?;
+    for (recipient, amount) in &split.royalties {
+        let sale = ("listing_id", listing.id);
+        pay_royalty(state, recipient, *amount, listing.token_id, sale)?;
+    }
+
+    // Hand the escrowed NFT to the buyer and mark the listing inactive
+    close_listing(state, &mut listing, &buyer)?;
+
+    state.emit_event(
+        Event::new(MODULE_ID, "buy", buyer)
+            .with("listing_id", params.listing_id)
+            .with("seller", seller.to_display())
+            .with("price", price),
+    );
+    Ok(())
+}
+
+/// Check a bundle's NFTs and resolve its allocation: the given one, which
+/// must have an entry per NFT and sum to the price, or an even split whose
+/// rounding remainder goes to the first NFT.
+fn bundle_allocation(params: &CreateBundleListingParams) -> Result<Vec<u64>, String> {
+    let count = params.token_ids.len();
+    if !(2..=MAX_BUNDLE_TOKENS).contains(&count) {
+        return Err(format!(
+            "a bundle holds 2 to {} NFTs, got {}",
+            MAX_BUNDLE_TOKENS, count
+        ));
+    }
+    if let Some(duplicate) = params
+        .token_ids
+        .iter()
+        .enumerate()
+        .find_map(|(i, id)| params.token_ids[..i].contains(id).then_some(id))
+    {
+        return Err(format!("NFT {} is bundled more than once", duplicate));
+    }
+    if params.price_cgt == 0 {
+        return Err("price must be > 0".into());
+    }
+
+    if params.allocation.is_empty() {
+        let part = params.price_cgt / count as u64;
+        let mut allocation = vec![part; count];
+        allocation[0] += params.price_cgt - part * count as u64;
+        return Ok(allocation);
+    }
+    if params.allocation.len() != count {
+        return Err(format!(
+            "allocation has {} parts for {} NFTs",
+            params.allocation.len(),
+            count
+        ));
+    }
+    let total = params
+        .allocation
+        .iter()
+        .try_fold(0u64, |total, part| total.checked_add(*part));
+    if total != Some(params.price_cgt) {
+        return Err(format!(
+            "allocation must sum to the price of {}",
+            params.price_cgt
+        ));
+    }
+    Ok(params.allocation.clone())
+}
+
+fn handle_create_bundle_listing(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: CreateBundleListingParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+    let allocation = bundle_allocation(&params)?;
+
+    // An NFT that pays royalties gets at least an even share of the price,
+    // so bundling it with worthless fillers cannot shrink its royalty to
+    // nothing
+    let even_share = params.price_cgt / params.token_ids.len() as u64;
+    for (token_id, part) in params.token_ids.iter().zip(&allocation) {
+        let nft =
+            nft::metadata(state, *token_id).ok_or_else(|| format!("NFT {} not found", token_id))?;
+        if nft.owner != tx.from {
+            return Err(format!("only the NFT owner may bundle NFT {}", token_id));
+        }
+        let pays_royalties = nft::royalty_shares(&nft).iter().any(|(_, bps)| *bps > 0);
+        if pays_royalties && *part < even_share {
+            return Err(format!(
+                "NFT {} pays royalties, so its part must be at least an even share of {}, not {}",
+                token_id, even_share, part
+            ));
+        }
+    }
 
-    let (royalty_recipient, royalty_bps) = match (nft.royalty_recipient, nft.royalty_bps) {
-        (Some(addr), bps) if bps > 0 => (Some(addr), bps),
-        _ => (None, 0),
+    let bundle = BundleListing {
+        id: allocate_listing_id(state)?,
+        token_ids: params.token_ids,
+        allocation,
+        seller: tx.from,
+        price_cgt: params.price_cgt,
+        active: true,
+        allowed_buyer: params.allowed_buyer,
     };
 
-    let mut royalty_amount = 0u64;
-    if royalty_recipient.is_some() {
-        royalty_amount = (price as u128)
-            .saturating_mul(royalty_bps as u128)
-            .checked_div(10_000)
-            .unwrap_or(0) as u64;
+    // Escrow every NFT; a failure fails the whole call
+    for token_id in &bundle.token_ids {
+        nft::force_transfer(state, *token_id, &listing_escrow_account())?;
     }
+    let mut ids = load_seller_bundles(state, &bundle.seller);
+    ids.push(bundle.id);
+    store_seller_bundles(state, &bundle.seller, &ids)?;
+    store_bundle(state, &bundle)?;
 
-    if royalty_amount > price {
-        royalty_amount = price;
+    Ok(())
+}
+
+/// Deactivate a bundle, handing all of its NFTs to `to`.
+fn close_bundle(state: &mut State, bundle: &mut BundleListing, to: &Address) -> Result<(), String> {
+    for token_id in &bundle.token_ids {
+        nft::force_transfer(state, *token_id, to)?;
     }
+    let mut ids = load_seller_bundles(state, &bundle.seller);
+    ids.retain(|id| *id != bundle.id);
+    store_seller_bundles(state, &bundle.seller, &ids)?;
+    bundle.active = false;
+    store_bundle(state, bundle)
+}
 
-    let seller_amount = price - royalty_amount;
+fn handle_cancel_bundle_listing(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: CancelBundleListingParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
-    // Debit buyer
-    let new_buyer_balance = buyer_balance - price;
-    set_balance_for_module(state, &buyer, new_buyer_balance)?;
+    let mut bundle =
+        load_bundle(state, params.bundle_id).ok_or_else(|| "Bundle not found".to_string())?;
 
-    // Credit seller
-    let seller_balance = get_balance_cgt(state, &seller);
-    let new_seller_balance = seller_balance
-        .checked_add(seller_amount)
-        .ok_or("overflow crediting seller")?;
-    set_balance_for_module(state, &seller, new_seller_balance)?;
+    if bundle.seller != tx.from {
+        return Err("only the bundle seller may cancel it".into());
+    }
 
-    // Credit royalty recipient
-    if let Some(recipient) = royalty_recipient {
-        let rec_balance = get_balance_cgt(state, &recipient);
-        let new_rec_balance = rec_balance
-            .checked_add(royalty_amount)
-            .ok_or("overflow crediting royalty")?;
-        set_balance_for_module(state, &recipient, new_rec_balance)?;
+    if !bundle.active {
+        return Err("bundle is already inactive".into());
     }
 
-    // Transfer NFT ownership to buyer
-    let transfer_params = TransferNftParams {
-        token_id: listing.token_id,
-        to: buyer,
-    };
-    let transfer_payload = bincode::serialize(&transfer_params).map_err(|e| e.to_string())?;
-
-    let transfer_tx = Transaction {
-        from: seller,
-        nonce: 0, // internal/system-level transfer; nonce not used here
-        module_id: "nft_dgen".to_string(),
-        call_id: "transfer_nft".to_string(),
-        payload: transfer_payload,
-        fee: 0,
-        signature: vec![],
-    };
+    let seller = bundle.seller;
+    close_bundle(state, &mut bundle, &seller)
+}
 
-    // We call NFT transfer directly via the module, bypassing Runtime's module lookup.
-    NftDgenModule::new().dispatch("transfer_nft", &transfer_tx, state)?;
+fn handle_buy_bundle_listing(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: BuyBundleListingParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
-    // Mark listing inactive
-    listing.active = false;
-    store_listing(state, &listing)?;
+    let mut bundle =
+        load_bundle(state, params.bundle_id).ok_or_else(|| "Bundle not found".to_string())?;
+
+    if !bundle.active {
+        return Err("bundle is not active".into());
+    }
 
+    if bundle
+        .allowed_buyer
+        .is_some_and(|allowed| allowed != tx.from)
+    {
+        return Err("bundle is reserved for another buyer".into());
+    }
+
+    let buyer = tx.from;
+    let seller = bundle.seller;
+    let price = bundle.price_cgt;
+    let splits = bundle_breakdown(state, &bundle)?;
+
+    if bank::balance(state, &buyer) < price {
+        return Err("buyer has insufficient CGT".into());
+    }
+
+    // Debit the buyer once, pay each NFT's royalties out of its part,
+    // credit the seller the rest, and burn the fees. Any failed credit fails
+    // the transaction, whose writes are then all discarded
+    bank::debit(state, &buyer, price)?;
+    let seller_amount: u64 = splits.iter().map(|split| split.seller_amount).sum();
+    bank::credit(state, &seller, seller_amount)?;
+    burn_fee(state, splits.iter().map(|split| split.protocol_fee).sum())?;
+    for (token_id, split) in bundle.token_ids.iter().zip(&splits) {
+        for (recipient, amount) in &split.royalties {
+            pay_royalty(
+                state,
+                recipient,
+                *amount,
+                *token_id,
+                ("bundle_id", bundle.id),
+            )?;
+        }
+    }
+
+    close_bundle(state, &mut bundle, &buyer)?;
+
+    state.emit_event(
+        Event::new(MODULE_ID, "buy_bundle", buyer)
+            .with("bundle_id", params.bundle_id)
+            .with("seller", seller.to_display())
+            .with("price", price)
+            .with("tokens", bundle.token_ids.len() as u64),
+    );
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::runtime::nft_dgen::{check_owner_index, MintDgenParams, TransferNftParams};
+    use crate::runtime::Runtime;
+
+    fn seller() -> Address {
+        account(1)
+    }
+
+    fn buyer() -> Address {
+        account(2)
+    }
+
+    fn tx(from: Address, module_id: &str, call_id: &str, payload: Vec<u8>) -> Transaction {
+        signed(Transaction {
+            from,
+            nonce: 0,
+            module_id: module_id.to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        })
+    }
+
+    fn call(runtime: &Runtime, state: &mut State, from: Address, call_id: &str, payload: Vec<u8>) {
+        runtime
+            .dispatch_tx(&tx(from, MODULE_ID, call_id, payload), state)
+            .unwrap();
+    }
+
+    fn mint_nft(state: &mut State) -> NftId {
+        let params = MintDgenParams {
+            fabric_root_hash: [7; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
+        };
+        nft::mint(state, &seller(), &params).unwrap()
+    }
+
+    fn create_listing(runtime: &Runtime, state: &mut State, token_id: NftId) {
+        let payload = codec::encode(&CreateListingParams {
+            token_id,
+            price_cgt: 1_000,
+            allowed_buyer: None,
+        })
+        .unwrap();
+        call(runtime, state, seller(), "create_listing", payload);
+    }
+
+    fn owner(state: &State, token_id: NftId) -> Address {
+        nft::metadata(state, token_id).unwrap().owner
+    }
+
+    fn mint_with_royalty(
+        state: &mut State,
+        royalty_recipient: Option<Address>,
+        royalty_bps: u16,
+        royalty_splits: Vec<(Address, u16)>,
+    ) -> NftId {
+        let params = MintDgenParams {
+            fabric_root_hash: [7; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient,
+            royalty_bps,
+            uri_hint: None,
+            royalty_splits,
+        };
+        nft::mint(state, &seller(), &params).unwrap()
+    }
+
+    fn set_param(state: &mut State, key: &str, value: u64) {
+        let key = StorageKey::new("governance", "param")
+            .bytes(key.as_bytes())
+            .into_bytes();
+        state.put_raw(key, codec::encode(&value).unwrap()).unwrap();
+    }
+
+    fn bundle_payload(token_ids: &[NftId], price_cgt: u64, allocation: &[u64]) -> Vec<u8> {
+        codec::encode(&CreateBundleListingParams {
+            token_ids: token_ids.to_vec(),
+            price_cgt,
+            allocation: allocation.to_vec(),
+            allowed_buyer: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_listed_nft_is_escrowed_until_closed() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let token_id = mint_nft(&mut state);
+        bank::mint(&mut state, &buyer(), 5_000).unwrap();
+
+        create_listing(&runtime, &mut state, token_id);
+        assert_eq!(owner(&state, token_id), listing_escrow_account());
+        let listed = get_escrowed_listings(&state, &seller());
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].escrowed);
+
+        // The seller can neither transfer the listed NFT nor list it twice
+        let transfer = codec::encode(&TransferNftParams {
+            token_id,
+            to: buyer(),
+        })
+        .unwrap();
+        let err = runtime
+            .dispatch_tx(
+                &tx(seller(), "nft_dgen", "transfer_nft", transfer),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("only the current owner"), "{}", err);
+        let err = runtime
+            .dispatch_tx(
+                &tx(
+                    seller(),
+                    MODULE_ID,
+                    "create_listing",
+                    codec::encode(&(token_id, 1_000u64, None::<Address>)).unwrap(),
+                ),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("only the NFT owner"), "{}", err);
+
+        // Canceling returns the NFT
+        let cancel = codec::encode(&CancelListingParams { listing_id: 0 }).unwrap();
+        call(&runtime, &mut state, seller(), "cancel_listing", cancel);
+        assert_eq!(owner(&state, token_id), seller());
+        assert!(get_escrowed_listings(&state, &seller()).is_empty());
+        assert!(!get_listing(&state, 0).unwrap().escrowed);
+
+        // Buying hands it to the buyer
+        create_listing(&runtime, &mut state, token_id);
+        let buy = codec::encode(&BuyListingParams { listing_id: 1 }).unwrap();
+        call(&runtime, &mut state, buyer(), "buy_listing", buy);
+        assert_eq!(owner(&state, token_id), buyer());
+        assert!(!get_listing(&state, 1).unwrap().active);
+        assert!(get_escrowed_listings(&state, &seller()).is_empty());
+        assert_eq!(bank::balance(&state, &seller()), 1_000);
+        assert!(check_owner_index(&state).is_empty());
+    }
+
+    #[test]
+    fn test_purchase_breakdown_sums_to_price() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let fee_key = StorageKey::new("governance", "param")
+            .bytes(governance::PARAM_MARKETPLACE_FEE_BPS.as_bytes())
+            .into_bytes();
+        state
+            .put_raw(fee_key, codec::encode(&100u64).unwrap())
+            .unwrap();
+        let creator = [5; 32];
+        let mut mint_with_royalty = |royalty_recipient| {
+            let params = MintDgenParams {
+                fabric_root_hash: [7; 32],
+                forge_model_id: None,
+                forge_prompt_hash: None,
+                royalty_recipient,
+                royalty_bps: 250,
+                uri_hint: None,
+                royalty_splits: Vec::new(),
+            };
+            let token_id = nft::mint(&mut state, &seller(), &params).unwrap();
+            let payload = codec::encode(&CreateListingParams {
+                token_id,
+                price_cgt: 12_345,
+                allowed_buyer: None,
+            })
+            .unwrap();
+            call(&runtime, &mut state, seller(), "create_listing", payload);
+        };
+        mint_with_royalty(Some(creator));
+        mint_with_royalty(None);
+
+        let split = purchase_breakdown(&state, &get_listing(&state, 0).unwrap()).unwrap();
+        assert_eq!(
+            split,
+            PurchaseBreakdown {
+                price: 12_345,
+                royalty_amount: 308,
+                royalties: vec![(creator, 308)],
+                seller_amount: 11_914,
+                protocol_fee: 123,
+            }
+        );
+        assert_eq!(
+            split.royalty_amount + split.seller_amount + split.protocol_fee,
+            split.price
+        );
+
+        // Without a recipient the royalty goes to the seller
+        let split = purchase_breakdown(&state, &get_listing(&state, 1).unwrap()).unwrap();
+        assert!(split.royalties.is_empty());
+        assert_eq!(split.royalty_amount, 0);
+        assert_eq!(split.seller_amount + split.protocol_fee, split.price);
+
+        // and buying pays exactly what was previewed
+        let preview = purchase_breakdown(&state, &get_listing(&state, 0).unwrap()).unwrap();
+        bank::mint(&mut state, &buyer(), 20_000).unwrap();
+        let buy = codec::encode(&BuyListingParams { listing_id: 0 }).unwrap();
+        call(&runtime, &mut state, buyer(), "buy_listing", buy);
+        assert_eq!(bank::balance(&state, &creator), preview.royalty_amount);
+        assert_eq!(bank::balance(&state, &seller()), preview.seller_amount);
+        assert_eq!(bank::balance(&state, &buyer()), 20_000 - preview.price);
+    }
+
+    #[test]
+    fn test_royalty_split_remainder_goes_to_first_recipient() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let fee_key = StorageKey::new("governance", "param")
+            .bytes(governance::PARAM_MARKETPLACE_FEE_BPS.as_bytes())
+            .into_bytes();
+        state
+            .put_raw(fee_key, codec::encode(&0u64).unwrap())
+            .unwrap();
+        let (first, second, single) = ([5; 32], [6; 32], [9; 32]);
+        let mut params = MintDgenParams {
+            fabric_root_hash: [7; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: Some(single),
+            royalty_bps: 250,
+            uri_hint: None,
+            royalty_splits: vec![(first, 6_000), (second, 4_001)],
+        };
+        let err = nft::mint(&mut state, &seller(), &params).unwrap_err();
+        assert!(err.contains("sum to 10001 bps"), "{}", err);
+
+        // A 60/40 split of a 10% royalty; the splits replace the single recipient
+        params.royalty_splits = vec![(first, 600), (second, 400)];
+        let token_id = nft::mint(&mut state, &seller(), &params).unwrap();
+        let payload = codec::encode(&CreateListingParams {
+            token_id,
+            price_cgt: 12_345,
+            allowed_buyer: None,
+        })
+        .unwrap();
+        call(&runtime, &mut state, seller(), "create_listing", payload);
+
+        // 1234 in royalty shares out as 740.4 and 493.6; the 1 rounded off
+        // goes to the first recipient
+        let split = purchase_breakdown(&state, &get_listing(&state, 0).unwrap()).unwrap();
+        assert_eq!(split.royalty_amount, 1_234);
+        assert_eq!(split.royalties, vec![(first, 741), (second, 493)]);
+        assert_eq!(split.seller_amount, 11_111);
+
+        bank::mint(&mut state, &buyer(), 20_000).unwrap();
+        let buy = codec::encode(&BuyListingParams { listing_id: 0 }).unwrap();
+        call(&runtime, &mut state, buyer(), "buy_listing", buy);
+        assert_eq!(bank::balance(&state, &first), 741);
+        assert_eq!(bank::balance(&state, &second), 493);
+        assert_eq!(bank::balance(&state, &single), 0);
+        assert_eq!(bank::balance(&state, &seller()), 11_111);
+    }
+
+    #[test]
+    fn test_unescrowed_listing_escrows_on_first_touch() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let token_id = mint_nft(&mut state);
+        bank::mint(&mut state, &buyer(), 5_000).unwrap();
+
+        // Two listings of the same NFT, stored the way listings were before escrow
+        for id in 0..2 {
+            let v2 = (id, token_id, seller(), 1_000u64, true, None::<Address>);
+            let mut bytes = vec![2];
+            bytes.extend(codec::encode(&v2).unwrap());
+            state.put_raw(listing_key(id), bytes).unwrap();
+        }
+        assert!(!get_listing(&state, 0).unwrap().escrowed);
+
+        let reprice = codec::encode(&UpdateListingPriceParams {
+            listing_id: 0,
+            price_cgt: 2_000,
+        })
+        .unwrap();
+        // Bytes past the params are refused rather than ignored
+        let mut padded = reprice.clone();
+        padded.push(0);
+        assert!(runtime
+            .dispatch_tx(
+                &tx(seller(), MODULE_ID, "update_listing_price", padded),
+                &mut state
+            )
+            .is_err());
+        call(
+            &runtime,
+            &mut state,
+            seller(),
+            "update_listing_price",
+            reprice,
+        );
+        assert_eq!(owner(&state, token_id), listing_escrow_account());
+        assert!(get_listing(&state, 0).unwrap().escrowed);
+
+        // The other listing's seller no longer holds the NFT
+        let buy = |listing_id| codec::encode(&BuyListingParams { listing_id }).unwrap();
+        let err = runtime
+            .dispatch_tx(&tx(buyer(), MODULE_ID, "buy_listing", buy(1)), &mut state)
+            .unwrap_err();
+        assert!(err.contains("no longer NFT owner"), "{}", err);
+        let cancel = codec::encode(&CancelListingParams { listing_id: 1 }).unwrap();
+        call(&runtime, &mut state, seller(), "cancel_listing", cancel);
+        assert_eq!(owner(&state, token_id), listing_escrow_account());
+
+        call(&runtime, &mut state, buyer(), "buy_listing", buy(0));
+        assert_eq!(owner(&state, token_id), buyer());
+        assert_eq!(bank::balance(&state, &buyer()), 3_000);
+    }
+
+    #[test]
+    fn test_bundle_settles_each_royalty_and_pays_out_the_price() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        set_param(&mut state, governance::PARAM_MARKETPLACE_FEE_BPS, 100);
+        let (creator, first, second) = ([5; 32], [6; 32], [8; 32]);
+        let tokens = [
+            mint_with_royalty(&mut state, Some(creator), 250, Vec::new()),
+            mint_with_royalty(&mut state, None, 0, vec![(first, 600), (second, 400)]),
+            mint_with_royalty(&mut state, None, 0, Vec::new()),
+        ];
+        call(
+            &runtime,
+            &mut state,
+            seller(),
+            "create_bundle_listing",
+            bundle_payload(&tokens, 10_000, &[]),
+        );
+        let bundle = get_bundle_listing(&state, 0).unwrap();
+        // An even split, with the rounding remainder on the first NFT
+        assert_eq!(bundle.allocation, vec![3_334, 3_333, 3_333]);
+        for token_id in tokens {
+            assert_eq!(owner(&state, token_id), listing_escrow_account());
+        }
+        assert_eq!(get_active_bundles(&state, &seller()), vec![bundle.clone()]);
+
+        let splits = bundle_breakdown(&state, &bundle).unwrap();
+        let royalties: Vec<_> = splits.iter().map(|split| split.royalties.clone()).collect();
+        assert_eq!(
+            royalties,
+            vec![
+                vec![(creator, 83)],
+                vec![(first, 200), (second, 133)],
+                vec![]
+            ]
+        );
+        let fees: u64 = splits.iter().map(|split| split.protocol_fee).sum();
+        let royalty: u64 = splits.iter().map(|split| split.royalty_amount).sum();
+        let proceeds: u64 = splits.iter().map(|split| split.seller_amount).sum();
+        assert_eq!((fees, royalty, proceeds), (99, 416, 9_485));
+        assert_eq!(fees + royalty + proceeds, bundle.price_cgt);
+
+        bank::mint(&mut state, &buyer(), 20_000).unwrap();
+        let buy = codec::encode(&BuyBundleListingParams { bundle_id: 0 }).unwrap();
+        call(&runtime, &mut state, buyer(), "buy_bundle_listing", buy);
+        assert_eq!(bank::balance(&state, &buyer()), 10_000);
+        // The fees left the supply
+        assert_eq!(bank::total_supply(&state), 20_000 - 99);
+        assert_eq!(bank::balance(&state, &seller()), 9_485);
+        assert_eq!(bank::balance(&state, &creator), 83);
+        assert_eq!(bank::balance(&state, &first), 200);
+        assert_eq!(bank::balance(&state, &second), 133);
+        for token_id in tokens {
+            assert_eq!(owner(&state, token_id), buyer());
+        }
+        assert!(!get_bundle_listing(&state, 0).unwrap().active);
+        assert!(get_active_bundles(&state, &seller()).is_empty());
+        assert!(check_owner_index(&state).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_rules_and_cancel() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let tokens = [
+            mint_nft(&mut state),
+            mint_nft(&mut state),
+            mint_nft(&mut state),
+        ];
+        let theirs = nft::mint(
+            &mut state,
+            &buyer(),
+            &MintDgenParams {
+                fabric_root_hash: [7; 32],
+                forge_model_id: None,
+                forge_prompt_hash: None,
+                royalty_recipient: None,
+                royalty_bps: 0,
+                uri_hint: None,
+                royalty_splits: Vec::new(),
+            },
+        )
+        .unwrap();
+        let create = |state: &mut State, payload| {
+            runtime.dispatch_tx(
+                &tx(seller(), MODULE_ID, "create_bundle_listing", payload),
+                state,
+            )
+        };
+
+        // A token the seller does not own fails the bundle, before anything moves
+        let err = create(
+            &mut state,
+            bundle_payload(&[tokens[0], theirs, tokens[1]], 900, &[]),
+        )
+        .unwrap_err();
+        assert_eq!(err, format!("only the NFT owner may bundle NFT {}", theirs));
+        assert_eq!(owner(&state, tokens[0]), seller());
+        assert_eq!(owner(&state, theirs), buyer());
+        assert_eq!(get_next_listing_id(&state), 0);
+
+        for (payload, expected) in [
+            (
+                bundle_payload(&tokens[..1], 900, &[]),
+                "a bundle holds 2 to 32 NFTs",
+            ),
+            (
+                bundle_payload(&[tokens[0], tokens[0]], 900, &[]),
+                "bundled more than once",
+            ),
+            (bundle_payload(&tokens, 0, &[]), "price must be > 0"),
+            (
+                bundle_payload(&tokens, 900, &[450, 450]),
+                "2 parts for 3 NFTs",
+            ),
+            (
+                bundle_payload(&tokens, 900, &[450, 450, 1]),
+                "must sum to the price",
+            ),
+        ] {
+            let err = create(&mut state, payload).unwrap_err();
+            assert!(err.contains(expected), "{}", err);
+        }
+
+        // An explicit allocation is kept as given
+        create(&mut state, bundle_payload(&tokens, 900, &[600, 300, 0])).unwrap();
+        let bundle = get_bundle_listing(&state, 0).unwrap();
+        assert_eq!(bundle.allocation, vec![600, 300, 0]);
+        let parts: Vec<u64> = bundle_breakdown(&state, &bundle)
+            .unwrap()
+            .iter()
+            .map(|split| split.price)
+            .collect();
+        assert_eq!(parts, bundle.allocation);
+
+        // Only the seller may cancel, which returns every NFT
+        let cancel = codec::encode(&CancelBundleListingParams { bundle_id: 0 }).unwrap();
+        let err = runtime
+            .dispatch_tx(
+                &tx(buyer(), MODULE_ID, "cancel_bundle_listing", cancel.clone()),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("only the bundle seller"), "{}", err);
+        call(
+            &runtime,
+            &mut state,
+            seller(),
+            "cancel_bundle_listing",
+            cancel,
+        );
+        for token_id in tokens {
+            assert_eq!(owner(&state, token_id), seller());
+        }
+        assert!(get_active_bundles(&state, &seller()).is_empty());
+        let buy = codec::encode(&BuyBundleListingParams { bundle_id: 0 }).unwrap();
+        let err = runtime
+            .dispatch_tx(
+                &tx(buyer(), MODULE_ID, "buy_bundle_listing", buy),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("not active"), "{}", err);
+        assert!(check_owner_index(&state).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_allocation_cannot_evade_royalties() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let creator = [5; 32];
+        let royalty_bearing = mint_with_royalty(&mut state, Some(creator), 2_000, Vec::new());
+        let filler = mint_nft(&mut state);
+        let create = |state: &mut State, allocation: &[u64]| {
+            let payload = bundle_payload(&[royalty_bearing, filler], 10_000, allocation);
+            runtime.dispatch_tx(
+                &tx(seller(), MODULE_ID, "create_bundle_listing", payload),
+                state,
+            )
+        };
+
+        // Putting the whole price on a self-minted filler would leave the
+        // creator nothing, and anything below an even share is refused too
+        for allocation in [[0, 10_000], [4_999, 5_001]] {
+            let err = create(&mut state, &allocation).unwrap_err();
+            assert!(err.contains("at least an even share of 5000"), "{}", err);
+        }
+        assert_eq!(owner(&state, royalty_bearing), seller());
+
+        // The filler may still go for less
+        create(&mut state, &[10_000, 0]).unwrap();
+        let bundle = get_bundle_listing(&state, 0).unwrap();
+        let splits = bundle_breakdown(&state, &bundle).unwrap();
+        assert_eq!(splits[0].royalties, vec![(creator, 2_000)]);
+    }
+
+    #[test]
+    fn test_failed_bundle_settlement_rolls_back_the_purchase() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let (funded, full) = ([5; 32], [6; 32]);
+        bank::mint(&mut state, &funded, 5_000).unwrap();
+        bank::credit(&mut state, &full, u64::MAX).unwrap();
+        bank::mint(&mut state, &buyer(), 20_000).unwrap();
+        let tokens = [
+            mint_with_royalty(&mut state, Some(funded), 1_000, Vec::new()),
+            mint_with_royalty(&mut state, Some(full), 100, Vec::new()),
+        ];
+        call(
+            &runtime,
+            &mut state,
+            seller(),
+            "create_bundle_listing",
+            bundle_payload(&tokens, 10_000, &[]),
+        );
+
+        // The seller and the first royalty are credited before the second
+        // royalty, 50, would overflow its recipient's balance
+        let buy = codec::encode(&BuyBundleListingParams { bundle_id: 0 }).unwrap();
+        let before = state.scan_prefix(b"");
+        let (err, events) = state
+            .dispatch_scoped(&runtime, &tx(buyer(), MODULE_ID, "buy_bundle_listing", buy))
+            .unwrap();
+        assert!(err.unwrap().contains("overflow"));
+        assert!(events.is_empty());
+        assert_eq!(state.scan_prefix(b""), before);
+        assert_eq!(bank::balance(&state, &buyer()), 20_000);
+        assert_eq!(bank::balance(&state, &seller()), 0);
+        assert_eq!(bank::balance(&state, &funded), 5_000);
+        for token_id in tokens {
+            assert_eq!(owner(&state, token_id), listing_escrow_account());
+        }
+        assert!(get_bundle_listing(&state, 0).unwrap().active);
+    }
+
+    #[test]
+    fn test_royalty_stats_accumulate_across_sale_types() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let (creator, other) = ([5; 32], [6; 32]);
+        let single = mint_with_royalty(&mut state, Some(creator), 1_000, Vec::new());
+        let bundled = [
+            mint_with_royalty(&mut state, Some(creator), 500, Vec::new()),
+            mint_with_royalty(&mut state, None, 0, vec![(creator, 600), (other, 400)]),
+        ];
+        bank::mint(&mut state, &buyer(), 10_000).unwrap();
+        assert_eq!(get_royalty_stats(&state, &creator), RoyaltyStats::default());
+
+        let listing = CreateListingParams {
+            token_id: single,
+            price_cgt: 2_000,
+            allowed_buyer: None,
+        };
+        call(
+            &runtime,
+            &mut state,
+            seller(),
+            "create_listing",
+            codec::encode(&listing).unwrap(),
+        );
+        call(
+            &runtime,
+            &mut state,
+            seller(),
+            "create_bundle_listing",
+            bundle_payload(&bundled, 6_000, &[]),
+        );
+
+        // Each sale's payouts to the creator, as previewed before it
+        let paid_to = |splits: &[PurchaseBreakdown]| -> u64 {
+            splits
+                .iter()
+                .flat_map(|split| &split.royalties)
+                .filter(|(recipient, _)| *recipient == creator)
+                .map(|(_, amount)| amount)
+                .sum()
+        };
+        let listing_payout =
+            paid_to(&[purchase_breakdown(&state, &get_listing(&state, 0).unwrap()).unwrap()]);
+        let bundle_payout =
+            paid_to(&bundle_breakdown(&state, &get_bundle_listing(&state, 1).unwrap()).unwrap());
+        assert_eq!((listing_payout, bundle_payout), (200, 150 + 180));
+
+        state.set_block_height(5);
+        let buy = codec::encode(&BuyListingParams { listing_id: 0 }).unwrap();
+        call(&runtime, &mut state, buyer(), "buy_listing", buy);
+        assert_eq!(
+            get_royalty_stats(&state, &creator),
+            RoyaltyStats {
+                total_amount: 200,
+                sale_count: 1,
+                last_height: 5,
+            }
+        );
+
+        state.set_block_height(7);
+        let buy = codec::encode(&BuyBundleListingParams { bundle_id: 1 }).unwrap();
+        let bundle_tx = tx(buyer(), MODULE_ID, "buy_bundle_listing", buy);
+        let (error, events) = state.dispatch_scoped(&runtime, &bundle_tx).unwrap();
+        assert_eq!(error, None);
+        assert_eq!(
+            get_royalty_stats(&state, &creator),
+            RoyaltyStats {
+                total_amount: listing_payout + bundle_payout,
+                sale_count: 3,
+                last_height: 7,
+            }
+        );
+        assert_eq!(get_royalty_stats(&state, &other).total_amount, 120);
+        assert_eq!(
+            bank::balance(&state, &creator),
+            listing_payout + bundle_payout
+        );
+
+        // One royalty_paid event per payout, about its recipient
+        let payouts: Vec<_> = events
+            .iter()
+            .filter(|event| event.kind == "royalty_paid")
+            .map(|event| (event.address, event.attributes.clone()))
+            .collect();
+        let attributes = |token_id: NftId, amount: u64| {
+            vec![
+                ("token_id".to_string(), token_id.to_string()),
+                ("bundle_id".to_string(), "1".to_string()),
+                ("amount".to_string(), amount.to_string()),
+            ]
+        };
+        assert_eq!(
+            payouts,
+            vec![
+                (creator, attributes(bundled[0], 150)),
+                (creator, attributes(bundled[1], 180)),
+                (other, attributes(bundled[1], 120)),
+            ]
+        );
+
+        // and each indexed under its recipient, newest first. The listing
+        // was bought outside `dispatch_scoped`, so no hash was recorded
+        let indexed: Vec<_> = get_royalty_payouts(&state, &creator, 0, 10)
+            .into_iter()
+            .map(|payout| (payout.height, payout.tx_hash, payout.event.attributes))
+            .collect();
+        let bundle_hash = bundle_tx.hash();
+        assert_eq!(
+            indexed,
+            vec![
+                (7, bundle_hash, attributes(bundled[1], 180)),
+                (7, bundle_hash, attributes(bundled[0], 150)),
+                (
+                    5,
+                    [0; 32],
+                    vec![
+                        ("token_id".to_string(), single.to_string()),
+                        ("listing_id".to_string(), "0".to_string()),
+                        ("amount".to_string(), "200".to_string()),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(
+            get_royalty_payouts(&state, &creator, 1, 1)[0]
+                .event
+                .attributes,
+            attributes(bundled[0], 150)
+        );
+    }
+
+    #[test]
+    fn test_legacy_listings_and_payloads_decode() {
+        let seller = [1u8; 32];
+
+        // Listings stored before private listings upgrade to public ones
+        let v1 = codec::encode(&(4u64, 7u64, seller, 10u64, true)).unwrap();
+        let listing = codec::decode_versioned::<Listing>(&v1).unwrap();
+        assert_eq!((listing.id, listing.token_id), (4, 7));
+        assert_eq!(listing.allowed_buyer, None);
+        assert!(!listing.escrowed);
+
+        let mut state = State::in_memory();
+        let private = Listing {
+            allowed_buyer: Some([2; 32]),
+            ..listing
+        };
+        store_listing(&mut state, &private).unwrap();
+        assert_eq!(get_listing(&state, 4).unwrap().allowed_buyer, Some([2; 32]));
+
+        // So do create_listing payloads from older blocks
+        let params = decode_create_listing(&codec::encode(&(7u64, 10u64)).unwrap()).unwrap();
+        assert_eq!((params.token_id, params.price_cgt), (7, 10));
+        assert_eq!(params.allowed_buyer, None);
+        let payload = codec::encode(&CreateListingParams {
+            token_id: 7,
+            price_cgt: 10,
+            allowed_buyer: Some([2; 32]),
+        })
+        .unwrap();
+        assert_eq!(
+            decode_create_listing(&payload).unwrap().allowed_buyer,
+            Some([2; 32])
+        );
+
+        // A private listing cut off inside its buyer is not an old public one
+        let truncated = &payload[..payload.len() - 20];
+        assert!(decode_create_listing(truncated).is_err());
+    }
+}
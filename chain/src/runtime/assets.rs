@@ -0,0 +1,434 @@
+//! Assets module for fungible tokens other than CGT.
+//!
+//! This module handles:
+//! - Registering an asset under a unique `asset_id`; the registrant becomes
+//!   its authority
+//! - Minting, restricted to the asset's authority
+//! - Transfers between addresses, per asset
+//!
+//! Assets are how external tokens are represented on chain (e.g. a bridged
+//! `wETH`). CGT itself stays in `bank_cgt`.
+
+use serde::{Deserialize, Serialize};
+
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::CGT_SYMBOL;
+use crate::core::codec;
+use crate::core::receipt::Event;
+use crate::core::state::State;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+
+const MODULE_ID: &str = "assets";
+const PREFIX_ASSET: &str = "asset";
+const PREFIX_BALANCE: &str = "balance";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_ASSET, PREFIX_BALANCE];
+
+/// Longest accepted asset id, in bytes.
+pub const MAX_ASSET_ID_LEN: usize = 32;
+
+/// Asset identifier, e.g. `wETH`.
+pub type AssetId = String;
+
+/// A registered fungible asset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Asset {
+    pub asset_id: AssetId,
+    pub name: String,
+    pub decimals: u8,
+    /// The only address allowed to mint the asset.
+    pub authority: Address,
+    pub total_supply: u64,
+}
+
+/// Register asset parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterAssetParams {
+    pub asset_id: AssetId,
+    pub name: String,
+    pub decimals: u8,
+}
+
+/// Mint asset parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MintAssetParams {
+    pub asset_id: AssetId,
+    pub to: Address,
+    pub amount: u64,
+}
+
+/// Transfer asset parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferAssetParams {
+    pub asset_id: AssetId,
+    pub to: Address,
+    pub amount: u64,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "register_asset",
+        CallSchema::new(&[
+            FieldSchema::new("asset_id", FieldType::String),
+            FieldSchema::new("name", FieldType::String),
+            FieldSchema::new("decimals", FieldType::U8),
+        ]),
+    ),
+    (
+        "mint_asset",
+        CallSchema::new(&[
+            FieldSchema::new("asset_id", FieldType::String),
+            FieldSchema::new("to", FieldType::Address),
+            FieldSchema::new("amount", FieldType::U64),
+        ]),
+    ),
+    (
+        "transfer_asset",
+        CallSchema::new(&[
+            FieldSchema::new("asset_id", FieldType::String),
+            FieldSchema::new("to", FieldType::Address),
+            FieldSchema::new("amount", FieldType::U64),
+        ]),
+    ),
+];
+
+// Helper functions for asset management
+
+fn asset_key(asset_id: &str) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ASSET)
+        .bytes(asset_id.as_bytes())
+        .into_bytes()
+}
+
+fn balance_key(asset_id: &str, addr: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_BALANCE)
+        .address(addr)
+        .bytes(asset_id.as_bytes())
+        .into_bytes()
+}
+
+fn load_asset(state: &State, asset_id: &str) -> Option<Asset> {
+    state
+        .get_raw(&asset_key(asset_id))
+        .and_then(|bytes| codec::decode_state::<Asset>(&bytes).ok())
+}
+
+fn store_asset(state: &mut State, asset: &Asset) -> Result<(), String> {
+    let bytes = codec::encode(asset).map_err(|e| e.to_string())?;
+    state
+        .put_raw(asset_key(&asset.asset_id), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn set_balance(
+    state: &mut State,
+    asset_id: &str,
+    addr: &Address,
+    amount: u64,
+) -> Result<(), String> {
+    // A missing key already reads as zero; don't store dead entries
+    if amount == 0 {
+        return state
+            .delete_raw(&balance_key(asset_id, addr))
+            .map_err(|e| e.to_string());
+    }
+    let bytes = codec::encode(&amount).map_err(|e| e.to_string())?;
+    state
+        .put_raw(balance_key(asset_id, addr), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Public helper for querying a registered asset (for RPC/SDK use).
+pub fn get_asset(state: &State, asset_id: &str) -> Option<Asset> {
+    load_asset(state, asset_id)
+}
+
+/// Public helper for querying an address's balance of an asset.
+///
+/// Unregistered assets read as a zero balance.
+pub fn balance_of_asset(state: &State, asset_id: &str, addr: &Address) -> u64 {
+    state
+        .get_raw(&balance_key(asset_id, addr))
+        .and_then(|bytes| codec::decode_state::<u64>(&bytes).ok())
+        .unwrap_or(0)
+}
+
+/// Asset ids are 1 to `MAX_ASSET_ID_LEN` ASCII letters, digits, `.`, `-`, or
+/// `_`, and may not claim the CGT symbol.
+fn validate_asset_id(asset_id: &str) -> Result<(), String> {
+    if asset_id.is_empty() || asset_id.len() > MAX_ASSET_ID_LEN {
+        return Err(format!(
+            "asset_id must be 1 to {} characters",
+            MAX_ASSET_ID_LEN
+        ));
+    }
+    if !asset_id
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'_'))
+    {
+        return Err("asset_id may only contain ASCII letters, digits, '.', '-', and '_'".into());
+    }
+    if asset_id.eq_ignore_ascii_case(CGT_SYMBOL) {
+        return Err("CGT is the native token and cannot be registered as an asset".into());
+    }
+    Ok(())
+}
+
+/// AssetsModule handles fungible tokens other than CGT
+#[derive(Default)]
+pub struct AssetsModule;
+
+impl AssetsModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for AssetsModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["register_asset", "mint_asset", "transfer_asset"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "register_asset" => handle_register_asset(tx, state),
+            "mint_asset" => handle_mint_asset(tx, state),
+            "transfer_asset" => handle_transfer_asset(tx, state),
+            other => Err(format!("assets: unknown call_id '{}'", other)),
+        }
+    }
+}
+
+fn handle_register_asset(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RegisterAssetParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    validate_asset_id(&params.asset_id)?;
+    if load_asset(state, &params.asset_id).is_some() {
+        return Err(format!("asset {} is already registered", params.asset_id));
+    }
+
+    store_asset(
+        state,
+        &Asset {
+            asset_id: params.asset_id.clone(),
+            name: params.name,
+            decimals: params.decimals,
+            authority: tx.from,
+            total_supply: 0,
+        },
+    )?;
+
+    state.emit_event(Event::new(MODULE_ID, "register", tx.from).with("asset_id", params.asset_id));
+    Ok(())
+}
+
+fn handle_mint_asset(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: MintAssetParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let mut asset = load_asset(state, &params.asset_id)
+        .ok_or_else(|| format!("asset {} is not registered", params.asset_id))?;
+    if asset.authority != tx.from {
+        return Err("only the asset's authority may mint it".into());
+    }
+
+    asset.total_supply = asset
+        .total_supply
+        .checked_add(params.amount)
+        .ok_or("asset supply overflow")?;
+    let balance = balance_of_asset(state, &params.asset_id, &params.to)
+        .checked_add(params.amount)
+        .ok_or("overflow crediting asset balance")?;
+    set_balance(state, &params.asset_id, &params.to, balance)?;
+    store_asset(state, &asset)?;
+
+    state.emit_event(
+        Event::new(MODULE_ID, "mint", params.to)
+            .with("asset_id", params.asset_id)
+            .with("amount", params.amount),
+    );
+    Ok(())
+}
+
+fn handle_transfer_asset(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: TransferAssetParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if load_asset(state, &params.asset_id).is_none() {
+        return Err(format!("asset {} is not registered", params.asset_id));
+    }
+
+    let from_balance = balance_of_asset(state, &params.asset_id, &tx.from)
+        .checked_sub(params.amount)
+        .ok_or("insufficient asset balance")?;
+    set_balance(state, &params.asset_id, &tx.from, from_balance)?;
+    let to_balance = balance_of_asset(state, &params.asset_id, &params.to)
+        .checked_add(params.amount)
+        .ok_or("overflow on recipient")?;
+    set_balance(state, &params.asset_id, &params.to, to_balance)?;
+
+    state.emit_event(
+        Event::new(MODULE_ID, "transfer", tx.from)
+            .with("asset_id", params.asset_id)
+            .with("to", params.to.to_display())
+            .with("amount", params.amount),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::bank_cgt::get_balance_cgt;
+    use crate::runtime::module_api::bank;
+
+    const ISSUER: Address = [1; 32];
+    const HOLDER: Address = [2; 32];
+    const OTHER: Address = [3; 32];
+
+    fn call(
+        state: &mut State,
+        from: Address,
+        call_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<(), String> {
+        let tx = Transaction {
+            from,
+            nonce: 0,
+            module_id: "assets".to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        AssetsModule::new().dispatch(call_id, &tx, state)
+    }
+
+    fn register(state: &mut State, asset_id: &str) -> Result<(), String> {
+        let params = RegisterAssetParams {
+            asset_id: asset_id.to_string(),
+            name: "Wrapped Ether".to_string(),
+            decimals: 18,
+        };
+        call(
+            state,
+            ISSUER,
+            "register_asset",
+            codec::encode(&params).unwrap(),
+        )
+    }
+
+    fn mint(state: &mut State, from: Address, to: Address, amount: u64) -> Result<(), String> {
+        let params = MintAssetParams {
+            asset_id: "wETH".to_string(),
+            to,
+            amount,
+        };
+        call(state, from, "mint_asset", codec::encode(&params).unwrap())
+    }
+
+    fn transfer(state: &mut State, from: Address, to: Address, amount: u64) -> Result<(), String> {
+        let params = TransferAssetParams {
+            asset_id: "wETH".to_string(),
+            to,
+            amount,
+        };
+        call(
+            state,
+            from,
+            "transfer_asset",
+            codec::encode(&params).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_register_mint_and_transfer() {
+        let mut state = State::in_memory();
+        register(&mut state, "wETH").unwrap();
+        let asset = get_asset(&state, "wETH").unwrap();
+        assert_eq!(asset.authority, ISSUER);
+        assert_eq!(asset.decimals, 18);
+        assert_eq!(asset.total_supply, 0);
+
+        mint(&mut state, ISSUER, HOLDER, 500).unwrap();
+        assert_eq!(get_asset(&state, "wETH").unwrap().total_supply, 500);
+        transfer(&mut state, HOLDER, OTHER, 200).unwrap();
+
+        assert_eq!(balance_of_asset(&state, "wETH", &HOLDER), 300);
+        assert_eq!(balance_of_asset(&state, "wETH", &OTHER), 200);
+        assert_eq!(balance_of_asset(&state, "wBTC", &HOLDER), 0);
+
+        // CGT balances are untouched by asset activity
+        assert_eq!(get_balance_cgt(&state, &HOLDER), 0);
+        assert_eq!(get_balance_cgt(&state, &OTHER), 0);
+    }
+
+    #[test]
+    fn test_assets_independent_of_cgt() {
+        let mut state = State::in_memory();
+        bank::credit(&mut state, &HOLDER, 1_000).unwrap();
+        register(&mut state, "wETH").unwrap();
+        mint(&mut state, ISSUER, HOLDER, 10).unwrap();
+
+        // A CGT balance cannot pay for an asset transfer
+        assert!(transfer(&mut state, HOLDER, OTHER, 11)
+            .unwrap_err()
+            .contains("insufficient asset balance"));
+        transfer(&mut state, HOLDER, OTHER, 10).unwrap();
+        assert_eq!(get_balance_cgt(&state, &HOLDER), 1_000);
+        assert_eq!(balance_of_asset(&state, "wETH", &HOLDER), 0);
+        assert!(state.get_raw(&balance_key("wETH", &HOLDER)).is_none());
+    }
+
+    #[test]
+    fn test_only_authority_mints() {
+        let mut state = State::in_memory();
+        register(&mut state, "wETH").unwrap();
+
+        let err = mint(&mut state, HOLDER, HOLDER, 1).unwrap_err();
+        assert!(err.contains("authority"), "{}", err);
+        assert_eq!(balance_of_asset(&state, "wETH", &HOLDER), 0);
+    }
+
+    #[test]
+    fn test_register_rejects_bad_or_taken_ids() {
+        let mut state = State::in_memory();
+        register(&mut state, "wETH").unwrap();
+        assert!(register(&mut state, "wETH")
+            .unwrap_err()
+            .contains("already registered"));
+        assert!(register(&mut state, "").is_err());
+        assert!(register(&mut state, "has space").is_err());
+        assert!(register(&mut state, "cgt").is_err());
+        assert!(register(&mut state, &"x".repeat(MAX_ASSET_ID_LEN + 1)).is_err());
+
+        // Unregistered assets can be neither minted nor moved
+        let params = MintAssetParams {
+            asset_id: "wBTC".to_string(),
+            to: HOLDER,
+            amount: 1,
+        };
+        assert!(call(
+            &mut state,
+            ISSUER,
+            "mint_asset",
+            codec::encode(&params).unwrap()
+        )
+        .unwrap_err()
+        .contains("not registered"));
+    }
+}
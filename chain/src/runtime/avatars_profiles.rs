@@ -1,27 +1,74 @@
 //! Aeon Registry module for user profiles and progression.
 //!
 //! This module handles:
-//! - Aeon profiles (display name, bio, progression stats)
-//! - Gnosis XP and Syzygy Score tracking
+//! - Aeon profiles (display name, bio, progression stats), and their deletion
+//!   by the owner, which leaves a tombstone
+//! - Gnosis XP and Syzygy Score tracking, recorded by the seeding Aeon or
+//!   by recorders it has delegated to, each record consuming the sender's
+//!   nonce so it cannot be replayed, with the weight one Aeon's content can
+//!   add, and the weight one seeder can gain in all, capped per height window
 //! - Ascension Level computation
+//! - Syzygy Score decay, so old scores stop ranking with fresh ones
 //! - Badge management (e.g., Luminary)
 //! - Legacy Archon flag support (for backward compatibility)
+//! - An index of every Archon, for enumeration
 
 use serde::{Deserialize, Serialize};
 
-use super::RuntimeModule;
+use super::module_api::{bank, governance, names};
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::{
+    DEFAULT_LUMINARY_SYZYGY_THRESHOLD, DEFAULT_SYZYGY_DECAY_BPS, DEFAULT_SYZYGY_DECAY_EPOCH_BLOCKS,
+};
+use crate::core::codec;
+use crate::core::receipt::Event;
 use crate::core::state::State;
-use crate::core::transaction::{Address, Transaction};
-
-const PREFIX_ARCHON_FLAG: &[u8] = b"avatars:archon:";
-const PREFIX_AEON_PROFILE: &[u8] = b"aeon/profile:";
-const PREFIX_AEON_HANDLE: &[u8] = b"aeon/handle/";
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+
+const MODULE_ID: &str = "avatars_profiles";
+const PREFIX_ARCHON_FLAG: &str = "archon";
+const PREFIX_AEON_PROFILE: &str = "profile";
+const PREFIX_AEON_HANDLE: &str = "handle";
+const PREFIX_ARCHON_INDEX: &str = "archon_index";
+const PREFIX_RECORDER: &str = "recorder";
+const PREFIX_SYZYGY_EDGE: &str = "syzygy_edge";
+const PREFIX_SYZYGY_SEEDER: &str = "syzygy_seeder";
+const PREFIX_DECAY_CHECKPOINT: &str = "decay_checkpoint";
+const PREFIX_TOMBSTONE: &str = "tombstone";
+const STORAGE_PREFIXES: &[&str] = &[
+    PREFIX_ARCHON_FLAG,
+    PREFIX_AEON_PROFILE,
+    PREFIX_AEON_HANDLE,
+    PREFIX_ARCHON_INDEX,
+    PREFIX_RECORDER,
+    PREFIX_SYZYGY_EDGE,
+    PREFIX_SYZYGY_SEEDER,
+    PREFIX_DECAY_CHECKPOINT,
+    PREFIX_TOMBSTONE,
+];
 
 // Progression constants
 const ASCENSION_STEP: u64 = 1_000;
-const LUMINARY_SYZYGY_THRESHOLD: u64 = 10_000;
+
+/// Most Syzygy weight a seeding Aeon can be credited for one other Aeon's
+/// content within a window; weight recorded beyond it is dropped.
+pub const SYZYGY_EDGE_CAP: u64 = 1_000;
+
+/// Most Syzygy weight a seeding Aeon can be credited within a window across
+/// all the Aeons it seeds, so spreading Syzygies over many Aeons cannot
+/// multiply the edge cap without bound.
+pub const SYZYGY_SEEDER_CAP: u64 = 5 * SYZYGY_EDGE_CAP;
+
+/// Length of a Syzygy cap window in blocks (about a day at the default
+/// 10-second block time). Windows start at multiples of this height.
+pub const SYZYGY_WINDOW_BLOCKS: u64 = 8_640;
+
+/// Longest avatar URI a profile may hold, in bytes.
+pub const MAX_AVATAR_URI_LEN: usize = 256;
 
 /// Aeon profile with progression stats.
+///
+/// Stored versioned (see `codec::Versioned`); this is layout version 3.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AeonProfile {
     pub address: Address,
@@ -36,15 +83,102 @@ pub struct AeonProfile {
     // Badges
     pub badges: Vec<String>,
     pub created_at_height: u64,
+    /// Optional avatar image URI (added in version 2)
+    pub avatar_uri: Option<String>,
+    /// Height the Syzygy Score decay was last applied at; older profiles
+    /// count from their creation (added in version 3)
+    pub syzygy_decayed_at: u64,
+}
+
+/// Version 2 profile layout.
+#[derive(Deserialize)]
+struct AeonProfileV2 {
+    address: Address,
+    display_name: String,
+    bio: Option<String>,
+    handle: Option<String>,
+    gnosis_xp: u64,
+    syzygy_score: u64,
+    ascension_level: u32,
+    badges: Vec<String>,
+    created_at_height: u64,
+    avatar_uri: Option<String>,
+}
+
+impl From<AeonProfileV2> for AeonProfile {
+    fn from(v2: AeonProfileV2) -> Self {
+        Self {
+            address: v2.address,
+            display_name: v2.display_name,
+            bio: v2.bio,
+            handle: v2.handle,
+            gnosis_xp: v2.gnosis_xp,
+            syzygy_score: v2.syzygy_score,
+            ascension_level: v2.ascension_level,
+            badges: v2.badges,
+            created_at_height: v2.created_at_height,
+            avatar_uri: v2.avatar_uri,
+            syzygy_decayed_at: v2.created_at_height,
+        }
+    }
+}
+
+/// Version 1 profile layout, stored without a version prefix.
+#[derive(Deserialize)]
+struct AeonProfileV1 {
+    address: Address,
+    display_name: String,
+    bio: Option<String>,
+    handle: Option<String>,
+    gnosis_xp: u64,
+    syzygy_score: u64,
+    ascension_level: u32,
+    badges: Vec<String>,
+    created_at_height: u64,
+}
+
+impl From<AeonProfileV1> for AeonProfileV2 {
+    fn from(v1: AeonProfileV1) -> Self {
+        Self {
+            address: v1.address,
+            display_name: v1.display_name,
+            bio: v1.bio,
+            handle: v1.handle,
+            gnosis_xp: v1.gnosis_xp,
+            syzygy_score: v1.syzygy_score,
+            ascension_level: v1.ascension_level,
+            badges: v1.badges,
+            created_at_height: v1.created_at_height,
+            avatar_uri: None,
+        }
+    }
+}
+
+impl codec::Versioned for AeonProfile {
+    const VERSION: u8 = 3;
+
+    fn upgrade(version: Option<u8>, body: &[u8]) -> bincode::Result<Self> {
+        match version {
+            // Each version upgrades to the next, so a layout change only
+            // needs one new step
+            None | Some(1) => codec::decode_state_exact::<AeonProfileV1>(body)
+                .map(AeonProfileV2::from)
+                .map(Into::into),
+            Some(2) => codec::decode_state_exact::<AeonProfileV2>(body).map(Into::into),
+            Some(other) => Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "no upgrade from Aeon profile version {}",
+                other
+            )))),
+        }
+    }
 }
 
 /// Legacy Archon flag management (kept for backward compatibility)
 
 fn archon_flag_key(address: &Address) -> Vec<u8> {
-    let mut key = Vec::with_capacity(PREFIX_ARCHON_FLAG.len() + address.len());
-    key.extend_from_slice(PREFIX_ARCHON_FLAG);
-    key.extend_from_slice(address);
-    key
+    StorageKey::new(MODULE_ID, PREFIX_ARCHON_FLAG)
+        .address(address)
+        .into_bytes()
 }
 
 /// Check if an address has Archon status.
@@ -61,26 +195,124 @@ fn set_archon_flag(state: &mut State, addr: &Address, value: bool) -> Result<(),
     let bytes = if value { vec![1u8] } else { vec![0u8] };
     state
         .put_raw(archon_flag_key(addr), bytes)
+        .map_err(|e| e.to_string())?;
+
+    let mut index = archon_index(state);
+    match (index.binary_search(addr), value) {
+        (Err(pos), true) => index.insert(pos, *addr),
+        (Ok(pos), false) => {
+            index.remove(pos);
+        }
+        _ => return Ok(()),
+    }
+    store_archon_index(state, &index)
+}
+
+/// Grant Archon status to an address.
+///
+/// Cross-module API used by genesis init; `claim_archon` grants it to the
+/// transaction sender.
+pub fn grant_archon(state: &mut State, addr: &Address) -> Result<(), String> {
+    state.with_namespace(MODULE_ID, |state| set_archon_flag(state, addr, true))
+}
+
+/// Revoke an address's Archon status.
+pub fn revoke_archon(state: &mut State, addr: &Address) -> Result<(), String> {
+    state.with_namespace(MODULE_ID, |state| set_archon_flag(state, addr, false))
+}
+
+/// Archon index management
+///
+/// The index is a sorted list of every Archon address. Databases created
+/// before it existed only have the per-address flags; until
+/// `backfill_archon_index` persists it, the index is rebuilt from those.
+
+fn archon_index_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ARCHON_INDEX).into_bytes()
+}
+
+/// Archon addresses from the per-address flags, in address order.
+fn archons_from_flags(state: &State) -> Vec<Address> {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_ARCHON_FLAG).into_bytes();
+    state
+        .scan_prefix(&prefix)
+        .into_iter()
+        .filter(|(_, value)| value == &[1u8])
+        .filter_map(|(key, _)| key[prefix.len()..].try_into().ok())
+        .collect()
+}
+
+fn archon_index(state: &State) -> Vec<Address> {
+    state
+        .get_raw(&archon_index_key())
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+        .unwrap_or_else(|| archons_from_flags(state))
+}
+
+fn store_archon_index(state: &mut State, index: &[Address]) -> Result<(), String> {
+    let bytes = codec::encode(index).map_err(|e| e.to_string())?;
+    state
+        .put_raw(archon_index_key(), bytes)
         .map_err(|e| e.to_string())
 }
 
+/// Number of Archons.
+pub fn archon_count(state: &State) -> u64 {
+    archon_index(state).len() as u64
+}
+
+/// A window of the Archon list, in address order.
+///
+/// # Returns
+/// The total number of Archons and the addresses in the window.
+pub fn list_archons(state: &State, offset: u64, limit: u64) -> (u64, Vec<Address>) {
+    let index = archon_index(state);
+    let page = index
+        .iter()
+        .skip(offset.try_into().unwrap_or(usize::MAX))
+        .take(limit.try_into().unwrap_or(usize::MAX))
+        .copied()
+        .collect();
+    (index.len() as u64, page)
+}
+
+/// Build the Archon index from the per-address flags, for databases created
+/// before the index existed.
+///
+/// A no-op once the index is stored.
+///
+/// # Returns
+/// - `Ok(usize)` with the number of Archons indexed
+/// - `Err(String)` on storage failure
+pub fn backfill_archon_index(state: &mut State) -> Result<usize, String> {
+    if state.get_raw(&archon_index_key()).is_some() {
+        return Ok(0);
+    }
+    let archons = archons_from_flags(state);
+    if archons.is_empty() {
+        return Ok(0);
+    }
+    store_archon_index(state, &archons)?;
+    Ok(archons.len())
+}
+
 /// Aeon profile management
 
 fn aeon_profile_key(address: &Address) -> Vec<u8> {
-    let mut key = Vec::with_capacity(PREFIX_AEON_PROFILE.len() + address.len());
-    key.extend_from_slice(PREFIX_AEON_PROFILE);
-    key.extend_from_slice(address);
-    key
+    StorageKey::new(MODULE_ID, PREFIX_AEON_PROFILE)
+        .address(address)
+        .into_bytes()
 }
 
 fn load_aeon_profile(state: &State, address: &Address) -> Option<AeonProfile> {
     state
         .get_raw(&aeon_profile_key(address))
-        .and_then(|bytes| bincode::deserialize::<AeonProfile>(&bytes).ok())
+        .and_then(|bytes| codec::decode_versioned::<AeonProfile>(&bytes).ok())
 }
 
 fn store_aeon_profile(state: &mut State, profile: &AeonProfile) -> Result<(), String> {
-    let bytes = bincode::serialize(profile).map_err(|e| e.to_string())?;
+    super::bank_cgt::touch_account(state, &profile.address)?;
+    let bytes = codec::encode_versioned(profile).map_err(|e| e.to_string())?;
     state
         .put_raw(aeon_profile_key(&profile.address), bytes)
         .map_err(|e| e.to_string())
@@ -89,10 +321,22 @@ fn store_aeon_profile(state: &mut State, profile: &AeonProfile) -> Result<(), St
 /// Handle mapping management
 
 fn handle_key(handle: &str) -> Vec<u8> {
-    let mut key = Vec::with_capacity(PREFIX_AEON_HANDLE.len() + handle.len());
-    key.extend_from_slice(PREFIX_AEON_HANDLE);
-    key.extend_from_slice(handle.as_bytes());
-    key
+    StorageKey::new(MODULE_ID, PREFIX_AEON_HANDLE)
+        .bytes(handle.as_bytes())
+        .into_bytes()
+}
+
+/// Canonical form of a handle as typed by a user: trimmed, without a leading
+/// `@`, and lowercase.
+pub fn normalize_handle(handle: &str) -> String {
+    let handle = handle.trim();
+    handle.strip_prefix('@').unwrap_or(handle).to_lowercase()
+}
+
+/// Address currently holding `handle`, which is normalized first (so
+/// "@Orpheus" finds "orpheus").
+pub fn resolve_handle(state: &State, handle: &str) -> Option<Address> {
+    get_address_by_handle(state, &normalize_handle(handle))
 }
 
 /// Get address by handle.
@@ -119,17 +363,55 @@ fn set_handle_mapping(state: &mut State, handle: &str, address: Address) -> Resu
 
 /// Remove handle mapping.
 fn remove_handle_mapping(state: &mut State, handle: &str) -> Result<(), String> {
-    // For now, we'll just overwrite with empty or leave it
-    // In a real implementation, you might want to track deletions
-    // For simplicity, we'll just remove the key by setting it to empty
-    // RocksDB doesn't have explicit delete in our abstraction, so we'll leave it
-    // The lookup will fail if the mapping doesn't exist
-    Ok(())
+    state
+        .delete_raw(&handle_key(handle))
+        .map_err(|e| e.to_string())
+}
+
+/// Profile tombstones
+///
+/// Deleting a profile leaves a marker holding the deletion height, so the
+/// address cannot create a fresh profile (and collect a new starter grant).
+
+fn tombstone_key(address: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_TOMBSTONE)
+        .address(address)
+        .into_bytes()
+}
+
+/// Height at which an address deleted its Aeon profile, if it has.
+pub fn aeon_tombstone(state: &State, address: &Address) -> Option<u64> {
+    state
+        .get_raw(&tombstone_key(address))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+}
+
+/// Delete an Aeon profile and leave a tombstone.
+///
+/// Everything in the profile goes, including its badges, Gnosis XP, and
+/// Syzygy Score, and its handle becomes free. Archon status is kept, since it
+/// is not part of the profile.
+pub fn delete_aeon_profile(state: &mut State, address: &Address) -> Result<(), String> {
+    let profile =
+        load_aeon_profile(state, address).ok_or_else(|| "Aeon profile not found".to_string())?;
+    let bytes = codec::encode(&state.block_height()).map_err(|e| e.to_string())?;
+    state.with_namespace(MODULE_ID, |state| {
+        if let Some(handle) = &profile.handle {
+            remove_handle_mapping(state, handle)?;
+        }
+        state
+            .delete_raw(&aeon_profile_key(address))
+            .map_err(|e| e.to_string())?;
+        state
+            .put_raw(tombstone_key(address), bytes)
+            .map_err(|e| e.to_string())
+    })
 }
 
 /// Create a new Aeon profile.
 ///
-/// Returns an error if a profile already exists for this address.
+/// Returns an error if a profile already exists for this address, or if
+/// the address deleted its profile.
 pub fn create_aeon_profile(
     state: &mut State,
     address: Address,
@@ -140,6 +422,12 @@ pub fn create_aeon_profile(
     if load_aeon_profile(state, &address).is_some() {
         return Err("Aeon profile already exists for this address".into());
     }
+    if let Some(height) = aeon_tombstone(state, &address) {
+        return Err(format!(
+            "Aeon profile for this address was deleted at height {}",
+            height
+        ));
+    }
 
     let profile = AeonProfile {
         address,
@@ -151,9 +439,11 @@ pub fn create_aeon_profile(
         ascension_level: 1,
         badges: vec![],
         created_at_height: current_height,
+        avatar_uri: None,
+        syzygy_decayed_at: current_height,
     };
 
-    store_aeon_profile(state, &profile)?;
+    state.with_namespace(MODULE_ID, |state| store_aeon_profile(state, &profile))?;
     Ok(profile)
 }
 
@@ -165,8 +455,7 @@ pub fn set_handle(
     address: Address,
     new_handle: String,
 ) -> Result<AeonProfile, String> {
-    // Normalize handle: lowercase, trim
-    let normalized = new_handle.trim().to_lowercase();
+    let normalized = normalize_handle(&new_handle);
 
     // Validate format: [a-z0-9_]{3,32}
     if normalized.len() < 3 || normalized.len() > 32 {
@@ -185,6 +474,17 @@ pub fn set_handle(
         // Same address, updating is fine
     }
 
+    // A handle and a registered name with the same text must belong to the
+    // same address, so `@name` resolves alike through either
+    if let Some(name_owner) = names::resolve_name(state, &normalized) {
+        if name_owner != address {
+            return Err(format!(
+                "Handle is registered as a name by {}",
+                name_owner.to_display()
+            ));
+        }
+    }
+
     // Load current profile
     let mut profile = load_aeon_profile(state, &address)
         .ok_or_else(|| "Aeon profile not found".to_string())?;
@@ -208,6 +508,28 @@ pub fn set_handle(
     Ok(profile)
 }
 
+/// Set or clear an Aeon's avatar URI.
+pub fn set_avatar_uri(
+    state: &mut State,
+    address: &Address,
+    avatar_uri: Option<String>,
+) -> Result<AeonProfile, String> {
+    if avatar_uri
+        .as_ref()
+        .is_some_and(|uri| uri.len() > MAX_AVATAR_URI_LEN)
+    {
+        return Err(format!(
+            "Avatar URI must be at most {} bytes",
+            MAX_AVATAR_URI_LEN
+        ));
+    }
+    let mut profile =
+        load_aeon_profile(state, address).ok_or_else(|| "Aeon profile not found".to_string())?;
+    profile.avatar_uri = avatar_uri;
+    state.with_namespace(MODULE_ID, |state| store_aeon_profile(state, &profile))?;
+    Ok(profile)
+}
+
 /// Get an Aeon profile by address.
 pub fn get_aeon_profile(state: &State, address: &Address) -> Option<AeonProfile> {
     load_aeon_profile(state, address)
@@ -223,8 +545,7 @@ pub fn add_gnosis_xp(state: &mut State, address: &Address, amount: u64) -> Resul
         .checked_add(amount)
         .ok_or("Gnosis XP overflow")?;
 
-    store_aeon_profile(state, &profile)?;
-    Ok(())
+    state.with_namespace(MODULE_ID, |state| store_aeon_profile(state, &profile))
 }
 
 /// Add Syzygy Score to an Aeon.
@@ -241,11 +562,8 @@ pub fn add_syzygy_score(state: &mut State, address: &Address, amount: u64) -> Re
     Ok(())
 }
 
-/// Recompute Ascension Level based on Gnosis XP and Syzygy Score.
-pub fn recompute_ascension(state: &mut State, address: &Address) -> Result<(), String> {
-    let mut profile = load_aeon_profile(state, address)
-        .ok_or_else(|| "Aeon profile not found".to_string())?;
-
+/// Ascension Level for a profile's Gnosis XP and Syzygy Score.
+fn ascension_level(profile: &AeonProfile) -> Result<u32, String> {
     // total_score = gnosis_xp + (syzygy_score * 2)
     let syzygy_weighted = profile
         .syzygy_score
@@ -257,28 +575,384 @@ pub fn recompute_ascension(state: &mut State, address: &Address) -> Result<(), S
         .ok_or("Total score overflow")?;
 
     // ascension_level = 1 + (total_score / ASCENSION_STEP)
-    profile.ascension_level = 1 + (total_score / ASCENSION_STEP) as u32;
-
-    store_aeon_profile(state, &profile)?;
-    Ok(())
+    Ok(1 + (total_score / ASCENSION_STEP) as u32)
 }
 
-/// Update badges based on thresholds.
-pub fn update_badges(state: &mut State, address: &Address) -> Result<(), String> {
+/// Recompute Ascension Level based on Gnosis XP and Syzygy Score.
+pub fn recompute_ascension(state: &mut State, address: &Address) -> Result<(), String> {
     let mut profile = load_aeon_profile(state, address)
         .ok_or_else(|| "Aeon profile not found".to_string())?;
+    profile.ascension_level = ascension_level(&profile)?;
+    state.with_namespace(MODULE_ID, |state| store_aeon_profile(state, &profile))
+}
 
-    // Check for Luminary badge
+/// Syzygy Score decay
+///
+/// Every `aeon.syzygy_decay_epoch_blocks` blocks (epochs start at multiples
+/// of it), an Aeon's Syzygy Score loses `aeon.syzygy_decay_bps` basis points
+/// of what is left, rounded down; Gnosis XP never decays. Decay is applied
+/// lazily, for every epoch boundary passed since `syzygy_decayed_at`, when a
+/// Syzygy is recorded for the Aeon, and `get_aeon_profile_at` shows it
+/// without storing it. Before governance changes either rate it checkpoints
+/// the rates in force until then (`checkpoint_syzygy_decay`), so missed
+/// epochs are caught up at the rates each boundary passed under, the same as
+/// decaying every Aeon at every boundary.
+
+/// Decay rates in force up to and including the height they are
+/// checkpointed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct DecayRates {
+    decay_bps: u64,
+    epoch_blocks: u64,
+}
+
+fn decay_checkpoint_key(height: u64) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_DECAY_CHECKPOINT)
+        .u64(height)
+        .into_bytes()
+}
+
+/// The decay rates governance has set now.
+fn current_decay_rates(state: &State) -> DecayRates {
+    DecayRates {
+        decay_bps: governance::param(state, governance::PARAM_SYZYGY_DECAY_BPS)
+            .unwrap_or(DEFAULT_SYZYGY_DECAY_BPS),
+        epoch_blocks: governance::param(state, governance::PARAM_SYZYGY_DECAY_EPOCH_BLOCKS)
+            .unwrap_or(DEFAULT_SYZYGY_DECAY_EPOCH_BLOCKS)
+            .max(1),
+    }
+}
+
+/// Record that the current decay rates apply up to and including this
+/// block; governance calls it before changing either rate. A second change
+/// within the block keeps the first checkpoint, since the rates between the
+/// two were never in force at a boundary.
+pub fn checkpoint_syzygy_decay(state: &mut State) -> Result<(), String> {
+    let key = decay_checkpoint_key(state.block_height());
+    if state.get_raw(&key).is_some() {
+        return Ok(());
+    }
+    let bytes = codec::encode(&current_decay_rates(state)).map_err(|e| e.to_string())?;
+    state.with_namespace(MODULE_ID, |state| {
+        state.put_raw(key, bytes).map_err(|e| e.to_string())
+    })
+}
+
+/// The decay rates over `(after, height]`, in spans `(start, end]` with
+/// the rates in force over each: checkpointed spans first, then the current
+/// rates up to `height`.
+fn decay_spans(state: &State, after: u64, height: u64) -> Vec<(u64, u64, DecayRates)> {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_DECAY_CHECKPOINT).into_bytes();
+    let mut spans = Vec::new();
+    let mut start = after;
+    for (key, bytes) in state.scan_prefix(&prefix) {
+        let Ok(end) = <[u8; 8]>::try_from(&key[prefix.len()..]).map(u64::from_be_bytes) else {
+            continue;
+        };
+        let Ok(rates) = codec::decode_state::<DecayRates>(&bytes) else {
+            continue;
+        };
+        if end <= start {
+            continue;
+        }
+        if end >= height {
+            spans.push((start, height, rates));
+            return spans;
+        }
+        spans.push((start, end, rates));
+        start = end;
+    }
+    spans.push((start, height, current_decay_rates(state)));
+    spans
+}
+
+/// Syzygy Score left of `score` after `epochs` decay epochs at `decay_bps`.
+///
+/// The same as applying the epochs one at a time: it stops early only once
+/// an epoch would take nothing, so every later one would take nothing too.
+pub fn decay_syzygy_score(score: u64, decay_bps: u64, epochs: u64) -> u64 {
+    let decay_bps = u128::from(decay_bps.min(10_000));
+    let mut score = score;
+    for _ in 0..epochs {
+        let lost = (u128::from(score) * decay_bps / 10_000) as u64;
+        if lost == 0 {
+            break;
+        }
+        score -= lost;
+    }
+    score
+}
+
+/// Apply the decay due by `height` to a profile's Syzygy Score, recomputing
+/// its Ascension Level if any was lost.
+///
+/// # Returns
+/// The Syzygy Score lost
+fn apply_syzygy_decay(state: &State, profile: &mut AeonProfile, height: u64) -> u64 {
+    if height <= profile.syzygy_decayed_at {
+        return 0;
+    }
+    let before = profile.syzygy_score;
+    for (start, end, rates) in decay_spans(state, profile.syzygy_decayed_at, height) {
+        let epochs = end / rates.epoch_blocks - start / rates.epoch_blocks;
+        profile.syzygy_score = decay_syzygy_score(profile.syzygy_score, rates.decay_bps, epochs);
+    }
+    profile.syzygy_decayed_at = height;
+    let lost = before - profile.syzygy_score;
+    if lost > 0 {
+        // Losing score cannot overflow a level that computed before
+        profile.ascension_level = ascension_level(profile).unwrap_or(profile.ascension_level);
+        refresh_score_badges(state, profile);
+    }
+    lost
+}
+
+/// Apply the Syzygy Score decay due at the current block height to an
+/// Aeon's profile, whose Ascension Level and score badges may drop with it.
+///
+/// Emits a `decay` event if any score was lost.
+///
+/// # Returns
+/// The Syzygy Score lost
+pub fn decay_syzygy(state: &mut State, address: &Address) -> Result<u64, String> {
+    let mut profile =
+        load_aeon_profile(state, address).ok_or_else(|| "Aeon profile not found".to_string())?;
+    let height = state.block_height();
+    if height <= profile.syzygy_decayed_at {
+        return Ok(0);
+    }
+    let lost = apply_syzygy_decay(state, &mut profile, height);
+    state.with_namespace(MODULE_ID, |state| store_aeon_profile(state, &profile))?;
+    if lost > 0 {
+        state.emit_event(
+            Event::new(MODULE_ID, "decay", *address)
+                .with("lost", lost)
+                .with("syzygy_score", profile.syzygy_score)
+                .with("ascension_level", profile.ascension_level),
+        );
+    }
+    Ok(lost)
+}
+
+/// An Aeon's profile with the Syzygy Score decay due by `height` applied,
+/// as it would read once next touched (for RPC/SDK use).
+pub fn get_aeon_profile_at(state: &State, address: &Address, height: u64) -> Option<AeonProfile> {
+    let mut profile = load_aeon_profile(state, address)?;
+    apply_syzygy_decay(state, &mut profile, height);
+    Some(profile)
+}
+
+/// Award the badges a profile's Syzygy Score has reached, and remove those
+/// it has fallen below (e.g. by decay or a raised threshold).
+fn refresh_score_badges(state: &State, profile: &mut AeonProfile) {
+    let threshold = governance::param(state, governance::PARAM_LUMINARY_SYZYGY_THRESHOLD)
+        .unwrap_or(DEFAULT_LUMINARY_SYZYGY_THRESHOLD);
     let has_luminary = profile.badges.iter().any(|b| b == "Luminary");
-    if profile.syzygy_score >= LUMINARY_SYZYGY_THRESHOLD && !has_luminary {
+    let earns_luminary = profile.syzygy_score >= threshold;
+    if earns_luminary && !has_luminary {
         profile.badges.push("Luminary".to_string());
+    } else if !earns_luminary && has_luminary {
+        profile.badges.retain(|b| b != "Luminary");
     }
+}
 
+/// Update badges based on thresholds.
+pub fn update_badges(state: &mut State, address: &Address) -> Result<(), String> {
+    let mut profile = load_aeon_profile(state, address)
+        .ok_or_else(|| "Aeon profile not found".to_string())?;
+    refresh_score_badges(state, &mut profile);
     store_aeon_profile(state, &profile)?;
     Ok(())
 }
 
+/// Award or remove a badge granted by another module (e.g., "Bonded").
+///
+/// No-op if the profile already matches.
+pub fn set_badge(
+    state: &mut State,
+    address: &Address,
+    badge: &str,
+    present: bool,
+) -> Result<(), String> {
+    let mut profile = load_aeon_profile(state, address)
+        .ok_or_else(|| "Aeon profile not found".to_string())?;
+
+    let has_badge = profile.badges.iter().any(|b| b == badge);
+    if has_badge == present {
+        return Ok(());
+    }
+    if present {
+        profile.badges.push(badge.to_string());
+    } else {
+        profile.badges.retain(|b| b != badge);
+    }
+
+    state.with_namespace(MODULE_ID, |state| store_aeon_profile(state, &profile))
+}
+
+/// Syzygy weight credited in a window: along one `(from, to)` edge, or to
+/// one seeder in all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyzygyWindow {
+    /// First height of the window the total belongs to.
+    pub window_start: u64,
+    /// Weight credited so far in that window, at most `SYZYGY_EDGE_CAP` for
+    /// an edge and `SYZYGY_SEEDER_CAP` for a seeder.
+    pub credited: u64,
+}
+
+fn syzygy_edge_key(from: &Address, to: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_SYZYGY_EDGE)
+        .address(from)
+        .address(to)
+        .into_bytes()
+}
+
+fn syzygy_seeder_key(from: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_SYZYGY_SEEDER)
+        .address(from)
+        .into_bytes()
+}
+
+/// The total stored under `key` if it belongs to the window containing
+/// `height`, or an empty one.
+fn syzygy_window(state: &State, key: &[u8], height: u64) -> SyzygyWindow {
+    let window_start = height - height % SYZYGY_WINDOW_BLOCKS;
+    state
+        .get_raw(key)
+        .and_then(|bytes| codec::decode_state::<SyzygyWindow>(&bytes).ok())
+        .filter(|window| window.window_start == window_start)
+        .unwrap_or(SyzygyWindow {
+            window_start,
+            credited: 0,
+        })
+}
+
+fn store_syzygy_window(
+    state: &mut State,
+    key: Vec<u8>,
+    window: &SyzygyWindow,
+) -> Result<(), String> {
+    let bytes = codec::encode(window).map_err(|e| e.to_string())?;
+    state.with_namespace(MODULE_ID, |state| {
+        state.put_raw(key, bytes).map_err(|e| e.to_string())
+    })
+}
+
+/// Weight credited along the `(from, to)` edge in the window containing
+/// `height`.
+pub fn syzygy_edge(state: &State, from: &Address, to: &Address, height: u64) -> SyzygyWindow {
+    syzygy_window(state, &syzygy_edge_key(from, to), height)
+}
+
+/// Weight credited to the seeder `from` across all edges in the window
+/// containing `height`.
+pub fn syzygy_seeder_total(state: &State, from: &Address, height: u64) -> SyzygyWindow {
+    syzygy_window(state, &syzygy_seeder_key(from), height)
+}
+
+/// Credit a Syzygy of `from` seeding `to`'s content: the weight left under
+/// both the edge's cap and the seeder's cap for the current window as
+/// Syzygy Score, and half as much Gnosis XP, then refresh the seeder's
+/// Ascension Level and badges. Decay due on the seeder's score is applied
+/// first (see `decay_syzygy`).
+///
+/// # Returns
+/// - `Ok` with the weight credited, which is less than `weight` once a cap
+///   is reached
+/// - `Err` if `from` and `to` are the same, or `to` has no Aeon profile
+pub fn record_syzygy(
+    state: &mut State,
+    from: &Address,
+    to: &Address,
+    weight: u64,
+) -> Result<u64, String> {
+    if from == to {
+        return Err("an Aeon cannot record a Syzygy with its own content".into());
+    }
+    if load_aeon_profile(state, to).is_none() {
+        return Err(format!("{} has no Aeon profile", to.to_display()));
+    }
+    decay_syzygy(state, from)?;
+    let height = state.block_height();
+    let mut edge = syzygy_edge(state, from, to, height);
+    let mut seeder = syzygy_seeder_total(state, from, height);
+    let credited = weight
+        .min(SYZYGY_EDGE_CAP.saturating_sub(edge.credited))
+        .min(SYZYGY_SEEDER_CAP.saturating_sub(seeder.credited));
+    if credited > 0 {
+        edge.credited += credited;
+        seeder.credited += credited;
+        store_syzygy_window(state, syzygy_edge_key(from, to), &edge)?;
+        store_syzygy_window(state, syzygy_seeder_key(from), &seeder)?;
+        add_syzygy_score(state, from, credited)?;
+        let xp_gain = credited / 2;
+        if xp_gain > 0 {
+            add_gnosis_xp(state, from, xp_gain)?;
+        }
+    }
+    recompute_ascension(state, from)?;
+    update_badges(state, from)?;
+    Ok(credited)
+}
+
+/// Recorder delegation
+///
+/// An Aeon may let other addresses record Syzygies on its behalf. A grant is
+/// a marker keyed by the Aeon and then the recorder.
+
+fn recorder_key(aeon: &Address, recorder: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_RECORDER)
+        .address(aeon)
+        .address(recorder)
+        .into_bytes()
+}
+
+/// Whether `aeon` has granted `recorder` the recorder role.
+pub fn is_recorder(state: &State, aeon: &Address, recorder: &Address) -> bool {
+    state.get_raw(&recorder_key(aeon, recorder)).is_some()
+}
+
+/// Record Syzygy parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordSyzygyParams {
+    /// The seeding Aeon credited with the Syzygy.
+    pub from: Address,
+    /// The Aeon whose original content was seeded.
+    pub to: Address,
+    pub weight: u64,
+}
+
+/// Grant or revoke recorder parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecorderParams {
+    pub recorder: Address,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    ("claim_archon", CallSchema::new(&[])),
+    (
+        "record_syzygy",
+        CallSchema::new(&[
+            FieldSchema::new("from", FieldType::Address),
+            FieldSchema::new("to", FieldType::Address),
+            FieldSchema::new("weight", FieldType::U64),
+        ]),
+    ),
+    (
+        "grant_recorder",
+        CallSchema::new(&[FieldSchema::new("recorder", FieldType::Address)]),
+    ),
+    (
+        "revoke_recorder",
+        CallSchema::new(&[FieldSchema::new("recorder", FieldType::Address)]),
+    ),
+    ("delete_aeon_profile", CallSchema::new(&[])),
+];
+
 /// AvatarsProfilesModule (now Aeon Registry) handles profiles and progression
+#[derive(Default)]
 pub struct AvatarsProfilesModule;
 
 impl AvatarsProfilesModule {
@@ -289,12 +963,34 @@ impl AvatarsProfilesModule {
 
 impl RuntimeModule for AvatarsProfilesModule {
     fn module_id(&self) -> &'static str {
-        "avatars_profiles"
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &[
+            "claim_archon",
+            "record_syzygy",
+            "grant_recorder",
+            "revoke_recorder",
+            "delete_aeon_profile",
+        ]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
     }
 
     fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
         match call_id {
             "claim_archon" => handle_claim_archon(tx, state),
+            "record_syzygy" => handle_record_syzygy(tx, state),
+            "grant_recorder" => handle_grant_recorder(tx, state),
+            "revoke_recorder" => handle_revoke_recorder(tx, state),
+            "delete_aeon_profile" => delete_aeon_profile(state, &tx.from),
             other => Err(format!("avatars_profiles: unknown call_id '{}'", other)),
         }
     }
@@ -302,14 +998,70 @@ impl RuntimeModule for AvatarsProfilesModule {
 
 fn handle_claim_archon(tx: &Transaction, state: &mut State) -> Result<(), String> {
     // Legacy: mark as Archon (for backward compatibility)
-    set_archon_flag(state, &tx.from, true)
+    grant_archon(state, &tx.from)
+}
+
+fn handle_record_syzygy(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RecordSyzygyParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    // The seeding Aeon records its own Syzygies or delegates that to recorders
+    if tx.from != params.from && !is_recorder(state, &params.from, &tx.from) {
+        return Err("only the seeding Aeon or one of its recorders may record a Syzygy".into());
+    }
+    // Consuming the sender's nonce keeps an included record from replaying
+    let nonce = bank::nonce(state, &tx.from);
+    if tx.nonce != nonce {
+        return Err(format!(
+            "invalid nonce: expected {}, got {}",
+            nonce, tx.nonce
+        ));
+    }
+
+    let credited = record_syzygy(state, &params.from, &params.to, params.weight)?;
+    bank::consume_nonce(state, &tx.from, tx.nonce)?;
+    state.emit_event(
+        Event::new(MODULE_ID, "syzygy", params.from)
+            .with("to", params.to.to_display())
+            .with("weight", params.weight)
+            .with("credited", credited),
+    );
+    Ok(())
+}
+
+fn handle_grant_recorder(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RecorderParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if params.recorder == tx.from {
+        return Err("an Aeon always records its own Syzygies".into());
+    }
+    if load_aeon_profile(state, &tx.from).is_none() {
+        return Err("Aeon profile not found".into());
+    }
+
+    state
+        .put_raw(recorder_key(&tx.from, &params.recorder), vec![1u8])
+        .map_err(|e| e.to_string())
+}
+
+fn handle_revoke_recorder(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RecorderParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if !is_recorder(state, &tx.from, &params.recorder) {
+        return Err("address is not a recorder for this Aeon".into());
+    }
+
+    state
+        .delete_raw(&recorder_key(&tx.from, &params.recorder))
+        .map_err(|e| e.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::state::State;
+    use crate::core::transaction::test_keys::{account, signed};
     use crate::core::transaction::{Address, Transaction};
+    use crate::runtime::Runtime;
 
     #[test]
     fn test_is_archon_default_false() {
@@ -331,6 +1083,8 @@ mod tests {
             payload: vec![],
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         let module = AvatarsProfilesModule::new();
@@ -339,6 +1093,41 @@ mod tests {
         assert!(is_archon(&state, &addr));
     }
 
+    #[test]
+    fn test_archon_index_follows_grant_and_revoke() {
+        let mut state = State::in_memory();
+        for addr in [[3u8; 32], [1; 32], [2; 32]] {
+            grant_archon(&mut state, &addr).unwrap();
+        }
+        // Granting twice does not duplicate
+        grant_archon(&mut state, &[1; 32]).unwrap();
+        assert_eq!(
+            list_archons(&state, 0, 10),
+            (3, vec![[1; 32], [2; 32], [3; 32]])
+        );
+        assert_eq!(list_archons(&state, 1, 1), (3, vec![[2; 32]]));
+        assert_eq!(list_archons(&state, 5, 10), (3, vec![]));
+
+        revoke_archon(&mut state, &[2; 32]).unwrap();
+        assert!(!is_archon(&state, &[2; 32]));
+        assert_eq!(list_archons(&state, 0, 10), (2, vec![[1; 32], [3; 32]]));
+        assert_eq!(archon_count(&state), 2);
+    }
+
+    #[test]
+    fn test_backfill_archon_index_from_flags() {
+        // Pre-index database: flags only, including a revoked one
+        let mut state = State::in_memory();
+        state.put_raw(archon_flag_key(&[5; 32]), vec![1]).unwrap();
+        state.put_raw(archon_flag_key(&[4; 32]), vec![1]).unwrap();
+        state.put_raw(archon_flag_key(&[6; 32]), vec![0]).unwrap();
+        assert!(state.get_raw(&archon_index_key()).is_none());
+
+        assert_eq!(backfill_archon_index(&mut state), Ok(2));
+        assert_eq!(list_archons(&state, 0, 10), (2, vec![[4; 32], [5; 32]]));
+        assert_eq!(backfill_archon_index(&mut state), Ok(0));
+    }
+
     #[test]
     fn test_create_aeon_profile() {
         let mut state = State::in_memory();
@@ -370,6 +1159,385 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_v1_profile_upgrades_on_read() {
+        let mut state = State::in_memory();
+        let addr = [4u8; 32];
+        // Version 1 layout as stored before versioning: no prefix, no avatar_uri
+        let v1 = (
+            addr,
+            "Old Aeon".to_string(),
+            Some("from v1".to_string()),
+            Some("old_aeon".to_string()),
+            700u64,
+            20u64,
+            1u32,
+            vec!["Luminary".to_string()],
+            9u64,
+        );
+        state
+            .put_raw(aeon_profile_key(&addr), codec::encode(&v1).unwrap())
+            .unwrap();
+
+        let profile = get_aeon_profile(&state, &addr).unwrap();
+        assert_eq!(profile.display_name, "Old Aeon");
+        assert_eq!(profile.handle.as_deref(), Some("old_aeon"));
+        assert_eq!(profile.gnosis_xp, 700);
+        assert_eq!(profile.badges, vec!["Luminary".to_string()]);
+        assert_eq!(profile.created_at_height, 9);
+        assert_eq!(profile.avatar_uri, None);
+
+        assert_eq!(profile.syzygy_decayed_at, 9);
+
+        // The next write stores it in the current version
+        add_gnosis_xp(&mut state, &addr, 1).unwrap();
+        let bytes = state.get_raw(&aeon_profile_key(&addr)).unwrap();
+        assert_eq!(bytes[0], <AeonProfile as codec::Versioned>::VERSION);
+        assert_eq!(get_aeon_profile(&state, &addr).unwrap().gnosis_xp, 701);
+
+        // Versions from the future are refused rather than misread
+        let mut future = codec::encode_versioned(&profile).unwrap();
+        future[0] = <AeonProfile as codec::Versioned>::VERSION + 1;
+        assert!(codec::decode_versioned::<AeonProfile>(&future).is_err());
+    }
+
+    #[test]
+    fn test_set_avatar_uri() {
+        let mut state = State::in_memory();
+        let addr = [1u8; 32];
+        create_aeon_profile(&mut state, addr, "Test".to_string(), None, 0).unwrap();
+
+        let uri = "ipfs://avatar".to_string();
+        set_avatar_uri(&mut state, &addr, Some(uri.clone())).unwrap();
+        assert_eq!(
+            get_aeon_profile(&state, &addr).unwrap().avatar_uri,
+            Some(uri)
+        );
+
+        let too_long = "x".repeat(MAX_AVATAR_URI_LEN + 1);
+        assert!(set_avatar_uri(&mut state, &addr, Some(too_long)).is_err());
+        assert!(set_avatar_uri(&mut state, &[9; 32], None).is_err());
+    }
+
+    #[test]
+    fn test_record_syzygy_needs_seeder_or_recorder() {
+        let mut state = State::in_memory();
+        let seeder = account(1);
+        let recorder = account(2);
+        let author = account(3);
+        create_aeon_profile(&mut state, seeder, "Seeder".to_string(), None, 0).unwrap();
+        create_aeon_profile(&mut state, author, "Author".to_string(), None, 0).unwrap();
+
+        let runtime = Runtime::with_default_modules();
+        let tx = |from: Address, nonce: u64, call_id: &str, payload: Vec<u8>| {
+            signed(Transaction {
+                from,
+                nonce,
+                module_id: MODULE_ID.to_string(),
+                call_id: call_id.to_string(),
+                payload,
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            })
+        };
+        let record = codec::encode(&RecordSyzygyParams {
+            from: seeder,
+            to: author,
+            weight: 100,
+        })
+        .unwrap();
+        let role = codec::encode(&RecorderParams { recorder }).unwrap();
+        let score = |state: &State| get_aeon_profile(state, &seeder).unwrap().syzygy_score;
+
+        let err = runtime
+            .dispatch_tx(
+                &tx(recorder, 0, "record_syzygy", record.clone()),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("only the seeding Aeon"));
+        let by_seeder = tx(seeder, 0, "record_syzygy", record.clone());
+        runtime.dispatch_tx(&by_seeder, &mut state).unwrap();
+        assert_eq!(score(&state), 100);
+
+        // An included record cannot be replayed, and `from` must have signed
+        let err = runtime.dispatch_tx(&by_seeder, &mut state).unwrap_err();
+        assert!(err.contains("invalid nonce"));
+        let mut forged = tx(seeder, 1, "record_syzygy", record.clone());
+        forged.from = recorder;
+        let mut unsigned = tx(seeder, 1, "record_syzygy", record.clone());
+        unsigned.signature.clear();
+        for tx in [forged, unsigned] {
+            let err = runtime.dispatch_tx(&tx, &mut state).unwrap_err();
+            assert!(err.contains("Invalid signature"), "{}", err);
+        }
+        assert_eq!(score(&state), 100);
+
+        runtime
+            .dispatch_tx(&tx(seeder, 1, "grant_recorder", role.clone()), &mut state)
+            .unwrap();
+        assert!(is_recorder(&state, &seeder, &recorder));
+        runtime
+            .dispatch_tx(
+                &tx(recorder, 0, "record_syzygy", record.clone()),
+                &mut state,
+            )
+            .unwrap();
+        let profile = get_aeon_profile(&state, &seeder).unwrap();
+        assert_eq!(profile.syzygy_score, 200);
+        assert_eq!(profile.gnosis_xp, 100);
+
+        runtime
+            .dispatch_tx(&tx(seeder, 1, "revoke_recorder", role.clone()), &mut state)
+            .unwrap();
+        assert!(!is_recorder(&state, &seeder, &recorder));
+        assert!(runtime
+            .dispatch_tx(&tx(recorder, 1, "record_syzygy", record), &mut state)
+            .is_err());
+        assert!(runtime
+            .dispatch_tx(&tx(seeder, 1, "revoke_recorder", role), &mut state)
+            .is_err());
+    }
+
+    #[test]
+    fn test_syzygy_edge_cap_clamps_and_resets_each_window() {
+        let mut state = State::in_memory();
+        let seeder = [1u8; 32];
+        let author = [2u8; 32];
+        let other = [3u8; 32];
+        for (addr, name) in [(seeder, "Seeder"), (author, "Author"), (other, "Other")] {
+            create_aeon_profile(&mut state, addr, name.to_string(), None, 0).unwrap();
+        }
+        let score = |state: &State| get_aeon_profile(state, &seeder).unwrap().syzygy_score;
+
+        state.set_block_height(SYZYGY_WINDOW_BLOCKS + 5);
+        assert_eq!(
+            record_syzygy(&mut state, &seeder, &author, SYZYGY_EDGE_CAP - 100).unwrap(),
+            SYZYGY_EDGE_CAP - 100
+        );
+        // Only what is left under the cap counts, then nothing does
+        assert_eq!(
+            record_syzygy(&mut state, &seeder, &author, 500).unwrap(),
+            100
+        );
+        assert_eq!(record_syzygy(&mut state, &seeder, &author, 500).unwrap(), 0);
+        assert_eq!(score(&state), SYZYGY_EDGE_CAP);
+        assert_eq!(
+            get_aeon_profile(&state, &seeder).unwrap().gnosis_xp,
+            SYZYGY_EDGE_CAP / 2
+        );
+
+        // Each edge has its own cap
+        assert_eq!(
+            record_syzygy(&mut state, &seeder, &other, 300).unwrap(),
+            300
+        );
+
+        // The last block of the window is still capped; the next window starts afresh
+        state.set_block_height(2 * SYZYGY_WINDOW_BLOCKS - 1);
+        assert_eq!(record_syzygy(&mut state, &seeder, &author, 500).unwrap(), 0);
+        state.set_block_height(2 * SYZYGY_WINDOW_BLOCKS);
+        assert_eq!(
+            syzygy_edge(&state, &seeder, &author, 2 * SYZYGY_WINDOW_BLOCKS).credited,
+            0
+        );
+        assert_eq!(
+            record_syzygy(&mut state, &seeder, &author, 500).unwrap(),
+            500
+        );
+        assert_eq!(score(&state), SYZYGY_EDGE_CAP + 800);
+        assert_eq!(
+            syzygy_edge(&state, &seeder, &author, 2 * SYZYGY_WINDOW_BLOCKS),
+            SyzygyWindow {
+                window_start: 2 * SYZYGY_WINDOW_BLOCKS,
+                credited: 500,
+            }
+        );
+    }
+
+    #[test]
+    fn test_syzygy_needs_another_aeon_and_caps_each_seeder() {
+        let mut state = State::in_memory();
+        let seeder = [1u8; 32];
+        create_aeon_profile(&mut state, seeder, "Seeder".to_string(), None, 0).unwrap();
+
+        // Neither the seeder itself nor an address without a profile counts
+        let err = record_syzygy(&mut state, &seeder, &seeder, 100).unwrap_err();
+        assert!(err.contains("its own content"), "{}", err);
+        let err = record_syzygy(&mut state, &seeder, &[9; 32], 100).unwrap_err();
+        assert!(err.contains("has no Aeon profile"), "{}", err);
+
+        // Rotating through authors stops at the seeder's cap for the window
+        let authors = SYZYGY_SEEDER_CAP / SYZYGY_EDGE_CAP + 1;
+        let mut credited = 0;
+        for i in 0..authors {
+            let author = [10 + i as u8; 32];
+            create_aeon_profile(&mut state, author, "Author".to_string(), None, 0).unwrap();
+            credited += record_syzygy(&mut state, &seeder, &author, SYZYGY_EDGE_CAP).unwrap();
+        }
+        assert_eq!(credited, SYZYGY_SEEDER_CAP);
+        assert_eq!(
+            syzygy_seeder_total(&state, &seeder, 0).credited,
+            SYZYGY_SEEDER_CAP
+        );
+        assert_eq!(
+            get_aeon_profile(&state, &seeder).unwrap().syzygy_score,
+            SYZYGY_SEEDER_CAP
+        );
+
+        // and starts afresh in the next one
+        state.set_block_height(SYZYGY_WINDOW_BLOCKS);
+        assert_eq!(
+            record_syzygy(&mut state, &seeder, &[10; 32], 100).unwrap(),
+            100
+        );
+    }
+
+    fn set_decay(state: &mut State, decay_bps: u64, epoch_blocks: u64) {
+        for (param, value) in [
+            (governance::PARAM_SYZYGY_DECAY_BPS, decay_bps),
+            (governance::PARAM_SYZYGY_DECAY_EPOCH_BLOCKS, epoch_blocks),
+        ] {
+            let key = StorageKey::new("governance", "param")
+                .bytes(param.as_bytes())
+                .into_bytes();
+            state.put_raw(key, codec::encode(&value).unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_syzygy_decay_catch_up_matches_stepwise() {
+        // One epoch takes its share rounded down, and never underflows
+        assert_eq!(decay_syzygy_score(10_000, 1_000, 1), 9_000);
+        assert_eq!(decay_syzygy_score(9, 1_000, 1), 9);
+        assert_eq!(decay_syzygy_score(u64::MAX, 10_000, 1), 0);
+        assert_eq!(decay_syzygy_score(0, 5_000, 3), 0);
+
+        for (score, decay_bps) in [(10_000, 1_000), (123_456_789, 250), (u64::MAX, 1)] {
+            let mut stepwise = score;
+            for epochs in 0..200 {
+                assert_eq!(decay_syzygy_score(score, decay_bps, epochs), stepwise);
+                stepwise = decay_syzygy_score(stepwise, decay_bps, 1);
+            }
+        }
+        // A long backlog ends where single epochs stop taking anything
+        assert_eq!(decay_syzygy_score(10_000, 1_000, u64::MAX), 9);
+    }
+
+    #[test]
+    fn test_syzygy_decay_drops_ascension_level() {
+        let mut state = State::in_memory();
+        let seeder = [1u8; 32];
+        for (addr, name) in [(seeder, "Seeder"), ([2; 32], "Author"), ([3; 32], "Other")] {
+            create_aeon_profile(&mut state, addr, name.to_string(), None, 0).unwrap();
+        }
+        set_decay(&mut state, 5_000, 100);
+
+        state.set_block_height(50);
+        record_syzygy(&mut state, &seeder, &[2; 32], 1_000).unwrap();
+        // 500 Gnosis XP + 2 * 1_000 Syzygy Score
+        assert_eq!(
+            get_aeon_profile(&state, &seeder).unwrap().ascension_level,
+            3
+        );
+
+        // Passing one epoch boundary halves the score but not the XP; reads
+        // show that without storing it
+        let view = get_aeon_profile_at(&state, &seeder, 150).unwrap();
+        assert_eq!((view.syzygy_score, view.gnosis_xp), (500, 500));
+        assert_eq!(view.ascension_level, 2);
+        assert_eq!(
+            get_aeon_profile(&state, &seeder).unwrap().syzygy_score,
+            1_000
+        );
+
+        // Three boundaries passed at once decay as three epochs
+        state.set_block_height(399);
+        assert_eq!(decay_syzygy(&mut state, &seeder).unwrap(), 875);
+        let profile = get_aeon_profile(&state, &seeder).unwrap();
+        assert_eq!((profile.syzygy_score, profile.gnosis_xp), (125, 500));
+        assert_eq!(profile.ascension_level, 1);
+        assert_eq!(profile.syzygy_decayed_at, 399);
+        // and are not applied again within the epoch
+        record_syzygy(&mut state, &seeder, &[3; 32], 100).unwrap();
+        assert_eq!(get_aeon_profile(&state, &seeder).unwrap().syzygy_score, 225);
+    }
+
+    #[test]
+    fn test_syzygy_decay_keeps_rates_across_changes_and_revokes_badges() {
+        let mut state = State::in_memory();
+        let seeder = [1u8; 32];
+        create_aeon_profile(&mut state, seeder, "Seeder".to_string(), None, 0).unwrap();
+        set_decay(&mut state, 5_000, 100);
+        add_syzygy_score(&mut state, &seeder, 40_000).unwrap();
+        update_badges(&mut state, &seeder).unwrap();
+        assert_eq!(
+            get_aeon_profile(&state, &seeder).unwrap().badges,
+            vec!["Luminary".to_string()]
+        );
+
+        // Boundaries 100 and 200 pass at 50%; then decay is switched off at
+        // 250 and the epoch lengthened at 450, before the seeder is touched
+        state.set_block_height(250);
+        checkpoint_syzygy_decay(&mut state).unwrap();
+        set_decay(&mut state, 0, 100);
+        state.set_block_height(450);
+        checkpoint_syzygy_decay(&mut state).unwrap();
+        set_decay(&mut state, 5_000, 1_000);
+
+        // Boundary 1_000 takes half again, as stepwise decay would have
+        state.set_block_height(999);
+        let view = get_aeon_profile_at(&state, &seeder, 999).unwrap();
+        assert_eq!(view.syzygy_score, 10_000);
+        assert_eq!(view.badges, vec!["Luminary".to_string()]);
+        state.set_block_height(1_000);
+        assert_eq!(decay_syzygy(&mut state, &seeder).unwrap(), 35_000);
+        let profile = get_aeon_profile(&state, &seeder).unwrap();
+        assert_eq!(profile.syzygy_score, 5_000);
+        assert!(profile.badges.is_empty());
+    }
+
+    #[test]
+    fn test_delete_aeon_profile_leaves_tombstone() {
+        let mut state = State::in_memory();
+        let addr = [1u8; 32];
+        create_aeon_profile(&mut state, addr, "Leaving".to_string(), None, 0).unwrap();
+        set_handle(&mut state, addr, "leaving".to_string()).unwrap();
+        add_syzygy_score(&mut state, &addr, 500).unwrap();
+        grant_archon(&mut state, &addr).unwrap();
+
+        let tx = Transaction {
+            from: addr,
+            nonce: 0,
+            module_id: MODULE_ID.to_string(),
+            call_id: "delete_aeon_profile".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let module = AvatarsProfilesModule::new();
+        state.set_block_height(12);
+        module
+            .dispatch("delete_aeon_profile", &tx, &mut state)
+            .unwrap();
+
+        assert!(get_aeon_profile(&state, &addr).is_none());
+        assert_eq!(aeon_tombstone(&state, &addr), Some(12));
+        assert_eq!(get_address_by_handle(&state, "leaving"), None);
+        assert!(is_archon(&state, &addr));
+
+        // Nothing is left to delete, and the address cannot start over
+        assert!(module
+            .dispatch("delete_aeon_profile", &tx, &mut state)
+            .is_err());
+        let err = create_aeon_profile(&mut state, addr, "Again".to_string(), None, 13).unwrap_err();
+        assert!(err.contains("deleted at height 12"));
+    }
+
     #[test]
     fn test_progression() {
         let mut state = State::in_memory();
@@ -398,10 +1566,20 @@ mod tests {
 
         create_aeon_profile(&mut state, addr, "Test".to_string(), None, 0).unwrap();
 
-        add_syzygy_score(&mut state, &addr, LUMINARY_SYZYGY_THRESHOLD).unwrap();
+        add_syzygy_score(&mut state, &addr, DEFAULT_LUMINARY_SYZYGY_THRESHOLD).unwrap();
         update_badges(&mut state, &addr).unwrap();
 
         let profile = get_aeon_profile(&state, &addr).unwrap();
         assert!(profile.badges.contains(&"Luminary".to_string()));
+
+        // A threshold raised by governance takes the badge back
+        let key = StorageKey::new("governance", "param")
+            .bytes(governance::PARAM_LUMINARY_SYZYGY_THRESHOLD.as_bytes())
+            .into_bytes();
+        let raised = DEFAULT_LUMINARY_SYZYGY_THRESHOLD + 1;
+        state.put_raw(key, codec::encode(&raised).unwrap()).unwrap();
+        update_badges(&mut state, &addr).unwrap();
+        let profile = get_aeon_profile(&state, &addr).unwrap();
+        assert!(profile.badges.is_empty());
     }
 }
@@ -3,59 +3,173 @@
 //! This module handles:
 //! - CGT balance tracking per address
 //! - Transfers between addresses
-//! - Minting (restricted to genesis authority for now)
+//! - Minting, restricted to the mint authorities in the genesis config
+//! - Total supply, capped at `CGT_MAX_SUPPLY`
+//! - Module accounts: addresses derived from a module id that hold funds on
+//!   the module's behalf and that only that module's dispatch can spend
+//! - The existential deposit (`bank.existential_deposit`): a transfer that
+//!   would leave its recipient holding less is refused, and a debit that
+//!   would leave less reaps the account, sweeping the remainder to the fee
+//!   pool and deleting its balance key. Genesis Archons and module accounts
+//!   are exempt. Credits made by other modules (payouts, refunds, royalties)
+//!   are never refused: their recipients did not choose the amount
+//! - Transfer memos: an optional reference of at most `bank.max_memo_bytes`
+//!   bytes, recorded in the transfer event and nowhere else
+//! - Transfers to an Aeon handle (`transfer_to_handle`), resolved to the
+//!   address holding it when the transaction executes
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use super::RuntimeModule;
+use super::module_api::{avatars, governance};
+use super::storage_value::{decode_u64, get_u64, put_u64, raise_u64};
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::{
+    GenesisConfig, CGT_MAX_SUPPLY, DEFAULT_EXISTENTIAL_DEPOSIT, DEFAULT_MAX_MEMO_BYTES,
+};
+use crate::core::codec;
+use crate::core::receipt::Event;
 use crate::core::state::State;
-use crate::core::transaction::{Address, Transaction};
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
 
-const PREFIX_BALANCE: &[u8] = b"bank:balance:";
-const PREFIX_NONCE: &[u8] = b"bank:nonce:";
+const MODULE_ID: &str = "bank_cgt";
+const PREFIX_BALANCE: &str = "balance";
+const PREFIX_NONCE: &str = "nonce";
+const PREFIX_ACCOUNT: &str = "account";
+const PREFIX_SUPPLY: &str = "supply";
+const PREFIX_MODULE_ACCOUNT: &str = "module_account";
+const PREFIX_EXEMPT: &str = "exempt";
+const STORAGE_PREFIXES: &[&str] = &[
+    PREFIX_BALANCE,
+    PREFIX_NONCE,
+    PREFIX_ACCOUNT,
+    PREFIX_SUPPLY,
+    PREFIX_MODULE_ACCOUNT,
+    PREFIX_EXEMPT,
+];
+
+/// Domain prefix hashed with a module id to derive its account address.
+const MODULE_ADDRESS_DOMAIN: &[u8] = b"module:";
 
 /// Helper functions for balance management
 
 fn balance_key(address: &Address) -> Vec<u8> {
-    let mut key = Vec::with_capacity(PREFIX_BALANCE.len() + address.len());
-    key.extend_from_slice(PREFIX_BALANCE);
-    key.extend_from_slice(address);
-    key
+    StorageKey::new(MODULE_ID, PREFIX_BALANCE)
+        .address(address)
+        .into_bytes()
 }
 
 fn nonce_key(address: &Address) -> Vec<u8> {
-    let mut key = Vec::with_capacity(PREFIX_NONCE.len() + address.len());
-    key.extend_from_slice(PREFIX_NONCE);
-    key.extend_from_slice(address);
-    key
+    StorageKey::new(MODULE_ID, PREFIX_NONCE)
+        .address(address)
+        .into_bytes()
 }
 
-fn get_balance(state: &State, addr: &Address) -> u64 {
+fn account_key(address: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ACCOUNT)
+        .address(address)
+        .into_bytes()
+}
+
+fn supply_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_SUPPLY).into_bytes()
+}
+
+fn module_account_key(address: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_MODULE_ACCOUNT)
+        .address(address)
+        .into_bytes()
+}
+
+fn exempt_key(address: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_EXEMPT)
+        .address(address)
+        .into_bytes()
+}
+
+/// Account address of a module: SHA-256 of `module:{module_id}`.
+///
+/// No key controls it. Once registered (see `register_module_account`),
+/// ordinary transactions cannot be sent from it and only the module's own
+/// dispatch can debit it.
+pub fn module_address(module_id: &str) -> Address {
+    let mut hasher = Sha256::new();
+    hasher.update(MODULE_ADDRESS_DOMAIN);
+    hasher.update(module_id.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Record `module_id`'s account in the module account registry.
+///
+/// # Returns
+/// Whether the account was newly registered
+pub fn register_module_account(state: &mut State, module_id: &str) -> Result<bool, String> {
+    let key = module_account_key(&module_address(module_id));
+    if state.get_raw(&key).is_some() {
+        return Ok(false);
+    }
+    let bytes = codec::encode(&module_id).map_err(|e| e.to_string())?;
+    state.with_namespace(MODULE_ID, |state| {
+        state.put_raw(key, bytes).map_err(|e| e.to_string())
+    })?;
+    Ok(true)
+}
+
+/// Module owning `addr`, if it is a registered module account.
+pub fn module_account_owner(state: &State, addr: &Address) -> Option<String> {
     state
-        .get_raw(&balance_key(addr))
-        .and_then(|bytes| bincode::deserialize::<u64>(&bytes).ok())
-        .unwrap_or(0)
+        .get_raw(&module_account_key(addr))
+        .and_then(|bytes| codec::decode_state(&bytes).ok())
+}
+
+/// Mark an address as existing the first time it is touched on chain.
+///
+/// Called from other modules (e.g. when a profile is stored), so it writes
+/// through bank_cgt's namespace escape hatch.
+pub(crate) fn touch_account(state: &mut State, addr: &Address) -> Result<(), String> {
+    let key = account_key(addr);
+    if state.get_raw(&key).is_some() {
+        return Ok(());
+    }
+    state.with_namespace(MODULE_ID, |state| {
+        state.put_raw(key, vec![1u8]).map_err(|e| e.to_string())
+    })
+}
+
+/// Check whether an address has ever been seen on chain.
+///
+/// True once the address has been touched (balance, nonce, or profile
+/// written), even if its balance is back to zero. Balance/nonce/profile keys
+/// are also checked so accounts written before the marker existed count.
+pub fn account_exists(state: &State, addr: &Address) -> bool {
+    state.get_raw(&account_key(addr)).is_some()
+        || state.get_raw(&balance_key(addr)).is_some()
+        || state.get_raw(&nonce_key(addr)).is_some()
+        || super::avatars_profiles::get_aeon_profile(state, addr).is_some()
+}
+
+fn get_balance(state: &State, addr: &Address) -> u64 {
+    get_u64(state, &balance_key(addr)).unwrap_or(0)
 }
 
 fn set_balance(state: &mut State, addr: &Address, amount: u64) -> Result<(), String> {
-    let bytes = bincode::serialize(&amount).map_err(|e| e.to_string())?;
-    state
-        .put_raw(balance_key(addr), bytes)
-        .map_err(|e| e.to_string())
+    touch_account(state, addr)?;
+    // A missing key already reads as zero; don't store dead entries
+    if amount == 0 {
+        return state
+            .delete_raw(&balance_key(addr))
+            .map_err(|e| e.to_string());
+    }
+    put_u64(state, balance_key(addr), amount)
 }
 
 fn get_nonce(state: &State, addr: &Address) -> u64 {
-    state
-        .get_raw(&nonce_key(addr))
-        .and_then(|bytes| bincode::deserialize::<u64>(&bytes).ok())
-        .unwrap_or(0)
+    get_u64(state, &nonce_key(addr)).unwrap_or(0)
 }
 
 fn set_nonce(state: &mut State, addr: &Address, nonce: u64) -> Result<(), String> {
-    let bytes = bincode::serialize(&nonce).map_err(|e| e.to_string())?;
-    state
-        .put_raw(nonce_key(addr), bytes)
-        .map_err(|e| e.to_string())
+    touch_account(state, addr)?;
+    put_u64(state, nonce_key(addr), nonce)
 }
 
 /// Public helper for querying CGT balance (for RPC/wallet use).
@@ -63,17 +177,224 @@ pub fn get_balance_cgt(state: &State, addr: &Address) -> u64 {
     get_balance(state, addr)
 }
 
-/// Internal helper for modules to directly set balances.
+/// Public helper for querying the nonce the address's next CGT transfer
+/// must carry (for RPC/wallet use).
+pub fn get_nonce_cgt(state: &State, addr: &Address) -> u64 {
+    get_nonce(state, addr)
+}
+
+/// Record that the address's transaction carrying `nonce` executed, so its
+/// next one must carry `nonce + 1`.
 ///
-/// This is used by other runtime modules (e.g., fabric_manager, abyss_registry)
-/// to perform balance operations without going through the bank_cgt transfer call.
-/// Use with caution - this bypasses nonce checks and other validations.
-pub(crate) fn set_balance_for_module(
-    state: &mut State,
-    addr: &Address,
-    amount: u64,
-) -> Result<(), String> {
-    set_balance(state, addr, amount)
+/// Cross-module API for the batch module: a batch consumes its own nonce,
+/// however many transfers inside it moved the nonce along.
+pub fn consume_nonce(state: &mut State, addr: &Address, nonce: u64) -> Result<(), String> {
+    let next = nonce.checked_add(1).ok_or("nonce overflow")?;
+    state.with_namespace(MODULE_ID, |state| set_nonce(state, addr, next))
+}
+
+/// Add CGT to an address's balance.
+///
+/// Cross-module API: no permission checks are made here; callers are
+/// responsible for authorizing the credit at their dispatch boundary. The
+/// existential deposit is not applied: escrow refunds, unbondings, and
+/// royalties must reach their recipients whatever the amount, even if
+/// governance raised the minimum after they were set up.
+///
+/// # Returns
+/// - `Ok(u64)` with the new balance
+/// - `Err(String)` on overflow or on storage failure
+pub fn credit(state: &mut State, addr: &Address, amount: u64) -> Result<u64, String> {
+    let new_balance = get_balance(state, addr)
+        .checked_add(amount)
+        .ok_or("overflow crediting balance")?;
+    state.with_namespace(MODULE_ID, |state| set_balance(state, addr, new_balance))?;
+    Ok(new_balance)
+}
+
+/// Smallest balance an account that is not exempt may hold, in base units.
+/// 0 disables the rule.
+pub fn existential_deposit(state: &State) -> u64 {
+    governance::param(state, governance::PARAM_EXISTENTIAL_DEPOSIT)
+        .unwrap_or(DEFAULT_EXISTENTIAL_DEPOSIT)
+}
+
+/// Exempt an address from the existential deposit; genesis does this for
+/// every genesis Archon.
+pub fn exempt_from_existential_deposit(state: &mut State, addr: &Address) -> Result<(), String> {
+    state.with_namespace(MODULE_ID, |state| {
+        state
+            .put_raw(exempt_key(addr), vec![1u8])
+            .map_err(|e| e.to_string())
+    })
+}
+
+fn is_exempt(state: &State, addr: &Address) -> bool {
+    state.get_raw(&exempt_key(addr)).is_some() || module_account_owner(state, addr).is_some()
+}
+
+/// Refuse a transfer that would leave `addr` holding some CGT, but less
+/// than the existential deposit.
+fn check_credited_balance(state: &State, addr: &Address, new_balance: u64) -> Result<(), String> {
+    let minimum = existential_deposit(state);
+    if new_balance > 0 && new_balance < minimum && !is_exempt(state, addr) {
+        return Err(format!(
+            "{} would hold {} base units, below the existential deposit of {}",
+            addr.to_display(),
+            new_balance,
+            minimum
+        ));
+    }
+    Ok(())
+}
+
+/// Account collecting the dust swept from reaped accounts: bank_cgt's own
+/// module account, which only this module can spend from.
+pub fn fee_pool_address() -> Address {
+    module_address(MODULE_ID)
+}
+
+/// Store a debited balance, reaping the account if that leaves it below the
+/// existential deposit: the remainder is swept to the fee pool and the
+/// balance key deleted. The nonce is kept, so the account's old
+/// transactions cannot be replayed.
+///
+/// # Returns
+/// The balance stored: `new_balance`, or 0 if the account was reaped
+fn set_debited_balance(state: &mut State, addr: &Address, new_balance: u64) -> Result<u64, String> {
+    if new_balance == 0 || new_balance >= existential_deposit(state) || is_exempt(state, addr) {
+        set_balance(state, addr, new_balance)?;
+        return Ok(new_balance);
+    }
+    set_balance(state, addr, 0)?;
+    let pool = fee_pool_address();
+    let pool_balance = get_balance(state, &pool)
+        .checked_add(new_balance)
+        .ok_or("overflow crediting the fee pool")?;
+    set_balance(state, &pool, pool_balance)?;
+    state.emit_event(Event::new(MODULE_ID, "reap", *addr).with("amount", new_balance));
+    Ok(0)
+}
+
+/// Remove CGT from an address's balance.
+///
+/// Cross-module API: callers are responsible for authorizing the debit at
+/// their dispatch boundary. Two checks are made here: only code running in
+/// the owning module's namespace may debit a module account, and CGT locked
+/// by a governance vote may not leave the address (see
+/// `governance::check_vote_lock`). An account left below the existential
+/// deposit is reaped.
+///
+/// # Returns
+/// - `Ok(u64)` with the new balance (0 if the account was reaped)
+/// - `Err(String)` if the balance is insufficient, the address is another
+///   module's account, the CGT is locked by a vote, or storage fails
+pub fn debit(state: &mut State, addr: &Address, amount: u64) -> Result<u64, String> {
+    governance::check_vote_lock(state, addr, amount)?;
+    debit_into_stake(state, addr, amount)
+}
+
+/// Remove CGT from an address's balance to add it to the address's own
+/// stake or bond.
+///
+/// Cross-module API: like `debit`, except that CGT locked by a governance
+/// vote may move, since staked and bonded CGT still count toward the
+/// address's voting weight. Only the staking modules should call this.
+pub fn debit_into_stake(state: &mut State, addr: &Address, amount: u64) -> Result<u64, String> {
+    if let Some(owner) = module_account_owner(state, addr) {
+        if state.namespace_module() != Some(owner.as_bytes()) {
+            return Err(format!(
+                "only the {} module can spend from its account",
+                owner
+            ));
+        }
+    }
+    let new_balance = get_balance(state, addr)
+        .checked_sub(amount)
+        .ok_or("insufficient CGT balance")?;
+    state.with_namespace(MODULE_ID, |state| {
+        set_debited_balance(state, addr, new_balance)
+    })
+}
+
+/// Total CGT in existence: everything minted, less fees burned by transfers.
+///
+/// Chains created before the counter existed only count mints made since.
+pub fn total_supply(state: &State) -> u64 {
+    get_u64(state, &supply_key()).unwrap_or(0)
+}
+
+/// Check that all CGT balances, plus the `held` base units that modules
+/// keep outside balances (stakes, bonds, pools), add up to the total supply.
+///
+/// More than the supply means CGT was created outside `mint`, e.g. by a
+/// double spend; less means it was destroyed outside `burn`. Chains created
+/// before the supply counter existed fail this check.
+///
+/// # Returns
+/// A description of each problem found
+pub fn check_supply(state: &State, held: u128) -> Vec<String> {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_BALANCE).into_bytes();
+    let mut problems = Vec::new();
+    let mut sum: u128 = 0;
+    for (key, value) in state.scan_prefix(&prefix) {
+        match decode_u64(&value) {
+            Some(balance) => sum += u128::from(balance),
+            None => problems.push(format!(
+                "balance of {} does not decode",
+                hex::encode(&key[prefix.len()..])
+            )),
+        }
+    }
+    let supply = total_supply(state);
+    if sum + held != u128::from(supply) {
+        problems.push(format!(
+            "CGT balances sum to {} base units and modules hold {} more, but the total supply is {}",
+            sum, held, supply
+        ));
+    }
+    problems
+}
+
+fn set_total_supply(state: &mut State, supply: u64) -> Result<(), String> {
+    state.with_namespace(MODULE_ID, |state| put_u64(state, supply_key(), supply))
+}
+
+/// Destroy CGT held by an address, as transfer fees are.
+///
+/// Cross-module API: like `debit`, but the amount also leaves the total
+/// supply.
+///
+/// # Returns
+/// - `Ok(u64)` with the new balance (0 if the account was reaped)
+/// - `Err(String)` if the balance is insufficient or storage fails
+pub fn burn(state: &mut State, addr: &Address, amount: u64) -> Result<u64, String> {
+    let balance = debit(state, addr, amount)?;
+    set_total_supply(state, total_supply(state).saturating_sub(amount))?;
+    Ok(balance)
+}
+
+/// Create new CGT and credit it to an address.
+///
+/// Cross-module API: like `credit`, but the amount is added to the total
+/// supply, and the mint is refused if it would exceed `CGT_MAX_SUPPLY`.
+/// Callers are responsible for authorizing the mint.
+///
+/// # Returns
+/// - `Ok(u64)` with the new balance
+/// - `Err(String)` if the cap would be exceeded, on overflow, or on storage failure
+pub fn mint(state: &mut State, addr: &Address, amount: u64) -> Result<u64, String> {
+    let supply = total_supply(state)
+        .checked_add(amount)
+        .ok_or("overflow minting CGT")?;
+    if CGT_MAX_SUPPLY != 0 && supply > CGT_MAX_SUPPLY {
+        return Err(format!(
+            "max supply exceeded: minting {} would bring supply to {}, above the cap of {}",
+            amount, supply, CGT_MAX_SUPPLY
+        ));
+    }
+    set_total_supply(state, supply)?;
+    credit(state, addr, amount)
 }
 
 /// Transfer parameters
@@ -81,6 +402,30 @@ pub(crate) fn set_balance_for_module(
 pub struct TransferParams {
     pub to: Address,
     pub amount: u64,
+    /// Reference for the recipient, such as an exchange deposit ID; see
+    /// `check_memo`.
+    pub memo: Option<Vec<u8>>,
+}
+
+/// Transfer parameters as encoded before `memo`.
+#[derive(Deserialize)]
+struct TransferParamsV1 {
+    to: Address,
+    amount: u64,
+}
+
+/// Transfer-to-handle parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferToHandleParams {
+    /// Recipient's Aeon handle, normalized when resolved (see
+    /// `avatars_profiles::normalize_handle`).
+    pub handle: String,
+    pub amount: u64,
+    pub memo: Option<Vec<u8>>,
+    /// Address the handle resolved to when the transaction was signed. The
+    /// transfer fails unless the handle still resolves to it, so a handle
+    /// released and claimed by someone else in between is not paid.
+    pub resolved_to: Address,
 }
 
 /// Mint parameters
@@ -90,31 +435,204 @@ pub struct MintToParams {
     pub amount: u64,
 }
 
+/// Decode a transfer payload, including ones encoded before `memo`.
+pub fn decode_transfer(payload: &[u8]) -> Result<TransferParams, String> {
+    codec::decode_exact(payload).or_else(|e| {
+        codec::decode_exact::<TransferParamsV1>(payload)
+            .map(|v1| TransferParams {
+                to: v1.to,
+                amount: v1.amount,
+                memo: None,
+            })
+            .map_err(|_| e.to_string())
+    })
+}
+
+/// Longest memo a transfer may carry, in bytes: the governed value, or the
+/// built-in default until a proposal sets one.
+pub fn max_memo_bytes(state: &State) -> u64 {
+    governance::param(state, governance::PARAM_MAX_MEMO_BYTES).unwrap_or(DEFAULT_MAX_MEMO_BYTES)
+}
+
+/// Check that a transfer's memo, if any, is at most `max_memo_bytes` long.
+pub fn check_memo(state: &State, memo: Option<&[u8]>) -> Result<(), String> {
+    let limit = max_memo_bytes(state);
+    match memo {
+        Some(memo) if memo.len() as u64 > limit => Err(format!(
+            "memo is {} bytes, more than the limit of {}",
+            memo.len(),
+            limit
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "transfer",
+        CallSchema::new(&[
+            FieldSchema::new("to", FieldType::Address),
+            FieldSchema::new("amount", FieldType::U64),
+            FieldSchema::new("memo", FieldType::Option(&FieldType::Bytes)),
+        ]),
+    ),
+    (
+        "transfer_to_handle",
+        CallSchema::new(&[
+            FieldSchema::new("handle", FieldType::String),
+            FieldSchema::new("amount", FieldType::U64),
+            FieldSchema::new("memo", FieldType::Option(&FieldType::Bytes)),
+            FieldSchema::new("resolved_to", FieldType::Address),
+        ]),
+    ),
+    (
+        "mint_to",
+        CallSchema::new(&[
+            FieldSchema::new("to", FieldType::Address),
+            FieldSchema::new("amount", FieldType::U64),
+        ]),
+    ),
+];
+
 /// BankCgtModule handles CGT token operations
-pub struct BankCgtModule;
+pub struct BankCgtModule {
+    mint_authorities: Vec<Address>,
+}
 
 impl BankCgtModule {
     pub fn new() -> Self {
-        Self
+        Self::with_mint_authorities(GenesisConfig::default().mint_authorities)
+    }
+
+    /// Create the module allowing only `mint_authorities` to call `mint_to`.
+    pub fn with_mint_authorities(mint_authorities: Vec<Address>) -> Self {
+        Self { mint_authorities }
+    }
+}
+
+impl Default for BankCgtModule {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl RuntimeModule for BankCgtModule {
     fn module_id(&self) -> &'static str {
-        "bank_cgt"
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["transfer", "transfer_to_handle", "mint_to"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
     }
 
     fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
         match call_id {
             "transfer" => handle_transfer(tx, state),
-            "mint_to" => handle_mint_to(tx, state),
+            "transfer_to_handle" => handle_transfer_to_handle(tx, state),
+            "mint_to" => handle_mint_to(&self.mint_authorities, tx, state),
             other => Err(format!("bank_cgt: unknown call_id '{}'", other)),
         }
     }
+
+    /// Nonces never go back, or an imported account's executed transactions
+    /// could be replayed.
+    fn reconcile_import(
+        &self,
+        key: &[u8],
+        local: Option<&[u8]>,
+        imported: &[u8],
+    ) -> Result<Option<Vec<u8>>, String> {
+        if !key.starts_with(&StorageKey::new(MODULE_ID, PREFIX_NONCE).into_bytes()) {
+            return Ok(None);
+        }
+        raise_u64(local, imported).map(Some)
+    }
+
+    /// A transfer writes both balances and account markers, the sender's
+    /// nonce, and the total supply when a fee is burned. Reaping the sender
+    /// also writes the fee pool's balance; that undeclared write sends the batch
+    /// back to sequential execution, as does a transfer to a handle, whose
+    /// recipient is not known until it runs.
+    fn declared_writes(&self, tx: &Transaction) -> Option<Vec<Vec<u8>>> {
+        if tx.call_id != "transfer" {
+            return None;
+        }
+        let params = decode_transfer(&tx.payload).ok()?;
+        let mut keys = vec![
+            balance_key(&tx.from),
+            balance_key(&params.to),
+            account_key(&tx.from),
+            account_key(&params.to),
+            nonce_key(&tx.from),
+        ];
+        if tx.fee > 0 {
+            keys.push(supply_key());
+        }
+        Some(keys)
+    }
 }
 
 fn handle_transfer(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    let params: TransferParams = bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params = decode_transfer(&tx.payload)?;
+    transfer(tx, state, params)
+}
+
+/// Resolve a transfer's handle to the address holding it now.
+///
+/// # Returns
+/// - `Ok(Address)` of the handle's holder
+/// - `Err` if no one holds the handle, or someone other than `resolved_to`
+///   holds it
+pub fn resolve_transfer_handle(
+    state: &State,
+    params: &TransferToHandleParams,
+) -> Result<Address, String> {
+    let handle = avatars::normalize_handle(&params.handle);
+    let signed = params.resolved_to;
+    match avatars::resolve_handle(state, &handle) {
+        Some(holder) if holder == signed => Ok(holder),
+        Some(holder) => Err(format!(
+            "handle @{} now resolves to {}, not {} as signed",
+            handle,
+            holder.to_display(),
+            signed.to_display()
+        )),
+        None => Err(format!(
+            "handle @{} is no longer registered (it resolved to {} as signed)",
+            handle,
+            signed.to_display()
+        )),
+    }
+}
+
+fn handle_transfer_to_handle(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: TransferToHandleParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+    let to = resolve_transfer_handle(state, &params)?;
+    transfer(
+        tx,
+        state,
+        TransferParams {
+            to,
+            amount: params.amount,
+            memo: params.memo,
+        },
+    )
+}
+
+/// Move `params.amount` from the sender to `params.to`, checking and bumping
+/// the sender's nonce and burning the fee.
+fn transfer(tx: &Transaction, state: &mut State, params: TransferParams) -> Result<(), String> {
+    check_memo(state, params.memo.as_deref())?;
 
     // Simple nonce check
     let current_nonce = get_nonce(state, &tx.from);
@@ -126,46 +644,61 @@ fn handle_transfer(tx: &Transaction, state: &mut State) -> Result<(), String> {
     }
 
     let mut from_balance = get_balance(state, &tx.from);
-    let mut to_balance = get_balance(state, &params.to);
 
     let total = params.amount.checked_add(tx.fee).ok_or("overflow")?;
 
     if from_balance < total {
         return Err("insufficient balance for amount + fee".into());
     }
+    governance::check_vote_lock(state, &tx.from, total)?;
 
     from_balance -= total;
-    to_balance = to_balance
-        .checked_add(params.amount)
-        .ok_or("overflow on recipient")?;
+    if params.to == tx.from {
+        // Paying oneself moves only the fee; crediting a recipient balance
+        // read before the debit would overwrite it and mint the amount
+        set_debited_balance(state, &tx.from, from_balance + params.amount)?;
+    } else {
+        let to_balance = get_balance(state, &params.to)
+            .checked_add(params.amount)
+            .ok_or("overflow on recipient")?;
+        check_credited_balance(state, &params.to, to_balance)?;
 
-    set_balance(state, &tx.from, from_balance)?;
-    set_balance(state, &params.to, to_balance)?;
+        set_debited_balance(state, &tx.from, from_balance)?;
+        set_balance(state, &params.to, to_balance)?;
+    }
 
     // Increment nonce
     set_nonce(state, &tx.from, current_nonce + 1)?;
 
-    // TODO: handle fee routing (burn or pool); for now, fee is effectively burned.
+    // TODO: handle fee routing (burn or pool); for now, fee is burned.
+    if tx.fee > 0 {
+        set_total_supply(state, total_supply(state).saturating_sub(tx.fee))?;
+    }
 
+    let mut event = Event::new(MODULE_ID, "transfer", tx.from)
+        .with("to", params.to.to_display())
+        .with("amount", params.amount);
+    if let Some(memo) = &params.memo {
+        event = event.with("memo", hex::encode(memo));
+    }
+    state.emit_event(event);
     Ok(())
 }
 
-fn handle_mint_to(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    // For Phase 3, keep this extremely simple:
-    // Allow minting only if tx.from is all zeros (a pseudo "genesis" authority).
-    if tx.from != [0u8; 32] {
-        return Err("mint_to can only be called by genesis authority (all-zero address)".into());
+fn handle_mint_to(
+    mint_authorities: &[Address],
+    tx: &Transaction,
+    state: &mut State,
+) -> Result<(), String> {
+    if !mint_authorities.contains(&tx.from) {
+        return Err("mint_to can only be called by a configured mint authority".into());
     }
 
-    let params: MintToParams = bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params: MintToParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
-    let current = get_balance(state, &params.to);
-    let new_balance = current
-        .checked_add(params.amount)
-        .ok_or("overflow on mint_to")?;
-
-    set_balance(state, &params.to, new_balance)?;
+    mint(state, &params.to, params.amount)?;
 
+    state.emit_event(Event::new(MODULE_ID, "mint", params.to).with("amount", params.amount));
     Ok(())
 }
 
@@ -173,6 +706,7 @@ fn handle_mint_to(tx: &Transaction, state: &mut State) -> Result<(), String> {
 mod tests {
     use super::*;
     use crate::core::state::State;
+    use crate::core::transaction::test_keys::{account, signed};
     use crate::core::transaction::{Address, Transaction};
 
     #[test]
@@ -182,6 +716,26 @@ mod tests {
         assert_eq!(get_balance_cgt(&state, &addr), 0);
     }
 
+    #[test]
+    fn test_legacy_bincode_balance_and_nonce_read() {
+        let mut state = State::in_memory();
+        let addr = [1u8; 32];
+        state
+            .put_raw(balance_key(&addr), codec::encode(&500u64).unwrap())
+            .unwrap();
+        state
+            .put_raw(nonce_key(&addr), codec::encode(&3u64).unwrap())
+            .unwrap();
+        assert_eq!(get_balance_cgt(&state, &addr), 500);
+        assert_eq!(get_nonce_cgt(&state, &addr), 3);
+
+        assert_eq!(credit(&mut state, &addr, 25).unwrap(), 525);
+        assert_eq!(
+            state.get_raw(&balance_key(&addr)).unwrap(),
+            525u64.to_le_bytes()
+        );
+    }
+
     #[test]
     fn test_mint_to_and_get_balance() {
         let mut state = State::in_memory();
@@ -198,17 +752,188 @@ mod tests {
             nonce: 0,
             module_id: "bank_cgt".to_string(),
             call_id: "mint_to".to_string(),
-            payload: bincode::serialize(&params).unwrap(),
+            payload: codec::encode(&params).unwrap(),
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
-        let module = BankCgtModule::new();
+        let module = BankCgtModule::with_mint_authorities(vec![[0; 32]]);
         module.dispatch("mint_to", &tx, &mut state).unwrap();
 
         assert_eq!(get_balance_cgt(&state, &addr), 1000);
     }
 
+    #[test]
+    fn test_zero_balance_key_removed() {
+        let mut state = State::in_memory();
+        let from = [1u8; 32];
+        let to = [2u8; 32];
+        set_balance(&mut state, &from, 500).unwrap();
+
+        // Transfer everything away (amount + fee)
+        let tx = Transaction {
+            from,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to,
+                amount: 490,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 10,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        BankCgtModule::new()
+            .dispatch("transfer", &tx, &mut state)
+            .unwrap();
+
+        assert_eq!(get_balance_cgt(&state, &from), 0);
+        assert!(state.get_raw(&balance_key(&from)).is_none());
+        assert_eq!(get_balance_cgt(&state, &to), 490);
+    }
+
+    #[test]
+    fn test_account_exists() {
+        let mut state = State::in_memory();
+        let addr = [1u8; 32];
+        assert!(!account_exists(&state, &addr));
+
+        let tx = Transaction {
+            from: [0u8; 32],
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "mint_to".to_string(),
+            payload: codec::encode(&MintToParams {
+                to: addr,
+                amount: 1,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        BankCgtModule::with_mint_authorities(vec![[0; 32]])
+            .dispatch("mint_to", &tx, &mut state)
+            .unwrap();
+        assert!(account_exists(&state, &addr));
+
+        // Emptying the balance does not make the account disappear
+        set_balance(&mut state, &addr, 0).unwrap();
+        assert!(account_exists(&state, &addr));
+        assert!(!account_exists(&state, &[2u8; 32]));
+    }
+
+    #[test]
+    fn test_mint_to_restricted_to_configured_authorities() {
+        let minter = [7u8; 32];
+        let module = BankCgtModule::with_mint_authorities(vec![minter]);
+        let mint = |from: Address| Transaction {
+            from,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "mint_to".to_string(),
+            payload: codec::encode(&MintToParams {
+                to: [1u8; 32],
+                amount: 250,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let mut state = State::in_memory();
+
+        module
+            .dispatch("mint_to", &mint(minter), &mut state)
+            .unwrap();
+        assert_eq!(get_balance_cgt(&state, &[1u8; 32]), 250);
+
+        // Neither an unlisted address nor the dev zero address may mint
+        for from in [[8u8; 32], [0u8; 32]] {
+            let err = module
+                .dispatch("mint_to", &mint(from), &mut state)
+                .unwrap_err();
+            assert!(err.contains("mint authority"), "{}", err);
+        }
+        assert_eq!(get_balance_cgt(&state, &[1u8; 32]), 250);
+    }
+
+    #[test]
+    fn test_mint_to_capped_at_max_supply() {
+        let minter = [7u8; 32];
+        let module = BankCgtModule::with_mint_authorities(vec![minter]);
+        let mint_tx = |amount: u64| Transaction {
+            from: minter,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "mint_to".to_string(),
+            payload: codec::encode(&MintToParams {
+                to: [1u8; 32],
+                amount,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let mut state = State::in_memory();
+
+        // Minting exactly up to the cap succeeds
+        module
+            .dispatch("mint_to", &mint_tx(CGT_MAX_SUPPLY - 1), &mut state)
+            .unwrap();
+        module.dispatch("mint_to", &mint_tx(1), &mut state).unwrap();
+        assert_eq!(total_supply(&state), CGT_MAX_SUPPLY);
+
+        // One more base unit is refused and nothing changes
+        let err = module
+            .dispatch("mint_to", &mint_tx(1), &mut state)
+            .unwrap_err();
+        assert!(err.contains("max supply exceeded"), "{}", err);
+        assert_eq!(total_supply(&state), CGT_MAX_SUPPLY);
+        assert_eq!(get_balance_cgt(&state, &[1u8; 32]), CGT_MAX_SUPPLY);
+
+        // Other mint paths (faucet, rewards) share the cap
+        assert!(mint(&mut state, &[2u8; 32], 1).is_err());
+        assert_eq!(get_balance_cgt(&state, &[2u8; 32]), 0);
+    }
+
+    #[test]
+    fn test_transfer_fee_burns_supply() {
+        let mut state = State::in_memory();
+        let from = [1u8; 32];
+        mint(&mut state, &from, 1_000).unwrap();
+        let tx = Transaction {
+            from,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to: [2u8; 32],
+                amount: 400,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 10,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        BankCgtModule::new()
+            .dispatch("transfer", &tx, &mut state)
+            .unwrap();
+        assert_eq!(total_supply(&state), 990);
+    }
+
     #[test]
     fn test_transfer() {
         let mut state = State::in_memory();
@@ -226,24 +951,32 @@ mod tests {
             nonce: 0,
             module_id: "bank_cgt".to_string(),
             call_id: "mint_to".to_string(),
-            payload: bincode::serialize(&mint_params).unwrap(),
+            payload: codec::encode(&mint_params).unwrap(),
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
-        let module = BankCgtModule::new();
+        let module = BankCgtModule::with_mint_authorities(vec![[0; 32]]);
         module.dispatch("mint_to", &mint_tx, &mut state).unwrap();
 
         // Now transfer
-        let transfer_params = TransferParams { to, amount: 300 };
+        let transfer_params = TransferParams {
+            to,
+            amount: 300,
+            memo: None,
+        };
         let transfer_tx = Transaction {
             from,
             nonce: 0,
             module_id: "bank_cgt".to_string(),
             call_id: "transfer".to_string(),
-            payload: bincode::serialize(&transfer_params).unwrap(),
+            payload: codec::encode(&transfer_params).unwrap(),
             fee: 10,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         module
@@ -253,4 +986,277 @@ mod tests {
         assert_eq!(get_balance_cgt(&state, &from), 690); // 1000 - 300 - 10
         assert_eq!(get_balance_cgt(&state, &to), 300);
     }
+
+    #[test]
+    fn test_credit_and_debit() {
+        let mut state = State::in_memory();
+        let addr: Address = [3; 32];
+
+        assert_eq!(credit(&mut state, &addr, 500), Ok(500));
+        assert_eq!(debit(&mut state, &addr, 200), Ok(300));
+        assert!(debit(&mut state, &addr, 301).is_err());
+        assert_eq!(get_balance_cgt(&state, &addr), 300);
+    }
+
+    #[test]
+    fn test_module_account_spendable_only_by_owner() {
+        let mut state = State::in_memory();
+        let account = module_address("escrow");
+        assert_ne!(account, module_address("staking"));
+        assert_eq!(register_module_account(&mut state, "escrow"), Ok(true));
+        assert_eq!(register_module_account(&mut state, "escrow"), Ok(false));
+        assert_eq!(
+            module_account_owner(&state, &account),
+            Some("escrow".to_string())
+        );
+        credit(&mut state, &account, 100).unwrap();
+
+        let err = state
+            .with_namespace("staking", |state| debit(state, &account, 10))
+            .unwrap_err();
+        assert_eq!(err, "only the escrow module can spend from its account");
+        // Outside any module's namespace, nobody can spend from it either
+        assert!(debit(&mut state, &account, 10).is_err());
+        assert_eq!(
+            state.with_namespace("escrow", |state| debit(state, &account, 10)),
+            Ok(90)
+        );
+    }
+
+    fn set_existential_deposit(state: &mut State, minimum: u64) {
+        let key = StorageKey::new("governance", "param")
+            .bytes(governance::PARAM_EXISTENTIAL_DEPOSIT.as_bytes())
+            .into_bytes();
+        state
+            .put_raw(key, codec::encode(&minimum).unwrap())
+            .unwrap();
+    }
+
+    fn transfer(from: Address, nonce: u64, to: Address, amount: u64) -> Transaction {
+        Transaction {
+            from,
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to,
+                amount,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    #[test]
+    fn test_self_transfer_only_pays_the_fee() {
+        let mut state = State::in_memory();
+        let from = [1u8; 32];
+        mint(&mut state, &from, 1_000).unwrap();
+        let supply = total_supply(&state);
+
+        let tx = Transaction {
+            fee: 10,
+            ..transfer(from, 0, from, 400)
+        };
+        BankCgtModule::new()
+            .dispatch("transfer", &tx, &mut state)
+            .unwrap();
+        assert_eq!(get_balance_cgt(&state, &from), 990);
+        assert_eq!(total_supply(&state), supply - 10);
+        assert_eq!(get_nonce(&state, &from), 1);
+    }
+
+    #[test]
+    fn test_existential_deposit_refuses_dust_recipients() {
+        let mut state = State::in_memory();
+        let (from, to) = ([1u8; 32], [2u8; 32]);
+        mint(&mut state, &from, 1_000).unwrap();
+        set_existential_deposit(&mut state, 100);
+        let module = BankCgtModule::new();
+
+        let err = module
+            .dispatch("transfer", &transfer(from, 0, to, 99), &mut state)
+            .unwrap_err();
+        assert!(
+            err.contains("below the existential deposit of 100"),
+            "{}",
+            err
+        );
+        assert!(!account_exists(&state, &to));
+
+        module
+            .dispatch("transfer", &transfer(from, 0, to, 100), &mut state)
+            .unwrap();
+        // Topping up an account above the minimum takes any amount
+        module
+            .dispatch("transfer", &transfer(from, 1, to, 1), &mut state)
+            .unwrap();
+        assert_eq!(get_balance_cgt(&state, &to), 101);
+
+        // Other modules' credits are payouts the recipient did not choose,
+        // so they are never refused
+        assert_eq!(credit(&mut state, &[3u8; 32], 99), Ok(99));
+    }
+
+    #[test]
+    fn test_existential_deposit_reaps_dust_senders() {
+        let mut state = State::in_memory();
+        let (from, to) = (account(1), [2u8; 32]);
+        mint(&mut state, &from, 1_000).unwrap();
+        mint(&mut state, &to, 1_000).unwrap();
+        set_existential_deposit(&mut state, 100);
+
+        let runtime = crate::runtime::Runtime::with_default_modules();
+        let (error, events) = state
+            .dispatch_scoped(&runtime, &signed(transfer(from, 0, to, 950)))
+            .unwrap();
+        assert_eq!(error, None);
+        assert_eq!(get_balance_cgt(&state, &from), 0);
+        assert_eq!(state.get_raw(&balance_key(&from)), None);
+        assert_eq!(get_nonce_cgt(&state, &from), 1);
+        // The dust is swept to the fee pool, not destroyed
+        assert_eq!(get_balance_cgt(&state, &fee_pool_address()), 50);
+        assert_eq!(total_supply(&state), 2_000);
+        assert!(events
+            .iter()
+            .any(|event| event.kind == "reap"
+                && event.attributes == [("amount".into(), "50".into())]));
+
+        // Cross-module debits reap too; the 1,900 debited moves elsewhere
+        assert_eq!(debit(&mut state, &to, 1_900), Ok(0));
+        assert_eq!(get_balance_cgt(&state, &fee_pool_address()), 100);
+        assert_eq!(total_supply(&state), 2_000);
+    }
+
+    #[test]
+    fn test_existential_deposit_exemptions() {
+        let mut state = State::in_memory();
+        set_existential_deposit(&mut state, 100);
+
+        // Genesis Archons may hold and keep dust
+        let archon = [1u8; 32];
+        exempt_from_existential_deposit(&mut state, &archon).unwrap();
+        assert_eq!(credit(&mut state, &archon, 150), Ok(150));
+        BankCgtModule::new()
+            .dispatch("transfer", &transfer(archon, 0, [2u8; 32], 140), &mut state)
+            .unwrap();
+        assert_eq!(get_balance_cgt(&state, &archon), 10);
+
+        // So may module accounts, such as escrow's
+        register_module_account(&mut state, "escrow").unwrap();
+        let account = module_address("escrow");
+        assert_eq!(credit(&mut state, &account, 5), Ok(5));
+        assert_eq!(
+            state.with_namespace("escrow", |state| debit(state, &account, 4)),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_transfer_memo_is_recorded_and_bounded() {
+        let mut state = State::in_memory();
+        let (from, to) = (account(1), [2u8; 32]);
+        mint(&mut state, &from, 1_000).unwrap();
+        let runtime = crate::runtime::Runtime::with_default_modules();
+        let with_memo = |nonce, memo: Vec<u8>| {
+            let mut tx = transfer(from, nonce, to, 10);
+            tx.payload = codec::encode(&TransferParams {
+                to,
+                amount: 10,
+                memo: Some(memo),
+            })
+            .unwrap();
+            signed(tx)
+        };
+
+        let (error, events) = state
+            .dispatch_scoped(&runtime, &with_memo(0, b"INV-42".to_vec()))
+            .unwrap();
+        assert_eq!(error, None);
+        assert!(events[0]
+            .attributes
+            .contains(&("memo".into(), hex::encode("INV-42"))));
+        assert_eq!(get_balance_cgt(&state, &to), 10);
+
+        let limit = DEFAULT_MAX_MEMO_BYTES as usize;
+        let (error, _) = state
+            .dispatch_scoped(&runtime, &with_memo(1, vec![0; limit + 1]))
+            .unwrap();
+        assert_eq!(
+            error.unwrap(),
+            format!(
+                "memo is {} bytes, more than the limit of {}",
+                limit + 1,
+                limit
+            )
+        );
+        assert_eq!(get_nonce_cgt(&state, &from), 1);
+        assert!(state
+            .dispatch_scoped(&runtime, &with_memo(1, vec![0; limit]))
+            .unwrap()
+            .0
+            .is_none());
+
+        // Payloads from before memos decode without one
+        let legacy = codec::encode(&(to, 10u64)).unwrap();
+        assert_eq!(decode_transfer(&legacy).unwrap().memo, None);
+    }
+
+    #[test]
+    fn test_transfer_to_handle_resolves_at_execution() {
+        use crate::runtime::avatars_profiles::{create_aeon_profile, set_handle};
+
+        let mut state = State::in_memory();
+        let (from, orpheus, usurper) = (account(1), [2u8; 32], [3u8; 32]);
+        mint(&mut state, &from, 1_000).unwrap();
+        for addr in [orpheus, usurper] {
+            create_aeon_profile(&mut state, addr, "Aeon".to_string(), None, 0).unwrap();
+        }
+        set_handle(&mut state, orpheus, "orpheus".to_string()).unwrap();
+        let runtime = crate::runtime::Runtime::with_default_modules();
+        let mut nonce = 0;
+        let mut send = |state: &mut State, handle: &str, resolved_to: Address| {
+            let mut tx = transfer(from, nonce, [0; 32], 10);
+            tx.call_id = "transfer_to_handle".to_string();
+            tx.payload = codec::encode(&TransferToHandleParams {
+                handle: handle.to_string(),
+                amount: 10,
+                memo: None,
+                resolved_to,
+            })
+            .unwrap();
+            let (error, _) = state.dispatch_scoped(&runtime, &signed(tx)).unwrap();
+            if error.is_none() {
+                nonce += 1;
+            }
+            error
+        };
+
+        // Case and a leading @ do not matter
+        assert_eq!(send(&mut state, " @Orpheus", orpheus), None);
+        assert_eq!(send(&mut state, "orpheus", orpheus), None);
+        assert_eq!(get_balance_cgt(&state, &orpheus), 20);
+
+        // Released after signing
+        set_handle(&mut state, orpheus, "@Eurydice".to_string()).unwrap();
+        let err = send(&mut state, "orpheus", orpheus).unwrap();
+        assert!(err.contains("@orpheus is no longer registered"), "{}", err);
+        assert_eq!(get_nonce_cgt(&state, &from), 2);
+
+        // and claimed by someone else, who is not paid
+        set_handle(&mut state, usurper, "orpheus".to_string()).unwrap();
+        let err = send(&mut state, "orpheus", orpheus).unwrap();
+        assert!(err.contains("@orpheus now resolves to"), "{}", err);
+        assert_eq!(send(&mut state, "eurydice", orpheus), None);
+        assert_eq!(get_balance_cgt(&state, &orpheus), 30);
+        assert_eq!(get_balance_cgt(&state, &usurper), 0);
+
+        // unless the sender signs for the new holder
+        assert_eq!(send(&mut state, "orpheus", usurper), None);
+        assert_eq!(get_balance_cgt(&state, &usurper), 10);
+    }
 }
@@ -0,0 +1,305 @@
+//! Batch module for running several calls as one atomic transaction.
+//!
+//! This module handles:
+//! - Executing a batch (`execute_batch`): the inner calls run in order, each
+//!   dispatched to its module as if the sender had sent it in a transaction
+//!   of its own, within one state scope
+//!
+//! Either every call succeeds or none of them leaves a trace: the first
+//! failure rolls back the writes and events of the calls before it, and the
+//! fee, and fails the whole transaction. A batch may not contain another
+//! batch.
+//!
+//! The batch is signed once, as the outer transaction, whose nonce must be
+//! the sender's current one. Its fee is burned once; inner calls run with no
+//! fee. Each inner call gets the sender's nonce as it stands when the call
+//! runs, and once they all succeed the batch consumes its own nonce: the
+//! sender's next nonce is the batch's plus one, however many transfers it
+//! held, so block templates can order it among the sender's transfers.
+//!
+//! Modules cannot reach each other's dispatch, so the runtime routes batch
+//! transactions here (see `Runtime::dispatch_tx`).
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::bank;
+use super::{CallSchema, FieldSchema, FieldType, Runtime, RuntimeModule};
+use crate::core::codec;
+use crate::core::receipt::Event;
+use crate::core::state::State;
+use crate::core::transaction::Transaction;
+
+pub(crate) const MODULE_ID: &str = "batch";
+
+/// Most calls a batch may carry.
+pub const MAX_BATCH_CALLS: usize = 16;
+
+/// One call of a batch, with its module's bincode payload.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchCall {
+    pub module_id: String,
+    pub call_id: String,
+    pub payload: Vec<u8>,
+}
+
+/// Execute batch parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecuteBatchParams {
+    pub calls: Vec<BatchCall>,
+}
+
+const BATCH_CALL_FIELDS: &[FieldSchema] = &[
+    FieldSchema::new("module_id", FieldType::String),
+    FieldSchema::new("call_id", FieldType::String),
+    FieldSchema::new("payload", FieldType::Bytes),
+];
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[(
+    "execute_batch",
+    CallSchema::new(&[FieldSchema::new(
+        "calls",
+        FieldType::List(&FieldType::Struct(BATCH_CALL_FIELDS)),
+    )]),
+)];
+
+/// BatchModule runs several calls as one atomic transaction
+#[derive(Default)]
+pub struct BatchModule;
+
+impl BatchModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for BatchModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["execute_batch"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, _tx: &Transaction, _state: &mut State) -> Result<(), String> {
+        // Batches need the other modules, so `Runtime::dispatch_tx` sends
+        // them to `dispatch_batch` instead
+        Err(format!(
+            "batch: call_id '{}' must be dispatched by the runtime",
+            call_id
+        ))
+    }
+}
+
+/// Run a batch transaction's calls in order, all or nothing.
+///
+/// # Returns
+/// - `Ok(())` if every call succeeded, keeping all of their writes and events
+/// - `Err(String)` naming the first call that failed, or why the batch is
+///   malformed; nothing the batch did is kept
+pub(crate) fn dispatch_batch(
+    runtime: &Runtime,
+    tx: &Transaction,
+    state: &mut State,
+) -> Result<(), String> {
+    if tx.call_id != "execute_batch" {
+        return Err(format!("batch: unknown call_id '{}'", tx.call_id));
+    }
+    let params: ExecuteBatchParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+    if params.calls.is_empty() || params.calls.len() > MAX_BATCH_CALLS {
+        return Err(format!(
+            "a batch holds 1 to {} calls, not {}",
+            MAX_BATCH_CALLS,
+            params.calls.len()
+        ));
+    }
+
+    let nonce = bank::nonce(state, &tx.from);
+    if tx.nonce != nonce {
+        return Err(format!(
+            "invalid nonce: expected {}, got {}",
+            nonce, tx.nonce
+        ));
+    }
+
+    state.atomically(|state| {
+        if tx.fee > 0 {
+            bank::burn(state, &tx.from, tx.fee)
+                .map_err(|e| format!("batch fee of {} not paid: {}", tx.fee, e))?;
+        }
+        for (index, call) in params.calls.iter().enumerate() {
+            let failed = |e: String| {
+                format!(
+                    "batch call {} ({}.{}) failed: {}",
+                    index, call.module_id, call.call_id, e
+                )
+            };
+            if call.module_id == MODULE_ID {
+                return Err(failed("a batch may not contain another batch".into()));
+            }
+            let module = runtime
+                .modules
+                .get(call.module_id.as_str())
+                .ok_or_else(|| failed(format!("Unknown module: {}", call.module_id)))?;
+            let inner = Transaction {
+                module_id: call.module_id.clone(),
+                call_id: call.call_id.clone(),
+                payload: call.payload.clone(),
+                nonce: bank::nonce(state, &tx.from),
+                fee: 0,
+                ..tx.clone()
+            };
+            state
+                .with_namespace(module.module_id(), |state| {
+                    module.dispatch(&inner.call_id, &inner, state)
+                })
+                .map_err(failed)?;
+        }
+        bank::consume_nonce(state, &tx.from, tx.nonce)?;
+        state.emit_event(
+            Event::new(MODULE_ID, "execute", tx.from).with("calls", params.calls.len()),
+        );
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::runtime::bank_cgt::{self, TransferParams};
+
+    const BOB: [u8; 32] = [2; 32];
+    const CAROL: [u8; 32] = [3; 32];
+
+    /// The sender of every batch.
+    fn alice() -> [u8; 32] {
+        account(1)
+    }
+
+    fn transfer(to: [u8; 32], amount: u64) -> BatchCall {
+        BatchCall {
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to,
+                amount,
+                memo: None,
+            })
+            .unwrap(),
+        }
+    }
+
+    fn batch_tx(calls: Vec<BatchCall>) -> Transaction {
+        Transaction {
+            from: alice(),
+            nonce: 0,
+            module_id: MODULE_ID.to_string(),
+            call_id: "execute_batch".to_string(),
+            payload: codec::encode(&ExecuteBatchParams { calls }).unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    fn funded_state() -> State {
+        let mut state = State::in_memory();
+        bank_cgt::mint(&mut state, &alice(), 1_000).unwrap();
+        state
+    }
+
+    #[test]
+    fn test_batch_of_two_transfers() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = funded_state();
+
+        // The fee is burned once, and the batch takes one nonce
+        let mut tx = batch_tx(vec![transfer(BOB, 300), transfer(CAROL, 200)]);
+        tx.fee = 10;
+        let (error, events) = state.dispatch_scoped(&runtime, &signed(tx)).unwrap();
+        assert_eq!(error, None);
+        assert_eq!(bank_cgt::get_balance_cgt(&state, &alice()), 490);
+        assert_eq!(bank_cgt::total_supply(&state), 990);
+        assert_eq!(bank_cgt::get_nonce_cgt(&state, &alice()), 1);
+        assert_eq!(bank_cgt::get_balance_cgt(&state, &BOB), 300);
+        assert_eq!(bank_cgt::get_balance_cgt(&state, &CAROL), 200);
+        let kinds: Vec<_> = events
+            .iter()
+            .map(|event| (event.module_id.as_str(), event.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("bank_cgt", "transfer"),
+                ("bank_cgt", "transfer"),
+                ("batch", "execute")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_failing_call_leaves_no_changes() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = funded_state();
+        let before = state.scan_prefix(b"");
+
+        // The second transfer overdraws, so the first is undone too. Plain
+        // dispatch has no scope of its own, so the batch's scope alone must
+        // do the undoing
+        let mut tx = batch_tx(vec![transfer(BOB, 600), transfer(CAROL, 600)]);
+        tx.fee = 10;
+        let err = runtime.dispatch_tx(&signed(tx), &mut state).unwrap_err();
+        assert!(
+            err.starts_with("batch call 1 (bank_cgt.transfer) failed"),
+            "{}",
+            err
+        );
+        assert_eq!(state.scan_prefix(b""), before);
+        assert_eq!(bank_cgt::get_balance_cgt(&state, &alice()), 1_000);
+        assert_eq!(bank_cgt::get_balance_cgt(&state, &BOB), 0);
+        assert_eq!(bank_cgt::get_nonce_cgt(&state, &alice()), 0);
+    }
+
+    #[test]
+    fn test_malformed_batches() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = funded_state();
+        let nested = BatchCall {
+            module_id: MODULE_ID.to_string(),
+            call_id: "execute_batch".to_string(),
+            payload: batch_tx(vec![transfer(BOB, 1)]).payload,
+        };
+        let unknown = BatchCall {
+            module_id: "nope".to_string(),
+            call_id: "transfer".to_string(),
+            payload: Vec::new(),
+        };
+
+        for (calls, expected) in [
+            (Vec::new(), "1 to 16 calls"),
+            (vec![transfer(BOB, 1); MAX_BATCH_CALLS + 1], "not 17"),
+            (
+                vec![transfer(BOB, 1), nested],
+                "may not contain another batch",
+            ),
+            (vec![unknown], "Unknown module: nope"),
+        ] {
+            let err = runtime
+                .dispatch_tx(&signed(batch_tx(calls)), &mut state)
+                .unwrap_err();
+            assert!(err.contains(expected), "{}", err);
+        }
+        let mut stale = batch_tx(vec![transfer(BOB, 1)]);
+        stale.nonce = 1;
+        let err = runtime.dispatch_tx(&signed(stale), &mut state).unwrap_err();
+        assert_eq!(err, "invalid nonce: expected 0, got 1");
+        assert_eq!(bank_cgt::get_balance_cgt(&state, &BOB), 0);
+    }
+}
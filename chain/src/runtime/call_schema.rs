@@ -0,0 +1,191 @@
+//! Payload schemas for runtime module calls.
+//!
+//! Each module declares, next to its params structs, the fields its calls
+//! expect (see `RuntimeModule::call_schemas`), so clients can discover a
+//! payload's layout with `cgt_describeCall` instead of reading the source.
+//! Fields are listed in encoding order; the JSON params accepted by
+//! `cgt_sendTransaction` use the same names.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Type of a payload field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// 32-byte account address (checksummed or hex in JSON).
+    Address,
+    /// 32-byte hash (hex in JSON).
+    Hash,
+    U8,
+    U16,
+    U32,
+    U64,
+    Bool,
+    String,
+    /// Variable-length bytes (hex in JSON).
+    Bytes,
+    /// A value that may be absent (`null` or omitted in JSON).
+    Option(&'static FieldType),
+    /// A sequence of values (a JSON array).
+    List(&'static FieldType),
+    /// A nested struct with its own fields.
+    Struct(&'static [FieldSchema]),
+}
+
+impl FieldType {
+    /// Type name as shown to clients, e.g. `u64` or `option<address>`.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Address => "address".to_string(),
+            Self::Hash => "hash".to_string(),
+            Self::U8 => "u8".to_string(),
+            Self::U16 => "u16".to_string(),
+            Self::U32 => "u32".to_string(),
+            Self::U64 => "u64".to_string(),
+            Self::Bool => "bool".to_string(),
+            Self::String => "string".to_string(),
+            Self::Bytes => "bytes".to_string(),
+            Self::Option(inner) => format!("option<{}>", inner.name()),
+            Self::List(inner) => format!("list<{}>", inner.name()),
+            Self::Struct(_) => "struct".to_string(),
+        }
+    }
+
+    /// Fields of a struct type (or of the struct an option or list wraps).
+    fn fields(&self) -> Option<&'static [FieldSchema]> {
+        match self {
+            Self::Struct(fields) => Some(fields),
+            Self::Option(inner) | Self::List(inner) => inner.fields(),
+            _ => None,
+        }
+    }
+}
+
+/// One named field of a call's payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSchema {
+    pub name: &'static str,
+    pub ty: FieldType,
+}
+
+impl FieldSchema {
+    pub const fn new(name: &'static str, ty: FieldType) -> Self {
+        Self { name, ty }
+    }
+}
+
+/// Serialized as `{ "name", "type" }`, plus `"fields"` for structs.
+impl Serialize for FieldSchema {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = self.ty.fields();
+        let len = if fields.is_some() { 3 } else { 2 };
+        let mut field = serializer.serialize_struct("FieldSchema", len)?;
+        field.serialize_field("name", self.name)?;
+        field.serialize_field("type", &self.ty.name())?;
+        if let Some(fields) = fields {
+            field.serialize_field("fields", fields)?;
+        }
+        field.end()
+    }
+}
+
+/// Payload layout of one module call. Calls without a payload have no fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CallSchema {
+    pub fields: &'static [FieldSchema],
+}
+
+impl CallSchema {
+    pub const fn new(fields: &'static [FieldSchema]) -> Self {
+        Self { fields }
+    }
+
+    /// Field names, in encoding order.
+    pub fn field_names(&self) -> Vec<&'static str> {
+        self.fields.iter().map(|field| field.name).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{CodecRegistry, Runtime};
+    use serde_json::{json, Map, Value};
+
+    #[test]
+    fn test_schema_json_shape() {
+        const INNER: &[FieldSchema] = &[FieldSchema::new("count", FieldType::U32)];
+        const SCHEMA: CallSchema = CallSchema::new(&[
+            FieldSchema::new("to", FieldType::Option(&FieldType::Address)),
+            FieldSchema::new("criteria", FieldType::Struct(INNER)),
+        ]);
+        assert_eq!(
+            serde_json::to_value(SCHEMA).unwrap(),
+            json!({
+                "fields": [
+                    { "name": "to", "type": "option<address>" },
+                    {
+                        "name": "criteria",
+                        "type": "struct",
+                        "fields": [{ "name": "count", "type": "u32" }],
+                    },
+                ],
+            })
+        );
+        assert_eq!(SCHEMA.field_names(), vec!["to", "criteria"]);
+    }
+
+    fn sample(ty: &FieldType) -> Value {
+        match ty {
+            FieldType::Address => json!(hex::encode([2u8; 32])),
+            FieldType::Hash => json!(hex::encode([3u8; 32])),
+            FieldType::U8 | FieldType::U16 | FieldType::U32 | FieldType::U64 => json!(1),
+            FieldType::Bool => json!(true),
+            FieldType::String => json!("ipfs://sample"),
+            FieldType::Bytes => json!("0a0b"),
+            FieldType::Option(inner) => sample(inner),
+            FieldType::List(inner) => json!([sample(inner)]),
+            FieldType::Struct(fields) => sample_params(fields, None),
+        }
+    }
+
+    /// Sample params for `fields`, leaving out the field named `skip`.
+    fn sample_params(fields: &[FieldSchema], skip: Option<&str>) -> Value {
+        let params: Map<String, Value> = fields
+            .iter()
+            .filter(|field| Some(field.name) != skip)
+            .map(|field| (field.name.to_string(), sample(&field.ty)))
+            .collect();
+        Value::Object(params)
+    }
+
+    /// Every default call is described, and its schema agrees with the JSON
+    /// codec: sample params encode, and dropping any field either is rejected
+    /// or (for optional and defaulted fields) changes the payload.
+    #[test]
+    fn test_schemas_match_codecs() {
+        let runtime = Runtime::with_default_modules();
+        let codecs = CodecRegistry::with_default_codecs();
+        for module in runtime.modules() {
+            for call_id in module.call_ids {
+                let schema = runtime
+                    .call_schema(module.module_id, call_id)
+                    .unwrap_or_else(|| panic!("{}.{} has no schema", module.module_id, call_id));
+                let encode = |params: &Value| codecs.encode(module.module_id, call_id, params);
+                let payload = encode(&sample_params(schema.fields, None)).unwrap();
+
+                for field in schema.fields {
+                    let without = encode(&sample_params(schema.fields, Some(field.name)));
+                    assert_ne!(
+                        without.ok().as_ref(),
+                        Some(&payload),
+                        "{}.{} ignores field {}",
+                        module.module_id,
+                        call_id,
+                        field.name
+                    );
+                }
+            }
+        }
+    }
+}
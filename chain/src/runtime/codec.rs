@@ -0,0 +1,712 @@
+//! JSON codecs for runtime call parameters.
+//!
+//! Runtime modules take bincode-encoded parameters in `Transaction::payload`.
+//! This registry maps each `(module_id, call_id)` pair to a codec that turns
+//! human-readable JSON params (addresses and hashes as hex strings) into that
+//! payload, so clients can submit transactions without producing bincode.
+//! CGT amounts in these params may be base units or decimal CGT strings
+//! (see `amount::deserialize_cgt`).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::amount::deserialize_cgt;
+
+use super::abyss_registry::{
+    BuyBundleListingParams, BuyListingParams, CancelBundleListingParams, CancelListingParams,
+    CreateBundleListingParams, CreateListingParams, UpdateListingPriceParams,
+};
+use super::assets;
+use super::avatars_profiles::{RecordSyzygyParams, RecorderParams};
+use super::bank_cgt::{MintToParams, TransferParams, TransferToHandleParams};
+use super::batch::{BatchCall, ExecuteBatchParams};
+use super::escrow::{CreateEscrowParams, RefundEscrowParams, ReleaseEscrowParams};
+use super::fabric_manager::{RegisterAssetParams, RewardSeederParams, SetAssetUriHintParams};
+use super::governance::{CreateProposalParams, VoteParams};
+use super::multisig::CreateMultisigParams;
+use super::names::RegisterNameParams;
+use super::nft_dgen::{MintDgenParams, NftId, SetUriHintParams, TransferNftParams};
+use super::quests::{CreateQuestParams, RetireQuestParams};
+use super::staking::{StakeParams, UnstakeParams};
+use super::staking_cgt::{BondParams, UnbondParams};
+
+/// Converts JSON call params into a module's bincode payload.
+pub type JsonCodec = fn(&Value) -> Result<Vec<u8>, String>;
+
+/// Registry of JSON codecs keyed by `(module_id, call_id)`.
+pub struct CodecRegistry {
+    codecs: BTreeMap<(&'static str, &'static str), JsonCodec>,
+}
+
+impl CodecRegistry {
+    /// Create an empty codec registry.
+    pub fn new() -> Self {
+        Self {
+            codecs: BTreeMap::new(),
+        }
+    }
+
+    /// Register a codec for a module call.
+    pub fn with_codec(
+        mut self,
+        module_id: &'static str,
+        call_id: &'static str,
+        codec: JsonCodec,
+    ) -> Self {
+        self.codecs.insert((module_id, call_id), codec);
+        self
+    }
+
+    /// Create a registry covering every call of the default runtime modules.
+    pub fn with_default_codecs() -> Self {
+        Self::new()
+            .with_codec("bank_cgt", "transfer", encode_transfer)
+            .with_codec("bank_cgt", "transfer_to_handle", encode_transfer_to_handle)
+            .with_codec("bank_cgt", "mint_to", encode_mint_to)
+            .with_codec("avatars_profiles", "claim_archon", encode_empty)
+            .with_codec("avatars_profiles", "record_syzygy", encode_record_syzygy)
+            .with_codec("avatars_profiles", "grant_recorder", encode_recorder)
+            .with_codec("avatars_profiles", "revoke_recorder", encode_recorder)
+            .with_codec("avatars_profiles", "delete_aeon_profile", encode_empty)
+            .with_codec("nft_dgen", "mint_dgen", encode_mint_dgen)
+            .with_codec("nft_dgen", "transfer_nft", encode_transfer_nft)
+            .with_codec("nft_dgen", "set_uri_hint", encode_plain::<SetUriHintParams>)
+            .with_codec("fabric_manager", "register_asset", encode_register_asset)
+            .with_codec("fabric_manager", "reward_seeder", encode_reward_seeder)
+            .with_codec("fabric_manager", "set_uri_hint", encode_set_asset_uri_hint)
+            .with_codec("abyss_registry", "create_listing", encode_create_listing)
+            .with_codec(
+                "abyss_registry",
+                "update_listing_price",
+                encode_plain::<UpdateListingPriceParams>,
+            )
+            .with_codec(
+                "abyss_registry",
+                "cancel_listing",
+                encode_plain::<CancelListingParams>,
+            )
+            .with_codec(
+                "abyss_registry",
+                "buy_listing",
+                encode_plain::<BuyListingParams>,
+            )
+            .with_codec(
+                "abyss_registry",
+                "create_bundle_listing",
+                encode_create_bundle_listing,
+            )
+            .with_codec(
+                "abyss_registry",
+                "cancel_bundle_listing",
+                encode_plain::<CancelBundleListingParams>,
+            )
+            .with_codec(
+                "abyss_registry",
+                "buy_bundle_listing",
+                encode_plain::<BuyBundleListingParams>,
+            )
+            .with_codec("escrow", "create_escrow", encode_create_escrow)
+            .with_codec(
+                "escrow",
+                "release_escrow",
+                encode_plain::<ReleaseEscrowParams>,
+            )
+            .with_codec(
+                "escrow",
+                "refund_escrow",
+                encode_plain::<RefundEscrowParams>,
+            )
+            .with_codec("staking", "stake", encode_plain::<StakeParams>)
+            .with_codec("staking", "unstake", encode_plain::<UnstakeParams>)
+            .with_codec("staking", "withdraw_unbonded", encode_empty)
+            .with_codec("staking", "claim_rewards", encode_empty)
+            .with_codec(
+                "governance",
+                "create_proposal",
+                encode_plain::<CreateProposalParams>,
+            )
+            .with_codec("governance", "vote", encode_plain::<VoteParams>)
+            .with_codec("staking_cgt", "bond", encode_plain::<BondParams>)
+            .with_codec("staking_cgt", "unbond", encode_plain::<UnbondParams>)
+            .with_codec("staking_cgt", "withdraw", encode_empty)
+            .with_codec("quests", "create_quest", encode_plain::<CreateQuestParams>)
+            .with_codec("quests", "retire_quest", encode_plain::<RetireQuestParams>)
+            .with_codec(
+                "assets",
+                "register_asset",
+                encode_plain::<assets::RegisterAssetParams>,
+            )
+            .with_codec("assets", "mint_asset", encode_mint_asset)
+            .with_codec("assets", "transfer_asset", encode_transfer_asset)
+            .with_codec("multisig", "multisig_create", encode_multisig_create)
+            .with_codec("names", "register_name", encode_plain::<RegisterNameParams>)
+            .with_codec("batch", "execute_batch", encode_execute_batch)
+    }
+
+    /// Encode JSON params for a module call into its bincode payload.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<u8>)` with the payload
+    /// - `Err(String)` if the params are invalid or the call has no codec
+    ///   (the error lists the supported calls)
+    pub fn encode(
+        &self,
+        module_id: &str,
+        call_id: &str,
+        params: &Value,
+    ) -> Result<Vec<u8>, String> {
+        let codec = self
+            .codecs
+            .iter()
+            .find(|((m, c), _)| *m == module_id && *c == call_id)
+            .map(|(_, codec)| codec)
+            .ok_or_else(|| {
+                format!(
+                    "unsupported call {}.{}; supported: {}",
+                    module_id,
+                    call_id,
+                    self.supported().join(", ")
+                )
+            })?;
+        codec(params).map_err(|e| format!("invalid params for {}.{}: {}", module_id, call_id, e))
+    }
+
+    /// All supported calls as `module_id.call_id`, sorted.
+    pub fn supported(&self) -> Vec<String> {
+        self.codecs
+            .keys()
+            .map(|(m, c)| format!("{}.{}", m, c))
+            .collect()
+    }
+}
+
+impl Default for CodecRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a hex string into a 32-byte array.
+fn parse_hex32(s: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(s).map_err(|e| format!("invalid hex: {}", e))?;
+    bytes
+        .try_into()
+        .map_err(|_| "expected 32 bytes of hex".to_string())
+}
+
+fn parse_opt_hex32(s: Option<String>) -> Result<Option<[u8; 32]>, String> {
+    s.as_deref().map(parse_hex32).transpose()
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(params: &Value) -> Result<T, String> {
+    serde_json::from_value(params.clone()).map_err(|e| e.to_string())
+}
+
+fn to_payload<T: Serialize>(params: &T) -> Result<Vec<u8>, String> {
+    crate::core::codec::encode(params).map_err(|e| e.to_string())
+}
+
+/// Codec for params without byte arrays, whose JSON form is the struct itself.
+fn encode_plain<T: Serialize + for<'de> Deserialize<'de>>(
+    params: &Value,
+) -> Result<Vec<u8>, String> {
+    to_payload(&from_json::<T>(params)?)
+}
+
+/// Codec for calls that take no params.
+fn encode_empty(_params: &Value) -> Result<Vec<u8>, String> {
+    Ok(Vec::new())
+}
+
+#[derive(Deserialize)]
+struct AddressAmountJson {
+    to: String,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    amount: u64,
+}
+
+#[derive(Deserialize)]
+struct TransferJson {
+    to: String,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    amount: u64,
+    /// Hex-encoded.
+    #[serde(default)]
+    memo: Option<String>,
+}
+
+fn encode_transfer(params: &Value) -> Result<Vec<u8>, String> {
+    let p: TransferJson = from_json(params)?;
+    let memo = p
+        .memo
+        .map(|memo| hex::decode(memo).map_err(|e| format!("invalid memo hex: {}", e)))
+        .transpose()?;
+    to_payload(&TransferParams {
+        to: parse_hex32(&p.to)?,
+        amount: p.amount,
+        memo,
+    })
+}
+
+#[derive(Deserialize)]
+struct TransferToHandleJson {
+    handle: String,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    amount: u64,
+    /// Hex-encoded.
+    #[serde(default)]
+    memo: Option<String>,
+    resolved_to: String,
+}
+
+fn encode_transfer_to_handle(params: &Value) -> Result<Vec<u8>, String> {
+    let p: TransferToHandleJson = from_json(params)?;
+    let memo = p
+        .memo
+        .map(|memo| hex::decode(memo).map_err(|e| format!("invalid memo hex: {}", e)))
+        .transpose()?;
+    to_payload(&TransferToHandleParams {
+        handle: p.handle,
+        amount: p.amount,
+        memo,
+        resolved_to: parse_hex32(&p.resolved_to)?,
+    })
+}
+
+fn encode_mint_to(params: &Value) -> Result<Vec<u8>, String> {
+    let p: AddressAmountJson = from_json(params)?;
+    to_payload(&MintToParams {
+        to: parse_hex32(&p.to)?,
+        amount: p.amount,
+    })
+}
+
+#[derive(Deserialize)]
+struct RecordSyzygyJson {
+    from: String,
+    to: String,
+    weight: u64,
+}
+
+fn encode_record_syzygy(params: &Value) -> Result<Vec<u8>, String> {
+    let p: RecordSyzygyJson = from_json(params)?;
+    to_payload(&RecordSyzygyParams {
+        from: parse_hex32(&p.from)?,
+        to: parse_hex32(&p.to)?,
+        weight: p.weight,
+    })
+}
+
+#[derive(Deserialize)]
+struct RecorderJson {
+    recorder: String,
+}
+
+fn encode_recorder(params: &Value) -> Result<Vec<u8>, String> {
+    let p: RecorderJson = from_json(params)?;
+    to_payload(&RecorderParams {
+        recorder: parse_hex32(&p.recorder)?,
+    })
+}
+
+#[derive(Deserialize)]
+struct MintDgenJson {
+    fabric_root_hash: String,
+    #[serde(default)]
+    forge_model_id: Option<String>,
+    #[serde(default)]
+    forge_prompt_hash: Option<String>,
+    #[serde(default)]
+    royalty_recipient: Option<String>,
+    #[serde(default)]
+    royalty_bps: u16,
+    #[serde(default)]
+    uri_hint: Option<String>,
+    #[serde(default)]
+    royalty_splits: Vec<RoyaltySplitJson>,
+}
+
+#[derive(Deserialize)]
+struct RoyaltySplitJson {
+    recipient: String,
+    bps: u16,
+}
+
+fn encode_mint_dgen(params: &Value) -> Result<Vec<u8>, String> {
+    let p: MintDgenJson = from_json(params)?;
+    let royalty_splits = p
+        .royalty_splits
+        .iter()
+        .map(|split| Ok((parse_hex32(&split.recipient)?, split.bps)))
+        .collect::<Result<_, String>>()?;
+    to_payload(&MintDgenParams {
+        fabric_root_hash: parse_hex32(&p.fabric_root_hash)?,
+        forge_model_id: parse_opt_hex32(p.forge_model_id)?,
+        forge_prompt_hash: parse_opt_hex32(p.forge_prompt_hash)?,
+        royalty_recipient: parse_opt_hex32(p.royalty_recipient)?,
+        royalty_bps: p.royalty_bps,
+        uri_hint: p.uri_hint,
+        royalty_splits,
+    })
+}
+
+#[derive(Deserialize)]
+struct TransferNftJson {
+    token_id: NftId,
+    to: String,
+}
+
+fn encode_transfer_nft(params: &Value) -> Result<Vec<u8>, String> {
+    let p: TransferNftJson = from_json(params)?;
+    to_payload(&TransferNftParams {
+        token_id: p.token_id,
+        to: parse_hex32(&p.to)?,
+    })
+}
+
+#[derive(Deserialize)]
+struct RegisterAssetJson {
+    fabric_root_hash: String,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    initial_pool_cgt: u64,
+    #[serde(default)]
+    uri_hint: Option<String>,
+}
+
+fn encode_register_asset(params: &Value) -> Result<Vec<u8>, String> {
+    let p: RegisterAssetJson = from_json(params)?;
+    to_payload(&RegisterAssetParams {
+        fabric_root_hash: parse_hex32(&p.fabric_root_hash)?,
+        initial_pool_cgt: p.initial_pool_cgt,
+        uri_hint: p.uri_hint,
+    })
+}
+
+#[derive(Deserialize)]
+struct RewardSeederJson {
+    fabric_root_hash: String,
+    seeder: String,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    amount_cgt: u64,
+}
+
+#[derive(Deserialize)]
+struct SetAssetUriHintJson {
+    fabric_root_hash: String,
+    #[serde(default)]
+    uri_hint: Option<String>,
+}
+
+fn encode_set_asset_uri_hint(params: &Value) -> Result<Vec<u8>, String> {
+    let p: SetAssetUriHintJson = from_json(params)?;
+    to_payload(&SetAssetUriHintParams {
+        fabric_root_hash: parse_hex32(&p.fabric_root_hash)?,
+        uri_hint: p.uri_hint,
+    })
+}
+
+fn encode_reward_seeder(params: &Value) -> Result<Vec<u8>, String> {
+    let p: RewardSeederJson = from_json(params)?;
+    to_payload(&RewardSeederParams {
+        fabric_root_hash: parse_hex32(&p.fabric_root_hash)?,
+        seeder: parse_hex32(&p.seeder)?,
+        amount_cgt: p.amount_cgt,
+    })
+}
+
+#[derive(Deserialize)]
+struct CreateListingJson {
+    token_id: NftId,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    price_cgt: u64,
+    #[serde(default)]
+    allowed_buyer: Option<String>,
+}
+
+fn encode_create_listing(params: &Value) -> Result<Vec<u8>, String> {
+    let p: CreateListingJson = from_json(params)?;
+    to_payload(&CreateListingParams {
+        token_id: p.token_id,
+        price_cgt: p.price_cgt,
+        allowed_buyer: parse_opt_hex32(p.allowed_buyer)?,
+    })
+}
+
+/// A CGT amount in base units or as a decimal CGT string, for lists.
+#[derive(Deserialize)]
+struct CgtJson(#[serde(deserialize_with = "deserialize_cgt")] u64);
+
+#[derive(Deserialize)]
+struct CreateBundleListingJson {
+    token_ids: Vec<NftId>,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    price_cgt: u64,
+    #[serde(default)]
+    allocation: Vec<CgtJson>,
+    #[serde(default)]
+    allowed_buyer: Option<String>,
+}
+
+fn encode_create_bundle_listing(params: &Value) -> Result<Vec<u8>, String> {
+    let p: CreateBundleListingJson = from_json(params)?;
+    to_payload(&CreateBundleListingParams {
+        token_ids: p.token_ids,
+        price_cgt: p.price_cgt,
+        allocation: p.allocation.into_iter().map(|part| part.0).collect(),
+        allowed_buyer: parse_opt_hex32(p.allowed_buyer)?,
+    })
+}
+
+#[derive(Deserialize)]
+struct CreateEscrowJson {
+    beneficiary: String,
+    #[serde(deserialize_with = "deserialize_cgt")]
+    amount: u64,
+    refund_after_height: u64,
+}
+
+fn encode_create_escrow(params: &Value) -> Result<Vec<u8>, String> {
+    let p: CreateEscrowJson = from_json(params)?;
+    to_payload(&CreateEscrowParams {
+        beneficiary: parse_hex32(&p.beneficiary)?,
+        amount: p.amount,
+        refund_after_height: p.refund_after_height,
+    })
+}
+
+#[derive(Deserialize)]
+struct AssetAmountJson {
+    asset_id: String,
+    to: String,
+    amount: u64,
+}
+
+fn encode_mint_asset(params: &Value) -> Result<Vec<u8>, String> {
+    let p: AssetAmountJson = from_json(params)?;
+    to_payload(&assets::MintAssetParams {
+        asset_id: p.asset_id,
+        to: parse_hex32(&p.to)?,
+        amount: p.amount,
+    })
+}
+
+fn encode_transfer_asset(params: &Value) -> Result<Vec<u8>, String> {
+    let p: AssetAmountJson = from_json(params)?;
+    to_payload(&assets::TransferAssetParams {
+        asset_id: p.asset_id,
+        to: parse_hex32(&p.to)?,
+        amount: p.amount,
+    })
+}
+
+#[derive(Deserialize)]
+struct CreateMultisigJson {
+    keys: Vec<String>,
+    threshold: u8,
+}
+
+fn encode_multisig_create(params: &Value) -> Result<Vec<u8>, String> {
+    let p: CreateMultisigJson = from_json(params)?;
+    to_payload(&CreateMultisigParams {
+        keys: p
+            .keys
+            .iter()
+            .map(|key| parse_hex32(key))
+            .collect::<Result<_, _>>()?,
+        threshold: p.threshold,
+    })
+}
+
+#[derive(Deserialize)]
+struct BatchCallJson {
+    module_id: String,
+    call_id: String,
+    /// The call's bincode payload, as hex
+    #[serde(default)]
+    payload: Option<String>,
+    /// The call's JSON params, encoded by its own codec
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct ExecuteBatchJson {
+    calls: Vec<BatchCallJson>,
+}
+
+/// Each call takes either a hex `payload` or JSON `params` for the default
+/// codec of its module call.
+fn encode_execute_batch(params: &Value) -> Result<Vec<u8>, String> {
+    let p: ExecuteBatchJson = from_json(params)?;
+    let codecs = CodecRegistry::with_default_codecs();
+    let calls = p
+        .calls
+        .into_iter()
+        .map(|call| {
+            let payload = match (call.payload, call.params) {
+                (Some(payload), None) => {
+                    hex::decode(payload).map_err(|e| format!("invalid payload hex: {}", e))?
+                }
+                (None, Some(params)) => codecs.encode(&call.module_id, &call.call_id, &params)?,
+                _ => {
+                    return Err(format!(
+                        "batch call {}.{} needs either payload or params",
+                        call.module_id, call.call_id
+                    ))
+                }
+            };
+            Ok(BatchCall {
+                module_id: call.module_id,
+                call_id: call.call_id,
+                payload,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+    to_payload(&ExecuteBatchParams { calls })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::codec;
+    use serde_json::json;
+
+    #[test]
+    fn test_transfer_codec_matches_bincode() {
+        let registry = CodecRegistry::with_default_codecs();
+        let payload = registry
+            .encode(
+                "bank_cgt",
+                "transfer",
+                &json!({ "to": hex::encode([2u8; 32]), "amount": 300 }),
+            )
+            .unwrap();
+
+        let expected = codec::encode(&TransferParams {
+            to: [2u8; 32],
+            amount: 300,
+            memo: None,
+        })
+        .unwrap();
+        assert_eq!(payload, expected);
+
+        let memo = |memo: &str| {
+            registry.encode(
+                "bank_cgt",
+                "transfer",
+                &json!({ "to": hex::encode([2u8; 32]), "amount": 300, "memo": memo }),
+            )
+        };
+        let expected = codec::encode(&TransferParams {
+            to: [2u8; 32],
+            amount: 300,
+            memo: Some(b"INV-42".to_vec()),
+        })
+        .unwrap();
+        assert_eq!(memo(&hex::encode("INV-42")).unwrap(), expected);
+        assert!(memo("not hex").unwrap_err().contains("invalid memo hex"));
+    }
+
+    #[test]
+    fn test_cgt_amounts_accept_decimal_strings() {
+        let registry = CodecRegistry::with_default_codecs();
+        let transfer = |amount: Value| {
+            registry.encode(
+                "bank_cgt",
+                "transfer",
+                &json!({ "to": hex::encode([2u8; 32]), "amount": amount }),
+            )
+        };
+        assert_eq!(
+            transfer(json!("1.5")).unwrap(),
+            transfer(json!(150_000_000)).unwrap()
+        );
+        let err = transfer(json!("0.123456789")).unwrap_err();
+        assert!(err.contains("more than 8 decimal places"), "{}", err);
+
+        let listing = registry
+            .encode(
+                "abyss_registry",
+                "create_listing",
+                &json!({ "token_id": 1, "price_cgt": "2.0" }),
+            )
+            .unwrap();
+        let expected = codec::encode(&CreateListingParams {
+            token_id: 1,
+            price_cgt: 200_000_000,
+            allowed_buyer: None,
+        })
+        .unwrap();
+        assert_eq!(listing, expected);
+
+        // Bundle allocations too, part by part
+        let bundle = registry
+            .encode(
+                "abyss_registry",
+                "create_bundle_listing",
+                &json!({ "token_ids": [1, 2], "price_cgt": "2.0", "allocation": ["1.5", 50_000_000] }),
+            )
+            .unwrap();
+        let expected = codec::encode(&CreateBundleListingParams {
+            token_ids: vec![1, 2],
+            price_cgt: 200_000_000,
+            allocation: vec![150_000_000, 50_000_000],
+            allowed_buyer: None,
+        })
+        .unwrap();
+        assert_eq!(bundle, expected);
+    }
+
+    #[test]
+    fn test_batch_calls_take_payloads_or_params() {
+        let registry = CodecRegistry::with_default_codecs();
+        let transfer = json!({ "to": hex::encode([2u8; 32]), "amount": "1.5" });
+        let inner = registry.encode("bank_cgt", "transfer", &transfer).unwrap();
+        let batch =
+            |call: Value| registry.encode("batch", "execute_batch", &json!({ "calls": [call] }));
+
+        let expected = codec::encode(&ExecuteBatchParams {
+            calls: vec![BatchCall {
+                module_id: "bank_cgt".to_string(),
+                call_id: "transfer".to_string(),
+                payload: inner.clone(),
+            }],
+        })
+        .unwrap();
+        let by_params =
+            json!({ "module_id": "bank_cgt", "call_id": "transfer", "params": transfer });
+        let by_payload = json!({ "module_id": "bank_cgt", "call_id": "transfer", "payload": hex::encode(&inner) });
+        assert_eq!(batch(by_params).unwrap(), expected);
+        assert_eq!(batch(by_payload).unwrap(), expected);
+
+        let err = batch(json!({ "module_id": "bank_cgt", "call_id": "transfer" })).unwrap_err();
+        assert!(err.contains("needs either payload or params"), "{}", err);
+        let err =
+            batch(json!({ "module_id": "bank_cgt", "call_id": "burn", "params": {} })).unwrap_err();
+        assert!(err.contains("unsupported call bank_cgt.burn"), "{}", err);
+    }
+
+    #[test]
+    fn test_unsupported_call_lists_supported() {
+        let registry = CodecRegistry::with_default_codecs();
+        let err = registry.encode("bank_cgt", "burn", &json!({})).unwrap_err();
+        assert!(err.contains("unsupported call bank_cgt.burn"));
+        assert!(err.contains("bank_cgt.transfer"));
+        assert!(err.contains("nft_dgen.transfer_nft"));
+    }
+
+    #[test]
+    fn test_invalid_params_rejected() {
+        let registry = CodecRegistry::with_default_codecs();
+        let err = registry
+            .encode(
+                "nft_dgen",
+                "transfer_nft",
+                &json!({ "token_id": 1, "to": "abcd" }),
+            )
+            .unwrap_err();
+        assert!(err.contains("32 bytes"));
+    }
+}
@@ -0,0 +1,375 @@
+//! Escrow module for conditional CGT payments.
+//!
+//! This module handles:
+//! - Locking a payer's CGT for a beneficiary until the payer releases it
+//! - Refunding the payer once the escrow's deadline height has passed
+//!
+//! Locked CGT is held in the module's own account (`escrow_account`), which
+//! only this module's dispatch can spend from.
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::bank;
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::{Address, Transaction};
+
+const MODULE_ID: &str = "escrow";
+const PREFIX_ESCROW: &str = "escrow";
+const PREFIX_ESCROW_COUNTER: &str = "counter";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_ESCROW, PREFIX_ESCROW_COUNTER];
+
+/// Escrow ID type
+pub type EscrowId = u64;
+
+/// Lifecycle of an escrow.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowStatus {
+    /// Funds are held.
+    Open,
+    /// Funds went to the beneficiary.
+    Released,
+    /// Funds went back to the payer.
+    Refunded,
+}
+
+/// Escrowed CGT payment
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Escrow {
+    pub id: EscrowId,
+    pub payer: Address,
+    pub beneficiary: Address,
+    pub amount: u64,
+    /// The payer may reclaim the funds in blocks above this height.
+    pub refund_after_height: u64,
+    pub status: EscrowStatus,
+}
+
+/// Create escrow parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateEscrowParams {
+    pub beneficiary: Address,
+    pub amount: u64,
+    pub refund_after_height: u64,
+}
+
+/// Release escrow parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReleaseEscrowParams {
+    pub id: EscrowId,
+}
+
+/// Refund escrow parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefundEscrowParams {
+    pub id: EscrowId,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "create_escrow",
+        CallSchema::new(&[
+            FieldSchema::new("beneficiary", FieldType::Address),
+            FieldSchema::new("amount", FieldType::U64),
+            FieldSchema::new("refund_after_height", FieldType::U64),
+        ]),
+    ),
+    (
+        "release_escrow",
+        CallSchema::new(&[FieldSchema::new("id", FieldType::U64)]),
+    ),
+    (
+        "refund_escrow",
+        CallSchema::new(&[FieldSchema::new("id", FieldType::U64)]),
+    ),
+];
+
+// Helper functions for escrow management
+
+fn escrow_key(id: EscrowId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ESCROW)
+        .u64(id)
+        .into_bytes()
+}
+
+fn escrow_counter_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ESCROW_COUNTER).into_bytes()
+}
+
+fn load_escrow(state: &State, id: EscrowId) -> Option<Escrow> {
+    state
+        .get_raw(&escrow_key(id))
+        .and_then(|bytes| codec::decode_state::<Escrow>(&bytes).ok())
+}
+
+fn store_escrow(state: &mut State, escrow: &Escrow) -> Result<(), String> {
+    let bytes = codec::encode(escrow).map_err(|e| e.to_string())?;
+    state
+        .put_raw(escrow_key(escrow.id), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn get_next_escrow_id(state: &State) -> EscrowId {
+    state
+        .get_raw(&escrow_counter_key())
+        .and_then(|bytes| codec::decode_state::<EscrowId>(&bytes).ok())
+        .unwrap_or(0)
+}
+
+fn set_next_escrow_id(state: &mut State, next: EscrowId) -> Result<(), String> {
+    let bytes = codec::encode(&next).map_err(|e| e.to_string())?;
+    state
+        .put_raw(escrow_counter_key(), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Account holding the CGT of every open escrow.
+pub fn escrow_account() -> Address {
+    bank::module_address(MODULE_ID)
+}
+
+/// Move the CGT of escrows opened before escrowed funds were held in
+/// `escrow_account` into it, so releasing or refunding them can debit it.
+///
+/// Those escrows debited the payer without crediting anyone. Does nothing
+/// once the account is in the module account registry, so run it before the
+/// account is first registered.
+///
+/// # Returns
+/// The amount credited to the account
+pub fn backfill_escrow_account(state: &mut State) -> Result<u64, String> {
+    if bank::module_account_owner(state, &escrow_account()).is_some() {
+        return Ok(0);
+    }
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_ESCROW).into_bytes();
+    let held = state
+        .scan_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(_, bytes)| codec::decode_state::<Escrow>(&bytes).ok())
+        .filter(|escrow| escrow.status == EscrowStatus::Open)
+        .try_fold(0u64, |total, escrow| total.checked_add(escrow.amount))
+        .ok_or("overflow summing open escrows")?;
+    if held > 0 {
+        bank::credit(state, &escrow_account(), held)?;
+    }
+    Ok(held)
+}
+
+/// Public helper for querying an escrow (for RPC/SDK use).
+pub fn get_escrow(state: &State, id: EscrowId) -> Option<Escrow> {
+    load_escrow(state, id)
+}
+
+/// EscrowModule handles conditional CGT payments
+#[derive(Default)]
+pub struct EscrowModule;
+
+impl EscrowModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for EscrowModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["create_escrow", "release_escrow", "refund_escrow"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "create_escrow" => handle_create_escrow(tx, state),
+            "release_escrow" => handle_release_escrow(tx, state),
+            "refund_escrow" => handle_refund_escrow(tx, state),
+            other => Err(format!("escrow: unknown call_id '{}'", other)),
+        }
+    }
+}
+
+fn handle_create_escrow(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: CreateEscrowParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if params.amount == 0 {
+        return Err("escrow amount must be > 0".into());
+    }
+
+    if params.refund_after_height < state.block_height() {
+        return Err("refund_after_height is already in the past".into());
+    }
+
+    // Funds leave the payer's balance now and are held in the module account
+    bank::debit(state, &tx.from, params.amount)?;
+    bank::credit(state, &escrow_account(), params.amount)?;
+
+    let id = get_next_escrow_id(state);
+    set_next_escrow_id(state, id.checked_add(1).ok_or("escrow id overflow")?)?;
+
+    let escrow = Escrow {
+        id,
+        payer: tx.from,
+        beneficiary: params.beneficiary,
+        amount: params.amount,
+        refund_after_height: params.refund_after_height,
+        status: EscrowStatus::Open,
+    };
+
+    store_escrow(state, &escrow)
+}
+
+/// Load an open escrow that `caller` paid into.
+fn load_open_escrow_for_payer(
+    state: &State,
+    id: EscrowId,
+    caller: &Address,
+) -> Result<Escrow, String> {
+    let escrow = load_escrow(state, id).ok_or_else(|| "Escrow not found".to_string())?;
+
+    if escrow.payer != *caller {
+        return Err("only the escrow payer may settle it".into());
+    }
+
+    if escrow.status != EscrowStatus::Open {
+        return Err("escrow is already settled".into());
+    }
+
+    Ok(escrow)
+}
+
+fn handle_release_escrow(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: ReleaseEscrowParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let mut escrow = load_open_escrow_for_payer(state, params.id, &tx.from)?;
+
+    bank::debit(state, &escrow_account(), escrow.amount)?;
+    bank::credit(state, &escrow.beneficiary, escrow.amount)?;
+
+    escrow.status = EscrowStatus::Released;
+    store_escrow(state, &escrow)
+}
+
+fn handle_refund_escrow(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RefundEscrowParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let mut escrow = load_open_escrow_for_payer(state, params.id, &tx.from)?;
+
+    if state.block_height() <= escrow.refund_after_height {
+        return Err(format!(
+            "escrow cannot be refunded until after height {}",
+            escrow.refund_after_height
+        ));
+    }
+
+    bank::debit(state, &escrow_account(), escrow.amount)?;
+    bank::credit(state, &escrow.payer, escrow.amount)?;
+
+    escrow.status = EscrowStatus::Refunded;
+    store_escrow(state, &escrow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::bank_cgt::get_balance_cgt;
+
+    const PAYER: Address = [1; 32];
+    const BENEFICIARY: Address = [2; 32];
+
+    fn call_tx(call_id: &str, payload: Vec<u8>) -> Transaction {
+        Transaction {
+            from: PAYER,
+            nonce: 0,
+            module_id: "escrow".to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    fn funded_state_with_escrow() -> State {
+        let mut state = State::in_memory();
+        bank::credit(&mut state, &PAYER, 1_000).unwrap();
+
+        let params = CreateEscrowParams {
+            beneficiary: BENEFICIARY,
+            amount: 400,
+            refund_after_height: 10,
+        };
+        let tx = call_tx("create_escrow", codec::encode(&params).unwrap());
+        EscrowModule::new()
+            .dispatch("create_escrow", &tx, &mut state)
+            .unwrap();
+        state
+    }
+
+    #[test]
+    fn test_create_and_release() {
+        let mut state = funded_state_with_escrow();
+        assert_eq!(get_balance_cgt(&state, &PAYER), 600);
+        assert_eq!(get_balance_cgt(&state, &escrow_account()), 400);
+        assert_eq!(get_escrow(&state, 0).unwrap().status, EscrowStatus::Open);
+
+        let tx = call_tx(
+            "release_escrow",
+            codec::encode(&ReleaseEscrowParams { id: 0 }).unwrap(),
+        );
+        EscrowModule::new()
+            .dispatch("release_escrow", &tx, &mut state)
+            .unwrap();
+
+        assert_eq!(get_balance_cgt(&state, &BENEFICIARY), 400);
+        assert_eq!(get_balance_cgt(&state, &escrow_account()), 0);
+        assert_eq!(
+            get_escrow(&state, 0).unwrap().status,
+            EscrowStatus::Released
+        );
+
+        // Settled escrows cannot be paid out twice
+        assert!(EscrowModule::new()
+            .dispatch("release_escrow", &tx, &mut state)
+            .is_err());
+    }
+
+    #[test]
+    fn test_refund_only_after_deadline() {
+        let mut state = funded_state_with_escrow();
+        let tx = call_tx(
+            "refund_escrow",
+            codec::encode(&RefundEscrowParams { id: 0 }).unwrap(),
+        );
+
+        state.set_block_height(10);
+        let err = EscrowModule::new()
+            .dispatch("refund_escrow", &tx, &mut state)
+            .unwrap_err();
+        assert!(err.contains("until after height 10"));
+
+        state.set_block_height(11);
+        EscrowModule::new()
+            .dispatch("refund_escrow", &tx, &mut state)
+            .unwrap();
+
+        assert_eq!(get_balance_cgt(&state, &PAYER), 1_000);
+        assert_eq!(get_balance_cgt(&state, &BENEFICIARY), 0);
+        assert_eq!(
+            get_escrow(&state, 0).unwrap().status,
+            EscrowStatus::Refunded
+        );
+    }
+}
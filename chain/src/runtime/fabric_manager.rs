@@ -4,26 +4,103 @@
 //! - Registration of Fabric assets (by fabric_root_hash)
 //! - CGT fee pool management for seeder rewards
 //! - Distribution of rewards to seeders
+//! - Content location hints (`uri_hint`) for assets and the NFTs built on them
 
 use serde::{Deserialize, Serialize};
 
-use super::bank_cgt::{get_balance_cgt, set_balance_for_module};
-use super::RuntimeModule;
+use super::module_api::bank;
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::core::codec;
 use crate::core::state::State;
 use crate::core::transaction::{Address, Transaction};
 
-const PREFIX_FABRIC_ASSET: &[u8] = b"fabric:asset:";
+const MODULE_ID: &str = "fabric_manager";
+const PREFIX_FABRIC_ASSET: &str = "asset";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_FABRIC_ASSET];
 
 /// Fabric root hash type
 pub type FabricRootHash = [u8; 32];
 
+/// Longest `uri_hint` an asset or NFT may carry, in bytes.
+pub const MAX_URI_HINT_LEN: usize = 256;
+
+/// Schemes a `uri_hint` may use.
+pub const URI_HINT_SCHEMES: &[&str] = &["ipfs://", "https://", "fabric://"];
+
+/// Check a content location hint: at most `MAX_URI_HINT_LEN` bytes, one of
+/// `URI_HINT_SCHEMES` followed by a location, and no whitespace or control
+/// characters.
+pub fn validate_uri_hint(uri: &str) -> Result<(), String> {
+    if uri.len() > MAX_URI_HINT_LEN {
+        return Err(format!(
+            "uri_hint must be at most {} bytes",
+            MAX_URI_HINT_LEN
+        ));
+    }
+    let Some(location) = URI_HINT_SCHEMES
+        .iter()
+        .find_map(|scheme| uri.strip_prefix(scheme))
+    else {
+        return Err(format!(
+            "uri_hint must start with one of {}",
+            URI_HINT_SCHEMES.join(", ")
+        ));
+    };
+    if location.is_empty() {
+        return Err("uri_hint has no location after its scheme".into());
+    }
+    if uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("uri_hint may not contain whitespace or control characters".into());
+    }
+    Ok(())
+}
+
 /// Fabric asset with fee pool information
+///
+/// Stored versioned (see `codec::Versioned`); this is layout version 2.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FabricAsset {
     pub owner: Address, // Archon who registered it
     pub fabric_root_hash: FabricRootHash,
     pub pool_cgt_total: u64,     // total CGT originally allocated
     pub pool_cgt_remaining: u64, // remaining CGT to distribute
+    /// Where to fetch the asset's bytes (added in version 2)
+    pub uri_hint: Option<String>,
+}
+
+/// Version 1 asset layout, stored without a version prefix.
+#[derive(Deserialize)]
+struct FabricAssetV1 {
+    owner: Address,
+    fabric_root_hash: FabricRootHash,
+    pool_cgt_total: u64,
+    pool_cgt_remaining: u64,
+}
+
+impl From<FabricAssetV1> for FabricAsset {
+    fn from(v1: FabricAssetV1) -> Self {
+        Self {
+            owner: v1.owner,
+            fabric_root_hash: v1.fabric_root_hash,
+            pool_cgt_total: v1.pool_cgt_total,
+            pool_cgt_remaining: v1.pool_cgt_remaining,
+            uri_hint: None,
+        }
+    }
+}
+
+impl codec::Versioned for FabricAsset {
+    const VERSION: u8 = 2;
+
+    fn upgrade(version: Option<u8>, body: &[u8]) -> bincode::Result<Self> {
+        match version {
+            None | Some(1) => codec::decode_state_exact::<FabricAssetV1>(body).map(Into::into),
+            Some(other) => Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "no upgrade from Fabric asset version {}",
+                other
+            )))),
+        }
+    }
 }
 
 /// Register asset parameters
@@ -31,6 +108,22 @@ pub struct FabricAsset {
 pub struct RegisterAssetParams {
     pub fabric_root_hash: FabricRootHash,
     pub initial_pool_cgt: u64,
+    /// Where to fetch the asset's bytes; see `validate_uri_hint`.
+    pub uri_hint: Option<String>,
+}
+
+/// Register asset parameters as encoded before `uri_hint`.
+#[derive(Deserialize)]
+struct RegisterAssetParamsV1 {
+    fabric_root_hash: FabricRootHash,
+    initial_pool_cgt: u64,
+}
+
+/// Set or clear an asset's `uri_hint`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetAssetUriHintParams {
+    pub fabric_root_hash: FabricRootHash,
+    pub uri_hint: Option<String>,
 }
 
 /// Reward seeder parameters
@@ -41,22 +134,49 @@ pub struct RewardSeederParams {
     pub amount_cgt: u64,
 }
 
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "register_asset",
+        CallSchema::new(&[
+            FieldSchema::new("fabric_root_hash", FieldType::Hash),
+            FieldSchema::new("initial_pool_cgt", FieldType::U64),
+            FieldSchema::new("uri_hint", FieldType::Option(&FieldType::String)),
+        ]),
+    ),
+    (
+        "reward_seeder",
+        CallSchema::new(&[
+            FieldSchema::new("fabric_root_hash", FieldType::Hash),
+            FieldSchema::new("seeder", FieldType::Address),
+            FieldSchema::new("amount_cgt", FieldType::U64),
+        ]),
+    ),
+    (
+        "set_uri_hint",
+        CallSchema::new(&[
+            FieldSchema::new("fabric_root_hash", FieldType::Hash),
+            FieldSchema::new("uri_hint", FieldType::Option(&FieldType::String)),
+        ]),
+    ),
+];
+
 /// Helper functions for Fabric asset management
 
 fn asset_key(root: &FabricRootHash) -> Vec<u8> {
-    let mut key = Vec::from(PREFIX_FABRIC_ASSET);
-    key.extend_from_slice(root);
-    key
+    StorageKey::new(MODULE_ID, PREFIX_FABRIC_ASSET)
+        .bytes(root)
+        .into_bytes()
 }
 
 fn load_asset(state: &State, root: &FabricRootHash) -> Option<FabricAsset> {
     state
         .get_raw(&asset_key(root))
-        .and_then(|bytes| bincode::deserialize::<FabricAsset>(&bytes).ok())
+        .and_then(|bytes| codec::decode_versioned::<FabricAsset>(&bytes).ok())
 }
 
 fn store_asset(state: &mut State, asset: &FabricAsset) -> Result<(), String> {
-    let bytes = bincode::serialize(asset).map_err(|e| e.to_string())?;
+    let bytes = codec::encode_versioned(asset).map_err(|e| e.to_string())?;
     state
         .put_raw(asset_key(&asset.fabric_root_hash), bytes)
         .map_err(|e| e.to_string())
@@ -68,6 +188,7 @@ pub fn get_fabric_asset(state: &State, root: &FabricRootHash) -> Option<FabricAs
 }
 
 /// FabricManagerModule handles Fabric asset registration and seeder rewards
+#[derive(Default)]
 pub struct FabricManagerModule;
 
 impl FabricManagerModule {
@@ -78,21 +199,61 @@ impl FabricManagerModule {
 
 impl RuntimeModule for FabricManagerModule {
     fn module_id(&self) -> &'static str {
-        "fabric_manager"
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["register_asset", "reward_seeder", "set_uri_hint"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
     }
 
     fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
         match call_id {
             "register_asset" => handle_register_asset(tx, state),
             "reward_seeder" => handle_reward_seeder(tx, state),
+            "set_uri_hint" => handle_set_uri_hint(tx, state),
             other => Err(format!("fabric_manager: unknown call_id '{}'", other)),
         }
     }
+
+    /// CGT left in the assets' seeder reward pools.
+    fn held_cgt(&self, state: &State) -> u128 {
+        let prefix = StorageKey::new(MODULE_ID, PREFIX_FABRIC_ASSET).into_bytes();
+        state
+            .scan_prefix(&prefix)
+            .iter()
+            .filter_map(|(_, bytes)| codec::decode_versioned::<FabricAsset>(bytes).ok())
+            .map(|asset| u128::from(asset.pool_cgt_remaining))
+            .sum()
+    }
+}
+
+/// Decode `register_asset` params, including payloads from before
+/// `uri_hint`, which end after the pool amount.
+fn decode_register_asset(payload: &[u8]) -> Result<RegisterAssetParams, String> {
+    codec::decode_exact(payload).or_else(|e| {
+        codec::decode_exact::<RegisterAssetParamsV1>(payload)
+            .map(|v1| RegisterAssetParams {
+                fabric_root_hash: v1.fabric_root_hash,
+                initial_pool_cgt: v1.initial_pool_cgt,
+                uri_hint: None,
+            })
+            .map_err(|_| e.to_string())
+    })
 }
 
 fn handle_register_asset(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    let params: RegisterAssetParams =
-        bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params = decode_register_asset(&tx.payload)?;
+    if let Some(uri) = &params.uri_hint {
+        validate_uri_hint(uri)?;
+    }
 
     // Ensure not already registered
     if load_asset(state, &params.fabric_root_hash).is_some() {
@@ -100,20 +261,17 @@ fn handle_register_asset(tx: &Transaction, state: &mut State) -> Result<(), Stri
     }
 
     // Charge the Archon initial_pool_cgt from their CGT balance.
-    let from_balance = get_balance_cgt(state, &tx.from);
-    if from_balance < params.initial_pool_cgt {
+    if bank::balance(state, &tx.from) < params.initial_pool_cgt {
         return Err("insufficient CGT to seed Fabric fee pool".into());
     }
-
-    // Deduct from Archon using bank_cgt's helpers.
-    let new_balance = from_balance - params.initial_pool_cgt;
-    set_balance_for_module(state, &tx.from, new_balance)?;
+    bank::debit(state, &tx.from, params.initial_pool_cgt)?;
 
     let asset = FabricAsset {
         owner: tx.from,
         fabric_root_hash: params.fabric_root_hash,
         pool_cgt_total: params.initial_pool_cgt,
         pool_cgt_remaining: params.initial_pool_cgt,
+        uri_hint: params.uri_hint,
     };
 
     store_asset(state, &asset)?;
@@ -122,8 +280,7 @@ fn handle_register_asset(tx: &Transaction, state: &mut State) -> Result<(), Stri
 }
 
 fn handle_reward_seeder(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    let params: RewardSeederParams =
-        bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params: RewardSeederParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
     let mut asset = load_asset(state, &params.fabric_root_hash)
         .ok_or_else(|| "Fabric asset not found".to_string())?;
@@ -141,12 +298,142 @@ fn handle_reward_seeder(tx: &Transaction, state: &mut State) -> Result<(), Strin
     store_asset(state, &asset)?;
 
     // Credit seeder
-    let current_seeder_balance = get_balance_cgt(state, &params.seeder);
-    let new_seeder_balance = current_seeder_balance
-        .checked_add(params.amount_cgt)
-        .ok_or("overflow crediting seeder")?;
-
-    set_balance_for_module(state, &params.seeder, new_seeder_balance)?;
+    bank::credit(state, &params.seeder, params.amount_cgt)?;
 
     Ok(())
 }
+
+fn handle_set_uri_hint(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: SetAssetUriHintParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+    if let Some(uri) = &params.uri_hint {
+        validate_uri_hint(uri)?;
+    }
+
+    let mut asset = load_asset(state, &params.fabric_root_hash)
+        .ok_or_else(|| "Fabric asset not found".to_string())?;
+    if asset.owner != tx.from {
+        return Err("only the asset's owner may set its uri_hint".into());
+    }
+
+    asset.uri_hint = params.uri_hint;
+    store_asset(state, &asset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(from: Address, call_id: &str, payload: Vec<u8>) -> Transaction {
+        Transaction {
+            from,
+            nonce: 0,
+            module_id: MODULE_ID.to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        }
+    }
+
+    #[test]
+    fn test_uri_hint_schemes_and_bounds() {
+        for ok in [
+            "ipfs://bafy",
+            "https://cdn.example/a.png",
+            "fabric://root/0",
+        ] {
+            validate_uri_hint(ok).unwrap();
+        }
+        let too_long = format!("https://{}", "a".repeat(MAX_URI_HINT_LEN));
+        for (bad, reason) in [
+            ("http://example.com", "must start with"),
+            ("javascript:alert(1)", "must start with"),
+            ("IPFS://bafy", "must start with"),
+            ("ipfs://", "no location"),
+            ("https://a b", "whitespace"),
+            ("fabric://x\u{0}", "control"),
+            (too_long.as_str(), "at most"),
+        ] {
+            let err = validate_uri_hint(bad).unwrap_err();
+            assert!(err.contains(reason), "{}: {}", bad, err);
+        }
+    }
+
+    #[test]
+    fn test_set_uri_hint_owner_only_and_legacy_assets() {
+        let mut state = State::in_memory();
+        let owner = [1u8; 32];
+        let root = [7u8; 32];
+        // Version 1 layout as stored before uri_hint: no prefix
+        let v1 = codec::encode(&(owner, root, 5u64, 5u64)).unwrap();
+        state.put_raw(asset_key(&root), v1).unwrap();
+        assert_eq!(get_fabric_asset(&state, &root).unwrap().uri_hint, None);
+
+        let set = |uri: Option<&str>| {
+            codec::encode(&SetAssetUriHintParams {
+                fabric_root_hash: root,
+                uri_hint: uri.map(str::to_string),
+            })
+            .unwrap()
+        };
+        let module = FabricManagerModule::new();
+        let err = module
+            .dispatch(
+                "set_uri_hint",
+                &tx([2; 32], "set_uri_hint", set(Some("ipfs://x"))),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("only the asset's owner"));
+        let err = module
+            .dispatch(
+                "set_uri_hint",
+                &tx(owner, "set_uri_hint", set(Some("ftp://x"))),
+                &mut state,
+            )
+            .unwrap_err();
+        assert!(err.contains("must start with"));
+
+        module
+            .dispatch(
+                "set_uri_hint",
+                &tx(owner, "set_uri_hint", set(Some("ipfs://x"))),
+                &mut state,
+            )
+            .unwrap();
+        let asset = get_fabric_asset(&state, &root).unwrap();
+        assert_eq!(asset.uri_hint.as_deref(), Some("ipfs://x"));
+        assert_eq!(asset.pool_cgt_remaining, 5);
+        module
+            .dispatch(
+                "set_uri_hint",
+                &tx(owner, "set_uri_hint", set(None)),
+                &mut state,
+            )
+            .unwrap();
+        assert_eq!(get_fabric_asset(&state, &root).unwrap().uri_hint, None);
+    }
+
+    #[test]
+    fn test_legacy_register_asset_payloads_decode() {
+        let v1 = codec::encode(&([7u8; 32], 5u64)).unwrap();
+        let params = decode_register_asset(&v1).unwrap();
+        assert_eq!(
+            (params.fabric_root_hash, params.initial_pool_cgt),
+            ([7; 32], 5)
+        );
+        assert_eq!(params.uri_hint, None);
+
+        // A payload cut off inside its hint is not an older one without it
+        let payload = codec::encode(&RegisterAssetParams {
+            fabric_root_hash: [7; 32],
+            initial_pool_cgt: 5,
+            uri_hint: Some("ipfs://bafy".to_string()),
+        })
+        .unwrap();
+        assert!(decode_register_asset(&payload[..payload.len() - 3]).is_err());
+    }
+}
@@ -0,0 +1,853 @@
+//! Governance module for CGT-weighted parameter changes.
+//!
+//! This module handles:
+//! - Proposals by Archons to change a governed chain parameter
+//! - Votes weighted by the voter's held, staked, and bonded CGT at the time of voting,
+//!   with that weight locked until the proposal is tallied so the same CGT
+//!   cannot move to another address and vote again (see `check_vote_lock`)
+//! - Tallying proposals at the end of the first block after their deadline,
+//!   applying the change if it met the configured quorum and threshold
+//!
+//! Other modules read governed values with `get_param`, falling back to
+//! their built-in or config defaults until a proposal sets one.
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::{avatars, bank, staking};
+use super::storage_value::{decode_u64, put_u64};
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::{GovernanceConfig, CGT_UNIT};
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+
+const MODULE_ID: &str = "governance";
+const PREFIX_PROPOSAL: &str = "proposal";
+const PREFIX_PROPOSAL_COUNTER: &str = "counter";
+const PREFIX_VOTE: &str = "vote";
+const PREFIX_PARAM: &str = "param";
+const PREFIX_OPEN: &str = "open";
+const PREFIX_LOCK: &str = "lock";
+const STORAGE_PREFIXES: &[&str] = &[
+    PREFIX_PROPOSAL,
+    PREFIX_PROPOSAL_COUNTER,
+    PREFIX_VOTE,
+    PREFIX_PARAM,
+    PREFIX_OPEN,
+    PREFIX_LOCK,
+];
+
+/// Overrides the staking module's unbonding period, in blocks.
+pub const PARAM_UNBONDING_PERIOD_BLOCKS: &str = "staking.unbonding_period_blocks";
+
+/// Overrides the Abyss marketplace fee, in basis points of the sale price.
+pub const PARAM_MARKETPLACE_FEE_BPS: &str = "abyss.marketplace_fee_bps";
+
+/// Overrides the dev faucet's per-address cooldown, in blocks.
+pub const PARAM_FAUCET_COOLDOWN_BLOCKS: &str = "faucet.cooldown_blocks";
+
+/// Overrides the existential deposit, in CGT base units.
+pub const PARAM_EXISTENTIAL_DEPOSIT: &str = "bank.existential_deposit";
+
+/// Overrides the longest memo a CGT transfer may carry, in bytes.
+pub const PARAM_MAX_MEMO_BYTES: &str = "bank.max_memo_bytes";
+
+/// Overrides the share of an Aeon's Syzygy Score lost each decay epoch, in
+/// basis points.
+pub const PARAM_SYZYGY_DECAY_BPS: &str = "aeon.syzygy_decay_bps";
+
+/// Overrides the length of a Syzygy Score decay epoch, in blocks.
+pub const PARAM_SYZYGY_DECAY_EPOCH_BLOCKS: &str = "aeon.syzygy_decay_epoch_blocks";
+
+/// Overrides the Syzygy Score at which an Aeon earns the Luminary badge.
+pub const PARAM_LUMINARY_SYZYGY_THRESHOLD: &str = "aeon.luminary_syzygy_threshold";
+
+/// Overrides the CGT burned to register a name, in base units.
+pub const PARAM_NAME_REGISTRATION_FEE: &str = "names.registration_fee";
+
+/// A parameter proposals may change, with its allowed range.
+struct GovernedParam {
+    key: &'static str,
+    min: u64,
+    max: u64,
+}
+
+const GOVERNED_PARAMS: &[GovernedParam] = &[
+    GovernedParam {
+        key: PARAM_UNBONDING_PERIOD_BLOCKS,
+        min: 1,
+        max: 1_000_000,
+    },
+    GovernedParam {
+        key: PARAM_MARKETPLACE_FEE_BPS,
+        min: 0,
+        max: 1_000,
+    },
+    GovernedParam {
+        key: PARAM_FAUCET_COOLDOWN_BLOCKS,
+        min: 1,
+        max: 1_000_000,
+    },
+    GovernedParam {
+        key: PARAM_EXISTENTIAL_DEPOSIT,
+        min: 0,
+        max: CGT_UNIT,
+    },
+    GovernedParam {
+        key: PARAM_MAX_MEMO_BYTES,
+        min: 0,
+        max: 1_024,
+    },
+    GovernedParam {
+        key: PARAM_SYZYGY_DECAY_BPS,
+        min: 0,
+        max: 10_000,
+    },
+    GovernedParam {
+        key: PARAM_SYZYGY_DECAY_EPOCH_BLOCKS,
+        min: 1,
+        max: 10_000_000,
+    },
+    GovernedParam {
+        key: PARAM_LUMINARY_SYZYGY_THRESHOLD,
+        min: 1,
+        max: 10_000_000,
+    },
+    GovernedParam {
+        key: PARAM_NAME_REGISTRATION_FEE,
+        min: 0,
+        max: 1_000 * CGT_UNIT,
+    },
+];
+
+/// Proposal ID type
+pub type ProposalId = u64;
+
+/// Lifecycle of a proposal.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    /// Accepting votes, or past the deadline until the block-end tally.
+    Open,
+    /// Approved and applied.
+    Passed,
+    /// Tallied without reaching quorum or the approval threshold.
+    Rejected,
+}
+
+/// Parameter change proposal with its running tally
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Proposal {
+    pub id: ProposalId,
+    pub proposer: Address,
+    pub description: String,
+    pub param_key: String,
+    pub new_value: u64,
+    /// Votes are accepted up to and including this height.
+    pub voting_deadline: u64,
+    /// Total CGT weight of approving voters.
+    pub approve_weight: u64,
+    /// Total CGT weight of rejecting voters.
+    pub reject_weight: u64,
+    pub status: ProposalStatus,
+}
+
+/// Create proposal parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateProposalParams {
+    pub description: String,
+    pub param_key: String,
+    pub new_value: u64,
+    pub voting_deadline: u64,
+}
+
+/// Vote parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VoteParams {
+    pub proposal_id: ProposalId,
+    pub approve: bool,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "create_proposal",
+        CallSchema::new(&[
+            FieldSchema::new("description", FieldType::String),
+            FieldSchema::new("param_key", FieldType::String),
+            FieldSchema::new("new_value", FieldType::U64),
+            FieldSchema::new("voting_deadline", FieldType::U64),
+        ]),
+    ),
+    (
+        "vote",
+        CallSchema::new(&[
+            FieldSchema::new("proposal_id", FieldType::U64),
+            FieldSchema::new("approve", FieldType::Bool),
+        ]),
+    ),
+];
+
+// Helper functions for governance storage
+
+fn proposal_key(id: ProposalId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_PROPOSAL)
+        .u64(id)
+        .into_bytes()
+}
+
+fn proposal_counter_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_PROPOSAL_COUNTER).into_bytes()
+}
+
+fn vote_key(id: ProposalId, voter: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_VOTE)
+        .u64(id)
+        .address(voter)
+        .into_bytes()
+}
+
+fn param_key(key: &str) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_PARAM)
+        .bytes(key.as_bytes())
+        .into_bytes()
+}
+
+fn open_key(id: ProposalId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_OPEN).u64(id).into_bytes()
+}
+
+fn lock_key(voter: &Address, id: ProposalId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_LOCK)
+        .address(voter)
+        .u64(id)
+        .into_bytes()
+}
+
+fn load_proposal(state: &State, id: ProposalId) -> Option<Proposal> {
+    state
+        .get_raw(&proposal_key(id))
+        .and_then(|bytes| codec::decode_state::<Proposal>(&bytes).ok())
+}
+
+fn store_proposal(state: &mut State, proposal: &Proposal) -> Result<(), String> {
+    let bytes = codec::encode(proposal).map_err(|e| e.to_string())?;
+    state
+        .put_raw(proposal_key(proposal.id), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn get_next_proposal_id(state: &State) -> ProposalId {
+    state
+        .get_raw(&proposal_counter_key())
+        .and_then(|bytes| codec::decode_state::<ProposalId>(&bytes).ok())
+        .unwrap_or(0)
+}
+
+fn set_next_proposal_id(state: &mut State, next: ProposalId) -> Result<(), String> {
+    let bytes = codec::encode(&next).map_err(|e| e.to_string())?;
+    state
+        .put_raw(proposal_counter_key(), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Public helper for querying a proposal (for RPC/SDK use).
+pub fn get_proposal(state: &State, id: ProposalId) -> Option<Proposal> {
+    load_proposal(state, id)
+}
+
+/// All proposals, in id order.
+pub fn list_proposals(state: &State) -> Vec<Proposal> {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_PROPOSAL).into_bytes();
+    state
+        .scan_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(_, bytes)| codec::decode_state::<Proposal>(&bytes).ok())
+        .collect()
+}
+
+/// Current value of a governed parameter, if a proposal has set one.
+pub fn get_param(state: &State, key: &str) -> Option<u64> {
+    state
+        .get_raw(&param_key(key))
+        .and_then(|bytes| codec::decode_state::<u64>(&bytes).ok())
+}
+
+/// CGT an address can vote with: its free balance plus staked and bonded CGT.
+fn voting_weight(state: &State, addr: &Address) -> u64 {
+    let staked = staking::stake(state, addr, state.block_height()).map_or(0, |s| s.amount);
+    bank::balance(state, addr)
+        .saturating_add(staked)
+        .saturating_add(staking::bonded(state, addr))
+}
+
+/// CGT weight an address voted with on proposals not yet tallied: the
+/// largest of those votes, or 0.
+pub fn locked_weight(state: &State, addr: &Address) -> u64 {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_LOCK)
+        .address(addr)
+        .into_bytes();
+    state
+        .scan_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(_, value)| decode_u64(&value))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Check that `amount` of an address's CGT may leave its holdings.
+///
+/// A vote's weight stays locked until its proposal is tallied: what the
+/// address holds, stakes, and bonds must not drop below it, or the same CGT
+/// could vote again from another address. Moving CGT into the address's own
+/// stake or bond keeps its weight, so `bank_cgt` does not check that.
+pub fn check_vote_lock(state: &State, addr: &Address, amount: u64) -> Result<(), String> {
+    let locked = locked_weight(state, addr);
+    if locked == 0 || voting_weight(state, addr).saturating_sub(amount) >= locked {
+        return Ok(());
+    }
+    Err(format!(
+        "{} has {} base units locked by votes on open proposals",
+        addr.to_display(),
+        locked
+    ))
+}
+
+/// GovernanceModule handles CGT-weighted parameter proposals
+#[derive(Default)]
+pub struct GovernanceModule {
+    rules: GovernanceConfig,
+}
+
+impl GovernanceModule {
+    pub fn new() -> Self {
+        Self::with_rules(GovernanceConfig::default())
+    }
+
+    /// Create the module with the chain's quorum and threshold rules.
+    pub fn with_rules(rules: GovernanceConfig) -> Self {
+        Self { rules }
+    }
+
+    /// Whether a proposal's tally meets quorum and the approval threshold.
+    fn passes(&self, proposal: &Proposal) -> bool {
+        let approve = proposal.approve_weight as u128;
+        let total = approve + proposal.reject_weight as u128;
+        total >= self.rules.quorum as u128
+            && total > 0
+            && approve * 10_000 > total * self.rules.approval_threshold_bps as u128
+    }
+
+    /// Tally a proposal whose voting period has ended, applying it if it passed.
+    fn tally(&self, state: &mut State, mut proposal: Proposal) -> Result<(), String> {
+        if self.passes(&proposal) {
+            // Lazily applied decay must still see the old rates up to here
+            if [PARAM_SYZYGY_DECAY_BPS, PARAM_SYZYGY_DECAY_EPOCH_BLOCKS]
+                .contains(&proposal.param_key.as_str())
+            {
+                avatars::checkpoint_syzygy_decay(state)?;
+            }
+            let bytes = codec::encode(&proposal.new_value).map_err(|e| e.to_string())?;
+            state
+                .put_raw(param_key(&proposal.param_key), bytes)
+                .map_err(|e| e.to_string())?;
+            proposal.status = ProposalStatus::Passed;
+        } else {
+            proposal.status = ProposalStatus::Rejected;
+        }
+
+        // Release the voters' locked weight
+        let votes = StorageKey::new(MODULE_ID, PREFIX_VOTE)
+            .u64(proposal.id)
+            .into_bytes();
+        for (key, _) in state.scan_prefix(&votes) {
+            let Ok(voter) = Address::try_from(&key[votes.len()..]) else {
+                continue;
+            };
+            state
+                .delete_raw(&lock_key(&voter, proposal.id))
+                .map_err(|e| e.to_string())?;
+        }
+        state
+            .delete_raw(&open_key(proposal.id))
+            .map_err(|e| e.to_string())?;
+        store_proposal(state, &proposal)
+    }
+}
+
+impl RuntimeModule for GovernanceModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["create_proposal", "vote"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "create_proposal" => handle_create_proposal(tx, state),
+            "vote" => handle_vote(tx, state),
+            other => Err(format!("governance: unknown call_id '{}'", other)),
+        }
+    }
+
+    /// Tally every open proposal whose voting period ended before this block.
+    fn on_block_end(&self, state: &mut State) -> Result<(), String> {
+        let height = state.block_height();
+        let prefix = StorageKey::new(MODULE_ID, PREFIX_OPEN).into_bytes();
+        for (key, _) in state.scan_prefix(&prefix) {
+            let Ok(id_bytes) = <[u8; 8]>::try_from(&key[prefix.len()..]) else {
+                continue;
+            };
+            let Some(proposal) = load_proposal(state, ProposalId::from_be_bytes(id_bytes)) else {
+                continue;
+            };
+            if height > proposal.voting_deadline {
+                self.tally(state, proposal)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn handle_create_proposal(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: CreateProposalParams =
+        codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let governed = GOVERNED_PARAMS
+        .iter()
+        .find(|p| p.key == params.param_key)
+        .ok_or_else(|| format!("{} is not a governed parameter", params.param_key))?;
+
+    if params.new_value < governed.min || params.new_value > governed.max {
+        return Err(format!(
+            "{} must be between {} and {}",
+            governed.key, governed.min, governed.max
+        ));
+    }
+
+    if params.voting_deadline < state.block_height() {
+        return Err("voting_deadline is already in the past".into());
+    }
+
+    if !avatars::is_archon(state, &tx.from) {
+        return Err("only Archons may create proposals".into());
+    }
+
+    let id = get_next_proposal_id(state);
+    set_next_proposal_id(state, id.checked_add(1).ok_or("proposal id overflow")?)?;
+
+    let proposal = Proposal {
+        id,
+        proposer: tx.from,
+        description: params.description,
+        param_key: params.param_key,
+        new_value: params.new_value,
+        voting_deadline: params.voting_deadline,
+        approve_weight: 0,
+        reject_weight: 0,
+        status: ProposalStatus::Open,
+    };
+
+    state
+        .put_raw(open_key(id), vec![1u8])
+        .map_err(|e| e.to_string())?;
+    store_proposal(state, &proposal)
+}
+
+fn handle_vote(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: VoteParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let mut proposal =
+        load_proposal(state, params.proposal_id).ok_or_else(|| "Proposal not found".to_string())?;
+
+    if state.block_height() > proposal.voting_deadline {
+        return Err("voting has closed for this proposal".into());
+    }
+
+    let voted_key = vote_key(proposal.id, &tx.from);
+    if state.get_raw(&voted_key).is_some() {
+        return Err("address has already voted on this proposal".into());
+    }
+
+    let weight = voting_weight(state, &tx.from);
+    if weight == 0 {
+        return Err("only CGT holders may vote".into());
+    }
+
+    // Weights are u64 amounts, so the tally saturates only past total supply
+    if params.approve {
+        proposal.approve_weight = proposal.approve_weight.saturating_add(weight);
+    } else {
+        proposal.reject_weight = proposal.reject_weight.saturating_add(weight);
+    }
+
+    state
+        .put_raw(voted_key, vec![params.approve as u8])
+        .map_err(|e| e.to_string())?;
+    put_u64(state, lock_key(&tx.from, proposal.id), weight)?;
+    store_proposal(state, &proposal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CGT_UNIT;
+
+    const PROPOSER: Address = [1; 32];
+    const WHALE: Address = [2; 32];
+    const MINNOW: Address = [3; 32];
+
+    fn call(
+        state: &mut State,
+        from: Address,
+        call_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<(), String> {
+        let tx = Transaction {
+            from,
+            nonce: 0,
+            module_id: "governance".to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        GovernanceModule::new().dispatch(call_id, &tx, state)
+    }
+
+    fn propose(state: &mut State, param_key: &str, new_value: u64) -> Result<(), String> {
+        let params = CreateProposalParams {
+            description: format!("Set {}", param_key),
+            param_key: param_key.to_string(),
+            new_value,
+            voting_deadline: 10,
+        };
+        call(
+            state,
+            PROPOSER,
+            "create_proposal",
+            codec::encode(&params).unwrap(),
+        )
+    }
+
+    fn vote(state: &mut State, from: Address, approve: bool) -> Result<(), String> {
+        let params = VoteParams {
+            proposal_id: 0,
+            approve,
+        };
+        call(state, from, "vote", codec::encode(&params).unwrap())
+    }
+
+    /// Run the governance block-end hook at `height`.
+    fn end_block_at(state: &mut State, height: u64) {
+        state.set_block_height(height);
+        GovernanceModule::new().on_block_end(state).unwrap();
+    }
+
+    fn state_with_proposal() -> State {
+        let mut state = State::in_memory();
+        avatars::grant_archon(&mut state, &PROPOSER).unwrap();
+        bank::mint(&mut state, &WHALE, 1_000 * CGT_UNIT).unwrap();
+        bank::mint(&mut state, &MINNOW, 100 * CGT_UNIT).unwrap();
+
+        propose(&mut state, PARAM_UNBONDING_PERIOD_BLOCKS, 50).unwrap();
+        state
+    }
+
+    #[test]
+    fn test_create_proposal() {
+        let mut state = state_with_proposal();
+        let proposal = get_proposal(&state, 0).unwrap();
+        assert_eq!(proposal.proposer, PROPOSER);
+        assert_eq!(proposal.status, ProposalStatus::Open);
+
+        let err = propose(&mut state, "bank.mint_everything", 1).unwrap_err();
+        assert!(err.contains("not a governed parameter"));
+
+        // CGT alone does not qualify a proposer
+        let params = CreateProposalParams {
+            description: "Whale wants cheaper trades".to_string(),
+            param_key: PARAM_MARKETPLACE_FEE_BPS.to_string(),
+            new_value: 0,
+            voting_deadline: 10,
+        };
+        let err = call(
+            &mut state,
+            WHALE,
+            "create_proposal",
+            codec::encode(&params).unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.contains("only Archons"));
+        assert_eq!(list_proposals(&state).len(), 1);
+    }
+
+    #[test]
+    fn test_votes_weighted_by_held_and_staked_cgt() {
+        let mut state = state_with_proposal();
+
+        // Staked CGT leaves the free balance but still counts
+        let stake = crate::runtime::staking::StakeParams {
+            amount: 40 * CGT_UNIT,
+        };
+        let tx = Transaction {
+            from: MINNOW,
+            nonce: 0,
+            module_id: "staking".to_string(),
+            call_id: "stake".to_string(),
+            payload: codec::encode(&stake).unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        crate::runtime::StakingModule::new()
+            .dispatch("stake", &tx, &mut state)
+            .unwrap();
+
+        vote(&mut state, MINNOW, true).unwrap();
+        vote(&mut state, WHALE, false).unwrap();
+        assert!(vote(&mut state, WHALE, true).is_err());
+        assert!(vote(&mut state, [9; 32], true).is_err());
+
+        let proposal = get_proposal(&state, 0).unwrap();
+        assert_eq!(proposal.approve_weight, 100 * CGT_UNIT);
+        assert_eq!(proposal.reject_weight, 1_000 * CGT_UNIT);
+
+        state.set_block_height(11);
+        let err = vote(&mut state, PROPOSER, true).unwrap_err();
+        assert!(err.contains("voting has closed"));
+    }
+
+    #[test]
+    fn test_vote_weight_locked_until_tally() {
+        use crate::runtime::bank_cgt::{BankCgtModule, TransferParams};
+        let mut state = state_with_proposal();
+        vote(&mut state, MINNOW, true).unwrap();
+        assert_eq!(locked_weight(&state, &MINNOW), 100 * CGT_UNIT);
+
+        // The CGT that voted cannot move to another address to vote again
+        let transfer = |amount| Transaction {
+            from: MINNOW,
+            nonce: 0,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to: [9; 32],
+                amount,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let err = BankCgtModule::new()
+            .dispatch("transfer", &transfer(CGT_UNIT), &mut state)
+            .unwrap_err();
+        assert!(err.contains("locked by votes"), "{}", err);
+        assert!(bank::debit(&mut state, &MINNOW, 1).is_err());
+        assert!(vote(&mut state, [9; 32], true).is_err());
+
+        // Staking it keeps its weight, so it may
+        let stake = crate::runtime::staking::StakeParams {
+            amount: 40 * CGT_UNIT,
+        };
+        let tx = Transaction {
+            module_id: "staking".to_string(),
+            call_id: "stake".to_string(),
+            payload: codec::encode(&stake).unwrap(),
+            ..transfer(0)
+        };
+        crate::runtime::StakingModule::new()
+            .dispatch("stake", &tx, &mut state)
+            .unwrap();
+
+        // CGT received after voting is free, and the tally releases the rest
+        bank::credit(&mut state, &MINNOW, CGT_UNIT).unwrap();
+        BankCgtModule::new()
+            .dispatch("transfer", &transfer(CGT_UNIT), &mut state)
+            .unwrap();
+        end_block_at(&mut state, 11);
+        assert_eq!(locked_weight(&state, &MINNOW), 0);
+        assert_eq!(bank::debit(&mut state, &MINNOW, 60 * CGT_UNIT), Ok(0));
+    }
+
+    #[test]
+    fn test_passed_proposal_applied_after_deadline() {
+        let mut state = state_with_proposal();
+        vote(&mut state, WHALE, true).unwrap();
+        vote(&mut state, MINNOW, false).unwrap();
+
+        // Still open through the deadline block
+        end_block_at(&mut state, 10);
+        assert_eq!(
+            get_proposal(&state, 0).unwrap().status,
+            ProposalStatus::Open
+        );
+        assert_eq!(get_param(&state, PARAM_UNBONDING_PERIOD_BLOCKS), None);
+
+        end_block_at(&mut state, 11);
+        assert_eq!(
+            get_proposal(&state, 0).unwrap().status,
+            ProposalStatus::Passed
+        );
+        assert_eq!(get_param(&state, PARAM_UNBONDING_PERIOD_BLOCKS), Some(50));
+
+        // Staking picks up the governed unbonding period
+        use crate::runtime::staking::{get_unbondings, StakeParams, UnstakeParams};
+        use crate::runtime::StakingModule;
+        for (call_id, payload) in [
+            ("stake", codec::encode(&StakeParams { amount: 10 }).unwrap()),
+            (
+                "unstake",
+                codec::encode(&UnstakeParams { amount: 10 }).unwrap(),
+            ),
+        ] {
+            let tx = Transaction {
+                from: MINNOW,
+                nonce: 0,
+                module_id: "staking".to_string(),
+                call_id: call_id.to_string(),
+                payload,
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            };
+            StakingModule::new()
+                .dispatch(call_id, &tx, &mut state)
+                .unwrap();
+        }
+        assert_eq!(get_unbondings(&state, &MINNOW)[0].release_height, 61);
+    }
+
+    #[test]
+    fn test_rejected_proposal_left_unapplied() {
+        let mut state = state_with_proposal();
+        vote(&mut state, WHALE, false).unwrap();
+        vote(&mut state, MINNOW, true).unwrap();
+
+        end_block_at(&mut state, 11);
+        assert_eq!(
+            get_proposal(&state, 0).unwrap().status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(get_param(&state, PARAM_UNBONDING_PERIOD_BLOCKS), None);
+    }
+
+    #[test]
+    fn test_unanimous_proposal_fails_without_quorum() {
+        let mut state = state_with_proposal();
+        vote(&mut state, MINNOW, true).unwrap();
+
+        // 100 CGT voted, below the default 1,000 CGT quorum
+        end_block_at(&mut state, 11);
+        assert_eq!(
+            get_proposal(&state, 0).unwrap().status,
+            ProposalStatus::Rejected
+        );
+        assert_eq!(get_param(&state, PARAM_UNBONDING_PERIOD_BLOCKS), None);
+
+        // The same tally passes under a lower configured quorum
+        let mut state = state_with_proposal();
+        vote(&mut state, MINNOW, true).unwrap();
+        state.set_block_height(11);
+        GovernanceModule::with_rules(GovernanceConfig {
+            quorum: 100 * CGT_UNIT,
+            ..GovernanceConfig::default()
+        })
+        .on_block_end(&mut state)
+        .unwrap();
+        assert_eq!(get_param(&state, PARAM_UNBONDING_PERIOD_BLOCKS), Some(50));
+    }
+
+    #[test]
+    fn test_marketplace_fee_takes_effect() {
+        use crate::runtime::abyss_registry::{BuyListingParams, CreateListingParams};
+        use crate::runtime::module_api::nft;
+        use crate::runtime::nft_dgen::MintDgenParams;
+        use crate::runtime::AbyssRegistryModule;
+
+        let mut state = state_with_proposal();
+        let params = CreateProposalParams {
+            description: "2% marketplace fee".to_string(),
+            param_key: PARAM_MARKETPLACE_FEE_BPS.to_string(),
+            new_value: 200,
+            voting_deadline: 10,
+        };
+        call(
+            &mut state,
+            PROPOSER,
+            "create_proposal",
+            codec::encode(&params).unwrap(),
+        )
+        .unwrap();
+        let params = VoteParams {
+            proposal_id: 1,
+            approve: true,
+        };
+        call(&mut state, WHALE, "vote", codec::encode(&params).unwrap()).unwrap();
+        end_block_at(&mut state, 11);
+        assert_eq!(get_param(&state, PARAM_MARKETPLACE_FEE_BPS), Some(200));
+
+        // The seller receives the price less the burned fee
+        let mint = MintDgenParams {
+            fabric_root_hash: [0; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
+        };
+        let token_id = nft::mint(&mut state, &PROPOSER, &mint).unwrap();
+        let supply = bank::total_supply(&state);
+        let abyss = AbyssRegistryModule::new();
+        for (from, call_id, payload) in [
+            (
+                PROPOSER,
+                "create_listing",
+                codec::encode(&CreateListingParams {
+                    token_id,
+                    price_cgt: 50 * CGT_UNIT,
+                    allowed_buyer: None,
+                })
+                .unwrap(),
+            ),
+            (
+                MINNOW,
+                "buy_listing",
+                codec::encode(&BuyListingParams { listing_id: 0 }).unwrap(),
+            ),
+        ] {
+            let tx = Transaction {
+                from,
+                nonce: 0,
+                module_id: "abyss_registry".to_string(),
+                call_id: call_id.to_string(),
+                payload,
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            };
+            abyss.dispatch(call_id, &tx, &mut state).unwrap();
+        }
+        assert_eq!(bank::balance(&state, &MINNOW), 50 * CGT_UNIT);
+        assert_eq!(bank::balance(&state, &PROPOSER), 49 * CGT_UNIT);
+        assert_eq!(bank::total_supply(&state), supply - CGT_UNIT);
+    }
+}
@@ -0,0 +1,120 @@
+//! Sets of ids indexed under a key prefix, one storage key per id.
+//!
+//! An index kept as one `Vec` value is rewritten whole on every insert and
+//! removal, so a write costs as much as the index is long, and an index
+//! shared by many users (e.g. the NFTs held by a marketplace's escrow
+//! account) grows without bound. Here each id is its own key, the prefix
+//! followed by the id big-endian, and reading the index is a prefix scan in
+//! id order. Inserting or removing one id writes one key.
+//!
+//! Indexes stored before this layout, as a bincode `Vec<u64>` under the
+//! prefix itself, still read, and are split into per-id keys the first time
+//! the index is written.
+
+use crate::core::codec;
+use crate::core::state::State;
+
+/// Value stored under each id's key; the key alone carries the entry.
+const PRESENT: &[u8] = &[1];
+
+fn id_key(prefix: &[u8], id: u64) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(&id.to_be_bytes());
+    key
+}
+
+/// The ids indexed under `prefix`, in ascending order.
+pub fn ids(state: &State, prefix: &[u8]) -> Vec<u64> {
+    let mut ids = Vec::new();
+    for (key, value) in state.scan_prefix(prefix) {
+        let rest = &key[prefix.len()..];
+        if rest.is_empty() {
+            ids.extend(codec::decode_state::<Vec<u64>>(&value).unwrap_or_default());
+        } else if let Ok(id) = <[u8; 8]>::try_from(rest) {
+            ids.push(u64::from_be_bytes(id));
+        }
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
+/// Whether `id` is indexed under `prefix`.
+pub fn contains(state: &State, prefix: &[u8], id: u64) -> bool {
+    state.get_raw(&id_key(prefix, id)).is_some()
+        || state
+            .get_raw(prefix)
+            .and_then(|bytes| codec::decode_state::<Vec<u64>>(&bytes).ok())
+            .is_some_and(|legacy| legacy.contains(&id))
+}
+
+/// Index `id` under `prefix`.
+pub fn insert(state: &mut State, prefix: &[u8], id: u64) -> Result<(), String> {
+    split_legacy(state, prefix)?;
+    state
+        .put_raw(id_key(prefix, id), PRESENT.to_vec())
+        .map_err(|e| e.to_string())
+}
+
+/// Drop `id` from the index under `prefix`; nothing happens if it is absent.
+pub fn remove(state: &mut State, prefix: &[u8], id: u64) -> Result<(), String> {
+    split_legacy(state, prefix)?;
+    state
+        .delete_raw(&id_key(prefix, id))
+        .map_err(|e| e.to_string())
+}
+
+/// Replace an index stored as one `Vec` under `prefix` with per-id keys.
+fn split_legacy(state: &mut State, prefix: &[u8]) -> Result<(), String> {
+    let Some(bytes) = state.get_raw(prefix) else {
+        return Ok(());
+    };
+    for id in codec::decode_state::<Vec<u64>>(&bytes).unwrap_or_default() {
+        state
+            .put_raw(id_key(prefix, id), PRESENT.to_vec())
+            .map_err(|e| e.to_string())?;
+    }
+    state.delete_raw(prefix).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREFIX: &[u8] = b"test/owner/";
+
+    #[test]
+    fn test_ids_are_one_key_each_in_order() {
+        let mut state = State::in_memory();
+        for id in [5, 1, 300] {
+            insert(&mut state, PREFIX, id).unwrap();
+        }
+        insert(&mut state, PREFIX, 5).unwrap();
+        assert_eq!(ids(&state, PREFIX), vec![1, 5, 300]);
+        assert_eq!(state.scan_prefix(PREFIX).len(), 3);
+
+        remove(&mut state, PREFIX, 5).unwrap();
+        remove(&mut state, PREFIX, 9).unwrap();
+        assert_eq!(ids(&state, PREFIX), vec![1, 300]);
+        assert!(contains(&state, PREFIX, 300));
+        assert!(!contains(&state, PREFIX, 5));
+
+        remove(&mut state, PREFIX, 1).unwrap();
+        remove(&mut state, PREFIX, 300).unwrap();
+        assert!(state.scan_prefix(PREFIX).is_empty());
+    }
+
+    #[test]
+    fn test_legacy_vec_reads_and_splits_on_write() {
+        let mut state = State::in_memory();
+        state
+            .put_raw(PREFIX.to_vec(), codec::encode(&vec![7u64, 2]).unwrap())
+            .unwrap();
+        assert_eq!(ids(&state, PREFIX), vec![2, 7]);
+        assert!(contains(&state, PREFIX, 7));
+
+        remove(&mut state, PREFIX, 7).unwrap();
+        assert!(state.get_raw(PREFIX).is_none());
+        assert_eq!(ids(&state, PREFIX), vec![2]);
+    }
+}
@@ -4,24 +4,70 @@
 //! runtime modules. In Phase 3, concrete modules (bank_cgt, nft_dgen, etc.)
 //! are registered here and handle transaction execution.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::config::ChainConfig;
+use crate::core::merkle;
 use crate::core::state::State;
-use crate::core::transaction::Transaction;
+use crate::core::transaction::{AddressEncoding, Transaction};
+use crate::module_state;
 
 pub mod abyss_registry;
+pub mod assets;
 pub mod avatars_profiles;
 pub mod bank_cgt;
+pub mod batch;
+pub mod call_schema;
+pub mod codec;
+pub mod escrow;
 pub mod fabric_manager;
+pub mod governance;
+pub mod id_index;
+pub mod module_api;
+pub mod multisig;
+pub mod names;
 pub mod nft_dgen;
+pub mod quests;
+pub mod staking;
+pub mod staking_cgt;
+pub mod storage_key;
+pub mod storage_value;
 
-pub use abyss_registry::{get_listing, AbyssRegistryModule, ListingId};
+pub use abyss_registry::{
+    bundle_breakdown, get_active_bundles, get_bundle_listing, get_escrowed_listings, get_listing,
+    get_royalty_payouts, get_royalty_stats, listing_escrow_account, purchase_breakdown,
+    AbyssRegistryModule, BundleListing, ListingId, PurchaseBreakdown, RoyaltyPayout, RoyaltyStats,
+};
+pub use assets::{balance_of_asset, get_asset, AssetId, AssetsModule};
 pub use avatars_profiles::{
-    add_gnosis_xp, add_syzygy_score, create_aeon_profile, get_aeon_profile,
-    get_address_by_handle, is_archon, recompute_ascension, set_handle, update_badges,
-    AvatarsProfilesModule, AeonProfile,
+    add_gnosis_xp, add_syzygy_score, aeon_tombstone, archon_count, backfill_archon_index,
+    create_aeon_profile, decay_syzygy, delete_aeon_profile, get_address_by_handle,
+    get_aeon_profile, get_aeon_profile_at, is_archon, is_recorder, list_archons, normalize_handle,
+    recompute_ascension, record_syzygy, resolve_handle, set_avatar_uri, set_handle, update_badges,
+    AeonProfile, AvatarsProfilesModule,
 };
-pub use bank_cgt::{get_balance_cgt, BankCgtModule};
+pub use bank_cgt::{
+    account_exists, get_balance_cgt, get_nonce_cgt, module_account_owner, module_address,
+    BankCgtModule,
+};
+pub use batch::{BatchCall, BatchModule};
+pub use call_schema::{CallSchema, FieldSchema, FieldType};
+pub use codec::CodecRegistry;
+pub use escrow::{backfill_escrow_account, escrow_account, get_escrow, EscrowId, EscrowModule};
 pub use fabric_manager::{get_fabric_asset, FabricManagerModule, FabricRootHash};
+pub use governance::{get_proposal, list_proposals, GovernanceModule, ProposalId};
+pub use multisig::{
+    check_signature, get_multisig, multisig_address, MultisigAccount, MultisigModule,
+};
+pub use names::{name_of, resolve_name, NamesModule};
 pub use nft_dgen::{get_nft, get_nfts_by_owner, NftDgenModule, NftId};
+pub use quests::{get_progress, list_quests, QuestId, QuestsModule};
+pub use staking::{get_stake, get_unbondings, StakingModule};
+pub use staking_cgt::{get_bond_unbondings, get_bonded, StakingCgtModule};
+pub use storage_key::StorageKey;
 
 /// Trait that all runtime modules must implement.
 ///
@@ -33,6 +79,24 @@ pub trait RuntimeModule: Send + Sync {
     /// Returns the unique identifier for this module (e.g., "bank_cgt").
     fn module_id(&self) -> &'static str;
 
+    /// Storage prefixes this module writes under its `module_id/` namespace.
+    ///
+    /// Checked for uniqueness when the module is registered (debug builds).
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Call ids this module's `dispatch` accepts (e.g. "transfer", "mint_to").
+    fn call_ids(&self) -> &'static [&'static str];
+
+    /// Payload layout of each call, keyed by call id (see `CallSchema`).
+    ///
+    /// Modules keep this table next to their params structs. Calls left out
+    /// are not described by `cgt_describeCall`.
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        &[]
+    }
+
     /// Dispatches a call to this module.
     ///
     /// # Arguments
@@ -44,57 +108,349 @@ pub trait RuntimeModule: Send + Sync {
     /// - `Ok(())` if the call succeeded
     /// - `Err(String)` with an error message if the call failed
     fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String>;
+
+    /// Every storage key a call could write, derived from its params, so the
+    /// executor can run transactions with disjoint keys in parallel (see
+    /// `core::parallel`).
+    ///
+    /// The set may be conservative but must not miss a key: a write outside
+    /// it only costs a sequential re-run, but `None` (the default) is the
+    /// honest answer for calls whose writes depend on state, and makes the
+    /// transaction run on its own.
+    fn declared_writes(&self, _tx: &Transaction) -> Option<Vec<Vec<u8>>> {
+        None
+    }
+
+    /// Runs once per block after all of its transactions, e.g. for per-block accrual.
+    ///
+    /// An error fails the whole block, so hooks should only fail on storage errors.
+    fn on_block_end(&self, _state: &mut State) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// CGT this module holds outside bank balances, such as stakes and fee
+    /// pools, in base units. `Runtime::verify_invariants` adds it to the
+    /// balances when checking them against the total supply.
+    fn held_cgt(&self, _state: &State) -> u128 {
+        0
+    }
+
+    /// Check invariants of this module's own storage for
+    /// `Runtime::verify_invariants`.
+    ///
+    /// # Returns
+    /// A description of each violation found
+    fn check_invariants(&self, _state: &State) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Reconcile the value of a counter-like key, one that must never go
+    /// back, when `module_state::import_module` brings in `imported` over
+    /// `local`. Other keys that already hold a different value collide.
+    ///
+    /// The default covers the `u64` next-id counter under
+    /// `module_state::COUNTER_PREFIX`, raising it to the imported value.
+    ///
+    /// # Returns
+    /// - `Ok(Some(value))` with the value to keep, which may be `local`
+    /// - `Ok(None)` if `key` is not counter-like
+    /// - `Err(String)` if the imported value does not decode
+    fn reconcile_import(
+        &self,
+        key: &[u8],
+        local: Option<&[u8]>,
+        imported: &[u8],
+    ) -> Result<Option<Vec<u8>>, String> {
+        if key != StorageKey::new(self.module_id(), module_state::COUNTER_PREFIX).into_bytes() {
+            return Ok(None);
+        }
+        storage_value::raise_u64(local, imported).map(Some)
+    }
+}
+
+/// Builds the runtime a node executes blocks with from its config.
+///
+/// Embedding binaries supply their own factory (see `Node::with_runtime_factory`)
+/// to register modules beyond the defaults.
+pub type RuntimeFactory = Arc<dyn Fn(&ChainConfig) -> Runtime + Send + Sync>;
+
+/// A registered module and the calls it accepts.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ModuleInfo {
+    pub module_id: &'static str,
+    pub call_ids: &'static [&'static str],
 }
 
 /// Runtime registry that holds all registered modules.
 ///
-/// The Runtime is created fresh for each block execution in Phase 3.
-/// In later phases, this may be stored in Node for reuse.
+/// Modules are stateless, so a node builds its runtime once and reuses it
+/// for every block. Dispatch looks modules up by id; anything that visits
+/// every module (block-end hooks, module state) goes in registration order,
+/// so execution never depends on map iteration order.
 pub struct Runtime {
-    /// List of registered runtime modules.
-    modules: Vec<Box<dyn RuntimeModule>>,
+    /// Registered runtime modules, keyed by module id.
+    modules: HashMap<&'static str, Box<dyn RuntimeModule>>,
+    /// Module ids in registration order.
+    order: Vec<&'static str>,
 }
 
 impl Runtime {
     /// Create a new empty runtime registry.
     pub fn new() -> Self {
         Self {
-            modules: Vec::new(),
+            modules: HashMap::new(),
+            order: Vec::new(),
         }
     }
 
     /// Add a module to the runtime registry.
-    pub fn with_module(mut self, module: Box<dyn RuntimeModule>) -> Self {
-        self.modules.push(module);
-        self
+    ///
+    /// # Panics
+    /// If the module id is already registered or the module declares a
+    /// storage prefix twice. Use `try_with_module` to handle this as an error.
+    pub fn with_module(self, module: Box<dyn RuntimeModule>) -> Self {
+        self.try_with_module(module)
+            .unwrap_or_else(|e| panic!("invalid runtime module registration: {}", e))
+    }
+
+    /// Add a module to the runtime registry, rejecting namespace conflicts.
+    ///
+    /// # Returns
+    /// - `Ok(Runtime)` with the module registered
+    /// - `Err(String)` if the module id is already registered or the module
+    ///   declares a storage prefix twice
+    pub fn try_with_module(mut self, module: Box<dyn RuntimeModule>) -> Result<Self, String> {
+        let module_id = module.module_id();
+        storage_key::check_namespace(&self.order, module_id, module.storage_prefixes())?;
+        self.order.push(module_id);
+        self.modules.insert(module_id, module);
+        Ok(self)
+    }
+
+    /// Registered module ids, in registration order.
+    pub fn module_ids(&self) -> &[&'static str] {
+        &self.order
+    }
+
+    /// Storage prefixes a registered module declares, or `None` for an
+    /// unknown id.
+    pub fn storage_prefixes(&self, module_id: &str) -> Option<&'static [&'static str]> {
+        Some(self.modules.get(module_id)?.storage_prefixes())
+    }
+
+    /// Registered modules in registration order.
+    /// Reconcile an imported value of a registered module's key (see
+    /// `RuntimeModule::reconcile_import`); `Ok(None)` for an unknown id.
+    pub fn reconcile_import(
+        &self,
+        module_id: &str,
+        key: &[u8],
+        local: Option<&[u8]>,
+        imported: &[u8],
+    ) -> Result<Option<Vec<u8>>, String> {
+        match self.modules.get(module_id) {
+            Some(module) => module.reconcile_import(key, local, imported),
+            None => Ok(None),
+        }
+    }
+
+    fn ordered_modules(&self) -> impl Iterator<Item = &dyn RuntimeModule> + '_ {
+        self.order.iter().map(|id| self.modules[id].as_ref())
+    }
+
+    /// Add every registered module's account to the module account registry
+    /// (see `bank_cgt::module_address`).
+    ///
+    /// # Returns
+    /// The ids of modules whose accounts were newly registered
+    pub fn register_module_accounts(&self, state: &mut State) -> Result<Vec<&'static str>, String> {
+        let mut registered = Vec::new();
+        for module_id in &self.order {
+            if bank_cgt::register_module_account(state, module_id)? {
+                registered.push(*module_id);
+            }
+        }
+        Ok(registered)
+    }
+
+    /// Registered modules and their call ids, in registration order.
+    pub fn modules(&self) -> Vec<ModuleInfo> {
+        self.ordered_modules()
+            .map(|m| ModuleInfo {
+                module_id: m.module_id(),
+                call_ids: m.call_ids(),
+            })
+            .collect()
+    }
+
+    /// Payload layout of a registered module's call, if the module
+    /// describes it.
+    pub fn call_schema(&self, module_id: &str, call_id: &str) -> Option<CallSchema> {
+        self.modules
+            .get(module_id)?
+            .call_schemas()
+            .iter()
+            .find(|(id, _)| *id == call_id)
+            .map(|(_, schema)| *schema)
     }
 
-    /// Create a runtime with all default modules registered.
+    /// Create a runtime with all default modules registered, using default config.
     pub fn with_default_modules() -> Self {
+        Self::for_chain(&ChainConfig::default())
+    }
+
+    /// Create a runtime with all default modules, configured from `chain`.
+    pub fn for_chain(chain: &ChainConfig) -> Self {
         Self::new()
-            .with_module(Box::new(BankCgtModule::new()))
+            .with_module(Box::new(BankCgtModule::with_mint_authorities(
+                chain.genesis.mint_authorities.clone(),
+            )))
             .with_module(Box::new(AvatarsProfilesModule::new()))
             .with_module(Box::new(NftDgenModule::new()))
             .with_module(Box::new(FabricManagerModule::new()))
             .with_module(Box::new(AbyssRegistryModule::new()))
+            .with_module(Box::new(EscrowModule::new()))
+            .with_module(Box::new(StakingModule::new()))
+            .with_module(Box::new(GovernanceModule::with_rules(
+                chain.governance.clone(),
+            )))
+            .with_module(Box::new(StakingCgtModule::new()))
+            .with_module(Box::new(QuestsModule::new()))
+            .with_module(Box::new(AssetsModule::new()))
+            .with_module(Box::new(MultisigModule::new()))
+            .with_module(Box::new(NamesModule::new()))
+            .with_module(Box::new(BatchModule::new()))
     }
 
     /// Dispatch a transaction to the appropriate runtime module.
     ///
     /// Looks up the module by `module_id` and calls its `dispatch` method
-    /// with the transaction's `call_id` and the full transaction.
+    /// with the transaction's `call_id` and the full transaction. Writes during
+    /// dispatch are confined to the module's `module_id/` storage namespace.
+    /// Batches go to `batch::dispatch_batch`, which dispatches their calls in
+    /// turn.
     ///
     /// # Returns
     /// - `Ok(())` if the transaction was successfully dispatched and executed
-    /// - `Err(String)` if the module was not found or execution failed
-    pub fn dispatch_tx(&mut self, tx: &Transaction, state: &mut State) -> Result<(), String> {
-        let module = self
-            .modules
+    /// - `Err(String)` if it was sent from a module account, is not signed
+    ///   the way its sender requires (see `multisig::check_signature`), the
+    ///   module was not found, or execution failed
+    pub fn dispatch_tx(&self, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        let _span = tracing::info_span!(
+            "dispatch_tx",
+            tx_hash = %hex::encode(tx.hash()),
+            module_id = %tx.module_id,
+            call_id = %tx.call_id,
+            from = %tx.from.to_display()
+        )
+        .entered();
+
+        // No key controls a module account, so nothing may be sent from one
+        let result = match self.modules.get(tx.module_id.as_str()) {
+            _ if bank_cgt::module_account_owner(state, &tx.from).is_some() => Err(format!(
+                "{} is a module account and cannot send transactions",
+                tx.from.to_display()
+            )),
+            Some(module) if module.module_id() == batch::MODULE_ID => {
+                multisig::check_signature(state, tx)
+                    .and_then(|()| batch::dispatch_batch(self, tx, state))
+            }
+            Some(module) => multisig::check_signature(state, tx).and_then(|()| {
+                let _span = tracing::info_span!(
+                    "module_dispatch",
+                    module_id = %module.module_id(),
+                    call_id = %tx.call_id
+                )
+                .entered();
+                state.with_namespace(module.module_id(), |state| {
+                    module.dispatch(&tx.call_id, tx, state)
+                })
+            }),
+            None => Err(format!("Unknown module: {}", tx.module_id)),
+        };
+        if let Err(e) = &result {
+            tracing::info!(error = %e, "transaction failed");
+        }
+        result
+    }
+
+    /// Storage keys a transaction declares it could write (see
+    /// `RuntimeModule::declared_writes`), or `None` if its module is unknown
+    /// or does not declare them.
+    pub fn declared_writes(&self, tx: &Transaction) -> Option<Vec<Vec<u8>>> {
+        self.modules.get(tx.module_id.as_str())?.declared_writes(tx)
+    }
+
+    /// Run every module's `on_block_end` hook, in registration order.
+    ///
+    /// Like `dispatch_tx`, each hook's writes are confined to its module's namespace.
+    pub fn end_block(&self, state: &mut State) -> Result<(), String> {
+        for module in self.ordered_modules() {
+            state.with_namespace(module.module_id(), |state| module.on_block_end(state))?;
+        }
+        Ok(())
+    }
+
+    /// Check invariants that no transaction should be able to break: CGT
+    /// balances and the CGT modules hold (see `RuntimeModule::held_cgt`) add
+    /// up to the total supply, and each module's own
+    /// `RuntimeModule::check_invariants` hold.
+    ///
+    /// Scans every balance, stake, pool, and NFT, so it is meant for
+    /// operators, not for block execution; run it on a `State::snapshot` to
+    /// leave the live state unlocked.
+    ///
+    /// # Returns
+    /// - `Ok(())` if every invariant holds
+    /// - `Err(Vec<String>)` describing each violation
+    pub fn verify_invariants(&self, state: &State) -> Result<(), Vec<String>> {
+        let held = self
+            .ordered_modules()
+            .map(|module| module.held_cgt(state))
+            .sum();
+        let mut violations = bank_cgt::check_supply(state, held);
+        for module in self.ordered_modules() {
+            violations.extend(module.check_invariants(state));
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Every key-value pair under the registered modules' `{module_id}/`
+    /// namespaces, module by module in registration order, sorted by key
+    /// within each.
+    ///
+    /// Chain metadata outside module namespaces (blocks, receipts, faucet
+    /// claims) is not included.
+    pub fn module_state(&self, state: &State) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.order
             .iter()
-            .find(|m| m.module_id() == tx.module_id)
-            .ok_or_else(|| format!("Unknown module: {}", tx.module_id))?;
+            .flat_map(|id| state.scan_prefix(format!("{}/", id).as_bytes()))
+            .collect()
+    }
+
+    /// Merkle root of `module_state` (see `core::merkle`), so two states
+    /// have the same root only if they hold the same entries.
+    pub fn state_root(&self, state: &State) -> [u8; 32] {
+        merkle::root(&self.sorted_module_state(state))
+    }
+
+    /// Proof that `key` holds its current value under `state_root`, if the
+    /// key is set in a module namespace.
+    ///
+    /// Builds the whole tree, so the cost grows with the size of the state.
+    pub fn prove(&self, state: &State, key: &[u8]) -> Option<merkle::Proof> {
+        merkle::prove(&self.sorted_module_state(state), key)
+    }
 
-        module.dispatch(&tx.call_id, tx, state)
+    /// `module_state` sorted by key, as the Merkle tree's leaves.
+    fn sorted_module_state(&self, state: &State) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut entries = self.module_state(state);
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        entries
     }
 }
 
@@ -107,18 +463,139 @@ impl Default for Runtime {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::codec;
     use crate::core::state::State;
-    use crate::core::transaction::{Address, Transaction};
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::core::transaction::Transaction;
 
     #[test]
     fn test_runtime_with_default_modules() {
         let runtime = Runtime::with_default_modules();
-        assert_eq!(runtime.modules.len(), 5);
+        assert_eq!(runtime.modules.len(), 14);
+
+        let modules = runtime.modules();
+        assert_eq!(modules[0].module_id, "bank_cgt");
+        assert_eq!(
+            modules[0].call_ids,
+            &["transfer", "transfer_to_handle", "mint_to"]
+        );
+    }
+
+    #[test]
+    fn test_call_schema_for_bank_transfer() {
+        let runtime = Runtime::with_default_modules();
+        let schema = runtime.call_schema("bank_cgt", "transfer").unwrap();
+        assert_eq!(
+            schema.fields,
+            &[
+                FieldSchema::new("to", FieldType::Address),
+                FieldSchema::new("amount", FieldType::U64),
+                FieldSchema::new("memo", FieldType::Option(&FieldType::Bytes)),
+            ]
+        );
+
+        assert_eq!(runtime.call_schema("bank_cgt", "burn"), None);
+        assert_eq!(runtime.call_schema("nope", "transfer"), None);
+    }
+
+    #[test]
+    fn test_dispatch_routes_by_module_id() {
+        let runtime = Runtime::with_default_modules().with_module(Box::new(RogueModule));
+        assert_eq!(runtime.module_ids()[0], "bank_cgt");
+        assert_eq!(runtime.module_ids().last(), Some(&"rogue"));
+
+        let mut state = State::in_memory();
+        let tx = |module_id: &str, call_id: &str| {
+            signed(Transaction {
+                from: account(6),
+                nonce: 0,
+                module_id: module_id.to_string(),
+                call_id: call_id.to_string(),
+                payload: vec![],
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            })
+        };
+        runtime
+            .dispatch_tx(&tx("avatars_profiles", "claim_archon"), &mut state)
+            .unwrap();
+        assert!(is_archon(&state, &account(6)));
+        let err = runtime
+            .dispatch_tx(&tx("bank_cgt", "claim_archon"), &mut state)
+            .unwrap_err();
+        assert!(err.starts_with("bank_cgt:"), "{}", err);
+    }
+
+    #[test]
+    fn test_duplicate_module_registration_rejected() {
+        let err = Runtime::with_default_modules()
+            .try_with_module(Box::new(BankCgtModule::new()))
+            .err()
+            .unwrap();
+        assert!(err.contains("module bank_cgt is already registered"));
+    }
+
+    #[test]
+    #[should_panic(expected = "already registered")]
+    fn test_duplicate_module_registration_panics() {
+        let _ = Runtime::new()
+            .with_module(Box::new(BankCgtModule::new()))
+            .with_module(Box::new(BankCgtModule::new()));
+    }
+
+    /// Module that tries to write into bank_cgt's namespace.
+    struct RogueModule;
+
+    impl RuntimeModule for RogueModule {
+        fn module_id(&self) -> &'static str {
+            "rogue"
+        }
+
+        fn call_ids(&self) -> &'static [&'static str] {
+            &["steal"]
+        }
+
+        fn dispatch(
+            &self,
+            _call_id: &str,
+            tx: &Transaction,
+            state: &mut State,
+        ) -> Result<(), String> {
+            let key = StorageKey::new("bank_cgt", "balance")
+                .address(&tx.from)
+                .into_bytes();
+            state
+                .put_raw(key, codec::encode(&u64::MAX).unwrap())
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    #[test]
+    fn test_write_outside_namespace_rejected() {
+        let runtime = Runtime::with_default_modules().with_module(Box::new(RogueModule));
+        let mut state = State::in_memory();
+        let tx = signed(Transaction {
+            from: account(6),
+            nonce: 0,
+            module_id: "rogue".to_string(),
+            call_id: "steal".to_string(),
+            payload: vec![],
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+
+        let err = runtime.dispatch_tx(&tx, &mut state).unwrap_err();
+        assert!(err.contains("outside storage namespace rogue/"));
+        assert_eq!(get_balance_cgt(&state, &account(6)), 0);
     }
 
     #[test]
     fn test_dispatch_unknown_module() {
-        let mut runtime = Runtime::with_default_modules();
+        let runtime = Runtime::with_default_modules();
         let mut state = State::in_memory();
         let tx = Transaction {
             from: [0; 32],
@@ -128,10 +605,217 @@ mod tests {
             payload: vec![],
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         let result = runtime.dispatch_tx(&tx, &mut state);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Unknown module"));
     }
+
+    #[test]
+    fn test_dispatch_rejects_payloads_with_trailing_bytes() {
+        use crate::config::ChainConfig;
+
+        let mut chain = ChainConfig::default();
+        chain.genesis.mint_authorities = vec![account(1)];
+        let runtime = Runtime::for_chain(&chain);
+        let mut state = State::in_memory();
+        let id = codec::encode(&0u64).unwrap();
+        let transfer = codec::encode(&(0u64, [2u8; 32], 1u64)).unwrap();
+        let calls = [
+            (
+                "bank_cgt",
+                "mint_to",
+                codec::encode(&([2u8; 32], 1u64)).unwrap(),
+            ),
+            (
+                "escrow",
+                "create_escrow",
+                codec::encode(&([2u8; 32], 1u64, 9u64)).unwrap(),
+            ),
+            ("escrow", "release_escrow", id.clone()),
+            ("escrow", "refund_escrow", id.clone()),
+            (
+                "batch",
+                "execute_batch",
+                codec::encode(&Vec::<u8>::new()).unwrap(),
+            ),
+            (
+                "assets",
+                "register_asset",
+                codec::encode(&(0u64, "Gold", 2u8)).unwrap(),
+            ),
+            ("assets", "mint_asset", transfer.clone()),
+            ("assets", "transfer_asset", transfer),
+            (
+                "fabric_manager",
+                "reward_seeder",
+                codec::encode(&([7u8; 32], [2u8; 32], 1u64)).unwrap(),
+            ),
+            (
+                "quests",
+                "create_quest",
+                codec::encode(&(
+                    "Q",
+                    ("bank_cgt", "transfer", 1u32, 9u64),
+                    1u64,
+                    None::<String>,
+                ))
+                .unwrap(),
+            ),
+            ("quests", "retire_quest", id.clone()),
+            (
+                "nft_dgen",
+                "transfer_nft",
+                codec::encode(&(0u64, [2u8; 32])).unwrap(),
+            ),
+            ("staking_cgt", "bond", id.clone()),
+            ("staking_cgt", "unbond", id.clone()),
+            ("staking", "stake", id.clone()),
+            ("staking", "unstake", id.clone()),
+            ("names", "register_name", codec::encode("alice").unwrap()),
+            (
+                "governance",
+                "create_proposal",
+                codec::encode(&("d", "k", 1u64, 9u64)).unwrap(),
+            ),
+            ("governance", "vote", codec::encode(&(0u64, true)).unwrap()),
+            ("abyss_registry", "cancel_listing", id.clone()),
+            ("abyss_registry", "buy_listing", id.clone()),
+            (
+                "abyss_registry",
+                "create_bundle_listing",
+                codec::encode(&(vec![0u64], 1u64, vec![1u64], None::<[u8; 32]>)).unwrap(),
+            ),
+            ("abyss_registry", "cancel_bundle_listing", id.clone()),
+            ("abyss_registry", "buy_bundle_listing", id),
+        ];
+        for (module_id, call_id, mut payload) in calls {
+            payload.push(0);
+            let tx = signed(Transaction {
+                from: account(1),
+                nonce: 0,
+                module_id: module_id.to_string(),
+                call_id: call_id.to_string(),
+                payload,
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            });
+            let err = runtime.dispatch_tx(&tx, &mut state).unwrap_err();
+            assert!(
+                err.contains("bytes remaining"),
+                "{}.{}: {}",
+                module_id,
+                call_id,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_state_root_covers_module_state_only() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let empty = runtime.state_root(&state);
+
+        // Chain metadata does not move the root
+        state.put_raw(b"chain:block:x".to_vec(), vec![1]).unwrap();
+        assert_eq!(runtime.state_root(&state), empty);
+
+        module_api::bank::credit(&mut state, &[1; 32], 10).unwrap();
+        let credited = runtime.state_root(&state);
+        assert_ne!(credited, empty);
+        assert!(runtime
+            .module_state(&state)
+            .iter()
+            .all(|(key, _)| key.starts_with(b"bank_cgt/")));
+
+        module_api::bank::credit(&mut state, &[1; 32], 1).unwrap();
+        assert_ne!(runtime.state_root(&state), credited);
+    }
+
+    #[test]
+    fn test_verify_invariants_reports_each_mismatch() {
+        use crate::core::transaction::AddressEncoding;
+        use crate::runtime::bank_cgt::{credit, mint};
+        use crate::runtime::nft_dgen::{self, MintDgenParams};
+        use crate::runtime::staking::StakeParams;
+
+        let runtime = Runtime::with_default_modules();
+        let mut state = State::in_memory();
+        let (alice, bob) = (account(1), [2u8; 32]);
+        mint(&mut state, &alice, 1_000).unwrap();
+        // Staked CGT leaves Alice's balance but still counts toward the supply
+        let stake = signed(Transaction {
+            from: alice,
+            nonce: 0,
+            module_id: "staking".to_string(),
+            call_id: "stake".to_string(),
+            payload: codec::encode(&StakeParams { amount: 400 }).unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+        runtime.dispatch_tx(&stake, &mut state).unwrap();
+        let params = MintDgenParams {
+            fabric_root_hash: [9; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
+        };
+        let token = nft_dgen::mint(&mut state, &alice, &params).unwrap();
+        nft_dgen::force_transfer(&mut state, token, &bob).unwrap();
+        assert_eq!(runtime.verify_invariants(&state), Ok(()));
+
+        // CGT credited without being minted, as a double spend would be
+        credit(&mut state, &bob, 1).unwrap();
+        // Bob's NFT dropped from his index and listed under Alice
+        let owner_key = |owner: &[u8; 32]| {
+            StorageKey::new("nft_dgen", "owner")
+                .address(owner)
+                .into_bytes()
+        };
+        let mut bob_token = owner_key(&bob);
+        bob_token.extend_from_slice(&token.to_be_bytes());
+        state.delete_raw(&bob_token).unwrap();
+        state
+            .put_raw(
+                owner_key(&alice),
+                codec::encode(&vec![token, 7u64]).unwrap(),
+            )
+            .unwrap();
+
+        let violations = runtime.verify_invariants(&state).unwrap_err();
+        assert_eq!(
+            violations,
+            vec![
+                "CGT balances sum to 601 base units and modules hold 400 more, \
+                 but the total supply is 1000"
+                    .to_string(),
+                format!(
+                    "NFT {} is owned by {} but missing from its owner index",
+                    token,
+                    bob.to_display()
+                ),
+                format!(
+                    "owner index of {} lists NFT {}, which {} owns",
+                    alice.to_display(),
+                    token,
+                    bob.to_display()
+                ),
+                format!(
+                    "owner index of {} lists NFT 7, which does not exist",
+                    alice.to_display()
+                ),
+            ]
+        );
+    }
 }
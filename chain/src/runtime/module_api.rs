@@ -0,0 +1,55 @@
+//! Typed cross-module API.
+//!
+//! Modules, genesis init, the faucet, and RPC helpers call these functions to
+//! move CGT and NFTs instead of fabricating `Transaction`s and dispatching
+//! them. None of them re-enter `RuntimeModule::dispatch`, and none perform
+//! permission checks: authorization (signer, Archon status, ownership,
+//! genesis authority) lives at each module's dispatch boundary.
+//!
+//! Functions that write enter their owning module's storage namespace via
+//! `State::with_namespace`, the explicit escape hatch that lets them run
+//! inside another module's dispatch.
+
+/// CGT balances.
+pub mod bank {
+    pub use crate::runtime::bank_cgt::{
+        burn, consume_nonce, credit, debit, debit_into_stake, exempt_from_existential_deposit,
+        existential_deposit, get_balance_cgt as balance, get_nonce_cgt as nonce, mint,
+        module_account_owner, module_address, total_supply,
+    };
+}
+
+/// D-GEN NFTs.
+pub mod nft {
+    pub use crate::runtime::nft_dgen::{force_transfer, get_nft as metadata, mint, royalty_shares};
+}
+
+/// Governed chain parameters.
+pub mod governance {
+    pub use crate::runtime::governance::{
+        check_vote_lock, get_param as param, PARAM_EXISTENTIAL_DEPOSIT,
+        PARAM_FAUCET_COOLDOWN_BLOCKS, PARAM_LUMINARY_SYZYGY_THRESHOLD, PARAM_MARKETPLACE_FEE_BPS,
+        PARAM_MAX_MEMO_BYTES, PARAM_NAME_REGISTRATION_FEE, PARAM_SYZYGY_DECAY_BPS,
+        PARAM_SYZYGY_DECAY_EPOCH_BLOCKS, PARAM_UNBONDING_PERIOD_BLOCKS,
+    };
+}
+
+/// Staked and bonded CGT.
+pub mod staking {
+    pub use crate::runtime::staking::get_stake as stake;
+    pub use crate::runtime::staking_cgt::get_bonded as bonded;
+}
+
+/// Registered names.
+pub mod names {
+    pub use crate::runtime::names::{name_of, registration_fee, resolve_name};
+}
+
+/// Archon status and Aeon profiles.
+pub mod avatars {
+    pub use crate::runtime::avatars_profiles::{
+        add_gnosis_xp, checkpoint_syzygy_decay, create_aeon_profile, get_aeon_profile as profile,
+        grant_archon, is_archon, normalize_handle, recompute_ascension, resolve_handle,
+        revoke_archon, set_badge,
+    };
+}
@@ -0,0 +1,325 @@
+//! Names module for human-readable account names.
+//!
+//! This module handles:
+//! - Registering a name for the sender (`register_name`): first come, first
+//!   served, for a fee in CGT that is burned (`names.registration_fee`)
+//! - Resolving a name to its address (`resolve_name`) and an address back to
+//!   its name (`name_of`)
+//!
+//! An address holds at most one name, its primary name, and a registered
+//! name never changes hands. Names and Aeon handles share one namespace: a
+//! name that is another address's handle cannot be registered, and
+//! `avatars_profiles` refuses a handle that is another address's name, so
+//! `@orpheus` never resolves to two addresses. Names are 3 to 32 lowercase ASCII letters,
+//! digits, and inner hyphens; lookups ignore case and a leading `@`.
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::{avatars, bank, governance};
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::DEFAULT_NAME_REGISTRATION_FEE;
+use crate::core::codec;
+use crate::core::receipt::Event;
+use crate::core::state::State;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+
+const MODULE_ID: &str = "names";
+const PREFIX_NAME: &str = "name";
+const PREFIX_PRIMARY: &str = "primary";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_NAME, PREFIX_PRIMARY];
+
+/// Shortest name that may be registered, in bytes.
+pub const MIN_NAME_LEN: usize = 3;
+
+/// Longest name that may be registered, in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Register name parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterNameParams {
+    pub name: String,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[(
+    "register_name",
+    CallSchema::new(&[FieldSchema::new("name", FieldType::String)]),
+)];
+
+fn name_key(name: &str) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_NAME)
+        .bytes(name.as_bytes())
+        .into_bytes()
+}
+
+fn primary_key(addr: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_PRIMARY)
+        .address(addr)
+        .into_bytes()
+}
+
+/// Check a name: `MIN_NAME_LEN` to `MAX_NAME_LEN` bytes of lowercase ASCII
+/// letters, digits, and hyphens, not starting or ending with a hyphen.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if !(MIN_NAME_LEN..=MAX_NAME_LEN).contains(&name.len()) {
+        return Err(format!(
+            "a name must be {} to {} characters",
+            MIN_NAME_LEN, MAX_NAME_LEN
+        ));
+    }
+    if !name
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    {
+        return Err("a name may only hold lowercase letters, digits, and hyphens".into());
+    }
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err("a name may not start or end with a hyphen".into());
+    }
+    Ok(())
+}
+
+/// The name a lookup of `name` refers to: trimmed, without a leading `@`,
+/// and lowercased.
+fn normalize_name(name: &str) -> String {
+    let name = name.trim();
+    name.strip_prefix('@').unwrap_or(name).to_lowercase()
+}
+
+/// Address `name` is registered to, if any; case and a leading `@` are
+/// ignored.
+pub fn resolve_name(state: &State, name: &str) -> Option<Address> {
+    state
+        .get_raw(&name_key(&normalize_name(name)))
+        .and_then(|bytes| bytes.try_into().ok())
+}
+
+/// Primary name of `addr`, if it registered one.
+pub fn name_of(state: &State, addr: &Address) -> Option<String> {
+    state
+        .get_raw(&primary_key(addr))
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
+/// CGT burned to register a name (`names.registration_fee`).
+pub fn registration_fee(state: &State) -> u64 {
+    governance::param(state, governance::PARAM_NAME_REGISTRATION_FEE)
+        .unwrap_or(DEFAULT_NAME_REGISTRATION_FEE)
+}
+
+/// NamesModule maps human-readable names to addresses
+#[derive(Default)]
+pub struct NamesModule;
+
+impl NamesModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for NamesModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["register_name"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "register_name" => handle_register_name(tx, state),
+            other => Err(format!("names: unknown call_id '{}'", other)),
+        }
+    }
+}
+
+fn handle_register_name(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RegisterNameParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+    validate_name(&params.name)?;
+
+    if let Some(owner) = resolve_name(state, &params.name) {
+        return Err(format!(
+            "name {} is already registered to {}",
+            params.name,
+            owner.to_display()
+        ));
+    }
+    if let Some(holder) = avatars::resolve_handle(state, &params.name) {
+        if holder != tx.from {
+            return Err(format!(
+                "name {} is the Aeon handle of {}",
+                params.name,
+                holder.to_display()
+            ));
+        }
+    }
+    if let Some(existing) = name_of(state, &tx.from) {
+        return Err(format!(
+            "{} already has the name {}",
+            tx.from.to_display(),
+            existing
+        ));
+    }
+
+    let fee = registration_fee(state);
+    if bank::balance(state, &tx.from) < fee {
+        return Err(format!("registering a name costs {} CGT base units", fee));
+    }
+    bank::burn(state, &tx.from, fee)?;
+
+    state
+        .put_raw(name_key(&params.name), tx.from.to_vec())
+        .map_err(|e| e.to_string())?;
+    state
+        .put_raw(primary_key(&tx.from), params.name.as_bytes().to_vec())
+        .map_err(|e| e.to_string())?;
+
+    state.emit_event(
+        Event::new(MODULE_ID, "register", tx.from)
+            .with("name", &params.name)
+            .with("fee", fee),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CGT_UNIT;
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::runtime::Runtime;
+
+    fn alice() -> Address {
+        account(1)
+    }
+
+    fn bob() -> Address {
+        account(2)
+    }
+
+    fn register(
+        runtime: &Runtime,
+        state: &mut State,
+        from: Address,
+        name: &str,
+    ) -> Result<(), String> {
+        let payload = codec::encode(&RegisterNameParams {
+            name: name.to_string(),
+        })
+        .unwrap();
+        let tx = signed(Transaction {
+            from,
+            nonce: 0,
+            module_id: MODULE_ID.to_string(),
+            call_id: "register_name".to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        });
+        runtime.dispatch_tx(&tx, state)
+    }
+
+    fn funded_state() -> State {
+        let mut state = State::in_memory();
+        for addr in [alice(), bob()] {
+            bank::mint(&mut state, &addr, 5 * CGT_UNIT).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_register_and_resolve_both_ways() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = funded_state();
+
+        register(&runtime, &mut state, alice(), "alice-01").unwrap();
+        assert_eq!(resolve_name(&state, "alice-01"), Some(alice()));
+        assert_eq!(resolve_name(&state, " @Alice-01"), Some(alice()));
+        assert_eq!(name_of(&state, &alice()).as_deref(), Some("alice-01"));
+        assert_eq!(resolve_name(&state, "bob"), None);
+        assert_eq!(name_of(&state, &bob()), None);
+
+        // The fee is burned, leaving the total supply
+        assert_eq!(
+            bank::balance(&state, &alice()),
+            5 * CGT_UNIT - DEFAULT_NAME_REGISTRATION_FEE
+        );
+        assert_eq!(
+            bank::total_supply(&state),
+            10 * CGT_UNIT - DEFAULT_NAME_REGISTRATION_FEE
+        );
+    }
+
+    #[test]
+    fn test_collisions_and_one_name_per_address() {
+        let runtime = Runtime::with_default_modules();
+        let mut state = funded_state();
+        register(&runtime, &mut state, alice(), "alice").unwrap();
+
+        let err = register(&runtime, &mut state, bob(), "alice").unwrap_err();
+        assert!(err.contains("already registered"), "{}", err);
+        let err = register(&runtime, &mut state, alice(), "alice2").unwrap_err();
+        assert!(err.contains("already has the name alice"), "{}", err);
+        assert_eq!(resolve_name(&state, "alice2"), None);
+        assert_eq!(bank::balance(&state, &bob()), 5 * CGT_UNIT);
+
+        // An address that cannot pay gets nothing
+        let err = register(&runtime, &mut state, account(3), "carol").unwrap_err();
+        assert!(err.contains("costs"), "{}", err);
+        assert_eq!(resolve_name(&state, "carol"), None);
+    }
+
+    #[test]
+    fn test_names_and_handles_share_a_namespace() {
+        use crate::runtime::avatars_profiles::{create_aeon_profile, set_handle};
+        let runtime = Runtime::with_default_modules();
+        let mut state = funded_state();
+        for addr in [alice(), bob()] {
+            create_aeon_profile(&mut state, addr, "Aeon".to_string(), None, 0).unwrap();
+        }
+        set_handle(&mut state, alice(), "orpheus".to_string()).unwrap();
+
+        // Another address can take neither the name of a handle nor the
+        // handle of a name
+        let err = register(&runtime, &mut state, bob(), "orpheus").unwrap_err();
+        assert!(err.contains("Aeon handle of"), "{}", err);
+        register(&runtime, &mut state, bob(), "eurydice").unwrap();
+        let err = set_handle(&mut state, alice(), "eurydice".to_string()).unwrap_err();
+        assert!(err.contains("registered as a name"), "{}", err);
+
+        // The holder of both may have both
+        register(&runtime, &mut state, alice(), "orpheus").unwrap();
+        set_handle(&mut state, bob(), "eurydice".to_string()).unwrap();
+        assert_eq!(resolve_name(&state, "@orpheus"), Some(alice()));
+    }
+
+    #[test]
+    fn test_name_rules() {
+        for bad in [
+            "ab",
+            &"a".repeat(MAX_NAME_LEN + 1),
+            "Alice",
+            "al ice",
+            "-alice",
+            "alice-",
+            "al_ice",
+            "ålice",
+        ] {
+            assert!(validate_name(bad).is_err(), "{}", bad);
+        }
+        for good in ["abc", "a-b-c", "0x42", &"z".repeat(MAX_NAME_LEN)] {
+            assert!(validate_name(good).is_ok(), "{}", good);
+        }
+    }
+}
@@ -5,22 +5,33 @@
 //! - NFT transfers
 //! - NFT metadata storage (fabric_root_hash, royalties, etc.)
 //! - Owner tracking
+//! - Content location hints (`uri_hint`), settable by the creator
+//! - Royalty splits between several recipients (`royalty_splits`)
 
 use serde::{Deserialize, Serialize};
 
-use super::RuntimeModule;
+use super::id_index;
+use super::storage_value::{get_u64, put_u64};
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::core::codec;
+use crate::core::receipt::Event;
 use crate::core::state::State;
-use crate::core::transaction::{Address, Transaction};
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
 use crate::runtime::avatars_profiles::is_archon;
+use crate::runtime::fabric_manager::validate_uri_hint;
 
-const PREFIX_NFT: &[u8] = b"nft:token:";
-const KEY_NFT_COUNTER: &[u8] = b"nft:counter";
-const PREFIX_OWNER_NFTS: &[u8] = b"nft:owner:";
+const MODULE_ID: &str = "nft_dgen";
+const PREFIX_NFT: &str = "token";
+const PREFIX_NFT_COUNTER: &str = "counter";
+const PREFIX_OWNER_NFTS: &str = "owner";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_NFT, PREFIX_NFT_COUNTER, PREFIX_OWNER_NFTS];
 
 /// NFT ID type
 pub type NftId = u64;
 
 /// D-GEN NFT metadata
+///
+/// Stored versioned (see `codec::Versioned`); this is layout version 3.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DGenMetadata {
     pub creator: Address,
@@ -31,6 +42,84 @@ pub struct DGenMetadata {
     // Simple royalty: a single address + percentage (0-10000 = basis points).
     pub royalty_recipient: Option<Address>,
     pub royalty_bps: u16,
+    /// Where to fetch the content's bytes (added in version 2)
+    pub uri_hint: Option<String>,
+    /// Royalty recipients with their basis points; when not empty, these are
+    /// paid instead of `royalty_recipient` (added in version 3)
+    pub royalty_splits: Vec<(Address, u16)>,
+}
+
+/// Version 2 metadata layout.
+#[derive(Deserialize)]
+struct DGenMetadataV2 {
+    creator: Address,
+    owner: Address,
+    fabric_root_hash: [u8; 32],
+    forge_model_id: Option<[u8; 32]>,
+    forge_prompt_hash: Option<[u8; 32]>,
+    royalty_recipient: Option<Address>,
+    royalty_bps: u16,
+    uri_hint: Option<String>,
+}
+
+impl From<DGenMetadataV2> for DGenMetadata {
+    fn from(v2: DGenMetadataV2) -> Self {
+        Self {
+            creator: v2.creator,
+            owner: v2.owner,
+            fabric_root_hash: v2.fabric_root_hash,
+            forge_model_id: v2.forge_model_id,
+            forge_prompt_hash: v2.forge_prompt_hash,
+            royalty_recipient: v2.royalty_recipient,
+            royalty_bps: v2.royalty_bps,
+            uri_hint: v2.uri_hint,
+            royalty_splits: Vec::new(),
+        }
+    }
+}
+
+/// Version 1 metadata layout, stored without a version prefix.
+#[derive(Deserialize)]
+struct DGenMetadataV1 {
+    creator: Address,
+    owner: Address,
+    fabric_root_hash: [u8; 32],
+    forge_model_id: Option<[u8; 32]>,
+    forge_prompt_hash: Option<[u8; 32]>,
+    royalty_recipient: Option<Address>,
+    royalty_bps: u16,
+}
+
+impl From<DGenMetadataV1> for DGenMetadataV2 {
+    fn from(v1: DGenMetadataV1) -> Self {
+        Self {
+            creator: v1.creator,
+            owner: v1.owner,
+            fabric_root_hash: v1.fabric_root_hash,
+            forge_model_id: v1.forge_model_id,
+            forge_prompt_hash: v1.forge_prompt_hash,
+            royalty_recipient: v1.royalty_recipient,
+            royalty_bps: v1.royalty_bps,
+            uri_hint: None,
+        }
+    }
+}
+
+impl codec::Versioned for DGenMetadata {
+    const VERSION: u8 = 3;
+
+    fn upgrade(version: Option<u8>, body: &[u8]) -> bincode::Result<Self> {
+        match version {
+            None | Some(1) => codec::decode_state_exact::<DGenMetadataV1>(body)
+                .map(DGenMetadataV2::from)
+                .map(Into::into),
+            Some(2) => codec::decode_state_exact::<DGenMetadataV2>(body).map(Into::into),
+            Some(other) => Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "no upgrade from NFT metadata version {}",
+                other
+            )))),
+        }
+    }
 }
 
 /// Mint D-GEN parameters
@@ -41,6 +130,39 @@ pub struct MintDgenParams {
     pub forge_prompt_hash: Option<[u8; 32]>,
     pub royalty_recipient: Option<Address>,
     pub royalty_bps: u16,
+    /// Where to fetch the content's bytes; see `validate_uri_hint`.
+    pub uri_hint: Option<String>,
+    /// Royalty recipients and their basis points, paid instead of
+    /// `royalty_recipient` when not empty; see `validate_royalty_splits`.
+    pub royalty_splits: Vec<(Address, u16)>,
+}
+
+/// Mint D-GEN parameters as encoded before `royalty_splits`.
+#[derive(Deserialize)]
+struct MintDgenParamsV2 {
+    fabric_root_hash: [u8; 32],
+    forge_model_id: Option<[u8; 32]>,
+    forge_prompt_hash: Option<[u8; 32]>,
+    royalty_recipient: Option<Address>,
+    royalty_bps: u16,
+    uri_hint: Option<String>,
+}
+
+/// Mint D-GEN parameters as encoded before `uri_hint`.
+#[derive(Deserialize)]
+struct MintDgenParamsV1 {
+    fabric_root_hash: [u8; 32],
+    forge_model_id: Option<[u8; 32]>,
+    forge_prompt_hash: Option<[u8; 32]>,
+    royalty_recipient: Option<Address>,
+    royalty_bps: u16,
+}
+
+/// Set or clear an NFT's `uri_hint`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetUriHintParams {
+    pub token_id: NftId,
+    pub uri_hint: Option<String>,
 }
 
 /// Transfer NFT parameters
@@ -50,57 +172,129 @@ pub struct TransferNftParams {
     pub to: Address,
 }
 
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "mint_dgen",
+        CallSchema::new(&[
+            FieldSchema::new("fabric_root_hash", FieldType::Hash),
+            FieldSchema::new("forge_model_id", FieldType::Option(&FieldType::Hash)),
+            FieldSchema::new("forge_prompt_hash", FieldType::Option(&FieldType::Hash)),
+            FieldSchema::new("royalty_recipient", FieldType::Option(&FieldType::Address)),
+            FieldSchema::new("royalty_bps", FieldType::U16),
+            FieldSchema::new("uri_hint", FieldType::Option(&FieldType::String)),
+            FieldSchema::new(
+                "royalty_splits",
+                FieldType::List(&FieldType::Struct(&[
+                    FieldSchema::new("recipient", FieldType::Address),
+                    FieldSchema::new("bps", FieldType::U16),
+                ])),
+            ),
+        ]),
+    ),
+    (
+        "transfer_nft",
+        CallSchema::new(&[
+            FieldSchema::new("token_id", FieldType::U64),
+            FieldSchema::new("to", FieldType::Address),
+        ]),
+    ),
+    (
+        "set_uri_hint",
+        CallSchema::new(&[
+            FieldSchema::new("token_id", FieldType::U64),
+            FieldSchema::new("uri_hint", FieldType::Option(&FieldType::String)),
+        ]),
+    ),
+];
+
 /// Helper functions for NFT management
 
 fn nft_key(id: NftId) -> Vec<u8> {
-    let mut key = Vec::from(PREFIX_NFT);
-    key.extend_from_slice(&id.to_be_bytes());
-    key
+    StorageKey::new(MODULE_ID, PREFIX_NFT).u64(id).into_bytes()
 }
 
+fn nft_counter_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_NFT_COUNTER).into_bytes()
+}
+
+/// Prefix of an owner's index, one key per NFT (see `id_index`).
 fn owner_nfts_key(owner: &Address) -> Vec<u8> {
-    let mut key = Vec::from(PREFIX_OWNER_NFTS);
-    key.extend_from_slice(owner);
-    key
+    StorageKey::new(MODULE_ID, PREFIX_OWNER_NFTS)
+        .address(owner)
+        .into_bytes()
 }
 
 fn load_nft(state: &State, id: NftId) -> Option<DGenMetadata> {
     state
         .get_raw(&nft_key(id))
-        .and_then(|bytes| bincode::deserialize::<DGenMetadata>(&bytes).ok())
+        .and_then(|bytes| codec::decode_versioned::<DGenMetadata>(&bytes).ok())
 }
 
 fn store_nft(state: &mut State, id: NftId, meta: &DGenMetadata) -> Result<(), String> {
-    let bytes = bincode::serialize(meta).map_err(|e| e.to_string())?;
+    let bytes = codec::encode_versioned(meta).map_err(|e| e.to_string())?;
     state.put_raw(nft_key(id), bytes).map_err(|e| e.to_string())
 }
 
 fn get_next_nft_id(state: &State) -> NftId {
-    state
-        .get_raw(KEY_NFT_COUNTER)
-        .and_then(|bytes| bincode::deserialize::<NftId>(&bytes).ok())
-        .unwrap_or(0)
+    get_u64(state, &nft_counter_key()).unwrap_or(0)
 }
 
 fn set_next_nft_id(state: &mut State, next: NftId) -> Result<(), String> {
-    let bytes = bincode::serialize(&next).map_err(|e| e.to_string())?;
-    state
-        .put_raw(KEY_NFT_COUNTER.to_vec(), bytes)
-        .map_err(|e| e.to_string())
+    put_u64(state, nft_counter_key(), next)
 }
 
 fn load_owner_nfts(state: &State, owner: &Address) -> Vec<NftId> {
-    state
-        .get_raw(&owner_nfts_key(owner))
-        .and_then(|bytes| bincode::deserialize::<Vec<NftId>>(&bytes).ok())
-        .unwrap_or_default()
+    id_index::ids(state, &owner_nfts_key(owner))
 }
 
-fn store_owner_nfts(state: &mut State, owner: &Address, ids: &[NftId]) -> Result<(), String> {
-    let bytes = bincode::serialize(ids).map_err(|e| e.to_string())?;
-    state
-        .put_raw(owner_nfts_key(owner), bytes)
-        .map_err(|e| e.to_string())
+/// Most recipients a royalty may be split between.
+pub const MAX_ROYALTY_SPLITS: usize = 16;
+
+/// Check royalty splits: at most `MAX_ROYALTY_SPLITS` distinct recipients,
+/// each with some basis points, summing to at most 10000.
+pub fn validate_royalty_splits(splits: &[(Address, u16)]) -> Result<(), String> {
+    if splits.len() > MAX_ROYALTY_SPLITS {
+        return Err(format!(
+            "a royalty may be split between at most {} recipients",
+            MAX_ROYALTY_SPLITS
+        ));
+    }
+    for (i, (recipient, bps)) in splits.iter().enumerate() {
+        if *bps == 0 {
+            return Err(format!(
+                "royalty recipient {} has no basis points",
+                recipient.to_display()
+            ));
+        }
+        if splits[..i].iter().any(|(other, _)| other == recipient) {
+            return Err(format!(
+                "royalty recipient {} is listed twice",
+                recipient.to_display()
+            ));
+        }
+    }
+    let total: u32 = splits.iter().map(|(_, bps)| u32::from(*bps)).sum();
+    if total > 10_000 {
+        return Err(format!(
+            "royalty splits sum to {} bps, more than 10000",
+            total
+        ));
+    }
+    Ok(())
+}
+
+/// Who an NFT's royalty is paid to, with their basis points: its
+/// `royalty_splits`, or else `royalty_recipient` with `royalty_bps`. Empty
+/// without a royalty.
+pub fn royalty_shares(meta: &DGenMetadata) -> Vec<(Address, u16)> {
+    if !meta.royalty_splits.is_empty() {
+        return meta.royalty_splits.clone();
+    }
+    match meta.royalty_recipient {
+        Some(recipient) if meta.royalty_bps > 0 => vec![(recipient, meta.royalty_bps)],
+        _ => Vec::new(),
+    }
 }
 
 /// Public helper for querying NFT metadata (for RPC/SDK use).
@@ -108,12 +302,143 @@ pub fn get_nft(state: &State, id: NftId) -> Option<DGenMetadata> {
     load_nft(state, id)
 }
 
+/// Storage key of an NFT's metadata, for proving it against the state root.
+pub fn nft_storage_key(id: NftId) -> Vec<u8> {
+    nft_key(id)
+}
+
 /// Public helper for querying NFTs by owner (for RPC/SDK use).
 pub fn get_nfts_by_owner(state: &State, owner: &Address) -> Vec<NftId> {
     load_owner_nfts(state, owner)
 }
 
+/// Check that the owner index and the NFTs' metadata agree: every NFT is
+/// listed under its owner, and every listed NFT exists and is owned by the
+/// address it is listed under.
+///
+/// # Returns
+/// A description of each mismatch found
+pub fn check_owner_index(state: &State) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let token_prefix = StorageKey::new(MODULE_ID, PREFIX_NFT).into_bytes();
+    for (key, _) in state.scan_prefix(&token_prefix) {
+        let Ok(id) = <[u8; 8]>::try_from(&key[token_prefix.len()..]).map(NftId::from_be_bytes)
+        else {
+            continue;
+        };
+        match load_nft(state, id) {
+            Some(meta) if !id_index::contains(state, &owner_nfts_key(&meta.owner), id) => problems
+                .push(format!(
+                    "NFT {} is owned by {} but missing from its owner index",
+                    id,
+                    meta.owner.to_display()
+                )),
+            Some(_) => {}
+            None => problems.push(format!("metadata of NFT {} does not decode", id)),
+        }
+    }
+
+    let owner_prefix = StorageKey::new(MODULE_ID, PREFIX_OWNER_NFTS).into_bytes();
+    let mut owners: Vec<Address> = state
+        .scan_prefix(&owner_prefix)
+        .into_iter()
+        .filter_map(|(key, _)| key.get(owner_prefix.len()..)?.get(..32)?.try_into().ok())
+        .collect();
+    owners.dedup();
+    for owner in owners {
+        for id in load_owner_nfts(state, &owner) {
+            match load_nft(state, id) {
+                Some(meta) if meta.owner != owner => problems.push(format!(
+                    "owner index of {} lists NFT {}, which {} owns",
+                    owner.to_display(),
+                    id,
+                    meta.owner.to_display()
+                )),
+                Some(_) => {}
+                None => problems.push(format!(
+                    "owner index of {} lists NFT {}, which does not exist",
+                    owner.to_display(),
+                    id
+                )),
+            }
+        }
+    }
+    problems
+}
+
+/// Mint a D-GEN NFT owned by its creator.
+///
+/// Cross-module API: the Archon check lives at the `mint_dgen` dispatch
+/// boundary, not here.
+///
+/// # Returns
+/// - `Ok(NftId)` with the new token's ID
+/// - `Err(String)` on an invalid `uri_hint` or `royalty_splits`, ID
+///   overflow, or storage failure
+pub fn mint(
+    state: &mut State,
+    creator: &Address,
+    params: &MintDgenParams,
+) -> Result<NftId, String> {
+    state.with_namespace(MODULE_ID, |state| mint_in_namespace(state, creator, params))
+}
+
+fn mint_in_namespace(
+    state: &mut State,
+    creator: &Address,
+    params: &MintDgenParams,
+) -> Result<NftId, String> {
+    if let Some(uri) = &params.uri_hint {
+        validate_uri_hint(uri)?;
+    }
+    validate_royalty_splits(&params.royalty_splits)?;
+    let token_id = get_next_nft_id(state);
+    set_next_nft_id(state, token_id.checked_add(1).ok_or("nft id overflow")?)?;
+
+    let meta = DGenMetadata {
+        creator: *creator,
+        owner: *creator,
+        fabric_root_hash: params.fabric_root_hash,
+        forge_model_id: params.forge_model_id,
+        forge_prompt_hash: params.forge_prompt_hash,
+        royalty_recipient: params.royalty_recipient,
+        royalty_bps: params.royalty_bps,
+        uri_hint: params.uri_hint.clone(),
+        royalty_splits: params.royalty_splits.clone(),
+    };
+
+    store_nft(state, token_id, &meta)?;
+
+    // index under owner
+    id_index::insert(state, &owner_nfts_key(creator), token_id)?;
+
+    Ok(token_id)
+}
+
+/// Move an NFT to a new owner, updating both owner indexes.
+///
+/// Cross-module API: the ownership check lives at the `transfer_nft`
+/// dispatch boundary (and in the marketplace's listing checks), not here.
+pub fn force_transfer(state: &mut State, id: NftId, to: &Address) -> Result<(), String> {
+    state.with_namespace(MODULE_ID, |state| {
+        force_transfer_in_namespace(state, id, to)
+    })
+}
+
+fn force_transfer_in_namespace(state: &mut State, id: NftId, to: &Address) -> Result<(), String> {
+    let mut meta = load_nft(state, id).ok_or_else(|| "NFT not found".to_string())?;
+
+    id_index::remove(state, &owner_nfts_key(&meta.owner), id)?;
+    id_index::insert(state, &owner_nfts_key(to), id)?;
+
+    // Update owner in metadata
+    meta.owner = *to;
+    store_nft(state, id, &meta)
+}
+
 /// NftDgenModule handles D-GEN NFT operations
+#[derive(Default)]
 pub struct NftDgenModule;
 
 impl NftDgenModule {
@@ -124,16 +449,63 @@ impl NftDgenModule {
 
 impl RuntimeModule for NftDgenModule {
     fn module_id(&self) -> &'static str {
-        "nft_dgen"
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["mint_dgen", "transfer_nft", "set_uri_hint"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
     }
 
     fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
         match call_id {
             "mint_dgen" => handle_mint_dgen(tx, state),
             "transfer_nft" => handle_transfer_nft(tx, state),
+            "set_uri_hint" => handle_set_uri_hint(tx, state),
             other => Err(format!("nft_dgen: unknown call_id '{}'", other)),
         }
     }
+
+    fn check_invariants(&self, state: &State) -> Vec<String> {
+        check_owner_index(state)
+    }
+}
+
+/// Decode `mint_dgen` params, including payloads from before
+/// `royalty_splits`, which end after `uri_hint`, and from before `uri_hint`,
+/// which end after the royalty.
+fn decode_mint_dgen(payload: &[u8]) -> Result<MintDgenParams, String> {
+    codec::decode_exact(payload).or_else(|e| {
+        codec::decode_exact::<MintDgenParamsV2>(payload)
+            .map(|v2| MintDgenParams {
+                fabric_root_hash: v2.fabric_root_hash,
+                forge_model_id: v2.forge_model_id,
+                forge_prompt_hash: v2.forge_prompt_hash,
+                royalty_recipient: v2.royalty_recipient,
+                royalty_bps: v2.royalty_bps,
+                uri_hint: v2.uri_hint,
+                royalty_splits: Vec::new(),
+            })
+            .or_else(|_| {
+                codec::decode_exact::<MintDgenParamsV1>(payload).map(|v1| MintDgenParams {
+                    fabric_root_hash: v1.fabric_root_hash,
+                    forge_model_id: v1.forge_model_id,
+                    forge_prompt_hash: v1.forge_prompt_hash,
+                    royalty_recipient: v1.royalty_recipient,
+                    royalty_bps: v1.royalty_bps,
+                    uri_hint: None,
+                    royalty_splits: Vec::new(),
+                })
+            })
+            .map_err(|_| e.to_string())
+    })
 }
 
 fn handle_mint_dgen(tx: &Transaction, state: &mut State) -> Result<(), String> {
@@ -142,56 +514,48 @@ fn handle_mint_dgen(tx: &Transaction, state: &mut State) -> Result<(), String> {
         return Err("only Archons may mint D-GEN NFTs".into());
     }
 
-    let params: MintDgenParams = bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
-
-    let mut next_id = get_next_nft_id(state);
-    let token_id = next_id;
-    next_id = next_id.checked_add(1).ok_or("nft id overflow")?;
-    set_next_nft_id(state, next_id)?;
-
-    let meta = DGenMetadata {
-        creator: tx.from,
-        owner: tx.from,
-        fabric_root_hash: params.fabric_root_hash,
-        forge_model_id: params.forge_model_id,
-        forge_prompt_hash: params.forge_prompt_hash,
-        royalty_recipient: params.royalty_recipient,
-        royalty_bps: params.royalty_bps,
-    };
+    let params = decode_mint_dgen(&tx.payload)?;
 
-    store_nft(state, token_id, &meta)?;
-
-    // index under owner
-    let mut owner_list = load_owner_nfts(state, &tx.from);
-    owner_list.push(token_id);
-    store_owner_nfts(state, &tx.from, &owner_list)?;
+    let token_id = mint(state, &tx.from, &params)?;
 
+    state.emit_event(Event::new(MODULE_ID, "mint", tx.from).with("token_id", token_id));
     Ok(())
 }
 
 fn handle_transfer_nft(tx: &Transaction, state: &mut State) -> Result<(), String> {
-    let params: TransferNftParams = bincode::deserialize(&tx.payload).map_err(|e| e.to_string())?;
+    let params: TransferNftParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
 
-    let mut meta = load_nft(state, params.token_id).ok_or_else(|| "NFT not found".to_string())?;
+    let meta = load_nft(state, params.token_id).ok_or_else(|| "NFT not found".to_string())?;
 
     if meta.owner != tx.from {
         return Err("only the current owner may transfer this NFT".into());
     }
 
-    // Remove from old owner list
-    let mut old_owner_list = load_owner_nfts(state, &meta.owner);
-    old_owner_list.retain(|id| *id != params.token_id);
-    store_owner_nfts(state, &meta.owner, &old_owner_list)?;
+    force_transfer(state, params.token_id, &params.to)?;
 
-    // Add to new owner
-    let mut new_owner_list = load_owner_nfts(state, &params.to);
-    new_owner_list.push(params.token_id);
-    store_owner_nfts(state, &params.to, &new_owner_list)?;
+    state.emit_event(
+        Event::new(MODULE_ID, "transfer", tx.from)
+            .with("to", params.to.to_display())
+            .with("token_id", params.token_id),
+    );
+    Ok(())
+}
 
-    // Update owner in metadata
-    meta.owner = params.to;
+fn handle_set_uri_hint(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: SetUriHintParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+    if let Some(uri) = &params.uri_hint {
+        validate_uri_hint(uri)?;
+    }
+
+    let mut meta = load_nft(state, params.token_id).ok_or_else(|| "NFT not found".to_string())?;
+    if meta.creator != tx.from {
+        return Err("only the NFT's creator may set its uri_hint".into());
+    }
+
+    meta.uri_hint = params.uri_hint;
     store_nft(state, params.token_id, &meta)?;
 
+    state.emit_event(Event::new(MODULE_ID, "uri_hint", tx.from).with("token_id", params.token_id));
     Ok(())
 }
 
@@ -213,6 +577,8 @@ mod tests {
             forge_prompt_hash: None,
             royalty_recipient: None,
             royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
         };
 
         let tx = Transaction {
@@ -220,9 +586,11 @@ mod tests {
             nonce: 0,
             module_id: "nft_dgen".to_string(),
             call_id: "mint_dgen".to_string(),
-            payload: bincode::serialize(&params).unwrap(),
+            payload: codec::encode(&params).unwrap(),
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         let module = NftDgenModule::new();
@@ -245,6 +613,8 @@ mod tests {
             payload: vec![],
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
         let avatars_module = AvatarsProfilesModule::new();
         avatars_module
@@ -259,6 +629,8 @@ mod tests {
             forge_prompt_hash: None,
             royalty_recipient: None,
             royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
         };
 
         let mint_tx = Transaction {
@@ -266,9 +638,11 @@ mod tests {
             nonce: 0,
             module_id: "nft_dgen".to_string(),
             call_id: "mint_dgen".to_string(),
-            payload: bincode::serialize(&params).unwrap(),
+            payload: codec::encode(&params).unwrap(),
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         let nft_module = NftDgenModule::new();
@@ -302,6 +676,8 @@ mod tests {
             payload: vec![],
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
         let avatars_module = AvatarsProfilesModule::new();
         avatars_module
@@ -314,15 +690,19 @@ mod tests {
             forge_prompt_hash: None,
             royalty_recipient: None,
             royalty_bps: 0,
+            uri_hint: None,
+            royalty_splits: Vec::new(),
         };
         let mint_tx = Transaction {
             from: creator,
             nonce: 0,
             module_id: "nft_dgen".to_string(),
             call_id: "mint_dgen".to_string(),
-            payload: bincode::serialize(&params).unwrap(),
+            payload: codec::encode(&params).unwrap(),
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
         let nft_module = NftDgenModule::new();
         nft_module
@@ -339,9 +719,11 @@ mod tests {
             nonce: 0,
             module_id: "nft_dgen".to_string(),
             call_id: "transfer_nft".to_string(),
-            payload: bincode::serialize(&transfer_params).unwrap(),
+            payload: codec::encode(&transfer_params).unwrap(),
             fee: 0,
             signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
         };
 
         nft_module
@@ -356,5 +738,135 @@ mod tests {
         // Verify owner indices
         assert_eq!(get_nfts_by_owner(&state, &creator), Vec::<NftId>::new());
         assert_eq!(get_nfts_by_owner(&state, &new_owner), vec![0]);
+
+        // The emptied owner index leaves no keys behind
+        assert!(state.scan_prefix(&owner_nfts_key(&creator)).is_empty());
+    }
+
+    #[test]
+    fn test_set_uri_hint_creator_only() {
+        let mut state = State::in_memory();
+        let creator = [1u8; 32];
+        let new_owner = [2u8; 32];
+        let tx = |from: Address, call_id: &str, payload: Vec<u8>| Transaction {
+            from,
+            nonce: 0,
+            module_id: "nft_dgen".to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let set_hint = |uri: &str| {
+            codec::encode(&SetUriHintParams {
+                token_id: 0,
+                uri_hint: Some(uri.to_string()),
+            })
+            .unwrap()
+        };
+
+        let claim_tx = Transaction {
+            module_id: "avatars_profiles".to_string(),
+            ..tx(creator, "claim_archon", vec![])
+        };
+        AvatarsProfilesModule::new()
+            .dispatch("claim_archon", &claim_tx, &mut state)
+            .unwrap();
+
+        let nft_module = NftDgenModule::new();
+        let mut params = MintDgenParams {
+            fabric_root_hash: [0u8; 32],
+            forge_model_id: None,
+            forge_prompt_hash: None,
+            royalty_recipient: None,
+            royalty_bps: 0,
+            uri_hint: Some("ftp://relic".to_string()),
+            royalty_splits: Vec::new(),
+        };
+        let mint_tx = tx(creator, "mint_dgen", codec::encode(&params).unwrap());
+        let err = nft_module
+            .dispatch("mint_dgen", &mint_tx, &mut state)
+            .unwrap_err();
+        assert!(err.contains("must start with one of"));
+
+        params.uri_hint = Some("ipfs://relic".to_string());
+        let mint_tx = tx(creator, "mint_dgen", codec::encode(&params).unwrap());
+        nft_module
+            .dispatch("mint_dgen", &mint_tx, &mut state)
+            .unwrap();
+        assert_eq!(
+            get_nft(&state, 0).unwrap().uri_hint.as_deref(),
+            Some("ipfs://relic")
+        );
+
+        // The creator keeps the right to update the hint after a transfer;
+        // the new owner does not gain it
+        let transfer = TransferNftParams {
+            token_id: 0,
+            to: new_owner,
+        };
+        let transfer_tx = tx(creator, "transfer_nft", codec::encode(&transfer).unwrap());
+        nft_module
+            .dispatch("transfer_nft", &transfer_tx, &mut state)
+            .unwrap();
+
+        let owner_tx = tx(new_owner, "set_uri_hint", set_hint("https://relic.example"));
+        let err = nft_module
+            .dispatch("set_uri_hint", &owner_tx, &mut state)
+            .unwrap_err();
+        assert!(err.contains("only the NFT's creator"));
+
+        let creator_tx = tx(creator, "set_uri_hint", set_hint("https://relic.example"));
+        nft_module
+            .dispatch("set_uri_hint", &creator_tx, &mut state)
+            .unwrap();
+        assert_eq!(
+            get_nft(&state, 0).unwrap().uri_hint.as_deref(),
+            Some("https://relic.example")
+        );
+    }
+
+    #[test]
+    fn test_legacy_metadata_loads_without_uri_hint() {
+        let mut state = State::in_memory();
+
+        // Metadata stored before uri_hint upgrades with no hint
+        let none: Option<[u8; 32]> = None;
+        let v1 =
+            codec::encode(&([1u8; 32], [2u8; 32], [3u8; 32], none, none, none, 250u16)).unwrap();
+        state.put_raw(nft_key(0), v1).unwrap();
+        let nft = get_nft(&state, 0).unwrap();
+        assert_eq!(nft.owner, [2u8; 32]);
+        assert_eq!(nft.royalty_bps, 250);
+        assert_eq!(nft.uri_hint, None);
+        assert!(nft.royalty_splits.is_empty());
+
+        // and version 2 metadata with no royalty splits
+        let hint = Some("ipfs://relic".to_string());
+        let mut v2 = vec![2];
+        v2.extend(
+            codec::encode(&(
+                [1u8; 32], [2u8; 32], [3u8; 32], none, none, none, 250u16, &hint,
+            ))
+            .unwrap(),
+        );
+        state.put_raw(nft_key(1), v2).unwrap();
+        let nft = get_nft(&state, 1).unwrap();
+        assert_eq!(nft.uri_hint, hint);
+        assert!(nft.royalty_splits.is_empty());
+
+        // So do mint_dgen payloads from older blocks
+        let v1 = codec::encode(&([3u8; 32], none, none, none, 250u16)).unwrap();
+        let params = decode_mint_dgen(&v1).unwrap();
+        assert_eq!(params.royalty_bps, 250);
+        assert_eq!(params.uri_hint, None);
+        let v2 = codec::encode(&([3u8; 32], none, none, none, 250u16, &hint)).unwrap();
+        let params = decode_mint_dgen(&v2).unwrap();
+        assert_eq!(params.uri_hint, hint);
+        assert!(params.royalty_splits.is_empty());
+        // A version 2 payload cut off inside its hint is not a version 1 one
+        assert!(decode_mint_dgen(&v2[..v2.len() - 3]).is_err());
     }
 }
@@ -0,0 +1,501 @@
+//! Quests module for recurring Gnosis quests with XP rewards.
+//!
+//! This module handles:
+//! - Archons defining quests: a count of one module event kind to reach
+//!   within a window of blocks, rewarded with Gnosis XP and optionally a badge
+//! - Matching each block's events against active quests at the end of the block
+//! - Granting rewards on completion, after which the quest can be completed again
+//!
+//! Only Aeons (addresses with a profile) make progress, since rewards land
+//! on the Aeon profile.
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::avatars;
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::{Address, Transaction};
+
+const MODULE_ID: &str = "quests";
+const PREFIX_QUEST: &str = "quest";
+const PREFIX_QUEST_COUNTER: &str = "counter";
+const PREFIX_PROGRESS: &str = "progress";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_QUEST, PREFIX_QUEST_COUNTER, PREFIX_PROGRESS];
+
+/// Quest ID type
+pub type QuestId = u64;
+
+/// What an address must do to complete a quest.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct QuestCriteria {
+    /// Module whose events count (e.g. "bank_cgt").
+    pub module_id: String,
+    /// Event kind within that module (e.g. "transfer").
+    pub event_kind: String,
+    /// Matching events needed to complete the quest.
+    pub count: u32,
+    /// Blocks, starting at the first counted event, in which to reach `count`.
+    pub window_blocks: u64,
+}
+
+/// Quest definition
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Quest {
+    pub id: QuestId,
+    pub creator: Address,
+    pub name: String,
+    pub criteria: QuestCriteria,
+    pub xp_reward: u64,
+    /// Badge granted on completion, if any.
+    pub badge: Option<String>,
+    pub active: bool,
+}
+
+/// An address's progress on one quest.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct QuestProgress {
+    /// Height of the first event counted in the current window.
+    pub window_start: u64,
+    /// Matching events counted in the current window.
+    pub count: u32,
+    /// Times the quest has been completed.
+    pub completions: u32,
+}
+
+/// Create quest parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateQuestParams {
+    pub name: String,
+    pub criteria: QuestCriteria,
+    pub xp_reward: u64,
+    pub badge: Option<String>,
+}
+
+/// Retire quest parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RetireQuestParams {
+    pub quest_id: QuestId,
+}
+
+/// Payload layout of `QuestCriteria`.
+const CRITERIA_SCHEMA: &[FieldSchema] = &[
+    FieldSchema::new("module_id", FieldType::String),
+    FieldSchema::new("event_kind", FieldType::String),
+    FieldSchema::new("count", FieldType::U32),
+    FieldSchema::new("window_blocks", FieldType::U64),
+];
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "create_quest",
+        CallSchema::new(&[
+            FieldSchema::new("name", FieldType::String),
+            FieldSchema::new("criteria", FieldType::Struct(CRITERIA_SCHEMA)),
+            FieldSchema::new("xp_reward", FieldType::U64),
+            FieldSchema::new("badge", FieldType::Option(&FieldType::String)),
+        ]),
+    ),
+    (
+        "retire_quest",
+        CallSchema::new(&[FieldSchema::new("quest_id", FieldType::U64)]),
+    ),
+];
+
+// Helper functions for quest storage
+
+fn quest_key(id: QuestId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_QUEST)
+        .u64(id)
+        .into_bytes()
+}
+
+fn quest_counter_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_QUEST_COUNTER).into_bytes()
+}
+
+fn progress_key(addr: &Address, id: QuestId) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_PROGRESS)
+        .address(addr)
+        .u64(id)
+        .into_bytes()
+}
+
+fn load_quest(state: &State, id: QuestId) -> Option<Quest> {
+    state
+        .get_raw(&quest_key(id))
+        .and_then(|bytes| codec::decode_state::<Quest>(&bytes).ok())
+}
+
+fn store_quest(state: &mut State, quest: &Quest) -> Result<(), String> {
+    let bytes = codec::encode(quest).map_err(|e| e.to_string())?;
+    state
+        .put_raw(quest_key(quest.id), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn load_progress(state: &State, addr: &Address, id: QuestId) -> QuestProgress {
+    state
+        .get_raw(&progress_key(addr, id))
+        .and_then(|bytes| codec::decode_state::<QuestProgress>(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn store_progress(
+    state: &mut State,
+    addr: &Address,
+    id: QuestId,
+    progress: &QuestProgress,
+) -> Result<(), String> {
+    let bytes = codec::encode(progress).map_err(|e| e.to_string())?;
+    state
+        .put_raw(progress_key(addr, id), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn get_next_quest_id(state: &State) -> QuestId {
+    state
+        .get_raw(&quest_counter_key())
+        .and_then(|bytes| codec::decode_state::<QuestId>(&bytes).ok())
+        .unwrap_or(0)
+}
+
+fn set_next_quest_id(state: &mut State, next: QuestId) -> Result<(), String> {
+    let bytes = codec::encode(&next).map_err(|e| e.to_string())?;
+    state
+        .put_raw(quest_counter_key(), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Public helper for querying a quest (for RPC/SDK use).
+pub fn get_quest(state: &State, id: QuestId) -> Option<Quest> {
+    load_quest(state, id)
+}
+
+/// All quests, active or retired, in id order.
+pub fn list_quests(state: &State) -> Vec<Quest> {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_QUEST).into_bytes();
+    state
+        .scan_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(_, bytes)| codec::decode_state::<Quest>(&bytes).ok())
+        .collect()
+}
+
+/// An address's progress on every quest it has made progress on, by quest id.
+pub fn get_progress(state: &State, addr: &Address) -> Vec<(QuestId, QuestProgress)> {
+    let prefix = StorageKey::new(MODULE_ID, PREFIX_PROGRESS)
+        .address(addr)
+        .into_bytes();
+    state
+        .scan_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(key, bytes)| {
+            let id = <[u8; 8]>::try_from(&key[prefix.len()..]).ok()?;
+            let progress = codec::decode_state::<QuestProgress>(&bytes).ok()?;
+            Some((QuestId::from_be_bytes(id), progress))
+        })
+        .collect()
+}
+
+/// QuestsModule handles Gnosis quests and their rewards
+#[derive(Default)]
+pub struct QuestsModule;
+
+impl QuestsModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for QuestsModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["create_quest", "retire_quest"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "create_quest" => handle_create_quest(tx, state),
+            "retire_quest" => handle_retire_quest(tx, state),
+            other => Err(format!("quests: unknown call_id '{}'", other)),
+        }
+    }
+
+    /// Count this block's events toward active quests, rewarding completions.
+    fn on_block_end(&self, state: &mut State) -> Result<(), String> {
+        let events = state.block_events().to_vec();
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let height = state.block_height();
+        let quests: Vec<Quest> = list_quests(state)
+            .into_iter()
+            .filter(|q| q.active)
+            .collect();
+        for event in &events {
+            if avatars::profile(state, &event.address).is_none() {
+                continue;
+            }
+            for quest in quests.iter().filter(|q| {
+                q.criteria.module_id == event.module_id && q.criteria.event_kind == event.kind
+            }) {
+                record_event(state, quest, &event.address, height)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Count one matching event for `addr`, completing the quest if it reaches the target.
+fn record_event(
+    state: &mut State,
+    quest: &Quest,
+    addr: &Address,
+    height: u64,
+) -> Result<(), String> {
+    let mut progress = load_progress(state, addr, quest.id);
+
+    // Start a new window on the first event, or once the last one has lapsed
+    if progress.count == 0
+        || height
+            >= progress
+                .window_start
+                .saturating_add(quest.criteria.window_blocks)
+    {
+        progress.window_start = height;
+        progress.count = 0;
+    }
+    progress.count += 1;
+
+    if progress.count >= quest.criteria.count {
+        progress.count = 0;
+        progress.completions = progress.completions.saturating_add(1);
+        avatars::add_gnosis_xp(state, addr, quest.xp_reward)?;
+        if let Some(badge) = &quest.badge {
+            avatars::set_badge(state, addr, badge, true)?;
+        }
+        avatars::recompute_ascension(state, addr)?;
+    }
+
+    store_progress(state, addr, quest.id, &progress)
+}
+
+fn handle_create_quest(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: CreateQuestParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if !avatars::is_archon(state, &tx.from) {
+        return Err("only Archons may create quests".into());
+    }
+
+    if params.criteria.count == 0 || params.criteria.window_blocks == 0 {
+        return Err("quest count and window_blocks must be > 0".into());
+    }
+
+    let id = get_next_quest_id(state);
+    set_next_quest_id(state, id.checked_add(1).ok_or("quest id overflow")?)?;
+
+    let quest = Quest {
+        id,
+        creator: tx.from,
+        name: params.name,
+        criteria: params.criteria,
+        xp_reward: params.xp_reward,
+        badge: params.badge,
+        active: true,
+    };
+
+    store_quest(state, &quest)
+}
+
+fn handle_retire_quest(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: RetireQuestParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    let mut quest =
+        load_quest(state, params.quest_id).ok_or_else(|| "Quest not found".to_string())?;
+
+    if quest.creator != tx.from {
+        return Err("only the quest creator may retire it".into());
+    }
+
+    if !quest.active {
+        return Err("quest is already retired".into());
+    }
+
+    quest.active = false;
+    store_quest(state, &quest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ChainConfig, CGT_UNIT};
+    use crate::core::block::{Block, BlockHeader};
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::runtime::bank_cgt::TransferParams;
+    use crate::runtime::module_api::bank;
+
+    const ARCHON: Address = [1; 32];
+
+    fn sprinter() -> Address {
+        account(2)
+    }
+
+    fn stroller() -> Address {
+        account(3)
+    }
+
+    fn transfer(from: Address, nonce: u64) -> Transaction {
+        signed(Transaction {
+            from,
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to: ARCHON,
+                amount: CGT_UNIT,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        })
+    }
+
+    fn execute(state: &mut State, height: u64, body: Vec<Transaction>) {
+        let block = Block {
+            header: BlockHeader {
+                height,
+                prev_hash: [0; 32],
+                state_root: [0; 32],
+                timestamp: height,
+                difficulty_target: u128::MAX,
+                nonce: 0,
+                tx_root: None,
+            },
+            body,
+        };
+        let mut chain = ChainConfig::default();
+        chain.genesis.difficulty_target = u128::MAX;
+        state.execute_block(&block, &chain).unwrap();
+    }
+
+    fn state_with_quest() -> State {
+        let mut state = State::in_memory();
+        avatars::grant_archon(&mut state, &ARCHON).unwrap();
+        for (addr, name) in [(sprinter(), "Sprinter"), (stroller(), "Stroller")] {
+            bank::credit(&mut state, &addr, 10 * CGT_UNIT).unwrap();
+            avatars::create_aeon_profile(&mut state, addr, name.to_string(), None, 0).unwrap();
+        }
+
+        let params = CreateQuestParams {
+            name: "Three transfers".to_string(),
+            criteria: QuestCriteria {
+                module_id: "bank_cgt".to_string(),
+                event_kind: "transfer".to_string(),
+                count: 3,
+                window_blocks: 3,
+            },
+            xp_reward: 25,
+            badge: Some("Courier".to_string()),
+        };
+        let tx = Transaction {
+            from: ARCHON,
+            nonce: 0,
+            module_id: "quests".to_string(),
+            call_id: "create_quest".to_string(),
+            payload: codec::encode(&params).unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        QuestsModule::new()
+            .dispatch("create_quest", &tx, &mut state)
+            .unwrap();
+        state
+    }
+
+    #[test]
+    fn test_create_quest_requires_archon() {
+        let mut state = state_with_quest();
+        assert_eq!(list_quests(&state).len(), 1);
+
+        let params = CreateQuestParams {
+            name: "Self-serve XP".to_string(),
+            criteria: get_quest(&state, 0).unwrap().criteria,
+            xp_reward: 1_000,
+            badge: None,
+        };
+        let tx = Transaction {
+            from: sprinter(),
+            nonce: 0,
+            module_id: "quests".to_string(),
+            call_id: "create_quest".to_string(),
+            payload: codec::encode(&params).unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        let err = QuestsModule::new()
+            .dispatch("create_quest", &tx, &mut state)
+            .unwrap_err();
+        assert!(err.contains("only Archons"));
+    }
+
+    #[test]
+    fn test_quest_completed_across_blocks_within_window() {
+        let mut state = state_with_quest();
+
+        execute(
+            &mut state,
+            1,
+            vec![
+                transfer(sprinter(), 0),
+                transfer(sprinter(), 1),
+                transfer(stroller(), 0),
+            ],
+        );
+        let progress = get_progress(&state, &sprinter());
+        assert_eq!(progress[0].1.count, 2);
+        assert_eq!(progress[0].1.completions, 0);
+
+        // The third transfer lands inside the window and completes the quest
+        execute(
+            &mut state,
+            2,
+            vec![transfer(sprinter(), 2), transfer(stroller(), 1)],
+        );
+        let progress = &get_progress(&state, &sprinter())[0].1;
+        assert_eq!(progress.completions, 1);
+        assert_eq!(progress.count, 0);
+        let profile = avatars::profile(&state, &sprinter()).unwrap();
+        assert_eq!(profile.gnosis_xp, 25);
+        assert!(profile.badges.contains(&"Courier".to_string()));
+
+        // The other address's third transfer comes after its window lapsed,
+        // so counting starts over
+        execute(&mut state, 4, vec![transfer(stroller(), 2)]);
+        let progress = &get_progress(&state, &stroller())[0].1;
+        assert_eq!(progress.completions, 0);
+        assert_eq!(progress.window_start, 4);
+        assert_eq!(progress.count, 1);
+        assert_eq!(avatars::profile(&state, &stroller()).unwrap().gnosis_xp, 0);
+    }
+}
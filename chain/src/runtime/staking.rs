@@ -0,0 +1,399 @@
+//! Staking module for CGT.
+//!
+//! This module handles:
+//! - Locking CGT as stake
+//! - Unstaking with an unbonding delay before funds can be withdrawn
+//! - Reward accrual proportional to stake and elapsed blocks
+//!
+//! Rewards are tracked with a global accumulator of reward per staked base
+//! unit, so accruing them costs the same regardless of how many stakers exist.
+//! Claimed rewards are newly minted CGT.
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::{bank, governance};
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::{Address, Transaction};
+
+const MODULE_ID: &str = "staking";
+const PREFIX_STAKE: &str = "stake";
+const PREFIX_UNBONDING: &str = "unbonding";
+const PREFIX_ACCUMULATOR: &str = "accumulator";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_STAKE, PREFIX_UNBONDING, PREFIX_ACCUMULATOR];
+
+/// Default blocks between an unstake and the funds becoming withdrawable.
+///
+/// Governance can override this with `staking.unbonding_period_blocks`.
+pub const UNBONDING_PERIOD_BLOCKS: u64 = 100;
+
+/// Reward per block, in parts per billion of the staked amount.
+pub const REWARD_RATE_PPB_PER_BLOCK: u128 = 10;
+
+/// Fixed-point scale of the reward accumulator (parts per billion).
+const ACCUMULATOR_SCALE: u128 = 1_000_000_000;
+
+/// An address's active stake.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Stake {
+    pub amount: u64,
+    /// Rewards settled but not yet claimed.
+    pub pending_rewards: u64,
+    /// Accumulator value at the last settlement.
+    reward_snapshot: u128,
+}
+
+/// Unstaked CGT waiting out the unbonding period.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Unbonding {
+    pub amount: u64,
+    /// Withdrawable from this height onwards.
+    pub release_height: u64,
+}
+
+/// Global reward accumulator.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct Accumulator {
+    /// Total reward per staked base unit since genesis, scaled by `ACCUMULATOR_SCALE`.
+    reward_per_unit: u128,
+    last_height: u64,
+}
+
+/// Stake parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StakeParams {
+    pub amount: u64,
+}
+
+/// Unstake parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnstakeParams {
+    pub amount: u64,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "stake",
+        CallSchema::new(&[FieldSchema::new("amount", FieldType::U64)]),
+    ),
+    (
+        "unstake",
+        CallSchema::new(&[FieldSchema::new("amount", FieldType::U64)]),
+    ),
+    ("withdraw_unbonded", CallSchema::new(&[])),
+    ("claim_rewards", CallSchema::new(&[])),
+];
+
+// Helper functions for staking storage
+
+fn stake_key(addr: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_STAKE)
+        .address(addr)
+        .into_bytes()
+}
+
+fn unbonding_key(addr: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_UNBONDING)
+        .address(addr)
+        .into_bytes()
+}
+
+fn accumulator_key() -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_ACCUMULATOR).into_bytes()
+}
+
+fn load<T: for<'de> Deserialize<'de>>(state: &State, key: &[u8]) -> Option<T> {
+    state
+        .get_raw(key)
+        .and_then(|bytes| codec::decode_state::<T>(&bytes).ok())
+}
+
+fn store<T: Serialize>(state: &mut State, key: Vec<u8>, value: &T) -> Result<(), String> {
+    let bytes = codec::encode(value).map_err(|e| e.to_string())?;
+    state.put_raw(key, bytes).map_err(|e| e.to_string())
+}
+
+/// Advance the accumulator to the current block height.
+fn accumulator_at(state: &State, height: u64) -> Accumulator {
+    let mut acc: Accumulator = load(state, &accumulator_key()).unwrap_or_default();
+    let elapsed = height.saturating_sub(acc.last_height) as u128;
+    acc.reward_per_unit += elapsed * REWARD_RATE_PPB_PER_BLOCK;
+    acc.last_height = acc.last_height.max(height);
+    acc
+}
+
+/// Move rewards earned since the stake's last settlement into `pending_rewards`.
+fn settle(stake: &mut Stake, acc: &Accumulator) -> Result<(), String> {
+    let earned =
+        stake.amount as u128 * (acc.reward_per_unit - stake.reward_snapshot) / ACCUMULATOR_SCALE;
+    let earned = u64::try_from(earned).map_err(|_| "staking reward overflow")?;
+    stake.pending_rewards = stake
+        .pending_rewards
+        .checked_add(earned)
+        .ok_or("staking reward overflow")?;
+    stake.reward_snapshot = acc.reward_per_unit;
+    Ok(())
+}
+
+/// Load the caller's stake settled up to the current height, persisting the accumulator.
+fn settled_stake(state: &mut State, addr: &Address) -> Result<Stake, String> {
+    let acc = accumulator_at(state, state.block_height());
+    store(state, accumulator_key(), &acc)?;
+
+    let mut stake = load::<Stake>(state, &stake_key(addr)).unwrap_or_else(|| Stake {
+        reward_snapshot: acc.reward_per_unit,
+        ..Stake::default()
+    });
+    settle(&mut stake, &acc)?;
+    Ok(stake)
+}
+
+/// Public helper for querying a stake, with rewards accrued up to `height`.
+///
+/// Queries outside block execution pass the chain tip's height:
+/// `State::block_height` is only set while a block or template runs.
+pub fn get_stake(state: &State, addr: &Address, height: u64) -> Option<Stake> {
+    let mut stake = load::<Stake>(state, &stake_key(addr))?;
+    settle(&mut stake, &accumulator_at(state, height)).ok()?;
+    Some(stake)
+}
+
+/// Public helper for querying an address's pending unbondings.
+pub fn get_unbondings(state: &State, addr: &Address) -> Vec<Unbonding> {
+    load(state, &unbonding_key(addr)).unwrap_or_default()
+}
+
+/// StakingModule handles CGT staking and rewards
+#[derive(Default)]
+pub struct StakingModule;
+
+impl StakingModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for StakingModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["stake", "unstake", "withdraw_unbonded", "claim_rewards"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "stake" => handle_stake(tx, state),
+            "unstake" => handle_unstake(tx, state),
+            "withdraw_unbonded" => handle_withdraw_unbonded(tx, state),
+            "claim_rewards" => handle_claim_rewards(tx, state),
+            other => Err(format!("staking: unknown call_id '{}'", other)),
+        }
+    }
+
+    /// Staked CGT and CGT waiting out the unbonding period. Unclaimed
+    /// rewards are not counted: they are minted when claimed.
+    fn held_cgt(&self, state: &State) -> u128 {
+        let stake_prefix = StorageKey::new(MODULE_ID, PREFIX_STAKE).into_bytes();
+        let staked: u128 = state
+            .scan_prefix(&stake_prefix)
+            .iter()
+            .filter_map(|(_, bytes)| codec::decode_state::<Stake>(bytes).ok())
+            .map(|stake| u128::from(stake.amount))
+            .sum();
+        let unbonding_prefix = StorageKey::new(MODULE_ID, PREFIX_UNBONDING).into_bytes();
+        let unbonding: u128 = state
+            .scan_prefix(&unbonding_prefix)
+            .iter()
+            .filter_map(|(_, bytes)| codec::decode_state::<Vec<Unbonding>>(bytes).ok())
+            .flatten()
+            .map(|unbonding| u128::from(unbonding.amount))
+            .sum();
+        staked + unbonding
+    }
+}
+
+fn handle_stake(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: StakeParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if params.amount == 0 {
+        return Err("stake amount must be > 0".into());
+    }
+
+    let mut stake = settled_stake(state, &tx.from)?;
+    bank::debit_into_stake(state, &tx.from, params.amount)?;
+    stake.amount = stake
+        .amount
+        .checked_add(params.amount)
+        .ok_or("stake overflow")?;
+
+    store(state, stake_key(&tx.from), &stake)
+}
+
+fn handle_unstake(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: UnstakeParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if params.amount == 0 {
+        return Err("unstake amount must be > 0".into());
+    }
+
+    let mut stake = settled_stake(state, &tx.from)?;
+    if stake.amount < params.amount {
+        return Err("insufficient stake".into());
+    }
+    stake.amount -= params.amount;
+    store(state, stake_key(&tx.from), &stake)?;
+
+    let period = governance::param(state, governance::PARAM_UNBONDING_PERIOD_BLOCKS)
+        .unwrap_or(UNBONDING_PERIOD_BLOCKS);
+    let mut unbondings = get_unbondings(state, &tx.from);
+    unbondings.push(Unbonding {
+        amount: params.amount,
+        release_height: state.block_height() + period,
+    });
+    store(state, unbonding_key(&tx.from), &unbondings)
+}
+
+fn handle_withdraw_unbonded(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let height = state.block_height();
+    let (released, waiting): (Vec<_>, Vec<_>) = get_unbondings(state, &tx.from)
+        .into_iter()
+        .partition(|u| u.release_height <= height);
+
+    if released.is_empty() {
+        return Err("no unbonded stake to withdraw".into());
+    }
+
+    let amount = released
+        .iter()
+        .try_fold(0u64, |sum, u| sum.checked_add(u.amount))
+        .ok_or("unbonding overflow")?;
+    bank::credit(state, &tx.from, amount)?;
+
+    store(state, unbonding_key(&tx.from), &waiting)
+}
+
+fn handle_claim_rewards(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let mut stake = settled_stake(state, &tx.from)?;
+
+    if stake.pending_rewards == 0 {
+        return Err("no staking rewards to claim".into());
+    }
+
+    bank::mint(state, &tx.from, stake.pending_rewards)?;
+    stake.pending_rewards = 0;
+
+    store(state, stake_key(&tx.from), &stake)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::bank_cgt::get_balance_cgt;
+
+    const STAKER: Address = [3; 32];
+    const STAKE_AMOUNT: u64 = 1_000_000_000;
+
+    fn call(state: &mut State, call_id: &str, payload: Vec<u8>) -> Result<(), String> {
+        let tx = Transaction {
+            from: STAKER,
+            nonce: 0,
+            module_id: "staking".to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        StakingModule::new().dispatch(call_id, &tx, state)
+    }
+
+    fn staked_state() -> State {
+        let mut state = State::in_memory();
+        bank::credit(&mut state, &STAKER, STAKE_AMOUNT).unwrap();
+        state.set_block_height(5);
+        let params = StakeParams {
+            amount: STAKE_AMOUNT,
+        };
+        call(&mut state, "stake", codec::encode(&params).unwrap()).unwrap();
+        state
+    }
+
+    #[test]
+    fn test_stake_locks_cgt() {
+        let mut state = staked_state();
+        assert_eq!(get_balance_cgt(&state, &STAKER), 0);
+        assert_eq!(
+            get_stake(&state, &STAKER, state.block_height())
+                .unwrap()
+                .amount,
+            STAKE_AMOUNT
+        );
+
+        let params = StakeParams { amount: 1 };
+        let err = call(&mut state, "stake", codec::encode(&params).unwrap()).unwrap_err();
+        assert!(err.contains("insufficient"), "{}", err);
+    }
+
+    #[test]
+    fn test_rewards_accrue_with_elapsed_blocks() {
+        let mut state = staked_state();
+        // 10 ppb of 1e9 base units is 10 base units per block
+        state.set_block_height(15);
+        assert_eq!(
+            get_stake(&state, &STAKER, state.block_height())
+                .unwrap()
+                .pending_rewards,
+            100
+        );
+
+        call(&mut state, "claim_rewards", vec![]).unwrap();
+        assert_eq!(get_balance_cgt(&state, &STAKER), 100);
+        assert!(call(&mut state, "claim_rewards", vec![]).is_err());
+
+        // Half the stake earns half the rate from here on
+        let params = UnstakeParams {
+            amount: STAKE_AMOUNT / 2,
+        };
+        call(&mut state, "unstake", codec::encode(&params).unwrap()).unwrap();
+        state.set_block_height(25);
+        call(&mut state, "claim_rewards", vec![]).unwrap();
+        assert_eq!(get_balance_cgt(&state, &STAKER), 150);
+    }
+
+    #[test]
+    fn test_unstake_after_unbonding_period() {
+        let mut state = staked_state();
+        let params = UnstakeParams {
+            amount: STAKE_AMOUNT,
+        };
+        call(&mut state, "unstake", codec::encode(&params).unwrap()).unwrap();
+        assert_eq!(
+            get_stake(&state, &STAKER, state.block_height())
+                .unwrap()
+                .amount,
+            0
+        );
+
+        state.set_block_height(5 + UNBONDING_PERIOD_BLOCKS - 1);
+        let err = call(&mut state, "withdraw_unbonded", vec![]).unwrap_err();
+        assert!(err.contains("no unbonded stake"));
+
+        state.set_block_height(5 + UNBONDING_PERIOD_BLOCKS);
+        call(&mut state, "withdraw_unbonded", vec![]).unwrap();
+        assert_eq!(get_balance_cgt(&state, &STAKER), STAKE_AMOUNT);
+        assert!(get_unbondings(&state, &STAKER).is_empty());
+    }
+}
@@ -0,0 +1,332 @@
+//! CGT bonding module for Aeon ascension boosts.
+//!
+//! This module handles:
+//! - Bonding CGT from an Aeon's free balance
+//! - Unbonding with a delay before a `withdraw` releases the funds
+//! - A per-block Gnosis XP trickle proportional to bonded CGT
+//! - The "Bonded" badge, held while an Aeon has CGT bonded
+//!
+//! Unlike the `staking` module, bonding mints no CGT; its only reward is
+//! progression on the Aeon profile.
+
+use serde::{Deserialize, Serialize};
+
+use super::module_api::{avatars, bank};
+use super::staking::Unbonding;
+use super::{CallSchema, FieldSchema, FieldType, RuntimeModule, StorageKey};
+use crate::config::CGT_UNIT;
+use crate::core::codec;
+use crate::core::state::State;
+use crate::core::transaction::{Address, Transaction};
+
+const MODULE_ID: &str = "staking_cgt";
+const PREFIX_BOND: &str = "bond";
+const PREFIX_UNBONDING: &str = "unbonding";
+const STORAGE_PREFIXES: &[&str] = &[PREFIX_BOND, PREFIX_UNBONDING];
+
+/// Blocks between an unbond and the funds becoming withdrawable.
+pub const BOND_UNBONDING_PERIOD_BLOCKS: u64 = 50;
+
+/// Gnosis XP accrued per block for each whole CGT bonded.
+pub const XP_PER_BONDED_CGT_PER_BLOCK: u64 = 1;
+
+/// Badge shown on Aeons with CGT bonded.
+pub const BONDED_BADGE: &str = "Bonded";
+
+/// Bond parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BondParams {
+    pub amount: u64,
+}
+
+/// Unbond parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnbondParams {
+    pub amount: u64,
+}
+
+/// Payload layout of each call; keep in step with the params structs above.
+const CALL_SCHEMAS: &[(&str, CallSchema)] = &[
+    (
+        "bond",
+        CallSchema::new(&[FieldSchema::new("amount", FieldType::U64)]),
+    ),
+    (
+        "unbond",
+        CallSchema::new(&[FieldSchema::new("amount", FieldType::U64)]),
+    ),
+    ("withdraw", CallSchema::new(&[])),
+];
+
+// Helper functions for bond storage
+
+fn bond_key(addr: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_BOND)
+        .address(addr)
+        .into_bytes()
+}
+
+fn unbonding_key(addr: &Address) -> Vec<u8> {
+    StorageKey::new(MODULE_ID, PREFIX_UNBONDING)
+        .address(addr)
+        .into_bytes()
+}
+
+fn store_bonded(state: &mut State, addr: &Address, amount: u64) -> Result<(), String> {
+    if amount == 0 {
+        return state.delete_raw(&bond_key(addr)).map_err(|e| e.to_string());
+    }
+    let bytes = codec::encode(&amount).map_err(|e| e.to_string())?;
+    state
+        .put_raw(bond_key(addr), bytes)
+        .map_err(|e| e.to_string())
+}
+
+fn store_unbondings(
+    state: &mut State,
+    addr: &Address,
+    unbondings: &[Unbonding],
+) -> Result<(), String> {
+    let bytes = codec::encode(unbondings).map_err(|e| e.to_string())?;
+    state
+        .put_raw(unbonding_key(addr), bytes)
+        .map_err(|e| e.to_string())
+}
+
+/// Public helper for querying an address's bonded CGT.
+pub fn get_bonded(state: &State, addr: &Address) -> u64 {
+    state
+        .get_raw(&bond_key(addr))
+        .and_then(|bytes| codec::decode_state::<u64>(&bytes).ok())
+        .unwrap_or(0)
+}
+
+/// Public helper for querying an address's pending unbonds.
+pub fn get_bond_unbondings(state: &State, addr: &Address) -> Vec<Unbonding> {
+    state
+        .get_raw(&unbonding_key(addr))
+        .and_then(|bytes| codec::decode_state::<Vec<Unbonding>>(&bytes).ok())
+        .unwrap_or_default()
+}
+
+/// StakingCgtModule handles CGT bonding for Aeon progression
+#[derive(Default)]
+pub struct StakingCgtModule;
+
+impl StakingCgtModule {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RuntimeModule for StakingCgtModule {
+    fn module_id(&self) -> &'static str {
+        MODULE_ID
+    }
+
+    fn storage_prefixes(&self) -> &'static [&'static str] {
+        STORAGE_PREFIXES
+    }
+
+    fn call_ids(&self) -> &'static [&'static str] {
+        &["bond", "unbond", "withdraw"]
+    }
+
+    fn call_schemas(&self) -> &'static [(&'static str, CallSchema)] {
+        CALL_SCHEMAS
+    }
+
+    fn dispatch(&self, call_id: &str, tx: &Transaction, state: &mut State) -> Result<(), String> {
+        match call_id {
+            "bond" => handle_bond(tx, state),
+            "unbond" => handle_unbond(tx, state),
+            "withdraw" => handle_withdraw(tx, state),
+            other => Err(format!("staking_cgt: unknown call_id '{}'", other)),
+        }
+    }
+
+    /// Accrue Gnosis XP to every bonded Aeon.
+    ///
+    /// Scans all bonds each block, which is fine at devnet scale.
+    fn on_block_end(&self, state: &mut State) -> Result<(), String> {
+        let prefix = StorageKey::new(MODULE_ID, PREFIX_BOND).into_bytes();
+        for (key, value) in state.scan_prefix(&prefix) {
+            let Ok(addr) = Address::try_from(&key[prefix.len()..]) else {
+                continue;
+            };
+            let bonded: u64 = codec::decode_state(&value).map_err(|e| e.to_string())?;
+            let xp = (bonded / CGT_UNIT).saturating_mul(XP_PER_BONDED_CGT_PER_BLOCK);
+            if xp == 0 || avatars::profile(state, &addr).is_none() {
+                continue;
+            }
+            avatars::add_gnosis_xp(state, &addr, xp)?;
+            avatars::recompute_ascension(state, &addr)?;
+        }
+        Ok(())
+    }
+
+    /// Bonded CGT and CGT waiting out the unbonding period.
+    fn held_cgt(&self, state: &State) -> u128 {
+        let bond_prefix = StorageKey::new(MODULE_ID, PREFIX_BOND).into_bytes();
+        let bonded: u128 = state
+            .scan_prefix(&bond_prefix)
+            .iter()
+            .filter_map(|(_, bytes)| codec::decode_state::<u64>(bytes).ok())
+            .map(u128::from)
+            .sum();
+        let unbonding_prefix = StorageKey::new(MODULE_ID, PREFIX_UNBONDING).into_bytes();
+        let unbonding: u128 = state
+            .scan_prefix(&unbonding_prefix)
+            .iter()
+            .filter_map(|(_, bytes)| codec::decode_state::<Vec<Unbonding>>(bytes).ok())
+            .flatten()
+            .map(|unbonding| u128::from(unbonding.amount))
+            .sum();
+        bonded + unbonding
+    }
+}
+
+fn handle_bond(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: BondParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if params.amount == 0 {
+        return Err("bond amount must be > 0".into());
+    }
+
+    if avatars::profile(state, &tx.from).is_none() {
+        return Err("bonding requires an Aeon profile".into());
+    }
+
+    bank::debit_into_stake(state, &tx.from, params.amount)?;
+    let bonded = get_bonded(state, &tx.from)
+        .checked_add(params.amount)
+        .ok_or("bond overflow")?;
+    store_bonded(state, &tx.from, bonded)?;
+
+    avatars::set_badge(state, &tx.from, BONDED_BADGE, true)
+}
+
+fn handle_unbond(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let params: UnbondParams = codec::decode_exact(&tx.payload).map_err(|e| e.to_string())?;
+
+    if params.amount == 0 {
+        return Err("unbond amount must be > 0".into());
+    }
+
+    let bonded = get_bonded(state, &tx.from);
+    if bonded < params.amount {
+        return Err("insufficient bonded CGT".into());
+    }
+    store_bonded(state, &tx.from, bonded - params.amount)?;
+
+    let mut unbondings = get_bond_unbondings(state, &tx.from);
+    unbondings.push(Unbonding {
+        amount: params.amount,
+        release_height: state.block_height() + BOND_UNBONDING_PERIOD_BLOCKS,
+    });
+    store_unbondings(state, &tx.from, &unbondings)?;
+
+    if bonded == params.amount && avatars::profile(state, &tx.from).is_some() {
+        avatars::set_badge(state, &tx.from, BONDED_BADGE, false)?;
+    }
+    Ok(())
+}
+
+fn handle_withdraw(tx: &Transaction, state: &mut State) -> Result<(), String> {
+    let height = state.block_height();
+    let (released, waiting): (Vec<_>, Vec<_>) = get_bond_unbondings(state, &tx.from)
+        .into_iter()
+        .partition(|u| u.release_height <= height);
+
+    if released.is_empty() {
+        return Err(match waiting.iter().map(|u| u.release_height).min() {
+            Some(next) => format!("unbonding period ends at height {}", next),
+            None => "no unbonded CGT to withdraw".to_string(),
+        });
+    }
+
+    let amount = released
+        .iter()
+        .try_fold(0u64, |sum, u| sum.checked_add(u.amount))
+        .ok_or("unbonding overflow")?;
+    bank::credit(state, &tx.from, amount)?;
+
+    store_unbondings(state, &tx.from, &waiting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::bank_cgt::get_balance_cgt;
+
+    const AEON: Address = [5; 32];
+
+    fn call(state: &mut State, call_id: &str, payload: Vec<u8>) -> Result<(), String> {
+        let tx = Transaction {
+            from: AEON,
+            nonce: 0,
+            module_id: "staking_cgt".to_string(),
+            call_id: call_id.to_string(),
+            payload,
+            fee: 0,
+            signature: vec![],
+            valid_until_height: None,
+            sig_scheme: 0,
+        };
+        StakingCgtModule::new().dispatch(call_id, &tx, state)
+    }
+
+    fn bond(state: &mut State, amount: u64) -> Result<(), String> {
+        call(
+            state,
+            "bond",
+            codec::encode(&BondParams { amount }).unwrap(),
+        )
+    }
+
+    fn unbond(state: &mut State, amount: u64) -> Result<(), String> {
+        call(
+            state,
+            "unbond",
+            codec::encode(&UnbondParams { amount }).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_bond_requires_profile() {
+        let mut state = State::in_memory();
+        bank::credit(&mut state, &AEON, 10 * CGT_UNIT).unwrap();
+        let err = bond(&mut state, CGT_UNIT).unwrap_err();
+        assert!(err.contains("Aeon profile"));
+    }
+
+    #[test]
+    fn test_bond_unbond_and_withdraw_timing() {
+        let mut state = State::in_memory();
+        bank::credit(&mut state, &AEON, 10 * CGT_UNIT).unwrap();
+        avatars::create_aeon_profile(&mut state, AEON, "Bonder".to_string(), None, 0).unwrap();
+
+        bond(&mut state, 4 * CGT_UNIT).unwrap();
+        assert_eq!(get_balance_cgt(&state, &AEON), 6 * CGT_UNIT);
+        assert_eq!(get_bonded(&state, &AEON), 4 * CGT_UNIT);
+        let profile = avatars::profile(&state, &AEON).unwrap();
+        assert!(profile.badges.contains(&BONDED_BADGE.to_string()));
+
+        state.set_block_height(10);
+        unbond(&mut state, 4 * CGT_UNIT).unwrap();
+        assert_eq!(get_bonded(&state, &AEON), 0);
+        let profile = avatars::profile(&state, &AEON).unwrap();
+        assert!(!profile.badges.contains(&BONDED_BADGE.to_string()));
+
+        // Withdrawing before the unbonding period ends is refused
+        state.set_block_height(10 + BOND_UNBONDING_PERIOD_BLOCKS - 1);
+        let err = call(&mut state, "withdraw", vec![]).unwrap_err();
+        assert!(err.contains("ends at height 60"), "{}", err);
+
+        state.set_block_height(10 + BOND_UNBONDING_PERIOD_BLOCKS);
+        call(&mut state, "withdraw", vec![]).unwrap();
+        assert_eq!(get_balance_cgt(&state, &AEON), 10 * CGT_UNIT);
+        assert!(get_bond_unbondings(&state, &AEON).is_empty());
+        assert!(call(&mut state, "withdraw", vec![]).is_err());
+    }
+}
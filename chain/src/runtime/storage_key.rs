@@ -0,0 +1,258 @@
+//! Per-module storage key namespacing.
+//!
+//! Every key a module writes starts with `module_id/prefix/`, followed by
+//! typed sub-key segments. Module ids are unique in the runtime and prefixes
+//! are unique within a module, so two modules using the same logical sub-key
+//! (e.g. `balance` + address) can never collide.
+
+use crate::core::state::State;
+use crate::core::transaction::Address;
+
+/// Separator between the module id, the prefix, and the sub-key.
+const SEPARATOR: u8 = b'/';
+
+/// Builder for a namespaced storage key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageKey {
+    bytes: Vec<u8>,
+}
+
+impl StorageKey {
+    /// Start a key in `module_id`'s namespace under the given prefix.
+    pub fn new(module_id: &str, prefix: &str) -> Self {
+        debug_assert!(
+            is_valid_segment(module_id),
+            "invalid module id: {:?}",
+            module_id
+        );
+        debug_assert!(
+            is_valid_segment(prefix),
+            "invalid storage prefix: {:?}",
+            prefix
+        );
+
+        let mut bytes = Vec::with_capacity(module_id.len() + prefix.len() + 2 + 32);
+        bytes.extend_from_slice(module_id.as_bytes());
+        bytes.push(SEPARATOR);
+        bytes.extend_from_slice(prefix.as_bytes());
+        bytes.push(SEPARATOR);
+        Self { bytes }
+    }
+
+    /// Append an address sub-key.
+    pub fn address(mut self, addr: &Address) -> Self {
+        self.bytes.extend_from_slice(addr);
+        self
+    }
+
+    /// Append a `u64` sub-key, big-endian so keys sort numerically.
+    pub fn u64(mut self, value: u64) -> Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    /// Append a raw byte sub-key (e.g. a hash or a handle).
+    ///
+    /// Raw sub-keys must be the final segment, since they carry no length.
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    /// The finished key bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Module ids and prefixes must be non-empty and must not contain the separator.
+fn is_valid_segment(segment: &str) -> bool {
+    !segment.is_empty() && !segment.as_bytes().contains(&SEPARATOR)
+}
+
+/// Check that a module's namespace is well-formed and not already taken.
+///
+/// # Arguments
+/// - `registered`: module ids already registered in the runtime
+/// - `module_id`: the module being registered
+/// - `prefixes`: the storage prefixes the module declares
+///
+/// # Returns
+/// - `Ok(())` if the module's keys cannot collide with any other module's
+/// - `Err(String)` describing the conflict
+pub fn check_namespace(
+    registered: &[&str],
+    module_id: &str,
+    prefixes: &[&str],
+) -> Result<(), String> {
+    if !is_valid_segment(module_id) {
+        return Err(format!("invalid module id: {:?}", module_id));
+    }
+    if registered.contains(&module_id) {
+        return Err(format!("module {} is already registered", module_id));
+    }
+    for (i, prefix) in prefixes.iter().enumerate() {
+        if !is_valid_segment(prefix) {
+            return Err(format!(
+                "module {} has invalid storage prefix {:?}",
+                module_id, prefix
+            ));
+        }
+        if prefixes[..i].contains(prefix) {
+            return Err(format!(
+                "module {} declares storage prefix {} twice",
+                module_id, prefix
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Where a pre-namespacing key layout now lives.
+enum LegacyKey {
+    /// A single key.
+    Exact(&'static [u8], &'static str, &'static str),
+    /// Every key under a prefix; the remainder is kept as the sub-key.
+    Prefix(&'static [u8], &'static str, &'static str),
+}
+
+/// Key layouts used before modules were namespaced, mapped to their
+/// `(module_id, prefix)`. Exact keys come first because
+/// `abyss:listing:counter` also sits under the listing prefix.
+const LEGACY_KEYS: &[LegacyKey] = &[
+    LegacyKey::Exact(b"nft:counter", "nft_dgen", "counter"),
+    LegacyKey::Exact(b"abyss:listing:counter", "abyss_registry", "counter"),
+    LegacyKey::Prefix(b"bank:balance:", "bank_cgt", "balance"),
+    LegacyKey::Prefix(b"bank:nonce:", "bank_cgt", "nonce"),
+    LegacyKey::Prefix(b"bank:account:", "bank_cgt", "account"),
+    LegacyKey::Prefix(b"nft:token:", "nft_dgen", "token"),
+    LegacyKey::Prefix(b"nft:owner:", "nft_dgen", "owner"),
+    LegacyKey::Prefix(b"avatars:archon:", "avatars_profiles", "archon"),
+    LegacyKey::Prefix(b"aeon/profile:", "avatars_profiles", "profile"),
+    LegacyKey::Prefix(b"aeon/handle/", "avatars_profiles", "handle"),
+    LegacyKey::Prefix(b"fabric:asset:", "fabric_manager", "asset"),
+    LegacyKey::Prefix(b"abyss:listing:", "abyss_registry", "listing"),
+];
+
+/// Move data stored under pre-namespacing keys to their `module_id/` keys.
+///
+/// Idempotent: once migrated, no legacy keys remain. If both layouts hold a
+/// value, the namespaced one wins and the legacy key is dropped.
+///
+/// # Returns
+/// - `Ok(usize)` with the number of keys moved
+/// - `Err(String)` on storage failure
+pub fn migrate_legacy_keys(state: &mut State) -> Result<usize, String> {
+    let mut moved = 0;
+    for legacy in LEGACY_KEYS {
+        let entries = match legacy {
+            LegacyKey::Exact(key, module_id, prefix) => state
+                .get_raw(key)
+                .map(|value| (key.to_vec(), value, StorageKey::new(module_id, prefix)))
+                .into_iter()
+                .collect::<Vec<_>>(),
+            LegacyKey::Prefix(old_prefix, module_id, prefix) => state
+                .scan_prefix(old_prefix)
+                .into_iter()
+                .map(|(key, value)| {
+                    let new_key =
+                        StorageKey::new(module_id, prefix).bytes(&key[old_prefix.len()..]);
+                    (key, value, new_key)
+                })
+                .collect(),
+        };
+
+        for (old_key, value, new_key) in entries {
+            let new_key = new_key.into_bytes();
+            if state.get_raw(&new_key).is_none() {
+                state.put_raw(new_key, value).map_err(|e| e.to_string())?;
+                moved += 1;
+            }
+            state.delete_raw(&old_key).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::codec;
+
+    #[test]
+    fn test_same_sub_key_in_different_modules_does_not_collide() {
+        let addr = [7u8; 32];
+        let bank = StorageKey::new("bank_cgt", "balance").address(&addr);
+        let other = StorageKey::new("fabric_manager", "balance").address(&addr);
+        assert_ne!(bank, other);
+        assert!(bank.into_bytes().starts_with(b"bank_cgt/balance/"));
+    }
+
+    #[test]
+    fn test_prefix_that_extends_another_does_not_collide() {
+        // Without a separator, every "owners" key would fall under "owner"
+        let owner = StorageKey::new("nft_dgen", "owner").into_bytes();
+        let owners = StorageKey::new("nft_dgen", "owners").into_bytes();
+        assert!(!owners.starts_with(&owner));
+    }
+
+    #[test]
+    fn test_migrate_legacy_keys() {
+        use crate::runtime::{get_balance_cgt, get_listing, get_nfts_by_owner, is_archon};
+
+        let mut state = State::in_memory();
+        let addr = [8u8; 32];
+        let put = |state: &mut State, key: &[u8], value: Vec<u8>| {
+            state.put_raw(key.to_vec(), value).unwrap();
+        };
+        let legacy = |prefix: &[u8], suffix: &[u8]| [prefix, suffix].concat();
+
+        put(
+            &mut state,
+            &legacy(b"bank:balance:", &addr),
+            codec::encode(&250u64).unwrap(),
+        );
+        put(&mut state, &legacy(b"avatars:archon:", &addr), vec![1u8]);
+        put(
+            &mut state,
+            &legacy(b"nft:owner:", &addr),
+            codec::encode(&vec![0u64]).unwrap(),
+        );
+        put(
+            &mut state,
+            b"abyss:listing:counter",
+            codec::encode(&1u64).unwrap(),
+        );
+        // id, token_id, seller, price_cgt, active: the unversioned layout
+        let listing = (0u64, 0u64, addr, 10u64, true);
+        put(
+            &mut state,
+            &legacy(b"abyss:listing:", &0u64.to_be_bytes()),
+            codec::encode(&listing).unwrap(),
+        );
+
+        assert_eq!(migrate_legacy_keys(&mut state), Ok(5));
+        assert_eq!(get_balance_cgt(&state, &addr), 250);
+        assert!(is_archon(&state, &addr));
+        assert_eq!(get_nfts_by_owner(&state, &addr), vec![0]);
+        assert_eq!(get_listing(&state, 0).unwrap().price_cgt, 10);
+        assert_eq!(
+            state.get_raw(b"abyss_registry/counter/"),
+            Some(codec::encode(&1u64).unwrap())
+        );
+
+        // Legacy keys are gone, so a second run is a no-op
+        assert!(state.get_raw(&legacy(b"bank:balance:", &addr)).is_none());
+        assert!(state.scan_prefix(b"abyss:").is_empty());
+        assert_eq!(migrate_legacy_keys(&mut state), Ok(0));
+    }
+
+    #[test]
+    fn test_check_namespace_rejects_conflicts() {
+        assert!(check_namespace(&[], "bank_cgt", &["balance", "nonce"]).is_ok());
+        assert!(check_namespace(&["bank_cgt"], "bank_cgt", &["balance"]).is_err());
+        assert!(check_namespace(&[], "bank_cgt", &["balance", "balance"]).is_err());
+        assert!(check_namespace(&[], "bank_cgt", &["bal/ance"]).is_err());
+        assert!(check_namespace(&[], "", &["balance"]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Fixed-width integer values in module storage.
+//!
+//! Balances, nonces, and counters are always a single `u64`, so they are
+//! stored as its 8 little-endian bytes and read back with `from_le_bytes`
+//! instead of going through the bincode deserializer. This is byte-for-byte
+//! the layout `codec::encode` gives a `u64` (fixed-width, little-endian), so
+//! values written before these helpers existed read back unchanged; anything
+//! that is not exactly 8 bytes falls back to the bincode decoder.
+
+use crate::core::codec;
+use crate::core::state::State;
+
+/// Read a `u64` stored under `key`, or `None` if the key is missing or the
+/// value does not decode.
+pub fn get_u64(state: &State, key: &[u8]) -> Option<u64> {
+    decode_u64(&state.get_raw(key)?)
+}
+
+/// Decode a stored `u64` value, as read by `get_u64` (e.g. from a prefix
+/// scan).
+pub fn decode_u64(bytes: &[u8]) -> Option<u64> {
+    match <[u8; 8]>::try_from(bytes) {
+        Ok(fixed) => Some(u64::from_le_bytes(fixed)),
+        Err(_) => codec::decode_state::<u64>(bytes).ok(),
+    }
+}
+
+/// The larger of a local and an imported `u64` value, encoded for storage,
+/// for counters a module import must never lower (see
+/// `RuntimeModule::reconcile_import`). A local value that does not decode
+/// counts as missing.
+pub fn raise_u64(local: Option<&[u8]>, imported: &[u8]) -> Result<Vec<u8>, String> {
+    let imported = decode_u64(imported).ok_or("not a u64")?;
+    let value = local
+        .and_then(decode_u64)
+        .map_or(imported, |local| local.max(imported));
+    Ok(value.to_le_bytes().to_vec())
+}
+
+/// Store a `u64` under `key` as 8 little-endian bytes.
+pub fn put_u64(state: &mut State, key: Vec<u8>, value: u64) -> Result<(), String> {
+    state
+        .put_raw(key, value.to_le_bytes().to_vec())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test/counter/";
+
+    #[test]
+    fn test_bincode_u64_is_fixed_width_le() {
+        for value in [0, 1, 0xfe, 1 << 40, u64::MAX] {
+            assert_eq!(codec::encode(&value).unwrap(), value.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_reads_legacy_bincode_values() {
+        let mut state = State::in_memory();
+        for value in [0, 7, u64::MAX] {
+            state
+                .put_raw(KEY.to_vec(), codec::encode(&value).unwrap())
+                .unwrap();
+            assert_eq!(get_u64(&state, KEY), Some(value));
+        }
+
+        put_u64(&mut state, KEY.to_vec(), 42).unwrap();
+        assert_eq!(
+            codec::decode_state::<u64>(&state.get_raw(KEY).unwrap()).unwrap(),
+            42
+        );
+    }
+
+    #[test]
+    fn test_missing_or_short_values() {
+        let mut state = State::in_memory();
+        assert_eq!(get_u64(&state, KEY), None);
+
+        state.put_raw(KEY.to_vec(), vec![1, 2, 3]).unwrap();
+        assert_eq!(get_u64(&state, KEY), None);
+    }
+}
@@ -0,0 +1,687 @@
+//! In-process node harness for end-to-end tests.
+//!
+//! `TestNode` runs a full node on a temporary RocksDB directory, serves
+//! `rpc_router` on an ephemeral localhost port, and talks to it over HTTP the
+//! way any client would, so a test covers RPC → mempool → block → state → RPC.
+//! Blocks are produced on demand with `cgt_getWork` / `cgt_submitWork`; the
+//! default config accepts every nonce so mining is a single round trip.
+//! Senders must be `account`s, whose keys sign everything the harness sends.
+//!
+//! Enabled by the `test-support` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! demiurge-chain = { path = "../chain", features = ["test-support"] }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::config::ChainConfig;
+use crate::core::transaction::{Address, AddressEncoding, Transaction};
+use crate::node::Node;
+use crate::rpc::{json_transaction, rpc_router, SendTransactionParams};
+
+pub use crate::core::transaction::test_keys::{account, signed};
+
+/// Error object returned by a JSON-RPC call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RPC error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// Result of a JSON-RPC call.
+pub type RpcResult<T> = std::result::Result<T, RpcError>;
+
+/// Genesis Archon of `test_config()`.
+pub fn test_archon() -> Address {
+    account(0)
+}
+
+/// Chain config for tests: faucet and `dev_reset` on, every Forge nonce
+/// meets the target, and the genesis Archon is `test_archon()`.
+pub fn test_config() -> ChainConfig {
+    let mut config = ChainConfig::default();
+    config.genesis.difficulty_target = u128::MAX;
+    config.genesis.archons[0].address = test_archon();
+    config.faucet.enabled = true;
+    config.rpc.dev_reset = true;
+    config
+}
+
+/// A full node served over HTTP on an ephemeral port.
+pub struct TestNode {
+    /// The running node, for assertions the RPC surface does not cover.
+    pub node: Arc<Node>,
+    addr: SocketAddr,
+    client: reqwest::Client,
+    dir: TempDir,
+    shutdown: oneshot::Sender<()>,
+    server: JoinHandle<()>,
+    /// Next nonce per sender for `send_transaction`.
+    nonces: Mutex<HashMap<Address, u64>>,
+}
+
+impl TestNode {
+    /// Start a node with `test_config()` on a fresh temporary directory.
+    pub async fn start() -> Result<Self> {
+        Self::start_with_config(test_config()).await
+    }
+
+    /// Start a node with the given config on a fresh temporary directory.
+    pub async fn start_with_config(config: ChainConfig) -> Result<Self> {
+        Self::start_in(tempfile::tempdir()?, config).await
+    }
+
+    async fn start_in(dir: TempDir, config: ChainConfig) -> Result<Self> {
+        let node = Arc::new(Node::with_config(dir.path().join("data"), config)?);
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let app = rpc_router(node.clone());
+
+        let (shutdown, signal) = oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, app)
+                .with_graceful_shutdown(async {
+                    let _ = signal.await;
+                })
+                .await;
+        });
+
+        Ok(Self {
+            node,
+            addr,
+            client: reqwest::Client::new(),
+            dir,
+            shutdown,
+            server,
+            nonces: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Base URL of the JSON-RPC endpoint.
+    pub fn url(&self) -> String {
+        format!("http://{}/rpc", self.addr)
+    }
+
+    /// Stop the server and node, then start a new node on the same data
+    /// directory and config, as if the process had restarted.
+    pub async fn restart(self) -> Result<Self> {
+        let config = self.node.config.clone();
+        let dir = self.stop().await;
+        Self::start_in(dir, config).await
+    }
+
+    /// Stop the server and close the node, keeping its data directory.
+    async fn stop(self) -> TempDir {
+        let _ = self.shutdown.send(());
+        let _ = self.server.await;
+        // The server has dropped its handle, so this closes the database
+        drop(self.node);
+        self.dir
+    }
+
+    /// Call a JSON-RPC method and return its `result`.
+    ///
+    /// # Panics
+    /// If the HTTP request itself fails or the response is not JSON-RPC.
+    pub async fn call(&self, method: &str, params: Value) -> RpcResult<Value> {
+        let body = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+        let resp: Value = self
+            .client
+            .post(self.url())
+            .json(&body)
+            .send()
+            .await
+            .expect("RPC request failed")
+            .json()
+            .await
+            .expect("RPC response is not JSON");
+
+        match resp.get("error") {
+            Some(error) if !error.is_null() => Err(RpcError {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            }),
+            _ => Ok(resp.get("result").cloned().unwrap_or(Value::Null)),
+        }
+    }
+
+    /// Mine one block with the pending mempool transactions; returns its height.
+    pub async fn mine_block(&self) -> RpcResult<u64> {
+        self.get_work().await?;
+        let accepted = self.submit_work(0).await?;
+        Ok(accepted["height"].as_u64().unwrap_or_default())
+    }
+
+    /// Mine `count` blocks; returns the last height.
+    pub async fn mine_blocks(&self, count: u64) -> RpcResult<u64> {
+        let mut height = self.chain_height().await?;
+        for _ in 0..count {
+            height = self.mine_block().await?;
+        }
+        Ok(height)
+    }
+
+    // Chain info
+
+    /// `cgt_getChainInfo`: current chain height.
+    pub async fn chain_height(&self) -> RpcResult<u64> {
+        let info = self.call("cgt_getChainInfo", Value::Null).await?;
+        Ok(info["height"].as_u64().unwrap_or_default())
+    }
+
+    /// `cgt_getTokenInfo`
+    pub async fn token_info(&self) -> RpcResult<Value> {
+        self.call("cgt_getTokenInfo", Value::Null).await
+    }
+
+    /// `cgt_getForgeConfig`
+    pub async fn forge_config(&self) -> RpcResult<Value> {
+        self.call("cgt_getForgeConfig", Value::Null).await
+    }
+
+    /// `runtime_listModules`
+    pub async fn list_modules(&self) -> RpcResult<Vec<Value>> {
+        let result = self.call("runtime_listModules", Value::Null).await?;
+        Ok(array(&result["modules"]))
+    }
+
+    /// `cgt_describeCall`
+    pub async fn describe_call(&self, module_id: &str, call_id: &str) -> RpcResult<Option<Value>> {
+        let result = self
+            .call(
+                "cgt_describeCall",
+                json!({ "module_id": module_id, "call_id": call_id }),
+            )
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `net_getPeers`
+    pub async fn peers(&self) -> RpcResult<Vec<Value>> {
+        let result = self.call("net_getPeers", Value::Null).await?;
+        Ok(array(&result["peers"]))
+    }
+
+    // Wallet
+
+    /// `cgt_getBalance`: balance in base units.
+    pub async fn balance(&self, addr: &Address) -> RpcResult<u64> {
+        let result = self
+            .call("cgt_getBalance", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(result["balance"].as_u64().unwrap_or_default())
+    }
+
+    /// `cgt_isArchon`
+    pub async fn is_archon(&self, addr: &Address) -> RpcResult<bool> {
+        let result = self
+            .call("cgt_isArchon", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(result["is_archon"].as_bool().unwrap_or_default())
+    }
+
+    /// `cgt_accountExists`
+    pub async fn account_exists(&self, addr: &Address) -> RpcResult<bool> {
+        let result = self
+            .call("cgt_accountExists", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(result["exists"].as_bool().unwrap_or_default())
+    }
+
+    /// `cgt_getMultisig`
+    pub async fn multisig(&self, addr: &Address) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_getMultisig", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getAddressStats`
+    pub async fn address_stats(&self, addr: &Address) -> RpcResult<Value> {
+        self.call(
+            "cgt_getAddressStats",
+            json!({ "address": addr.to_display() }),
+        )
+        .await
+    }
+
+    /// `cgt_devFaucet`: returns the address's new balance.
+    pub async fn dev_faucet(&self, addr: &Address) -> RpcResult<u64> {
+        let result = self
+            .call("cgt_devFaucet", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(result["new_balance"].as_u64().unwrap_or_default())
+    }
+
+    // NFTs and marketplace
+
+    /// `cgt_getNftsByOwner`
+    pub async fn nfts_by_owner(&self, owner: &Address) -> RpcResult<Vec<Value>> {
+        let result = self
+            .call(
+                "cgt_getNftsByOwner",
+                json!({ "address": owner.to_display() }),
+            )
+            .await?;
+        Ok(array(&result["nfts"]))
+    }
+
+    /// `cgt_mintDgenNft`: returns the new NFT id.
+    pub async fn mint_dgen_nft(
+        &self,
+        owner: &Address,
+        fabric_root_hash: [u8; 32],
+        name: &str,
+    ) -> RpcResult<u64> {
+        let result = self
+            .call(
+                "cgt_mintDgenNft",
+                json!({
+                    "owner": owner.to_display(),
+                    "fabric_root_hash": hex::encode(fabric_root_hash),
+                    "forge_model_id": null,
+                    "forge_prompt_hash": null,
+                    "name": name,
+                    "description": null,
+                }),
+            )
+            .await?;
+        Ok(result["nft_id"].as_u64().unwrap_or_default())
+    }
+
+    /// `cgt_getListing`
+    pub async fn listing(&self, listing_id: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_getListing", json!({ "listing_id": listing_id }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_previewPurchase`
+    pub async fn preview_purchase(&self, listing_id: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_previewPurchase", json!({ "listing_id": listing_id }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getBundleListing`
+    pub async fn bundle_listing(&self, bundle_id: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_getBundleListing", json!({ "bundle_id": bundle_id }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getRoyaltyStats`, listing up to `limit` payouts after `offset`.
+    pub async fn royalty_stats(&self, addr: &Address, offset: u64, limit: u64) -> RpcResult<Value> {
+        let params = json!({ "address": addr.to_display(), "offset": offset, "limit": limit });
+        self.call("cgt_getRoyaltyStats", params).await
+    }
+
+    /// `cgt_getFabricAsset`
+    pub async fn fabric_asset(&self, fabric_root_hash: [u8; 32]) -> RpcResult<Option<Value>> {
+        let params = json!({ "fabric_root_hash": hex::encode(fabric_root_hash) });
+        Ok(non_null(self.call("cgt_getFabricAsset", params).await?))
+    }
+
+    /// `cgt_getEscrow`
+    pub async fn escrow(&self, escrow_id: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_getEscrow", json!({ "escrow_id": escrow_id }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getStake`
+    pub async fn stake(&self, addr: &Address) -> RpcResult<Value> {
+        self.call("cgt_getStake", json!({ "address": addr.to_display() }))
+            .await
+    }
+
+    // Governance and quests
+
+    /// `gov_getProposal`
+    pub async fn proposal(&self, proposal_id: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("gov_getProposal", json!({ "proposal_id": proposal_id }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `gov_listProposals`
+    pub async fn list_proposals(&self) -> RpcResult<Vec<Value>> {
+        let result = self.call("gov_listProposals", Value::Null).await?;
+        Ok(array(&result["proposals"]))
+    }
+
+    /// `quests_list`
+    pub async fn list_quests(&self) -> RpcResult<Vec<Value>> {
+        let result = self.call("quests_list", Value::Null).await?;
+        Ok(array(&result["quests"]))
+    }
+
+    /// `quests_getProgress`
+    pub async fn quest_progress(&self, addr: &Address) -> RpcResult<Vec<Value>> {
+        let result = self
+            .call(
+                "quests_getProgress",
+                json!({ "address": addr.to_display() }),
+            )
+            .await?;
+        Ok(array(&result["progress"]))
+    }
+
+    // Aeon profiles
+
+    /// `aeon_create`
+    pub async fn aeon_create(&self, addr: &Address, display_name: &str) -> RpcResult<Value> {
+        self.call(
+            "aeon_create",
+            json!({ "address": addr.to_display(), "display_name": display_name, "bio": null }),
+        )
+        .await
+    }
+
+    /// `aeon_get`
+    pub async fn aeon(&self, addr: &Address) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("aeon_get", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `aeon_recordSyzygy` sent by `sender`, the seeding Aeon or one of its
+    /// recorders, with its next nonce; returns the hash.
+    pub async fn record_syzygy(
+        &self,
+        sender: &Address,
+        from: &Address,
+        to: &Address,
+        weight: u64,
+    ) -> RpcResult<[u8; 32]> {
+        let nonce = self.next_nonce(sender);
+        let signature = signature(json!({
+            "from": sender.to_display(),
+            "nonce": nonce,
+            "module_id": "avatars_profiles",
+            "call_id": "record_syzygy",
+            "params": { "from": hex::encode(from), "to": hex::encode(to), "weight": weight },
+        }));
+        let result = self
+            .call(
+                "aeon_recordSyzygy",
+                json!({
+                    "from": from.to_display(),
+                    "to": to.to_display(),
+                    "weight": weight,
+                    "recorder": sender.to_display(),
+                    "nonce": nonce,
+                    "signature": signature,
+                }),
+            )
+            .await?;
+        Ok(hash_field(&result))
+    }
+
+    /// `aeon_getAscension`
+    pub async fn ascension(&self, addr: &Address) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("aeon_getAscension", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `aeon_setHandle`
+    pub async fn set_handle(&self, addr: &Address, handle: &str) -> RpcResult<Value> {
+        self.call(
+            "aeon_setHandle",
+            json!({ "address": addr.to_display(), "handle": handle }),
+        )
+        .await
+    }
+
+    /// `aeon_getByHandle`
+    pub async fn aeon_by_handle(&self, handle: &str) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("aeon_getByHandle", json!({ "handle": handle }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_resolveHandle`
+    pub async fn resolve_handle(&self, handle: &str) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_resolveHandle", json!({ "handle": handle }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getHandle`: the handle `addr` holds, if any.
+    pub async fn handle_of(&self, addr: &Address) -> RpcResult<Option<String>> {
+        let result = self
+            .call("cgt_getHandle", json!({ "address": addr.to_display() }))
+            .await?;
+        Ok(result["handle"].as_str().map(str::to_string))
+    }
+
+    // Transactions and blocks
+
+    /// `cgt_sendTransaction` with the sender's next nonce, signed by the
+    /// sender; returns the hash.
+    ///
+    /// Addresses and hashes in `params` are hex, as for the RPC itself.
+    pub async fn send_transaction(
+        &self,
+        from: &Address,
+        module_id: &str,
+        call_id: &str,
+        params: Value,
+    ) -> RpcResult<[u8; 32]> {
+        let mut tx = json!({
+            "from": from.to_display(),
+            "nonce": self.next_nonce(from),
+            "module_id": module_id,
+            "call_id": call_id,
+            "params": params,
+        });
+        tx["signature"] = json!(signature(tx.clone()));
+        let result = self.call("cgt_sendTransaction", tx).await?;
+        Ok(hash_field(&result))
+    }
+
+    /// Take `from`'s next nonce for a transaction sent through this node.
+    fn next_nonce(&self, from: &Address) -> u64 {
+        let mut nonces = self.nonces.lock().expect("nonces mutex poisoned");
+        let next = nonces.entry(*from).or_insert(0);
+        *next += 1;
+        *next - 1
+    }
+
+    /// `cgt_sendRawTransaction`: returns the hash.
+    pub async fn send_raw_transaction(&self, tx: &Transaction) -> RpcResult<[u8; 32]> {
+        let bytes = tx.to_bytes().expect("transaction encodes");
+        let result = self
+            .call(
+                "cgt_sendRawTransaction",
+                json!({ "tx": hex::encode(bytes) }),
+            )
+            .await?;
+        Ok(hash_field(&result))
+    }
+
+    /// `cgt_getMempool`
+    pub async fn mempool(&self) -> RpcResult<Vec<Value>> {
+        let result = self.call("cgt_getMempool", Value::Null).await?;
+        Ok(array(&result["transactions"]))
+    }
+
+    /// `cgt_getTransactionReceipt`
+    pub async fn receipt(&self, tx_hash: &[u8; 32]) -> RpcResult<Option<Value>> {
+        let result = self
+            .call(
+                "cgt_getTransactionReceipt",
+                json!({ "tx_hash": hex::encode(tx_hash) }),
+            )
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getEvents`
+    pub async fn events(
+        &self,
+        from_height: u64,
+        to_height: u64,
+        module_id: Option<&str>,
+        name: Option<&str>,
+    ) -> RpcResult<Value> {
+        self.call(
+            "cgt_getEvents",
+            json!({
+                "from_height": from_height,
+                "to_height": to_height,
+                "module_id": module_id,
+                "name": name,
+            }),
+        )
+        .await
+    }
+
+    /// `cgt_getTransactionStatus`
+    pub async fn transaction_status(&self, tx_hash: &[u8; 32]) -> RpcResult<Value> {
+        self.call(
+            "cgt_getTransactionStatus",
+            json!({ "tx_hash": hex::encode(tx_hash) }),
+        )
+        .await
+    }
+
+    /// `cgt_getFailedTransaction`
+    pub async fn failed_transaction(&self, tx_hash: &[u8; 32]) -> RpcResult<Option<Value>> {
+        let result = self
+            .call(
+                "cgt_getFailedTransaction",
+                json!({ "tx_hash": hex::encode(tx_hash) }),
+            )
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_purgeFailed`, forgetting one dead-lettered transaction or all of
+    /// them; returns how many were forgotten.
+    pub async fn purge_failed(&self, tx_hash: Option<&[u8; 32]>) -> RpcResult<u64> {
+        let params = match tx_hash {
+            Some(tx_hash) => json!({ "tx_hash": hex::encode(tx_hash) }),
+            None => Value::Null,
+        };
+        let result = self.call("cgt_purgeFailed", params).await?;
+        Ok(result["purged"].as_u64().unwrap_or(0))
+    }
+
+    /// `admin_verifyState`; returns the invariant violations found, if any.
+    pub async fn verify_state(&self) -> RpcResult<Vec<String>> {
+        let result = self.call("admin_verifyState", Value::Null).await?;
+        Ok(serde_json::from_value(result["violations"].clone()).unwrap_or_default())
+    }
+
+    /// `dev_reset`, starting the chain over from genesis; sender nonces
+    /// tracked for `send_transaction` start over too.
+    pub async fn dev_reset(&self, keep_faucet_history: bool) -> RpcResult<Value> {
+        let result = self
+            .call(
+                "dev_reset",
+                json!({ "keep_faucet_history": keep_faucet_history }),
+            )
+            .await?;
+        self.nonces.lock().expect("nonces mutex poisoned").clear();
+        Ok(result)
+    }
+
+    /// `cgt_getBlockByHeight`
+    pub async fn block_by_height(&self, height: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_getBlockByHeight", json!({ "height": height }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_resolveNftContent`
+    pub async fn resolve_nft_content(&self, nft_id: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_resolveNftContent", json!({ "nft_id": nft_id }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getBlockSummary`
+    pub async fn block_summary(&self, height: u64) -> RpcResult<Option<Value>> {
+        let result = self
+            .call("cgt_getBlockSummary", json!({ "height": height }))
+            .await?;
+        Ok(non_null(result))
+    }
+
+    /// `cgt_getLatestBlocks`: summaries of the `count` newest blocks.
+    pub async fn latest_blocks(&self, count: u64) -> RpcResult<Vec<Value>> {
+        let result = self
+            .call("cgt_getLatestBlocks", json!({ "count": count }))
+            .await?;
+        Ok(result.as_array().cloned().unwrap_or_default())
+    }
+
+    /// `cgt_getWork`
+    pub async fn get_work(&self) -> RpcResult<Value> {
+        self.call("cgt_getWork", Value::Null).await
+    }
+
+    /// `cgt_submitWork`
+    pub async fn submit_work(&self, nonce: u64) -> RpcResult<Value> {
+        self.call("cgt_submitWork", json!({ "nonce": nonce })).await
+    }
+}
+
+/// Hex signature by `from` over the `cgt_sendTransaction` params `tx`, or an
+/// empty one if they do not build a transaction, so the node reports why.
+fn signature(tx: Value) -> String {
+    serde_json::from_value::<SendTransactionParams>(tx)
+        .map_err(|e| e.to_string())
+        .and_then(json_transaction)
+        .map(|tx| hex::encode(signed(tx).signature))
+        .unwrap_or_default()
+}
+
+fn non_null(value: Value) -> Option<Value> {
+    (!value.is_null()).then_some(value)
+}
+
+fn array(value: &Value) -> Vec<Value> {
+    value.as_array().cloned().unwrap_or_default()
+}
+
+fn hash_field(result: &Value) -> [u8; 32] {
+    result["hash"]
+        .as_str()
+        .and_then(|h| hex::decode(h).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .expect("RPC result has a 32-byte hex hash")
+}
@@ -0,0 +1,352 @@
+//! WebSocket subscriptions on `/ws`.
+//!
+//! Clients send JSON-RPC requests over the socket and receive pushed
+//! notifications for their subscriptions. Supported methods:
+//! - subscribe_pendingTransactions: Watch mempool admissions, inclusions,
+//!   and evictions, optionally filtered by `module_id`, `call_id`, and sender
+//!   `address`; returns a subscription id
+//! - unsubscribe: Cancel a subscription by id
+//!
+//! Notifications look like
+//! `{"jsonrpc":"2.0","method":"subscription","params":{"subscription":1,"result":{...}}}`,
+//! where `result.type` is `pending`, `included` (with `height`), or
+//! `evicted` (with `reason`). Filters run server-side, so a subscriber only
+//! receives the transactions it asked for.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::Extension;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::config::CorsConfig;
+use crate::core::transaction::{Address, AddressEncoding};
+use crate::node::{MempoolEvent, Node, PendingTx};
+use crate::rpc::{JsonRpcError, JsonRpcRequest, JsonRpcResponse, RpcErrorCode};
+
+/// Parameters of `subscribe_pendingTransactions`; every field is optional.
+#[derive(Debug, Default, Deserialize)]
+pub struct SubscribePendingParams {
+    pub module_id: Option<String>,
+    pub call_id: Option<String>,
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeParams {
+    pub subscription: u64,
+}
+
+/// Which mempool transactions a subscription receives.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PendingTxFilter {
+    pub module_id: Option<String>,
+    pub call_id: Option<String>,
+    /// Sender address.
+    pub address: Option<Address>,
+}
+
+impl PendingTxFilter {
+    /// Build a filter from request parameters.
+    pub fn from_params(params: SubscribePendingParams) -> Result<Self, String> {
+        Ok(Self {
+            module_id: params.module_id,
+            call_id: params.call_id,
+            address: params.address.as_deref().map(Address::parse).transpose()?,
+        })
+    }
+
+    /// Whether `tx` passes every field that is set.
+    pub fn matches(&self, tx: &PendingTx) -> bool {
+        self.module_id.as_ref().is_none_or(|m| *m == tx.module_id)
+            && self.call_id.as_ref().is_none_or(|c| *c == tx.call_id)
+            && self.address.is_none_or(|a| a == tx.from)
+    }
+}
+
+/// Notification body for a mempool event.
+pub fn pending_notification(event: &MempoolEvent) -> Value {
+    let tx = event.tx();
+    let mut result = json!({
+        "tx_hash": hex::encode(tx.tx_hash),
+        "from": tx.from.to_display(),
+        "from_hex": hex::encode(tx.from),
+        "module_id": tx.module_id,
+        "call_id": tx.call_id,
+        "fee": tx.fee,
+    });
+    match event {
+        MempoolEvent::Admitted(_) => result["type"] = json!("pending"),
+        MempoolEvent::Included { height, .. } => {
+            result["type"] = json!("included");
+            result["height"] = json!(height);
+        }
+        MempoolEvent::Evicted { reason, .. } => {
+            result["type"] = json!("evicted");
+            result["reason"] = json!(reason);
+        }
+    }
+    result
+}
+
+/// One connection's subscriptions, keyed by id.
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+    next_id: u64,
+    pending: HashMap<u64, PendingTxFilter>,
+}
+
+impl Subscriptions {
+    /// Apply a JSON-RPC request sent over the socket and build its response.
+    pub fn handle_request(&mut self, text: &str) -> JsonRpcResponse<Value> {
+        let req: JsonRpcRequest<Value> = match serde_json::from_str(text) {
+            Ok(req) => req,
+            Err(e) => {
+                return error_response(
+                    None,
+                    RpcErrorCode::parse_error(format!("parse error: {}", e)),
+                )
+            }
+        };
+        let params = req.params.unwrap_or(Value::Null);
+        let result = match req.method.as_str() {
+            "subscribe_pendingTransactions" => {
+                let params = if params.is_null() {
+                    Ok(SubscribePendingParams::default())
+                } else {
+                    serde_json::from_value(params).map_err(|e| e.to_string())
+                };
+                params
+                    .and_then(PendingTxFilter::from_params)
+                    .map(|filter| {
+                        self.next_id += 1;
+                        self.pending.insert(self.next_id, filter);
+                        json!(self.next_id)
+                    })
+                    .map_err(RpcErrorCode::invalid_params)
+            }
+            "unsubscribe" => serde_json::from_value::<UnsubscribeParams>(params)
+                .map(|p| json!(self.pending.remove(&p.subscription).is_some()))
+                .map_err(|e| RpcErrorCode::invalid_params(e.to_string())),
+            other => Err(RpcErrorCode::method_not_found(format!(
+                "Method not found: {}",
+                other
+            ))),
+        };
+        match result {
+            Ok(result) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(result),
+                error: None,
+                id: req.id,
+            },
+            Err(error) => error_response(req.id, error),
+        }
+    }
+
+    /// Notifications for `event`, one per matching subscription.
+    pub fn notifications(&self, event: &MempoolEvent) -> Vec<Value> {
+        let mut ids: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, filter)| filter.matches(event.tx()))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .map(|id| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "subscription",
+                    "params": { "subscription": id, "result": pending_notification(event) },
+                })
+            })
+            .collect()
+    }
+}
+
+fn error_response(id: Option<Value>, error: JsonRpcError) -> JsonRpcResponse<Value> {
+    JsonRpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: None,
+        error: Some(error),
+        id,
+    }
+}
+
+/// Upgrade `/ws` requests to a subscription socket.
+///
+/// Browsers must connect from an origin in `rpc.cors.allowed_origins`, since
+/// WebSockets are not covered by CORS.
+pub async fn handle_ws(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    Extension(node): Extension<Arc<Node>>,
+) -> Response {
+    if let Some(origin) = headers.get(header::ORIGIN) {
+        let origin = origin.to_str().unwrap_or_default();
+        if !CorsConfig::admits(&node.config.rpc.cors.allowed_origins, origin) {
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+    ws.on_upgrade(move |socket| run_socket(socket, node))
+}
+
+async fn run_socket(mut socket: WebSocket, node: Arc<Node>) {
+    let mut events = node.subscribe_mempool();
+    let mut subscriptions = Subscriptions::default();
+    loop {
+        let outgoing = tokio::select! {
+            msg = socket.recv() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    vec![serde_json::to_value(subscriptions.handle_request(&text))
+                        .unwrap_or(Value::Null)]
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) => subscriptions.notifications(&event),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "mempool subscriber lagged; notifications dropped");
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            },
+        };
+        for value in outgoing {
+            if socket.send(Message::Text(value.to_string())).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ChainConfig;
+    use crate::core::codec;
+    use crate::core::transaction::test_keys::{account, signed};
+    use crate::core::transaction::Transaction;
+    use crate::node::{put_chain_tip, ChainTip};
+    use crate::runtime::bank_cgt::{credit, TransferParams};
+
+    fn transfer(nonce: u64, valid_until_height: Option<u64>) -> Transaction {
+        signed(Transaction {
+            from: account(1),
+            nonce,
+            module_id: "bank_cgt".to_string(),
+            call_id: "transfer".to_string(),
+            payload: codec::encode(&TransferParams {
+                to: [2; 32],
+                amount: 10,
+                memo: None,
+            })
+            .unwrap(),
+            fee: 0,
+            signature: vec![],
+            valid_until_height,
+            sig_scheme: 0,
+        })
+    }
+
+    #[test]
+    fn test_filtered_subscriber_sees_admit_include_evict() {
+        let mut config = ChainConfig::default();
+        config.genesis.difficulty_target = u128::MAX;
+        config.genesis.archons[0].address = account(1);
+        let node = Node::in_memory(config).unwrap();
+        let mut events = node.subscribe_mempool();
+
+        let mut subscriptions = Subscriptions::default();
+        let resp = subscriptions.handle_request(&format!(
+            r#"{{"jsonrpc":"2.0","method":"subscribe_pendingTransactions","params":{{"module_id":"bank_cgt","address":"{}"}},"id":1}}"#,
+            hex::encode(account(1))
+        ));
+        assert_eq!(resp.result, Some(json!(1)));
+
+        let included = transfer(0, None);
+        node.submit_transaction(included.clone()).unwrap();
+        // Another sender's transaction does not reach the subscriber
+        node.with_state_mut(|state| credit(state, &account(9), 10))
+            .unwrap();
+        let mut other = transfer(0, None);
+        other.from = account(9);
+        node.submit_transaction(signed(other)).unwrap();
+        node.block_template();
+        node.submit_work(0).unwrap();
+        let expiring = transfer(1, Some(2));
+        node.submit_transaction(expiring.clone()).unwrap();
+        node.with_state_mut(|state| {
+            put_chain_tip(
+                state,
+                &ChainTip {
+                    height: 2,
+                    hash: [0; 32],
+                },
+            )
+        })
+        .unwrap();
+        assert_eq!(node.sweep_expired_mempool(), 1);
+
+        let mut pushed = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            pushed.extend(subscriptions.notifications(&event));
+        }
+        let kinds: Vec<_> = pushed
+            .iter()
+            .map(|n| {
+                let result = &n["params"]["result"];
+                (
+                    result["type"].as_str().unwrap().to_string(),
+                    result["tx_hash"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        let hash = |tx: &Transaction| hex::encode(tx.hash());
+        assert_eq!(
+            kinds,
+            vec![
+                ("pending".to_string(), hash(&included)),
+                ("included".to_string(), hash(&included)),
+                ("pending".to_string(), hash(&expiring)),
+                ("evicted".to_string(), hash(&expiring)),
+            ]
+        );
+        assert_eq!(pushed[1]["params"]["result"]["height"], 1);
+        assert_eq!(pushed[1]["params"]["subscription"], 1);
+        assert_eq!(pushed[0]["params"]["result"]["module_id"], "bank_cgt");
+
+        // After unsubscribing nothing matches
+        let resp = subscriptions.handle_request(
+            r#"{"jsonrpc":"2.0","method":"unsubscribe","params":{"subscription":1},"id":2}"#,
+        );
+        assert_eq!(resp.result, Some(json!(true)));
+        node.submit_transaction(transfer(2, None)).unwrap();
+        assert!(subscriptions
+            .notifications(&events.try_recv().unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_subscribe_rejects_bad_requests() {
+        let mut subscriptions = Subscriptions::default();
+        let resp = subscriptions.handle_request(
+            r#"{"jsonrpc":"2.0","method":"subscribe_pendingTransactions","params":{"address":"nope"},"id":1}"#,
+        );
+        assert_eq!(resp.error.unwrap().code, -32602);
+        let resp =
+            subscriptions.handle_request(r#"{"jsonrpc":"2.0","method":"subscribe_blocks","id":1}"#);
+        assert_eq!(resp.error.unwrap().code, -32601);
+        assert_eq!(
+            subscriptions.handle_request("not json").error.unwrap().code,
+            -32700
+        );
+    }
+}
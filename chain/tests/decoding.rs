@@ -0,0 +1,282 @@
+//! Property tests for decoding attacker-controlled bytes.
+//!
+//! Raw transactions arrive over `cgt_sendRawTransaction` and their payloads are
+//! decoded by module handlers, so any panic on the way is a remote crash. Each
+//! decoder must return `Ok` or `Err` for arbitrary input, and well-formed
+//! values must round-trip.
+
+use demiurge_chain::config::{ChainConfig, GENESIS_ARCHON_ADDRESS};
+use demiurge_chain::core::block::{Block, BlockHeader};
+use demiurge_chain::core::codec;
+use demiurge_chain::core::transaction::{Address, Transaction};
+use demiurge_chain::node::Node;
+use demiurge_chain::runtime::bank_cgt::{MintToParams, TransferParams};
+use demiurge_chain::runtime::nft_dgen::{MintDgenParams, TransferNftParams};
+use demiurge_chain::runtime::Runtime;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn address() -> impl Strategy<Value = Address> {
+    any::<[u8; 32]>()
+}
+
+fn transaction() -> impl Strategy<Value = Transaction> {
+    (
+        address(),
+        any::<u64>(),
+        "[a-z_]{0,16}",
+        "[a-z_]{0,16}",
+        vec(any::<u8>(), 0..128),
+        any::<u64>(),
+        vec(any::<u8>(), 0..64),
+        any::<Option<u64>>(),
+        any::<u8>(),
+    )
+        .prop_map(
+            |(
+                from,
+                nonce,
+                module_id,
+                call_id,
+                payload,
+                fee,
+                signature,
+                valid_until_height,
+                sig_scheme,
+            )| {
+                Transaction {
+                    from,
+                    nonce,
+                    module_id,
+                    call_id,
+                    payload,
+                    fee,
+                    signature,
+                    valid_until_height,
+                    sig_scheme,
+                }
+            },
+        )
+}
+
+fn block() -> impl Strategy<Value = Block> {
+    (
+        any::<u64>(),
+        any::<[u8; 32]>(),
+        any::<u64>(),
+        any::<u128>(),
+        any::<u64>(),
+        any::<Option<[u8; 32]>>(),
+        vec(transaction(), 0..4),
+    )
+        .prop_map(
+            |(height, prev_hash, timestamp, difficulty_target, nonce, tx_root, body)| Block {
+                header: BlockHeader {
+                    height,
+                    prev_hash,
+                    state_root: [0; 32],
+                    timestamp,
+                    difficulty_target,
+                    nonce,
+                    tx_root,
+                },
+                body,
+            },
+        )
+}
+
+/// Decoding arbitrary bytes as `T` must not panic, and a successful decode
+/// must re-encode to a value that decodes the same way.
+fn decodes_gracefully<T: Serialize + DeserializeOwned>(bytes: &[u8]) {
+    if let Ok(value) = codec::decode::<T>(bytes) {
+        let reencoded = codec::encode(&value).unwrap();
+        let again: T = codec::decode(&reencoded).unwrap();
+        assert_eq!(codec::encode(&again).unwrap(), reencoded);
+    }
+}
+
+/// `decode(encode(x))` re-encodes to the same bytes.
+fn round_trips<T: Serialize + DeserializeOwned>(value: &T) {
+    let bytes = codec::encode(value).unwrap();
+    let decoded: T = codec::decode(&bytes).unwrap();
+    assert_eq!(codec::encode(&decoded).unwrap(), bytes);
+}
+
+proptest! {
+    #[test]
+    fn transaction_decode_never_panics(bytes in vec(any::<u8>(), 0..512)) {
+        let _ = Transaction::from_bytes(&bytes);
+    }
+
+    #[test]
+    fn versioned_transaction_decode_never_panics(
+        version in 0u8..5,
+        body in vec(any::<u8>(), 0..512),
+    ) {
+        let mut bytes = vec![version];
+        bytes.extend(body);
+        let _ = Transaction::from_bytes(&bytes);
+    }
+
+    #[test]
+    fn transaction_round_trips(tx in transaction()) {
+        let bytes = tx.to_bytes().unwrap();
+        prop_assert_eq!(Transaction::from_bytes(&bytes).unwrap(), tx.clone());
+        prop_assert_eq!(Transaction::from_bytes(&bytes).unwrap().hash(), tx.hash());
+    }
+
+    #[test]
+    fn params_decode_never_panics(bytes in vec(any::<u8>(), 0..256)) {
+        decodes_gracefully::<TransferParams>(&bytes);
+        decodes_gracefully::<MintToParams>(&bytes);
+        decodes_gracefully::<MintDgenParams>(&bytes);
+        decodes_gracefully::<TransferNftParams>(&bytes);
+    }
+
+    #[test]
+    fn params_round_trip(
+        to in address(),
+        amount in any::<u64>(),
+        hashes in any::<([u8; 32], Option<[u8; 32]>, Option<[u8; 32]>, Option<Address>)>(),
+        royalty_bps in any::<u16>(),
+        uri_hint in any::<Option<String>>(),
+        memo in any::<Option<Vec<u8>>>(),
+    ) {
+        round_trips(&TransferParams { to, amount, memo });
+        round_trips(&MintToParams { to, amount });
+        round_trips(&TransferNftParams { token_id: amount, to });
+        let (fabric_root_hash, forge_model_id, forge_prompt_hash, royalty_recipient) = hashes;
+        round_trips(&MintDgenParams {
+            fabric_root_hash,
+            forge_model_id,
+            forge_prompt_hash,
+            royalty_recipient,
+            royalty_bps,
+            uri_hint,
+            royalty_splits: Vec::new(),
+        });
+    }
+
+    #[test]
+    fn block_decode_never_panics(bytes in vec(any::<u8>(), 0..1024)) {
+        decodes_gracefully::<Block>(&bytes);
+    }
+
+    #[test]
+    fn block_round_trips(block in block()) {
+        let bytes = codec::encode(&block).unwrap();
+        prop_assert_eq!(codec::decode_state::<Block>(&bytes).unwrap(), block.clone());
+        let bytes = codec::encode_versioned(&block).unwrap();
+        prop_assert_eq!(codec::decode_versioned::<Block>(&bytes).unwrap(), block);
+    }
+
+    /// Sequences of calls to every default module, with arbitrary payloads,
+    /// return errors or succeed; they never panic.
+    #[test]
+    fn dispatch_never_panics(
+        txs in vec((any::<prop::sample::Index>(), 0usize..3, vec(any::<u8>(), 0..160)), 1..6),
+        from in address(),
+    ) {
+        let node = Node::in_memory(ChainConfig::default()).unwrap();
+        let runtime = Runtime::for_chain(&node.config);
+        let calls: Vec<(&str, &str)> = runtime
+            .modules()
+            .into_iter()
+            .flat_map(|m| m.call_ids.iter().map(move |c| (m.module_id, *c)))
+            .collect();
+        // The Genesis Archon and the mint authority reach the privileged paths
+        let senders = [GENESIS_ARCHON_ADDRESS, [0; 32], from];
+
+        node.with_state_mut(|state| {
+            for (height, (call, sender, payload)) in txs.into_iter().enumerate() {
+                let (module_id, call_id) = calls[call.index(calls.len())];
+                let tx = Transaction {
+                    from: senders[sender],
+                    nonce: 0,
+                    module_id: module_id.to_string(),
+                    call_id: call_id.to_string(),
+                    payload,
+                    fee: 0,
+                    signature: vec![],
+                    valid_until_height: None,
+                    sig_scheme: 0,
+                };
+                state.set_block_height(height as u64 + 1);
+                let _ = runtime.dispatch_tx(&tx, state);
+                let _ = runtime.end_block(state);
+            }
+        });
+    }
+}
+
+/// Length prefixes claiming far more data than the input holds are rejected
+/// without allocating for them.
+#[test]
+fn oversized_length_prefixes_are_rejected() {
+    // V2 transaction whose module_id claims u64::MAX bytes
+    let mut bytes = vec![2u8];
+    bytes.extend([0u8; 32]); // from
+    bytes.extend(0u64.to_le_bytes()); // nonce
+    bytes.extend(u64::MAX.to_le_bytes()); // module_id length
+    bytes.extend(b"bank_cgt");
+    assert!(Transaction::from_bytes(&bytes).is_err());
+
+    // Block whose body claims u64::MAX transactions
+    let header = codec::encode(&BlockHeader {
+        height: 1,
+        prev_hash: [0; 32],
+        state_root: [0; 32],
+        timestamp: 0,
+        difficulty_target: 0,
+        nonce: 0,
+        tx_root: None,
+    })
+    .unwrap();
+    let mut bytes = header;
+    bytes.extend(u64::MAX.to_le_bytes());
+    assert!(codec::decode_state::<Block>(&bytes).is_err());
+}
+
+/// Every call that reads a payload rejects one made of huge length prefixes
+/// (and out-of-range tags), rather than allocating for them.
+#[test]
+fn length_prefix_bombs_fail_for_every_call() {
+    // These calls take no params and never look at the payload
+    const PAYLOADLESS: [&str; 5] = [
+        "claim_archon",
+        "delete_aeon_profile",
+        "withdraw_unbonded",
+        "claim_rewards",
+        "withdraw",
+    ];
+    let node = Node::in_memory(ChainConfig::default()).unwrap();
+    let runtime = Runtime::for_chain(&node.config);
+    let bomb = u64::MAX.to_le_bytes().repeat(32);
+
+    for module in runtime.modules() {
+        for call_id in module.call_ids {
+            let tx = Transaction {
+                from: GENESIS_ARCHON_ADDRESS,
+                nonce: 0,
+                module_id: module.module_id.to_string(),
+                call_id: call_id.to_string(),
+                payload: bomb.clone(),
+                fee: 0,
+                signature: vec![],
+                valid_until_height: None,
+                sig_scheme: 0,
+            };
+            let result = node.with_state_mut(|state| runtime.dispatch_tx(&tx, state));
+            if !PAYLOADLESS.contains(call_id) {
+                assert!(
+                    result.is_err(),
+                    "{}::{} accepted a bomb",
+                    module.module_id,
+                    call_id
+                );
+            }
+        }
+    }
+}
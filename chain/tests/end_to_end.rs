@@ -0,0 +1,745 @@
+//! End-to-end scenarios against an in-process node over JSON-RPC.
+
+use demiurge_chain::core::transaction::Address;
+use demiurge_chain::test_support::{account, test_archon, TestNode};
+use serde_json::json;
+
+fn nomad() -> Address {
+    account(0x11)
+}
+
+fn collector() -> Address {
+    account(0x22)
+}
+
+#[tokio::test]
+async fn faucet_transfer_balance() {
+    let node = TestNode::start().await.unwrap();
+    let claimed = node.dev_faucet(&nomad()).await.unwrap();
+    assert_eq!(claimed, node.node.config.faucet.amount);
+    assert!(!node.account_exists(&collector()).await.unwrap());
+
+    let hash = node
+        .send_transaction(
+            &nomad(),
+            "bank_cgt",
+            "transfer",
+            json!({ "to": hex::encode(collector()), "amount": 2_500 }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        node.transaction_status(&hash).await.unwrap()["status"],
+        "pending"
+    );
+    assert_eq!(node.mempool().await.unwrap().len(), 1);
+
+    assert_eq!(node.mine_block().await.unwrap(), 1);
+    assert_eq!(node.balance(&nomad()).await.unwrap(), claimed - 2_500);
+    assert_eq!(node.balance(&collector()).await.unwrap(), 2_500);
+    assert!(node.account_exists(&collector()).await.unwrap());
+    assert!(node.mempool().await.unwrap().is_empty());
+
+    let receipt = node.receipt(&hash).await.unwrap().unwrap();
+    assert_eq!(receipt["height"], 1);
+    assert_eq!(receipt["success"], true);
+}
+
+#[tokio::test]
+async fn archon_mints_and_transfers_nft() {
+    let node = TestNode::start().await.unwrap();
+    node.dev_faucet(&nomad()).await.unwrap();
+
+    // Minting is refused until the address claims Archon status
+    let err = node
+        .mint_dgen_nft(&nomad(), [7; 32], "Relic")
+        .await
+        .unwrap_err();
+    assert!(err.message.contains("only Archons"), "{}", err);
+
+    node.send_transaction(&nomad(), "avatars_profiles", "claim_archon", json!(null))
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    assert!(node.is_archon(&nomad()).await.unwrap());
+
+    let nft_id = node
+        .mint_dgen_nft(&nomad(), [7; 32], "Relic")
+        .await
+        .unwrap();
+    let owned = node.nfts_by_owner(&nomad()).await.unwrap();
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0]["id"], nft_id);
+
+    node.send_transaction(
+        &nomad(),
+        "nft_dgen",
+        "transfer_nft",
+        json!({ "token_id": nft_id, "to": hex::encode(collector()) }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+
+    assert!(node.nfts_by_owner(&nomad()).await.unwrap().is_empty());
+    let owned = node.nfts_by_owner(&collector()).await.unwrap();
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0]["id"], nft_id);
+    assert_eq!(owned[0]["creator_hex"], hex::encode(nomad()));
+}
+
+#[tokio::test]
+async fn address_stats_follow_scripted_history() {
+    let node = TestNode::start().await.unwrap();
+    let claimed = node.dev_faucet(&nomad()).await.unwrap();
+    node.send_transaction(
+        &nomad(),
+        "bank_cgt",
+        "transfer",
+        json!({ "to": hex::encode(collector()), "amount": 2_500 }),
+    )
+    .await
+    .unwrap();
+    node.send_transaction(&nomad(), "avatars_profiles", "claim_archon", json!(null))
+        .await
+        .unwrap();
+    assert_eq!(node.mine_block().await.unwrap(), 1);
+
+    for root in [[7; 32], [8; 32]] {
+        node.send_transaction(
+            &nomad(),
+            "nft_dgen",
+            "mint_dgen",
+            json!({ "fabric_root_hash": hex::encode(root) }),
+        )
+        .await
+        .unwrap();
+    }
+    node.mine_block().await.unwrap();
+
+    let nft_id = node.nfts_by_owner(&nomad()).await.unwrap()[0]["id"].clone();
+    node.send_transaction(
+        &nomad(),
+        "nft_dgen",
+        "transfer_nft",
+        json!({ "token_id": nft_id, "to": hex::encode(collector()) }),
+    )
+    .await
+    .unwrap();
+    node.send_transaction(
+        &collector(),
+        "bank_cgt",
+        "transfer",
+        json!({ "to": hex::encode(nomad()), "amount": 500 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+
+    // The faucet mints outside any transaction, so it is not counted
+    let nomad_stats = node.address_stats(&nomad()).await.unwrap();
+    assert_eq!(nomad_stats["first_seen_height"], 1);
+    assert_eq!(nomad_stats["txs_sent"], 5);
+    assert_eq!(nomad_stats["cgt_sent"], 2_500);
+    assert_eq!(nomad_stats["cgt_received"], 500);
+    assert_eq!(nomad_stats["nfts_minted"], 2);
+    assert_eq!(nomad_stats["nfts_owned"], 1);
+    assert_eq!(node.balance(&nomad()).await.unwrap(), claimed - 2_000);
+
+    let collector_stats = node.address_stats(&collector()).await.unwrap();
+    assert_eq!(collector_stats["first_seen_height"], 1);
+    assert_eq!(collector_stats["txs_sent"], 1);
+    assert_eq!(collector_stats["cgt_sent"], 500);
+    assert_eq!(collector_stats["cgt_received"], 2_500);
+    assert_eq!(collector_stats["nfts_minted"], 0);
+    assert_eq!(collector_stats["nfts_owned"], 1);
+
+    let stranger = node.address_stats(&[0x33; 32]).await.unwrap();
+    assert!(stranger["first_seen_height"].is_null());
+    assert_eq!(stranger["txs_sent"], 0);
+    assert_eq!(stranger["nfts_owned"], 0);
+}
+
+#[tokio::test]
+async fn aeon_syzygy_raises_ascension() {
+    let node = TestNode::start().await.unwrap();
+    node.aeon_create(&nomad(), "Seeder").await.unwrap();
+    node.aeon_create(&collector(), "Author").await.unwrap();
+
+    let before = node.ascension(&nomad()).await.unwrap().unwrap();
+    assert_eq!(before["syzygy_score"], 0);
+
+    node.record_syzygy(&nomad(), &nomad(), &collector(), 400)
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+
+    let after = node.ascension(&nomad()).await.unwrap().unwrap();
+    assert_eq!(after["syzygy_score"], 400);
+    assert_eq!(after["gnosis_xp"], 200);
+    assert_eq!(after["ascension_level"], 2);
+
+    // Only the seeding Aeon is credited
+    let author = node.ascension(&collector()).await.unwrap().unwrap();
+    assert_eq!(author["syzygy_score"], 0);
+    assert!(node.ascension(&[0x33; 32]).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn transfer_to_handle_resolves_at_inclusion() {
+    let node = TestNode::start().await.unwrap();
+    node.dev_faucet(&nomad()).await.unwrap();
+    node.aeon_create(&collector(), "Orpheus").await.unwrap();
+    node.set_handle(&collector(), "orpheus").await.unwrap();
+
+    let resolved = node.resolve_handle("@Orpheus").await.unwrap().unwrap();
+    assert_eq!(resolved["handle"], "orpheus");
+    assert_eq!(resolved["address_hex"], hex::encode(collector()));
+    assert_eq!(
+        node.handle_of(&collector()).await.unwrap().as_deref(),
+        Some("orpheus")
+    );
+    assert_eq!(node.handle_of(&nomad()).await.unwrap(), None);
+    let starting = node.balance(&collector()).await.unwrap();
+
+    let sender = nomad();
+    let send = |amount: u64| {
+        node.send_transaction(
+            &sender,
+            "bank_cgt",
+            "transfer_to_handle",
+            json!({
+                "handle": "@Orpheus",
+                "amount": amount,
+                "resolved_to": hex::encode(collector()),
+            }),
+        )
+    };
+    let paid = send(100).await.unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(node.receipt(&paid).await.unwrap().unwrap()["success"], true);
+    assert_eq!(node.balance(&collector()).await.unwrap(), starting + 100);
+
+    // The handle is released between submission and inclusion
+    let stranded = send(200).await.unwrap();
+    node.set_handle(&collector(), "eurydice").await.unwrap();
+    assert!(node.resolve_handle("orpheus").await.unwrap().is_none());
+    node.mine_block().await.unwrap();
+
+    let failure = node.failed_transaction(&stranded).await.unwrap().unwrap();
+    let reason = failure["reason"].as_str().unwrap();
+    assert!(
+        reason.contains("@orpheus is no longer registered"),
+        "{}",
+        reason
+    );
+    assert_eq!(node.balance(&collector()).await.unwrap(), starting + 100);
+}
+
+#[tokio::test]
+async fn syzygy_recording_needs_seeder_authorization() {
+    let node = TestNode::start().await.unwrap();
+    node.aeon_create(&nomad(), "Seeder").await.unwrap();
+    node.aeon_create(&collector(), "Author").await.unwrap();
+    let score =
+        || async { node.ascension(&nomad()).await.unwrap().unwrap()["syzygy_score"].clone() };
+
+    // Another address cannot inflate the seeder's score: its recording
+    // would fail, so blocks leave it out and it is dead-lettered
+    let hash = node
+        .record_syzygy(&account(0x33), &nomad(), &collector(), 500)
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(
+        node.transaction_status(&hash).await.unwrap()["status"],
+        "dead_lettered"
+    );
+    assert!(node.failed_transaction(&hash).await.unwrap().is_some());
+    assert_eq!(score().await, 0);
+
+    // Once the seeder grants it the recorder role, a new recording goes through
+    node.send_transaction(
+        &nomad(),
+        "avatars_profiles",
+        "grant_recorder",
+        json!({ "recorder": hex::encode(collector()) }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    let hash = node
+        .record_syzygy(&collector(), &nomad(), &collector(), 500)
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(node.receipt(&hash).await.unwrap().unwrap()["success"], true);
+    assert_eq!(score().await, 500);
+
+    // Revoking the role takes it away again
+    node.send_transaction(
+        &nomad(),
+        "avatars_profiles",
+        "revoke_recorder",
+        json!({ "recorder": hex::encode(collector()) }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    node.record_syzygy(&collector(), &nomad(), &collector(), 500)
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(score().await, 500);
+}
+
+#[tokio::test]
+async fn listing_lifecycle() {
+    let node = TestNode::start().await.unwrap();
+    let seller = test_archon();
+    let first = node.mint_dgen_nft(&seller, [1; 32], "First").await.unwrap();
+    let second = node
+        .mint_dgen_nft(&seller, [2; 32], "Second")
+        .await
+        .unwrap();
+    node.dev_faucet(&collector()).await.unwrap();
+
+    for token_id in [first, second] {
+        node.send_transaction(
+            &seller,
+            "abyss_registry",
+            "create_listing",
+            json!({ "token_id": token_id, "price_cgt": 5_000 }),
+        )
+        .await
+        .unwrap();
+    }
+    node.mine_block().await.unwrap();
+    let listing = node.listing(0).await.unwrap().unwrap();
+    assert_eq!(listing["token_id"], first);
+    assert_eq!(listing["active"], true);
+    assert_eq!(node.listing(1).await.unwrap().unwrap()["active"], true);
+
+    // Without a royalty or marketplace fee, the seller would get it all
+    let preview = node.preview_purchase(0).await.unwrap().unwrap();
+    assert_eq!(preview["price"], 5_000);
+    assert_eq!(preview["seller_amount"], 5_000);
+    assert_eq!(preview["royalty_amount"], 0);
+    assert_eq!(preview["royalties"], json!([]));
+    assert_eq!(preview["protocol_fee"], 0);
+    assert!(node.preview_purchase(9).await.unwrap().is_none());
+
+    // The marketplace holds listed NFTs, but the seller still sees them
+    let listed: Vec<_> = node
+        .nfts_by_owner(&seller)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|nft| nft["listed"] == true)
+        .collect();
+    assert_eq!(listed.len(), 2);
+    assert_eq!(listed[0]["id"], first);
+    assert_eq!(listed[0]["listing_id"], 0);
+    assert_ne!(listed[0]["owner_hex"], hex::encode(seller));
+
+    // Sell the first listing and cancel the second
+    let seller_balance = node.balance(&seller).await.unwrap();
+    let buyer_balance = node.balance(&collector()).await.unwrap();
+    node.send_transaction(
+        &collector(),
+        "abyss_registry",
+        "buy_listing",
+        json!({ "listing_id": 0 }),
+    )
+    .await
+    .unwrap();
+    node.send_transaction(
+        &seller,
+        "abyss_registry",
+        "cancel_listing",
+        json!({ "listing_id": 1 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+
+    assert_eq!(node.listing(0).await.unwrap().unwrap()["active"], false);
+    assert_eq!(node.listing(1).await.unwrap().unwrap()["active"], false);
+    assert!(node.preview_purchase(0).await.is_err());
+    assert_eq!(node.balance(&seller).await.unwrap(), seller_balance + 5_000);
+    assert_eq!(
+        node.balance(&collector()).await.unwrap(),
+        buyer_balance - 5_000
+    );
+    let owned = node.nfts_by_owner(&collector()).await.unwrap();
+    assert_eq!(owned.len(), 1);
+    assert_eq!(owned[0]["id"], first);
+
+    // An inactive listing cannot be bought; the failure lands in a receipt
+    // only if a block includes it, and templates dead-letter failing calls
+    let hash = node
+        .send_transaction(
+            &collector(),
+            "abyss_registry",
+            "buy_listing",
+            json!({ "listing_id": 1 }),
+        )
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(
+        node.transaction_status(&hash).await.unwrap()["status"],
+        "dead_lettered"
+    );
+    let failure = node.failed_transaction(&hash).await.unwrap().unwrap();
+    assert_eq!(failure["attempts"], 1);
+    assert_eq!(node.purge_failed(Some(&hash)).await.unwrap(), 1);
+    assert_eq!(node.nfts_by_owner(&seller).await.unwrap()[0]["id"], second);
+    assert_eq!(node.verify_state().await.unwrap(), Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn bundle_listing_sells_as_one_lot() {
+    let node = TestNode::start().await.unwrap();
+    let seller = test_archon();
+    let mut tokens = Vec::new();
+    for (i, name) in ["Sun", "Moon", "Star"].into_iter().enumerate() {
+        let root = [10 + i as u8; 32];
+        tokens.push(node.mint_dgen_nft(&seller, root, name).await.unwrap());
+    }
+    node.dev_faucet(&collector()).await.unwrap();
+
+    node.send_transaction(
+        &seller,
+        "abyss_registry",
+        "create_bundle_listing",
+        json!({ "token_ids": tokens, "price_cgt": 9_000, "allocation": [5_000, 3_000, 1_000] }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    let bundle = node.bundle_listing(0).await.unwrap().unwrap();
+    assert_eq!(bundle["token_ids"], json!(tokens));
+    assert_eq!(bundle["active"], true);
+    let parts: Vec<_> = bundle["breakdown"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|split| (split["token_id"].clone(), split["seller_amount"].clone()))
+        .collect();
+    assert_eq!(
+        parts,
+        vec![
+            (json!(tokens[0]), json!(5_000)),
+            (json!(tokens[1]), json!(3_000)),
+            (json!(tokens[2]), json!(1_000)),
+        ]
+    );
+    assert!(node.bundle_listing(9).await.unwrap().is_none());
+
+    // The seller still sees the bundled NFTs
+    let bundled: Vec<_> = node
+        .nfts_by_owner(&seller)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|nft| nft["bundle_id"] == 0)
+        .collect();
+    assert_eq!(bundled.len(), 3);
+    assert!(bundled.iter().all(|nft| nft["listed"] == true));
+
+    let seller_balance = node.balance(&seller).await.unwrap();
+    node.send_transaction(
+        &collector(),
+        "abyss_registry",
+        "buy_bundle_listing",
+        json!({ "bundle_id": 0 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(
+        node.bundle_listing(0).await.unwrap().unwrap()["active"],
+        false
+    );
+    assert_eq!(node.balance(&seller).await.unwrap(), seller_balance + 9_000);
+    let owned: Vec<_> = node
+        .nfts_by_owner(&collector())
+        .await
+        .unwrap()
+        .iter()
+        .map(|nft| nft["id"].clone())
+        .collect();
+    assert_eq!(owned, tokens.iter().map(|id| json!(id)).collect::<Vec<_>>());
+    assert_eq!(node.verify_state().await.unwrap(), Vec::<String>::new());
+}
+
+#[tokio::test]
+async fn royalty_stats_sum_listing_and_bundle_payouts() {
+    let node = TestNode::start().await.unwrap();
+    let seller = test_archon();
+    for root in [[20; 32], [21; 32], [22; 32]] {
+        node.send_transaction(
+            &seller,
+            "nft_dgen",
+            "mint_dgen",
+            json!({
+                "fabric_root_hash": hex::encode(root),
+                "royalty_recipient": hex::encode(nomad()),
+                "royalty_bps": 1_000,
+            }),
+        )
+        .await
+        .unwrap();
+    }
+    node.dev_faucet(&collector()).await.unwrap();
+    node.mine_block().await.unwrap();
+
+    // A listing of NFT 0 and a bundle of NFTs 1 and 2, sold in turn
+    node.send_transaction(
+        &seller,
+        "abyss_registry",
+        "create_listing",
+        json!({ "token_id": 0, "price_cgt": 5_000 }),
+    )
+    .await
+    .unwrap();
+    node.send_transaction(
+        &seller,
+        "abyss_registry",
+        "create_bundle_listing",
+        json!({ "token_ids": [1, 2], "price_cgt": 8_000 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    node.send_transaction(
+        &collector(),
+        "abyss_registry",
+        "buy_listing",
+        json!({ "listing_id": 0 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    node.send_transaction(
+        &collector(),
+        "abyss_registry",
+        "buy_bundle_listing",
+        json!({ "bundle_id": 1 }),
+    )
+    .await
+    .unwrap();
+    let height = node.mine_block().await.unwrap();
+
+    // 10% of the listing's 5,000 and of each bundled NFT's 4,000
+    let stats = node.royalty_stats(&nomad(), 0, 10).await.unwrap();
+    assert_eq!(stats["total_amount"], 500 + 400 + 400);
+    assert_eq!(stats["sale_count"], 3);
+    assert_eq!(stats["last_height"], height);
+    assert_eq!(node.balance(&nomad()).await.unwrap(), 1_300);
+    let payouts: Vec<_> = stats["payouts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|payout| {
+            let attributes = &payout["attributes"];
+            (attributes["token_id"].clone(), attributes["amount"].clone())
+        })
+        .collect();
+    assert_eq!(
+        payouts,
+        vec![
+            (json!("2"), json!("400")),
+            (json!("1"), json!("400")),
+            (json!("0"), json!("500")),
+        ]
+    );
+
+    let page = node.royalty_stats(&nomad(), 1, 1).await.unwrap();
+    let page = page["payouts"].as_array().unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0]["attributes"]["token_id"], "1");
+    assert_eq!(page[0]["attributes"]["bundle_id"], "1");
+    assert_eq!(page[0]["attributes"]["amount_display"], "0.000004");
+
+    let none = node.royalty_stats(&collector(), 0, 10).await.unwrap();
+    assert_eq!(none["total_amount"], 0);
+    assert_eq!(none["payouts"], json!([]));
+}
+
+#[tokio::test]
+async fn private_listing_with_updated_price() {
+    let node = TestNode::start().await.unwrap();
+    let seller = test_archon();
+    let token_id = node.mint_dgen_nft(&seller, [3; 32], "Relic").await.unwrap();
+    node.dev_faucet(&nomad()).await.unwrap();
+    node.dev_faucet(&collector()).await.unwrap();
+
+    node.send_transaction(
+        &seller,
+        "abyss_registry",
+        "create_listing",
+        json!({
+            "token_id": token_id,
+            "price_cgt": 5_000,
+            "allowed_buyer": hex::encode(collector()),
+        }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    node.send_transaction(
+        &seller,
+        "abyss_registry",
+        "update_listing_price",
+        json!({ "listing_id": 0, "price_cgt": 3_000 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    let listing = node.listing(0).await.unwrap().unwrap();
+    assert_eq!(listing["price_cgt"], 3_000);
+    assert_eq!(listing["allowed_buyer_hex"], hex::encode(collector()));
+
+    // A third party cannot snipe the negotiated sale
+    let hash = node
+        .send_transaction(
+            &nomad(),
+            "abyss_registry",
+            "buy_listing",
+            json!({ "listing_id": 0 }),
+        )
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(
+        node.transaction_status(&hash).await.unwrap()["status"],
+        "dead_lettered"
+    );
+    assert_eq!(node.listing(0).await.unwrap().unwrap()["active"], true);
+
+    // The allowlisted buyer pays the updated price
+    let buyer_balance = node.balance(&collector()).await.unwrap();
+    node.send_transaction(
+        &collector(),
+        "abyss_registry",
+        "buy_listing",
+        json!({ "listing_id": 0 }),
+    )
+    .await
+    .unwrap();
+    node.mine_block().await.unwrap();
+    assert_eq!(
+        node.balance(&collector()).await.unwrap(),
+        buyer_balance - 3_000
+    );
+    assert_eq!(
+        node.nfts_by_owner(&collector()).await.unwrap()[0]["id"],
+        token_id
+    );
+    assert_eq!(node.listing(0).await.unwrap().unwrap()["active"], false);
+}
+
+#[tokio::test]
+async fn state_persists_across_restart() {
+    let node = TestNode::start().await.unwrap();
+    node.dev_faucet(&nomad()).await.unwrap();
+    let hash = node
+        .send_transaction(
+            &nomad(),
+            "bank_cgt",
+            "transfer",
+            json!({ "to": hex::encode(collector()), "amount": 700 }),
+        )
+        .await
+        .unwrap();
+    node.mine_block().await.unwrap();
+    let nft_id = node
+        .mint_dgen_nft(&test_archon(), [9; 32], "Keepsake")
+        .await
+        .unwrap();
+    let nomad_balance = node.balance(&nomad()).await.unwrap();
+    let genesis = node.block_by_height(0).await.unwrap().unwrap();
+    let block = node.block_by_height(1).await.unwrap().unwrap();
+    assert_eq!(block["header"]["prev_hash"], genesis["hash"]);
+    assert_eq!(block["header"]["difficulty_target"], u128::MAX.to_string());
+    assert_eq!(block["transactions"][0]["hash"], hex::encode(hash));
+
+    let node = node.restart().await.unwrap();
+    assert_eq!(node.chain_height().await.unwrap(), 1);
+    assert_eq!(node.balance(&nomad()).await.unwrap(), nomad_balance);
+    assert_eq!(node.balance(&collector()).await.unwrap(), 700);
+    assert_eq!(
+        node.nfts_by_owner(&test_archon()).await.unwrap()[0]["id"],
+        nft_id
+    );
+    assert_eq!(node.block_by_height(0).await.unwrap().unwrap(), genesis);
+    assert_eq!(node.block_by_height(1).await.unwrap().unwrap(), block);
+    assert_eq!(node.receipt(&hash).await.unwrap().unwrap()["success"], true);
+
+    // Faucet claim history survives too
+    let err = node.dev_faucet(&nomad()).await.unwrap_err();
+    assert_eq!(err.code, -32003);
+
+    // Mining resumes on top of the restored tip
+    assert_eq!(node.mine_block().await.unwrap(), 2);
+    let next = node.block_by_height(2).await.unwrap().unwrap();
+    assert_eq!(next["header"]["prev_hash"], block["hash"]);
+}
+
+#[tokio::test]
+async fn dev_reset_restores_genesis() {
+    let node = TestNode::start().await.unwrap();
+    let genesis_balance = node.balance(&test_archon()).await.unwrap();
+    let genesis = node.block_by_height(0).await.unwrap().unwrap();
+    node.dev_faucet(&nomad()).await.unwrap();
+    node.send_transaction(
+        &nomad(),
+        "bank_cgt",
+        "transfer",
+        json!({ "to": hex::encode(collector()), "amount": 700 }),
+    )
+    .await
+    .unwrap();
+    node.mine_blocks(2).await.unwrap();
+    node.mint_dgen_nft(&test_archon(), [9; 32], "Keepsake")
+        .await
+        .unwrap();
+    node.send_transaction(
+        &nomad(),
+        "bank_cgt",
+        "transfer",
+        json!({ "to": hex::encode(collector()), "amount": 1 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(node.mempool().await.unwrap().len(), 1);
+
+    let reset = node.dev_reset(false).await.unwrap();
+    assert_eq!(reset["height"], 0);
+    assert_eq!(reset["genesis_hash"], genesis["hash"]);
+    assert_eq!(node.chain_height().await.unwrap(), 0);
+    assert_eq!(node.balance(&test_archon()).await.unwrap(), genesis_balance);
+    assert!(node.is_archon(&test_archon()).await.unwrap());
+    assert_eq!(node.balance(&nomad()).await.unwrap(), 0);
+    assert!(!node.account_exists(&collector()).await.unwrap());
+    assert!(node.nfts_by_owner(&test_archon()).await.unwrap().is_empty());
+    assert!(node.mempool().await.unwrap().is_empty());
+    assert_eq!(node.block_by_height(0).await.unwrap().unwrap(), genesis);
+    assert_eq!(node.block_by_height(1).await.unwrap(), None);
+    assert!(node.verify_state().await.unwrap().is_empty());
+
+    // The faucet and mining start over on the new chain
+    node.dev_faucet(&nomad()).await.unwrap();
+    node.send_transaction(
+        &nomad(),
+        "bank_cgt",
+        "transfer",
+        json!({ "to": hex::encode(collector()), "amount": 700 }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(node.mine_block().await.unwrap(), 1);
+    assert_eq!(node.balance(&collector()).await.unwrap(), 700);
+}
@@ -0,0 +1,67 @@
+//! JSON-RPC over TLS with a self-signed fixture certificate.
+//!
+//! The fixtures under `tests/fixtures/tls/` are a certificate for `localhost`
+//! and `127.0.0.1` with its key, plus an unrelated key for mismatch checks.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use demiurge_chain::config::ChainConfig;
+use demiurge_chain::node::Node;
+use demiurge_chain::rpc::{load_tls, rpc_router, serve};
+use serde_json::{json, Value};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
+#[tokio::test]
+async fn rpc_call_over_tls() {
+    let tls = load_tls(&fixture("cert.pem"), &fixture("key.pem")).unwrap();
+    let node = Arc::new(Node::in_memory(ChainConfig::default()).unwrap());
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve(listener, rpc_router(node), Some(tls)));
+
+    let cert = std::fs::read(fixture("cert.pem")).unwrap();
+    let client = reqwest::Client::builder()
+        .add_root_certificate(reqwest::Certificate::from_pem(&cert).unwrap())
+        .resolve("localhost", addr)
+        .build()
+        .unwrap();
+    let resp: Value = client
+        .post(format!("https://localhost:{}/rpc", addr.port()))
+        .json(&json!({ "jsonrpc": "2.0", "method": "cgt_getChainInfo", "id": 1 }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(resp["result"]["height"], 0);
+
+    // The listener does not speak plain HTTP
+    let plain = reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{}/rpc", addr.port()))
+        .json(&json!({ "jsonrpc": "2.0", "method": "cgt_getChainInfo", "id": 1 }))
+        .send()
+        .await;
+    assert!(plain.map_or(true, |resp| !resp.status().is_success()));
+
+    server.abort();
+}
+
+#[test]
+fn unreadable_or_mismatched_files_are_refused() {
+    let err = load_tls(&fixture("missing.pem"), &fixture("key.pem")).unwrap_err();
+    assert!(err.to_string().contains("cannot read TLS certificate"));
+
+    let err = load_tls(&fixture("cert.pem"), &fixture("other_key.pem")).unwrap_err();
+    assert!(err.to_string().contains("does not fit certificate"));
+
+    // A key file given as the certificate has no certificates in it
+    let err = load_tls(&fixture("key.pem"), &fixture("key.pem")).unwrap_err();
+    assert!(err.to_string().contains("no certificates"));
+}